@@ -0,0 +1,31 @@
+//! Compares resolving every built-in harness binary with one
+//! `find_binaries` scan against a `find_binary` lookup per name.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use harness_locate::HarnessKind;
+use harness_locate::detection::{find_binaries, find_binary};
+
+fn bench_detection(c: &mut Criterion) {
+    let names: Vec<&str> = HarnessKind::ALL
+        .iter()
+        .flat_map(|kind| kind.binary_names().iter().copied())
+        .collect();
+    let mut group = c.benchmark_group("binary_lookup");
+
+    group.bench_function("find_binary_each", |b| {
+        b.iter(|| {
+            for name in &names {
+                black_box(find_binary(black_box(name)).ok());
+            }
+        });
+    });
+
+    group.bench_function("find_binaries", |b| {
+        b.iter(|| black_box(find_binaries(black_box(&names))));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_detection);
+criterion_main!(benches);
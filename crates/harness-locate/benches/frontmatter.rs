@@ -0,0 +1,49 @@
+//! Compares full YAML frontmatter parsing with the `scan_skill_header`
+//! fast path over a batch of typical SKILL.md files.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use harness_locate::skill::{parse_frontmatter, scan_skill_header};
+
+/// Number of skill files parsed per iteration, roughly a large monorepo.
+const SKILLS: usize = 1_000;
+
+fn skill_files() -> Vec<String> {
+    (0..SKILLS)
+        .map(|i| {
+            format!(
+                "---\nname: skill-{i}\ndescription: \"Handles task {i} for the team\"\n\
+                 license: MIT\nallowed-tools:\n  - Read\n  - Bash(git:*)\nmetadata:\n  \
+                 version: 1.{i}\n  tags: [docs, automation]\n---\n# Skill {i}\n\n\
+                 Follow these steps carefully.\n"
+            )
+        })
+        .collect()
+}
+
+fn bench_frontmatter(c: &mut Criterion) {
+    let files = skill_files();
+    let mut group = c.benchmark_group("skill_header");
+
+    group.bench_function("parse_frontmatter", |b| {
+        b.iter(|| {
+            for content in &files {
+                let yaml = parse_frontmatter(black_box(content)).unwrap().yaml.unwrap();
+                black_box((yaml.get("name"), yaml.get("description")));
+            }
+        });
+    });
+
+    group.bench_function("scan_skill_header", |b| {
+        b.iter(|| {
+            for content in &files {
+                let header = scan_skill_header(black_box(content)).unwrap();
+                black_box((header.name, header.description));
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_frontmatter);
+criterion_main!(benches);
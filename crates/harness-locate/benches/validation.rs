@@ -0,0 +1,62 @@
+//! Compares owned skill validation with the borrowed
+//! `validate_skill_borrowed` path over a batch of typical SKILL.md files.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use harness_locate::types::HarnessKind;
+use harness_locate::validation::{validate_skill_borrowed, validate_skill_for_harness};
+
+/// Number of skill files validated per iteration.
+const SKILLS: usize = 1_000;
+
+fn skill_files() -> Vec<(String, String)> {
+    (0..SKILLS)
+        .map(|i| {
+            let name = if i % 10 == 0 {
+                format!("Skill {i}")
+            } else {
+                format!("skill-{i}")
+            };
+            let content = format!(
+                "---\nname: {name}\ndescription: \"Handles task {i} for the team\"\n\
+                 license: MIT\nallowed-tools:\n  - Read\n  - Bash(git:*)\nmetadata:\n  \
+                 version: 1.{i}\n  tags: [docs, automation]\n---\n# Skill {i}\n\n\
+                 Follow these steps carefully.\n"
+            );
+            (content, format!("skill-{i}"))
+        })
+        .collect()
+}
+
+fn bench_validation(c: &mut Criterion) {
+    let files = skill_files();
+    let mut group = c.benchmark_group("validate_skill");
+
+    group.bench_function("validate_skill_for_harness", |b| {
+        b.iter(|| {
+            for (content, dir) in &files {
+                black_box(validate_skill_for_harness(
+                    black_box(content),
+                    dir,
+                    HarnessKind::OpenCode,
+                ));
+            }
+        });
+    });
+
+    group.bench_function("validate_skill_borrowed", |b| {
+        b.iter(|| {
+            for (content, dir) in &files {
+                black_box(validate_skill_borrowed(
+                    black_box(content),
+                    dir,
+                    HarnessKind::OpenCode,
+                ));
+            }
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_validation);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+//! Parses arbitrary JSON as an MCP server for every harness and checks that
+//! anything it accepts survives a write and a second read unchanged.
+//!
+//! Run with `cargo +nightly fuzz run mcp_round_trip` from `crates/harness-locate`.
+
+#![no_main]
+
+use harness_locate::{Harness, HarnessKind};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(data) else {
+        return;
+    };
+    for &kind in HarnessKind::ALL {
+        let harness = Harness::new(kind);
+        let Ok(server) = harness.parse_mcp_server_config("fuzz", &value) else {
+            continue;
+        };
+        let Ok(native) = harness.mcp_to_native("fuzz", &server) else {
+            continue;
+        };
+        // The first read may normalize; after that the format is a fixed point.
+        let reparsed = harness
+            .parse_mcp_server_config("fuzz", &native)
+            .expect("native output must parse");
+        let rewritten = harness
+            .mcp_to_native("fuzz", &reparsed)
+            .expect("parsed output must convert");
+        assert_eq!(native, rewritten, "{kind} output is not stable");
+    }
+});
@@ -0,0 +1,320 @@
+//! Splitting and joining stdio command lines.
+//!
+//! Harnesses start stdio MCP servers without a shell: Claude Code, Amp,
+//! Copilot CLI and Goose store a program and an argument list, OpenCode a
+//! single array. A command line pasted whole into `command`, an argument
+//! such as `--port 8080`, or a pipe in an argument is therefore passed
+//! literally rather than split or interpreted.
+//!
+//! [`split`] and [`join`] convert between command lines and argument
+//! vectors with POSIX shell quoting rules, and [`normalize_command`] moves
+//! arguments out of a `command` string, leaving it alone when splitting
+//! would change what runs.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::argv::{join, normalize_command, split};
+//!
+//! let argv = split(r#"node "/opt/my server/index.js" --verbose"#).unwrap();
+//! assert_eq!(argv, ["node", "/opt/my server/index.js", "--verbose"]);
+//! assert_eq!(join(&argv), "node '/opt/my server/index.js' --verbose");
+//!
+//! let normalized = normalize_command("npx -y @acme/mcp", &["--stdio".to_string()]);
+//! assert_eq!(normalized.command, "npx");
+//! assert_eq!(normalized.args, ["-y", "@acme/mcp", "--stdio"]);
+//! assert!(normalized.unchanged_reason.is_none());
+//! ```
+
+use std::fmt;
+use std::path::Path;
+
+/// Why [`split`] could not parse a command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitError {
+    /// A quote was opened but never closed.
+    UnterminatedQuote(char),
+    /// The line ends with an escaping backslash.
+    TrailingBackslash,
+}
+
+impl fmt::Display for SplitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedQuote(quote) => write!(f, "unterminated {quote} quote"),
+            Self::TrailingBackslash => f.write_str("trailing backslash"),
+        }
+    }
+}
+
+impl std::error::Error for SplitError {}
+
+/// Splits a command line into arguments like a POSIX shell, without
+/// expanding variables or globs.
+///
+/// Single quotes keep everything literally, double quotes allow `\"`,
+/// `\\`, `\$` and `` \` `` escapes, and a backslash outside quotes
+/// escapes the next character.
+///
+/// # Errors
+///
+/// Returns an error if a quote is not closed or the line ends with a
+/// backslash.
+pub fn split(line: &str) -> Result<Vec<String>, SplitError> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => words.extend(word.take()),
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(SplitError::UnterminatedQuote('\'')),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some('\n') => {}
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(SplitError::UnterminatedQuote('"')),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(SplitError::UnterminatedQuote('"')),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+                None => return Err(SplitError::TrailingBackslash),
+            },
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// Joins arguments into a command line that [`split`] turns back into
+/// the same arguments, quoting only those that need it.
+#[must_use]
+pub fn join<S: AsRef<str>>(argv: &[S]) -> String {
+    argv.iter()
+        .map(|arg| quote(arg.as_ref()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns `true` if `arg` must be quoted to survive a round trip through
+/// a shell command line.
+#[must_use]
+pub fn needs_quoting(arg: &str) -> bool {
+    arg.is_empty()
+        || arg.chars().any(|c| {
+            c.is_whitespace()
+                || matches!(
+                    c,
+                    '\'' | '"'
+                        | '\\'
+                        | '$'
+                        | '`'
+                        | '|'
+                        | '&'
+                        | ';'
+                        | '<'
+                        | '>'
+                        | '('
+                        | ')'
+                        | '*'
+                        | '?'
+                        | '['
+                        | ']'
+                        | '{'
+                        | '}'
+                        | '!'
+                )
+        })
+        || arg.starts_with(['~', '#'])
+}
+
+/// Returns `arg` single-quoted if it [needs quoting](needs_quoting).
+#[must_use]
+pub fn quote(arg: &str) -> String {
+    if needs_quoting(arg) {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    } else {
+        arg.to_string()
+    }
+}
+
+/// Returns `true` if `arg` uses syntax only a shell interprets: pipes,
+/// redirections, command lists or command substitution.
+///
+/// `${VAR}` references are not counted, since several harnesses expand
+/// them themselves.
+#[must_use]
+pub fn has_shell_syntax(arg: &str) -> bool {
+    ["|", "&&", ";", ">", "<", "$(", "`"]
+        .iter()
+        .any(|op| arg.contains(op))
+        || arg.trim_end().ends_with('&')
+}
+
+/// Returns `true` if `command` runs a shell, whose arguments may use
+/// shell syntax.
+#[must_use]
+pub fn is_shell(command: &str) -> bool {
+    let program = command
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or(command)
+        .to_ascii_lowercase();
+    let program = program.strip_suffix(".exe").unwrap_or(&program);
+    matches!(
+        program,
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "fish" | "cmd" | "powershell" | "pwsh"
+    )
+}
+
+/// Returns `true` if `command` is a path that may contain spaces, such as
+/// `C:\Program Files\nodejs\node.exe` or an existing file.
+#[must_use]
+pub fn is_path_with_spaces(command: &str) -> bool {
+    let bytes = command.as_bytes();
+    let windows_absolute = bytes.len() > 2
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/');
+    windows_absolute
+        || command.starts_with(r"\\")
+        || crate::fs::current().exists(Path::new(command))
+}
+
+/// The result of [`normalize_command`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedCommand {
+    /// The program to run.
+    pub command: String,
+    /// Its arguments.
+    pub args: Vec<String>,
+    /// Why a `command` containing spaces was left as is, if it was.
+    pub unchanged_reason: Option<String>,
+}
+
+/// Moves arguments embedded in `command` to the front of `args`.
+///
+/// `command` is kept whole, with the reason in
+/// [`unchanged_reason`](NormalizedCommand::unchanged_reason), when it is
+/// a path with spaces, cannot be parsed, or uses shell syntax that
+/// splitting would turn into literal arguments.
+#[must_use]
+pub fn normalize_command(command: &str, args: &[String]) -> NormalizedCommand {
+    let unchanged = |reason: Option<String>| NormalizedCommand {
+        command: command.to_string(),
+        args: args.to_vec(),
+        unchanged_reason: reason,
+    };
+    if !command
+        .trim()
+        .chars()
+        .any(|c| c.is_whitespace() || c == '"' || c == '\'')
+    {
+        return unchanged(None);
+    }
+    if is_path_with_spaces(command) {
+        return unchanged(Some(format!("{command} is a path")));
+    }
+    let words = match split(command) {
+        Ok(words) => words,
+        Err(e) => return unchanged(Some(format!("cannot split {command}: {e}"))),
+    };
+    if words.iter().any(|word| has_shell_syntax(word)) {
+        return unchanged(Some(format!(
+            "{command} uses shell syntax; run it with `sh -c` instead"
+        )));
+    }
+    let mut words = words.into_iter();
+    let Some(program) = words.next() else {
+        return unchanged(None);
+    };
+    NormalizedCommand {
+        command: program,
+        args: words.chain(args.iter().cloned()).collect(),
+        unchanged_reason: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_quotes_and_escapes() {
+        assert_eq!(
+            split(r#"  a 'b c' "d \"e\" \$f" g\ h ''  "#).unwrap(),
+            ["a", "b c", r#"d "e" $f"#, "g h", ""]
+        );
+        assert_eq!(
+            split(r#"C:\\tools\\x.exe "C:\data""#).unwrap()[1],
+            r"C:\data"
+        );
+        assert_eq!(split("'open"), Err(SplitError::UnterminatedQuote('\'')));
+        assert_eq!(split("end\\"), Err(SplitError::TrailingBackslash));
+    }
+
+    #[test]
+    fn join_round_trips_through_split() {
+        let argv = [
+            "node",
+            "/opt/my server/index.js",
+            "it's",
+            "",
+            "a|b",
+            r"C:\Program Files",
+            "--flag=$HOME",
+        ];
+        let line = join(&argv);
+        assert_eq!(split(&line).unwrap(), argv);
+        assert_eq!(join(&["npx", "-y", "@scope/pkg"]), "npx -y @scope/pkg");
+    }
+
+    #[test]
+    fn normalize_keeps_paths_and_shell_lines_whole() {
+        let args = vec!["--stdio".to_string()];
+        let split_line = normalize_command("uvx 'mcp server' --x", &args);
+        assert_eq!(split_line.command, "uvx");
+        assert_eq!(split_line.args, ["mcp server", "--x", "--stdio"]);
+
+        let path = normalize_command(r"C:\Program Files\nodejs\node.exe", &args);
+        assert_eq!(path.command, r"C:\Program Files\nodejs\node.exe");
+        assert!(path.unchanged_reason.is_some());
+
+        let piped = normalize_command("cat log | mcp-server", &args);
+        assert_eq!(piped.command, "cat log | mcp-server");
+        assert!(piped.unchanged_reason.unwrap().contains("sh -c"));
+
+        assert_eq!(normalize_command("node", &args).unchanged_reason, None);
+    }
+
+    #[test]
+    fn recognizes_shells() {
+        assert!(is_shell("/bin/bash"));
+        assert!(is_shell(r"C:\Windows\System32\cmd.exe"));
+        assert!(!is_shell("node"));
+        assert!(has_shell_syntax("a && b"));
+        assert!(!has_shell_syntax("${TOKEN}"));
+    }
+}
@@ -0,0 +1,465 @@
+//! Finding stale harness files to delete.
+//!
+//! Harnesses never delete session transcripts, and caches and plugin
+//! clones pile up over months. [`cleanup_plan`] (also
+//! [`Harness::cleanup_plan`]) lists what can go, with sizes, and
+//! [`CleanupPlan::apply`] deletes it:
+//!
+//! - session files not modified within [`CleanupOptions::older_than`],
+//!   under the [data directory](Harness::data)
+//! - Claude Code marketplace clones no longer registered in
+//!   `plugins/known_marketplaces.json`
+//! - entries of the [cache directory](Harness::cache) not modified within
+//!   [`CleanupOptions::older_than`]
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use harness_locate::cleanup::CleanupOptions;
+//! use harness_locate::{Harness, HarnessKind};
+//!
+//! let harness = Harness::new(HarnessKind::ClaudeCode);
+//! let options = CleanupOptions::default().with_older_than(Duration::from_secs(90 * 86_400));
+//! let plan = harness.cleanup_plan(&options)?;
+//! println!("{} bytes in {} files", plan.total_size(), plan.items.len());
+//! plan.apply()?;
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::error::Result;
+use crate::harness::Harness;
+use crate::types::{HarnessKind, Scope};
+
+/// Default age after which files are stale.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// What [`cleanup_plan`] looks for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CleanupOptions {
+    /// Age after which sessions and cache entries are stale.
+    pub older_than: Duration,
+    /// Whether to include stale session files.
+    pub sessions: bool,
+    /// Whether to include orphaned plugin clones.
+    pub plugins: bool,
+    /// Whether to include stale cache entries.
+    pub caches: bool,
+}
+
+impl Default for CleanupOptions {
+    fn default() -> Self {
+        Self {
+            older_than: DEFAULT_MAX_AGE,
+            sessions: true,
+            plugins: true,
+            caches: true,
+        }
+    }
+}
+
+impl CleanupOptions {
+    /// Sets the age after which files are stale.
+    #[must_use]
+    pub fn with_older_than(mut self, older_than: Duration) -> Self {
+        self.older_than = older_than;
+        self
+    }
+
+    /// Sets whether session files are included.
+    #[must_use]
+    pub fn with_sessions(mut self, sessions: bool) -> Self {
+        self.sessions = sessions;
+        self
+    }
+
+    /// Sets whether orphaned plugin clones are included.
+    #[must_use]
+    pub fn with_plugins(mut self, plugins: bool) -> Self {
+        self.plugins = plugins;
+        self
+    }
+
+    /// Sets whether cache entries are included.
+    #[must_use]
+    pub fn with_caches(mut self, caches: bool) -> Self {
+        self.caches = caches;
+        self
+    }
+}
+
+/// Why an item is in a [`CleanupPlan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CleanupCategory {
+    /// A session transcript or other per-session state.
+    Session,
+    /// A plugin or marketplace clone nothing refers to.
+    OrphanedPlugin,
+    /// A cache entry.
+    Cache,
+}
+
+/// A file or directory to delete.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleanupItem {
+    /// The file or directory.
+    pub path: PathBuf,
+    /// Why it can be deleted.
+    pub category: CleanupCategory,
+    /// Its size in bytes, including everything in a directory.
+    pub size: u64,
+    /// When it or anything in it was last modified, if known.
+    pub modified: Option<SystemTime>,
+}
+
+/// Stale files of one harness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CleanupPlan {
+    /// Name of the harness.
+    pub harness: String,
+    /// Files and directories to delete.
+    pub items: Vec<CleanupItem>,
+}
+
+impl CleanupPlan {
+    /// Returns `true` if there is nothing to delete.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the bytes the plan frees.
+    #[must_use]
+    pub fn total_size(&self) -> u64 {
+        self.items.iter().map(|item| item.size).sum()
+    }
+
+    /// Returns the bytes the items of `category` take up.
+    #[must_use]
+    pub fn size_of(&self, category: CleanupCategory) -> u64 {
+        self.items
+            .iter()
+            .filter(|item| item.category == category)
+            .map(|item| item.size)
+            .sum()
+    }
+
+    /// Deletes every item and returns the bytes actually freed.
+    ///
+    /// Items deleted or modified since the plan was made are skipped. Each
+    /// deletion is recorded in the [journal](crate::journal) as
+    /// `cleanup.remove`; its hash covers the item's size and modification
+    /// time rather than its content, so nothing is read before deleting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an item cannot be deleted. Items deleted
+    /// before a failure stay deleted.
+    pub fn apply(&self) -> Result<u64> {
+        let fs = crate::fs::current();
+        let mut freed = 0;
+        for item in &self.items {
+            if !fs.exists(&item.path) {
+                continue;
+            }
+            let (size, modified) = measure(&item.path)?;
+            if modified != item.modified {
+                continue;
+            }
+            fs.remove(&item.path)?;
+            crate::journal::record(
+                "cleanup.remove",
+                &item.path,
+                &fingerprint(size, modified),
+                "",
+            );
+            freed += size;
+        }
+        Ok(freed)
+    }
+}
+
+/// Lists the stale sessions, orphaned plugins and old cache entries of
+/// `harness`.
+///
+/// Nothing is deleted; see [`CleanupPlan::apply`]. Entries whose
+/// modification time is unknown are never stale.
+///
+/// # Errors
+///
+/// Returns an error if a directory cannot be resolved or read, or the
+/// filesystem cannot report modification times.
+pub fn cleanup_plan(harness: &Harness, options: &CleanupOptions) -> Result<CleanupPlan> {
+    let cutoff = SystemTime::now()
+        .checked_sub(options.older_than)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    let is_stale = |modified: Option<SystemTime>| modified.is_some_and(|m| m < cutoff);
+    let fs = crate::fs::current();
+    let mut items = Vec::new();
+
+    if options.sessions
+        && let Some(data) = harness.data(&Scope::Global)?
+    {
+        let mut files = Vec::new();
+        for dir in session_dirs(harness.kind()) {
+            collect_files(&data.join(dir), &mut files)?;
+        }
+        for path in files {
+            let metadata = fs.metadata(&path)?;
+            if is_stale(metadata.modified) {
+                items.push(CleanupItem {
+                    path,
+                    category: CleanupCategory::Session,
+                    size: metadata.len,
+                    modified: metadata.modified,
+                });
+            }
+        }
+    }
+
+    if options.plugins && harness.kind() == Some(HarnessKind::ClaudeCode) {
+        let plugins = harness.config(&Scope::Global)?.join("plugins");
+        for path in orphaned_marketplaces(&plugins)? {
+            let (size, modified) = measure(&path)?;
+            items.push(CleanupItem {
+                path,
+                category: CleanupCategory::OrphanedPlugin,
+                size,
+                modified,
+            });
+        }
+    }
+
+    if options.caches
+        && let Some(cache) = harness.cache()?
+        && fs.is_dir(&cache)
+    {
+        for path in fs.read_dir(&cache)? {
+            let (size, modified) = measure(&path)?;
+            if is_stale(modified) {
+                items.push(CleanupItem {
+                    path,
+                    category: CleanupCategory::Cache,
+                    size,
+                    modified,
+                });
+            }
+        }
+    }
+
+    Ok(CleanupPlan {
+        harness: harness.name().to_string(),
+        items,
+    })
+}
+
+/// Directories under the data directory holding per-session files.
+pub(crate) fn session_dirs(kind: Option<HarnessKind>) -> &'static [&'static str] {
+    match kind {
+        Some(HarnessKind::ClaudeCode) => &["projects", "todos", "shell-snapshots"],
+        Some(HarnessKind::OpenCode) => &["storage"],
+        Some(HarnessKind::Goose) => &["sessions"],
+        Some(HarnessKind::CopilotCli) => &["session-state", "history-session-state"],
+        Some(HarnessKind::AmpCode) | None => &[],
+    }
+}
+
+/// Appends every file under `dir` to `files`.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let fs = crate::fs::current();
+    if !fs.is_dir(dir) {
+        return Ok(());
+    }
+    for entry in fs.read_dir(dir)? {
+        if fs.is_dir(&entry) {
+            collect_files(&entry, files)?;
+        } else {
+            files.push(entry);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the total size of `path` and the latest modification time of
+/// anything in it.
+fn measure(path: &Path) -> Result<(u64, Option<SystemTime>)> {
+    let (usage, modified) = crate::usage::measure(path, &[])?;
+    Ok((usage.bytes, modified))
+}
+
+/// Describes a deleted item for its journal entry.
+fn fingerprint(size: u64, modified: Option<SystemTime>) -> String {
+    let modified = modified
+        .and_then(|m| m.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos());
+    format!("{size} bytes, modified {modified}")
+}
+
+/// Returns the marketplace clones under `plugins/marketplaces/` that
+/// `plugins/known_marketplaces.json` does not list.
+///
+/// Without the registry nothing is reported, since every clone would look
+/// orphaned.
+fn orphaned_marketplaces(plugins: &Path) -> Result<Vec<PathBuf>> {
+    let fs = crate::fs::current();
+    let registry = plugins.join("known_marketplaces.json");
+    let clones = plugins.join("marketplaces");
+    if !fs.exists(&registry) || !fs.is_dir(&clones) {
+        return Ok(Vec::new());
+    }
+    let known: serde_json::Value = serde_json::from_str(&crate::fs::read_text(&registry)?.text)?;
+    let known = known.as_object().cloned().unwrap_or_default();
+    let locations: Vec<&str> = known
+        .values()
+        .filter_map(|entry| entry.get("installLocation")?.as_str())
+        .collect();
+
+    let mut orphans = Vec::new();
+    for path in fs.read_dir(&clones)? {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let registered = known.contains_key(&name)
+            || locations.iter().any(|location| {
+                crate::paths::normalize_for_compare(location)
+                    == crate::paths::normalize_for_compare(&path)
+            });
+        if fs.is_dir(&path) && !registered {
+            orphans.push(path);
+        }
+    }
+    Ok(orphans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    const DAY: Duration = Duration::from_secs(86_400);
+
+    #[test]
+    fn plans_stale_sessions_orphans_and_caches() {
+        let old = SystemTime::now() - 60 * DAY;
+        let claude = Path::new("/fake/home/.claude");
+        let cache = PathContext::fake("/fake")
+            .scope(crate::harness::claude_code::cache_dir)
+            .unwrap();
+        let fs = MemoryFs::new()
+            .with_file(claude.join("projects/-repo/old.jsonl"), "0123456789")
+            .with_modified(claude.join("projects/-repo/old.jsonl"), old)
+            .with_file(claude.join("projects/-repo/new.jsonl"), "x")
+            .with_file(
+                claude.join("plugins/known_marketplaces.json"),
+                r#"{"official": {"installLocation": "/elsewhere"}}"#,
+            )
+            .with_file(claude.join("plugins/marketplaces/official/a"), "kept")
+            .with_file(claude.join("plugins/marketplaces/gone/a"), "12345")
+            .with_file(cache.join("stale/log.txt"), "123")
+            .with_modified(cache.join("stale/log.txt"), old)
+            .with_modified(cache.join("stale"), old)
+            .with_file(cache.join("fresh/log.txt"), "1");
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+
+        let plan = ctx
+            .scope(|| cleanup_plan(&harness, &CleanupOptions::default()))
+            .unwrap();
+        let found: Vec<_> = plan
+            .items
+            .iter()
+            .map(|item| (item.category, item.path.clone(), item.size))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    CleanupCategory::Session,
+                    claude.join("projects/-repo/old.jsonl"),
+                    10
+                ),
+                (
+                    CleanupCategory::OrphanedPlugin,
+                    claude.join("plugins/marketplaces/gone"),
+                    5
+                ),
+                (CleanupCategory::Cache, cache.join("stale"), 3),
+            ]
+        );
+        assert_eq!(plan.size_of(CleanupCategory::Session), 10);
+
+        assert_eq!(ctx.scope(|| plan.apply()).unwrap(), 18);
+        let journal = ctx.scope(crate::journal::read_journal).unwrap();
+        let removed: Vec<_> = journal
+            .iter()
+            .map(|e| (e.operation.as_str(), e.file.clone()))
+            .collect();
+        assert_eq!(
+            removed,
+            [
+                ("cleanup.remove", claude.join("projects/-repo/old.jsonl")),
+                ("cleanup.remove", claude.join("plugins/marketplaces/gone")),
+                ("cleanup.remove", cache.join("stale")),
+            ]
+        );
+        assert_eq!(
+            journal[0].diff_hash,
+            crate::journal::diff_hash(&fingerprint(10, Some(old)), "")
+        );
+        let plan = ctx
+            .scope(|| cleanup_plan(&harness, &CleanupOptions::default()))
+            .unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn apply_skips_items_modified_since_planning() {
+        let old = SystemTime::now() - 2 * DAY;
+        let sessions = Path::new("/fake/home/.local/share/goose/sessions");
+        let fs = MemoryFs::new()
+            .with_file(sessions.join("a.jsonl"), "ab")
+            .with_modified(sessions.join("a.jsonl"), old)
+            .with_file(sessions.join("b.jsonl"), "cd")
+            .with_modified(sessions.join("b.jsonl"), old);
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let harness = Harness::new(HarnessKind::Goose);
+        let options = CleanupOptions::default().with_older_than(DAY);
+
+        let plan = ctx.scope(|| cleanup_plan(&harness, &options)).unwrap();
+        assert_eq!(plan.total_size(), 4);
+        ctx.scope(|| crate::fs::current().write(&sessions.join("b.jsonl"), b"resumed"))
+            .unwrap();
+
+        assert_eq!(ctx.scope(|| plan.apply()).unwrap(), 2);
+        ctx.scope(|| {
+            let fs = crate::fs::current();
+            assert!(!fs.exists(&sessions.join("a.jsonl")));
+            assert!(fs.exists(&sessions.join("b.jsonl")));
+        });
+    }
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn options_select_categories() {
+        let old = SystemTime::now() - 2 * DAY;
+        let fs = MemoryFs::new()
+            .with_file("/fake/home/.local/share/goose/sessions/a.jsonl", "ab")
+            .with_modified("/fake/home/.local/share/goose/sessions/a.jsonl", old);
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let harness = Harness::new(HarnessKind::Goose);
+        let plan =
+            |options: CleanupOptions| ctx.scope(|| cleanup_plan(&harness, &options)).unwrap();
+
+        assert!(plan(CleanupOptions::default()).is_empty());
+        let day = CleanupOptions::default().with_older_than(DAY);
+        assert_eq!(plan(day.clone()).total_size(), 2);
+        assert!(plan(day.with_sessions(false)).is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+//! Command file parsing utilities.
+//!
+//! Slash command bodies receive user input through placeholders:
+//! `$ARGUMENTS` expands to the full argument string and `$1`, `$2`, …
+//! to individual positional arguments. Claude Code additionally reads an
+//! `argument-hint` frontmatter field shown during autocompletion.
+
+use std::collections::BTreeSet;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+use crate::skill::parse_frontmatter;
+
+static ARGUMENTS_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$ARGUMENTS\b").unwrap());
+
+static POSITIONAL_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$([1-9][0-9]*)\b").unwrap());
+
+static HINT_ARG_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[([^\]]+)\]|<([^>]+)>").unwrap());
+
+/// The arguments a command accepts, derived from its placeholders.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandSignature {
+    /// Whether the body uses `$ARGUMENTS`.
+    pub uses_arguments: bool,
+    /// Positional placeholder numbers used in the body, sorted and deduplicated.
+    pub positional: Vec<usize>,
+    /// The raw `argument-hint` frontmatter value, if present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub argument_hint: Option<String>,
+    /// Argument names from `[name]` or `<name>` groups in the hint.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hint_arguments: Vec<String>,
+}
+
+impl CommandSignature {
+    /// Returns the number of positional arguments the body reads, i.e.
+    /// the highest `$n` placeholder.
+    #[must_use]
+    pub fn arity(&self) -> usize {
+        self.positional.last().copied().unwrap_or(0)
+    }
+
+    /// Returns `true` if the body uses any argument placeholder.
+    #[must_use]
+    pub fn takes_arguments(&self) -> bool {
+        self.uses_arguments || !self.positional.is_empty()
+    }
+
+    /// Returns positional numbers below [`arity`](Self::arity) that the
+    /// body never reads, e.g. `2` when only `$1` and `$3` are used.
+    #[must_use]
+    pub fn missing_positionals(&self) -> Vec<usize> {
+        (1..self.arity())
+            .filter(|n| self.positional.binary_search(n).is_err())
+            .collect()
+    }
+}
+
+/// Parses the argument placeholders and `argument-hint` of a command file.
+///
+/// Only the body after the frontmatter is scanned for placeholders. Hints
+/// with more than one group must be quoted to be valid YAML.
+///
+/// # Errors
+///
+/// Returns `Error::YamlParse` if frontmatter exists but contains invalid YAML.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::command::parse_command_signature;
+///
+/// let content = "---\nargument-hint: \"[pr-number] [priority]\"\n---\nReview PR #$1 with priority $2.";
+/// let signature = parse_command_signature(content).unwrap();
+///
+/// assert_eq!(signature.positional, vec![1, 2]);
+/// assert_eq!(signature.hint_arguments, vec!["pr-number", "priority"]);
+/// ```
+pub fn parse_command_signature(content: &str) -> Result<CommandSignature> {
+    let frontmatter = parse_frontmatter(content)?;
+
+    let argument_hint = frontmatter
+        .yaml
+        .as_ref()
+        .and_then(|yaml| yaml.get("argument-hint"))
+        .and_then(hint_string);
+    let hint_arguments = argument_hint
+        .as_deref()
+        .map(|hint| {
+            HINT_ARG_RE
+                .captures_iter(hint)
+                .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+                .map(|m| m.as_str().trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let positional: BTreeSet<usize> = POSITIONAL_RE
+        .captures_iter(frontmatter.body)
+        .filter_map(|c| c[1].parse().ok())
+        .collect();
+
+    Ok(CommandSignature {
+        uses_arguments: ARGUMENTS_RE.is_match(frontmatter.body),
+        positional: positional.into_iter().collect(),
+        argument_hint,
+        hint_arguments,
+    })
+}
+
+/// Reads `argument-hint`, which YAML parses as a sequence when a single
+/// group is written unquoted, e.g. `argument-hint: [file]`.
+fn hint_string(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Sequence(items) => Some(
+            items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .map(|item| format!("[{item}]"))
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_arguments_placeholder() {
+        let signature = parse_command_signature("Fix issue $ARGUMENTS now.").unwrap();
+        assert!(signature.uses_arguments);
+        assert!(signature.positional.is_empty());
+        assert!(signature.takes_arguments());
+    }
+
+    #[test]
+    fn parses_positional_placeholders() {
+        let signature =
+            parse_command_signature("Use $3 then $1, and $1 again; $0 is ignored.").unwrap();
+        assert_eq!(signature.positional, vec![1, 3]);
+        assert_eq!(signature.arity(), 3);
+        assert_eq!(signature.missing_positionals(), vec![2]);
+    }
+
+    #[test]
+    fn ignores_placeholders_in_frontmatter() {
+        let content = "---\ndescription: Costs $1 per run\n---\nNo placeholders.";
+        let signature = parse_command_signature(content).unwrap();
+        assert!(!signature.takes_arguments());
+    }
+
+    #[test]
+    fn parses_hint_with_angle_brackets() {
+        let content = "---\nargument-hint: \"<file> <message>\"\n---\n$1 $2";
+        let signature = parse_command_signature(content).unwrap();
+        assert_eq!(signature.hint_arguments, vec!["file", "message"]);
+    }
+
+    #[test]
+    fn parses_unquoted_single_bracket_hint() {
+        let content = "---\nargument-hint: [file]\n---\n$1";
+        let signature = parse_command_signature(content).unwrap();
+        assert_eq!(signature.argument_hint.as_deref(), Some("[file]"));
+        assert_eq!(signature.hint_arguments, vec!["file"]);
+    }
+
+    #[test]
+    fn no_arguments_without_placeholders() {
+        let signature = parse_command_signature("# Plain command").unwrap();
+        assert_eq!(signature, CommandSignature::default());
+        assert_eq!(signature.arity(), 0);
+    }
+}
@@ -1,17 +1,30 @@
 //! Binary detection utilities.
 //!
 //! This module provides cross-platform binary detection using the `which` crate.
+//!
+//! On Windows, npm-installed harnesses ship as `.cmd` and `.ps1` shims rather
+//! than native executables. `PATHEXT` normally covers `.cmd`, but `.ps1` is not
+//! part of the default list, so those shims are probed explicitly.
 
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 
 use crate::error::{Error, Result};
+use crate::platform::strip_verbatim_prefix;
+use crate::types::InstallSource;
 
 /// Finds a binary executable in PATH.
 ///
 /// Returns `Ok(Some(path))` if found, `Ok(None)` if not found,
 /// or `Err` for system errors (e.g., canonicalization failures).
 ///
-/// Cross-platform: handles Windows extensions (.exe, .cmd, etc.) automatically.
+/// Cross-platform: handles Windows extensions (.exe, .cmd, etc.) automatically,
+/// falls back to npm `.ps1` shims, and strips the `\\?\` verbatim prefix from
+/// the returned path.
+///
+/// When a [`PathContext`](crate::platform::PathContext) with an environment
+/// snapshot is active, `PATH` is read from that snapshot instead.
 ///
 /// # Arguments
 ///
@@ -33,13 +46,235 @@ use crate::error::{Error, Result};
 /// }
 /// ```
 pub fn find_binary(name: &str) -> Result<Option<PathBuf>> {
-    match which::which(name) {
-        Ok(path) => Ok(Some(path)),
-        Err(which::Error::CannotFindBinaryPath) => Ok(None),
+    match which(name) {
+        Ok(path) => Ok(Some(strip_verbatim_prefix(&path))),
+        Err(which::Error::CannotFindBinaryPath) => find_windows_shim(name),
         Err(e) => Err(Error::BinaryDetection(e.to_string())),
     }
 }
 
+/// Finds several binaries with a single scan of `PATH`.
+///
+/// `PATH` is read and split once per call into an index of the requested
+/// names, and every name is answered from that index. Each directory is
+/// probed only for the names not found yet, and the scan stops once every
+/// name is found; directories are never listed. Results match [`find_binary`]: the first directory in `PATH` wins, and
+/// on Windows `PATHEXT` extensions and npm `.ps1` shims are honored. Names
+/// containing a path separator are resolved with [`find_binary`] instead.
+///
+/// Missing directories are skipped, and system errors from the fallback
+/// resolve to `None`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use harness_locate::detection::find_binaries;
+///
+/// let found = find_binaries(&["claude", "goose"]);
+/// if let Some(Some(path)) = found.get("claude") {
+///     println!("claude at {}", path.display());
+/// }
+/// ```
+#[must_use]
+pub fn find_binaries<'a>(names: &[&'a str]) -> HashMap<&'a str, Option<PathBuf>> {
+    let (paths, bare): (Vec<&str>, Vec<&str>) =
+        names.iter().partition(|name| name.contains(['/', '\\']));
+    let wanted: HashSet<OsString> = bare
+        .iter()
+        .flat_map(|name| candidate_names(name).into_iter().chain(shim_names(name)))
+        .map(|name| index_key(name.as_ref()))
+        .collect();
+    let index = PathIndex::scan(path_var(), &wanted);
+    bare.into_iter()
+        .map(|name| (name, index.resolve(name)))
+        .chain(
+            paths
+                .into_iter()
+                .map(|name| (name, find_binary(name).ok().flatten())),
+        )
+        .collect()
+}
+
+/// Returns `PATH`, honoring the active environment snapshot.
+fn path_var() -> Option<OsString> {
+    if crate::platform::has_env_snapshot() {
+        crate::platform::var("PATH").map(OsString::from)
+    } else {
+        std::env::var_os("PATH")
+    }
+}
+
+/// Executables found on `PATH`, keyed by file name.
+///
+/// Each entry records the position of its directory in `PATH` so that
+/// candidates with different extensions can be ranked like `which` does.
+#[derive(Debug, Default)]
+struct PathIndex {
+    entries: HashMap<OsString, (usize, PathBuf)>,
+}
+
+impl PathIndex {
+    /// Indexes the first executable on `paths` for each file name in
+    /// `wanted`, probing only those names in each directory.
+    fn scan(paths: Option<OsString>, wanted: &HashSet<OsString>) -> Self {
+        let mut index = Self::default();
+        let Some(paths) = paths else {
+            return index;
+        };
+        for (position, dir) in std::env::split_paths(&paths).enumerate() {
+            if index.entries.len() == wanted.len() {
+                break;
+            }
+            if dir.as_os_str().is_empty() {
+                continue;
+            }
+            for name in wanted {
+                if index.entries.contains_key(name) {
+                    continue;
+                }
+                let path = dir.join(name);
+                if is_executable(&path) {
+                    index.entries.insert(name.clone(), (position, path));
+                }
+            }
+        }
+        index
+    }
+
+    fn resolve(&self, name: &str) -> Option<PathBuf> {
+        let best = |candidates: &[String]| {
+            candidates
+                .iter()
+                .filter_map(|c| self.entries.get(&index_key(c.as_ref())))
+                .min_by_key(|(position, _)| *position)
+                .map(|(_, path)| strip_verbatim_prefix(path))
+        };
+        best(&candidate_names(name)).or_else(|| best(&shim_names(name)))
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    std::fs::metadata(path).is_ok_and(|m| m.is_file())
+}
+
+#[cfg(target_os = "windows")]
+fn index_key(name: &std::ffi::OsStr) -> OsString {
+    name.to_ascii_lowercase()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn index_key(name: &std::ffi::OsStr) -> OsString {
+    name.to_os_string()
+}
+
+/// File names `name` may have on disk, as `which` would try them.
+#[cfg(target_os = "windows")]
+fn candidate_names(name: &str) -> Vec<String> {
+    if Path::new(name).extension().is_some() {
+        return vec![name.to_string()];
+    }
+    let pathext =
+        crate::platform::var("PATHEXT").unwrap_or_else(|| ".COM;.EXE;.BAT;.CMD".to_string());
+    pathext
+        .split(';')
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| format!("{name}{ext}"))
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn candidate_names(name: &str) -> Vec<String> {
+    vec![name.to_string()]
+}
+
+/// Shim file names tried when no candidate matched.
+#[cfg(target_os = "windows")]
+fn shim_names(name: &str) -> Vec<String> {
+    if Path::new(name).extension().is_some() {
+        return Vec::new();
+    }
+    WINDOWS_SHIM_EXTENSIONS
+        .iter()
+        .map(|ext| format!("{name}.{ext}"))
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn shim_names(_name: &str) -> Vec<String> {
+    Vec::new()
+}
+
+/// Resolves `name` against `PATH`, honoring the active environment snapshot.
+fn which(name: &str) -> which::Result<PathBuf> {
+    if !crate::platform::has_env_snapshot() {
+        return which::which(name);
+    }
+    let Some(paths) = crate::platform::var("PATH") else {
+        return Err(which::Error::CannotFindBinaryPath);
+    };
+    let cwd =
+        std::env::current_dir().map_err(|_| which::Error::CannotGetCurrentDirAndPathListEmpty)?;
+    which::which_in(name, Some(paths), cwd)
+}
+
+/// Determines how the binary at `path` was installed.
+///
+/// Symlinks are resolved first so that shims such as `/opt/homebrew/bin/goose`
+/// or npm's `bin/claude` are classified by their real location. If the path
+/// cannot be resolved, it is classified as given.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::InstallSource;
+/// use harness_locate::detection::install_source;
+/// use std::path::Path;
+///
+/// let source = install_source(Path::new("/nix/store/abc-goose/bin/goose"));
+/// assert_eq!(source, InstallSource::Nix);
+/// ```
+#[must_use]
+pub fn install_source(path: &Path) -> InstallSource {
+    let resolved = std::fs::canonicalize(path)
+        .map(|p| strip_verbatim_prefix(&p))
+        .unwrap_or_else(|_| path.to_path_buf());
+    match InstallSource::from_path(&resolved) {
+        InstallSource::Unknown => InstallSource::from_path(path),
+        source => source,
+    }
+}
+
+/// Shim extensions probed when `PATHEXT` resolution finds nothing.
+#[cfg(target_os = "windows")]
+const WINDOWS_SHIM_EXTENSIONS: &[&str] = &["exe", "cmd", "bat", "ps1"];
+
+#[cfg(target_os = "windows")]
+fn find_windows_shim(name: &str) -> Result<Option<PathBuf>> {
+    if Path::new(name).extension().is_some() {
+        return Ok(None);
+    }
+    for ext in WINDOWS_SHIM_EXTENSIONS {
+        match which(&format!("{name}.{ext}")) {
+            Ok(path) => return Ok(Some(strip_verbatim_prefix(&path))),
+            Err(which::Error::CannotFindBinaryPath) => continue,
+            Err(e) => return Err(Error::BinaryDetection(e.to_string())),
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn find_windows_shim(_name: &str) -> Result<Option<PathBuf>> {
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +297,61 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().is_some());
     }
+
+    #[test]
+    fn install_source_falls_back_to_given_path() {
+        let source = install_source(Path::new("/nonexistent/.cargo/bin/goose"));
+        assert_eq!(source, InstallSource::Cargo);
+    }
+
+    #[test]
+    fn find_binary_never_returns_verbatim_paths() {
+        #[cfg(unix)]
+        let binary = "ls";
+        #[cfg(windows)]
+        let binary = "cmd";
+
+        let path = find_binary(binary).unwrap().unwrap();
+        assert!(!path.to_string_lossy().starts_with(r"\\?\"));
+    }
+
+    #[test]
+    fn find_binaries_agrees_with_find_binary() {
+        #[cfg(unix)]
+        let binary = "ls";
+        #[cfg(windows)]
+        let binary = "cmd";
+
+        let found = find_binaries(&[binary, "nonexistent-binary-xyz-12345"]);
+        assert_eq!(found[binary], find_binary(binary).unwrap());
+        assert_eq!(found["nonexistent-binary-xyz-12345"], None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_binaries_honors_path_order_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = std::env::temp_dir().join(format!("harness-locate-path-{}", std::process::id()));
+        let (first, second) = (root.join("first"), root.join("second"));
+        for dir in [&first, &second] {
+            std::fs::create_dir_all(dir).unwrap();
+        }
+        let make = |path: PathBuf, mode: u32| {
+            std::fs::write(&path, "#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        };
+        make(first.join("tool"), 0o755);
+        make(second.join("tool"), 0o755);
+        make(first.join("notes"), 0o644);
+
+        let path = std::env::join_paths([&first, &second]).unwrap();
+        let ctx =
+            crate::platform::PathContext::fake(&root).with_var("PATH", path.to_str().unwrap());
+        let found = ctx.scope(|| find_binaries(&["tool", "notes"]));
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(found["tool"], Some(first.join("tool")));
+        assert_eq!(found["notes"], None);
+    }
 }
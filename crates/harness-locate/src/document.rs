@@ -0,0 +1,556 @@
+//! Editable MCP config documents.
+//!
+//! A [`ConfigResource`] only says where a harness keeps its MCP servers:
+//! a file and a JSON pointer into it. [`McpConfigDocument`] loads that file
+//! and edits the server entries at the pointer, leaving the rest of the
+//! document alone. Key order, indentation, the file's encoding, byte order
+//! mark and line endings are kept when it is saved, so a one-server change
+//! shows up as a one-server diff.
+//!
+//! JSON, JSONC and YAML files are supported. Comments are not kept:
+//! comments in YAML and JSONC files, and trailing commas in JSONC files,
+//! are accepted when loading but dropped when the document is saved.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::{Harness, HarnessKind, McpConfigDocument, Scope};
+//! use serde_json::json;
+//!
+//! let harness = Harness::new(HarnessKind::ClaudeCode);
+//! let resource = harness.mcp(&Scope::Global)?.expect("Claude Code has MCP config");
+//!
+//! let mut doc = McpConfigDocument::load(&resource)?;
+//! doc.set("filesystem", json!({ "command": "mcp-fs", "args": [] }));
+//! doc.save()?;
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+use crate::text::{DecodedText, Normalization};
+use crate::types::{ConfigResource, FileFormat};
+
+/// Indentation used when a file has none to copy.
+const DEFAULT_INDENT: &str = "  ";
+
+/// An MCP config file loaded for editing.
+///
+/// Server entries live in the object at [`key_path`](Self::key_path) and
+/// are addressed by name. Missing objects along the pointer are created on
+/// the first [`set`](Self::set).
+#[derive(Debug, Clone)]
+pub struct McpConfigDocument {
+    file: PathBuf,
+    key_path: String,
+    format: FileFormat,
+    root: Value,
+    indent: String,
+    trailing_newline: bool,
+    normalization: Normalization,
+    dirty: bool,
+    /// The text last read or written, for journaling the next save.
+    saved: String,
+}
+
+impl McpConfigDocument {
+    /// Loads the file described by `resource`.
+    ///
+    /// A missing file loads as an empty document, which is created on
+    /// [`save`](Self::save). The file's encoding and line endings are
+    /// recorded, and restored when it is saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or is not an
+    /// object at the top level.
+    pub fn load(resource: &ConfigResource) -> Result<Self> {
+        let decoded = match crate::fs::read_text(&resource.file) {
+            Ok(decoded) => decoded,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => DecodedText::default(),
+            Err(e) => return Err(e.into()),
+        };
+        let document = Self::parse(
+            &resource.file,
+            &resource.key_path,
+            resource.format,
+            &decoded.text,
+        )?;
+        Ok(document.with_normalization(decoded.normalization))
+    }
+
+    /// Parses `content` as the document for `file`, without reading it.
+    ///
+    /// The document is saved as UTF-8 with `\n` line endings unless
+    /// [`with_normalization`](Self::with_normalization) says otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `content` cannot be parsed or is not an object
+    /// at the top level. YAML indented with tabs or repeating a key fails
+    /// with [`Error::YamlLint`], listing where each problem is.
+    pub fn parse(
+        file: impl Into<PathBuf>,
+        key_path: &str,
+        format: FileFormat,
+        content: &str,
+    ) -> Result<Self> {
+        let file = file.into();
+        let root = if content.trim().is_empty() {
+            Value::Object(Map::new())
+        } else if format == FileFormat::Yaml {
+            parse_yaml(&file, content)?
+        } else if format == FileFormat::Jsonc {
+            serde_json::from_str(&crate::jsonc::strip(content))?
+        } else {
+            serde_json::from_str(content)?
+        };
+        if !root.is_object() {
+            return Err(Error::UnsupportedMcpConfig {
+                harness: "config document".into(),
+                reason: "top-level value must be an object".into(),
+            });
+        }
+        Ok(Self {
+            file,
+            key_path: key_path.into(),
+            indent: detect_indent(content, format),
+            format,
+            root,
+            trailing_newline: content.is_empty() || content.ends_with('\n'),
+            normalization: Normalization::default(),
+            dirty: false,
+            saved: content.to_string(),
+        })
+    }
+
+    /// Sets the encoding, byte order mark and line endings the document is
+    /// saved with, as reported by [`text::decode`](crate::text::decode) for
+    /// the file's original bytes.
+    #[must_use]
+    pub fn with_normalization(mut self, normalization: Normalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    /// Returns the encoding, byte order mark and line endings the document
+    /// is saved with.
+    #[must_use]
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Returns the path of the config file.
+    #[must_use]
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+
+    /// Returns the JSON pointer to the servers object.
+    #[must_use]
+    pub fn key_path(&self) -> &str {
+        &self.key_path
+    }
+
+    /// Returns the format of the config file.
+    #[must_use]
+    pub fn format(&self) -> FileFormat {
+        self.format
+    }
+
+    /// Returns the whole parsed document.
+    #[must_use]
+    pub fn root(&self) -> &Value {
+        &self.root
+    }
+
+    /// Returns the servers object, or `None` if the document has none yet.
+    #[must_use]
+    pub fn servers(&self) -> Option<&Map<String, Value>> {
+        self.root.pointer(&self.key_path)?.as_object()
+    }
+
+    /// Returns the server names in document order.
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        self.servers()
+            .map(|servers| servers.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the entry for server `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.servers()?.get(name)
+    }
+
+    /// Sets the entry for server `name`, returning the previous one.
+    ///
+    /// An existing entry keeps its position; a new one is appended.
+    pub fn set(&mut self, name: &str, value: Value) -> Option<Value> {
+        if self.get(name) == Some(&value) {
+            return Some(value);
+        }
+        self.dirty = true;
+        servers_mut(&mut self.root, &self.key_path).insert(name.to_string(), value)
+    }
+
+    /// Creates the servers object if the document lacks it.
+    pub(crate) fn ensure_servers(&mut self) {
+        if self.servers().is_none() {
+            servers_mut(&mut self.root, &self.key_path);
+            self.dirty = true;
+        }
+    }
+
+    /// Removes the entry for server `name`, returning it.
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        let removed = self
+            .root
+            .pointer_mut(&self.key_path)?
+            .as_object_mut()?
+            .shift_remove(name)?;
+        self.dirty = true;
+        Some(removed)
+    }
+
+    /// Returns `true` if the document changed since it was loaded or last
+    /// saved.
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Renders the document in its file format and indentation, as UTF-8
+    /// with `\n` line endings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document cannot be serialized.
+    pub fn render(&self) -> Result<String> {
+        let mut out = if self.format == FileFormat::Yaml {
+            render_yaml(&self.root, &self.indent)?
+        } else {
+            let mut buf = Vec::new();
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(self.indent.as_bytes());
+            let mut serializer = serde_json::Serializer::with_formatter(&mut buf, formatter);
+            serde::Serialize::serialize(&self.root, &mut serializer)?;
+            String::from_utf8(buf).expect("serde_json writes UTF-8")
+        };
+        while out.ends_with('\n') {
+            out.pop();
+        }
+        if self.trailing_newline {
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Writes the document back to its file if it changed, creating parent
+    /// directories as needed, and records the change in the
+    /// [journal](crate::journal) as `mcp.save`.
+    ///
+    /// The file is written in the encoding, byte order mark and line
+    /// endings it was loaded with. Comments are not written back.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document cannot be serialized or written.
+    pub fn save(&mut self) -> Result<()> {
+        self.save_as("mcp.save")
+    }
+
+    /// Like [`save`](Self::save), journaling the change as `operation`.
+    pub(crate) fn save_as(&mut self, operation: &str) -> Result<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+        let fs = crate::fs::current();
+        if let Some(parent) = self.file.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs.create_dir_all(parent)?;
+        }
+        let content = self.render()?;
+        fs.write(
+            &self.file,
+            &crate::text::encode(&content, self.normalization),
+        )?;
+        crate::journal::record(operation, &self.file, &self.saved, &content);
+        self.saved = content;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Returns the object at `pointer`, creating it and its parents as needed.
+///
+/// Non-object values along the way are replaced.
+fn servers_mut<'a>(root: &'a mut Value, pointer: &str) -> &'a mut Map<String, Value> {
+    let mut current = root;
+    for token in pointer.split('/').skip(1) {
+        let key = token.replace("~1", "/").replace("~0", "~");
+        if !current.is_object() {
+            *current = Value::Object(Map::new());
+        }
+        let Value::Object(map) = current else {
+            unreachable!()
+        };
+        current = map.entry(key).or_insert_with(|| Value::Object(Map::new()));
+    }
+    if !current.is_object() {
+        *current = Value::Object(Map::new());
+    }
+    let Value::Object(map) = current else {
+        unreachable!()
+    };
+    map
+}
+
+/// Returns the indentation unit of `content`: the leading whitespace of the
+/// first indented line.
+fn detect_indent(content: &str, format: FileFormat) -> String {
+    content
+        .lines()
+        .filter(|line| format != FileFormat::Yaml || !line.trim_start().starts_with('#'))
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or(DEFAULT_INDENT)
+        .to_string()
+}
+
+/// Parses YAML after checking it for tab indentation and duplicate keys,
+/// which strict parsing reports poorly or not at all.
+#[cfg(feature = "yaml")]
+fn parse_yaml(file: &Path, content: &str) -> Result<Value> {
+    let diagnostics = crate::yamllint::lint_yaml(content);
+    if !diagnostics.is_empty() {
+        return Err(Error::YamlLint {
+            file: file.to_path_buf(),
+            diagnostics,
+        });
+    }
+    Ok(serde_yaml::from_str(content)?)
+}
+
+#[cfg(feature = "yaml")]
+fn render_yaml(root: &Value, indent: &str) -> Result<String> {
+    Ok(reindent_yaml(&serde_yaml::to_string(root)?, indent))
+}
+
+#[cfg(not(feature = "yaml"))]
+fn parse_yaml(_: &Path, _: &str) -> Result<Value> {
+    Err(yaml_unsupported())
+}
+
+#[cfg(not(feature = "yaml"))]
+fn render_yaml(_: &Value, _: &str) -> Result<String> {
+    Err(yaml_unsupported())
+}
+
+#[cfg(not(feature = "yaml"))]
+fn yaml_unsupported() -> Error {
+    Error::UnsupportedMcpConfig {
+        harness: "config document".into(),
+        reason: "YAML documents require the `yaml` feature".into(),
+    }
+}
+
+/// Rewrites the two-space indentation `serde_yaml` emits to `indent`.
+#[cfg(feature = "yaml")]
+fn reindent_yaml(yaml: &str, indent: &str) -> String {
+    if indent == DEFAULT_INDENT {
+        return yaml.to_string();
+    }
+    yaml.lines()
+        .map(|line| {
+            let trimmed = line.trim_start_matches(' ');
+            let depth = (line.len() - trimmed.len()) / DEFAULT_INDENT.len();
+            format!("{}{trimmed}\n", indent.repeat(depth))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    fn resource(file: &str, key_path: &str, format: FileFormat) -> ConfigResource {
+        ConfigResource {
+            file: file.into(),
+            file_exists: true,
+            key_path: key_path.into(),
+            format,
+            schema_url: None,
+        }
+    }
+
+    #[test]
+    fn edits_servers_at_key_path() {
+        let mut doc = McpConfigDocument::parse(
+            "/cfg/.mcp.json",
+            "/mcpServers",
+            FileFormat::Json,
+            r#"{"mcpServers": {"a": {"command": "a"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(doc.get("a"), Some(&json!({ "command": "a" })));
+        assert!(!doc.is_dirty());
+
+        assert!(doc.set("b", json!({ "command": "b" })).is_none());
+        assert_eq!(doc.names(), ["a", "b"]);
+        assert!(doc.is_dirty());
+
+        assert_eq!(doc.remove("a"), Some(json!({ "command": "a" })));
+        assert_eq!(doc.names(), ["b"]);
+        assert!(doc.remove("missing").is_none());
+    }
+
+    #[test]
+    fn setting_an_equal_value_is_not_a_change() {
+        let mut doc = McpConfigDocument::parse(
+            "/cfg/.mcp.json",
+            "/mcpServers",
+            FileFormat::Json,
+            r#"{"mcpServers": {"a": {"command": "a"}}}"#,
+        )
+        .unwrap();
+        doc.set("a", json!({ "command": "a" }));
+        assert!(!doc.is_dirty());
+    }
+
+    #[test]
+    fn creates_missing_objects_along_key_path() {
+        let mut doc =
+            McpConfigDocument::parse("/cfg/c.json", "/a/b~1c", FileFormat::Json, "").unwrap();
+        assert!(doc.servers().is_none());
+        doc.set("s", json!({}));
+        assert_eq!(doc.root(), &json!({ "a": { "b/c": { "s": {} } } }));
+    }
+
+    #[test]
+    fn json_keeps_key_order_and_indentation() {
+        let content = "{\n    \"theme\": \"dark\",\n    \"mcpServers\": {\n        \"z\": {\n            \"command\": \"z\"\n        }\n    },\n    \"autoUpdate\": false\n}\n";
+        let mut doc =
+            McpConfigDocument::parse("/c.json", "/mcpServers", FileFormat::Json, content).unwrap();
+        assert_eq!(doc.render().unwrap(), content);
+
+        doc.set("a", json!({ "command": "a" }));
+        let rendered = doc.render().unwrap();
+        assert!(
+            rendered
+                .starts_with("{\n    \"theme\": \"dark\",\n    \"mcpServers\": {\n        \"z\"")
+        );
+        assert!(
+            rendered
+                .contains("\"z\": {\n            \"command\": \"z\"\n        },\n        \"a\"")
+        );
+        assert!(rendered.ends_with("\"autoUpdate\": false\n}\n"));
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn yaml_keeps_key_order_and_indentation() {
+        let content =
+            "extensions:\n    zeta:\n        cmd: zeta\n        enabled: true\nGOOSE_MODEL: gpt\n";
+        let mut doc =
+            McpConfigDocument::parse("/c.yaml", "/extensions", FileFormat::Yaml, content).unwrap();
+        assert_eq!(doc.render().unwrap(), content);
+
+        doc.set("alpha", json!({ "cmd": "alpha" }));
+        assert_eq!(
+            doc.render().unwrap(),
+            "extensions:\n    zeta:\n        cmd: zeta\n        enabled: true\n    alpha:\n        cmd: alpha\nGOOSE_MODEL: gpt\n"
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "yaml"))]
+    fn yaml_requires_feature() {
+        let result = McpConfigDocument::parse(
+            "/c.yaml",
+            "/extensions",
+            FileFormat::Yaml,
+            "extensions: {}\n",
+        );
+        assert!(matches!(result, Err(Error::UnsupportedMcpConfig { .. })));
+    }
+
+    #[test]
+    fn rejects_non_object_documents() {
+        let result = McpConfigDocument::parse("/c.json", "/mcpServers", FileFormat::Json, "[]");
+        assert!(matches!(result, Err(Error::UnsupportedMcpConfig { .. })));
+    }
+
+    #[test]
+    fn load_and_save_through_current_fs() {
+        let ctx = PathContext::fake("/fake")
+            .with_fs(MemoryFs::new().with_file("/cfg/.mcp.json", "{\n  \"mcpServers\": {}\n}\n"));
+        ctx.scope(|| {
+            let mut doc =
+                McpConfigDocument::load(&resource("/cfg/.mcp.json", "/mcpServers", FileFormat::Json))
+                    .unwrap();
+            doc.set("fs", json!({ "command": "mcp-fs" }));
+            doc.save().unwrap();
+            assert!(!doc.is_dirty());
+
+            let saved = crate::fs::read_text(Path::new("/cfg/.mcp.json")).unwrap().text;
+            assert_eq!(
+                saved,
+                "{\n  \"mcpServers\": {\n    \"fs\": {\n      \"command\": \"mcp-fs\"\n    }\n  }\n}\n"
+            );
+
+            let mut fresh =
+                McpConfigDocument::load(&resource("/new/dir/mcp.json", "/servers", FileFormat::Json))
+                    .unwrap();
+            assert!(fresh.names().is_empty());
+            fresh.set("x", json!({}));
+            fresh.save().unwrap();
+            assert!(crate::fs::current().exists(Path::new("/new/dir/mcp.json")));
+
+            let journal = crate::journal::read_journal().unwrap();
+            let files: Vec<&Path> = journal.iter().map(|e| e.file.as_path()).collect();
+            assert_eq!(
+                files,
+                [Path::new("/cfg/.mcp.json"), Path::new("/new/dir/mcp.json")]
+            );
+            assert!(journal.iter().all(|e| e.operation == "mcp.save"));
+            assert_eq!(
+                journal[1].diff_hash,
+                crate::journal::diff_hash("", &crate::fs::read_text(Path::new("/new/dir/mcp.json")).unwrap().text)
+            );
+        });
+    }
+
+    #[test]
+    fn save_keeps_encoding_and_line_endings() {
+        let original = "\u{feff}{\r\n  \"mcpServers\": {}\r\n}\r\n";
+        let utf16: Vec<u8> = original.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let ctx = PathContext::fake("/fake").with_fs(
+            MemoryFs::new()
+                .with_file("/cfg/crlf.json", original.trim_start_matches('\u{feff}'))
+                .with_file("/cfg/utf16.json", utf16),
+        );
+        ctx.scope(|| {
+            for file in ["/cfg/crlf.json", "/cfg/utf16.json"] {
+                let mut doc =
+                    McpConfigDocument::load(&resource(file, "/mcpServers", FileFormat::Json))
+                        .unwrap();
+                doc.set("fs", json!({ "command": "mcp-fs" }));
+                doc.save().unwrap();
+
+                let bytes = crate::fs::current().read(Path::new(file)).unwrap();
+                let decoded = crate::text::decode(&bytes).unwrap();
+                assert_eq!(decoded.normalization, doc.normalization());
+                assert!(decoded.normalization.crlf_normalized);
+                assert!(!decoded.text.contains('\r'));
+                assert!(decoded.text.contains("mcp-fs"));
+            }
+        });
+    }
+}
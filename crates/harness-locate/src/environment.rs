@@ -0,0 +1,237 @@
+//! Execution environment detection.
+//!
+//! Harness configuration behaves differently depending on where the
+//! process runs. Inside a devcontainer the home directory is usually
+//! rebuilt from an image, so global configuration is ephemeral; on a CI
+//! runner harnesses are rarely installed at all. This module detects
+//! those environments from well-known environment variables and
+//! filesystem markers so callers can pick sensible defaults.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::Scope;
+
+/// Environment variables that identify a CI runner.
+const CI_VARS: &[&str] = &[
+    "CI",
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "CIRCLECI",
+    "JENKINS_URL",
+    "TF_BUILD",
+];
+
+/// Environment variables that identify a devcontainer or codespace.
+const DEVCONTAINER_VARS: &[&str] = &["REMOTE_CONTAINERS", "CODESPACES", "DEVCONTAINER"];
+
+/// Files whose presence indicates the process runs inside a container.
+const CONTAINER_MARKERS: &[&str] = &["/.dockerenv", "/run/.containerenv"];
+
+/// Environment variables set by `sshd` for remote sessions.
+const SSH_VARS: &[&str] = &["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"];
+
+/// The kind of environment the current process is running in.
+///
+/// Use [`Environment::detect`] to inspect the current process.
+///
+/// # Extensibility
+///
+/// This enum is marked `#[non_exhaustive]` to allow adding new
+/// environments in future versions without breaking changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Environment {
+    /// A regular developer machine.
+    Local,
+    /// A devcontainer, codespace, or other container.
+    Devcontainer,
+    /// A continuous integration runner.
+    Ci,
+    /// A remote shell session over SSH.
+    RemoteSsh,
+}
+
+impl fmt::Display for Environment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Environment {
+    /// Detects the environment of the current process.
+    ///
+    /// CI takes precedence over containers (many CI jobs run in one),
+    /// and containers take precedence over SSH sessions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::Environment;
+    ///
+    /// let env = Environment::detect();
+    /// if env.global_config_is_ephemeral() {
+    ///     eprintln!("warning: global config will not survive a rebuild");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn detect() -> Self {
+        Self::detect_with(|name| std::env::var(name).ok(), Path::exists)
+    }
+
+    /// Detects the environment from the given variable lookup and
+    /// filesystem probe.
+    pub(crate) fn detect_with(
+        var: impl Fn(&str) -> Option<String>,
+        exists: impl Fn(&Path) -> bool,
+    ) -> Self {
+        let is_set = |name: &&str| {
+            var(name).is_some_and(|v| {
+                let v = v.trim();
+                !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false")
+            })
+        };
+
+        if CI_VARS.iter().any(is_set) {
+            Self::Ci
+        } else if DEVCONTAINER_VARS.iter().any(is_set)
+            || CONTAINER_MARKERS
+                .iter()
+                .any(|marker| exists(&PathBuf::from(marker)))
+        {
+            Self::Devcontainer
+        } else if SSH_VARS.iter().any(is_set) {
+            Self::RemoteSsh
+        } else {
+            Self::Local
+        }
+    }
+
+    /// Returns a short, stable identifier for this environment.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Local => "local",
+            Self::Devcontainer => "devcontainer",
+            Self::Ci => "ci",
+            Self::RemoteSsh => "remote_ssh",
+        }
+    }
+
+    /// Returns `true` if user-level configuration is likely discarded
+    /// when the environment is torn down.
+    #[must_use]
+    pub const fn global_config_is_ephemeral(&self) -> bool {
+        matches!(self, Self::Devcontainer | Self::Ci)
+    }
+
+    /// Returns the scope resources should be read from or written to by default.
+    ///
+    /// Ephemeral environments prefer project scope, since that is the only
+    /// configuration checked into the repository.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::{Environment, Scope};
+    /// use std::path::Path;
+    ///
+    /// let scope = Environment::Devcontainer.preferred_scope(Path::new("/workspaces/app"));
+    /// assert!(matches!(scope, Scope::Project(_)));
+    ///
+    /// let scope = Environment::Local.preferred_scope(Path::new("/workspaces/app"));
+    /// assert!(matches!(scope, Scope::Global));
+    /// ```
+    #[must_use]
+    pub fn preferred_scope(&self, project_root: &Path) -> Scope {
+        if self.global_config_is_ephemeral() {
+            Scope::Project(project_root.to_path_buf())
+        } else {
+            Scope::Global
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn detect(vars: &[(&str, &str)], markers: &[&str]) -> Environment {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Environment::detect_with(
+            |name| vars.get(name).cloned(),
+            |path| markers.iter().any(|m| Path::new(m) == path),
+        )
+    }
+
+    #[test]
+    fn detects_local_by_default() {
+        assert_eq!(detect(&[], &[]), Environment::Local);
+    }
+
+    #[test]
+    fn detects_ci_from_generic_var() {
+        assert_eq!(detect(&[("CI", "true")], &[]), Environment::Ci);
+    }
+
+    #[test]
+    fn ignores_falsy_ci_values() {
+        assert_eq!(detect(&[("CI", "false")], &[]), Environment::Local);
+        assert_eq!(detect(&[("CI", "0")], &[]), Environment::Local);
+        assert_eq!(detect(&[("CI", "")], &[]), Environment::Local);
+    }
+
+    #[test]
+    fn detects_devcontainer_from_var() {
+        assert_eq!(
+            detect(&[("REMOTE_CONTAINERS", "true")], &[]),
+            Environment::Devcontainer
+        );
+        assert_eq!(
+            detect(&[("CODESPACES", "true")], &[]),
+            Environment::Devcontainer
+        );
+    }
+
+    #[test]
+    fn detects_container_from_marker() {
+        assert_eq!(detect(&[], &["/.dockerenv"]), Environment::Devcontainer);
+    }
+
+    #[test]
+    fn detects_remote_ssh() {
+        assert_eq!(
+            detect(&[("SSH_CONNECTION", "10.0.0.1 22 10.0.0.2 22")], &[]),
+            Environment::RemoteSsh
+        );
+    }
+
+    #[test]
+    fn ci_takes_precedence_over_container() {
+        assert_eq!(
+            detect(&[("GITHUB_ACTIONS", "true")], &["/.dockerenv"]),
+            Environment::Ci
+        );
+    }
+
+    #[test]
+    fn preferred_scope_is_project_when_ephemeral() {
+        let root = Path::new("/repo");
+        assert!(matches!(
+            Environment::Ci.preferred_scope(root),
+            Scope::Project(p) if p == root
+        ));
+        assert!(matches!(
+            Environment::RemoteSsh.preferred_scope(root),
+            Scope::Global
+        ));
+    }
+}
@@ -0,0 +1,433 @@
+//! Environment variables referenced across a harness scope.
+//!
+//! [`McpServer::missing_env_vars`](crate::McpServer::missing_env_vars)
+//! checks one server. [`EnvReport::from_scope`] collects every variable a
+//! scope depends on, from:
+//!
+//! - env and header references of configured MCP servers
+//! - `$VAR` and `${VAR}` in hook commands of settings files
+//! - `${VAR}` in skill frontmatter values
+//!
+//! Each variable lists the resources that reference it and whether it is
+//! set, so that onboarding docs can list what a project needs.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+use crate::fs::{exists_for, read_to_string_for};
+use crate::harness::Harness;
+use crate::skill::parse_frontmatter;
+use crate::types::{DirectoryStructure, ResourceKind, Scope};
+
+/// `$VAR`, `${VAR}`, and `${VAR:-default}` in shell commands.
+static SHELL_VAR_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$(?:\{([A-Za-z_][A-Za-z0-9_]*)(?:[:?+=-][^}]*)?\}|([A-Za-z_][A-Za-z0-9_]*))")
+        .unwrap()
+});
+
+/// `${VAR}` in frontmatter values.
+static TEMPLATE_VAR_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+
+/// Variables the harness sets itself when running hooks.
+const HARNESS_PROVIDED: &[&str] = &["CLAUDE_PROJECT_DIR", "CLAUDE_PLUGIN_ROOT"];
+
+/// The kind of resource that references an environment variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceKind {
+    /// A configured MCP server.
+    McpServer,
+    /// A hook command in a settings file.
+    Hook,
+    /// A skill's frontmatter.
+    Skill,
+}
+
+/// A resource referencing an environment variable.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct EnvReference {
+    /// The kind of resource.
+    pub kind: ReferenceKind,
+    /// The resource name: the MCP server key, the hook event with its
+    /// matcher such as `PreToolUse(Bash)`, or the skill name.
+    pub name: String,
+    /// The file the reference was read from.
+    pub path: PathBuf,
+}
+
+/// Whether a referenced environment variable has a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnvVarStatus {
+    /// Set in the environment.
+    Set,
+    /// Not set in the environment, but defined in a `.env` file passed to
+    /// [`EnvReport::with_dotenv`].
+    DotEnv,
+    /// Not set anywhere.
+    Unset,
+}
+
+/// An environment variable with every resource referencing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvVarUsage {
+    /// The variable name.
+    pub name: String,
+    /// Whether the variable has a value.
+    pub status: EnvVarStatus,
+    /// The referencing resources, sorted and without duplicates.
+    pub references: BTreeSet<EnvReference>,
+}
+
+/// Environment variables referenced in a scope, keyed by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EnvReport {
+    vars: BTreeMap<String, EnvVarUsage>,
+}
+
+impl EnvReport {
+    /// Creates an empty report.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the variables referenced by the MCP servers, hooks, and
+    /// skills the harness has in `scope`.
+    ///
+    /// Missing config files and directories are skipped. Variables are
+    /// checked against the current environment; use
+    /// [`with_dotenv`](Self::with_dotenv) to also consult a `.env` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a resource location cannot be resolved, or a
+    /// config file, settings file, or skill cannot be read or parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, Scope};
+    ///
+    /// let root = std::env::current_dir()?;
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// let report = harness
+    ///     .env_report(&Scope::Project(root.clone()))?
+    ///     .with_dotenv(&root.join(".env"))?;
+    /// for var in report.unset() {
+    ///     println!("{} is needed by {} resource(s)", var.name, var.references.len());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_scope(harness: &Harness, scope: &Scope) -> Result<Self> {
+        let mut report = Self::new();
+
+        if let Some(mcp) = harness.mcp(scope)?.filter(|m| m.file_exists) {
+            for (name, server) in harness.read_mcp_servers(scope)? {
+                for var in server.env_var_names() {
+                    report.add(
+                        var,
+                        EnvReference {
+                            kind: ReferenceKind::McpServer,
+                            name: name.clone(),
+                            path: mcp.file.clone(),
+                        },
+                    );
+                }
+            }
+        }
+
+        let reason = harness.access_reason(None, "env report");
+        for file in harness.backend().hook_files(scope)? {
+            if !exists_for(&file, reason) {
+                continue;
+            }
+            let settings: serde_json::Value =
+                serde_json::from_str(&read_to_string_for(&file, reason)?)?;
+            report.add_hooks(&settings, &file);
+        }
+
+        if let Some(skills) = harness.skills(scope)?.filter(|r| r.exists) {
+            let reason = harness.access_reason(Some(ResourceKind::Skills), "env report");
+            let nested = matches!(skills.structure, DirectoryStructure::Nested { .. });
+            for file in skills.enumerate()? {
+                let name = if nested {
+                    file.parent().and_then(Path::file_name)
+                } else {
+                    file.file_stem()
+                };
+                let Some(name) = name.and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                let content = read_to_string_for(&file, reason)?;
+                if let Some(yaml) = parse_frontmatter(&content)?.yaml {
+                    let mut vars = BTreeSet::new();
+                    collect_template_vars(&yaml, &mut vars);
+                    for var in vars {
+                        report.add(
+                            &var,
+                            EnvReference {
+                                kind: ReferenceKind::Skill,
+                                name: name.to_string(),
+                                path: file.clone(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Records that `reference` uses the variable `name`.
+    ///
+    /// The variable's status is read from the current environment when it
+    /// is first added.
+    pub fn add(&mut self, name: &str, reference: EnvReference) {
+        self.vars
+            .entry(name.to_string())
+            .or_insert_with(|| EnvVarUsage {
+                name: name.to_string(),
+                status: if crate::platform::var(name).is_some() {
+                    EnvVarStatus::Set
+                } else {
+                    EnvVarStatus::Unset
+                },
+                references: BTreeSet::new(),
+            })
+            .references
+            .insert(reference);
+    }
+
+    /// Marks unset variables defined in the `.env` file at `path` as
+    /// [`EnvVarStatus::DotEnv`].
+    ///
+    /// A missing file leaves the report unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read.
+    pub fn with_dotenv(mut self, path: &Path) -> Result<Self> {
+        if !exists_for(path, None) {
+            return Ok(self);
+        }
+        for name in dotenv_names(&read_to_string_for(path, None)?) {
+            if let Some(var) = self.vars.get_mut(name)
+                && var.status == EnvVarStatus::Unset
+            {
+                var.status = EnvVarStatus::DotEnv;
+            }
+        }
+        Ok(self)
+    }
+
+    /// Returns every referenced variable, sorted by name.
+    pub fn vars(&self) -> impl Iterator<Item = &EnvVarUsage> {
+        self.vars.values()
+    }
+
+    /// Returns the usage of the variable `name`, if anything references it.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&EnvVarUsage> {
+        self.vars.get(name)
+    }
+
+    /// Returns the referenced variables that are not set anywhere.
+    #[must_use]
+    pub fn unset(&self) -> Vec<&EnvVarUsage> {
+        self.vars
+            .values()
+            .filter(|var| var.status == EnvVarStatus::Unset)
+            .collect()
+    }
+
+    /// Returns `true` if no variables are referenced.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    fn add_hooks(&mut self, settings: &serde_json::Value, path: &Path) {
+        let Some(events) = settings.get("hooks").and_then(|h| h.as_object()) else {
+            return;
+        };
+        for (event, groups) in events {
+            for group in groups.as_array().into_iter().flatten() {
+                let name = match group.get("matcher").and_then(|m| m.as_str()) {
+                    Some(matcher) if !matcher.is_empty() => format!("{event}({matcher})"),
+                    _ => event.clone(),
+                };
+                let commands = group
+                    .get("hooks")
+                    .and_then(|h| h.as_array())
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|hook| hook.get("command").and_then(|c| c.as_str()));
+                for command in commands {
+                    for captures in SHELL_VAR_RE.captures_iter(command) {
+                        let Some(var) = captures.get(1).or_else(|| captures.get(2)) else {
+                            continue;
+                        };
+                        if HARNESS_PROVIDED.contains(&var.as_str()) {
+                            continue;
+                        }
+                        self.add(
+                            var.as_str(),
+                            EnvReference {
+                                kind: ReferenceKind::Hook,
+                                name: name.clone(),
+                                path: path.to_path_buf(),
+                            },
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn collect_template_vars(value: &serde_yaml::Value, vars: &mut BTreeSet<String>) {
+    match value {
+        serde_yaml::Value::String(s) => {
+            for captures in TEMPLATE_VAR_RE.captures_iter(s) {
+                vars.insert(captures[1].to_string());
+            }
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                collect_template_vars(item, vars);
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for value in map.values() {
+                collect_template_vars(value, vars);
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => collect_template_vars(&tagged.value, vars),
+        _ => {}
+    }
+}
+
+/// Returns the variable names defined in a `.env` file.
+fn dotenv_names(content: &str) -> Vec<&str> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.starts_with('#') {
+                return None;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (name, _) = line.split_once('=')?;
+            Some(name.trim()).filter(|name| !name.is_empty())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HarnessKind;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    fn reference(kind: ReferenceKind, name: &str, path: &str) -> EnvReference {
+        EnvReference {
+            kind,
+            name: name.to_string(),
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn collects_mcp_hook_and_skill_references() {
+        let fs = MemoryFs::new()
+            .with_file(
+                "/project/.mcp.json",
+                r#"{"mcpServers": {
+                    "github": {"command": "gh-mcp", "env": {"TOKEN": "${GITHUB_TOKEN}"}},
+                    "api": {"type": "http", "url": "https://api.example.com/mcp",
+                            "headers": {"Authorization": "Bearer ${API_KEY}"}}
+                }}"#,
+            )
+            .with_file(
+                "/project/.claude/settings.json",
+                r#"{"hooks": {"PostToolUse": [{"matcher": "Edit", "hooks": [
+                    {"type": "command", "command": "$CLAUDE_PROJECT_DIR/fmt.sh --token ${GITHUB_TOKEN:-none} $LINT_LEVEL"}
+                ]}]}}"#,
+            )
+            .with_file(
+                "/project/.claude/skills/deploy/SKILL.md",
+                "---\nname: deploy\ndescription: Deploy to ${DEPLOY_ENV}\nmetadata:\n  region: ${DEPLOY_REGION}\n---\nBody $NOT_FRONTMATTER",
+            )
+            .with_file("/project/.env", "# local\nexport DEPLOY_ENV=staging\nLINT_LEVEL=\n");
+
+        let report = PathContext::fake("/fake")
+            .with_var("API_KEY", "secret")
+            .with_fs(fs)
+            .scope(|| {
+                Harness::new(HarnessKind::ClaudeCode)
+                    .env_report(&Scope::Project(PathBuf::from("/project")))
+                    .and_then(|report| report.with_dotenv(Path::new("/project/.env")))
+            })
+            .unwrap();
+
+        let names: Vec<_> = report.vars().map(|v| v.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "API_KEY",
+                "DEPLOY_ENV",
+                "DEPLOY_REGION",
+                "GITHUB_TOKEN",
+                "LINT_LEVEL"
+            ]
+        );
+        assert_eq!(report.get("API_KEY").unwrap().status, EnvVarStatus::Set);
+        assert_eq!(
+            report.get("DEPLOY_ENV").unwrap().status,
+            EnvVarStatus::DotEnv
+        );
+        assert_eq!(
+            report.get("GITHUB_TOKEN").unwrap().references,
+            BTreeSet::from([
+                reference(ReferenceKind::McpServer, "github", "/project/.mcp.json"),
+                reference(
+                    ReferenceKind::Hook,
+                    "PostToolUse(Edit)",
+                    "/project/.claude/settings.json"
+                ),
+            ])
+        );
+        assert_eq!(
+            report.get("DEPLOY_REGION").unwrap().references,
+            BTreeSet::from([reference(
+                ReferenceKind::Skill,
+                "deploy",
+                "/project/.claude/skills/deploy/SKILL.md"
+            )])
+        );
+        let unset: Vec<_> = report.unset().iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(unset, ["DEPLOY_REGION", "GITHUB_TOKEN"]);
+    }
+
+    #[test]
+    fn empty_scope_has_no_references() {
+        let report = PathContext::fake("/fake")
+            .with_fs(MemoryFs::new())
+            .scope(|| {
+                Harness::new(HarnessKind::ClaudeCode)
+                    .env_report(&Scope::Project(PathBuf::from("/project")))
+            })
+            .unwrap();
+        assert!(report.is_empty());
+    }
+}
@@ -35,6 +35,16 @@ pub enum Error {
         reason: String,
     },
 
+    /// Converting an MCP server would discard fields the harness cannot
+    /// represent.
+    #[error("lossy MCP conversion for {harness}: drops {}", fields.join(", "))]
+    LossyConversion {
+        /// The target harness.
+        harness: String,
+        /// The dropped fields, e.g. `cwd` or `timeout_ms`.
+        fields: Vec<String>,
+    },
+
     /// Binary detection failed due to system error.
     #[error("binary detection error: {0}")]
     BinaryDetection(String),
@@ -44,19 +54,79 @@ pub enum Error {
     UnsupportedScope { harness: String, scope: String },
 
     /// YAML parsing failed.
+    #[cfg(feature = "yaml")]
     #[error("YAML parse error: {0}")]
     YamlParse(#[from] serde_yaml::Error),
 
+    /// A YAML file has problems strict parsing would reject or hide, such
+    /// as tab indentation or duplicate keys.
+    #[cfg(feature = "yaml")]
+    #[error(
+        "invalid YAML in {}: {}",
+        file.display(),
+        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    YamlLint {
+        /// The file checked.
+        file: PathBuf,
+        /// Each problem found, in document order.
+        diagnostics: Vec<crate::yamllint::YamlDiagnostic>,
+    },
+
+    /// JSON parsing failed.
+    #[error("JSON parse error: {0}")]
+    JsonParse(#[from] serde_json::Error),
+
+    /// TOML parsing failed.
+    #[cfg(any(feature = "registry", feature = "yaml"))]
+    #[error("TOML parse error: {0}")]
+    TomlParse(#[from] toml::de::Error),
+
     /// A required field is missing from the input.
     #[error("missing required field: {0}")]
     MissingField(String),
 
+    /// A custom harness backend was registered under a name already in use.
+    #[error("harness already registered: {0}")]
+    DuplicateHarness(String),
+
+    /// A declarative harness definition is malformed.
+    #[error("invalid harness definition: {0}")]
+    InvalidDefinition(String),
+
     /// An environment variable referenced by EnvValue is not set.
     #[error("missing environment variable: {name}")]
     MissingEnvVar {
         /// The name of the environment variable that was not set.
         name: String,
     },
+
+    /// A `platforms` entry names no known operating system or
+    /// architecture.
+    #[error("unknown platform: {0}")]
+    InvalidPlatform(String),
+
+    /// A launched MCP server exited before answering a request, or
+    /// answered it with a JSON-RPC error.
+    #[cfg(feature = "launch")]
+    #[error("MCP request {method} failed: {message}")]
+    McpProtocol {
+        /// The request method, such as `initialize`.
+        method: String,
+        /// The JSON-RPC error code, or `None` if the server exited.
+        code: Option<i64>,
+        /// The server's error message, or why there was no answer.
+        message: String,
+    },
+
+    /// A validation baseline was written by a newer version of this crate.
+    #[error("validation baseline version {found} is newer than supported version {supported}")]
+    BaselineVersion {
+        /// The version in the file.
+        found: u32,
+        /// The newest version this crate reads.
+        supported: u32,
+    },
 }
 
 /// A specialized Result type for harness operations.
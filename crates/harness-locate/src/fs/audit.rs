@@ -0,0 +1,417 @@
+//! Access auditing for [`Fs`] implementations.
+
+use std::cell::Cell;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use super::{FileMetadata, Fs};
+use crate::text::{self, DecodedText, Normalization};
+use crate::types::{HarnessKind, ResourceKind};
+
+thread_local! {
+    static REASON: Cell<Option<AccessReason>> = const { Cell::new(None) };
+}
+
+/// Why a path was accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessReason {
+    /// The harness being resolved.
+    pub harness: HarnessKind,
+    /// The resource being resolved, if it has a [`ResourceKind`].
+    pub resource: Option<ResourceKind>,
+    /// Short label for the lookup (e.g., `"skills"`, `"mcp"`, `"installation"`).
+    pub purpose: &'static str,
+}
+
+impl AccessReason {
+    pub(crate) const fn new(
+        harness: HarnessKind,
+        resource: Option<ResourceKind>,
+        purpose: &'static str,
+    ) -> Self {
+        Self {
+            harness,
+            resource,
+            purpose,
+        }
+    }
+}
+
+/// The kind of filesystem operation performed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessOp {
+    /// Existence or type check.
+    Stat,
+    /// File read.
+    Read,
+    /// File write, deletion or directory creation.
+    Write,
+    /// Directory listing.
+    List,
+}
+
+/// The result of a filesystem operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessOutcome {
+    /// The path existed and the operation succeeded.
+    Found,
+    /// The path did not exist.
+    Missing,
+    /// The path is outside the allowlist and was not touched.
+    Denied,
+    /// The operation failed for another reason.
+    Failed,
+}
+
+/// A single recorded filesystem access.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessRecord {
+    /// The path accessed.
+    pub path: PathBuf,
+    /// The operation performed.
+    pub op: AccessOp,
+    /// Why the path was accessed, when known.
+    pub reason: Option<AccessReason>,
+    /// What happened.
+    pub outcome: AccessOutcome,
+}
+
+/// A log of every path accessed through an [`AuditFs`].
+///
+/// Optionally restricts access to an allowlist of directories. Paths outside
+/// the allowlist are never touched: stat operations report them as absent
+/// and other operations fail with [`io::ErrorKind::PermissionDenied`].
+#[derive(Debug, Default)]
+pub struct AccessLog {
+    records: Mutex<Vec<AccessRecord>>,
+    normalizations: Mutex<Vec<(PathBuf, Normalization)>>,
+    allowlist: Option<Vec<PathBuf>>,
+}
+
+impl AccessLog {
+    /// Creates a log that records accesses without restricting them.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a log that only permits access beneath the given roots.
+    #[must_use]
+    pub fn with_allowlist(roots: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        Self {
+            records: Mutex::default(),
+            normalizations: Mutex::default(),
+            allowlist: Some(roots.into_iter().map(Into::into).collect()),
+        }
+    }
+
+    /// Returns `true` if `path` is permitted by the allowlist.
+    ///
+    /// Both sides are compared in [normalized](crate::paths::normalize_for_compare)
+    /// form, so `..` components cannot step outside a root.
+    #[must_use]
+    pub fn is_allowed(&self, path: &Path) -> bool {
+        self.allowlist.as_ref().is_none_or(|roots| {
+            let path = crate::paths::normalize_for_compare(path);
+            roots
+                .iter()
+                .any(|root| path.starts_with(crate::paths::normalize_for_compare(root)))
+        })
+    }
+
+    /// Returns a copy of all records so far, in access order.
+    #[must_use]
+    pub fn records(&self) -> Vec<AccessRecord> {
+        self.lock().clone()
+    }
+
+    /// Returns the records for accesses that were denied by the allowlist.
+    #[must_use]
+    pub fn denied(&self) -> Vec<AccessRecord> {
+        self.lock()
+            .iter()
+            .filter(|r| r.outcome == AccessOutcome::Denied)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the files whose text was normalized when read, with what
+    /// was changed, in read order.
+    ///
+    /// Files read unchanged are not listed.
+    #[must_use]
+    pub fn normalizations(&self) -> Vec<(PathBuf, Normalization)> {
+        self.normalizations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Removes all records.
+    pub fn clear(&self) {
+        self.lock().clear();
+        self.normalizations
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clear();
+    }
+
+    fn record(&self, path: &Path, op: AccessOp, outcome: AccessOutcome) {
+        self.lock().push(AccessRecord {
+            path: path.to_path_buf(),
+            op,
+            reason: REASON.with(Cell::get),
+            outcome,
+        });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<AccessRecord>> {
+        self.records.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// An [`Fs`] wrapper that records every access into an [`AccessLog`].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use harness_locate::fs::{AccessLog, AuditFs, StdFs};
+/// use harness_locate::platform::PathContext;
+/// use harness_locate::{Harness, HarnessKind};
+///
+/// let log = Arc::new(AccessLog::new());
+/// let ctx = PathContext::new().with_fs(AuditFs::new(StdFs, Arc::clone(&log)));
+///
+/// ctx.scope(|| Harness::new(HarnessKind::Goose).is_installed());
+///
+/// for record in log.records() {
+///     println!("{:?} {} -> {:?}", record.op, record.path.display(), record.outcome);
+/// }
+/// ```
+pub struct AuditFs<F> {
+    inner: F,
+    log: Arc<AccessLog>,
+}
+
+impl<F> AuditFs<F> {
+    /// Wraps `inner`, recording accesses into `log`.
+    pub fn new(inner: F, log: Arc<AccessLog>) -> Self {
+        Self { inner, log }
+    }
+
+    /// Returns the log accesses are recorded into.
+    #[must_use]
+    pub fn log(&self) -> &Arc<AccessLog> {
+        &self.log
+    }
+
+    fn check(&self, path: &Path, op: AccessOp) -> io::Result<()> {
+        if self.log.is_allowed(path) {
+            Ok(())
+        } else {
+            self.log.record(path, op, AccessOutcome::Denied);
+            Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is outside the audit allowlist", path.display()),
+            ))
+        }
+    }
+
+    fn track<T>(&self, path: &Path, op: AccessOp, result: io::Result<T>) -> io::Result<T> {
+        let outcome = match &result {
+            Ok(_) => AccessOutcome::Found,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => AccessOutcome::Missing,
+            Err(_) => AccessOutcome::Failed,
+        };
+        self.log.record(path, op, outcome);
+        result
+    }
+
+    fn stat(&self, path: &Path, probe: impl FnOnce(&F) -> bool) -> bool {
+        if self.check(path, AccessOp::Stat).is_err() {
+            return false;
+        }
+        let found = probe(&self.inner);
+        let outcome = if found {
+            AccessOutcome::Found
+        } else {
+            AccessOutcome::Missing
+        };
+        self.log.record(path, AccessOp::Stat, outcome);
+        found
+    }
+}
+
+impl<F> fmt::Debug for AuditFs<F>
+where
+    F: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuditFs")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F: Fs> Fs for AuditFs<F> {
+    fn exists(&self, path: &Path) -> bool {
+        self.stat(path, |fs| fs.exists(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.stat(path, |fs| fs.is_dir(path))
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        self.check(path, AccessOp::Read)?;
+        self.track(path, AccessOp::Read, self.inner.read_to_string(path))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.check(path, AccessOp::Read)?;
+        self.track(path, AccessOp::Read, self.inner.read(path))
+    }
+
+    fn read_text(&self, path: &Path) -> io::Result<DecodedText> {
+        let decoded = text::decode(&self.read(path)?)?;
+        if !decoded.normalization.is_identity() {
+            self.log
+                .normalizations
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push((path.to_path_buf(), decoded.normalization));
+        }
+        Ok(decoded)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.check(path, AccessOp::Write)?;
+        self.track(path, AccessOp::Write, self.inner.write(path, contents))
+    }
+
+    fn append(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.check(path, AccessOp::Write)?;
+        self.track(path, AccessOp::Write, self.inner.append(path, contents))
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        self.check(path, AccessOp::Write)?;
+        self.track(path, AccessOp::Write, self.inner.create_dir_all(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        self.check(path, AccessOp::List)?;
+        self.track(path, AccessOp::List, self.inner.read_dir(path))
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        self.check(from, AccessOp::Write)?;
+        self.check(to, AccessOp::Write)?;
+        self.track(to, AccessOp::Write, self.inner.rename(from, to))
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        self.check(path, AccessOp::Stat)?;
+        self.track(path, AccessOp::Stat, self.inner.metadata(path))
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        self.check(path, AccessOp::Write)?;
+        self.track(path, AccessOp::Write, self.inner.remove(path))
+    }
+}
+
+/// Runs `f` with `reason` attached to any accesses it records.
+///
+/// Accesses made for a custom harness backend have no reason.
+pub(crate) fn with_reason<R>(reason: impl Into<Option<AccessReason>>, f: impl FnOnce() -> R) -> R {
+    let previous = REASON.with(|r| r.replace(reason.into()));
+    let result = f();
+    REASON.with(|r| r.set(previous));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+
+    #[test]
+    fn records_stat_outcomes() {
+        let log = Arc::new(AccessLog::new());
+        let fs = AuditFs::new(MemoryFs::new().with_dir("/present"), Arc::clone(&log));
+
+        assert!(fs.exists(Path::new("/present")));
+        assert!(!fs.exists(Path::new("/absent")));
+
+        let records = log.records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].outcome, AccessOutcome::Found);
+        assert_eq!(records[1].outcome, AccessOutcome::Missing);
+        assert!(records.iter().all(|r| r.op == AccessOp::Stat));
+    }
+
+    #[test]
+    fn records_normalized_reads() {
+        let log = Arc::new(AccessLog::new());
+        let fs = AuditFs::new(
+            MemoryFs::new()
+                .with_file("/crlf.md", "a\r\nb\r\n")
+                .with_file("/plain.md", "a\nb\n"),
+            Arc::clone(&log),
+        );
+
+        assert_eq!(fs.read_text(Path::new("/crlf.md")).unwrap().text, "a\nb\n");
+        assert_eq!(fs.read_text(Path::new("/plain.md")).unwrap().text, "a\nb\n");
+
+        let normalizations = log.normalizations();
+        assert_eq!(normalizations.len(), 1);
+        assert_eq!(normalizations[0].0, Path::new("/crlf.md"));
+        assert!(normalizations[0].1.crlf_normalized);
+        assert_eq!(log.records().len(), 2);
+    }
+
+    #[test]
+    fn attaches_reason() {
+        let log = Arc::new(AccessLog::new());
+        let fs = AuditFs::new(MemoryFs::new(), Arc::clone(&log));
+        let reason = AccessReason::new(HarnessKind::Goose, Some(ResourceKind::Skills), "skills");
+
+        with_reason(reason, || fs.exists(Path::new("/x")));
+        fs.exists(Path::new("/y"));
+
+        let records = log.records();
+        assert_eq!(records[0].reason, Some(reason));
+        assert_eq!(records[1].reason, None);
+    }
+
+    #[test]
+    fn allowlist_denies_out_of_scope_access() {
+        let log = Arc::new(AccessLog::with_allowlist(["/allowed"]));
+        let inner = MemoryFs::new()
+            .with_file("/allowed/a.md", "a")
+            .with_file("/secret/b.md", "b");
+        let fs = AuditFs::new(inner, Arc::clone(&log));
+
+        assert!(fs.read_to_string(Path::new("/allowed/a.md")).is_ok());
+        assert!(!fs.exists(Path::new("/secret/b.md")));
+        let err = fs.read_to_string(Path::new("/secret/b.md")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+        let denied = log.denied();
+        assert_eq!(denied.len(), 2);
+        assert!(denied.iter().all(|r| r.path == Path::new("/secret/b.md")));
+    }
+
+    #[test]
+    fn allowlist_rejects_parent_traversal() {
+        let log = AccessLog::with_allowlist(["/allowed"]);
+        assert!(!log.is_allowed(Path::new("/allowed/../etc/passwd")));
+        assert!(!log.is_allowed(Path::new("/allowed/sub/../../secret")));
+        assert!(log.is_allowed(Path::new("/allowed/sub/../a.md")));
+        assert!(log.is_allowed(Path::new("/allowed/./a.md")));
+    }
+}
@@ -0,0 +1,329 @@
+//! In-memory [`Fs`] implementation.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use super::{FileMetadata, Fs};
+
+/// An [`Fs`] that keeps all files and directories in memory.
+///
+/// Writing a file implicitly creates its parent directories, which keeps
+/// test setup short. Files are stamped with the time they were written;
+/// [`with_modified`](Self::with_modified) backdates them.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "test-util")]
+/// # {
+/// use std::path::Path;
+/// use harness_locate::fs::{Fs, MemoryFs};
+///
+/// let fs = MemoryFs::new().with_file("/home/me/.claude.json", "{}");
+/// assert!(fs.exists(Path::new("/home/me")));
+/// assert_eq!(fs.read_to_string(Path::new("/home/me/.claude.json")).unwrap(), "{}");
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MemoryFs {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+    dirs: BTreeSet<PathBuf>,
+    modified: BTreeMap<PathBuf, SystemTime>,
+}
+
+impl State {
+    fn add_ancestors(&mut self, path: &Path) {
+        for ancestor in path.ancestors().skip(1) {
+            if ancestor.as_os_str().is_empty() {
+                break;
+            }
+            self.dirs.insert(ancestor.to_path_buf());
+        }
+    }
+}
+
+impl MemoryFs {
+    /// Creates an empty filesystem.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file, creating its parent directories.
+    #[must_use]
+    pub fn with_file(self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Self {
+        // Writing to memory cannot fail.
+        let _ = self.write(path.as_ref(), contents.as_ref());
+        self
+    }
+
+    /// Adds a directory and its parents.
+    #[must_use]
+    pub fn with_dir(self, path: impl AsRef<Path>) -> Self {
+        let _ = self.create_dir_all(path.as_ref());
+        self
+    }
+
+    /// Sets the modification time of an existing file or directory.
+    #[must_use]
+    pub fn with_modified(self, path: impl AsRef<Path>, time: SystemTime) -> Self {
+        self.lock()
+            .modified
+            .insert(path.as_ref().to_path_buf(), time);
+        self
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path.display()),
+    )
+}
+
+impl Fs for MemoryFs {
+    fn exists(&self, path: &Path) -> bool {
+        let state = self.lock();
+        state.files.contains_key(path) || state.dirs.contains(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.lock().dirs.contains(path)
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let state = self.lock();
+        let bytes = state.files.get(path).ok_or_else(|| not_found(path))?;
+        String::from_utf8(bytes.clone()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        let state = self.lock();
+        state
+            .files
+            .get(path)
+            .cloned()
+            .ok_or_else(|| not_found(path))
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let mut state = self.lock();
+        if state.dirs.contains(path) {
+            return Err(io::Error::new(
+                io::ErrorKind::IsADirectory,
+                format!("{} is a directory", path.display()),
+            ));
+        }
+        state.add_ancestors(path);
+        state.files.insert(path.to_path_buf(), contents.to_vec());
+        state.modified.insert(path.to_path_buf(), SystemTime::now());
+        Ok(())
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        let mut state = self.lock();
+        if state.files.contains_key(path) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} is a file", path.display()),
+            ));
+        }
+        state.add_ancestors(path);
+        state.dirs.insert(path.to_path_buf());
+        Ok(())
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let state = self.lock();
+        if !state.dirs.contains(path) {
+            return Err(not_found(path));
+        }
+        let is_child = |p: &&PathBuf| p.parent() == Some(path);
+        let mut entries: Vec<PathBuf> = state
+            .files
+            .keys()
+            .filter(is_child)
+            .chain(state.dirs.iter().filter(is_child))
+            .cloned()
+            .collect();
+        entries.sort();
+        Ok(entries)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let mut state = self.lock();
+        if !state.files.contains_key(from) && !state.dirs.contains(from) {
+            return Err(not_found(from));
+        }
+        if to
+            .parent()
+            .is_some_and(|parent| !state.dirs.contains(parent))
+        {
+            return Err(not_found(to));
+        }
+        let moved = |path: &PathBuf| {
+            path.strip_prefix(from).ok().map(|rest| {
+                if rest.as_os_str().is_empty() {
+                    to.to_path_buf()
+                } else {
+                    to.join(rest)
+                }
+            })
+        };
+        let files: Vec<_> = state
+            .files
+            .keys()
+            .filter_map(|p| Some((p.clone(), moved(p)?)))
+            .collect();
+        for (old, new) in files {
+            let contents = state.files.remove(&old).unwrap_or_default();
+            state.files.insert(new, contents);
+        }
+        let dirs: Vec<_> = state
+            .dirs
+            .iter()
+            .filter_map(|p| Some((p.clone(), moved(p)?)))
+            .collect();
+        for (old, new) in dirs {
+            state.dirs.remove(&old);
+            state.dirs.insert(new);
+        }
+        let times: Vec<_> = state
+            .modified
+            .keys()
+            .filter_map(|p| Some((p.clone(), moved(p)?)))
+            .collect();
+        for (old, new) in times {
+            if let Some(time) = state.modified.remove(&old) {
+                state.modified.insert(new, time);
+            }
+        }
+        Ok(())
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        let state = self.lock();
+        let modified = state.modified.get(path).copied();
+        if let Some(contents) = state.files.get(path) {
+            Ok(FileMetadata {
+                len: contents.len() as u64,
+                modified,
+                is_dir: false,
+            })
+        } else if state.dirs.contains(path) {
+            Ok(FileMetadata {
+                len: 0,
+                modified,
+                is_dir: true,
+            })
+        } else {
+            Err(not_found(path))
+        }
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        let mut state = self.lock();
+        if !state.files.contains_key(path) && !state.dirs.contains(path) {
+            return Err(not_found(path));
+        }
+        state.files.retain(|p, _| !p.starts_with(path));
+        state.dirs.retain(|p| !p.starts_with(path));
+        state.modified.retain(|p, _| !p.starts_with(path));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_creates_parent_directories() {
+        let fs = MemoryFs::new().with_file("/a/b/c.txt", "hi");
+        assert!(fs.is_dir(Path::new("/a")));
+        assert!(fs.is_dir(Path::new("/a/b")));
+        assert!(!fs.is_dir(Path::new("/a/b/c.txt")));
+        assert!(fs.exists(Path::new("/a/b/c.txt")));
+    }
+
+    #[test]
+    fn read_missing_file_is_not_found() {
+        let fs = MemoryFs::new();
+        let err = fs.read_to_string(Path::new("/missing")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn read_dir_lists_immediate_children() {
+        let fs = MemoryFs::new()
+            .with_file("/root/one.md", "")
+            .with_file("/root/sub/two.md", "")
+            .with_dir("/root/empty");
+
+        let entries = fs.read_dir(Path::new("/root")).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                PathBuf::from("/root/empty"),
+                PathBuf::from("/root/one.md"),
+                PathBuf::from("/root/sub"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rename_moves_a_directory_tree() {
+        let fs = MemoryFs::new()
+            .with_file("/skills/old/SKILL.md", "a")
+            .with_file("/skills/old/scripts/run.sh", "b");
+        fs.rename(Path::new("/skills/old"), Path::new("/skills/new"))
+            .unwrap();
+
+        assert!(!fs.exists(Path::new("/skills/old")));
+        assert!(fs.is_dir(Path::new("/skills/new/scripts")));
+        assert_eq!(
+            fs.read_to_string(Path::new("/skills/new/scripts/run.sh"))
+                .unwrap(),
+            "b"
+        );
+        let err = fs
+            .rename(Path::new("/skills/new"), Path::new("/missing/dir"))
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn remove_deletes_a_directory_tree() {
+        let fs = MemoryFs::new()
+            .with_file("/cache/a/blob", "1234")
+            .with_file("/cache/b", "");
+        assert_eq!(fs.metadata(Path::new("/cache/a/blob")).unwrap().len, 4);
+        assert!(fs.metadata(Path::new("/cache/a")).unwrap().is_dir);
+
+        fs.remove(Path::new("/cache/a")).unwrap();
+        assert!(!fs.exists(Path::new("/cache/a/blob")));
+        assert_eq!(
+            fs.read_dir(Path::new("/cache")).unwrap(),
+            [PathBuf::from("/cache/b")]
+        );
+        assert!(fs.remove(Path::new("/cache/a")).is_err());
+    }
+
+    #[test]
+    fn cannot_write_over_directory() {
+        let fs = MemoryFs::new().with_dir("/dir");
+        assert!(fs.write(Path::new("/dir"), b"x").is_err());
+    }
+}
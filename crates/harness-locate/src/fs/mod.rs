@@ -0,0 +1,288 @@
+//! Filesystem abstraction.
+//!
+//! Harness resolution reads the filesystem through the [`Fs`] trait rather
+//! than calling [`std::fs`] directly. The default implementation is
+//! [`StdFs`]; a different one can be installed for the current thread with
+//! [`PathContext::with_fs`](crate::platform::PathContext::with_fs).
+//!
+//! Wrapping a filesystem in [`AuditFs`] records every path accessed, and why,
+//! into an [`AccessLog`], along with any encoding normalization applied to
+//! the files read.
+//!
+//! With the `test-util` feature enabled, [`MemoryFs`] provides an in-memory
+//! implementation for hermetic tests.
+
+use std::fmt::Debug;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use crate::text::{self, DecodedText};
+
+/// Size and modification time of a file or directory, from
+/// [`Fs::metadata`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileMetadata {
+    /// Size in bytes; zero for directories.
+    pub len: u64,
+    /// When the entry was last modified, if known.
+    pub modified: Option<SystemTime>,
+    /// Whether the entry is a directory.
+    pub is_dir: bool,
+}
+
+/// Minimal filesystem operations used by this crate.
+pub trait Fs: Debug + Send + Sync {
+    /// Returns `true` if a file or directory exists at `path`.
+    fn exists(&self, path: &Path) -> bool;
+
+    /// Returns `true` if `path` is a directory.
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// Reads the entire file at `path` as UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or is not valid UTF-8.
+    fn read_to_string(&self, path: &Path) -> io::Result<String>;
+
+    /// Reads the entire file at `path` as raw bytes.
+    ///
+    /// The default implementation delegates to [`Fs::read_to_string`], so
+    /// it only supports UTF-8 files; implementations should override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file does not exist or cannot be read.
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        self.read_to_string(path).map(String::into_bytes)
+    }
+
+    /// Reads the file at `path` and decodes it with [`text::decode`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not valid text.
+    fn read_text(&self, path: &Path) -> io::Result<DecodedText> {
+        text::decode(&self.read(path)?)
+    }
+
+    /// Writes `contents` to `path`, replacing any existing file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+
+    /// Appends `contents` to `path`, creating the file if needed.
+    ///
+    /// The default implementation reads the file and writes it back;
+    /// implementations should override it with a real append.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or written.
+    fn append(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let mut existing = match self.read(path) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        existing.extend_from_slice(contents);
+        self.write(path, &existing)
+    }
+
+    /// Creates `path` and all missing parent directories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory cannot be created.
+    fn create_dir_all(&self, path: &Path) -> io::Result<()>;
+
+    /// Lists the immediate children of the directory at `path`.
+    ///
+    /// Entries are returned as full paths, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` is not a readable directory.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+
+    /// Moves the file or directory at `from` to `to`, whose parent must
+    /// exist.
+    ///
+    /// The default implementation fails with
+    /// [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `from` does not exist or cannot be moved.
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        let _ = to;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("cannot move {}", from.display()),
+        ))
+    }
+
+    /// Returns the size and modification time of `path`.
+    ///
+    /// The default implementation fails with
+    /// [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` does not exist.
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("cannot stat {}", path.display()),
+        ))
+    }
+
+    /// Deletes the file at `path`, or the directory and everything in it.
+    ///
+    /// The default implementation fails with
+    /// [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` does not exist or cannot be deleted.
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("cannot delete {}", path.display()),
+        ))
+    }
+}
+
+/// [`Fs`] implementation backed by [`std::fs`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdFs;
+
+impl Fs for StdFs {
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        std::fs::read(path)
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        std::fs::write(path, contents)
+    }
+
+    fn append(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        use std::io::Write;
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(contents)
+    }
+
+    fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        std::fs::create_dir_all(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let mut entries = std::fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        entries.sort();
+        Ok(entries)
+    }
+
+    fn rename(&self, from: &Path, to: &Path) -> io::Result<()> {
+        std::fs::rename(from, to)
+    }
+
+    fn metadata(&self, path: &Path) -> io::Result<FileMetadata> {
+        let metadata = std::fs::metadata(path)?;
+        Ok(FileMetadata {
+            len: if metadata.is_dir() { 0 } else { metadata.len() },
+            modified: metadata.modified().ok(),
+            is_dir: metadata.is_dir(),
+        })
+    }
+
+    fn remove(&self, path: &Path) -> io::Result<()> {
+        if std::fs::symlink_metadata(path)?.is_dir() {
+            std::fs::remove_dir_all(path)
+        } else {
+            std::fs::remove_file(path)
+        }
+    }
+}
+
+/// Returns the filesystem in effect for the current thread.
+///
+/// This is the [`Fs`] of the active [`PathContext`](crate::platform::PathContext),
+/// or [`StdFs`] if none is set.
+#[must_use]
+pub fn current() -> Arc<dyn Fs> {
+    crate::platform::fs_override().unwrap_or_else(|| Arc::new(StdFs))
+}
+
+/// Checks whether `path` exists, attributing the access to `reason`.
+pub(crate) fn exists_for(path: &Path, reason: impl Into<Option<AccessReason>>) -> bool {
+    with_reason(reason, || current().exists(path))
+}
+
+/// Reads and decodes `path` with the current filesystem.
+///
+/// Byte order marks, CRLF line endings and UTF-16 are normalized; see
+/// [`text::decode`].
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not valid text.
+pub fn read_text(path: &Path) -> io::Result<DecodedText> {
+    current().read_text(path)
+}
+
+/// Reads and decodes `path`, attributing the access to `reason`.
+pub(crate) fn read_text_for(
+    path: &Path,
+    reason: impl Into<Option<AccessReason>>,
+) -> io::Result<DecodedText> {
+    with_reason(reason, || current().read_text(path))
+}
+
+/// Reads and decodes `path` to a string, attributing the access to
+/// `reason`.
+pub(crate) fn read_to_string_for(
+    path: &Path,
+    reason: impl Into<Option<AccessReason>>,
+) -> io::Result<String> {
+    read_text_for(path, reason).map(|decoded| decoded.text)
+}
+
+/// Lists `path`, attributing the access to `reason`.
+#[cfg(feature = "validation")]
+pub(crate) fn read_dir_for(
+    path: &Path,
+    reason: impl Into<Option<AccessReason>>,
+) -> io::Result<Vec<PathBuf>> {
+    with_reason(reason, || current().read_dir(path))
+}
+
+mod audit;
+use audit::with_reason;
+pub use audit::{AccessLog, AccessOp, AccessOutcome, AccessReason, AccessRecord, AuditFs};
+
+#[cfg(any(test, feature = "test-util"))]
+mod memory;
+#[cfg(any(test, feature = "test-util"))]
+pub use memory::MemoryFs;
@@ -0,0 +1,302 @@
+//! Which agent files in a project belong in version control.
+//!
+//! Skills, commands and shared MCP configs are meant to be committed so the
+//! whole team gets them. Personal settings and installed dependencies are
+//! not. [`gitignore_recommendations`] lists both kinds for a harness,
+//! [`check_gitignore`] compares them with a project's `.gitignore`, and
+//! [`GitignoreReport::apply`] appends the missing rules.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::gitignore::check_gitignore;
+//! use harness_locate::{Harness, HarnessKind};
+//!
+//! let harnesses = [Harness::new(HarnessKind::ClaudeCode)];
+//! let report = check_gitignore("/my/project".as_ref(), &harnesses)?;
+//! for entry in &report.committed_but_ignored {
+//!     println!("{} is ignored but should be committed", entry.pattern);
+//! }
+//! report.apply()?;
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::harness::Harness;
+use crate::ignore::IgnoreRules;
+use crate::types::{HarnessKind, ResourceKind, Scope};
+
+/// Comment heading the rules [`GitignoreReport::apply`] appends.
+const HEADER: &str = "# Local agent files";
+
+/// Whether a path belongs in version control.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GitignoreCategory {
+    /// Shared with the team: skills, commands, agents and project MCP
+    /// configs.
+    Commit,
+    /// Personal or generated: local settings and installed dependencies.
+    Ignore,
+}
+
+/// A project path and whether to commit it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitignoreEntry {
+    /// Path relative to the project root, with a trailing `/` for
+    /// directories. Usable as a `.gitignore` rule.
+    pub pattern: String,
+    /// Whether to commit or ignore it.
+    pub category: GitignoreCategory,
+    /// What the path holds.
+    pub description: String,
+}
+
+impl GitignoreEntry {
+    fn new(pattern: impl Into<String>, category: GitignoreCategory, description: &str) -> Self {
+        Self {
+            pattern: pattern.into(),
+            category,
+            description: description.to_string(),
+        }
+    }
+
+    /// Returns `true` if `rules` ignore the path or one of its parents.
+    fn is_ignored_by(&self, rules: &IgnoreRules) -> bool {
+        let is_dir = self.pattern.ends_with('/');
+        let path = Path::new(self.pattern.trim_end_matches('/'));
+        path.ancestors()
+            .filter(|p| !p.as_os_str().is_empty())
+            .any(|p| rules.is_ignored(p, is_dir || p != path))
+    }
+}
+
+/// Returns the project paths of `harness` to commit and to ignore.
+///
+/// Committed paths are the project skill, command and agent directories
+/// and MCP config the harness reads. Ignored paths are the personal files
+/// each harness documents, such as Claude Code's
+/// `.claude/settings.local.json`.
+///
+/// # Errors
+///
+/// Returns an error if a project path cannot be resolved.
+pub fn gitignore_recommendations(harness: &Harness) -> Result<Vec<GitignoreEntry>> {
+    let scope = Scope::Project(PathBuf::new());
+    let relative = |path: &Path| path.to_string_lossy().replace('\\', "/");
+    let mut entries = Vec::new();
+
+    for (kind, description) in [
+        (ResourceKind::Skills, "skills"),
+        (ResourceKind::Commands, "custom commands"),
+        (ResourceKind::Agents, "agent definitions"),
+    ] {
+        match harness.resource(kind, &scope) {
+            Ok(Some(dir)) if !dir.path.as_os_str().is_empty() => entries.push(GitignoreEntry::new(
+                format!("{}/", relative(&dir.path)),
+                GitignoreCategory::Commit,
+                description,
+            )),
+            Ok(_) | Err(Error::UnsupportedScope { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    match harness.mcp(&scope) {
+        Ok(Some(config)) => entries.push(GitignoreEntry::new(
+            relative(&config.file),
+            GitignoreCategory::Commit,
+            "shared MCP servers",
+        )),
+        Ok(None) | Err(Error::UnsupportedScope { .. }) => {}
+        Err(e) => return Err(e),
+    }
+
+    let ignored: &[(&str, &str)] = match harness.kind() {
+        Some(HarnessKind::ClaudeCode) => &[
+            (".claude/settings.local.json", "personal settings"),
+            ("CLAUDE.local.md", "personal instructions"),
+        ],
+        Some(HarnessKind::OpenCode) => {
+            &[(".opencode/node_modules/", "installed plugin dependencies")]
+        }
+        _ => &[],
+    };
+    entries.extend(ignored.iter().map(|(pattern, description)| {
+        GitignoreEntry::new(*pattern, GitignoreCategory::Ignore, description)
+    }));
+    Ok(entries)
+}
+
+/// How a project's `.gitignore` compares to the recommendations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitignoreReport {
+    /// The `.gitignore` checked.
+    pub path: PathBuf,
+    /// Paths that should be ignored but are not.
+    pub missing: Vec<GitignoreEntry>,
+    /// Paths that should be committed but are ignored.
+    pub committed_but_ignored: Vec<GitignoreEntry>,
+}
+
+impl GitignoreReport {
+    /// Returns `true` if the `.gitignore` follows every recommendation.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.committed_but_ignored.is_empty()
+    }
+
+    /// Returns the text to append to the `.gitignore` to ignore the
+    /// [`missing`](Self::missing) paths, or `None` if none are missing.
+    ///
+    /// Paths that are wrongly ignored are left to the user, since the
+    /// rule ignoring them may cover other files too.
+    #[must_use]
+    pub fn patch(&self) -> Option<String> {
+        if self.missing.is_empty() {
+            return None;
+        }
+        let mut text = format!("\n{HEADER}\n");
+        for entry in &self.missing {
+            text.push_str(&entry.pattern);
+            text.push('\n');
+        }
+        Some(text)
+    }
+
+    /// Appends the [`patch`](Self::patch) to the `.gitignore`, creating it
+    /// if needed, and records the change in the [journal](crate::journal)
+    /// as `gitignore.append`. Returns `false` if there was nothing to add.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn apply(&self) -> Result<bool> {
+        let Some(patch) = self.patch() else {
+            return Ok(false);
+        };
+        let fs = crate::fs::current();
+        let current = if fs.exists(&self.path) {
+            Some(crate::fs::read_text(&self.path)?.text)
+        } else {
+            None
+        };
+        let patch = match &current {
+            Some(current) if !current.is_empty() && !current.ends_with('\n') => {
+                format!("\n{patch}")
+            }
+            Some(_) => patch,
+            None => patch.trim_start().to_string(),
+        };
+        fs.append(&self.path, patch.as_bytes())?;
+        let before = current.unwrap_or_default();
+        let after = format!("{before}{patch}");
+        crate::journal::record("gitignore.append", &self.path, &before, &after);
+        Ok(true)
+    }
+}
+
+/// Checks the `.gitignore` at `root` against the recommendations for
+/// `harnesses`.
+///
+/// Only the root `.gitignore` is read. A missing file ignores nothing.
+///
+/// # Errors
+///
+/// Returns an error if a project path cannot be resolved or the
+/// `.gitignore` cannot be read.
+pub fn check_gitignore(root: &Path, harnesses: &[Harness]) -> Result<GitignoreReport> {
+    let path = root.join(".gitignore");
+    let content = match crate::fs::read_text(&path) {
+        Ok(decoded) => decoded.text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let rules = IgnoreRules::parse(&content);
+
+    let mut report = GitignoreReport {
+        path,
+        missing: Vec::new(),
+        committed_but_ignored: Vec::new(),
+    };
+    for harness in harnesses {
+        for entry in gitignore_recommendations(harness)? {
+            let list = match (entry.category, entry.is_ignored_by(&rules)) {
+                (GitignoreCategory::Ignore, false) => &mut report.missing,
+                (GitignoreCategory::Commit, true) => &mut report.committed_but_ignored,
+                _ => continue,
+            };
+            if list.iter().all(|e| e.pattern != entry.pattern) {
+                list.push(entry);
+            }
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    fn patterns(entries: &[GitignoreEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.pattern.as_str()).collect()
+    }
+
+    #[test]
+    fn recommends_paths_per_harness() {
+        let claude = gitignore_recommendations(&Harness::new(HarnessKind::ClaudeCode)).unwrap();
+        let (commit, ignore): (Vec<_>, Vec<_>) = claude
+            .into_iter()
+            .partition(|e| e.category == GitignoreCategory::Commit);
+        assert!(patterns(&commit).contains(&".claude/skills/"));
+        assert!(patterns(&commit).contains(&".mcp.json"));
+        assert_eq!(
+            patterns(&ignore),
+            [".claude/settings.local.json", "CLAUDE.local.md"]
+        );
+    }
+
+    #[test]
+    fn checks_and_patches_the_project_gitignore() {
+        let fs = MemoryFs::new().with_file("/repo/.gitignore", "target/\n.claude/\n");
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let harnesses = [
+            Harness::new(HarnessKind::ClaudeCode),
+            Harness::new(HarnessKind::OpenCode),
+        ];
+
+        let report = ctx
+            .scope(|| check_gitignore(Path::new("/repo"), &harnesses))
+            .unwrap();
+        assert_eq!(
+            patterns(&report.missing),
+            ["CLAUDE.local.md", ".opencode/node_modules/"]
+        );
+        assert!(patterns(&report.committed_but_ignored).contains(&".claude/commands/"));
+
+        assert!(ctx.scope(|| report.apply()).unwrap());
+        let content = ctx
+            .scope(|| crate::fs::read_text(Path::new("/repo/.gitignore")))
+            .unwrap()
+            .text;
+        assert_eq!(
+            content,
+            "target/\n.claude/\n\n# Local agent files\nCLAUDE.local.md\n.opencode/node_modules/\n"
+        );
+        let journal = ctx.scope(crate::journal::read_journal).unwrap();
+        assert_eq!(journal.len(), 1);
+        assert_eq!(journal[0].operation, "gitignore.append");
+        assert_eq!(journal[0].file, Path::new("/repo/.gitignore"));
+        assert_eq!(
+            journal[0].diff_hash,
+            crate::journal::diff_hash("target/\n.claude/\n", &content)
+        );
+        let report = ctx
+            .scope(|| check_gitignore(Path::new("/repo"), &harnesses))
+            .unwrap();
+        assert!(report.missing.is_empty());
+        assert!(report.patch().is_none());
+    }
+}
@@ -0,0 +1,192 @@
+//! Glob patterns for resource file names.
+//!
+//! Patterns match a single path component and support `*`, `?`, `[...]`
+//! character classes and `{a,b}` alternation.
+
+/// A compiled file name pattern.
+#[derive(Debug, Clone)]
+pub(crate) struct Glob {
+    /// One token sequence per expansion of the `{a,b}` groups.
+    alternatives: Vec<Vec<Token>>,
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Literal(char),
+    /// `?`, any character but `/`.
+    Any,
+    /// `*`, any run of characters without `/`.
+    Star,
+    /// `[...]`, with inclusive ranges and whether it is negated.
+    Class(Vec<(char, char)>, bool),
+}
+
+impl Glob {
+    /// Compiles `pattern`.
+    ///
+    /// Malformed constructs, such as an unclosed `{` or `[`, match
+    /// literally.
+    pub(crate) fn new(pattern: &str) -> Self {
+        let alternatives =
+            compile(pattern).unwrap_or_else(|| vec![pattern.chars().map(Token::Literal).collect()]);
+        Self { alternatives }
+    }
+
+    /// Returns `true` if `name` matches the whole pattern.
+    pub(crate) fn matches(&self, name: &str) -> bool {
+        let name: Vec<char> = name.chars().collect();
+        self.alternatives
+            .iter()
+            .any(|tokens| matches_tokens(tokens, &name))
+    }
+}
+
+/// Expands alternation and tokenizes each expansion, or returns `None` if
+/// a character class has a reversed range.
+fn compile(pattern: &str) -> Option<Vec<Vec<Token>>> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut alternatives = vec![Vec::new()];
+    // Alternatives of the open `{...}` group, each a token sequence.
+    let mut group: Option<Vec<Vec<Token>>> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let token = match chars[i] {
+            '*' => Token::Star,
+            '?' => Token::Any,
+            '{' if group.is_none() && chars[i..].contains(&'}') => {
+                group = Some(vec![Vec::new()]);
+                i += 1;
+                continue;
+            }
+            '}' if group.is_some() => {
+                let options = group.take().unwrap_or_default();
+                alternatives = alternatives
+                    .iter()
+                    .flat_map(|prefix| {
+                        options.iter().map(move |option| {
+                            let mut tokens = prefix.clone();
+                            tokens.extend(option.iter().cloned());
+                            tokens
+                        })
+                    })
+                    .collect();
+                i += 1;
+                continue;
+            }
+            ',' if group.is_some() => {
+                if let Some(options) = &mut group {
+                    options.push(Vec::new());
+                }
+                i += 1;
+                continue;
+            }
+            '[' => match chars[i + 1..].iter().position(|&c| c == ']') {
+                Some(len) if len > 0 => {
+                    let class = &chars[i + 1..i + 1 + len];
+                    let (class, negated) = match class.split_first() {
+                        Some(('!', rest)) => (rest, true),
+                        _ => (class, false),
+                    };
+                    i += len + 1;
+                    Token::Class(compile_class(class)?, negated)
+                }
+                _ => Token::Literal('['),
+            },
+            c => Token::Literal(c),
+        };
+        match &mut group {
+            Some(options) => options
+                .last_mut()
+                .expect("group is never empty")
+                .push(token),
+            None => alternatives
+                .iter_mut()
+                .for_each(|tokens| tokens.push(token.clone())),
+        }
+        i += 1;
+    }
+    Some(alternatives)
+}
+
+fn compile_class(class: &[char]) -> Option<Vec<(char, char)>> {
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == '-' {
+            if class[i] > class[i + 2] {
+                return None;
+            }
+            ranges.push((class[i], class[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((class[i], class[i]));
+            i += 1;
+        }
+    }
+    Some(ranges)
+}
+
+fn matches_tokens(tokens: &[Token], name: &[char]) -> bool {
+    match tokens.split_first() {
+        None => name.is_empty(),
+        Some((Token::Star, rest)) => {
+            let run = name.iter().take_while(|&&c| c != '/').count();
+            (0..=run).any(|skip| matches_tokens(rest, &name[skip..]))
+        }
+        Some((token, rest)) => name.split_first().is_some_and(|(&c, name)| {
+            let hit = match token {
+                Token::Literal(literal) => c == *literal,
+                Token::Any => c != '/',
+                Token::Class(ranges, negated) => {
+                    ranges.iter().any(|&(lo, hi)| (lo..=hi).contains(&c)) != *negated
+                }
+                Token::Star => unreachable!("handled above"),
+            };
+            hit && matches_tokens(rest, name)
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        let glob = Glob::new("*.md");
+        assert!(glob.matches("review.md"));
+        assert!(glob.matches(".md"));
+        assert!(!glob.matches("review.mdx"));
+        assert!(!glob.matches("nested/review.md"));
+        assert!(Glob::new("v?.txt").matches("v1.txt"));
+        assert!(Glob::new("*").matches("anything"));
+    }
+
+    #[test]
+    fn alternation() {
+        let glob = Glob::new("*.{js,ts}");
+        assert!(glob.matches("plugin.js"));
+        assert!(glob.matches("plugin.ts"));
+        assert!(!glob.matches("plugin.tsx"));
+        assert!(Glob::new("*.{yaml,json}").matches("recipe.yaml"));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(Glob::new("[ab]*.md").matches("a1.md"));
+        assert!(!Glob::new("[ab]*.md").matches("c1.md"));
+        assert!(Glob::new("[!ab]*.md").matches("c1.md"));
+        assert!(Glob::new("v[0-9].md").matches("v7.md"));
+        assert!(!Glob::new("v[0-9].md").matches("vx.md"));
+    }
+
+    #[test]
+    fn malformed_patterns_match_literally() {
+        assert!(Glob::new("{open.md").matches("{open.md"));
+        assert!(Glob::new("[.md").matches("[.md"));
+        assert!(Glob::new("a.b").matches("a.b"));
+        assert!(!Glob::new("a.b").matches("axb"));
+        assert!(Glob::new("[z-a].md").matches("[z-a].md"));
+        assert!(Glob::new("{a,b}}").matches("a}"));
+    }
+}
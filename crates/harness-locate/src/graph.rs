@@ -0,0 +1,638 @@
+//! Dependency graph of agents, commands, skills, and MCP servers.
+//!
+//! Claude Code agents and commands reference other resources by name:
+//!
+//! - `skills:` frontmatter lists skills an agent preloads
+//! - `tools:` / `allowed-tools:` entries such as `mcp__github__create_issue`,
+//!   `Task(reviewer)`, or `Skill(pdf)` reference MCP servers, subagents,
+//!   and skills
+//! - `@agent-<name>` mentions in the body invoke a subagent
+//!
+//! [`DependencyGraph::from_scope`] collects these references for every
+//! resource in a scope so that dangling references and cycles can be
+//! reported, and the graph exported as DOT or JSON.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+use crate::fs::{read_dir_for, read_to_string_for};
+use crate::glob::Glob;
+use crate::harness::Harness;
+use crate::ignore::IgnoreRules;
+use crate::skill::parse_frontmatter;
+use crate::types::{DirectoryStructure, FileFormat, ResourceKind, Scope};
+
+static AGENT_MENTION_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"@agent-([A-Za-z0-9][A-Za-z0-9_-]*)").unwrap());
+
+/// The kind of resource a graph node represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeKind {
+    /// A subagent definition.
+    Agent,
+    /// A slash command.
+    Command,
+    /// A skill directory.
+    Skill,
+    /// A configured MCP server.
+    McpServer,
+}
+
+impl NodeKind {
+    /// Returns the kind as a lowercase string.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Agent => "agent",
+            Self::Command => "command",
+            Self::Skill => "skill",
+            Self::McpServer => "mcp_server",
+        }
+    }
+}
+
+/// Identifies a resource by kind and name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct NodeId {
+    /// The kind of resource.
+    pub kind: NodeKind,
+    /// The resource name, e.g. the agent file stem or MCP server key.
+    pub name: String,
+}
+
+impl NodeId {
+    /// Creates a node identifier.
+    #[must_use]
+    pub fn new(kind: NodeKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+        }
+    }
+}
+
+impl fmt::Display for NodeId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind.as_str(), self.name)
+    }
+}
+
+/// A reference from one resource to another.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Edge {
+    /// The referencing resource.
+    pub from: NodeId,
+    /// The referenced resource.
+    pub to: NodeId,
+}
+
+/// Directed graph of references between resources in a scope.
+///
+/// Nodes are resources that exist; edges may point at resources that
+/// don't, which [`DependencyGraph::dangling`] reports.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    nodes: BTreeMap<NodeId, Option<PathBuf>>,
+    edges: BTreeSet<Edge>,
+}
+
+impl DependencyGraph {
+    /// Creates an empty graph.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds the graph for every agent, command, skill, and MCP server
+    /// the harness has in `scope`.
+    ///
+    /// Resource directories and MCP config files that don't exist are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a resource directory cannot be resolved or read,
+    /// or if a resource file or the MCP config cannot be parsed.
+    pub fn from_scope(harness: &Harness, scope: &Scope) -> Result<Self> {
+        let mut graph = Self::new();
+
+        let directories = [
+            (
+                harness.agents(scope)?,
+                ResourceKind::Agents,
+                NodeKind::Agent,
+            ),
+            (
+                harness.commands(scope)?,
+                ResourceKind::Commands,
+                NodeKind::Command,
+            ),
+            (
+                harness.skills(scope)?,
+                ResourceKind::Skills,
+                NodeKind::Skill,
+            ),
+        ];
+        for (resource, resource_kind, node_kind) in directories {
+            let Some(resource) = resource.filter(|r| r.exists) else {
+                continue;
+            };
+            if !matches!(
+                resource.file_format,
+                FileFormat::Markdown | FileFormat::MarkdownWithFrontmatter
+            ) {
+                continue;
+            }
+            let reason = harness.access_reason(Some(resource_kind), "graph");
+            let ignored = IgnoreRules::load(&resource.path)?;
+            let nested = matches!(resource.structure, DirectoryStructure::Nested { .. });
+            for entry in read_dir_for(&resource.path, reason)? {
+                let relative = entry.strip_prefix(&resource.path).unwrap_or(&entry);
+                if ignored.is_ignored(relative, nested) {
+                    continue;
+                }
+                let Some((name, file)) = resource_file(&entry, &resource.structure) else {
+                    continue;
+                };
+                let content = match read_to_string_for(&file, reason) {
+                    Ok(content) => content,
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                    Err(e) => return Err(e.into()),
+                };
+                graph.add_document(NodeId::new(node_kind, name), Some(file), &content)?;
+            }
+        }
+
+        if let Some(mcp) = harness.mcp(scope)?.filter(|m| m.file_exists) {
+            let reason = harness.access_reason(None, "graph");
+            let content = read_to_string_for(&mcp.file, reason)?;
+            let config: serde_json::Value = match mcp.format {
+                FileFormat::Yaml => serde_yaml::from_str(&content)?,
+                FileFormat::Jsonc => serde_json::from_str(&crate::jsonc::strip(&content))?,
+                _ => serde_json::from_str(&content)?,
+            };
+            if let Some(servers) = config.pointer(&mcp.key_path).and_then(|v| v.as_object()) {
+                for name in servers.keys() {
+                    graph.add_node(
+                        NodeId::new(NodeKind::McpServer, name.as_str()),
+                        Some(mcp.file.clone()),
+                    );
+                }
+            }
+        }
+
+        Ok(graph)
+    }
+
+    /// Adds a resource node, optionally recording the file it was read from.
+    pub fn add_node(&mut self, id: NodeId, path: Option<PathBuf>) {
+        self.nodes.insert(id, path);
+    }
+
+    /// Adds a reference from `from` to `to`.
+    ///
+    /// Neither end needs to have been added as a node.
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId) {
+        self.edges.insert(Edge { from, to });
+    }
+
+    /// Adds a resource node and an edge for every reference in its content.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::YamlParse` if the content has invalid frontmatter.
+    pub fn add_document(&mut self, id: NodeId, path: Option<PathBuf>, content: &str) -> Result<()> {
+        for reference in extract_references(content)? {
+            self.add_edge(id.clone(), reference);
+        }
+        self.add_node(id, path);
+        Ok(())
+    }
+
+    /// Returns `true` if a resource with this identifier exists.
+    #[must_use]
+    pub fn contains(&self, id: &NodeId) -> bool {
+        self.nodes.contains_key(id)
+    }
+
+    /// Returns all resource nodes in sorted order.
+    pub fn nodes(&self) -> impl Iterator<Item = &NodeId> {
+        self.nodes.keys()
+    }
+
+    /// Returns the file a resource was read from, if known.
+    #[must_use]
+    pub fn path(&self, id: &NodeId) -> Option<&Path> {
+        self.nodes.get(id)?.as_deref()
+    }
+
+    /// Returns all references in sorted order.
+    pub fn edges(&self) -> impl Iterator<Item = &Edge> {
+        self.edges.iter()
+    }
+
+    /// Returns the resources directly referenced by `id`.
+    #[must_use]
+    pub fn dependencies(&self, id: &NodeId) -> Vec<&NodeId> {
+        self.edges
+            .iter()
+            .filter(|e| &e.from == id)
+            .map(|e| &e.to)
+            .collect()
+    }
+
+    /// Returns references whose target resource doesn't exist.
+    #[must_use]
+    pub fn dangling(&self) -> Vec<&Edge> {
+        self.edges
+            .iter()
+            .filter(|e| !self.nodes.contains_key(&e.to))
+            .collect()
+    }
+
+    /// Returns every reference cycle, each as the resources involved.
+    ///
+    /// A resource that references itself is reported as a cycle of one.
+    #[must_use]
+    pub fn cycles(&self) -> Vec<Vec<NodeId>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: 0,
+            indices: BTreeMap::new(),
+            lowlinks: BTreeMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            components: Vec::new(),
+        };
+        let mut starts: BTreeSet<&NodeId> = self.nodes.keys().collect();
+        starts.extend(self.edges.iter().map(|e| &e.from));
+        for id in starts {
+            if !tarjan.indices.contains_key(id) {
+                tarjan.visit(id);
+            }
+        }
+
+        tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || {
+                    let id = component[0];
+                    self.edges.contains(&Edge {
+                        from: id.clone(),
+                        to: id.clone(),
+                    })
+                }
+            })
+            .map(|mut component| {
+                component.sort();
+                component.into_iter().cloned().collect()
+            })
+            .collect()
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    ///
+    /// Dangling references are drawn as dashed red nodes.
+    #[must_use]
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph dependencies {\n");
+        for id in self.nodes.keys() {
+            out.push_str(&format!(
+                "  \"{}\" [label=\"{}\", shape={}];\n",
+                escape_dot(&id.to_string()),
+                escape_dot(&id.name),
+                dot_shape(id.kind)
+            ));
+        }
+        let missing: BTreeSet<&NodeId> = self.dangling().into_iter().map(|e| &e.to).collect();
+        for id in missing {
+            out.push_str(&format!(
+                "  \"{}\" [label=\"{}\", shape={}, style=dashed, color=red];\n",
+                escape_dot(&id.to_string()),
+                escape_dot(&id.name),
+                dot_shape(id.kind)
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "  \"{}\" -> \"{}\";\n",
+                escape_dot(&edge.from.to_string()),
+                escape_dot(&edge.to.to_string())
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Returns the graph as JSON with `nodes`, `edges`, `dangling`, and
+    /// `cycles` arrays.
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        let nodes: Vec<_> = self
+            .nodes
+            .iter()
+            .map(|(id, path)| {
+                serde_json::json!({
+                    "kind": id.kind,
+                    "name": id.name,
+                    "path": path,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "nodes": nodes,
+            "edges": self.edges,
+            "dangling": self.dangling(),
+            "cycles": self.cycles(),
+        })
+    }
+}
+
+/// Extracts the resources referenced by an agent, command, or skill file.
+///
+/// # Errors
+///
+/// Returns `Error::YamlParse` if the content has invalid frontmatter.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::graph::{NodeId, NodeKind, extract_references};
+///
+/// let content = "---\ntools: Read, mcp__github__create_issue\nskills: pdf\n---\nAsk @agent-reviewer.";
+/// let refs = extract_references(content).unwrap();
+///
+/// assert!(refs.contains(&NodeId::new(NodeKind::McpServer, "github")));
+/// assert!(refs.contains(&NodeId::new(NodeKind::Skill, "pdf")));
+/// assert!(refs.contains(&NodeId::new(NodeKind::Agent, "reviewer")));
+/// ```
+pub fn extract_references(content: &str) -> Result<Vec<NodeId>> {
+    let frontmatter = parse_frontmatter(content)?;
+    let mut refs = BTreeSet::new();
+
+    if let Some(yaml) = &frontmatter.yaml {
+        for skill in yaml_list(yaml.get("skills")) {
+            refs.insert(NodeId::new(NodeKind::Skill, skill));
+        }
+        let tools = yaml_list(yaml.get("tools"))
+            .into_iter()
+            .chain(yaml_list(yaml.get("allowed-tools")));
+        for tool in tools {
+            if let Some(id) = tool_reference(&tool) {
+                refs.insert(id);
+            }
+        }
+    }
+
+    for capture in AGENT_MENTION_RE.captures_iter(frontmatter.body) {
+        let name = capture[1].trim_end_matches(['-', '_']);
+        refs.insert(NodeId::new(NodeKind::Agent, name));
+    }
+
+    Ok(refs.into_iter().collect())
+}
+
+/// Reads a frontmatter field given either as a YAML list or a comma- or
+/// whitespace-separated string.
+fn yaml_list(value: Option<&serde_yaml::Value>) -> Vec<String> {
+    let split = |s: &str| -> Vec<String> {
+        s.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .map(String::from)
+            .collect()
+    };
+    match value {
+        Some(serde_yaml::Value::String(s)) => split(s),
+        Some(serde_yaml::Value::Sequence(items)) => items
+            .iter()
+            .filter_map(|item| item.as_str())
+            .flat_map(split)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn tool_reference(tool: &str) -> Option<NodeId> {
+    if let Some(rest) = tool.strip_prefix("mcp__") {
+        let server = rest.split("__").next().filter(|s| !s.is_empty())?;
+        return Some(NodeId::new(NodeKind::McpServer, server));
+    }
+    let (name, arg) = tool.strip_suffix(')')?.split_once('(')?;
+    let kind = match name {
+        "Task" => NodeKind::Agent,
+        "Skill" => NodeKind::Skill,
+        _ => return None,
+    };
+    let arg = arg.trim();
+    (!arg.is_empty() && !arg.contains('*')).then(|| NodeId::new(kind, arg))
+}
+
+/// Resolves a directory entry to a resource name and the file to read.
+fn resource_file(entry: &Path, structure: &DirectoryStructure) -> Option<(String, PathBuf)> {
+    match structure {
+        DirectoryStructure::Flat { file_pattern } => {
+            let file_name = entry.file_name()?.to_str()?;
+            if !Glob::new(file_pattern).matches(file_name) {
+                return None;
+            }
+            let name = entry.file_stem()?.to_str()?.to_string();
+            Some((name, entry.to_path_buf()))
+        }
+        DirectoryStructure::Nested { file_name, .. } => {
+            let name = entry.file_name()?.to_str()?.to_string();
+            Some((name, entry.join(file_name)))
+        }
+    }
+}
+
+fn dot_shape(kind: NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Agent => "box",
+        NodeKind::Command => "cds",
+        NodeKind::Skill => "ellipse",
+        NodeKind::McpServer => "component",
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+struct Tarjan<'a> {
+    graph: &'a DependencyGraph,
+    index: usize,
+    indices: BTreeMap<&'a NodeId, usize>,
+    lowlinks: BTreeMap<&'a NodeId, usize>,
+    stack: Vec<&'a NodeId>,
+    on_stack: BTreeSet<&'a NodeId>,
+    components: Vec<Vec<&'a NodeId>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn visit(&mut self, id: &'a NodeId) {
+        self.indices.insert(id, self.index);
+        self.lowlinks.insert(id, self.index);
+        self.index += 1;
+        self.stack.push(id);
+        self.on_stack.insert(id);
+
+        for edge in self.graph.edges.iter().filter(|e| &e.from == id) {
+            let next = &edge.to;
+            if !self.indices.contains_key(next) {
+                self.visit(next);
+                let low = self.lowlinks[id].min(self.lowlinks[next]);
+                self.lowlinks.insert(id, low);
+            } else if self.on_stack.contains(next) {
+                let low = self.lowlinks[id].min(self.indices[next]);
+                self.lowlinks.insert(id, low);
+            }
+        }
+
+        if self.lowlinks[id] == self.indices[id] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(member);
+                component.push(member);
+                if member == id {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+    use crate::types::HarnessKind;
+
+    fn agent(name: &str) -> NodeId {
+        NodeId::new(NodeKind::Agent, name)
+    }
+
+    fn skill(name: &str) -> NodeId {
+        NodeId::new(NodeKind::Skill, name)
+    }
+
+    #[test]
+    fn extracts_frontmatter_and_body_references() {
+        let content = "---\nname: planner\ntools:\n  - Read\n  - Task(reviewer)\n  - mcp__github__create_issue\nallowed-tools: Skill(pdf), mcp__linear\nskills: [docx]\n---\nHand off to @agent-writer.\n";
+        let refs = extract_references(content).unwrap();
+
+        assert_eq!(
+            refs,
+            vec![
+                agent("reviewer"),
+                agent("writer"),
+                skill("docx"),
+                skill("pdf"),
+                NodeId::new(NodeKind::McpServer, "github"),
+                NodeId::new(NodeKind::McpServer, "linear"),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_wildcard_and_plain_tools() {
+        let refs = extract_references("---\ntools: Bash, Task(*), Skill()\n---\n").unwrap();
+        assert!(refs.is_empty());
+    }
+
+    #[test]
+    fn reports_dangling_references() {
+        let mut graph = DependencyGraph::new();
+        graph
+            .add_document(agent("a"), None, "Delegate to @agent-b and @agent-missing.")
+            .unwrap();
+        graph.add_node(agent("b"), None);
+
+        let dangling = graph.dangling();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].to, agent("missing"));
+    }
+
+    #[test]
+    fn detects_cycles_and_self_references() {
+        let mut graph = DependencyGraph::new();
+        graph.add_edge(agent("a"), agent("b"));
+        graph.add_edge(agent("b"), agent("c"));
+        graph.add_edge(agent("c"), agent("a"));
+        graph.add_edge(agent("c"), skill("leaf"));
+        graph.add_edge(agent("self"), agent("self"));
+
+        let cycles = graph.cycles();
+        assert_eq!(cycles.len(), 2);
+        assert!(cycles.contains(&vec![agent("a"), agent("b"), agent("c")]));
+        assert!(cycles.contains(&vec![agent("self")]));
+    }
+
+    #[test]
+    fn exports_dot_and_json() {
+        let mut graph = DependencyGraph::new();
+        graph
+            .add_document(agent("a"), None, "---\nskills: pdf\n---\n")
+            .unwrap();
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("\"agent:a\" -> \"skill:pdf\";"));
+        assert!(dot.contains("\"skill:pdf\" [label=\"pdf\", shape=ellipse, style=dashed"));
+
+        let json = graph.to_json();
+        assert_eq!(json["nodes"][0]["kind"], "agent");
+        assert_eq!(json["edges"][0]["to"]["name"], "pdf");
+        assert_eq!(json["dangling"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn builds_graph_from_claude_code_project_scope() {
+        let root = PathBuf::from("/project");
+        let fs = MemoryFs::new()
+            .with_file(
+                "/project/.claude/agents/planner.md",
+                "---\nname: planner\nskills: research\ntools: mcp__github__search\n---\nUse @agent-ghost.",
+            )
+            .with_file("/project/.claude/commands/ship.md", "Run @agent-planner.")
+            .with_file(
+                "/project/.claude/skills/research/SKILL.md",
+                "---\nname: research\n---\n",
+            )
+            .with_file("/project/.mcp.json", r#"{"mcpServers": {"github": {}}}"#);
+
+        let graph = PathContext::fake("/fake").with_fs(fs).scope(|| {
+            DependencyGraph::from_scope(
+                &Harness::new(HarnessKind::ClaudeCode),
+                &Scope::Project(root),
+            )
+        });
+        let graph = graph.unwrap();
+
+        assert!(graph.contains(&NodeId::new(NodeKind::Command, "ship")));
+        assert!(graph.contains(&NodeId::new(NodeKind::McpServer, "github")));
+        assert_eq!(
+            graph.dependencies(&agent("planner")),
+            vec![
+                &agent("ghost"),
+                &skill("research"),
+                &NodeId::new(NodeKind::McpServer, "github")
+            ]
+        );
+        let dangling: Vec<_> = graph.dangling().into_iter().map(|e| &e.to).collect();
+        assert_eq!(dangling, vec![&agent("ghost")]);
+        assert_eq!(
+            graph.path(&skill("research")),
+            Some(Path::new("/project/.claude/skills/research/SKILL.md"))
+        );
+    }
+}
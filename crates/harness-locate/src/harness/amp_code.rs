@@ -5,24 +5,60 @@
 //! - **Project**: Not supported (AMP has no project-scoped config directory)
 //!
 //! Note: Skills are shared with Goose at `~/.config/agents/skills/`.
+//! Project commands and agents live beside them under `.agents/`.
 
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use super::backend::{self, HarnessBackend};
 use crate::error::{Error, Result};
-use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use crate::fs::AccessReason;
+use crate::mcp::{HttpMcpServer, McpCapabilities, McpServer, SseMcpServer, StdioMcpServer};
 use crate::platform;
-use crate::types::{EnvValue, HarnessKind, Scope};
+use crate::types::{
+    ConfigResource, DirectoryResource, EnvValue, FileFormat, HarnessKind, ResourceKind, Scope,
+};
+#[cfg(feature = "validation")]
+use crate::validation::ValidationIssue;
+use crate::validation::{CommandCapabilities, SkillCapabilities};
+
+/// Rules file names in the order AMP looks for them; the first one that
+/// exists is used.
+pub const RULES_FILES: &[&str] = &["AGENTS.md", "AGENT.md", "CLAUDE.md"];
 
 /// Returns the global AMP Code configuration directory.
 ///
-/// Returns `~/.config/amp/`.
+/// Returns `~/.config/amp/` (`%USERPROFILE%\.config\amp\` on Windows).
 ///
 /// # Errors
 ///
 /// Returns an error if the home directory cannot be determined.
 pub fn global_config_dir() -> Result<PathBuf> {
-    Ok(platform::config_dir()?.join("amp"))
+    Ok(platform::dot_config_dir()?.join("amp"))
+}
+
+/// Returns the global AMP Code data directory, holding threads.
+///
+/// Returns `$XDG_DATA_HOME/amp/` if set, otherwise `~/.local/share/amp/`
+/// on all platforms.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn global_data_dir() -> Result<PathBuf> {
+    Ok(platform::xdg_dir("XDG_DATA_HOME", ".local/share")?.join("amp"))
+}
+
+/// Returns the global AMP Code cache directory.
+///
+/// Returns `$XDG_CACHE_HOME/amp/` if set, otherwise `~/.cache/amp/` on
+/// all platforms. Logs are written to `logs/` in it.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn global_cache_dir() -> Result<PathBuf> {
+    Ok(platform::xdg_dir("XDG_CACHE_HOME", ".cache")?.join("amp"))
 }
 
 /// Returns the config directory for the given scope.
@@ -56,6 +92,19 @@ pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
     }
 }
 
+/// Returns the agents directory for the given scope.
+///
+/// - **Global**: `~/.config/amp/agents/`
+/// - **Project**: `.agents/agents/`
+#[must_use]
+pub fn agents_dir(scope: &Scope) -> Option<PathBuf> {
+    match scope {
+        Scope::Global => global_config_dir().ok().map(|p| p.join("agents")),
+        Scope::Project(root) => Some(root.join(".agents").join("agents")),
+        Scope::Custom(path) => Some(path.join("agents")),
+    }
+}
+
 /// Returns the MCP configuration directory for the given scope.
 ///
 /// AMP stores MCP configuration in `settings.json` within the config directory.
@@ -78,7 +127,7 @@ pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
 #[must_use]
 pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
     match scope {
-        Scope::Global => platform::config_dir()
+        Scope::Global => platform::dot_config_dir()
             .ok()
             .map(|p| p.join("agents").join("skills")),
         Scope::Project(root) => Some(root.join(".agents").join("skills")),
@@ -88,7 +137,7 @@ pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
 
 /// Returns the rules directory for the given scope.
 ///
-/// AMP stores rules files (`AGENTS.md`) at:
+/// AMP stores rules files (see [`RULES_FILES`]) at:
 /// - **Global**: `~/.config/amp/`
 /// - **Project**: Project root directory
 #[must_use]
@@ -100,11 +149,26 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
     }
 }
 
+/// Returns the rules file AMP reads for the given scope.
+///
+/// `AGENTS.md` takes precedence; `AGENT.md` and `CLAUDE.md` are read only
+/// when no file earlier in [`RULES_FILES`] exists. Returns `None` if there
+/// is no rules file.
+#[must_use]
+pub fn rules_file(scope: &Scope) -> Option<PathBuf> {
+    let dir = rules_dir(scope)?;
+    let reason = AccessReason::new(HarnessKind::AmpCode, None, "rules");
+    RULES_FILES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| crate::fs::exists_for(path, reason))
+}
+
 /// Checks if AMP Code is installed on this system.
 ///
 /// Checks if the `amp` binary is available in PATH.
 pub fn is_installed() -> bool {
-    which::which("amp").is_ok()
+    matches!(crate::detection::find_binary("amp"), Ok(Some(_)))
 }
 
 /// Parses a single MCP server from AMP's native JSON format.
@@ -332,6 +396,128 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(result)
 }
 
+/// JSON pointer to the MCP servers in the config file.
+const MCP_KEY_PATH: &str = "/amp.mcpServers";
+
+/// The AMP Code [`HarnessBackend`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AmpCode;
+
+impl HarnessBackend for AmpCode {
+    fn name(&self) -> &str {
+        HarnessKind::AmpCode.as_str()
+    }
+
+    fn kind(&self) -> Option<HarnessKind> {
+        Some(HarnessKind::AmpCode)
+    }
+
+    fn binary_names(&self) -> Vec<&str> {
+        HarnessKind::AmpCode.binary_names().to_vec()
+    }
+
+    fn project_markers(&self) -> Vec<&str> {
+        HarnessKind::AmpCode.project_markers().to_vec()
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn is_installed_with(&self, binary_found: bool) -> bool {
+        binary_found
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn resource(&self, kind: ResourceKind, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let harness = HarnessKind::AmpCode;
+        match kind {
+            ResourceKind::Skills => {
+                backend::skills_directory(harness, skills_dir(scope), FileFormat::Markdown)
+            }
+            ResourceKind::Commands => Ok(Some(backend::markdown_directory(
+                harness,
+                kind,
+                "commands",
+                commands_dir(scope)?,
+            ))),
+            ResourceKind::Agents => {
+                let path =
+                    agents_dir(scope).ok_or_else(|| Error::NotFound("agents directory".into()))?;
+                Ok(Some(backend::markdown_directory(
+                    harness, kind, "agents", path,
+                )))
+            }
+            ResourceKind::Plugins => Ok(None),
+        }
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        backend::rules_directory(HarnessKind::AmpCode, rules_dir(scope))
+    }
+
+    fn mcp(&self, scope: &Scope) -> Result<Option<ConfigResource>> {
+        backend::mcp_config(
+            HarnessKind::AmpCode,
+            config_dir(scope)?.join("settings.json"),
+            MCP_KEY_PATH,
+            FileFormat::Json,
+        )
+    }
+
+    fn mcp_key_path(&self) -> Option<&str> {
+        Some(MCP_KEY_PATH)
+    }
+
+    fn data_dir(&self, scope: &Scope) -> Result<Option<PathBuf>> {
+        match scope {
+            Scope::Global => global_data_dir().map(Some),
+            Scope::Project(_) | Scope::Custom(_) => Ok(None),
+        }
+    }
+
+    fn logs_dir(&self) -> Result<Option<PathBuf>> {
+        Ok(Some(global_cache_dir()?.join("logs")))
+    }
+
+    fn cache_dir(&self) -> Result<Option<PathBuf>> {
+        global_cache_dir().map(Some)
+    }
+
+    fn mcp_capabilities(&self) -> McpCapabilities {
+        McpCapabilities::for_kind(HarnessKind::AmpCode)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server("(unnamed)", value)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.validate_capabilities(HarnessKind::AmpCode)?;
+        server.to_ampcode_value(HarnessKind::AmpCode)
+    }
+
+    #[cfg(feature = "validation")]
+    fn validate_mcp_server(&self, server: &McpServer) -> Vec<ValidationIssue> {
+        crate::validation::validate_for_harness(server, HarnessKind::AmpCode)
+    }
+
+    fn skill_capabilities(&self) -> Option<SkillCapabilities> {
+        SkillCapabilities::for_kind(HarnessKind::AmpCode)
+    }
+
+    fn command_capabilities(&self) -> Option<CommandCapabilities> {
+        CommandCapabilities::for_kind(HarnessKind::AmpCode)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -901,4 +1087,101 @@ mod tests {
         assert!(err.contains("incomplete"));
         assert!(err.contains("neither"));
     }
+
+    #[test]
+    fn agents_dir_global_and_project() {
+        if platform::config_dir().is_err() {
+            return;
+        }
+
+        let global = agents_dir(&Scope::Global).unwrap();
+        assert!(global.ends_with("amp/agents"));
+
+        let project = agents_dir(&Scope::Project(PathBuf::from("/some/project"))).unwrap();
+        assert_eq!(project, PathBuf::from("/some/project/.agents/agents"));
+    }
+
+    #[test]
+    fn agents_resource_is_markdown_with_frontmatter() {
+        let scope = Scope::Project(PathBuf::from("/some/project"));
+        let agents = AmpCode
+            .resource(ResourceKind::Agents, &scope)
+            .unwrap()
+            .unwrap();
+        assert_eq!(agents.path, PathBuf::from("/some/project/.agents/agents"));
+        assert_eq!(agents.file_format, FileFormat::MarkdownWithFrontmatter);
+    }
+
+    #[test]
+    fn rules_file_prefers_agents_md() {
+        use crate::fs::MemoryFs;
+        use crate::platform::PathContext;
+
+        let scope = Scope::Project(PathBuf::from("/repo"));
+        let ctx = PathContext::fake("/fake").with_fs(
+            MemoryFs::new()
+                .with_file("/repo/CLAUDE.md", "# Claude")
+                .with_file("/repo/AGENT.md", "# Agent"),
+        );
+        let file = ctx.scope(|| rules_file(&scope));
+        assert_eq!(file, Some(PathBuf::from("/repo/AGENT.md")));
+
+        let ctx = PathContext::fake("/fake").with_fs(
+            MemoryFs::new()
+                .with_file("/repo/AGENT.md", "# Agent")
+                .with_file("/repo/AGENTS.md", "# Agents"),
+        );
+        let file = ctx.scope(|| rules_file(&scope));
+        assert_eq!(file, Some(PathBuf::from("/repo/AGENTS.md")));
+
+        let ctx = PathContext::fake("/fake").with_fs(MemoryFs::new());
+        assert_eq!(ctx.scope(|| rules_file(&scope)), None);
+    }
+
+    #[test]
+    fn mcp_capabilities_include_remote_servers() {
+        let caps = AmpCode.mcp_capabilities();
+        assert!(caps.stdio);
+        assert!(caps.sse);
+        assert!(caps.http);
+        assert!(caps.headers);
+        assert!(!caps.oauth);
+        assert!(!caps.timeout);
+        assert!(!caps.toggle);
+        assert!(!caps.cwd);
+    }
+
+    #[test]
+    fn mcp_config_is_settings_json() {
+        let config = AmpCode
+            .mcp(&Scope::Custom(PathBuf::from("/profile")))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.file, PathBuf::from("/profile/settings.json"));
+        assert_eq!(config.key_path, "/amp.mcpServers");
+    }
+
+    #[test]
+    fn http_server_round_trips_through_native_format() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://mcp.example.com".to_string(),
+            headers: [("Authorization".to_string(), EnvValue::env("TOKEN"))].into(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        let native = AmpCode.mcp_to_native("remote", &server).unwrap();
+        assert_eq!(
+            native,
+            json!({
+                "url": "https://mcp.example.com",
+                "headers": { "Authorization": "${TOKEN}" }
+            })
+        );
+
+        let config = json!({ "amp.mcpServers": { "remote": native } });
+        let parsed = AmpCode.parse_mcp_servers(&config).unwrap();
+        assert_eq!(parsed, [("remote".to_string(), server)]);
+    }
 }
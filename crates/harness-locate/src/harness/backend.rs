@@ -0,0 +1,435 @@
+//! Pluggable harness implementations.
+//!
+//! Everything that differs between harnesses — paths, capabilities, MCP
+//! parsing and serialization, validation hooks — is described by a
+//! [`HarnessBackend`]. Each built-in harness is one module with one
+//! implementation, and [`Harness`](super::Harness) dispatches to it through
+//! [`builtin`].
+//!
+//! Downstream crates can describe harnesses this crate does not know about,
+//! such as internal forks, by implementing the trait and registering it
+//! with a [`HarnessRegistry`](super::HarnessRegistry).
+//!
+//! # Example
+//!
+//! ```
+//! use std::path::PathBuf;
+//!
+//! use harness_locate::harness::{HarnessBackend, HarnessRegistry};
+//! use harness_locate::mcp::{McpCapabilities, McpServer};
+//! use harness_locate::{ConfigResource, DirectoryResource, ResourceKind, Result, Scope};
+//!
+//! #[derive(Debug)]
+//! struct Internal;
+//!
+//! impl HarnessBackend for Internal {
+//!     fn name(&self) -> &str {
+//!         "internal-agent"
+//!     }
+//!     fn is_installed(&self) -> bool {
+//!         false
+//!     }
+//!     fn config_dir(&self, _: &Scope) -> Result<PathBuf> {
+//!         Ok(PathBuf::from("/etc/internal-agent"))
+//!     }
+//!     fn resource(&self, _: ResourceKind, _: &Scope) -> Result<Option<DirectoryResource>> {
+//!         Ok(None)
+//!     }
+//!     fn rules(&self, _: &Scope) -> Result<Option<DirectoryResource>> {
+//!         Ok(None)
+//!     }
+//!     fn mcp(&self, _: &Scope) -> Result<Option<ConfigResource>> {
+//!         Ok(None)
+//!     }
+//!     fn mcp_capabilities(&self) -> McpCapabilities {
+//!         McpCapabilities::default()
+//!     }
+//!     fn parse_mcp_servers(&self, _: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+//!         Ok(Vec::new())
+//!     }
+//!     fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+//!         Ok(serde_json::from_value(value.clone())?)
+//!     }
+//!     fn mcp_to_native(&self, _: &str, server: &McpServer) -> Result<serde_json::Value> {
+//!         Ok(serde_json::to_value(server)?)
+//!     }
+//! }
+//!
+//! let registry = HarnessRegistry::new();
+//! let harness = registry.register(Box::new(Internal))?;
+//! assert_eq!(harness.name(), "internal-agent");
+//! assert_eq!(harness.kind(), None);
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::fs::AccessReason;
+use crate::mcp::{McpCapabilities, McpServer};
+use crate::types::{
+    ConfigResource, DirectoryResource, DirectoryStructure, FileFormat, HarnessKind, ResourceKind,
+    Scope, TokenStore,
+};
+#[cfg(feature = "validation")]
+use crate::validation::ValidationIssue;
+use crate::validation::{CommandCapabilities, SkillCapabilities};
+
+use super::{amp_code, claude_code, copilot_cli, goose, opencode};
+
+/// Harness-specific behavior behind [`Harness`](super::Harness).
+///
+/// Required methods cover what every harness has: an installation check,
+/// a config directory, resource locations and an MCP config format.
+/// Provided methods describe optional features and default to "not
+/// supported".
+pub trait HarnessBackend: fmt::Debug + Send + Sync {
+    /// Returns the harness's unique display name, such as `"Claude Code"`.
+    ///
+    /// Built-in backends use [`HarnessKind::as_str`].
+    fn name(&self) -> &str;
+
+    /// Returns the built-in kind this backend implements, or `None` for a
+    /// registered custom harness.
+    fn kind(&self) -> Option<HarnessKind> {
+        None
+    }
+
+    /// Returns the executable names the harness can be launched as.
+    fn binary_names(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Returns files or directories whose presence in a directory marks it
+    /// as a project configured for this harness.
+    ///
+    /// Used by [`find_project_scopes`](crate::workspace::find_project_scopes).
+    fn project_markers(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Returns `true` if the harness is installed on the current system.
+    fn is_installed(&self) -> bool;
+
+    /// Like [`is_installed`](Self::is_installed), with the `PATH` lookup
+    /// for [`binary_names`](Self::binary_names) already done.
+    ///
+    /// [`Harness::installed`](super::Harness::installed) searches `PATH`
+    /// once for every harness and passes the result here. The default
+    /// ignores it and calls [`is_installed`](Self::is_installed).
+    fn is_installed_with(&self, binary_found: bool) -> bool {
+        let _ = binary_found;
+        self.is_installed()
+    }
+
+    /// Returns the base configuration directory for `scope`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be determined or the
+    /// harness does not support `scope`.
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf>;
+
+    /// Returns the directory holding resources of `kind`, or `None` if the
+    /// harness does not support them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be determined.
+    fn resource(&self, kind: ResourceKind, scope: &Scope) -> Result<Option<DirectoryResource>>;
+
+    /// Returns the rules directory, or `None` if the harness has no rules
+    /// for `scope`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be determined.
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>>;
+
+    /// Returns the MCP configuration file, or `None` if the harness has no
+    /// MCP configuration for `scope`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file location cannot be determined.
+    fn mcp(&self, scope: &Scope) -> Result<Option<ConfigResource>>;
+
+    /// Returns MCP config locations older versions of the harness read for
+    /// `scope`, whether or not they exist.
+    ///
+    /// Entries there are formatted like those in [`mcp`](Self::mcp). The
+    /// default is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a location cannot be determined.
+    fn legacy_mcp_configs(&self, scope: &Scope) -> Result<Vec<ConfigResource>> {
+        let _ = scope;
+        Ok(Vec::new())
+    }
+
+    /// Returns the JSON pointer to the servers object in the documents
+    /// [`parse_mcp_servers`](Self::parse_mcp_servers) accepts, if there is a
+    /// single one.
+    ///
+    /// Lets [`Harness::parse_mcp_config_lenient`](super::Harness::parse_mcp_config_lenient)
+    /// parse entries one at a time. The default is `None`, which parses the
+    /// whole document at once.
+    fn mcp_key_path(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the MCP features the harness supports.
+    fn mcp_capabilities(&self) -> McpCapabilities;
+
+    /// Parses every MCP server from a harness-native config document.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is not valid for this harness.
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>>;
+
+    /// Parses a single harness-native MCP server entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the entry is not valid for this harness.
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer>;
+
+    /// Converts `server` into the harness-native entry stored under `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UnsupportedMcpConfig` if the server uses features
+    /// the harness does not support.
+    fn mcp_to_native(&self, name: &str, server: &McpServer) -> Result<serde_json::Value>;
+
+    /// Validates `server` for this harness.
+    ///
+    /// The default runs only the harness-independent checks of
+    /// [`validate_mcp_server`](crate::validation::validate_mcp_server).
+    #[cfg(feature = "validation")]
+    fn validate_mcp_server(&self, server: &McpServer) -> Vec<ValidationIssue> {
+        crate::validation::validate_mcp_server(server)
+    }
+
+    /// Returns where the harness stores OAuth tokens for MCP servers.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store location cannot be determined.
+    fn oauth_token_store(&self) -> Result<Option<TokenStore>> {
+        Ok(None)
+    }
+
+    /// Returns whether the token store file `store` holds credentials for
+    /// `server_name`, or `None` if this cannot be determined.
+    fn has_oauth_credentials(&self, store: &serde_json::Value, server_name: &str) -> Option<bool> {
+        let _ = (store, server_name);
+        None
+    }
+
+    /// Returns the system-wide managed settings file, if the harness reads
+    /// one.
+    fn managed_settings_path(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Returns the merged settings the harness applies, if it layers
+    /// settings files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a settings file cannot be read or parsed.
+    fn effective_settings(&self, project_root: Option<&Path>) -> Result<Option<serde_json::Value>> {
+        let _ = project_root;
+        Ok(None)
+    }
+
+    /// Returns which project MCP servers the user approved, if the harness
+    /// gates them behind approval.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the approval state cannot be read or parsed.
+    fn project_mcp_approvals(
+        &self,
+        project_root: &Path,
+    ) -> Result<Option<claude_code::McpApprovals>> {
+        let _ = project_root;
+        Ok(None)
+    }
+
+    /// Returns the settings files that can define hooks for `scope`,
+    /// whether or not they exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined.
+    fn hook_files(&self, scope: &Scope) -> Result<Vec<PathBuf>> {
+        let _ = scope;
+        Ok(Vec::new())
+    }
+
+    /// Returns where the harness keeps state such as sessions and
+    /// transcripts for `scope`, if it does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be determined.
+    fn data_dir(&self, scope: &Scope) -> Result<Option<PathBuf>> {
+        let _ = scope;
+        Ok(None)
+    }
+
+    /// Returns where the harness writes its logs, if it does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be determined.
+    fn logs_dir(&self) -> Result<Option<PathBuf>> {
+        Ok(None)
+    }
+
+    /// Returns where the harness caches downloads and other files it can
+    /// recreate, if it does.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be determined.
+    fn cache_dir(&self) -> Result<Option<PathBuf>> {
+        Ok(None)
+    }
+
+    /// Returns the skill features the harness supports, if it has skills.
+    fn skill_capabilities(&self) -> Option<SkillCapabilities> {
+        None
+    }
+
+    /// Returns the command features the harness supports, if it has
+    /// commands.
+    fn command_capabilities(&self) -> Option<CommandCapabilities> {
+        None
+    }
+}
+
+/// Returns the backend for a built-in harness.
+#[must_use]
+pub fn builtin(kind: HarnessKind) -> &'static dyn HarnessBackend {
+    match kind {
+        HarnessKind::ClaudeCode => &claude_code::ClaudeCode,
+        HarnessKind::OpenCode => &opencode::OpenCode,
+        HarnessKind::Goose => &goose::Goose,
+        HarnessKind::AmpCode => &amp_code::AmpCode,
+        HarnessKind::CopilotCli => &copilot_cli::CopilotCli,
+    }
+}
+
+/// Builds a directory resource for a built-in harness, checking whether
+/// `path` exists.
+pub(crate) fn directory(
+    kind: HarnessKind,
+    resource: Option<ResourceKind>,
+    purpose: &'static str,
+    path: PathBuf,
+    structure: DirectoryStructure,
+    file_format: FileFormat,
+) -> DirectoryResource {
+    DirectoryResource {
+        exists: crate::fs::exists_for(&path, AccessReason::new(kind, resource, purpose)),
+        path,
+        structure,
+        file_format,
+    }
+}
+
+/// Builds a skills directory resource: one `SKILL.md` per subdirectory.
+pub(crate) fn skills_directory(
+    kind: HarnessKind,
+    path: Option<PathBuf>,
+    file_format: FileFormat,
+) -> Result<Option<DirectoryResource>> {
+    let path = path.ok_or_else(|| Error::NotFound("skills directory".into()))?;
+    Ok(Some(directory(
+        kind,
+        Some(ResourceKind::Skills),
+        "skills",
+        path,
+        DirectoryStructure::Nested {
+            subdir_pattern: "*".into(),
+            file_name: "SKILL.md".into(),
+        },
+        file_format,
+    )))
+}
+
+/// Builds a flat directory resource of `*.md` files with frontmatter.
+pub(crate) fn markdown_directory(
+    kind: HarnessKind,
+    resource: ResourceKind,
+    purpose: &'static str,
+    path: PathBuf,
+) -> DirectoryResource {
+    directory(
+        kind,
+        Some(resource),
+        purpose,
+        path,
+        DirectoryStructure::Flat {
+            file_pattern: "*.md".into(),
+        },
+        FileFormat::MarkdownWithFrontmatter,
+    )
+}
+
+/// Builds a rules directory resource of plain `*.md` files.
+pub(crate) fn rules_directory(
+    kind: HarnessKind,
+    path: Option<PathBuf>,
+) -> Result<Option<DirectoryResource>> {
+    Ok(path.map(|path| {
+        directory(
+            kind,
+            None,
+            "rules",
+            path,
+            DirectoryStructure::Flat {
+                file_pattern: "*.md".into(),
+            },
+            FileFormat::Markdown,
+        )
+    }))
+}
+
+/// Builds an MCP config file resource, checking whether `file` exists.
+pub(crate) fn mcp_config(
+    kind: HarnessKind,
+    file: PathBuf,
+    key_path: &str,
+    format: FileFormat,
+) -> Result<Option<ConfigResource>> {
+    Ok(Some(ConfigResource {
+        file_exists: crate::fs::exists_for(&file, AccessReason::new(kind, None, "mcp")),
+        file,
+        key_path: key_path.into(),
+        format,
+        schema_url: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtins_match_their_kind() {
+        for &kind in HarnessKind::ALL {
+            let backend = builtin(kind);
+            assert_eq!(backend.kind(), Some(kind));
+            assert_eq!(backend.name(), kind.as_str());
+            assert_eq!(backend.binary_names(), kind.binary_names());
+            assert_eq!(backend.project_markers(), kind.project_markers());
+        }
+    }
+}
@@ -3,14 +3,26 @@
 //! Claude Code stores its configuration in:
 //! - **Global**: `$CLAUDE_CONFIG_DIR` or `~/.claude/`
 //! - **Project**: `.claude/` in project root
+//! - **Managed**: a system-wide directory for enterprise policy, see
+//!   [`managed_config_dir`]
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
+use serde::{Deserialize, Serialize};
+
+use super::backend::{self, HarnessBackend};
 use crate::error::{Error, Result};
-use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use crate::fs::AccessReason;
+use crate::mcp::{HttpMcpServer, McpCapabilities, McpServer, SseMcpServer, StdioMcpServer};
 use crate::platform;
-use crate::types::{EnvValue, HarnessKind, Scope};
+use crate::types::{
+    ConfigResource, DirectoryResource, DirectoryStructure, EnvValue, FileFormat, HarnessKind,
+    ResourceKind, Scope, TokenStore,
+};
+#[cfg(feature = "validation")]
+use crate::validation::ValidationIssue;
+use crate::validation::{CommandCapabilities, SkillCapabilities};
 
 /// Environment variable for Claude Code config directory override.
 const CLAUDE_CONFIG_DIR_ENV: &str = "CLAUDE_CONFIG_DIR";
@@ -26,7 +38,7 @@ const CLAUDE_CONFIG_DIR_ENV: &str = "CLAUDE_CONFIG_DIR";
 /// no environment variable is set.
 pub fn global_config_dir() -> Result<PathBuf> {
     // Check environment variable first
-    if let Ok(dir) = std::env::var(CLAUDE_CONFIG_DIR_ENV) {
+    if let Some(dir) = platform::var(CLAUDE_CONFIG_DIR_ENV) {
         let path = PathBuf::from(dir);
         if path.is_absolute() {
             return Ok(path);
@@ -137,11 +149,344 @@ pub fn plugins_dir(scope: &Scope) -> Option<PathBuf> {
     }
 }
 
+/// Returns the settings files that can define hooks for the given scope.
+///
+/// - **Global**: `~/.claude/settings.json`
+/// - **Project**: `.claude/settings.json` and `.claude/settings.local.json`
+///
+/// # Errors
+///
+/// Returns an error if the global config directory cannot be determined.
+pub fn hook_files(scope: &Scope) -> Result<Vec<PathBuf>> {
+    let dir = config_dir(scope)?;
+    let mut files = vec![dir.join("settings.json")];
+    if let Scope::Project(_) = scope {
+        files.push(dir.join("settings.local.json"));
+    }
+    Ok(files)
+}
+
+/// Keychain service Claude Code stores credentials under on macOS.
+#[cfg(target_os = "macos")]
+const KEYCHAIN_SERVICE: &str = "Claude Code-credentials";
+
+/// Returns where Claude Code stores OAuth tokens for remote MCP servers.
+///
+/// - **macOS**: the `Claude Code-credentials` keychain entry
+/// - **Linux/Windows**: `~/.claude/.credentials.json`
+///
+/// # Errors
+///
+/// Returns an error if the config directory cannot be determined.
+pub fn oauth_token_store() -> Result<TokenStore> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(TokenStore::Keychain {
+            service: KEYCHAIN_SERVICE.to_string(),
+        })
+    }
+    #[cfg(not(target_os = "macos"))]
+    {
+        Ok(TokenStore::File {
+            path: global_config_dir()?.join(".credentials.json"),
+        })
+    }
+}
+
+/// Returns whether the credentials file has an OAuth entry for `server_name`.
+///
+/// Entries live under `mcpOAuth`, keyed as `<name>|<hash>` with a
+/// `serverName` field.
+pub(crate) fn has_oauth_credentials(content: &serde_json::Value, server_name: &str) -> bool {
+    let Some(entries) = content.get("mcpOAuth").and_then(|v| v.as_object()) else {
+        return false;
+    };
+    entries.iter().any(|(key, entry)| {
+        key == server_name
+            || key
+                .split_once('|')
+                .is_some_and(|(name, _)| name == server_name)
+            || entry.get("serverName").and_then(|v| v.as_str()) == Some(server_name)
+    })
+}
+
+/// File name of the enterprise managed settings file.
+const MANAGED_SETTINGS_FILE: &str = "managed-settings.json";
+
+/// Returns the system-wide directory Claude Code reads enterprise policy from.
+///
+/// - **macOS**: `/Library/Application Support/ClaudeCode/`
+/// - **Linux**: `/etc/claude-code/`
+/// - **Windows**: `%ProgramFiles%\ClaudeCode\`
+#[must_use]
+pub fn managed_config_dir() -> PathBuf {
+    #[cfg(target_os = "macos")]
+    {
+        PathBuf::from("/Library/Application Support/ClaudeCode")
+    }
+    #[cfg(target_os = "windows")]
+    {
+        PathBuf::from(
+            platform::var("ProgramFiles").unwrap_or_else(|| r"C:\Program Files".to_string()),
+        )
+        .join("ClaudeCode")
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        PathBuf::from("/etc/claude-code")
+    }
+}
+
+/// Returns the path of the enterprise `managed-settings.json`.
+///
+/// Values in this file take precedence over user and project settings.
+#[must_use]
+pub fn managed_settings_path() -> PathBuf {
+    managed_config_dir().join(MANAGED_SETTINGS_FILE)
+}
+
+/// Returns the settings files Claude Code reads, lowest precedence first.
+///
+/// User settings come first, then the project's shared and local settings
+/// when `project_root` is given, and managed settings last. Files are
+/// listed whether or not they exist.
+///
+/// # Errors
+///
+/// Returns an error if the global config directory cannot be determined.
+pub fn settings_paths(project_root: Option<&Path>) -> Result<Vec<PathBuf>> {
+    let mut paths = vec![global_config_dir()?.join("settings.json")];
+    if let Some(root) = project_root {
+        let project = project_config_dir(root);
+        paths.push(project.join("settings.json"));
+        paths.push(project.join("settings.local.json"));
+    }
+    paths.push(managed_settings_path());
+    Ok(paths)
+}
+
+/// Reads the managed settings file, or `None` if it does not exist.
+///
+/// # Errors
+///
+/// Returns an error if the file exists but cannot be read or is not
+/// valid JSON.
+pub fn managed_settings() -> Result<Option<serde_json::Value>> {
+    read_settings(&managed_settings_path())
+}
+
+/// Merges every settings file into the configuration Claude Code applies.
+///
+/// Files are layered in [`settings_paths`] order: objects merge key by
+/// key, arrays such as permission rules are concatenated without
+/// duplicates, and any other value from a higher-precedence file
+/// replaces the lower one. Managed settings are applied last, so they
+/// always win. Missing files are skipped.
+///
+/// # Errors
+///
+/// Returns an error if a settings file exists but cannot be read or is
+/// not valid JSON.
+pub fn effective_settings(project_root: Option<&Path>) -> Result<serde_json::Value> {
+    let mut merged = serde_json::Value::Object(serde_json::Map::new());
+    for path in settings_paths(project_root)? {
+        if let Some(settings) = read_settings(&path)? {
+            merge_settings(&mut merged, settings);
+        }
+    }
+    Ok(merged)
+}
+
+fn read_settings(path: &Path) -> Result<Option<serde_json::Value>> {
+    let reason = AccessReason::new(HarnessKind::ClaudeCode, None, "settings");
+    if !crate::fs::exists_for(path, reason) {
+        return Ok(None);
+    }
+    let content = crate::fs::read_to_string_for(path, reason)?;
+    Ok(Some(serde_json::from_str(&content)?))
+}
+
+fn merge_settings(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    match (base, overlay) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_settings(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (serde_json::Value::Array(base), serde_json::Value::Array(overlay)) => {
+            for value in overlay {
+                if !base.contains(&value) {
+                    base.push(value);
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Returns the path of Claude Code's state file, `.claude.json`.
+///
+/// The file lives in `$CLAUDE_CONFIG_DIR` when that is set, otherwise in
+/// the home directory (not in `~/.claude/`). It holds per-project state
+/// such as MCP server approvals; it is not an MCP config file.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn state_file() -> Result<PathBuf> {
+    if let Some(dir) = platform::var(CLAUDE_CONFIG_DIR_ENV) {
+        let path = PathBuf::from(dir);
+        if path.is_absolute() {
+            return Ok(path.join(".claude.json"));
+        }
+    }
+    Ok(platform::home_dir()?.join(".claude.json"))
+}
+
+/// Returns where Claude Code keeps session state for the given scope.
+///
+/// - **Global**: the [global config directory](global_config_dir), which
+///   holds `projects/`, `todos/` and `shell-snapshots/` next to settings
+/// - **Project**: `~/.claude/projects/<root>/`, the transcripts of the
+///   project, with every character of the root path other than ASCII
+///   letters and digits replaced by `-`
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn data_dir(scope: &Scope) -> Result<Option<PathBuf>> {
+    match scope {
+        Scope::Global => global_config_dir().map(Some),
+        Scope::Project(root) => {
+            let encoded: String = root
+                .to_string_lossy()
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+                .collect();
+            Ok(Some(global_config_dir()?.join("projects").join(encoded)))
+        }
+        Scope::Custom(_) => Ok(None),
+    }
+}
+
+/// Returns where Claude Code writes MCP server logs and caches files.
+///
+/// - **macOS**: `~/Library/Caches/claude-cli-nodejs/`
+/// - **Linux**: `$XDG_CACHE_HOME/claude-cli-nodejs/` or
+///   `~/.cache/claude-cli-nodejs/`
+/// - **Windows**: `%LOCALAPPDATA%\claude-cli-nodejs\Cache\`
+///
+/// # Errors
+///
+/// Returns an error if the cache directory cannot be determined.
+pub fn cache_dir() -> Result<PathBuf> {
+    let dir = platform::cache_dir()?.join("claude-cli-nodejs");
+    if cfg!(target_os = "windows") {
+        Ok(dir.join("Cache"))
+    } else {
+        Ok(dir)
+    }
+}
+
+/// Which project-scoped (`.mcp.json`) MCP servers the user approved.
+///
+/// Claude Code asks before starting servers from a project's `.mcp.json`
+/// and records the answers per project in [`state_file`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpApprovals {
+    /// Every project server is approved without asking.
+    #[serde(default, rename = "enableAllProjectMcpServers")]
+    pub enable_all: bool,
+    /// Servers the user approved.
+    #[serde(default, rename = "enabledMcpjsonServers")]
+    pub enabled: Vec<String>,
+    /// Servers the user declined.
+    #[serde(default, rename = "disabledMcpjsonServers")]
+    pub disabled: Vec<String>,
+}
+
+impl McpApprovals {
+    /// Returns `Some(true)` if `server_name` is approved, `Some(false)` if
+    /// it was declined, and `None` if the user has not been asked yet.
+    ///
+    /// A decline takes precedence over `enable_all`.
+    #[must_use]
+    pub fn is_approved(&self, server_name: &str) -> Option<bool> {
+        if self.disabled.iter().any(|name| name == server_name) {
+            Some(false)
+        } else if self.enable_all || self.enabled.iter().any(|name| name == server_name) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    /// Disables every server in `servers` the user declined.
+    ///
+    /// Servers awaiting approval are left unchanged.
+    pub fn apply(&self, servers: &mut BTreeMap<String, McpServer>) {
+        for (name, server) in servers.iter_mut() {
+            if self.is_approved(name) == Some(false) {
+                server.set_enabled(false);
+            }
+        }
+    }
+}
+
+/// Reads the MCP approvals recorded for `project_root`.
+///
+/// Returns the default (nothing approved or declined) if the state file
+/// or the project's entry does not exist. Projects are keyed by absolute
+/// path; separators are compared loosely so Windows paths match.
+///
+/// # Errors
+///
+/// Returns an error if the state file exists but cannot be read or is
+/// not valid JSON.
+pub fn project_mcp_approvals(project_root: &Path) -> Result<McpApprovals> {
+    let path = state_file()?;
+    let reason = AccessReason::new(HarnessKind::ClaudeCode, None, "mcp approvals");
+    if !crate::fs::exists_for(&path, reason) {
+        return Ok(McpApprovals::default());
+    }
+    let content: serde_json::Value =
+        serde_json::from_str(&crate::fs::read_to_string_for(&path, reason)?)?;
+    Ok(project_entry(&content, project_root)
+        .map(|entry| serde_json::from_value(entry.clone()))
+        .transpose()?
+        .unwrap_or_default())
+}
+
+fn project_entry<'a>(
+    content: &'a serde_json::Value,
+    project_root: &Path,
+) -> Option<&'a serde_json::Value> {
+    let normalize = |s: &str| s.replace('\\', "/").trim_end_matches('/').to_string();
+    let wanted = normalize(&project_root.to_string_lossy());
+    content
+        .get("projects")?
+        .as_object()?
+        .iter()
+        .find(|(key, _)| normalize(key) == wanted)
+        .map(|(_, entry)| entry)
+}
+
 /// Checks if Claude Code is installed on this system.
 ///
 /// Currently checks if the global config directory exists.
 pub fn is_installed() -> bool {
-    global_config_dir().map(|p| p.exists()).unwrap_or(false)
+    global_config_dir().is_ok_and(|p| {
+        crate::fs::exists_for(
+            &p,
+            AccessReason::new(HarnessKind::ClaudeCode, None, "installation"),
+        )
+    })
 }
 
 /// Parses a single MCP server from Claude Code's native JSON format.
@@ -344,11 +689,336 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(result)
 }
 
+/// JSON pointer to the MCP servers in the config file.
+const MCP_KEY_PATH: &str = "/mcpServers";
+
+/// The Claude Code [`HarnessBackend`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClaudeCode;
+
+impl HarnessBackend for ClaudeCode {
+    fn name(&self) -> &str {
+        HarnessKind::ClaudeCode.as_str()
+    }
+
+    fn kind(&self) -> Option<HarnessKind> {
+        Some(HarnessKind::ClaudeCode)
+    }
+
+    fn binary_names(&self) -> Vec<&str> {
+        HarnessKind::ClaudeCode.binary_names().to_vec()
+    }
+
+    fn project_markers(&self) -> Vec<&str> {
+        HarnessKind::ClaudeCode.project_markers().to_vec()
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn resource(&self, kind: ResourceKind, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let harness = HarnessKind::ClaudeCode;
+        match kind {
+            ResourceKind::Skills => backend::skills_directory(
+                harness,
+                skills_dir(scope),
+                FileFormat::MarkdownWithFrontmatter,
+            ),
+            ResourceKind::Commands => Ok(Some(backend::markdown_directory(
+                harness,
+                kind,
+                "commands",
+                commands_dir(scope)?,
+            ))),
+            ResourceKind::Agents => {
+                let path =
+                    agents_dir(scope).ok_or_else(|| Error::NotFound("agents directory".into()))?;
+                Ok(Some(backend::markdown_directory(
+                    harness, kind, "agents", path,
+                )))
+            }
+            ResourceKind::Plugins => {
+                let path = plugins_dir(scope)
+                    .ok_or_else(|| Error::NotFound("plugins directory".into()))?;
+                Ok(Some(backend::directory(
+                    harness,
+                    Some(kind),
+                    "plugins",
+                    path,
+                    DirectoryStructure::Nested {
+                        subdir_pattern: "*".into(),
+                        file_name: ".claude-plugin".into(),
+                    },
+                    FileFormat::Json,
+                )))
+            }
+        }
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        backend::rules_directory(HarnessKind::ClaudeCode, rules_dir(scope))
+    }
+
+    fn mcp(&self, scope: &Scope) -> Result<Option<ConfigResource>> {
+        // Claude Code CLI uses .mcp.json in config directories:
+        // - Global: ~/.claude/.mcp.json
+        // - Project: .mcp.json (in project root)
+        // Note: ~/.claude.json is a cache/stats file, NOT MCP config
+        let file = match scope {
+            Scope::Global => config_dir(&Scope::Global)?.join(".mcp.json"),
+            Scope::Project(root) => root.join(".mcp.json"),
+            Scope::Custom(path) => path.join(".mcp.json"),
+        };
+        backend::mcp_config(
+            HarnessKind::ClaudeCode,
+            file,
+            MCP_KEY_PATH,
+            FileFormat::Json,
+        )
+    }
+
+    fn legacy_mcp_configs(&self, scope: &Scope) -> Result<Vec<ConfigResource>> {
+        // User-scoped servers used to be kept in `~/.claude.json`.
+        if !matches!(scope, Scope::Global) {
+            return Ok(Vec::new());
+        }
+        Ok(backend::mcp_config(
+            HarnessKind::ClaudeCode,
+            state_file()?,
+            MCP_KEY_PATH,
+            FileFormat::Json,
+        )?
+        .into_iter()
+        .collect())
+    }
+
+    fn mcp_key_path(&self) -> Option<&str> {
+        Some(MCP_KEY_PATH)
+    }
+
+    fn mcp_capabilities(&self) -> McpCapabilities {
+        McpCapabilities::for_kind(HarnessKind::ClaudeCode)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.validate_capabilities(HarnessKind::ClaudeCode)?;
+        server.to_claude_code_value(HarnessKind::ClaudeCode)
+    }
+
+    #[cfg(feature = "validation")]
+    fn validate_mcp_server(&self, server: &McpServer) -> Vec<ValidationIssue> {
+        crate::validation::validate_for_harness(server, HarnessKind::ClaudeCode)
+    }
+
+    fn oauth_token_store(&self) -> Result<Option<TokenStore>> {
+        oauth_token_store().map(Some)
+    }
+
+    fn has_oauth_credentials(&self, store: &serde_json::Value, server_name: &str) -> Option<bool> {
+        Some(has_oauth_credentials(store, server_name))
+    }
+
+    fn managed_settings_path(&self) -> Option<PathBuf> {
+        Some(managed_settings_path())
+    }
+
+    fn data_dir(&self, scope: &Scope) -> Result<Option<PathBuf>> {
+        data_dir(scope)
+    }
+
+    fn logs_dir(&self) -> Result<Option<PathBuf>> {
+        cache_dir().map(Some)
+    }
+
+    fn cache_dir(&self) -> Result<Option<PathBuf>> {
+        cache_dir().map(Some)
+    }
+
+    fn effective_settings(&self, project_root: Option<&Path>) -> Result<Option<serde_json::Value>> {
+        effective_settings(project_root).map(Some)
+    }
+
+    fn project_mcp_approvals(&self, project_root: &Path) -> Result<Option<McpApprovals>> {
+        project_mcp_approvals(project_root).map(Some)
+    }
+
+    fn hook_files(&self, scope: &Scope) -> Result<Vec<PathBuf>> {
+        hook_files(scope)
+    }
+
+    fn skill_capabilities(&self) -> Option<SkillCapabilities> {
+        SkillCapabilities::for_kind(HarnessKind::ClaudeCode)
+    }
+
+    fn command_capabilities(&self) -> Option<CommandCapabilities> {
+        CommandCapabilities::for_kind(HarnessKind::ClaudeCode)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn has_oauth_credentials_matches_name_and_hash_key() {
+        let content = json!({
+            "mcpOAuth": {
+                "github|4f2a": {"serverName": "github", "accessToken": "x"},
+                "legacy": {"accessToken": "y"}
+            }
+        });
+        assert!(has_oauth_credentials(&content, "github"));
+        assert!(has_oauth_credentials(&content, "legacy"));
+        assert!(!has_oauth_credentials(&content, "linear"));
+        assert!(!has_oauth_credentials(&json!({}), "github"));
+    }
+
+    #[test]
+    fn settings_paths_end_with_managed_settings() {
+        let root = std::env::temp_dir().join("harness-locate-settings-paths");
+        let ctx = platform::PathContext::fake(&root);
+        let project = root.join("project");
+        let paths = ctx.scope(|| settings_paths(Some(&project))).unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                root.join("home/.claude/settings.json"),
+                project.join(".claude/settings.json"),
+                project.join(".claude/settings.local.json"),
+                managed_settings_path(),
+            ]
+        );
+        assert!(managed_settings_path().ends_with("managed-settings.json"));
+    }
+
+    #[test]
+    fn effective_settings_applies_managed_settings_last() {
+        use crate::fs::MemoryFs;
+
+        let root = std::env::temp_dir().join("harness-locate-effective-settings");
+        let project = root.join("project");
+        let fs = MemoryFs::new()
+            .with_file(
+                root.join("home/.claude/settings.json"),
+                r#"{"model": "sonnet", "permissions": {"allow": ["Bash(ls)"]}}"#,
+            )
+            .with_file(
+                project.join(".claude/settings.json"),
+                r#"{"model": "opus", "permissions": {"allow": ["Bash(ls)", "Read"]}}"#,
+            )
+            .with_file(
+                managed_settings_path(),
+                r#"{"model": "haiku", "permissions": {"deny": ["WebFetch"]}}"#,
+            );
+        let ctx = platform::PathContext::fake(&root).with_fs(fs);
+
+        let settings = ctx.scope(|| effective_settings(Some(&project))).unwrap();
+        assert_eq!(
+            settings,
+            json!({
+                "model": "haiku",
+                "permissions": {"allow": ["Bash(ls)", "Read"], "deny": ["WebFetch"]}
+            })
+        );
+        assert_eq!(
+            ctx.scope(managed_settings).unwrap().unwrap()["model"],
+            "haiku"
+        );
+    }
+
+    #[test]
+    fn managed_settings_missing_is_none() {
+        let root = std::env::temp_dir().join("harness-locate-no-managed-settings");
+        let ctx = platform::PathContext::fake(&root).with_fs(crate::fs::MemoryFs::new());
+        assert_eq!(ctx.scope(managed_settings).unwrap(), None);
+        assert_eq!(ctx.scope(|| effective_settings(None)).unwrap(), json!({}));
+    }
+
+    #[test]
+    fn mcp_approvals_apply_declines() {
+        let approvals: McpApprovals = serde_json::from_value(json!({
+            "enabledMcpjsonServers": ["github"],
+            "disabledMcpjsonServers": ["linear"],
+            "hasTrustDialogAccepted": true
+        }))
+        .unwrap();
+        assert_eq!(approvals.is_approved("github"), Some(true));
+        assert_eq!(approvals.is_approved("linear"), Some(false));
+        assert_eq!(approvals.is_approved("sentry"), None);
+
+        let stdio = |name: &str| {
+            McpServer::Stdio(StdioMcpServer {
+                command: name.to_string(),
+                args: vec![],
+                env: HashMap::new(),
+                cwd: None,
+                enabled: true,
+                timeout_ms: None,
+            })
+        };
+        let mut servers: BTreeMap<String, McpServer> = ["github", "linear", "sentry"]
+            .into_iter()
+            .map(|name| (name.to_string(), stdio(name)))
+            .collect();
+        approvals.apply(&mut servers);
+        assert!(servers["github"].is_enabled());
+        assert!(!servers["linear"].is_enabled());
+        assert!(servers["sentry"].is_enabled());
+    }
+
+    #[test]
+    fn decline_overrides_enable_all() {
+        let approvals = McpApprovals {
+            enable_all: true,
+            enabled: vec![],
+            disabled: vec!["linear".to_string()],
+        };
+        assert_eq!(approvals.is_approved("anything"), Some(true));
+        assert_eq!(approvals.is_approved("linear"), Some(false));
+    }
+
+    #[test]
+    fn project_mcp_approvals_reads_state_file() {
+        use crate::fs::MemoryFs;
+
+        let root = std::env::temp_dir().join("harness-locate-mcp-approvals");
+        let project = root.join("project");
+        let state = platform::PathContext::fake(&root)
+            .scope(state_file)
+            .unwrap();
+        let content = json!({
+            "projects": {
+                format!("{}/", project.display()): {
+                    "disabledMcpjsonServers": ["linear"]
+                }
+            }
+        });
+        let ctx = platform::PathContext::fake(&root)
+            .with_fs(MemoryFs::new().with_file(&state, content.to_string()));
+
+        let approvals = ctx.scope(|| project_mcp_approvals(&project)).unwrap();
+        assert_eq!(approvals.disabled, vec!["linear"]);
+
+        let other = ctx
+            .scope(|| project_mcp_approvals(&root.join("other")))
+            .unwrap();
+        assert_eq!(other, McpApprovals::default());
+    }
+
     #[test]
     fn global_config_dir_is_absolute() {
         // Skip if home dir cannot be determined (CI environments)
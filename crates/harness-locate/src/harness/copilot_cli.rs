@@ -7,10 +7,17 @@
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use super::backend::{self, HarnessBackend};
 use crate::error::{Error, Result};
-use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use crate::fs::AccessReason;
+use crate::mcp::{HttpMcpServer, McpCapabilities, McpServer, SseMcpServer, StdioMcpServer};
 use crate::platform;
-use crate::types::{EnvValue, HarnessKind, Scope};
+use crate::types::{
+    ConfigResource, DirectoryResource, EnvValue, FileFormat, HarnessKind, ResourceKind, Scope,
+};
+#[cfg(feature = "validation")]
+use crate::validation::ValidationIssue;
+use crate::validation::{CommandCapabilities, SkillCapabilities};
 
 /// Environment variable for XDG config directory override.
 const XDG_CONFIG_HOME_ENV: &str = "XDG_CONFIG_HOME";
@@ -26,7 +33,7 @@ const XDG_CONFIG_HOME_ENV: &str = "XDG_CONFIG_HOME";
 /// no environment variable is set.
 pub fn global_config_dir() -> Result<PathBuf> {
     // Check XDG_CONFIG_HOME first
-    if let Ok(xdg_config) = std::env::var(XDG_CONFIG_HOME_ENV) {
+    if let Some(xdg_config) = platform::var(XDG_CONFIG_HOME_ENV) {
         let path = PathBuf::from(xdg_config);
         if path.is_absolute() {
             return Ok(path.join("copilot"));
@@ -76,14 +83,14 @@ pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
 
 /// Returns the skills directory for the given scope.
 ///
-/// Copilot CLI stores skills following the agentskills.io spec.
-/// Note that project-local skills are not yet natively supported in the CLI.
+/// Copilot CLI stores skills following the agentskills.io spec:
 /// - **Global**: `~/.copilot/skills/`
+/// - **Project**: `.github/skills/`
 #[must_use]
 pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
     match scope {
         Scope::Global => global_config_dir().ok().map(|p| p.join("skills")),
-        Scope::Project(_) => None,
+        Scope::Project(root) => Some(project_config_dir(root).join("skills")),
         Scope::Custom(path) => Some(path.join("skills")),
     }
 }
@@ -120,19 +127,24 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
 ///
 /// Checks for the `copilot` binary or the existence of `~/.copilot/`.
 pub fn is_installed() -> bool {
-    // Check for copilot binary
-    let copilot_exists = std::process::Command::new("copilot")
-        .arg("--version")
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false);
-
-    if copilot_exists {
+    // Resolve through PATH rather than spawning `copilot`: on Windows the npm
+    // shim is `copilot.cmd`, which `Command::new("copilot")` cannot launch.
+    if matches!(crate::detection::find_binary("copilot"), Ok(Some(_))) {
         return true;
     }
 
     // Fallback to checking for ~/.copilot directory
-    global_config_dir().map(|p| p.exists()).unwrap_or(false)
+    config_dir_exists()
+}
+
+/// Checks whether the global `~/.copilot` directory exists.
+pub(crate) fn config_dir_exists() -> bool {
+    global_config_dir().is_ok_and(|p| {
+        crate::fs::exists_for(
+            &p,
+            AccessReason::new(HarnessKind::CopilotCli, None, "installation"),
+        )
+    })
 }
 
 /// Parses a single MCP server from Copilot CLI's native JSON format.
@@ -306,6 +318,122 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(result)
 }
 
+/// JSON pointer to the MCP servers in the config file.
+const MCP_KEY_PATH: &str = "/mcpServers";
+
+/// The Copilot CLI [`HarnessBackend`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopilotCli;
+
+impl HarnessBackend for CopilotCli {
+    fn name(&self) -> &str {
+        HarnessKind::CopilotCli.as_str()
+    }
+
+    fn kind(&self) -> Option<HarnessKind> {
+        Some(HarnessKind::CopilotCli)
+    }
+
+    fn binary_names(&self) -> Vec<&str> {
+        HarnessKind::CopilotCli.binary_names().to_vec()
+    }
+
+    fn project_markers(&self) -> Vec<&str> {
+        HarnessKind::CopilotCli.project_markers().to_vec()
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn is_installed_with(&self, binary_found: bool) -> bool {
+        binary_found || config_dir_exists()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn resource(&self, kind: ResourceKind, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let harness = HarnessKind::CopilotCli;
+        match kind {
+            ResourceKind::Skills => backend::skills_directory(
+                harness,
+                skills_dir(scope),
+                FileFormat::MarkdownWithFrontmatter,
+            ),
+            ResourceKind::Agents => {
+                // Global and project agents are Markdown
+                let path =
+                    agents_dir(scope).ok_or_else(|| Error::NotFound("agents directory".into()))?;
+                Ok(Some(backend::markdown_directory(
+                    harness, kind, "agents", path,
+                )))
+            }
+            ResourceKind::Commands | ResourceKind::Plugins => Ok(None),
+        }
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        backend::rules_directory(HarnessKind::CopilotCli, rules_dir(scope))
+    }
+
+    fn mcp(&self, scope: &Scope) -> Result<Option<ConfigResource>> {
+        // Copilot CLI uses mcp-config.json in config directories
+        backend::mcp_config(
+            HarnessKind::CopilotCli,
+            mcp_dir(scope)?.join("mcp-config.json"),
+            MCP_KEY_PATH,
+            FileFormat::Json,
+        )
+    }
+
+    fn mcp_key_path(&self) -> Option<&str> {
+        Some(MCP_KEY_PATH)
+    }
+
+    fn data_dir(&self, scope: &Scope) -> Result<Option<PathBuf>> {
+        match scope {
+            Scope::Global => global_config_dir().map(Some),
+            Scope::Project(_) | Scope::Custom(_) => Ok(None),
+        }
+    }
+
+    fn logs_dir(&self) -> Result<Option<PathBuf>> {
+        Ok(Some(global_config_dir()?.join("logs")))
+    }
+
+    fn mcp_capabilities(&self) -> McpCapabilities {
+        McpCapabilities::for_kind(HarnessKind::CopilotCli)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.validate_capabilities(HarnessKind::CopilotCli)?;
+        server.to_copilot_cli_value(HarnessKind::CopilotCli)
+    }
+
+    #[cfg(feature = "validation")]
+    fn validate_mcp_server(&self, server: &McpServer) -> Vec<ValidationIssue> {
+        crate::validation::validate_for_harness(server, HarnessKind::CopilotCli)
+    }
+
+    fn skill_capabilities(&self) -> Option<SkillCapabilities> {
+        SkillCapabilities::for_kind(HarnessKind::CopilotCli)
+    }
+
+    fn command_capabilities(&self) -> Option<CommandCapabilities> {
+        CommandCapabilities::for_kind(HarnessKind::CopilotCli)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -349,10 +477,10 @@ mod tests {
     }
 
     #[test]
-    fn skills_dir_project_unsupported() {
+    fn skills_dir_project() {
         let root = PathBuf::from("/some/project");
         let result = skills_dir(&Scope::Project(root));
-        assert!(result.is_none());
+        assert_eq!(result, Some(PathBuf::from("/some/project/.github/skills")));
     }
 
     #[test]
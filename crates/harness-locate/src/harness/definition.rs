@@ -0,0 +1,844 @@
+//! Harnesses described by a data file instead of code.
+//!
+//! A [`HarnessDefinition`] declares where a harness keeps its configuration
+//! and how its MCP config is written. It implements [`HarnessBackend`], so a
+//! definition loaded at runtime can be registered with a
+//! [`HarnessRegistry`](super::HarnessRegistry) like a hand-written backend.
+//!
+//! Path templates start with one of these tokens, optionally followed by a
+//! relative path:
+//!
+//! - `{home}` - the home directory
+//! - `{config}` - the platform config directory
+//! - `{dot_config}` - `~/.config` on every platform
+//! - `{data}` - the platform data directory
+//! - `{project}` - the project root (project template only)
+//!
+//! Resource directories and the MCP file are relative to the config
+//! directory of the scope being resolved.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::harness::{HarnessDefinition, HarnessRegistry};
+//!
+//! let definition = HarnessDefinition::from_toml_str(r#"
+//!     name = "acme"
+//!     binaries = ["acme"]
+//!     project_markers = [".acme"]
+//!
+//!     [paths]
+//!     global = "{home}/.acme"
+//!     project = "{project}/.acme"
+//!
+//!     [resources]
+//!     skills = "skills"
+//!     commands = "commands"
+//!
+//!     [mcp]
+//!     file = "mcp.json"
+//!     key_path = "/servers"
+//!     env_syntax = "{env:VAR}"
+//! "#)?;
+//!
+//! let harness = HarnessRegistry::new().register(Box::new(definition))?;
+//! assert_eq!(harness.name(), "acme");
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::HarnessBackend;
+use crate::error::{Error, Result};
+use crate::mcp::{HttpMcpServer, McpCapabilities, McpServer, SseMcpServer, StdioMcpServer};
+use crate::platform;
+use crate::types::{
+    ConfigResource, DirectoryResource, DirectoryStructure, EnvValue, FileFormat, ResourceKind,
+    Scope,
+};
+use crate::validation::{CommandCapabilities, NameFormat, SkillCapabilities};
+
+/// Placeholder for the variable name in [`McpDefinition::env_syntax`].
+const ENV_VAR_PLACEHOLDER: &str = "VAR";
+
+/// A harness declared in TOML or JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HarnessDefinition {
+    /// Unique display name of the harness.
+    pub name: String,
+    /// Executable names looked up on `PATH` to detect an installation.
+    #[serde(default)]
+    pub binaries: Vec<String>,
+    /// Files or directories that mark a project configured for the harness.
+    #[serde(default)]
+    pub project_markers: Vec<String>,
+    /// Config directory templates.
+    pub paths: PathTemplates,
+    /// Resource directories, relative to the config directory.
+    #[serde(default)]
+    pub resources: ResourceDirectories,
+    /// MCP configuration, if the harness supports MCP servers.
+    #[serde(default)]
+    pub mcp: Option<McpDefinition>,
+}
+
+/// Templates for a [`HarnessDefinition`]'s config directories.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathTemplates {
+    /// Global config directory, such as `{home}/.acme`.
+    pub global: String,
+    /// Environment variable that overrides the global directory when set
+    /// to an absolute path.
+    #[serde(default)]
+    pub global_env: Option<String>,
+    /// Project config directory, such as `{project}/.acme`. Without it the
+    /// harness has no project scope.
+    #[serde(default)]
+    pub project: Option<String>,
+}
+
+/// Resource directories of a [`HarnessDefinition`].
+///
+/// Skills are `<name>/SKILL.md` subdirectories; commands, agents and rules
+/// are flat `*.md` files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceDirectories {
+    /// Skills directory.
+    #[serde(default)]
+    pub skills: Option<String>,
+    /// Commands directory.
+    #[serde(default)]
+    pub commands: Option<String>,
+    /// Agents directory.
+    #[serde(default)]
+    pub agents: Option<String>,
+    /// Rules directory.
+    #[serde(default)]
+    pub rules: Option<String>,
+}
+
+/// MCP configuration of a [`HarnessDefinition`].
+///
+/// Server entries use the common `command`/`args`/`env` shape for stdio
+/// servers and `url`/`headers` for remote ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct McpDefinition {
+    /// Config file, relative to the config directory. Its extension selects
+    /// the format: `.json`, `.jsonc`, `.yaml` or `.yml`.
+    pub file: String,
+    /// JSON pointer to the servers object, such as `/mcpServers`.
+    pub key_path: String,
+    /// How env references are written, with `VAR` standing for the variable
+    /// name. Defaults to `${VAR}`.
+    #[serde(default = "default_env_syntax")]
+    pub env_syntax: String,
+    /// Supported MCP features. Defaults to stdio servers only.
+    #[serde(default = "stdio_only")]
+    pub capabilities: McpCapabilities,
+    /// URL of a JSON Schema for the config file, such as
+    /// `https://charm.land/crush.json`.
+    #[serde(default)]
+    pub schema_url: Option<String>,
+}
+
+fn default_env_syntax() -> String {
+    "${VAR}".into()
+}
+
+fn stdio_only() -> McpCapabilities {
+    McpCapabilities {
+        stdio: true,
+        ..McpCapabilities::default()
+    }
+}
+
+impl HarnessDefinition {
+    /// Loads a definition from a `.toml` or `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, has another
+    /// extension, or the definition is invalid.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let parse = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml_str,
+            Some("json") => Self::from_json_str,
+            _ => {
+                return Err(Error::InvalidDefinition(format!(
+                    "unsupported file type: {}",
+                    path.display()
+                )));
+            }
+        };
+        parse(&crate::fs::read_text(path)?.text)
+    }
+
+    /// Parses a definition from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is malformed or the definition is
+    /// invalid.
+    pub fn from_toml_str(content: &str) -> Result<Self> {
+        let definition: Self = toml::from_str(content)?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// Parses a definition from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is malformed or the definition is
+    /// invalid.
+    pub fn from_json_str(content: &str) -> Result<Self> {
+        let definition: Self = serde_json::from_str(content)?;
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            return Err(Error::InvalidDefinition("name is empty".into()));
+        }
+        split_template(&self.paths.global, false)?;
+        if let Some(project) = &self.paths.project {
+            split_template(project, true)?;
+        }
+        if let Some(mcp) = &self.mcp {
+            mcp_format(&mcp.file)?;
+            if !mcp.key_path.starts_with('/') {
+                return Err(Error::InvalidDefinition(format!(
+                    "MCP key path must start with '/': {}",
+                    mcp.key_path
+                )));
+            }
+            if !mcp.env_syntax.contains(ENV_VAR_PLACEHOLDER) {
+                return Err(Error::InvalidDefinition(format!(
+                    "env syntax must contain {ENV_VAR_PLACEHOLDER}: {}",
+                    mcp.env_syntax
+                )));
+            }
+            let caps = &mcp.capabilities;
+            if caps.toggle != caps.enabled_semantics.honors_flag() {
+                return Err(Error::InvalidDefinition(
+                    "MCP toggle requires enabled_semantics \"enabled_key\" or \"disabled_key\""
+                        .into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn directory(
+        &self,
+        scope: &Scope,
+        relative: Option<&String>,
+        structure: DirectoryStructure,
+        file_format: FileFormat,
+    ) -> Result<Option<DirectoryResource>> {
+        let Some(relative) = relative else {
+            return Ok(None);
+        };
+        let path = self.config_dir(scope)?.join(relative);
+        Ok(Some(DirectoryResource {
+            exists: crate::fs::exists_for(&path, None),
+            path,
+            structure,
+            file_format,
+        }))
+    }
+
+    fn mcp_definition(&self) -> Result<&McpDefinition> {
+        self.mcp
+            .as_ref()
+            .ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: self.name.clone(),
+                reason: "MCP servers not supported".into(),
+            })
+    }
+
+    fn unsupported(&self, reason: impl Into<String>) -> Error {
+        Error::UnsupportedMcpConfig {
+            harness: self.name.clone(),
+            reason: reason.into(),
+        }
+    }
+
+    fn string_map(
+        &self,
+        obj: &serde_json::Map<String, serde_json::Value>,
+        field: &str,
+        env_syntax: &str,
+    ) -> Result<HashMap<String, EnvValue>> {
+        let Some(value) = obj.get(field) else {
+            return Ok(HashMap::new());
+        };
+        let map = value
+            .as_object()
+            .ok_or_else(|| self.unsupported(format!("'{field}' must be an object")))?;
+        map.iter()
+            .map(|(key, value)| {
+                let value = value
+                    .as_str()
+                    .ok_or_else(|| self.unsupported(format!("'{field}.{key}' must be a string")))?;
+                Ok((key.clone(), env_from_native(value, env_syntax)))
+            })
+            .collect()
+    }
+}
+
+/// Splits a path template into its token and the relative path after it.
+fn split_template(template: &str, project: bool) -> Result<(&str, &str)> {
+    let invalid = |reason: &str| Error::InvalidDefinition(format!("{reason}: {template}"));
+    let rest = template
+        .strip_prefix('{')
+        .ok_or_else(|| invalid("path template must start with a token"))?;
+    let (token, relative) = rest
+        .split_once('}')
+        .ok_or_else(|| invalid("unterminated token in path template"))?;
+    match token {
+        "home" | "config" | "dot_config" | "data" if !project => {}
+        "project" if project => {}
+        _ => return Err(invalid(&format!("unsupported token {{{token}}}"))),
+    }
+    let relative = relative.trim_start_matches(['/', '\\']);
+    if relative.contains(['{', '}']) {
+        return Err(invalid(
+            "tokens are only allowed at the start of a path template",
+        ));
+    }
+    Ok((token, relative))
+}
+
+/// Expands a validated path template.
+fn expand(template: &str, project_root: Option<&Path>) -> Result<PathBuf> {
+    let (token, relative) = split_template(template, project_root.is_some())?;
+    let base = match token {
+        "home" => platform::home_dir()?,
+        "config" => platform::config_dir()?,
+        "dot_config" => platform::dot_config_dir()?,
+        "data" => platform::data_dir()?,
+        _ => project_root.map(Path::to_path_buf).unwrap_or_default(),
+    };
+    Ok(if relative.is_empty() {
+        base
+    } else {
+        base.join(relative)
+    })
+}
+
+fn mcp_format(file: &str) -> Result<FileFormat> {
+    match Path::new(file).extension().and_then(|e| e.to_str()) {
+        Some("json") => Ok(FileFormat::Json),
+        Some("jsonc") => Ok(FileFormat::Jsonc),
+        Some("yaml" | "yml") => Ok(FileFormat::Yaml),
+        _ => Err(Error::InvalidDefinition(format!(
+            "unsupported MCP file type: {file}"
+        ))),
+    }
+}
+
+fn env_from_native(value: &str, env_syntax: &str) -> EnvValue {
+    let (open, close) = env_syntax
+        .split_once(ENV_VAR_PLACEHOLDER)
+        .unwrap_or((env_syntax, ""));
+    match crate::types::parse_reference(value, open, close) {
+        EnvValue::EnvRef { env, .. }
+            if env.is_empty() || !env.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') =>
+        {
+            EnvValue::plain(value)
+        }
+        parsed => parsed,
+    }
+}
+
+fn env_to_native(value: &EnvValue, env_syntax: &str) -> String {
+    match value {
+        EnvValue::Plain(s) => s.clone(),
+        EnvValue::EnvRef { env, prefix } => format!(
+            "{}{}",
+            prefix.as_deref().unwrap_or_default(),
+            env_syntax.replacen(ENV_VAR_PLACEHOLDER, env, 1)
+        ),
+    }
+}
+
+fn native_map(map: &HashMap<String, EnvValue>, env_syntax: &str) -> serde_json::Value {
+    let sorted: BTreeMap<_, _> = map.iter().collect();
+    sorted
+        .into_iter()
+        .map(|(k, v)| (k.clone(), env_to_native(v, env_syntax).into()))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+impl HarnessBackend for HarnessDefinition {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn binary_names(&self) -> Vec<&str> {
+        self.binaries.iter().map(String::as_str).collect()
+    }
+
+    fn project_markers(&self) -> Vec<&str> {
+        self.project_markers.iter().map(String::as_str).collect()
+    }
+
+    fn is_installed(&self) -> bool {
+        self.binaries
+            .iter()
+            .any(|name| matches!(crate::detection::find_binary(name), Ok(Some(_))))
+    }
+
+    fn is_installed_with(&self, binary_found: bool) -> bool {
+        binary_found
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        match scope {
+            Scope::Global => {
+                let env_dir = self
+                    .paths
+                    .global_env
+                    .as_deref()
+                    .and_then(platform::var)
+                    .map(PathBuf::from)
+                    .filter(|path| path.is_absolute());
+                match env_dir {
+                    Some(dir) => Ok(dir),
+                    None => expand(&self.paths.global, None),
+                }
+            }
+            Scope::Project(root) => match &self.paths.project {
+                Some(template) => expand(template, Some(root)),
+                None => Err(Error::UnsupportedScope {
+                    harness: self.name.clone(),
+                    scope: "project".into(),
+                }),
+            },
+            Scope::Custom(path) => Ok(path.clone()),
+        }
+    }
+
+    fn resource(&self, kind: ResourceKind, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let markdown = DirectoryStructure::Flat {
+            file_pattern: "*.md".into(),
+        };
+        let frontmatter = FileFormat::MarkdownWithFrontmatter;
+        match kind {
+            ResourceKind::Skills => self.directory(
+                scope,
+                self.resources.skills.as_ref(),
+                DirectoryStructure::Nested {
+                    subdir_pattern: "*".into(),
+                    file_name: "SKILL.md".into(),
+                },
+                frontmatter,
+            ),
+            ResourceKind::Commands => self.directory(
+                scope,
+                self.resources.commands.as_ref(),
+                markdown,
+                frontmatter,
+            ),
+            ResourceKind::Agents => {
+                self.directory(scope, self.resources.agents.as_ref(), markdown, frontmatter)
+            }
+            ResourceKind::Plugins => Ok(None),
+        }
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        self.directory(
+            scope,
+            self.resources.rules.as_ref(),
+            DirectoryStructure::Flat {
+                file_pattern: "*.md".into(),
+            },
+            FileFormat::Markdown,
+        )
+    }
+
+    fn mcp(&self, scope: &Scope) -> Result<Option<ConfigResource>> {
+        let Some(mcp) = &self.mcp else {
+            return Ok(None);
+        };
+        let file = self.config_dir(scope)?.join(&mcp.file);
+        Ok(Some(ConfigResource {
+            file_exists: crate::fs::exists_for(&file, None),
+            file,
+            key_path: mcp.key_path.clone(),
+            format: mcp_format(&mcp.file)?,
+            schema_url: mcp.schema_url.clone(),
+        }))
+    }
+
+    fn mcp_key_path(&self) -> Option<&str> {
+        self.mcp.as_ref().map(|mcp| mcp.key_path.as_str())
+    }
+
+    fn mcp_capabilities(&self) -> McpCapabilities {
+        self.mcp
+            .as_ref()
+            .map(|mcp| mcp.capabilities)
+            .unwrap_or_default()
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        let mcp = self.mcp_definition()?;
+        let servers = config
+            .pointer(&mcp.key_path)
+            .and_then(|v| v.as_object())
+            .ok_or_else(|| self.unsupported(format!("Config missing '{}' object", mcp.key_path)))?;
+        servers
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), self.parse_mcp_server(value)?)))
+            .collect()
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        let mcp = self.mcp_definition()?;
+        let env_syntax = &mcp.env_syntax;
+        let enabled = mcp.capabilities.enabled_semantics.is_enabled(value);
+        let obj = value
+            .as_object()
+            .ok_or_else(|| self.unsupported("Server configuration must be an object"))?;
+
+        if let Some(url) = obj.get("url") {
+            let url = url
+                .as_str()
+                .ok_or_else(|| self.unsupported("'url' must be a string"))?
+                .to_string();
+            let headers = self.string_map(obj, "headers", env_syntax)?;
+            return Ok(match obj.get("type").and_then(|v| v.as_str()) {
+                Some("sse") => McpServer::Sse(SseMcpServer {
+                    url,
+                    headers,
+                    enabled,
+                    timeout_ms: None,
+                }),
+                _ => McpServer::Http(HttpMcpServer {
+                    url,
+                    headers,
+                    oauth: None,
+                    enabled,
+                    timeout_ms: None,
+                }),
+            });
+        }
+
+        let command = obj
+            .get("command")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| self.unsupported("Stdio server missing 'command' field"))?
+            .to_string();
+        let args = match obj.get("args") {
+            Some(args) => args
+                .as_array()
+                .ok_or_else(|| self.unsupported("'args' must be an array"))?
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    v.as_str()
+                        .map(String::from)
+                        .ok_or_else(|| self.unsupported(format!("args[{i}] must be a string")))
+                })
+                .collect::<Result<_>>()?,
+            None => Vec::new(),
+        };
+        let cwd = obj.get("cwd").and_then(|v| v.as_str()).map(PathBuf::from);
+
+        Ok(McpServer::Stdio(StdioMcpServer {
+            command,
+            args,
+            env: self.string_map(obj, "env", env_syntax)?,
+            cwd,
+            enabled,
+            timeout_ms: None,
+        }))
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        let mcp = self.mcp_definition()?;
+        server.check_capabilities(&mcp.capabilities, &self.name)?;
+        let env_syntax = &mcp.env_syntax;
+        let mut native = match server {
+            McpServer::Stdio(s) => {
+                let mut obj = serde_json::json!({
+                    "command": s.command,
+                    "args": s.args,
+                });
+                if !s.env.is_empty() {
+                    obj["env"] = native_map(&s.env, env_syntax);
+                }
+                if let Some(cwd) = &s.cwd {
+                    obj["cwd"] = cwd.to_string_lossy().into();
+                }
+                Ok(obj)
+            }
+            McpServer::Sse(s) => {
+                let mut obj = serde_json::json!({ "type": "sse", "url": s.url });
+                if !s.headers.is_empty() {
+                    obj["headers"] = native_map(&s.headers, env_syntax);
+                }
+                Ok(obj)
+            }
+            McpServer::Http(h) => {
+                let mut obj = serde_json::json!({ "type": "http", "url": h.url });
+                if !h.headers.is_empty() {
+                    obj["headers"] = native_map(&h.headers, env_syntax);
+                }
+                Ok(obj)
+            }
+            McpServer::GooseNative(g) => Err(self.unsupported(format!(
+                "Goose {:?} extension '{}' not supported",
+                g.kind, g.name
+            ))),
+        }?;
+        let semantics = mcp.capabilities.enabled_semantics;
+        if !server.is_enabled()
+            && let (Some(key), Some(flag)) = (semantics.flag_key(), semantics.flag_value(false))
+        {
+            native[key] = flag.into();
+        }
+        Ok(native)
+    }
+
+    fn skill_capabilities(&self) -> Option<SkillCapabilities> {
+        self.resources.skills.as_ref().map(|_| SkillCapabilities {
+            name_format: NameFormat::Any,
+            name_must_match_directory: false,
+            description_required: false,
+        })
+    }
+
+    fn command_capabilities(&self) -> Option<CommandCapabilities> {
+        self.resources
+            .commands
+            .as_ref()
+            .map(|_| CommandCapabilities {
+                arguments_placeholder: true,
+                positional_placeholders: true,
+                argument_hint: true,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    const ACME: &str = r#"
+        name = "acme"
+        binaries = ["acme"]
+        project_markers = [".acme"]
+
+        [paths]
+        global = "{home}/.acme"
+        global_env = "ACME_CONFIG_DIR"
+        project = "{project}/.acme"
+
+        [resources]
+        skills = "skills"
+        rules = "rules"
+
+        [mcp]
+        file = "mcp.json"
+        key_path = "/servers"
+        env_syntax = "{env:VAR}"
+
+        [mcp.capabilities]
+        stdio = true
+        http = true
+    "#;
+
+    fn acme() -> HarnessDefinition {
+        HarnessDefinition::from_toml_str(ACME).unwrap()
+    }
+
+    #[test]
+    fn parses_toml() {
+        let def = acme();
+        assert_eq!(def.name, "acme");
+        assert_eq!(def.binary_names(), ["acme"]);
+        assert_eq!(def.project_markers(), [".acme"]);
+        let caps = def.mcp_capabilities();
+        assert!(caps.stdio && caps.http && !caps.sse);
+    }
+
+    #[test]
+    fn expands_path_templates() {
+        let def = acme();
+        let ctx =
+            PathContext::fake("/fake").with_fs(MemoryFs::new().with_dir("/fake/home/.acme/skills"));
+        ctx.scope(|| {
+            assert_eq!(
+                def.config_dir(&Scope::Global).unwrap(),
+                Path::new("/fake/home/.acme")
+            );
+            let skills = def.resource(ResourceKind::Skills, &Scope::Global).unwrap();
+            assert!(skills.unwrap().exists);
+            let project = Scope::Project("/repo".into());
+            let rules = def.rules(&project).unwrap().unwrap();
+            assert_eq!(rules.path, Path::new("/repo/.acme/rules"));
+            assert!(!rules.exists);
+            assert!(
+                def.resource(ResourceKind::Commands, &project)
+                    .unwrap()
+                    .is_none()
+            );
+        });
+    }
+
+    #[test]
+    fn env_var_overrides_global_dir() {
+        let def = acme();
+        let ctx = PathContext::fake("/fake").with_var("ACME_CONFIG_DIR", "/opt/acme");
+        let dir = ctx.scope(|| def.config_dir(&Scope::Global)).unwrap();
+        assert_eq!(dir, Path::new("/opt/acme"));
+    }
+
+    #[test]
+    fn project_scope_requires_template() {
+        let def = HarnessDefinition::from_toml_str(
+            "name = \"global-only\"\n[paths]\nglobal = \"{config}/global-only\"\n",
+        )
+        .unwrap();
+        let result = def.config_dir(&Scope::Project("/repo".into()));
+        assert!(matches!(result, Err(Error::UnsupportedScope { .. })));
+        assert!(def.mcp(&Scope::Global).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        for global in ["~/.acme", "{project}/.acme", "{home/.acme", "{home}/{data}"] {
+            let toml = format!("name = \"x\"\n[paths]\nglobal = \"{global}\"\n");
+            let result = HarnessDefinition::from_toml_str(&toml);
+            assert!(
+                matches!(result, Err(Error::InvalidDefinition(_))),
+                "{global}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let result = HarnessDefinition::from_toml_str(
+            "name = \"x\"\nbinary = \"x\"\n[paths]\nglobal = \"{home}\"\n",
+        );
+        assert!(matches!(result, Err(Error::TomlParse(_))));
+    }
+
+    #[test]
+    fn mcp_round_trips_with_env_syntax() {
+        let def = acme();
+        let config = json!({
+            "servers": {
+                "db": {
+                    "command": "db-mcp",
+                    "args": ["--stdio"],
+                    "env": { "TOKEN": "{env:DB_TOKEN}", "MODE": "ro" }
+                }
+            }
+        });
+        let servers = def.parse_mcp_servers(&config).unwrap();
+        assert_eq!(servers.len(), 1);
+        let McpServer::Stdio(stdio) = &servers[0].1 else {
+            panic!("expected stdio server");
+        };
+        assert_eq!(stdio.env["TOKEN"], EnvValue::env("DB_TOKEN"));
+        assert_eq!(stdio.env["MODE"], EnvValue::plain("ro"));
+
+        let native = def.mcp_to_native("db", &servers[0].1).unwrap();
+        assert_eq!(native, config["servers"]["db"]);
+    }
+
+    #[test]
+    fn mcp_disabled_flag_follows_enabled_semantics() {
+        let crush = ACME.replace(
+            "http = true",
+            "http = true\ntoggle = true\nenabled_semantics = \"disabled_key\"",
+        );
+        let def = HarnessDefinition::from_toml_str(&crush).unwrap();
+        let config = json!({
+            "servers": { "db": { "command": "db-mcp", "args": [], "disabled": true } }
+        });
+        let servers = def.parse_mcp_servers(&config).unwrap();
+        assert!(!servers[0].1.is_enabled());
+        let native = def.mcp_to_native("db", &servers[0].1).unwrap();
+        assert_eq!(native, config["servers"]["db"]);
+
+        let half = ACME.replace("http = true", "http = true\ntoggle = true");
+        assert!(matches!(
+            HarnessDefinition::from_toml_str(&half),
+            Err(Error::InvalidDefinition(_))
+        ));
+    }
+
+    #[test]
+    fn mcp_to_native_checks_declared_capabilities() {
+        let def = acme();
+        let sse = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".into(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+        });
+        let err = def.mcp_to_native("remote", &sse).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedMcpConfig { harness, .. } if harness == "acme"));
+    }
+
+    #[test]
+    fn from_file_reads_json() {
+        let json = json!({
+            "name": "json-harness",
+            "paths": { "global": "{data}/json-harness" },
+            "mcp": {
+                "file": "servers.yaml",
+                "key_path": "/mcp",
+                "schema_url": "https://example.com/schema.json"
+            }
+        });
+        let ctx = PathContext::fake("/fake")
+            .with_fs(MemoryFs::new().with_file("/defs/json-harness.json", json.to_string()));
+        let def = ctx
+            .scope(|| HarnessDefinition::from_file("/defs/json-harness.json"))
+            .unwrap();
+        let mcp = def.mcp.as_ref().unwrap();
+        assert_eq!(mcp.env_syntax, "${VAR}");
+        assert_eq!(mcp.capabilities, stdio_only());
+
+        let config = ctx.scope(|| def.mcp(&Scope::Global)).unwrap().unwrap();
+        assert_eq!(
+            config.file,
+            Path::new("/fake/data/json-harness/servers.yaml")
+        );
+        assert_eq!(config.format, FileFormat::Yaml);
+        assert_eq!(
+            config.schema_url.as_deref(),
+            Some("https://example.com/schema.json")
+        );
+    }
+
+    #[test]
+    fn from_file_rejects_other_extensions() {
+        let result = HarnessDefinition::from_file("/defs/harness.yaml");
+        assert!(matches!(result, Err(Error::InvalidDefinition(_))));
+    }
+}
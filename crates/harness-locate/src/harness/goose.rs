@@ -1,28 +1,60 @@
 //! Goose harness implementation.
 //!
 //! Goose stores its configuration in:
-//! - **Global**: `~/.config/goose/`
+//! - **Global**: `~/.config/goose/` (`%APPDATA%\Block\goose\config\` on Windows)
 //! - **Project**: `.goose/` in project root (if exists)
 
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use super::backend::{self, HarnessBackend};
+#[cfg(feature = "yaml")]
+use crate::document::McpConfigDocument;
 use crate::error::{Error, Result};
-use crate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use crate::fs::AccessReason;
+use crate::mcp::{
+    GooseExtension, GooseExtensionKind, HttpMcpServer, McpCapabilities, McpServer, SseMcpServer,
+    StdioMcpServer,
+};
 use crate::platform;
-use crate::types::{EnvValue, Scope};
+use crate::types::{
+    ConfigResource, DirectoryResource, EnvValue, FileFormat, HarnessKind, ResourceKind, Scope,
+};
+#[cfg(feature = "validation")]
+use crate::validation::ValidationIssue;
+use crate::validation::{CommandCapabilities, SkillCapabilities};
+
+/// Name of the Goose config file inside the config directory.
+const CONFIG_FILE: &str = "config.yaml";
 
 /// Returns the global Goose configuration directory.
 ///
-/// Returns `~/.config/goose/` on all platforms.
+/// Returns `~/.config/goose/` on macOS and Linux. On Windows, Goose uses its
+/// native app layout at `%APPDATA%\Block\goose\config\`.
 ///
 /// # Errors
 ///
 /// Returns an error if the home directory cannot be determined.
+#[cfg(not(target_os = "windows"))]
 pub fn global_config_dir() -> Result<PathBuf> {
     Ok(platform::config_dir()?.join("goose"))
 }
 
+/// Returns the global Goose configuration directory.
+///
+/// Returns `%APPDATA%\Block\goose\config\` on Windows.
+///
+/// # Errors
+///
+/// Returns an error if the `APPDATA` environment variable is not set.
+#[cfg(target_os = "windows")]
+pub fn global_config_dir() -> Result<PathBuf> {
+    Ok(platform::config_dir()?
+        .join("Block")
+        .join("goose")
+        .join("config"))
+}
+
 /// Returns the project-local Goose configuration directory.
 ///
 /// # Arguments
@@ -70,7 +102,7 @@ pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
 pub fn skills_dir(scope: &Scope) -> Option<PathBuf> {
     match scope {
         Scope::Global => {
-            let config = platform::config_dir().ok()?;
+            let config = platform::dot_config_dir().ok()?;
             Some(config.join("agents").join("skills"))
         }
         Scope::Project(root) => Some(root.join(".agents").join("skills")),
@@ -92,11 +124,56 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
     }
 }
 
+/// Returns the global Goose data directory, holding `sessions/`.
+///
+/// Returns `$XDG_DATA_HOME/goose/` or `~/.local/share/goose/` on macOS
+/// and Linux, and `%APPDATA%\Block\goose\data\` on Windows.
+///
+/// # Errors
+///
+/// Returns an error if the home or app data directory cannot be
+/// determined.
+pub fn global_data_dir() -> Result<PathBuf> {
+    if cfg!(target_os = "windows") {
+        Ok(platform::config_dir()?
+            .join("Block")
+            .join("goose")
+            .join("data"))
+    } else {
+        Ok(platform::xdg_dir("XDG_DATA_HOME", ".local/share")?.join("goose"))
+    }
+}
+
+/// Returns where Goose writes its logs.
+///
+/// Returns `$XDG_STATE_HOME/goose/logs/` or `~/.local/state/goose/logs/`
+/// on macOS and Linux, and `logs\` in the
+/// [data directory](global_data_dir) on Windows.
+///
+/// # Errors
+///
+/// Returns an error if the home or app data directory cannot be
+/// determined.
+pub fn logs_dir() -> Result<PathBuf> {
+    if cfg!(target_os = "windows") {
+        Ok(global_data_dir()?.join("logs"))
+    } else {
+        Ok(platform::xdg_dir("XDG_STATE_HOME", ".local/state")?
+            .join("goose")
+            .join("logs"))
+    }
+}
+
 /// Checks if Goose is installed on this system.
 ///
 /// Currently checks if the global config directory exists.
 pub fn is_installed() -> bool {
-    global_config_dir().map(|p| p.exists()).unwrap_or(false)
+    global_config_dir().is_ok_and(|p| {
+        crate::fs::exists_for(
+            &p,
+            AccessReason::new(HarnessKind::Goose, None, "installation"),
+        )
+    })
 }
 
 /// Parses a single MCP server from Goose's native JSON format.
@@ -263,6 +340,71 @@ pub(crate) fn parse_mcp_server(value: &serde_json::Value) -> Result<McpServer> {
                 timeout_ms,
             }))
         }
+        "builtin" | "platform" | "inline_python" => {
+            let kind = match server_type {
+                "builtin" => GooseExtensionKind::Builtin,
+                "platform" => GooseExtensionKind::Platform,
+                _ => GooseExtensionKind::InlinePython,
+            };
+            let name = obj
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| Error::UnsupportedMcpConfig {
+                    harness: "Goose".into(),
+                    reason: "Missing 'name' field".into(),
+                })?
+                .to_string();
+            let optional_string = |field: &str| -> Result<Option<String>> {
+                match obj.get(field) {
+                    None | Some(serde_json::Value::Null) => Ok(None),
+                    Some(v) => v.as_str().map(|s| Some(s.to_string())).ok_or_else(|| {
+                        Error::UnsupportedMcpConfig {
+                            harness: "Goose".into(),
+                            reason: format!("'{field}' must be a string"),
+                        }
+                    }),
+                }
+            };
+            let code = optional_string("code")?;
+            if kind == GooseExtensionKind::InlinePython && code.is_none() {
+                return Err(Error::UnsupportedMcpConfig {
+                    harness: "Goose".into(),
+                    reason: "Missing 'code' field".into(),
+                });
+            }
+
+            let dependencies = match obj.get("dependencies") {
+                None | Some(serde_json::Value::Null) => Vec::new(),
+                Some(value) => value
+                    .as_array()
+                    .ok_or_else(|| Error::UnsupportedMcpConfig {
+                        harness: "Goose".into(),
+                        reason: "'dependencies' must be an array".into(),
+                    })?
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        v.as_str()
+                            .ok_or_else(|| Error::UnsupportedMcpConfig {
+                                harness: "Goose".into(),
+                                reason: format!("dependencies[{}] must be a string", i),
+                            })
+                            .map(String::from)
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+            };
+
+            Ok(McpServer::GooseNative(GooseExtension {
+                kind,
+                name,
+                display_name: optional_string("display_name")?,
+                description: optional_string("description")?.filter(|d| !d.is_empty()),
+                code,
+                dependencies,
+                enabled,
+                timeout_ms,
+            }))
+        }
         _ => Err(Error::UnsupportedMcpConfig {
             harness: "Goose".into(),
             reason: format!("Unknown server type: {}", server_type),
@@ -300,6 +442,402 @@ pub(crate) fn parse_mcp_servers(config: &serde_json::Value) -> Result<Vec<(Strin
     Ok(servers)
 }
 
+/// Top-level key holding the provider name.
+#[cfg(feature = "yaml")]
+const PROVIDER_KEY: &str = "GOOSE_PROVIDER";
+/// Top-level key holding the model name.
+#[cfg(feature = "yaml")]
+const MODEL_KEY: &str = "GOOSE_MODEL";
+/// Top-level key holding the approval mode.
+#[cfg(feature = "yaml")]
+const MODE_KEY: &str = "GOOSE_MODE";
+/// Top-level key holding MCP extensions.
+#[cfg(feature = "yaml")]
+const EXTENSIONS_KEY: &str = "extensions";
+
+/// Goose's `config.yaml`, with the keys this crate understands typed and
+/// everything else passed through.
+///
+/// The file mixes MCP extensions with provider, model and experimental
+/// settings. Updates only touch the `extensions` block, so writing back
+/// keeps the values and order of the other keys. Comments are not kept:
+/// saving rewrites the file from its parsed values.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::harness::goose::GooseConfig;
+///
+/// let config = GooseConfig::parse(
+///     "config.yaml",
+///     "GOOSE_PROVIDER: openai\nGOOSE_MODEL: gpt-4o\nOPENAI_HOST: https://api.openai.com\n",
+/// )?;
+/// assert_eq!(config.provider(), Some("openai"));
+/// assert_eq!(config.model(), Some("gpt-4o"));
+/// assert!(config.get("OPENAI_HOST").is_some());
+/// assert!(config.extensions()?.is_empty());
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+#[cfg(feature = "yaml")]
+#[derive(Debug, Clone)]
+pub struct GooseConfig {
+    document: McpConfigDocument,
+}
+
+#[cfg(feature = "yaml")]
+impl GooseConfig {
+    /// Loads `config.yaml` for `scope`.
+    ///
+    /// A missing file loads as an empty config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory cannot be determined or the
+    /// file cannot be read or parsed.
+    pub fn load(scope: &Scope) -> Result<Self> {
+        let file = config_dir(scope)?.join(CONFIG_FILE);
+        let decoded = match crate::fs::read_text_for(
+            &file,
+            AccessReason::new(HarnessKind::Goose, None, "config"),
+        ) {
+            Ok(decoded) => decoded,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                crate::text::DecodedText::default()
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let mut config = Self::parse(file, &decoded.text)?;
+        config.document = config.document.with_normalization(decoded.normalization);
+        Ok(config)
+    }
+
+    /// Parses `content` as the config stored at `file`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `content` is not a YAML mapping, or
+    /// [`Error::YamlLint`] with line numbers if it is indented with tabs or
+    /// repeats a key.
+    pub fn parse(file: impl Into<PathBuf>, content: &str) -> Result<Self> {
+        let document = McpConfigDocument::parse(file, MCP_KEY_PATH, FileFormat::Yaml, content)?;
+        Ok(Self { document })
+    }
+
+    /// Returns the configured provider (`GOOSE_PROVIDER`).
+    #[must_use]
+    pub fn provider(&self) -> Option<&str> {
+        self.get(PROVIDER_KEY)?.as_str()
+    }
+
+    /// Returns the configured model (`GOOSE_MODEL`).
+    #[must_use]
+    pub fn model(&self) -> Option<&str> {
+        self.get(MODEL_KEY)?.as_str()
+    }
+
+    /// Returns the tool approval mode (`GOOSE_MODE`), such as `"auto"`.
+    #[must_use]
+    pub fn mode(&self) -> Option<&str> {
+        self.get(MODE_KEY)?.as_str()
+    }
+
+    /// Returns the raw value of a top-level key.
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&serde_json::Value> {
+        self.document.root().get(key)
+    }
+
+    /// Returns the top-level keys other than `extensions` and the typed
+    /// settings, such as provider credentials and experiment flags, in file
+    /// order.
+    pub fn passthrough(&self) -> impl Iterator<Item = (&str, &serde_json::Value)> {
+        self.document
+            .root()
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (key.as_str(), value))
+            .filter(|(key, _)| ![PROVIDER_KEY, MODEL_KEY, MODE_KEY, EXTENSIONS_KEY].contains(key))
+    }
+
+    /// Returns the MCP extensions in file order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an extension entry is malformed.
+    pub fn extensions(&self) -> Result<Vec<(String, McpServer)>> {
+        self.document
+            .servers()
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| Ok((name.clone(), parse_mcp_server(value)?)))
+            .collect()
+    }
+
+    /// Adds or replaces the extension `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `server` cannot be written in Goose's format.
+    pub fn set_extension(&mut self, name: &str, server: &McpServer) -> Result<()> {
+        let value = Goose.mcp_to_native(name, server)?;
+        self.document.set(name, value);
+        Ok(())
+    }
+
+    /// Removes the extension `name`, returning `true` if it existed.
+    pub fn remove_extension(&mut self, name: &str) -> bool {
+        self.document.remove(name).is_some()
+    }
+
+    /// Returns `true` if extensions changed since the config was loaded or
+    /// last saved.
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.document.is_dirty()
+    }
+
+    /// Renders the config as YAML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config cannot be serialized.
+    pub fn render(&self) -> Result<String> {
+        self.document.render()
+    }
+
+    /// Writes the config back if extensions changed, recording the change
+    /// in the [journal](crate::journal) as `goose.config.save`.
+    ///
+    /// The file keeps its encoding and line endings; comments are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&mut self) -> Result<()> {
+        self.document.save_as("goose.config.save")
+    }
+
+    /// Renders `server` as a commented YAML fragment to paste into
+    /// `config.yaml`, for tools that show users what to add rather than
+    /// writing the file.
+    ///
+    /// Keys are sorted as Goose writes them. Secret values, and every env
+    /// reference, are written as [`REDACTED`](crate::mcp::REDACTED), with
+    /// a comment naming what to fill in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `server` cannot be written in Goose's format.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::harness::goose::GooseConfig;
+    /// use harness_locate::mcp::{McpServer, StdioMcpServer};
+    ///
+    /// let server = McpServer::Stdio(StdioMcpServer {
+    ///     command: "npx".to_string(),
+    ///     args: vec!["-y".to_string(), "mcp-fs".to_string()],
+    ///     env: Default::default(),
+    ///     cwd: None,
+    ///     enabled: true,
+    ///     timeout_ms: None,
+    /// });
+    /// let snippet = GooseConfig::extension_snippet("fs", &server)?;
+    /// assert!(snippet.contains("extensions:\n  fs:\n    args:\n    - -y\n"));
+    /// assert_eq!(GooseConfig::parse_extension_snippet(&snippet)?, [("fs".to_string(), server)]);
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn extension_snippet(name: &str, server: &McpServer) -> Result<String> {
+        server.validate_capabilities(HarnessKind::Goose)?;
+        let value = server
+            .redacted(HarnessKind::Goose)
+            .to_goose_value(HarnessKind::Goose, name)?;
+        let entry = serde_json::json!({ EXTENSIONS_KEY: { name: sorted_keys(value) } });
+
+        let mut snippet = format!(
+            "# MCP extension `{name}` for Goose.\n\
+             # Append to config.yaml. If the file already has an `extensions:` key,\n\
+             # paste only the indented lines below it.\n"
+        );
+        let redacted: Vec<&str> = match server {
+            McpServer::Stdio(stdio) => {
+                let mut names: Vec<&str> = stdio
+                    .env
+                    .iter()
+                    .filter(|(key, value)| {
+                        matches!(value, EnvValue::EnvRef { .. })
+                            || crate::validation::is_sensitive_name(key)
+                    })
+                    .map(|(key, _)| key.as_str())
+                    .collect();
+                names.sort_unstable();
+                names
+            }
+            _ => Vec::new(),
+        };
+        if !redacted.is_empty() {
+            snippet.push_str(&format!(
+                "# Fill in the envs written as {}: {}\n",
+                crate::mcp::REDACTED,
+                redacted.join(", ")
+            ));
+        }
+        snippet.push_str(&serde_yaml::to_string(&entry)?);
+        Ok(snippet)
+    }
+
+    /// Reads the extensions from a fragment of `config.yaml`, such as one
+    /// made by [`extension_snippet`](Self::extension_snippet).
+    ///
+    /// The fragment may include the `extensions:` key or hold only the
+    /// entries under it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::YamlLint`] if the fragment is indented with tabs or
+    /// repeats a key, a parse error if it is otherwise invalid YAML, and
+    /// `Error::UnsupportedMcpConfig` if an entry is malformed.
+    pub fn parse_extension_snippet(snippet: &str) -> Result<Vec<(String, McpServer)>> {
+        let diagnostics = crate::yamllint::lint_yaml(snippet);
+        if !diagnostics.is_empty() {
+            return Err(Error::YamlLint {
+                file: PathBuf::from(CONFIG_FILE),
+                diagnostics,
+            });
+        }
+        let root: serde_json::Value = match serde_yaml::from_str(snippet)? {
+            serde_json::Value::Null => return Ok(Vec::new()),
+            root => root,
+        };
+        let config = if root.get(EXTENSIONS_KEY).is_some() {
+            root
+        } else {
+            serde_json::json!({ EXTENSIONS_KEY: root })
+        };
+        parse_mcp_servers(&config)
+    }
+}
+
+/// Returns `value` with the keys of every object in sorted order.
+#[cfg(feature = "yaml")]
+fn sorted_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            entries
+                .into_iter()
+                .map(|(key, value)| (key, sorted_keys(value)))
+                .collect()
+        }
+        other => other,
+    }
+}
+
+/// JSON pointer to the MCP servers in the config file.
+const MCP_KEY_PATH: &str = "/extensions";
+
+/// The Goose [`HarnessBackend`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Goose;
+
+impl HarnessBackend for Goose {
+    fn name(&self) -> &str {
+        HarnessKind::Goose.as_str()
+    }
+
+    fn kind(&self) -> Option<HarnessKind> {
+        Some(HarnessKind::Goose)
+    }
+
+    fn binary_names(&self) -> Vec<&str> {
+        HarnessKind::Goose.binary_names().to_vec()
+    }
+
+    fn project_markers(&self) -> Vec<&str> {
+        HarnessKind::Goose.project_markers().to_vec()
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn resource(&self, kind: ResourceKind, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        match kind {
+            ResourceKind::Skills => backend::skills_directory(
+                HarnessKind::Goose,
+                skills_dir(scope),
+                FileFormat::Markdown,
+            ),
+            ResourceKind::Commands | ResourceKind::Agents | ResourceKind::Plugins => Ok(None),
+        }
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        backend::rules_directory(HarnessKind::Goose, rules_dir(scope))
+    }
+
+    fn mcp(&self, scope: &Scope) -> Result<Option<ConfigResource>> {
+        backend::mcp_config(
+            HarnessKind::Goose,
+            config_dir(scope)?.join(CONFIG_FILE),
+            MCP_KEY_PATH,
+            FileFormat::Yaml,
+        )
+    }
+
+    fn mcp_key_path(&self) -> Option<&str> {
+        Some(MCP_KEY_PATH)
+    }
+
+    fn data_dir(&self, scope: &Scope) -> Result<Option<PathBuf>> {
+        match scope {
+            Scope::Global => global_data_dir().map(Some),
+            Scope::Project(_) | Scope::Custom(_) => Ok(None),
+        }
+    }
+
+    fn logs_dir(&self) -> Result<Option<PathBuf>> {
+        logs_dir().map(Some)
+    }
+
+    fn mcp_capabilities(&self) -> McpCapabilities {
+        McpCapabilities::for_kind(HarnessKind::Goose)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn mcp_to_native(&self, name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.validate_capabilities(HarnessKind::Goose)?;
+        server.to_goose_value(HarnessKind::Goose, name)
+    }
+
+    #[cfg(feature = "validation")]
+    fn validate_mcp_server(&self, server: &McpServer) -> Vec<ValidationIssue> {
+        crate::validation::validate_for_harness(server, HarnessKind::Goose)
+    }
+
+    fn skill_capabilities(&self) -> Option<SkillCapabilities> {
+        SkillCapabilities::for_kind(HarnessKind::Goose)
+    }
+
+    fn command_capabilities(&self) -> Option<CommandCapabilities> {
+        CommandCapabilities::for_kind(HarnessKind::Goose)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -821,4 +1359,235 @@ mod tests {
             panic!("Expected Stdio variant");
         }
     }
+
+    #[test]
+    fn parse_builtin_extension() {
+        let json = json!({
+            "type": "builtin",
+            "name": "developer",
+            "display_name": "Developer",
+            "description": "",
+            "bundled": true,
+            "enabled": true,
+            "timeout": 300
+        });
+
+        let parsed = parse_mcp_server(&json).unwrap();
+        let McpServer::GooseNative(extension) = &parsed else {
+            panic!("Expected GooseNative variant");
+        };
+        assert_eq!(extension.kind, GooseExtensionKind::Builtin);
+        assert_eq!(extension.name, "developer");
+        assert_eq!(extension.display_name.as_deref(), Some("Developer"));
+        assert_eq!(extension.description, None);
+        assert_eq!(extension.timeout_ms, Some(300_000));
+
+        let native = parsed
+            .to_native_value(HarnessKind::Goose, "developer")
+            .unwrap();
+        assert_eq!(native["type"], "builtin");
+        assert_eq!(native["name"], "developer");
+        assert_eq!(native["timeout"], 300);
+        assert_eq!(parse_mcp_server(&native).unwrap(), parsed);
+    }
+
+    #[test]
+    fn parse_inline_python_extension() {
+        let json = json!({
+            "type": "inline_python",
+            "name": "word_count",
+            "code": "print('hi')",
+            "dependencies": ["mcp"]
+        });
+
+        let parsed = parse_mcp_server(&json).unwrap();
+        let McpServer::GooseNative(extension) = &parsed else {
+            panic!("Expected GooseNative variant");
+        };
+        assert_eq!(extension.kind, GooseExtensionKind::InlinePython);
+        assert_eq!(extension.code.as_deref(), Some("print('hi')"));
+        assert_eq!(extension.dependencies, vec!["mcp"]);
+    }
+
+    #[test]
+    fn parse_inline_python_requires_code() {
+        let json = json!({"type": "inline_python", "name": "word_count"});
+        let err = parse_mcp_server(&json).unwrap_err();
+        assert!(err.to_string().contains("code"));
+    }
+
+    #[test]
+    fn goose_native_extensions_do_not_convert_to_other_harnesses() {
+        let server = McpServer::GooseNative(GooseExtension::builtin("memory"));
+        for kind in HarnessKind::ALL {
+            let result = server.to_native_value(*kind, "memory");
+            assert_eq!(result.is_ok(), *kind == HarnessKind::Goose, "{kind}");
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    const GOOSE_CONFIG: &str = "\
+GOOSE_PROVIDER: anthropic
+extensions:
+  developer:
+    bundled: true
+    enabled: true
+    name: developer
+    timeout: 300
+    type: builtin
+GOOSE_MODEL: claude-sonnet
+experiments:
+  GOOSE_SMART_APPROVE: true
+";
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn goose_config_types_known_keys_and_passes_through_the_rest() {
+        let config = GooseConfig::parse("config.yaml", GOOSE_CONFIG).unwrap();
+        assert_eq!(config.provider(), Some("anthropic"));
+        assert_eq!(config.model(), Some("claude-sonnet"));
+        assert_eq!(config.mode(), None);
+
+        let passthrough: Vec<_> = config.passthrough().map(|(key, _)| key).collect();
+        assert_eq!(passthrough, ["experiments"]);
+
+        let extensions = config.extensions().unwrap();
+        assert_eq!(extensions.len(), 1);
+        assert!(matches!(extensions[0].1, McpServer::GooseNative(_)));
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn goose_config_updates_only_extensions() {
+        let mut config = GooseConfig::parse("config.yaml", GOOSE_CONFIG).unwrap();
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "mcp-fs".into(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        config.set_extension("fs", &server).unwrap();
+        assert!(config.remove_extension("developer"));
+        assert!(!config.remove_extension("developer"));
+        assert!(config.is_dirty());
+
+        let rendered = config.render().unwrap();
+        let (before, after) = rendered.split_once("extensions:\n").unwrap();
+        assert_eq!(before, "GOOSE_PROVIDER: anthropic\n");
+        assert!(after.starts_with("  fs:\n"));
+        assert!(
+            after.ends_with(
+                "GOOSE_MODEL: claude-sonnet\nexperiments:\n  GOOSE_SMART_APPROVE: true\n"
+            )
+        );
+
+        let reparsed = GooseConfig::parse("config.yaml", &rendered).unwrap();
+        assert_eq!(reparsed.extensions().unwrap(), [("fs".to_string(), server)]);
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn goose_config_reports_tabs_and_duplicate_keys() {
+        let content =
+            GOOSE_CONFIG.replacen("  developer:", "\tdeveloper:", 1) + "GOOSE_MODEL: gpt-4o\n";
+        let Err(Error::YamlLint { file, diagnostics }) =
+            GooseConfig::parse("config.yaml", &content)
+        else {
+            panic!("expected lint error");
+        };
+        assert_eq!(file, PathBuf::from("config.yaml"));
+        let problems: Vec<_> = diagnostics.iter().map(|d| &d.problem).collect();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0], &crate::yamllint::YamlProblem::TabIndent);
+        assert!(matches!(
+            problems[1],
+            crate::yamllint::YamlProblem::DuplicateKey { key, .. } if key == "GOOSE_MODEL"
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn extension_snippet_redacts_secrets_and_imports_back() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "npx".into(),
+            args: vec!["-y".into(), "@modelcontextprotocol/server-github".into()],
+            env: HashMap::from([
+                ("GITHUB_TOKEN".to_string(), EnvValue::env("GH_TOKEN")),
+                ("LOG_LEVEL".to_string(), EnvValue::plain("debug")),
+            ]),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        let snippet = GooseConfig::extension_snippet("github", &server).unwrap();
+        let yaml = snippet
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(
+            yaml,
+            "extensions:\n  github:\n    args:\n    - -y\n    - '@modelcontextprotocol/server-github'\n    cmd: npx\n    description: ''\n    enabled: true\n    envs:\n      GITHUB_TOKEN: '***'\n      LOG_LEVEL: debug\n    name: github\n    type: stdio"
+        );
+        assert!(snippet.contains("# Fill in the envs written as ***: GITHUB_TOKEN\n"));
+
+        let entries: String = yaml
+            .lines()
+            .skip(1)
+            .map(|line| format!("{line}\n"))
+            .collect();
+        let imported = GooseConfig::parse_extension_snippet(&entries).unwrap();
+        assert_eq!(imported[0].0, "github");
+        let McpServer::Stdio(stdio) = &imported[0].1 else {
+            panic!("expected stdio: {imported:?}");
+        };
+        assert_eq!(stdio.env["LOG_LEVEL"], EnvValue::plain("debug"));
+
+        let sse = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".into(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+        });
+        assert!(GooseConfig::extension_snippet("sse", &sse).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn goose_config_load_and_save() {
+        use crate::fs::MemoryFs;
+        use crate::platform::PathContext;
+
+        let ctx = PathContext::fake("/fake").with_fs(MemoryFs::new());
+        ctx.scope(|| {
+            let mut config = GooseConfig::load(&Scope::Global).unwrap();
+            assert!(config.extensions().unwrap().is_empty());
+            config.save().unwrap();
+            let file = config_dir(&Scope::Global).unwrap().join(CONFIG_FILE);
+            assert!(!crate::fs::current().exists(&file));
+
+            let server = McpServer::Http(HttpMcpServer {
+                url: "https://example.com/mcp".into(),
+                headers: HashMap::new(),
+                oauth: None,
+                enabled: true,
+                timeout_ms: None,
+            });
+            config.set_extension("remote", &server).unwrap();
+            config.save().unwrap();
+            let loaded = GooseConfig::load(&Scope::Global).unwrap();
+            assert_eq!(loaded.extensions().unwrap()[0].0, "remote");
+
+            let journal = crate::journal::read_journal().unwrap();
+            assert_eq!(journal.len(), 1);
+            assert_eq!(journal[0].operation, "goose.config.save");
+            assert_eq!(journal[0].file, file);
+            assert_eq!(
+                journal[0].diff_hash,
+                crate::journal::diff_hash("", &loaded.render().unwrap())
+            );
+        });
+    }
 }
@@ -1,29 +1,62 @@
 //! Harness discovery and path resolution.
-
-use std::collections::HashMap;
-use std::path::PathBuf;
-
+//!
+//! Each supported harness lives in its own module and implements
+//! [`HarnessBackend`]; [`Harness`] dispatches to it. Harnesses this crate
+//! does not know about can be added to a [`HarnessRegistry`].
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+pub use self::backend::HarnessBackend;
+#[cfg(feature = "registry")]
+pub use self::definition::HarnessDefinition;
+pub use self::registry::HarnessRegistry;
+use crate::document::McpConfigDocument;
+use crate::environment::Environment;
 use crate::error::{Error, Result};
-use crate::mcp::{McpCapabilities, McpServer};
+use crate::fs::AccessReason;
+use crate::mcp::{McpCapabilities, McpParseReport, McpServer, NativeOptions};
+use crate::metrics::MetricKind;
+use crate::observer::{LocateEvent, ResolvedKind};
 use crate::types::{
-    ConfigResource, DirectoryResource, DirectoryStructure, FileFormat, HarnessKind,
-    InstallationStatus, ResourceKind, Scope,
+    ConfigResource, DirectoryResource, DirectoryStructure, HarnessKind, InstallationStatus,
+    ResourceKind, ResourceLookup, ResourceMatch, Scope, TokenStore,
+};
+use crate::validation::{
+    CODE_ENV_GLOBAL_EPHEMERAL, CODE_ENV_NOT_INSTALLED_CI, CODE_OAUTH_NO_CREDENTIALS,
+    ValidationIssue,
 };
 
 pub mod amp_code;
+pub mod backend;
 pub mod claude_code;
 pub mod copilot_cli;
+#[cfg(feature = "registry")]
+pub mod definition;
 pub mod goose;
 pub mod opencode;
+pub mod registry;
 
 /// A discovered harness with resolved base paths.
 ///
 /// Use [`Harness::locate`] to find a harness on the current system.
-#[derive(Debug)]
+///
+/// A harness is either built in, with a [`HarnessKind`], or a custom
+/// backend from a [`HarnessRegistry`]. Two harnesses are equal if they have
+/// the same [name](Harness::name).
+#[derive(Debug, Clone, Copy)]
 pub struct Harness {
-    kind: HarnessKind,
+    backend: &'static dyn HarnessBackend,
 }
 
+impl PartialEq for Harness {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for Harness {}
+
 impl Harness {
     /// Locate a harness on the current system.
     ///
@@ -41,26 +74,50 @@ impl Harness {
     /// use harness_locate::{Harness, HarnessKind};
     ///
     /// let harness = Harness::locate(HarnessKind::ClaudeCode)?;
-    /// println!("Found {} at {:?}", harness.kind(), harness.config(&harness_locate::Scope::Global)?);
+    /// println!("Found {} at {:?}", harness.name(), harness.config(&harness_locate::Scope::Global)?);
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn locate(kind: HarnessKind) -> Result<Self> {
-        let is_installed = match kind {
-            HarnessKind::ClaudeCode => claude_code::is_installed(),
-            HarnessKind::OpenCode => opencode::is_installed(),
-            HarnessKind::Goose => goose::is_installed(),
-            HarnessKind::AmpCode => amp_code::is_installed(),
-            HarnessKind::CopilotCli => copilot_cli::is_installed(),
-        };
-
-        if is_installed {
-            Ok(Self { kind })
+        if backend::builtin(kind).is_installed() {
+            let harness = Self::new(kind);
+            harness.emit_located();
+            Ok(harness)
         } else {
             Err(Error::NotFound(kind.to_string()))
         }
     }
 
-    /// Returns the kind of harness.
+    /// Returns the kind of harness, or `None` for a custom harness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::{Harness, HarnessKind};
+    ///
+    /// let harness = Harness::new(HarnessKind::Goose);
+    /// assert_eq!(harness.kind(), Some(HarnessKind::Goose));
+    /// ```
+    #[must_use]
+    pub fn kind(&self) -> Option<HarnessKind> {
+        self.backend.kind()
+    }
+
+    /// Returns the display name of the harness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::{Harness, HarnessKind};
+    ///
+    /// let harness = Harness::new(HarnessKind::AmpCode);
+    /// assert_eq!(harness.name(), "AMP Code");
+    /// ```
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.backend.name()
+    }
+
+    /// Returns the backend implementing this harness.
     ///
     /// # Examples
     ///
@@ -68,19 +125,35 @@ impl Harness {
     /// use harness_locate::{Harness, HarnessKind};
     ///
     /// let harness = Harness::new(HarnessKind::Goose);
-    /// assert_eq!(harness.kind(), HarnessKind::Goose);
+    /// assert_eq!(harness.backend().name(), "Goose");
     /// ```
     #[must_use]
-    pub fn kind(&self) -> HarnessKind {
-        self.kind
+    pub fn backend(&self) -> &'static dyn HarnessBackend {
+        self.backend
+    }
+
+    pub(crate) fn from_backend(backend: &'static dyn HarnessBackend) -> Self {
+        Self { backend }
+    }
+
+    /// Returns the reason to attribute a filesystem access to, or `None`
+    /// for a custom harness.
+    pub(crate) fn access_reason(
+        &self,
+        resource: Option<ResourceKind>,
+        purpose: &'static str,
+    ) -> Option<AccessReason> {
+        self.kind()
+            .map(|kind| AccessReason::new(kind, resource, purpose))
     }
 
     /// Returns the expected directory name(s) for a resource kind.
     ///
-    /// Delegates to [`HarnessKind::directory_names`].
+    /// Delegates to [`HarnessKind::directory_names`]; custom harnesses
+    /// return `None`.
     #[must_use]
-    pub const fn directory_names(&self, resource: ResourceKind) -> Option<&'static [&'static str]> {
-        self.kind.directory_names(resource)
+    pub fn directory_names(&self, resource: ResourceKind) -> Option<&'static [&'static str]> {
+        self.kind()?.directory_names(resource)
     }
 
     /// Creates a new harness instance for the given kind.
@@ -98,11 +171,11 @@ impl Harness {
     ///
     /// // Create without checking installation
     /// let harness = Harness::new(HarnessKind::OpenCode);
-    /// assert_eq!(harness.kind(), HarnessKind::OpenCode);
+    /// assert_eq!(harness.kind(), Some(HarnessKind::OpenCode));
     /// ```
     #[must_use]
     pub fn new(kind: HarnessKind) -> Self {
-        Self { kind }
+        Self::from_backend(backend::builtin(kind))
     }
 
     /// Returns `true` if this harness is installed on the current system.
@@ -122,13 +195,7 @@ impl Harness {
     /// ```
     #[must_use]
     pub fn is_installed(&self) -> bool {
-        match self.kind {
-            HarnessKind::ClaudeCode => claude_code::is_installed(),
-            HarnessKind::OpenCode => opencode::is_installed(),
-            HarnessKind::Goose => goose::is_installed(),
-            HarnessKind::AmpCode => amp_code::is_installed(),
-            HarnessKind::CopilotCli => copilot_cli::is_installed(),
-        }
+        self.backend().is_installed()
     }
 
     /// Returns detailed installation status for this harness.
@@ -141,14 +208,10 @@ impl Harness {
     pub fn installation_status(&self) -> Result<InstallationStatus> {
         let binary_path = self.find_first_binary()?;
 
-        let config_path = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::global_config_dir().ok(),
-            HarnessKind::OpenCode => opencode::global_config_dir().ok(),
-            HarnessKind::Goose => goose::global_config_dir().ok(),
-            HarnessKind::AmpCode => amp_code::global_config_dir().ok(),
-            HarnessKind::CopilotCli => copilot_cli::global_config_dir().ok(),
-        }
-        .filter(|p| p.exists());
+        let config_path = self
+            .config(&Scope::Global)
+            .ok()
+            .filter(|p| self.exists(p, None, "installation"));
 
         let status = match (binary_path, config_path) {
             (Some(binary_path), Some(config_path)) => InstallationStatus::FullyInstalled {
@@ -159,12 +222,35 @@ impl Harness {
             (None, Some(config_path)) => InstallationStatus::ConfigOnly { config_path },
             (None, None) => InstallationStatus::NotInstalled,
         };
+        if status != InstallationStatus::NotInstalled {
+            self.emit_located();
+        }
 
         Ok(status)
     }
 
+    pub(crate) fn emit_located(&self) {
+        crate::observer::emit(|| LocateEvent::HarnessLocated {
+            harness: self.name().to_string(),
+        });
+        crate::metrics::record(MetricKind::HarnessDetected, self.kind(), 1);
+    }
+
+    fn emit_resolved(&self, resource: ResolvedKind, scope: &Scope, path: &Path) {
+        crate::observer::emit(|| LocateEvent::ResourceResolved {
+            harness: self.name().to_string(),
+            resource,
+            scope: scope.clone(),
+            path: path.to_path_buf(),
+        });
+    }
+
+    fn exists(&self, path: &Path, resource: Option<ResourceKind>, purpose: &'static str) -> bool {
+        crate::fs::exists_for(path, self.access_reason(resource, purpose))
+    }
+
     fn find_first_binary(&self) -> Result<Option<PathBuf>> {
-        for name in self.kind.binary_names() {
+        for name in self.backend.binary_names() {
             if let Some(path) = crate::detection::find_binary(name)? {
                 return Ok(Some(path));
             }
@@ -172,8 +258,211 @@ impl Harness {
         Ok(None)
     }
 
+    /// Returns warnings about using this harness in the given environment.
+    ///
+    /// In devcontainers and on CI runners, global configuration does not
+    /// outlive the environment, so callers should prefer project scope
+    /// (see [`Environment::preferred_scope`]). On CI, a missing binary is
+    /// expected and reported as a warning rather than treated as a failure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::{Environment, Harness, HarnessKind};
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// for issue in harness.environment_warnings(Environment::detect()) {
+    ///     eprintln!("{}: {}", issue.field, issue.message);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn environment_warnings(&self, environment: Environment) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+
+        if environment.global_config_is_ephemeral() {
+            issues.push(ValidationIssue::warning(
+                "scope",
+                format!(
+                    "{} global config is ephemeral in a {environment} environment; prefer project scope",
+                    self.name()
+                ),
+                Some(CODE_ENV_GLOBAL_EPHEMERAL),
+            ));
+        }
+
+        if environment == Environment::Ci && !self.is_installed() {
+            issues.push(ValidationIssue::warning(
+                "installation",
+                format!(
+                    "{} is not installed on this CI runner; only project-scoped resources are available",
+                    self.name()
+                ),
+                Some(CODE_ENV_NOT_INSTALLED_CI),
+            ));
+        }
+
+        issues
+    }
+
+    /// Returns where this harness stores OAuth tokens for remote MCP servers.
+    ///
+    /// Only the location is reported; tokens are never decrypted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config or data directory cannot be determined.
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(None)` if this harness does not support OAuth for MCP servers
+    /// - `Ok(Some(store))` for Claude Code and OpenCode
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, TokenStore};
+    ///
+    /// let harness = Harness::new(HarnessKind::OpenCode);
+    /// if let Some(TokenStore::File { path }) = harness.oauth_token_store()? {
+    ///     println!("OAuth tokens in {}", path.display());
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn oauth_token_store(&self) -> Result<Option<TokenStore>> {
+        self.backend().oauth_token_store()
+    }
+
+    /// Returns the path of the system-wide managed settings file.
+    ///
+    /// Returns `None` if this harness has no managed settings. Only Claude
+    /// Code reads one, see [`claude_code::managed_settings_path`].
+    #[must_use]
+    pub fn managed_settings_path(&self) -> Option<PathBuf> {
+        self.backend().managed_settings_path()
+    }
+
+    /// Returns the settings this harness applies after merging user,
+    /// project, and managed settings files.
+    ///
+    /// Returns `Ok(None)` if this harness does not layer settings files.
+    /// See [`claude_code::effective_settings`] for the merge rules.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a settings file exists but cannot be read or
+    /// parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind};
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// if let Some(settings) = harness.effective_settings(Some(".".as_ref()))? {
+    ///     println!("model: {}", settings["model"]);
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn effective_settings(
+        &self,
+        project_root: Option<&Path>,
+    ) -> Result<Option<serde_json::Value>> {
+        self.backend().effective_settings(project_root)
+    }
+
+    /// Returns every environment variable the MCP servers, hooks, and
+    /// skills of `scope` reference, with whether each is set.
+    ///
+    /// See [`EnvReport::from_scope`](crate::envreport::EnvReport::from_scope).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a config file, settings file, or skill cannot be
+    /// read or parsed.
+    #[cfg(feature = "validation")]
+    pub fn env_report(&self, scope: &Scope) -> Result<crate::envreport::EnvReport> {
+        crate::envreport::EnvReport::from_scope(self, scope)
+    }
+
+    /// Returns which project-scoped MCP servers the user approved for
+    /// `project_root`.
+    ///
+    /// Returns `Ok(None)` if this harness does not gate project servers
+    /// behind approval. Call [`apply`](claude_code::McpApprovals::apply) on
+    /// the result with the servers parsed from the project's config to
+    /// disable the declined ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the harness state file exists but cannot be
+    /// read or parsed.
+    pub fn project_mcp_approvals(
+        &self,
+        project_root: &Path,
+    ) -> Result<Option<claude_code::McpApprovals>> {
+        self.backend().project_mcp_approvals(project_root)
+    }
+
+    /// Returns whether this harness has stored OAuth credentials for a server.
+    ///
+    /// Returns `None` when this cannot be determined: the harness has no
+    /// token store, the store is a keychain, or the store file is unreadable.
+    /// A missing store file means no credentials have been stored yet.
+    #[must_use]
+    pub fn has_stored_credentials(&self, server_name: &str) -> Option<bool> {
+        let Ok(Some(TokenStore::File { path })) = self.oauth_token_store() else {
+            return None;
+        };
+        let reason = self.access_reason(None, "oauth");
+        if !crate::fs::exists_for(&path, reason) {
+            return Some(false);
+        }
+        let content = crate::fs::read_to_string_for(&path, reason).ok()?;
+        let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+        self.backend().has_oauth_credentials(&value, server_name)
+    }
+
+    /// Returns warnings about OAuth credentials for a remote MCP server.
+    ///
+    /// Warns when `server` is configured for OAuth but this harness has no
+    /// stored credentials for it, meaning the user will be prompted to sign
+    /// in on first use.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, McpServer};
+    ///
+    /// # fn check(server: &McpServer) {
+    /// let harness = Harness::new(HarnessKind::OpenCode);
+    /// for issue in harness.oauth_warnings("github", server) {
+    ///     eprintln!("{}", issue.message);
+    /// }
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn oauth_warnings(&self, server_name: &str, server: &McpServer) -> Vec<ValidationIssue> {
+        let McpServer::Http(http) = server else {
+            return Vec::new();
+        };
+        if http.oauth.is_none() || self.has_stored_credentials(server_name) != Some(false) {
+            return Vec::new();
+        }
+        vec![ValidationIssue::warning(
+            "oauth",
+            format!(
+                "OAuth configured but {} has no stored credentials for '{server_name}'; user will be prompted to sign in",
+                self.name()
+            ),
+            Some(CODE_OAUTH_NO_CREDENTIALS),
+        )]
+    }
+
     /// Returns all harnesses that are installed on the current system.
     ///
+    /// Custom harnesses registered with the
+    /// [global registry](HarnessRegistry::global) are included.
+    ///
     /// # Errors
     ///
     /// Returns an error if the home directory or config directory cannot
@@ -185,19 +474,39 @@ impl Harness {
     /// use harness_locate::Harness;
     ///
     /// for harness in Harness::installed()? {
-    ///     println!("{} is installed", harness.kind());
+    ///     println!("{} is installed", harness.name());
     /// }
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn installed() -> Result<Vec<Harness>> {
-        let mut result = Vec::new();
-        for &kind in HarnessKind::ALL {
-            let harness = Self::new(kind);
-            if harness.is_installed() {
-                result.push(harness);
-            }
+        Ok(HarnessRegistry::global().installed())
+    }
+
+    /// Like [`Harness::is_installed`], with `PATH` lookups answered from
+    /// `binaries` (as returned by [`find_binaries`](crate::detection::find_binaries)).
+    pub(crate) fn is_installed_with(&self, binaries: &HashMap<&str, Option<PathBuf>>) -> bool {
+        let has_binary = self
+            .backend
+            .binary_names()
+            .iter()
+            .any(|name| binaries.get(name).is_some_and(Option::is_some));
+        self.backend.is_installed_with(has_binary)
+    }
+
+    /// Returns the directory resource of the given kind for the given scope.
+    ///
+    /// [`Harness::skills`], [`Harness::commands`], [`Harness::agents`] and
+    /// [`Harness::plugins`] are shorthands for this.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration directory cannot be determined.
+    pub fn resource(&self, kind: ResourceKind, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let resource = self.backend().resource(kind, scope)?;
+        if let Some(dir) = &resource {
+            self.emit_resolved(ResolvedKind::Directory(kind), scope, &dir.path);
         }
-        Ok(result)
+        Ok(resource)
     }
 
     /// Returns the skills directory resource for the given scope.
@@ -223,73 +532,7 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn skills(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        match self.kind {
-            HarnessKind::ClaudeCode => {
-                let path = claude_code::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
-            }
-            HarnessKind::OpenCode => {
-                let path = opencode::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::Markdown,
-                }))
-            }
-            HarnessKind::Goose => {
-                let path = goose::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::Markdown,
-                }))
-            }
-            HarnessKind::AmpCode => {
-                let path = amp_code::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::Markdown,
-                }))
-            }
-            HarnessKind::CopilotCli => {
-                let path = copilot_cli::skills_dir(scope)
-                    .ok_or_else(|| Error::NotFound("skills directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: "SKILL.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
-            }
-        }
+        self.resource(ResourceKind::Skills, scope)
     }
 
     /// Returns the commands directory resource for the given scope.
@@ -310,20 +553,7 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn commands(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        let path = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::commands_dir(scope)?,
-            HarnessKind::OpenCode => opencode::commands_dir(scope)?,
-            HarnessKind::Goose | HarnessKind::CopilotCli => return Ok(None),
-            HarnessKind::AmpCode => amp_code::commands_dir(scope)?,
-        };
-        Ok(Some(DirectoryResource {
-            exists: path.exists(),
-            path,
-            structure: DirectoryStructure::Flat {
-                file_pattern: "*.md".into(),
-            },
-            file_format: FileFormat::MarkdownWithFrontmatter,
-        }))
+        self.resource(ResourceKind::Commands, scope)
     }
 
     /// Returns the plugins directory resource for the given scope.
@@ -349,33 +579,7 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn plugins(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        match self.kind {
-            HarnessKind::ClaudeCode => {
-                let path = claude_code::plugins_dir(scope)
-                    .ok_or_else(|| Error::NotFound("plugins directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Nested {
-                        subdir_pattern: "*".into(),
-                        file_name: ".claude-plugin".into(),
-                    },
-                    file_format: FileFormat::Json,
-                }))
-            }
-            HarnessKind::OpenCode => {
-                let path = opencode::config_dir(scope)?.join("plugin");
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Flat {
-                        file_pattern: "*.{js,ts}".into(),
-                    },
-                    file_format: FileFormat::Json,
-                }))
-            }
-            HarnessKind::Goose | HarnessKind::AmpCode | HarnessKind::CopilotCli => Ok(None),
-        }
+        self.resource(ResourceKind::Plugins, scope)
     }
 
     /// Returns the agents directory resource for the given scope.
@@ -401,45 +605,112 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn agents(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        match self.kind {
-            HarnessKind::ClaudeCode => {
-                let path = claude_code::agents_dir(scope)
-                    .ok_or_else(|| Error::NotFound("agents directory".into()))?;
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Flat {
-                        file_pattern: "*.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
-            }
-            HarnessKind::OpenCode => {
-                let path = opencode::config_dir(scope)?.join("agent");
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
-                    path,
-                    structure: DirectoryStructure::Flat {
-                        file_pattern: "*.{yaml,json}".into(),
-                    },
-                    file_format: FileFormat::Yaml,
-                }))
-            }
-            HarnessKind::CopilotCli => {
-                let path = copilot_cli::agents_dir(scope)
-                    .ok_or_else(|| Error::NotFound("agents directory".into()))?;
-                // Global and project agents are Markdown
-                Ok(Some(DirectoryResource {
-                    exists: path.exists(),
+        self.resource(ResourceKind::Agents, scope)
+    }
+
+    /// Searches `scopes`, highest precedence first, for every copy of the
+    /// resource called `name`.
+    ///
+    /// Skills are matched by directory name, other resources by file name
+    /// without extension. Scopes the harness does not support, or where it
+    /// has no directory for `kind`, are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configuration directory cannot be determined
+    /// or a resource directory cannot be listed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use harness_locate::{Harness, HarnessKind, ResourceKind, Scope};
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// let scopes = [Scope::Global, Scope::Custom(PathBuf::from("/profiles/work"))];
+    /// let lookup = harness.find_resource(ResourceKind::Agents, "reviewer", &scopes)?;
+    /// for copy in lookup.shadowed() {
+    ///     println!("ignored: {}", copy.path.display());
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn find_resource(
+        &self,
+        kind: ResourceKind,
+        name: &str,
+        scopes: &[Scope],
+    ) -> Result<ResourceLookup> {
+        let mut matches = Vec::new();
+        for scope in scopes {
+            let dir = match self.resource(kind, scope) {
+                Ok(Some(dir)) => dir,
+                Ok(None) | Err(Error::UnsupportedScope { .. }) => continue,
+                Err(e) => return Err(e),
+            };
+            let nested = matches!(dir.structure, DirectoryStructure::Nested { .. });
+            let found = dir.enumerate()?.into_iter().find(|path| {
+                let resource_name = if nested {
+                    path.parent().and_then(Path::file_name)
+                } else {
+                    path.file_stem()
+                };
+                resource_name.is_some_and(|n| n == name)
+            });
+            if let Some(path) = found {
+                matches.push(ResourceMatch {
+                    scope: scope.clone(),
                     path,
-                    structure: DirectoryStructure::Flat {
-                        file_pattern: "*.md".into(),
-                    },
-                    file_format: FileFormat::MarkdownWithFrontmatter,
-                }))
+                });
             }
-            HarnessKind::Goose | HarnessKind::AmpCode => Ok(None),
         }
+        Ok(ResourceLookup {
+            kind,
+            name: name.to_string(),
+            matches,
+        })
+    }
+
+    /// Finds the skill called `name`, searching the project at
+    /// `project_root` before the global scope.
+    ///
+    /// [`ResourceLookup::active`] is the copy the harness loads and
+    /// [`ResourceLookup::shadowed`] lists the copies it ignores, which is
+    /// why edits to them are not picked up. Use
+    /// [`find_resource`](Self::find_resource) to include custom scopes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configuration directory cannot be determined
+    /// or a skills directory cannot be listed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use harness_locate::{Harness, HarnessKind};
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// let lookup = harness.find_skill("deploy", Some(Path::new("/my/project")))?;
+    /// if let Some(active) = lookup.active() {
+    ///     println!("loaded from {}", active.path.display());
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn find_skill(&self, name: &str, project_root: Option<&Path>) -> Result<ResourceLookup> {
+        self.find_resource(ResourceKind::Skills, name, &search_scopes(project_root))
+    }
+
+    /// Finds the command called `name`, searching the project at
+    /// `project_root` before the global scope.
+    ///
+    /// See [`find_skill`](Self::find_skill).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configuration directory cannot be determined
+    /// or a commands directory cannot be listed.
+    pub fn find_command(&self, name: &str, project_root: Option<&Path>) -> Result<ResourceLookup> {
+        self.find_resource(ResourceKind::Commands, name, &search_scopes(project_root))
     }
 
     /// Returns the base configuration directory path for the given scope.
@@ -464,13 +735,86 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn config(&self, scope: &Scope) -> Result<PathBuf> {
-        match self.kind {
-            HarnessKind::ClaudeCode => claude_code::config_dir(scope),
-            HarnessKind::OpenCode => opencode::config_dir(scope),
-            HarnessKind::Goose => goose::config_dir(scope),
-            HarnessKind::AmpCode => amp_code::config_dir(scope),
-            HarnessKind::CopilotCli => copilot_cli::config_dir(scope),
-        }
+        self.backend().config_dir(scope)
+    }
+
+    /// Returns where this harness keeps state such as sessions, todos and
+    /// transcripts for the given scope.
+    ///
+    /// Returns `Ok(None)` if the harness keeps no state for the scope.
+    /// Only Claude Code keeps per-project state, under
+    /// [`claude_code::data_dir`]. The directory may not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be determined.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, Scope};
+    ///
+    /// let harness = Harness::new(HarnessKind::Goose);
+    /// if let Some(data) = harness.data(&Scope::Global)? {
+    ///     println!("sessions in {}", data.join("sessions").display());
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn data(&self, scope: &Scope) -> Result<Option<PathBuf>> {
+        self.backend().data_dir(scope)
+    }
+
+    /// Returns where this harness writes its logs.
+    ///
+    /// Returns `Ok(None)` if the harness writes no log files. The
+    /// directory may not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be determined.
+    pub fn logs(&self) -> Result<Option<PathBuf>> {
+        self.backend().logs_dir()
+    }
+
+    /// Returns where this harness caches files it can recreate, which
+    /// cleanup tools may delete.
+    ///
+    /// Returns `Ok(None)` if the harness has no cache directory. The
+    /// directory may not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be determined.
+    pub fn cache(&self) -> Result<Option<PathBuf>> {
+        self.backend().cache_dir()
+    }
+
+    /// Lists the stale session files, orphaned plugin clones and old cache
+    /// entries of this harness, with their sizes.
+    ///
+    /// See [`cleanup::cleanup_plan`](crate::cleanup::cleanup_plan).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory cannot be resolved or read.
+    pub fn cleanup_plan(
+        &self,
+        options: &crate::cleanup::CleanupOptions,
+    ) -> Result<crate::cleanup::CleanupPlan> {
+        crate::cleanup::cleanup_plan(self, options)
+    }
+
+    /// Returns the bytes and files this harness takes up in `scope`, by
+    /// category: resource directories, sessions, caches, logs and config.
+    ///
+    /// See [`usage::disk_usage`](crate::usage::disk_usage).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a path cannot be resolved or a directory cannot
+    /// be read.
+    pub fn disk_usage(&self, scope: &Scope) -> Result<crate::usage::DiskUsage> {
+        crate::usage::disk_usage(self, scope)
     }
 
     /// Returns the MCP configuration resource for the given scope.
@@ -492,56 +836,11 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn mcp(&self, scope: &Scope) -> Result<Option<ConfigResource>> {
-        let (file, key_path, format) = match self.kind {
-            HarnessKind::ClaudeCode => {
-                // Claude Code CLI uses .mcp.json in config directories:
-                // - Global: ~/.claude/.mcp.json
-                // - Project: .mcp.json (in project root)
-                // Note: ~/.claude.json is a cache/stats file, NOT MCP config
-                let file = match scope {
-                    Scope::Global => claude_code::config_dir(&Scope::Global)?.join(".mcp.json"),
-                    Scope::Project(root) => root.join(".mcp.json"),
-                    Scope::Custom(path) => path.join(".mcp.json"),
-                };
-                (file, "/mcpServers".into(), FileFormat::Json)
-            }
-            HarnessKind::OpenCode => {
-                let base = opencode::config_dir(scope)?;
-                (base.join("opencode.json"), "/mcp".into(), FileFormat::Json)
-            }
-            HarnessKind::Goose => {
-                let base = goose::config_dir(scope)?;
-                (
-                    base.join("config.yaml"),
-                    "/extensions".into(),
-                    FileFormat::Yaml,
-                )
-            }
-            HarnessKind::AmpCode => {
-                let base = amp_code::config_dir(scope)?;
-                (
-                    base.join("amp.json"),
-                    "/amp.mcpServers".into(),
-                    FileFormat::Json,
-                )
-            }
-            HarnessKind::CopilotCli => {
-                // Copilot CLI uses mcp-config.json in config directories
-                let base = copilot_cli::mcp_dir(scope)?;
-                (
-                    base.join("mcp-config.json"),
-                    "/mcpServers".into(),
-                    FileFormat::Json,
-                )
-            }
-        };
-        Ok(Some(ConfigResource {
-            file_exists: file.exists(),
-            file,
-            key_path,
-            format,
-            schema_url: None,
-        }))
+        let resource = self.backend().mcp(scope)?;
+        if let Some(config) = &resource {
+            self.emit_resolved(ResolvedKind::McpConfig, scope, &config.file);
+        }
+        Ok(resource)
     }
 
     /// Returns the MCP capabilities for this harness.
@@ -560,7 +859,7 @@ impl Harness {
     /// ```
     #[must_use]
     pub fn mcp_capabilities(&self) -> McpCapabilities {
-        McpCapabilities::for_kind(self.kind)
+        self.backend().mcp_capabilities()
     }
 
     /// Checks if this harness supports a specific MCP server configuration.
@@ -641,6 +940,7 @@ impl Harness {
                 }
                 true
             }
+            McpServer::GooseNative(_) => caps.goose_native,
         }
     }
 
@@ -648,26 +948,54 @@ impl Harness {
     ///
     /// Combines base validation with harness-specific capability checks.
     /// Returns detailed issues explaining any incompatibilities.
+    #[cfg(feature = "validation")]
     #[must_use]
     pub fn validate_mcp_server(
         &self,
         server: &McpServer,
     ) -> Vec<crate::validation::ValidationIssue> {
-        crate::validation::validate_for_harness(server, self.kind)
+        crate::observer::emit_issues(self, self.backend().validate_mcp_server(server))
     }
 
     #[must_use]
     pub fn skill_capabilities(&self) -> Option<crate::validation::SkillCapabilities> {
-        crate::validation::SkillCapabilities::for_kind(self.kind)
+        self.backend().skill_capabilities()
     }
 
+    #[cfg(feature = "validation")]
     #[must_use]
     pub fn validate_skill(
         &self,
         content: &str,
         directory_name: &str,
     ) -> Vec<crate::validation::ValidationIssue> {
-        crate::validation::validate_skill_for_harness(content, directory_name, self.kind)
+        crate::observer::emit_issues(
+            self,
+            crate::validation::validate_skill_with(
+                content,
+                directory_name,
+                self.name(),
+                self.skill_capabilities(),
+            ),
+        )
+    }
+
+    #[must_use]
+    pub fn command_capabilities(&self) -> Option<crate::validation::CommandCapabilities> {
+        self.backend().command_capabilities()
+    }
+
+    #[cfg(feature = "validation")]
+    #[must_use]
+    pub fn validate_command(&self, content: &str) -> Vec<crate::validation::ValidationIssue> {
+        crate::observer::emit_issues(
+            self,
+            crate::validation::validate_command_with(
+                content,
+                self.name(),
+                self.command_capabilities(),
+            ),
+        )
     }
 
     /// Returns the rules directory resource for the given scope.
@@ -691,24 +1019,11 @@ impl Harness {
     /// # Ok::<(), harness_locate::Error>(())
     /// ```
     pub fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
-        let path = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::rules_dir(scope),
-            HarnessKind::OpenCode => opencode::rules_dir(scope),
-            HarnessKind::Goose => goose::rules_dir(scope),
-            HarnessKind::AmpCode => amp_code::rules_dir(scope),
-            HarnessKind::CopilotCli => copilot_cli::rules_dir(scope),
-        };
-        match path {
-            Some(p) => Ok(Some(DirectoryResource {
-                exists: p.exists(),
-                path: p,
-                structure: DirectoryStructure::Flat {
-                    file_pattern: "*.md".into(),
-                },
-                file_format: FileFormat::Markdown,
-            })),
-            None => Ok(None),
+        let resource = self.backend().rules(scope)?;
+        if let Some(dir) = &resource {
+            self.emit_resolved(ResolvedKind::Rules, scope, &dir.path);
         }
+        Ok(resource)
     }
 
     /// Converts an MCP server configuration to native harness format.
@@ -742,7 +1057,36 @@ impl Harness {
     /// let native = harness.mcp_to_native("my-server", &server).unwrap();
     /// ```
     pub fn mcp_to_native(&self, name: &str, server: &McpServer) -> Result<serde_json::Value> {
-        server.to_native_value(self.kind, name)
+        self.backend.mcp_to_native(name, server)
+    }
+
+    /// Like [`mcp_to_native`](Self::mcp_to_native), with `options`
+    /// controlling how values are written.
+    ///
+    /// Pass `NativeOptions::default().with_redact(true)` to mask secrets
+    /// before printing a config, or `with_strict(true)` to reject
+    /// conversions that would drop fields. Strict and portable modes only
+    /// apply to built-in harnesses; custom backends write what they
+    /// support.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UnsupportedMcpConfig` if the server uses features
+    /// not supported by this harness, or `Error::LossyConversion` in strict
+    /// mode if fields would be dropped.
+    pub fn mcp_to_native_with(
+        &self,
+        name: &str,
+        server: &McpServer,
+        options: NativeOptions,
+    ) -> Result<serde_json::Value> {
+        match self.kind() {
+            Some(kind) => server.to_native_value_with(kind, name, options),
+            None if options.redact => self
+                .backend
+                .mcp_to_native(name, &server.redacted_with(false)),
+            None => self.backend.mcp_to_native(name, server),
+        }
     }
 
     /// Parses MCP server configurations from harness-native JSON format.
@@ -752,8 +1096,8 @@ impl Harness {
     /// - OpenCode: `{"mcp": {...}}`
     /// - Goose: `{"extensions": {...}}`
     ///
-    /// Returns all servers including disabled ones. Callers can filter
-    /// by checking the `enabled` field on each server variant.
+    /// Returns all servers including disabled ones, sorted by name. Callers
+    /// can filter by checking the `enabled` field on each server variant.
     ///
     /// # Errors
     ///
@@ -762,7 +1106,6 @@ impl Harness {
     /// # Example
     ///
     /// ```
-    /// use std::collections::HashMap;
     /// use harness_locate::{Harness, HarnessKind};
     /// use serde_json::json;
     ///
@@ -782,17 +1125,176 @@ impl Harness {
     pub fn parse_mcp_config(
         &self,
         config: &serde_json::Value,
-    ) -> Result<HashMap<String, McpServer>> {
-        let servers = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::parse_mcp_servers(config)?,
-            HarnessKind::OpenCode => opencode::parse_mcp_servers(config)?,
-            HarnessKind::Goose => goose::parse_mcp_servers(config)?,
-            HarnessKind::AmpCode => claude_code::parse_mcp_servers(config)?,
-            HarnessKind::CopilotCli => copilot_cli::parse_mcp_servers(config)?,
-        };
+    ) -> Result<BTreeMap<String, McpServer>> {
+        let servers = self.backend().parse_mcp_servers(config)?;
         Ok(servers.into_iter().collect())
     }
 
+    /// Like [`parse_mcp_config`](Self::parse_mcp_config), but a malformed
+    /// server entry is reported instead of failing the whole config.
+    ///
+    /// A config without the servers object has no servers. Harnesses whose
+    /// backend has no [`mcp_key_path`](HarnessBackend::mcp_key_path) are
+    /// parsed all at once.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the servers object itself is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::{Harness, HarnessKind};
+    /// use serde_json::json;
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// let config = json!({
+    ///     "mcpServers": {
+    ///         "good": { "command": "node", "args": ["server.js"] },
+    ///         "broken": { "args": ["missing-command"] }
+    ///     }
+    /// });
+    ///
+    /// let report = harness.parse_mcp_config_lenient(&config).unwrap();
+    /// assert!(report.servers.contains_key("good"));
+    /// assert_eq!(report.errors[0].0, "broken");
+    /// ```
+    pub fn parse_mcp_config_lenient(&self, config: &serde_json::Value) -> Result<McpParseReport> {
+        let Some(key_path) = self.backend().mcp_key_path() else {
+            return Ok(McpParseReport {
+                servers: self.parse_mcp_config(config)?,
+                errors: Vec::new(),
+            });
+        };
+        let Some(entries) = config.pointer(key_path) else {
+            return Ok(McpParseReport::default());
+        };
+        let entries = entries
+            .as_object()
+            .ok_or_else(|| Error::UnsupportedMcpConfig {
+                harness: self.name().to_string(),
+                reason: format!("'{key_path}' must be an object"),
+            })?;
+
+        let mut report = McpParseReport::default();
+        for (name, value) in entries {
+            match self.parse_mcp_server_config(name, value) {
+                Ok(server) => {
+                    report.servers.insert(name.clone(), server);
+                }
+                Err(e) => report.errors.push((name.clone(), e)),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Reads every MCP server from the harness's config file for `scope`.
+    ///
+    /// The file is located with [`mcp`](Self::mcp), read in its declared
+    /// [`FileFormat`](crate::types::FileFormat) and each entry under its key
+    /// path is parsed. A file without the key path has no servers. Servers
+    /// are sorted by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if the harness has no MCP config for
+    /// `scope` or the file does not exist, a parse error if the file is not
+    /// valid JSON or YAML, and `Error::UnsupportedMcpConfig` naming the
+    /// server if an entry is malformed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, Scope};
+    ///
+    /// let harness = Harness::new(HarnessKind::Goose);
+    /// for (name, server) in harness.read_mcp_servers(&Scope::Global)? {
+    ///     println!("{name}: enabled={}", server.is_enabled());
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn read_mcp_servers(&self, scope: &Scope) -> Result<BTreeMap<String, McpServer>> {
+        let resource = self.mcp(scope)?.ok_or_else(|| {
+            Error::NotFound(format!("{} has no MCP config for this scope", self.name()))
+        })?;
+        let content =
+            match crate::fs::read_to_string_for(&resource.file, self.access_reason(None, "mcp")) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(Error::NotFound(format!(
+                        "MCP config file {}",
+                        resource.file.display()
+                    )));
+                }
+                Err(e) => return Err(e.into()),
+            };
+        let document = McpConfigDocument::parse(
+            &resource.file,
+            &resource.key_path,
+            resource.format,
+            &content,
+        )?;
+        if document.root().pointer(&resource.key_path).is_some() && document.servers().is_none() {
+            return Err(Error::UnsupportedMcpConfig {
+                harness: self.name().to_string(),
+                reason: format!("'{}' must be an object", resource.key_path),
+            });
+        }
+        let servers: BTreeMap<String, McpServer> = document
+            .servers()
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| Ok((name.clone(), self.parse_mcp_server_config(name, value)?)))
+            .collect::<Result<_>>()?;
+        crate::observer::emit(|| LocateEvent::ConfigParsed {
+            harness: self.name().to_string(),
+            path: resource.file.clone(),
+            servers: servers.keys().cloned().collect(),
+        });
+        crate::metrics::record(MetricKind::ConfigParsed, self.kind(), 1);
+        crate::metrics::record(
+            MetricKind::McpServersParsed,
+            self.kind(),
+            servers.len() as u64,
+        );
+        Ok(servers)
+    }
+
+    /// Like [`read_mcp_servers`](Self::read_mcp_servers), with `~`,
+    /// `$HOME`, `${workspaceFolder}` and the variables of `ctx` expanded in
+    /// each server's command, arguments and working directory. See
+    /// [`crate::interpolate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`read_mcp_servers`](Self::read_mcp_servers).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use harness_locate::interpolate::InterpolationContext;
+    /// use harness_locate::{Harness, HarnessKind, Scope};
+    ///
+    /// let scope = Scope::Project("/work/app".into());
+    /// let ctx = InterpolationContext::for_scope(&scope)?;
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// for (name, server) in harness.read_mcp_servers_interpolated(&scope, &ctx)? {
+    ///     println!("{name}: {server:?}");
+    /// }
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn read_mcp_servers_interpolated(
+        &self,
+        scope: &Scope,
+        ctx: &crate::interpolate::InterpolationContext,
+    ) -> Result<BTreeMap<String, McpServer>> {
+        let mut servers = self.read_mcp_servers(scope)?;
+        for server in servers.values_mut() {
+            crate::interpolate::expand_server(server, ctx);
+        }
+        Ok(servers)
+    }
+
     /// Parses a single MCP server from harness-native JSON format.
     ///
     /// The `name` parameter is used for error context if parsing fails.
@@ -820,15 +1322,7 @@ impl Harness {
         name: &str,
         value: &serde_json::Value,
     ) -> Result<McpServer> {
-        let result = match self.kind {
-            HarnessKind::ClaudeCode => claude_code::parse_mcp_server(value),
-            HarnessKind::OpenCode => opencode::parse_mcp_server(value),
-            HarnessKind::Goose => goose::parse_mcp_server(value),
-            HarnessKind::AmpCode => claude_code::parse_mcp_server(value),
-            HarnessKind::CopilotCli => copilot_cli::parse_mcp_server(value),
-        };
-
-        result.map_err(|e| match e {
+        self.backend().parse_mcp_server(value).map_err(|e| match e {
             Error::UnsupportedMcpConfig { harness, reason } => Error::UnsupportedMcpConfig {
                 harness,
                 reason: format!("server '{}': {}", name, reason),
@@ -838,9 +1332,19 @@ impl Harness {
     }
 }
 
+/// The project scope at `project_root`, if any, then the global scope.
+fn search_scopes(project_root: Option<&Path>) -> Vec<Scope> {
+    project_root
+        .map(|root| Scope::Project(root.to_path_buf()))
+        .into_iter()
+        .chain([Scope::Global])
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{DirectoryStructure, FileFormat};
 
     #[test]
     fn locate_claude_code_when_installed() {
@@ -850,7 +1354,7 @@ mod tests {
 
         let result = Harness::locate(HarnessKind::ClaudeCode);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().kind(), HarnessKind::ClaudeCode);
+        assert_eq!(result.unwrap().kind(), Some(HarnessKind::ClaudeCode));
     }
 
     #[test]
@@ -914,7 +1418,7 @@ mod tests {
 
         let result = Harness::locate(HarnessKind::OpenCode);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().kind(), HarnessKind::OpenCode);
+        assert_eq!(result.unwrap().kind(), Some(HarnessKind::OpenCode));
     }
 
     #[test]
@@ -963,7 +1467,7 @@ mod tests {
 
         let result = Harness::locate(HarnessKind::Goose);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap().kind(), HarnessKind::Goose);
+        assert_eq!(result.unwrap().kind(), Some(HarnessKind::Goose));
     }
 
     #[test]
@@ -1118,6 +1622,222 @@ mod tests {
         assert_eq!(config.key_path, "/mcpServers");
     }
 
+    #[test]
+    fn mcp_opencode_reports_jsonc_format() {
+        use crate::fs::MemoryFs;
+
+        let root = std::env::temp_dir().join("harness-locate-opencode-jsonc");
+        let ctx = crate::platform::PathContext::fake(&root);
+        let file = ctx
+            .scope(opencode::global_config_dir)
+            .unwrap()
+            .join("opencode.jsonc");
+        let ctx = ctx.with_fs(MemoryFs::new().with_file(&file, "{}"));
+
+        let harness = Harness::new(HarnessKind::OpenCode);
+        let config = ctx.scope(|| harness.mcp(&Scope::Global)).unwrap().unwrap();
+        assert_eq!(config.file, file);
+        assert!(config.file_exists);
+        assert_eq!(config.format, FileFormat::Jsonc);
+        assert_eq!(config.schema_url.as_deref(), Some(opencode::SCHEMA_URL));
+    }
+
+    #[test]
+    fn read_mcp_servers_accepts_commented_jsonc() {
+        use crate::fs::MemoryFs;
+
+        let ctx = crate::platform::PathContext::fake("/fake");
+        let file = ctx
+            .scope(opencode::global_config_dir)
+            .unwrap()
+            .join("opencode.jsonc");
+        let ctx = ctx.with_fs(MemoryFs::new().with_file(
+            &file,
+            "{\n  // local servers\n  \"mcp\": {\n    \"fs\": { \"type\": \"local\", \"command\": [\"mcp-fs\"], },\n  },\n}\n",
+        ));
+
+        let harness = Harness::new(HarnessKind::OpenCode);
+        let servers = ctx
+            .scope(|| harness.read_mcp_servers(&Scope::Global))
+            .unwrap();
+        assert!(matches!(&servers["fs"], McpServer::Stdio(s) if s.command == "mcp-fs"));
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn read_mcp_servers_reads_goose_yaml() {
+        use crate::fs::MemoryFs;
+
+        let ctx = crate::platform::PathContext::fake("/fake");
+        let file = ctx
+            .scope(|| goose::config_dir(&Scope::Global))
+            .unwrap()
+            .join("config.yaml");
+        let ctx = ctx.with_fs(MemoryFs::new().with_file(
+            &file,
+            "GOOSE_MODEL: gpt\nextensions:\n  fs:\n    type: stdio\n    cmd: mcp-fs\n",
+        ));
+
+        let harness = Harness::new(HarnessKind::Goose);
+        let servers = ctx
+            .scope(|| harness.read_mcp_servers(&Scope::Global))
+            .unwrap();
+        assert_eq!(servers.len(), 1);
+        assert!(matches!(&servers["fs"], McpServer::Stdio(s) if s.command == "mcp-fs"));
+    }
+
+    #[test]
+    fn resolves_state_directories() {
+        let home = Path::new("/fake/home");
+        let resolve = |kind| {
+            let harness = Harness::new(kind);
+            crate::platform::PathContext::fake("/fake")
+                .scope(|| {
+                    Ok::<_, Error>((
+                        harness.data(&Scope::Global)?,
+                        harness.data(&Scope::Project("/repo/my.app".into()))?,
+                        harness.logs()?,
+                        harness.cache()?,
+                    ))
+                })
+                .unwrap()
+        };
+
+        let (_, project, _, _) = resolve(HarnessKind::ClaudeCode);
+        assert_eq!(project, Some(home.join(".claude/projects/-repo-my-app")));
+        let (data, project, logs, _) = resolve(HarnessKind::OpenCode);
+        assert_eq!(data, Some(home.join(".local/share/opencode")));
+        assert_eq!(logs, Some(home.join(".local/share/opencode/log")));
+        assert_eq!(project, None);
+        let (_, _, logs, cache) = resolve(HarnessKind::AmpCode);
+        assert_eq!(cache, Some(home.join(".cache/amp")));
+        assert_eq!(logs, Some(home.join(".cache/amp/logs")));
+        let (_, _, logs, cache) = resolve(HarnessKind::CopilotCli);
+        assert_eq!(logs, Some(home.join(".copilot/logs")));
+        assert_eq!(cache, None);
+        #[cfg(not(target_os = "windows"))]
+        assert_eq!(
+            resolve(HarnessKind::Goose).2,
+            Some(home.join(".local/state/goose/logs"))
+        );
+    }
+
+    #[test]
+    fn find_skill_reports_the_project_copy_shadowing_the_global_one() {
+        use crate::fs::MemoryFs;
+
+        let ctx = crate::platform::PathContext::fake("/fake").with_fs(
+            MemoryFs::new()
+                .with_file("/repo/.claude/skills/deploy/SKILL.md", "project")
+                .with_file("/fake/home/.claude/skills/deploy/SKILL.md", "global")
+                .with_file("/fake/home/.claude/skills/lint/SKILL.md", "global")
+                .with_file("/fake/home/.claude/commands/deploy.md", "global"),
+        );
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let repo = Path::new("/repo");
+
+        let lookup = ctx
+            .scope(|| harness.find_skill("deploy", Some(repo)))
+            .unwrap();
+        let active = lookup.active().unwrap();
+        assert_eq!(active.scope, Scope::Project(repo.to_path_buf()));
+        assert_eq!(active.path, repo.join(".claude/skills/deploy/SKILL.md"));
+        assert_eq!(lookup.shadowed().len(), 1);
+        assert_eq!(lookup.shadowed()[0].scope, Scope::Global);
+
+        let lookup = ctx
+            .scope(|| harness.find_skill("lint", Some(repo)))
+            .unwrap();
+        assert_eq!(lookup.active().unwrap().scope, Scope::Global);
+        assert!(lookup.shadowed().is_empty());
+
+        let lookup = ctx.scope(|| harness.find_command("deploy", None)).unwrap();
+        assert_eq!(lookup.matches.len(), 1);
+        assert!(
+            ctx.scope(|| harness.find_skill("missing", None))
+                .unwrap()
+                .active()
+                .is_none()
+        );
+
+        let amp = Harness::new(HarnessKind::AmpCode);
+        let lookup = ctx.scope(|| amp.find_skill("deploy", Some(repo)));
+        assert!(lookup.is_ok());
+    }
+
+    #[test]
+    fn read_mcp_servers_distinguishes_missing_and_malformed_files() {
+        use crate::fs::MemoryFs;
+
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let scope = Scope::Project(PathBuf::from("/repo"));
+
+        let ctx = crate::platform::PathContext::fake("/fake").with_fs(MemoryFs::new());
+        let result = ctx.scope(|| harness.read_mcp_servers(&scope));
+        assert!(matches!(result, Err(Error::NotFound(msg)) if msg.contains(".mcp.json")));
+
+        let ctx = crate::platform::PathContext::fake("/fake")
+            .with_fs(MemoryFs::new().with_file("/repo/.mcp.json", "{ not json"));
+        let result = ctx.scope(|| harness.read_mcp_servers(&scope));
+        assert!(matches!(result, Err(Error::JsonParse(_))));
+
+        let ctx = crate::platform::PathContext::fake("/fake").with_fs(
+            MemoryFs::new().with_file("/repo/.mcp.json", r#"{"mcpServers": {"bad": {}}}"#),
+        );
+        let result = ctx.scope(|| harness.read_mcp_servers(&scope));
+        assert!(
+            matches!(result, Err(Error::UnsupportedMcpConfig { reason, .. }) if reason.contains("'bad'"))
+        );
+
+        let ctx = crate::platform::PathContext::fake("/fake")
+            .with_fs(MemoryFs::new().with_file("/repo/.mcp.json", "{}"));
+        let servers = ctx.scope(|| harness.read_mcp_servers(&scope)).unwrap();
+        assert!(servers.is_empty());
+    }
+
+    #[test]
+    fn parse_mcp_config_lenient_collects_per_server_errors() {
+        let harness = Harness::new(HarnessKind::Goose);
+        let config = serde_json::json!({
+            "extensions": {
+                "fs": { "type": "stdio", "cmd": "mcp-fs" },
+                "no-type": { "cmd": "x" },
+                "web": { "type": "streamable_http", "uri": "https://example.com/mcp" },
+                "bad-timeout": { "type": "stdio", "cmd": "y", "timeout": "soon" }
+            }
+        });
+
+        assert!(harness.parse_mcp_config(&config).is_err());
+
+        let report = harness.parse_mcp_config_lenient(&config).unwrap();
+        assert!(!report.is_complete());
+        let names: Vec<_> = report.servers.keys().map(String::as_str).collect();
+        assert_eq!(names, ["fs", "web"]);
+        let failed: Vec<_> = report
+            .errors
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(failed, ["no-type", "bad-timeout"]);
+        assert!(matches!(
+            &report.errors[0].1,
+            Error::UnsupportedMcpConfig { reason, .. } if reason.contains("'no-type'")
+        ));
+    }
+
+    #[test]
+    fn parse_mcp_config_lenient_handles_missing_and_malformed_objects() {
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+
+        let report = harness
+            .parse_mcp_config_lenient(&serde_json::json!({}))
+            .unwrap();
+        assert!(report.servers.is_empty() && report.is_complete());
+
+        let result = harness.parse_mcp_config_lenient(&serde_json::json!({ "mcpServers": [] }));
+        assert!(matches!(result, Err(Error::UnsupportedMcpConfig { .. })));
+    }
+
     #[test]
     fn commands_none_for_goose() {
         let harness = Harness::new(HarnessKind::Goose);
@@ -1138,13 +1858,13 @@ mod tests {
     #[test]
     fn new_creates_harness_without_installation_check() {
         let harness = Harness::new(HarnessKind::ClaudeCode);
-        assert_eq!(harness.kind(), HarnessKind::ClaudeCode);
+        assert_eq!(harness.kind(), Some(HarnessKind::ClaudeCode));
 
         let harness = Harness::new(HarnessKind::OpenCode);
-        assert_eq!(harness.kind(), HarnessKind::OpenCode);
+        assert_eq!(harness.kind(), Some(HarnessKind::OpenCode));
 
         let harness = Harness::new(HarnessKind::Goose);
-        assert_eq!(harness.kind(), HarnessKind::Goose);
+        assert_eq!(harness.kind(), Some(HarnessKind::Goose));
     }
 
     #[test]
@@ -1630,4 +2350,153 @@ mod tests {
             "error should include server name"
         );
     }
+
+    #[test]
+    fn environment_warnings_flag_ephemeral_global_config() {
+        let harness = Harness::new(HarnessKind::Goose);
+
+        let issues = harness.environment_warnings(Environment::Devcontainer);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_ENV_GLOBAL_EPHEMERAL))
+        );
+        assert!(
+            !issues
+                .iter()
+                .any(|i| i.code == Some(CODE_ENV_NOT_INSTALLED_CI))
+        );
+    }
+
+    #[test]
+    fn environment_warnings_empty_locally() {
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        assert!(harness.environment_warnings(Environment::Local).is_empty());
+    }
+
+    #[test]
+    fn fake_path_context_isolates_resolution() {
+        let root = std::env::temp_dir().join("harness-locate-fake-context");
+        let ctx = crate::platform::PathContext::fake(&root);
+
+        ctx.scope(|| {
+            let harness = Harness::new(HarnessKind::ClaudeCode);
+            assert_eq!(
+                harness.config(&Scope::Global).unwrap(),
+                root.join("home").join(".claude")
+            );
+            assert!(matches!(
+                harness.installation_status().unwrap(),
+                InstallationStatus::NotInstalled
+            ));
+        });
+    }
+
+    #[test]
+    fn memory_fs_drives_installation_checks() {
+        let root = std::env::temp_dir().join("harness-locate-memory-fs");
+        let ctx = crate::platform::PathContext::fake(&root);
+        let goose_config = ctx.scope(|| goose::global_config_dir().unwrap());
+        let ctx = ctx.with_fs(crate::fs::MemoryFs::new().with_dir(&goose_config));
+
+        ctx.scope(|| {
+            assert!(Harness::new(HarnessKind::Goose).is_installed());
+            assert!(!Harness::new(HarnessKind::ClaudeCode).is_installed());
+        });
+    }
+
+    #[test]
+    fn audit_log_records_resource_lookups() {
+        use crate::fs::{AccessLog, AccessOp, AuditFs, MemoryFs};
+        use std::sync::Arc;
+
+        let root = std::env::temp_dir().join("harness-locate-audit");
+        let log = Arc::new(AccessLog::new());
+        let ctx = crate::platform::PathContext::fake(&root)
+            .with_fs(AuditFs::new(MemoryFs::new(), Arc::clone(&log)));
+
+        let skills = ctx.scope(|| {
+            Harness::new(HarnessKind::ClaudeCode)
+                .skills(&Scope::Global)
+                .unwrap()
+                .unwrap()
+        });
+
+        let records = log.records();
+        let record = records.iter().find(|r| r.path == skills.path).unwrap();
+        assert_eq!(record.op, AccessOp::Stat);
+        let reason = record.reason.unwrap();
+        assert_eq!(reason.harness, HarnessKind::ClaudeCode);
+        assert_eq!(reason.resource, Some(ResourceKind::Skills));
+    }
+
+    #[test]
+    fn oauth_warnings_flag_missing_credentials() {
+        use crate::fs::MemoryFs;
+        use crate::mcp::{HttpMcpServer, OAuthConfig};
+
+        let root = std::env::temp_dir().join("harness-locate-oauth");
+        let ctx = crate::platform::PathContext::fake(&root);
+        let harness = Harness::new(HarnessKind::OpenCode);
+        let Some(TokenStore::File { path }) = ctx.scope(|| harness.oauth_token_store().unwrap())
+        else {
+            panic!("expected file token store");
+        };
+        assert_eq!(path, root.join("home/.local/share/opencode/mcp-auth.json"));
+
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://mcp.example.com".to_string(),
+            headers: HashMap::new(),
+            oauth: Some(OAuthConfig {
+                client_id: None,
+                client_secret: None,
+                scope: None,
+            }),
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        // No store file yet: every server lacks credentials.
+        ctx.scope(|| {
+            assert_eq!(harness.has_stored_credentials("github"), Some(false));
+            assert_eq!(harness.oauth_warnings("github", &server).len(), 1);
+        });
+
+        let ctx = ctx.with_fs(MemoryFs::new().with_file(&path, r#"{"github": {"tokens": {}}}"#));
+        ctx.scope(|| {
+            assert_eq!(harness.has_stored_credentials("github"), Some(true));
+            assert!(harness.oauth_warnings("github", &server).is_empty());
+            let warnings = harness.oauth_warnings("linear", &server);
+            assert_eq!(warnings[0].code, Some(CODE_OAUTH_NO_CREDENTIALS));
+        });
+    }
+
+    #[test]
+    fn token_store_tolerates_utf16_with_bom() {
+        use crate::fs::MemoryFs;
+
+        let root = std::env::temp_dir().join("harness-locate-oauth-utf16");
+        let ctx = crate::platform::PathContext::fake(&root);
+        let harness = Harness::new(HarnessKind::OpenCode);
+        let Some(TokenStore::File { path }) = ctx.scope(|| harness.oauth_token_store().unwrap())
+        else {
+            panic!("expected file token store");
+        };
+
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(
+            "{\r\n  \"github\": {}\r\n}\r\n"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes),
+        );
+        let ctx = ctx.with_fs(MemoryFs::new().with_file(&path, bytes));
+        ctx.scope(|| assert_eq!(harness.has_stored_credentials("github"), Some(true)));
+    }
+
+    #[test]
+    fn oauth_token_store_unsupported_harness() {
+        let harness = Harness::new(HarnessKind::Goose);
+        assert!(harness.oauth_token_store().unwrap().is_none());
+        assert_eq!(harness.has_stored_credentials("github"), None);
+    }
 }
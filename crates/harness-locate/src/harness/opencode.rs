@@ -3,24 +3,40 @@
 //! OpenCode stores its configuration in:
 //! - **Global**: `~/.config/opencode/`
 //! - **Project**: `.opencode/` in project root
+//!
+//! Config files are layered; see [`config_files`] for the order OpenCode
+//! reads them in.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use super::backend::{self, HarnessBackend};
 use crate::error::{Error, Result};
-use crate::mcp::{HttpMcpServer, McpServer, OAuthConfig, StdioMcpServer};
+use crate::fs::AccessReason;
+use crate::mcp::{HttpMcpServer, McpCapabilities, McpServer, OAuthConfig, StdioMcpServer};
+#[cfg(feature = "yaml")]
+use crate::meta::AgentMeta;
 use crate::platform;
-use crate::types::{EnvValue, HarnessKind, Scope};
+#[cfg(feature = "yaml")]
+use crate::skill::parse_frontmatter;
+use crate::types::{
+    ConfigResource, DirectoryResource, DirectoryStructure, EnvValue, FileFormat, HarnessKind,
+    ResourceKind, Scope, TokenStore,
+};
+#[cfg(feature = "validation")]
+use crate::validation::ValidationIssue;
+use crate::validation::{CommandCapabilities, SkillCapabilities};
 
 /// Returns the global OpenCode configuration directory.
 ///
-/// Returns `~/.config/opencode/` on all platforms.
+/// Returns `~/.config/opencode/` on all platforms, including Windows
+/// (`%USERPROFILE%\.config\opencode\`, not `%APPDATA%`).
 ///
 /// # Errors
 ///
 /// Returns an error if the home directory cannot be determined.
 pub fn global_config_dir() -> Result<PathBuf> {
-    Ok(platform::config_dir()?.join("opencode"))
+    Ok(platform::dot_config_dir()?.join("opencode"))
 }
 
 /// Returns the project-local OpenCode configuration directory.
@@ -45,6 +61,17 @@ pub fn commands_dir(scope: &Scope) -> Result<PathBuf> {
     }
 }
 
+/// Returns the agents directory for the given scope.
+///
+/// - **Global**: `~/.config/opencode/agent/`
+/// - **Project**: `.opencode/agent/`
+///
+/// Agents can also be defined under the `agent` key of the config file;
+/// [`agents`] lists both.
+pub fn agents_dir(scope: &Scope) -> Result<PathBuf> {
+    Ok(config_dir(scope)?.join("agent"))
+}
+
 /// Returns the config directory for the given scope.
 ///
 /// This is the base configuration directory.
@@ -64,6 +91,87 @@ pub fn mcp_dir(scope: &Scope) -> Result<PathBuf> {
     config_dir(scope)
 }
 
+/// Environment variable naming an extra config file layered over the
+/// global config.
+const OPENCODE_CONFIG_ENV: &str = "OPENCODE_CONFIG";
+
+/// Config file names OpenCode reads from a directory, preferred first.
+const CONFIG_FILE_NAMES: &[&str] = &["opencode.jsonc", "opencode.json"];
+
+/// Global config file name used by early OpenCode releases.
+const LEGACY_CONFIG_FILE: &str = "config.json";
+
+/// Returns the config file OpenCode reads for the given scope.
+///
+/// - **Global**: `$OPENCODE_CONFIG` when set to an absolute path, otherwise
+///   `opencode.jsonc` or `opencode.json` in the global config directory
+/// - **Project**: `opencode.jsonc` or `opencode.json` in `.opencode/`, then
+///   in the project root
+/// - **Custom**: `opencode.jsonc` or `opencode.json` in the directory
+///
+/// The first existing file wins. If none exists, returns `opencode.json`
+/// in the [config directory](config_dir) as the place to create one.
+///
+/// # Errors
+///
+/// Returns an error if the global config directory cannot be determined.
+pub fn config_file(scope: &Scope) -> Result<PathBuf> {
+    if matches!(scope, Scope::Global)
+        && let Some(path) = config_env_override()
+    {
+        return Ok(path);
+    }
+
+    let mut dirs = vec![config_dir(scope)?];
+    if let Scope::Project(root) = scope {
+        dirs.push(root.clone());
+    }
+    Ok(dirs
+        .iter()
+        .find_map(|dir| find_config_in(dir))
+        .unwrap_or_else(|| dirs[0].join("opencode.json")))
+}
+
+/// Returns the existing config files OpenCode merges, lowest precedence
+/// first.
+///
+/// The global config comes first, then `$OPENCODE_CONFIG`, then the
+/// project root's config and the project's `.opencode/` config when
+/// `project_root` is given. Later files override keys from earlier ones.
+///
+/// # Errors
+///
+/// Returns an error if the global config directory cannot be determined.
+pub fn config_files(project_root: Option<&Path>) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = find_config_in(&global_config_dir()?).into_iter().collect();
+    files.extend(config_env_override().filter(|path| config_exists(path)));
+    if let Some(root) = project_root {
+        files.extend(find_config_in(root));
+        files.extend(find_config_in(&project_config_dir(root)));
+    }
+    Ok(files)
+}
+
+fn config_env_override() -> Option<PathBuf> {
+    platform::var(OPENCODE_CONFIG_ENV)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+fn find_config_in(dir: &Path) -> Option<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| config_exists(path))
+}
+
+fn config_exists(path: &Path) -> bool {
+    crate::fs::exists_for(
+        path,
+        AccessReason::new(HarnessKind::OpenCode, None, "config"),
+    )
+}
+
 /// Returns the skills directory for the given scope.
 ///
 /// - **Global**: `~/.config/opencode/skill/`
@@ -91,11 +199,187 @@ pub fn rules_dir(scope: &Scope) -> Option<PathBuf> {
     }
 }
 
+/// Returns the global OpenCode data directory.
+///
+/// Returns `$XDG_DATA_HOME/opencode/` if set, otherwise
+/// `~/.local/share/opencode/` on all platforms.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn global_data_dir() -> Result<PathBuf> {
+    if let Some(dir) = platform::var("XDG_DATA_HOME") {
+        let path = PathBuf::from(dir);
+        if path.is_absolute() {
+            return Ok(path.join("opencode"));
+        }
+    }
+    Ok(platform::home_dir()?.join(".local/share/opencode"))
+}
+
+/// Returns where OpenCode writes its logs, `log/` in the
+/// [data directory](global_data_dir).
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn logs_dir() -> Result<PathBuf> {
+    Ok(global_data_dir()?.join("log"))
+}
+
+/// Returns the global OpenCode cache directory, holding installed
+/// providers and plugins.
+///
+/// Returns `$XDG_CACHE_HOME/opencode/` if set, otherwise
+/// `~/.cache/opencode/` on all platforms.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn global_cache_dir() -> Result<PathBuf> {
+    Ok(platform::xdg_dir("XDG_CACHE_HOME", ".cache")?.join("opencode"))
+}
+
+/// Returns where OpenCode stores OAuth tokens for remote MCP servers.
+///
+/// This is `mcp-auth.json` in the [data directory](global_data_dir).
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn oauth_token_store() -> Result<TokenStore> {
+    Ok(TokenStore::File {
+        path: global_data_dir()?.join("mcp-auth.json"),
+    })
+}
+
+/// Returns whether `mcp-auth.json` has an entry for `server_name`.
+///
+/// The file maps server names directly to their token sets.
+pub(crate) fn has_oauth_credentials(content: &serde_json::Value, server_name: &str) -> bool {
+    content.get(server_name).is_some_and(|v| !v.is_null())
+}
+
+/// Where an OpenCode agent is defined.
+#[cfg(feature = "yaml")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AgentSource {
+    /// A markdown file in the [agents directory](agents_dir), named after
+    /// the agent.
+    Markdown(PathBuf),
+    /// An entry under the `agent` key of this config file.
+    Config(PathBuf),
+}
+
+/// An OpenCode agent from either definition style.
+#[cfg(feature = "yaml")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Agent {
+    /// The agent name: the markdown file stem or the config key.
+    pub name: String,
+    /// Where the agent is defined.
+    pub source: AgentSource,
+    /// Frontmatter of a markdown agent, or the fields of a config agent
+    /// other than `prompt`.
+    pub meta: AgentMeta,
+    /// The system prompt: the markdown body, or the config's `prompt`
+    /// (which may be a `{file:...}` reference).
+    pub prompt: Option<String>,
+}
+
+/// Lists the agents defined for `scope`, markdown agents first, each group
+/// sorted by name.
+///
+/// Reads `*.md` files in [`agents_dir`] and the `agent` object of
+/// [`config_file`]. OpenCode merges an agent defined both ways; both
+/// definitions are returned.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or its frontmatter or JSON is
+/// invalid.
+///
+/// # Examples
+///
+/// ```no_run
+/// use harness_locate::Scope;
+/// use harness_locate::harness::opencode;
+///
+/// for agent in opencode::agents(&Scope::Global)? {
+///     println!("{} ({:?})", agent.name, agent.meta.mode);
+/// }
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+#[cfg(feature = "yaml")]
+pub fn agents(scope: &Scope) -> Result<Vec<Agent>> {
+    let mut agents = Vec::new();
+
+    let dir = backend::markdown_directory(
+        HarnessKind::OpenCode,
+        ResourceKind::Agents,
+        "agents",
+        agents_dir(scope)?,
+    );
+    for path in dir.enumerate()? {
+        let Some(name) = path.file_stem().map(|s| s.to_string_lossy().into_owned()) else {
+            continue;
+        };
+        let content = crate::fs::read_to_string_for(
+            &path,
+            AccessReason::new(HarnessKind::OpenCode, Some(ResourceKind::Agents), "agents"),
+        )?;
+        let frontmatter = parse_frontmatter(&content)?;
+        let body = frontmatter.body.trim();
+        agents.push(Agent {
+            name,
+            meta: AgentMeta::try_from(&frontmatter)?,
+            prompt: (!body.is_empty()).then(|| body.to_string()),
+            source: AgentSource::Markdown(path),
+        });
+    }
+    agents.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let file = config_file(scope)?;
+    let content = match crate::fs::read_to_string_for(
+        &file,
+        AccessReason::new(HarnessKind::OpenCode, Some(ResourceKind::Agents), "config"),
+    ) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(agents),
+        Err(e) => return Err(e.into()),
+    };
+    let config: serde_json::Value = serde_json::from_str(&crate::jsonc::strip(&content))?;
+    let Some(defined) = config.get("agent").and_then(|v| v.as_object()) else {
+        return Ok(agents);
+    };
+    let mut configured: Vec<Agent> = Vec::with_capacity(defined.len());
+    for (name, value) in defined {
+        let mut fields = value.as_object().cloned().unwrap_or_default();
+        let prompt = fields
+            .remove("prompt")
+            .and_then(|p| p.as_str().map(String::from));
+        configured.push(Agent {
+            name: name.clone(),
+            meta: serde_yaml::to_value(fields).and_then(serde_yaml::from_value)?,
+            prompt,
+            source: AgentSource::Config(file.clone()),
+        });
+    }
+    configured.sort_by(|a, b| a.name.cmp(&b.name));
+    agents.extend(configured);
+    Ok(agents)
+}
+
 /// Checks if OpenCode is installed on this system.
 ///
 /// Currently checks if the global config directory exists.
 pub fn is_installed() -> bool {
-    global_config_dir().map(|p| p.exists()).unwrap_or(false)
+    global_config_dir().is_ok_and(|p| {
+        crate::fs::exists_for(
+            &p,
+            AccessReason::new(HarnessKind::OpenCode, None, "installation"),
+        )
+    })
 }
 
 /// Parses a single MCP server from OpenCode's native JSON format.
@@ -350,11 +634,314 @@ fn parse_remote_server(obj: &serde_json::Map<String, serde_json::Value>) -> Resu
     }))
 }
 
+/// JSON pointer to the MCP servers in the config file.
+const MCP_KEY_PATH: &str = "/mcp";
+
+/// JSON Schema OpenCode publishes for its config file.
+pub const SCHEMA_URL: &str = "https://opencode.ai/config.json";
+
+/// The OpenCode [`HarnessBackend`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OpenCode;
+
+impl HarnessBackend for OpenCode {
+    fn name(&self) -> &str {
+        HarnessKind::OpenCode.as_str()
+    }
+
+    fn kind(&self) -> Option<HarnessKind> {
+        Some(HarnessKind::OpenCode)
+    }
+
+    fn binary_names(&self) -> Vec<&str> {
+        HarnessKind::OpenCode.binary_names().to_vec()
+    }
+
+    fn project_markers(&self) -> Vec<&str> {
+        HarnessKind::OpenCode.project_markers().to_vec()
+    }
+
+    fn is_installed(&self) -> bool {
+        is_installed()
+    }
+
+    fn config_dir(&self, scope: &Scope) -> Result<PathBuf> {
+        config_dir(scope)
+    }
+
+    fn resource(&self, kind: ResourceKind, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let harness = HarnessKind::OpenCode;
+        match kind {
+            ResourceKind::Skills => {
+                backend::skills_directory(harness, skills_dir(scope), FileFormat::Markdown)
+            }
+            ResourceKind::Commands => Ok(Some(backend::markdown_directory(
+                harness,
+                kind,
+                "commands",
+                commands_dir(scope)?,
+            ))),
+            ResourceKind::Agents => Ok(Some(backend::markdown_directory(
+                harness,
+                kind,
+                "agents",
+                agents_dir(scope)?,
+            ))),
+            ResourceKind::Plugins => Ok(Some(backend::directory(
+                harness,
+                Some(kind),
+                "plugins",
+                config_dir(scope)?.join("plugin"),
+                DirectoryStructure::Flat {
+                    file_pattern: "*.{js,ts}".into(),
+                },
+                FileFormat::Json,
+            ))),
+        }
+    }
+
+    fn rules(&self, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        backend::rules_directory(HarnessKind::OpenCode, rules_dir(scope))
+    }
+
+    fn mcp(&self, scope: &Scope) -> Result<Option<ConfigResource>> {
+        let file = config_file(scope)?;
+        let format = if file.extension().is_some_and(|ext| ext == "jsonc") {
+            FileFormat::Jsonc
+        } else {
+            FileFormat::Json
+        };
+        let resource = backend::mcp_config(HarnessKind::OpenCode, file, MCP_KEY_PATH, format)?;
+        Ok(resource.map(|resource| ConfigResource {
+            schema_url: Some(SCHEMA_URL.into()),
+            ..resource
+        }))
+    }
+
+    fn legacy_mcp_configs(&self, scope: &Scope) -> Result<Vec<ConfigResource>> {
+        // Before `opencode.json`, the global config was `config.json`.
+        if !matches!(scope, Scope::Global) {
+            return Ok(Vec::new());
+        }
+        let file = global_config_dir()?.join(LEGACY_CONFIG_FILE);
+        Ok(
+            backend::mcp_config(HarnessKind::OpenCode, file, MCP_KEY_PATH, FileFormat::Json)?
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    fn mcp_key_path(&self) -> Option<&str> {
+        Some(MCP_KEY_PATH)
+    }
+
+    fn mcp_capabilities(&self) -> McpCapabilities {
+        McpCapabilities::for_kind(HarnessKind::OpenCode)
+    }
+
+    fn parse_mcp_servers(&self, config: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+        parse_mcp_servers(config)
+    }
+
+    fn parse_mcp_server(&self, value: &serde_json::Value) -> Result<McpServer> {
+        parse_mcp_server(value)
+    }
+
+    fn mcp_to_native(&self, _name: &str, server: &McpServer) -> Result<serde_json::Value> {
+        server.validate_capabilities(HarnessKind::OpenCode)?;
+        server.to_opencode_value(HarnessKind::OpenCode)
+    }
+
+    #[cfg(feature = "validation")]
+    fn validate_mcp_server(&self, server: &McpServer) -> Vec<ValidationIssue> {
+        crate::validation::validate_for_harness(server, HarnessKind::OpenCode)
+    }
+
+    fn oauth_token_store(&self) -> Result<Option<TokenStore>> {
+        oauth_token_store().map(Some)
+    }
+
+    fn has_oauth_credentials(&self, store: &serde_json::Value, server_name: &str) -> Option<bool> {
+        Some(has_oauth_credentials(store, server_name))
+    }
+
+    fn data_dir(&self, scope: &Scope) -> Result<Option<PathBuf>> {
+        match scope {
+            Scope::Global => global_data_dir().map(Some),
+            Scope::Project(_) | Scope::Custom(_) => Ok(None),
+        }
+    }
+
+    fn logs_dir(&self) -> Result<Option<PathBuf>> {
+        logs_dir().map(Some)
+    }
+
+    fn cache_dir(&self) -> Result<Option<PathBuf>> {
+        global_cache_dir().map(Some)
+    }
+
+    fn skill_capabilities(&self) -> Option<SkillCapabilities> {
+        SkillCapabilities::for_kind(HarnessKind::OpenCode)
+    }
+
+    fn command_capabilities(&self) -> Option<CommandCapabilities> {
+        CommandCapabilities::for_kind(HarnessKind::OpenCode)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fs::MemoryFs;
     use serde_json::json;
 
+    #[test]
+    fn config_file_defaults_to_opencode_json() {
+        let root = std::env::temp_dir().join("harness-locate-opencode-default");
+        let ctx = platform::PathContext::fake(&root).with_fs(MemoryFs::new());
+        let project = root.join("project");
+
+        let global = ctx.scope(|| config_file(&Scope::Global)).unwrap();
+        assert_eq!(
+            global,
+            ctx.scope(global_config_dir).unwrap().join("opencode.json")
+        );
+        let local = ctx
+            .scope(|| config_file(&Scope::Project(project.clone())))
+            .unwrap();
+        assert_eq!(local, project.join(".opencode/opencode.json"));
+    }
+
+    #[test]
+    fn config_file_prefers_existing_files() {
+        let root = std::env::temp_dir().join("harness-locate-opencode-existing");
+        let global_dir = platform::PathContext::fake(&root)
+            .scope(global_config_dir)
+            .unwrap();
+        let project = root.join("project");
+        let fs = MemoryFs::new()
+            .with_file(global_dir.join("opencode.jsonc"), "{}")
+            .with_file(project.join("opencode.json"), "{}");
+        let ctx = platform::PathContext::fake(&root).with_fs(fs);
+
+        let global = ctx.scope(|| config_file(&Scope::Global)).unwrap();
+        assert_eq!(global, global_dir.join("opencode.jsonc"));
+        let local = ctx
+            .scope(|| config_file(&Scope::Project(project.clone())))
+            .unwrap();
+        assert_eq!(local, project.join("opencode.json"));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn agents_reads_commented_jsonc() {
+        let root = std::env::temp_dir().join("harness-locate-opencode-agents-jsonc");
+        let project = root.join("project");
+        let fs = MemoryFs::new().with_file(
+            project.join("opencode.jsonc"),
+            "{\n  /* agents */\n  \"agent\": {\n    \"plan\": { \"mode\": \"primary\" }, // planner\n  },\n}\n",
+        );
+        let ctx = platform::PathContext::fake(&root).with_fs(fs);
+
+        let agents = ctx
+            .scope(|| agents(&Scope::Project(project.clone())))
+            .unwrap();
+        let names: Vec<&str> = agents.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["plan"]);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn agents_lists_markdown_and_config_agents() {
+        let root = std::env::temp_dir().join("harness-locate-opencode-agents");
+        let project = root.join("project");
+        let agent_dir = project.join(".opencode/agent");
+        let fs = MemoryFs::new()
+            .with_file(
+                agent_dir.join("review.md"),
+                "---\ndescription: Reviews code\nmode: subagent\ntools:\n  write: false\n  read: true\n---\nYou review code.\n",
+            )
+            .with_file(agent_dir.join("notes.txt"), "ignored")
+            .with_file(
+                project.join("opencode.json"),
+                json!({
+                    "agent": {
+                        "plan": { "mode": "primary", "model": "anthropic/claude", "prompt": "{file:./plan.txt}" },
+                        "build": { "temperature": 0.2, "disable": true }
+                    }
+                })
+                .to_string(),
+            );
+        let ctx = platform::PathContext::fake(&root).with_fs(fs);
+
+        let agents = ctx
+            .scope(|| agents(&Scope::Project(project.clone())))
+            .unwrap();
+        let names: Vec<&str> = agents.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["review", "build", "plan"]);
+
+        let review = &agents[0];
+        assert_eq!(
+            review.source,
+            AgentSource::Markdown(agent_dir.join("review.md"))
+        );
+        assert_eq!(review.meta.tools.as_ref().unwrap().names(), ["read"]);
+        assert_eq!(review.prompt.as_deref(), Some("You review code."));
+
+        let plan = &agents[2];
+        assert_eq!(
+            plan.source,
+            AgentSource::Config(project.join("opencode.json"))
+        );
+        assert_eq!(plan.meta.mode.as_deref(), Some("primary"));
+        assert_eq!(plan.prompt.as_deref(), Some("{file:./plan.txt}"));
+        assert_eq!(agents[1].meta.temperature, Some(0.2));
+        assert_eq!(
+            agents[1].meta.metadata["disable"],
+            serde_yaml::Value::Bool(true)
+        );
+    }
+
+    #[test]
+    fn agents_resource_is_markdown() {
+        let resource = OpenCode
+            .resource(ResourceKind::Agents, &Scope::Custom("/oc".into()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(resource.path, Path::new("/oc/agent"));
+        assert_eq!(resource.file_format, FileFormat::MarkdownWithFrontmatter);
+    }
+
+    #[test]
+    fn config_env_override_and_layering() {
+        let root = std::env::temp_dir().join("harness-locate-opencode-env");
+        let global_dir = platform::PathContext::fake(&root)
+            .scope(global_config_dir)
+            .unwrap();
+        let project = root.join("project");
+        let custom = root.join("custom.json");
+        let fs = MemoryFs::new()
+            .with_file(global_dir.join("opencode.json"), "{}")
+            .with_file(&custom, "{}")
+            .with_file(project.join("opencode.json"), "{}")
+            .with_file(project.join(".opencode/opencode.jsonc"), "{}");
+        let ctx = platform::PathContext::fake(&root)
+            .with_var(OPENCODE_CONFIG_ENV, custom.to_string_lossy())
+            .with_fs(fs);
+
+        assert_eq!(ctx.scope(|| config_file(&Scope::Global)).unwrap(), custom);
+        let files = ctx.scope(|| config_files(Some(&project))).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                global_dir.join("opencode.json"),
+                custom,
+                project.join("opencode.json"),
+                project.join(".opencode/opencode.jsonc"),
+            ]
+        );
+    }
+
     #[test]
     fn global_config_dir_is_absolute() {
         if platform::config_dir().is_err() {
@@ -0,0 +1,263 @@
+//! Runtime registry of harness backends.
+//!
+//! A [`HarnessRegistry`] holds the built-in harnesses plus any custom
+//! [`HarnessBackend`] an application registers, such as an internal fork of
+//! a CLI. The [global](HarnessRegistry::global) registry is the one consulted
+//! by [`Harness::installed`] and
+//! [`find_project_scopes`](crate::workspace::find_project_scopes), so a
+//! harness registered there is detected, discovered and validated like a
+//! built-in one.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::{Harness, HarnessRegistry};
+//! # use harness_locate::HarnessBackend;
+//! # fn internal_backend() -> Box<dyn HarnessBackend> { unimplemented!() }
+//!
+//! HarnessRegistry::global().register(internal_backend())?;
+//! for harness in Harness::installed()? {
+//!     println!("{} is installed", harness.name());
+//! }
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::sync::RwLock;
+
+use super::{Harness, HarnessBackend, backend};
+use crate::error::{Error, Result};
+use crate::types::HarnessKind;
+
+static GLOBAL: HarnessRegistry = HarnessRegistry::new();
+
+/// The set of harnesses known to this process.
+///
+/// Built-in harnesses are always present. Custom backends are added with
+/// [`register`](Self::register) and kept for the rest of the process.
+#[derive(Debug, Default)]
+pub struct HarnessRegistry {
+    custom: RwLock<Vec<&'static dyn HarnessBackend>>,
+}
+
+impl HarnessRegistry {
+    /// Creates a registry holding only the built-in harnesses.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            custom: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Returns the process-wide registry.
+    ///
+    /// [`Harness::installed`] and
+    /// [`find_project_scopes`](crate::workspace::find_project_scopes) use
+    /// this registry.
+    #[must_use]
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// Registers a custom backend and returns a [`Harness`] for it.
+    ///
+    /// Registration is meant to happen once at startup; the backend is never
+    /// dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::DuplicateHarness`] if a built-in or registered
+    /// harness already uses the same [`name`](HarnessBackend::name).
+    pub fn register(&self, backend: Box<dyn HarnessBackend>) -> Result<Harness> {
+        let mut custom = self.custom.write().unwrap_or_else(|e| e.into_inner());
+        let name = backend.name();
+        let taken = HarnessKind::ALL.iter().any(|kind| kind.as_str() == name)
+            || custom.iter().any(|existing| existing.name() == name);
+        if taken {
+            return Err(Error::DuplicateHarness(name.to_string()));
+        }
+        let backend: &'static dyn HarnessBackend = Box::leak(backend);
+        custom.push(backend);
+        Ok(Harness::from_backend(backend))
+    }
+
+    /// Returns the harness with the given [name](Harness::name).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::{HarnessKind, HarnessRegistry};
+    ///
+    /// let registry = HarnessRegistry::new();
+    /// let harness = registry.get("OpenCode").unwrap();
+    /// assert_eq!(harness.kind(), Some(HarnessKind::OpenCode));
+    /// assert!(registry.get("unknown").is_none());
+    /// ```
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Harness> {
+        self.harnesses().into_iter().find(|h| h.name() == name)
+    }
+
+    /// Returns every harness: built-ins in [`HarnessKind::ALL`] order, then
+    /// custom harnesses in registration order.
+    #[must_use]
+    pub fn harnesses(&self) -> Vec<Harness> {
+        let custom = self.custom.read().unwrap_or_else(|e| e.into_inner());
+        HarnessKind::ALL
+            .iter()
+            .map(|&kind| backend::builtin(kind))
+            .chain(custom.iter().copied())
+            .map(Harness::from_backend)
+            .collect()
+    }
+
+    /// Returns the harnesses installed on the current system.
+    ///
+    /// `PATH` is searched in one pass for the binaries of every harness.
+    #[must_use]
+    pub fn installed(&self) -> Vec<Harness> {
+        let harnesses = self.harnesses();
+        let names: Vec<&str> = harnesses
+            .iter()
+            .flat_map(|h| h.backend().binary_names())
+            .collect();
+        let binaries = crate::detection::find_binaries(&names);
+
+        let installed: Vec<Harness> = harnesses
+            .into_iter()
+            .filter(|h| h.is_installed_with(&binaries))
+            .collect();
+        for harness in &installed {
+            harness.emit_located();
+        }
+        installed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::mcp::{McpCapabilities, McpServer};
+    use crate::platform::PathContext;
+    use crate::types::{ConfigResource, DirectoryResource, ResourceKind, Scope};
+    use crate::workspace::find_project_scopes;
+
+    #[derive(Debug)]
+    struct Custom {
+        name: &'static str,
+        installed: bool,
+    }
+
+    impl Custom {
+        fn boxed(name: &'static str, installed: bool) -> Box<dyn HarnessBackend> {
+            Box::new(Self { name, installed })
+        }
+    }
+
+    impl HarnessBackend for Custom {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn project_markers(&self) -> Vec<&str> {
+            vec![".registry-test"]
+        }
+        fn is_installed(&self) -> bool {
+            self.installed
+        }
+        fn config_dir(&self, _: &Scope) -> Result<PathBuf> {
+            Ok(PathBuf::from("/opt/custom"))
+        }
+        fn resource(&self, _: ResourceKind, _: &Scope) -> Result<Option<DirectoryResource>> {
+            Ok(None)
+        }
+        fn rules(&self, _: &Scope) -> Result<Option<DirectoryResource>> {
+            Ok(None)
+        }
+        fn mcp(&self, _: &Scope) -> Result<Option<ConfigResource>> {
+            Ok(None)
+        }
+        fn mcp_capabilities(&self) -> McpCapabilities {
+            McpCapabilities::default()
+        }
+        fn parse_mcp_servers(&self, _: &serde_json::Value) -> Result<Vec<(String, McpServer)>> {
+            Ok(Vec::new())
+        }
+        fn parse_mcp_server(&self, _: &serde_json::Value) -> Result<McpServer> {
+            Err(Error::MissingField("command".into()))
+        }
+        fn mcp_to_native(&self, _: &str, _: &McpServer) -> Result<serde_json::Value> {
+            Ok(serde_json::Value::Null)
+        }
+    }
+
+    #[test]
+    fn new_registry_has_only_builtins() {
+        let harnesses = HarnessRegistry::new().harnesses();
+        let kinds: Vec<_> = harnesses.iter().filter_map(Harness::kind).collect();
+        assert_eq!(kinds, HarnessKind::ALL);
+    }
+
+    #[test]
+    fn registered_harnesses_follow_builtins() {
+        let registry = HarnessRegistry::new();
+        let harness = registry.register(Custom::boxed("internal", false)).unwrap();
+        assert_eq!(harness.kind(), None);
+
+        let harnesses = registry.harnesses();
+        assert_eq!(harnesses.len(), HarnessKind::ALL.len() + 1);
+        assert_eq!(harnesses.last(), Some(&harness));
+        assert_eq!(registry.get("internal"), Some(harness));
+    }
+
+    #[test]
+    fn duplicate_names_are_rejected() {
+        let registry = HarnessRegistry::new();
+        registry.register(Custom::boxed("internal", false)).unwrap();
+
+        let result = registry.register(Custom::boxed("internal", false));
+        assert!(matches!(result, Err(Error::DuplicateHarness(_))));
+        let result = registry.register(Custom::boxed("Claude Code", false));
+        assert!(matches!(result, Err(Error::DuplicateHarness(_))));
+    }
+
+    #[test]
+    fn installed_includes_custom_harnesses() {
+        let registry = HarnessRegistry::new();
+        let present = registry.register(Custom::boxed("present", true)).unwrap();
+        let absent = registry.register(Custom::boxed("absent", false)).unwrap();
+
+        let installed = registry.installed();
+        assert!(installed.contains(&present));
+        assert!(!installed.contains(&absent));
+    }
+
+    #[test]
+    fn global_harnesses_are_discovered_in_workspaces() {
+        let harness = HarnessRegistry::global()
+            .register(Custom::boxed("registry-test", false))
+            .unwrap();
+        let ctx = PathContext::fake("/tmp/fake")
+            .with_fs(MemoryFs::new().with_dir("/repo/tools/.registry-test"));
+
+        let projects = ctx
+            .scope(|| find_project_scopes(Path::new("/repo")))
+            .unwrap();
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].path, Path::new("/repo/tools"));
+        assert_eq!(projects[0].harnesses, [harness]);
+    }
+
+    #[test]
+    #[cfg(feature = "validation")]
+    fn custom_harnesses_are_validated_against_their_capabilities() {
+        let registry = HarnessRegistry::new();
+        let harness = registry.register(Custom::boxed("internal", false)).unwrap();
+
+        let issues = harness.validate_skill("---\nname: demo\n---\n", "demo");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(crate::CODE_SKILL_UNSUPPORTED));
+        assert!(issues[0].message.starts_with("internal"));
+    }
+}
@@ -0,0 +1,322 @@
+//! Ignore rules for files inside resource directories.
+//!
+//! Skill folders often carry files that are not part of the skill: version
+//! control metadata, editor droppings, installed `node_modules`. Listing a
+//! resource directory skips these with [`BUILTIN_PATTERNS`], plus any rules
+//! found in the [`IGNORE_FILES`] of each directory walked.
+//!
+//! Rules use gitignore syntax: `#` comments, `!` negation, a trailing `/`
+//! for directories only, a leading or inner `/` to anchor a pattern to the
+//! directory holding the ignore file, and `**` for any number of
+//! directories. Later rules override earlier ones, and rules in a deeper
+//! directory override those above it. As in git, files inside an ignored
+//! directory cannot be re-included.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::fs::Fs;
+use crate::glob::Glob;
+
+/// Ignore files read from each directory, in order of increasing precedence.
+pub const IGNORE_FILES: &[&str] = &[".gitignore", ".skillignore"];
+
+/// Patterns ignored everywhere unless an ignore file re-includes them.
+///
+/// Covers hidden files and directories, including `.git` and the ignore
+/// files themselves, and `node_modules`.
+pub const BUILTIN_PATTERNS: &[&str] = &[".*", "node_modules/"];
+
+/// A list of gitignore-style rules.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+/// use harness_locate::ignore::IgnoreRules;
+///
+/// let mut rules = IgnoreRules::builtin();
+/// rules.extend(IgnoreRules::parse("*.log\n/drafts/\n!keep.log\n"));
+///
+/// assert!(rules.is_ignored(Path::new(".git"), true));
+/// assert!(rules.is_ignored(Path::new("scripts/debug.log"), false));
+/// assert!(!rules.is_ignored(Path::new("keep.log"), false));
+/// assert!(rules.is_ignored(Path::new("drafts"), true));
+/// assert!(!rules.is_ignored(Path::new("scripts/drafts"), true));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    segments: Vec<Segment>,
+    negated: bool,
+    dir_only: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    /// `**`, zero or more path components.
+    AnyDepth,
+    Name(Glob),
+}
+
+impl IgnoreRules {
+    /// Creates an empty rule list that ignores nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the [`BUILTIN_PATTERNS`].
+    #[must_use]
+    pub fn builtin() -> Self {
+        Self::parse(&BUILTIN_PATTERNS.join("\n"))
+    }
+
+    /// Parses the contents of a gitignore-style file.
+    ///
+    /// Blank lines and comments are skipped; a leading `\` escapes `#` or
+    /// `!`.
+    #[must_use]
+    pub fn parse(text: &str) -> Self {
+        Self {
+            rules: text.lines().filter_map(Rule::parse).collect(),
+        }
+    }
+
+    /// Reads the [`IGNORE_FILES`] in `dir` on top of the built-in patterns.
+    ///
+    /// Reads go through the current [`Fs`](crate::fs::Fs); missing files
+    /// are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an ignore file exists but cannot be read.
+    pub fn load(dir: &Path) -> io::Result<Self> {
+        let mut rules = Self::builtin();
+        rules.extend(read_ignore_files(&*crate::fs::current(), dir)?);
+        Ok(rules)
+    }
+
+    /// Appends `other`'s rules, which take precedence over these.
+    pub fn extend(&mut self, other: Self) {
+        self.rules.extend(other.rules);
+    }
+
+    /// Returns `true` if `path`, relative to the directory the rules apply
+    /// to, is ignored.
+    ///
+    /// Only `path` itself is checked, not its parent directories.
+    #[must_use]
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.verdict(path, is_dir).unwrap_or(false)
+    }
+
+    /// The outcome of the last rule matching `path`, if any.
+    fn verdict(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let parts: Vec<&str> = path
+            .components()
+            .map(|c| c.as_os_str().to_str())
+            .collect::<Option<_>>()?;
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (is_dir || !rule.dir_only) && matches_segments(&rule.segments, &parts))
+            .map(|rule| !rule.negated)
+    }
+}
+
+impl Rule {
+    fn parse(line: &str) -> Option<Self> {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, line) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line.strip_prefix('\\').unwrap_or(line)),
+        };
+        let (dir_only, line) = match line.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let anchored = line.contains('/');
+        let line = line.strip_prefix('/').unwrap_or(line);
+        if line.is_empty() {
+            return None;
+        }
+
+        let mut segments = Vec::new();
+        if !anchored {
+            segments.push(Segment::AnyDepth);
+        }
+        segments.extend(line.split('/').map(|part| match part {
+            "**" => Segment::AnyDepth,
+            part => Segment::Name(Glob::new(part)),
+        }));
+        Some(Self {
+            segments,
+            negated,
+            dir_only,
+        })
+    }
+}
+
+fn matches_segments(segments: &[Segment], parts: &[&str]) -> bool {
+    match segments.split_first() {
+        None => parts.is_empty(),
+        Some((Segment::AnyDepth, rest)) => {
+            (0..=parts.len()).any(|skip| matches_segments(rest, &parts[skip..]))
+        }
+        Some((Segment::Name(glob), rest)) => parts
+            .split_first()
+            .is_some_and(|(part, parts)| glob.matches(part) && matches_segments(rest, parts)),
+    }
+}
+
+/// Lists every file under `dir` that is not ignored, sorted by path.
+///
+/// Applies the [`BUILTIN_PATTERNS`] and the [`IGNORE_FILES`] of `dir` and
+/// every subdirectory walked. Reads go through the current
+/// [`Fs`](crate::fs::Fs); a missing `dir` yields no files.
+///
+/// # Errors
+///
+/// Returns an error if a directory or ignore file cannot be read.
+pub fn list_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let fs = crate::fs::current();
+    if !fs.is_dir(dir) {
+        return Ok(Vec::new());
+    }
+    let mut levels = vec![(dir.to_path_buf(), IgnoreRules::load(dir)?)];
+    let mut files = Vec::new();
+    walk(&*fs, dir, &mut levels, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+/// Rules in effect for a directory walk, one entry per directory entered.
+pub(crate) type Levels = Vec<(PathBuf, IgnoreRules)>;
+
+/// Returns `true` if `path` is ignored by any level, deeper levels winning.
+pub(crate) fn is_ignored(levels: &Levels, path: &Path, is_dir: bool) -> bool {
+    levels
+        .iter()
+        .rev()
+        .find_map(|(base, rules)| {
+            let relative = path.strip_prefix(base).ok()?;
+            rules.verdict(relative, is_dir)
+        })
+        .unwrap_or(false)
+}
+
+/// Collects the files under `dir` into `files`, reading `dir`'s ignore files
+/// unless `levels` already ends with them.
+pub(crate) fn walk(
+    fs: &dyn Fs,
+    dir: &Path,
+    levels: &mut Levels,
+    files: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    let entered = levels.last().is_none_or(|(base, _)| base != dir);
+    if entered {
+        levels.push((dir.to_path_buf(), read_ignore_files(fs, dir)?));
+    }
+    for entry in fs.read_dir(dir)? {
+        let is_dir = fs.is_dir(&entry);
+        if is_ignored(levels, &entry, is_dir) {
+            continue;
+        }
+        if is_dir {
+            walk(fs, &entry, levels, files)?;
+        } else {
+            files.push(entry);
+        }
+    }
+    if entered {
+        levels.pop();
+    }
+    Ok(())
+}
+
+fn read_ignore_files(fs: &dyn Fs, dir: &Path) -> io::Result<IgnoreRules> {
+    let mut rules = IgnoreRules::new();
+    for name in IGNORE_FILES {
+        match fs.read_text(&dir.join(name)) {
+            Ok(decoded) => rules.extend(IgnoreRules::parse(&decoded.text)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    #[test]
+    fn unanchored_patterns_match_at_any_depth() {
+        let rules = IgnoreRules::parse("*.log\nbuild/\n");
+        assert!(rules.is_ignored(Path::new("a.log"), false));
+        assert!(rules.is_ignored(Path::new("deep/nested/a.log"), false));
+        assert!(rules.is_ignored(Path::new("scripts/build"), true));
+        assert!(!rules.is_ignored(Path::new("scripts/build"), false));
+        assert!(!rules.is_ignored(Path::new("a.md"), false));
+    }
+
+    #[test]
+    fn anchored_and_double_star_patterns() {
+        let rules = IgnoreRules::parse("/out\ndocs/*.tmp\nassets/**/raw\n");
+        assert!(rules.is_ignored(Path::new("out"), true));
+        assert!(!rules.is_ignored(Path::new("src/out"), true));
+        assert!(rules.is_ignored(Path::new("docs/a.tmp"), false));
+        assert!(!rules.is_ignored(Path::new("docs/sub/a.tmp"), false));
+        assert!(rules.is_ignored(Path::new("assets/raw"), true));
+        assert!(rules.is_ignored(Path::new("assets/img/2024/raw"), true));
+    }
+
+    #[test]
+    fn later_rules_and_negation_win() {
+        let mut rules = IgnoreRules::builtin();
+        rules.extend(IgnoreRules::parse(
+            "# comment\n\n!.well-known\n\\#literal\n",
+        ));
+        assert!(rules.is_ignored(Path::new(".DS_Store"), false));
+        assert!(rules.is_ignored(Path::new("lib/node_modules"), true));
+        assert!(!rules.is_ignored(Path::new(".well-known"), true));
+        assert!(rules.is_ignored(Path::new("#literal"), false));
+        assert!(!rules.is_ignored(Path::new("node_modules"), false));
+    }
+
+    #[test]
+    fn list_files_applies_nested_ignore_files() {
+        let ctx = PathContext::fake("/tmp/fake").with_fs(
+            MemoryFs::new()
+                .with_file("/s/review/SKILL.md", "")
+                .with_file("/s/review/.skillignore", "*.bak\n!keep.bak\n")
+                .with_file("/s/review/scripts/run.sh", "")
+                .with_file("/s/review/scripts/old.bak", "")
+                .with_file("/s/review/scripts/keep.bak", "")
+                .with_file("/s/review/scripts/.gitignore", "keep.bak\n")
+                .with_file("/s/review/.git/HEAD", "")
+                .with_file("/s/review/node_modules/x/index.js", "")
+                .with_file("/s/review/.env", ""),
+        );
+        ctx.scope(|| {
+            assert_eq!(
+                list_files(Path::new("/s/review")).unwrap(),
+                [
+                    PathBuf::from("/s/review/SKILL.md"),
+                    PathBuf::from("/s/review/scripts/run.sh"),
+                ]
+            );
+            assert!(list_files(Path::new("/s/missing")).unwrap().is_empty());
+        });
+    }
+}
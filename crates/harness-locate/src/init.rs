@@ -0,0 +1,429 @@
+//! Scaffolding project-scope config for a set of harnesses.
+//!
+//! [`init_project`] plans the minimal files each harness reads in a
+//! project, such as `.claude/settings.json` and `.mcp.json` for Claude
+//! Code, `opencode.json` for OpenCode or `.goose/config.yaml` for Goose,
+//! seeded with MCP servers from the [`templates`](crate::templates)
+//! catalog or given directly. Existing files are never touched.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::init::{InitOptions, init_project};
+//! use harness_locate::{Harness, HarnessKind};
+//!
+//! let harnesses = [
+//!     Harness::new(HarnessKind::ClaudeCode),
+//!     Harness::new(HarnessKind::OpenCode),
+//! ];
+//! let options = InitOptions::default().with_template("fetch");
+//! let plan = init_project("/tmp/new-project", &harnesses, &options)?;
+//! for path in plan.files() {
+//!     println!("create {}", path.display());
+//! }
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::document::McpConfigDocument;
+use crate::error::{Error, Result};
+use crate::harness::Harness;
+use crate::mcp::McpServer;
+use crate::types::{FileFormat, HarnessKind, ResourceKind, Scope};
+
+/// Schema of Claude Code's `settings.json`.
+const CLAUDE_SETTINGS_SCHEMA: &str = "https://json.schemastore.org/claude-code-settings.json";
+
+/// What to put in a new project.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct InitOptions {
+    /// Names of [`templates`](crate::templates) whose servers to add.
+    pub templates: Vec<String>,
+    /// Further servers to add, by name.
+    pub servers: BTreeMap<String, McpServer>,
+    /// Whether to create each harness's project skills directory.
+    pub skills: bool,
+    /// Whether to add the paths [`gitignore`](crate::gitignore) recommends
+    /// ignoring to the project `.gitignore`.
+    pub gitignore: bool,
+}
+
+impl InitOptions {
+    /// Adds the servers of the template called `name`.
+    #[must_use]
+    pub fn with_template(mut self, name: impl Into<String>) -> Self {
+        self.templates.push(name.into());
+        self
+    }
+
+    /// Adds `server` as `name`.
+    #[must_use]
+    pub fn with_server(mut self, name: impl Into<String>, server: McpServer) -> Self {
+        self.servers.insert(name.into(), server);
+        self
+    }
+
+    /// Sets whether skills directories are created.
+    #[must_use]
+    pub fn with_skills(mut self, skills: bool) -> Self {
+        self.skills = skills;
+        self
+    }
+
+    /// Sets whether the project `.gitignore` is updated.
+    #[must_use]
+    pub fn with_gitignore(mut self, gitignore: bool) -> Self {
+        self.gitignore = gitignore;
+        self
+    }
+}
+
+/// A change [`InitPlan::apply`] makes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InitAction {
+    /// Create an empty directory.
+    CreateDirectory {
+        /// The directory.
+        path: PathBuf,
+    },
+    /// Create a file, with its parent directories.
+    CreateFile {
+        /// The file.
+        path: PathBuf,
+        /// Its contents.
+        contents: String,
+    },
+    /// Append to a file, creating it if needed.
+    AppendFile {
+        /// The file.
+        path: PathBuf,
+        /// The text to append.
+        contents: String,
+    },
+}
+
+impl InitAction {
+    /// Returns the path the action creates or changes.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::CreateDirectory { path }
+            | Self::CreateFile { path, .. }
+            | Self::AppendFile { path, .. } => path,
+        }
+    }
+}
+
+/// Something [`init_project`] left out, and why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitSkip {
+    /// Name of the harness.
+    pub harness: String,
+    /// Why it was skipped, such as an existing file or an unsupported
+    /// server.
+    pub reason: String,
+}
+
+/// Files and directories to create in a new project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitPlan {
+    /// The project root.
+    pub root: PathBuf,
+    /// Changes in the order [`apply`](Self::apply) makes them.
+    pub actions: Vec<InitAction>,
+    /// Things left out.
+    pub skipped: Vec<InitSkip>,
+}
+
+impl InitPlan {
+    /// Returns `true` if there is nothing to create.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty()
+    }
+
+    /// Returns the files the plan creates or appends to.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.actions.iter().filter_map(|action| match action {
+            InitAction::CreateFile { path, .. } | InitAction::AppendFile { path, .. } => {
+                Some(path.as_path())
+            }
+            InitAction::CreateDirectory { .. } => None,
+        })
+    }
+
+    /// Creates the planned files and directories.
+    ///
+    /// Files created since the plan was made are kept rather than
+    /// overwritten. Each file written is recorded in the
+    /// [journal](crate::journal) as `init.create` or `init.append`.
+    /// Returns the number of files written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory or file cannot be created. Actions
+    /// taken before a failure are not undone.
+    pub fn apply(&self) -> Result<usize> {
+        let fs = crate::fs::current();
+        let mut written = 0;
+        for action in &self.actions {
+            match action {
+                InitAction::CreateDirectory { path } => fs.create_dir_all(path)?,
+                InitAction::CreateFile { path, contents } => {
+                    if fs.exists(path) {
+                        continue;
+                    }
+                    if let Some(parent) = path.parent() {
+                        fs.create_dir_all(parent)?;
+                    }
+                    fs.write(path, contents.as_bytes())?;
+                    crate::journal::record("init.create", path, "", contents);
+                    written += 1;
+                }
+                InitAction::AppendFile { path, contents } => {
+                    let before = fs.read_text(path).map(|t| t.text).unwrap_or_default();
+                    fs.append(path, contents.as_bytes())?;
+                    let after = format!("{before}{contents}");
+                    crate::journal::record("init.append", path, &before, &after);
+                    written += 1;
+                }
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// Plans the project-scope config of each of `harnesses` under `root`.
+///
+/// Each harness gets its project MCP config with the servers of `options`
+/// it supports, Claude Code also a `.claude/settings.json`, and with
+/// [`InitOptions::skills`] the project skills directory. With
+/// [`InitOptions::gitignore`], the personal files the harnesses keep in
+/// the project are appended to its `.gitignore`. Files that exist,
+/// harnesses without project-scoped MCP config and servers a harness
+/// cannot run are listed in [`InitPlan::skipped`]. A file shared by
+/// several harnesses is planned once, for the first.
+///
+/// Nothing is written; see [`InitPlan::apply`].
+///
+/// # Errors
+///
+/// Returns [`Error::NotFound`] for an unknown template, or an error if a
+/// config path cannot be resolved or a config cannot be rendered, such
+/// as Goose's YAML without the `yaml` feature.
+pub fn init_project(
+    root: impl Into<PathBuf>,
+    harnesses: &[Harness],
+    options: &InitOptions,
+) -> Result<InitPlan> {
+    let root = root.into();
+    let scope = Scope::Project(root.clone());
+    let fs = crate::fs::current();
+
+    let mut servers: Vec<(String, McpServer)> = Vec::new();
+    for name in &options.templates {
+        let template = crate::templates::find_template(name)
+            .ok_or_else(|| Error::NotFound(format!("MCP template {name}")))?;
+        servers.push((template.name.clone(), template.server.clone()));
+    }
+    servers.extend(options.servers.clone());
+
+    let mut plan = InitPlan {
+        root,
+        actions: Vec::new(),
+        skipped: Vec::new(),
+    };
+    for harness in harnesses {
+        let mut skip = |reason: String| {
+            plan.skipped.push(InitSkip {
+                harness: harness.name().to_string(),
+                reason,
+            });
+        };
+        let mut files = Vec::new();
+
+        if harness.kind() == Some(HarnessKind::ClaudeCode) {
+            let contents = serde_json::to_string_pretty(
+                &serde_json::json!({ "$schema": CLAUDE_SETTINGS_SCHEMA }),
+            )? + "\n";
+            files.push((harness.config(&scope)?.join("settings.json"), contents));
+        }
+
+        match harness.mcp(&scope) {
+            Ok(Some(resource)) => {
+                let initial = match (&resource.schema_url, resource.format) {
+                    (Some(url), FileFormat::Json | FileFormat::Jsonc) => {
+                        serde_json::json!({ "$schema": url }).to_string()
+                    }
+                    _ => String::new(),
+                };
+                let mut document = McpConfigDocument::parse(
+                    &resource.file,
+                    &resource.key_path,
+                    resource.format,
+                    &initial,
+                )?;
+                document.ensure_servers();
+                for (name, server) in &servers {
+                    if harness.supports_mcp_server(server) {
+                        document.set(name, harness.mcp_to_native(name, server)?);
+                    } else {
+                        skip(format!("cannot run MCP server {name}"));
+                    }
+                }
+                files.push((resource.file, document.render()?));
+            }
+            Ok(None) | Err(Error::UnsupportedScope { .. }) => {
+                skip("no project-scoped MCP config".to_string());
+            }
+            Err(e) => return Err(e),
+        }
+
+        for (path, contents) in files {
+            if fs.exists(&path) {
+                skip(format!("{} already exists", path.display()));
+            } else if plan.actions.iter().all(|action| action.path() != path) {
+                plan.actions.push(InitAction::CreateFile { path, contents });
+            }
+        }
+
+        if options.skills {
+            match harness.resource(ResourceKind::Skills, &scope) {
+                Ok(Some(dir)) if !dir.exists => {
+                    if plan.actions.iter().all(|action| action.path() != dir.path) {
+                        plan.actions
+                            .push(InitAction::CreateDirectory { path: dir.path });
+                    }
+                }
+                Ok(_) | Err(Error::UnsupportedScope { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    if options.gitignore {
+        let report = crate::gitignore::check_gitignore(&plan.root, harnesses)?;
+        if let Some(patch) = report.patch() {
+            let contents = if fs.exists(&report.path) {
+                patch
+            } else {
+                patch.trim_start().to_string()
+            };
+            plan.actions.push(InitAction::AppendFile {
+                path: report.path,
+                contents,
+            });
+        }
+    }
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    #[test]
+    fn plans_each_harness_layout_once() {
+        let fs = MemoryFs::new().with_file("/repo/opencode.json", "{}");
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let harnesses = [
+            Harness::new(HarnessKind::ClaudeCode),
+            Harness::new(HarnessKind::OpenCode),
+            Harness::new(HarnessKind::AmpCode),
+        ];
+        let options = InitOptions::default()
+            .with_template("github")
+            .with_skills(true);
+
+        let plan = ctx
+            .scope(|| init_project("/repo", &harnesses, &options))
+            .unwrap();
+        let files: Vec<_> = plan.files().collect();
+        assert_eq!(
+            files,
+            [
+                Path::new("/repo/.claude/settings.json"),
+                Path::new("/repo/.mcp.json"),
+            ]
+        );
+        let InitAction::CreateFile { contents, .. } = &plan.actions[1] else {
+            panic!("expected a file");
+        };
+        let mcp: serde_json::Value = serde_json::from_str(contents).unwrap();
+        assert_eq!(mcp["mcpServers"]["github"]["command"], "docker");
+        assert!(plan.actions.contains(&InitAction::CreateDirectory {
+            path: PathBuf::from("/repo/.claude/skills")
+        }));
+        let reasons: Vec<_> = plan
+            .skipped
+            .iter()
+            .map(|s| (s.harness.as_str(), s.reason.as_str()))
+            .collect();
+        assert!(reasons.contains(&("OpenCode", "/repo/opencode.json already exists")));
+        assert!(reasons.contains(&("AMP Code", "no project-scoped MCP config")));
+
+        assert_eq!(ctx.scope(|| plan.apply()).unwrap(), 2);
+        let servers = ctx
+            .scope(|| harnesses[0].read_mcp_servers(&Scope::Project("/repo".into())))
+            .unwrap();
+        assert!(servers.contains_key("github"));
+    }
+
+    #[test]
+    fn appends_personal_files_to_gitignore() {
+        let fs = MemoryFs::new().with_file("/repo/.gitignore", "target/\n");
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let options = InitOptions::default().with_gitignore(true);
+        let harnesses = [Harness::new(HarnessKind::ClaudeCode)];
+
+        let plan = ctx
+            .scope(|| init_project("/repo", &harnesses, &options))
+            .unwrap();
+        assert_eq!(
+            plan.actions.last(),
+            Some(&InitAction::AppendFile {
+                path: PathBuf::from("/repo/.gitignore"),
+                contents: "\n# Local agent files\n.claude/settings.local.json\nCLAUDE.local.md\n"
+                    .to_string(),
+            })
+        );
+        ctx.scope(|| plan.apply()).unwrap();
+        let plan = ctx
+            .scope(|| init_project("/repo", &harnesses, &options))
+            .unwrap();
+        assert!(plan.is_empty());
+
+        let journal = ctx.scope(crate::journal::read_journal).unwrap();
+        let ops: Vec<_> = journal
+            .iter()
+            .map(|e| (e.operation.as_str(), e.file.as_path()))
+            .collect();
+        assert_eq!(
+            ops,
+            [
+                ("init.create", Path::new("/repo/.claude/settings.json")),
+                ("init.create", Path::new("/repo/.mcp.json")),
+                ("init.append", Path::new("/repo/.gitignore")),
+            ]
+        );
+        let gitignore = ctx.scope(|| crate::fs::read_text(Path::new("/repo/.gitignore")));
+        assert_eq!(
+            journal[2].diff_hash,
+            crate::journal::diff_hash("target/\n", &gitignore.unwrap().text)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_templates() {
+        let options = InitOptions::default().with_template("nope");
+        let err = PathContext::fake("/fake")
+            .scope(|| init_project("/repo", &[], &options))
+            .unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+    }
+}
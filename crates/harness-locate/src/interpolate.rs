@@ -0,0 +1,343 @@
+//! Expanding `~`, `$HOME` and `${workspaceFolder}` in server paths.
+//!
+//! Configs in the wild start servers with `~/bin/server`, `$HOME/x` or
+//! editor-style `${workspaceFolder}/tools/mcp`, and harnesses differ in
+//! which of these they expand. An [`InterpolationContext`] holds the
+//! values to substitute: [`expand_server`] (or
+//! [`Harness::read_mcp_servers_interpolated`](crate::Harness::read_mcp_servers_interpolated))
+//! resolves the command, arguments and working directory of a parsed
+//! server, and [`contract_native`] does the reverse for a config about to
+//! be written, so it works from any checkout and home directory.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::interpolate::InterpolationContext;
+//! use harness_locate::HarnessKind;
+//!
+//! let ctx = InterpolationContext::new()
+//!     .with_home("/home/alice")
+//!     .with_workspace("/home/alice/src/app");
+//!
+//! assert_eq!(ctx.expand("~/bin/server"), "/home/alice/bin/server");
+//! assert_eq!(ctx.expand("--root=${workspaceFolder}/docs"), "--root=/home/alice/src/app/docs");
+//! assert_eq!(ctx.expand("$UNSET/x"), "$UNSET/x");
+//!
+//! assert_eq!(
+//!     ctx.contract("/home/alice/src/app/tools/mcp", HarnessKind::ClaudeCode),
+//!     "./tools/mcp"
+//! );
+//! assert_eq!(ctx.contract("/home/alice/bin/server", HarnessKind::ClaudeCode), "${HOME}/bin/server");
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::error::Result;
+use crate::mcp::McpServer;
+use crate::types::{HarnessKind, Scope};
+
+/// Variable names that mean the workspace root.
+const WORKSPACE_VARS: &[&str] = &["workspaceFolder", "workspaceRoot"];
+
+/// Variable names that mean the home directory.
+const HOME_VARS: &[&str] = &["HOME", "USERPROFILE", "userHome"];
+
+/// Values substituted by [`InterpolationContext::expand`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct InterpolationContext {
+    /// Replaces the home directory placeholders that
+    /// [`expand_portable`](crate::portable::expand_portable) recognizes:
+    /// `~`, `$HOME`, `${HOME}`, `${env:HOME}`, `{env:HOME}`,
+    /// `$USERPROFILE` and `${userHome}`.
+    pub home: Option<PathBuf>,
+    /// Replaces `${workspaceFolder}` and `${workspaceRoot}`.
+    pub workspace: Option<PathBuf>,
+    /// Other variables, referenced as `$NAME`, `${NAME}` or `${env:NAME}`.
+    pub vars: BTreeMap<String, String>,
+}
+
+impl InterpolationContext {
+    /// Creates a context that substitutes nothing.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a context for configs of `scope`: the user's home directory
+    /// and, for [`Scope::Project`], the project root as the workspace.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the home directory cannot be determined.
+    pub fn for_scope(scope: &Scope) -> Result<Self> {
+        let mut ctx = Self::new().with_home(crate::platform::home_dir()?);
+        if let Scope::Project(root) = scope {
+            ctx = ctx.with_workspace(root);
+        }
+        Ok(ctx)
+    }
+
+    /// Sets the home directory.
+    #[must_use]
+    pub fn with_home(mut self, home: impl Into<PathBuf>) -> Self {
+        self.home = Some(home.into());
+        self
+    }
+
+    /// Sets the workspace root.
+    #[must_use]
+    pub fn with_workspace(mut self, workspace: impl Into<PathBuf>) -> Self {
+        self.workspace = Some(workspace.into());
+        self
+    }
+
+    /// Adds a variable.
+    #[must_use]
+    pub fn with_var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    /// Returns the value of the variable `name`, if the context has one.
+    #[must_use]
+    pub fn lookup(&self, name: &str) -> Option<String> {
+        let name = name.strip_prefix("env:").unwrap_or(name);
+        if let Some(value) = self.vars.get(name) {
+            return Some(value.clone());
+        }
+        let path = if WORKSPACE_VARS.contains(&name) {
+            self.workspace.as_deref()
+        } else if HOME_VARS.contains(&name) {
+            self.home.as_deref()
+        } else {
+            None
+        };
+        path.map(|path| trim_separator(path).to_string())
+    }
+
+    /// Substitutes the variables and home directory references in `s`.
+    ///
+    /// Home directory references are expanded as by
+    /// [`expand_portable`](crate::portable::expand_portable): only as a
+    /// whole leading path component, at the start of `s` or after
+    /// whitespace, `=`, `:` or `,`, so `a~b`, `~bob` and `$HOMEDIR` are
+    /// kept. Other variables are expanded wherever they appear. Variables
+    /// the context has no value for are left as written.
+    #[must_use]
+    pub fn expand(&self, s: &str) -> String {
+        let expanded;
+        let mut rest = match &self.home {
+            Some(home) => {
+                expanded = crate::portable::expand_home(s, trim_separator(home));
+                expanded.as_str()
+            }
+            None => s,
+        };
+        let mut out = String::with_capacity(rest.len());
+        while let Some(index) = rest.find('$') {
+            out.push_str(&rest[..index]);
+            rest = &rest[index..];
+            let (replacement, consumed) = self.expand_variable(rest);
+            match replacement {
+                Some(value) => out.push_str(&value),
+                None => out.push_str(&rest[..consumed]),
+            }
+            rest = &rest[consumed..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// Returns the value of the variable reference at the start of `s` and
+    /// its length, or `None` and the length to copy as is.
+    ///
+    /// Home directory variables are left to [`expand`](Self::expand)'s
+    /// placeholder pass, unless the context sets them explicitly.
+    fn expand_variable(&self, s: &str) -> (Option<String>, usize) {
+        let (name, len) = if let Some(braced) = s.strip_prefix("${") {
+            match braced.find('}') {
+                Some(end) => (&braced[..end], end + 3),
+                None => return (None, 1),
+            }
+        } else {
+            let len = s[1..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(s.len() - 1);
+            if len == 0 {
+                return (None, 1);
+            }
+            (&s[1..=len], len + 1)
+        };
+        let bare = name.strip_prefix("env:").unwrap_or(name);
+        if HOME_VARS.contains(&bare) && !self.vars.contains_key(bare) {
+            return (None, len);
+        }
+        (self.lookup(name), len)
+    }
+
+    /// Rewrites paths in `s` so they hold on any machine: paths under the
+    /// workspace become relative to it (`./tools/mcp`, or `.` for the root
+    /// itself) and paths under the home directory start with `kind`'s
+    /// [`home_placeholder`](crate::portable::home_placeholder).
+    ///
+    /// Relative paths resolve against the server's working directory,
+    /// which is the project root for project-scoped servers in every
+    /// supported harness.
+    #[must_use]
+    pub fn contract(&self, s: &str, kind: HarnessKind) -> String {
+        let mut s = s.to_string();
+        if let Some(workspace) = &self.workspace {
+            let workspace = trim_separator(workspace);
+            if !workspace.is_empty() {
+                s = crate::portable::replace_paths(&s, workspace, ".");
+            }
+        }
+        if let Some(home) = &self.home {
+            let home = trim_separator(home);
+            if !home.is_empty() {
+                s = crate::portable::replace_paths(
+                    &s,
+                    home,
+                    crate::portable::home_placeholder(kind),
+                );
+            }
+        }
+        s
+    }
+}
+
+fn trim_separator(path: &Path) -> &str {
+    path.to_str()
+        .unwrap_or_default()
+        .trim_end_matches(['/', '\\'])
+}
+
+/// Expands the command, arguments and working directory of a stdio
+/// `server` with `ctx`.
+///
+/// Environment values and URLs are left alone: harnesses already resolve
+/// env references, and other servers have no local paths.
+pub fn expand_server(server: &mut McpServer, ctx: &InterpolationContext) {
+    let McpServer::Stdio(stdio) = server else {
+        return;
+    };
+    stdio.command = ctx.expand(&stdio.command);
+    for arg in &mut stdio.args {
+        *arg = ctx.expand(arg);
+    }
+    if let Some(cwd) = &mut stdio.cwd {
+        *cwd = PathBuf::from(ctx.expand(&cwd.to_string_lossy()));
+    }
+}
+
+/// Applies [`InterpolationContext::contract`] to every string value of a
+/// native config, such as the output of
+/// [`McpServer::to_native_value`](crate::mcp::McpServer::to_native_value).
+/// Object keys are left alone.
+pub fn contract_native(value: &mut Value, ctx: &InterpolationContext, kind: HarnessKind) {
+    crate::portable::map_strings(value, &mut |s| ctx.contract(s, kind));
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::mcp::StdioMcpServer;
+
+    fn ctx() -> InterpolationContext {
+        InterpolationContext::new()
+            .with_home("/home/al/")
+            .with_workspace("/home/al/app")
+            .with_var("DATA", "/srv/data")
+    }
+
+    #[test]
+    fn expands_home_workspace_and_vars() {
+        let ctx = ctx();
+        let cases = [
+            ("~", "/home/al"),
+            ("~/bin", "/home/al/bin"),
+            ("--log=~/x.log", "--log=/home/al/x.log"),
+            ("a~b", "a~b"),
+            ("~bob/x", "~bob/x"),
+            ("$HOME/x", "/home/al/x"),
+            ("$HOMEDIR/x", "$HOMEDIR/x"),
+            ("a$HOME/x", "a$HOME/x"),
+            ("${env:HOME}/x", "/home/al/x"),
+            ("{env:HOME}/x", "/home/al/x"),
+            ("${workspaceFolder}/tools", "/home/al/app/tools"),
+            ("$DATA:${DATA}", "/srv/data:/srv/data"),
+            ("${unknown} $ ${open", "${unknown} $ ${open"),
+            ("naïve ~/é", "naïve /home/al/é"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(ctx.expand(input), expected, "{input}");
+        }
+        assert_eq!(InterpolationContext::new().expand("~/x"), "~/x");
+    }
+
+    #[test]
+    fn expands_home_like_expand_portable() {
+        let ctx = InterpolationContext::new().with_home("/home/al");
+        let inputs = [
+            "~",
+            "~/a",
+            "a~b",
+            "~bob",
+            "x=~/y",
+            "$HOME",
+            "$HOMEDIR",
+            "a$HOME",
+            "${HOME}/c",
+            "{env:HOME}/d",
+            "${userHome}",
+            "$USERPROFILE/e",
+        ];
+        let mut portable = json!(inputs);
+        crate::portable::expand_portable(&mut portable, Path::new("/home/al"));
+        for (input, expected) in inputs.iter().zip(portable.as_array().unwrap()) {
+            assert_eq!(ctx.expand(input), expected.as_str().unwrap(), "{input}");
+        }
+    }
+
+    #[test]
+    fn expands_stdio_paths_only() {
+        let mut server = McpServer::Stdio(StdioMcpServer {
+            command: "~/bin/server".to_string(),
+            args: vec!["--root=${workspaceFolder}".to_string()],
+            env: [("TOKEN".to_string(), crate::types::EnvValue::plain("$HOME"))].into(),
+            cwd: Some(PathBuf::from("${workspaceFolder}/sub")),
+            enabled: true,
+            timeout_ms: None,
+        });
+        expand_server(&mut server, &ctx());
+        let McpServer::Stdio(stdio) = server else {
+            unreachable!()
+        };
+        assert_eq!(stdio.command, "/home/al/bin/server");
+        assert_eq!(stdio.args, ["--root=/home/al/app"]);
+        assert_eq!(stdio.cwd, Some(PathBuf::from("/home/al/app/sub")));
+        assert_eq!(stdio.env["TOKEN"], crate::types::EnvValue::plain("$HOME"));
+    }
+
+    #[test]
+    fn contracts_workspace_before_home() {
+        let mut native = json!({
+            "command": "/home/al/app/bin/mcp",
+            "args": ["--root=/home/al/app", "/home/al/.cache", "/home/al/apple"],
+        });
+        contract_native(&mut native, &ctx(), HarnessKind::OpenCode);
+        assert_eq!(
+            native,
+            json!({
+                "command": "./bin/mcp",
+                "args": ["--root=.", "{env:HOME}/.cache", "{env:HOME}/apple"],
+            })
+        );
+    }
+}
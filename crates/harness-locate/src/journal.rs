@@ -0,0 +1,218 @@
+//! Journal of configuration changes.
+//!
+//! Mutations applied to a harness configuration are recorded with [`append`]
+//! as one JSON line in a per-user journal, so users can audit what automated
+//! tools changed in their agent configs over time. The journal lives at
+//! `harness-locate/journal.jsonl` in the platform data directory
+//! (`~/.local/share` on Linux).
+//!
+//! Entries only identify a change; the content itself is never written to
+//! the journal, since config files routinely contain credentials.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::journal::read_journal;
+//!
+//! for entry in read_journal()? {
+//!     println!("{} {} {} {}", entry.timestamp, entry.tool, entry.operation, entry.file.display());
+//! }
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+/// File name of the journal inside [`journal_dir`].
+pub const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// Tool name recorded for changes made by this crate's write APIs.
+pub const TOOL: &str = "harness-locate";
+
+/// A single recorded configuration change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Seconds since the Unix epoch when the change was applied.
+    pub timestamp: u64,
+    /// Name of the tool that applied the change.
+    pub tool: String,
+    /// What was done (e.g., `"mcp.add"`, `"skill.remove"`).
+    pub operation: String,
+    /// The file that was changed.
+    pub file: PathBuf,
+    /// Fingerprint of the change, from [`diff_hash`].
+    pub diff_hash: String,
+}
+
+impl JournalEntry {
+    /// Creates an entry for a change from `before` to `after`, timestamped
+    /// now.
+    ///
+    /// Pass an empty string as `before` for a newly created file, and as
+    /// `after` for a deleted one.
+    #[must_use]
+    pub fn new(
+        tool: impl Into<String>,
+        operation: impl Into<String>,
+        file: impl Into<PathBuf>,
+        before: &str,
+        after: &str,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        Self {
+            timestamp,
+            tool: tool.into(),
+            operation: operation.into(),
+            file: file.into(),
+            diff_hash: diff_hash(before, after),
+        }
+    }
+}
+
+/// Returns the directory holding the journal.
+///
+/// # Errors
+///
+/// Returns an error if the data directory cannot be determined.
+pub fn journal_dir() -> Result<PathBuf> {
+    Ok(crate::platform::data_dir()?.join("harness-locate"))
+}
+
+/// Returns the path of the journal file.
+///
+/// # Errors
+///
+/// Returns an error if the data directory cannot be determined.
+pub fn journal_path() -> Result<PathBuf> {
+    Ok(journal_dir()?.join(JOURNAL_FILE))
+}
+
+/// Appends `entry` to the journal, creating it if needed.
+///
+/// # Errors
+///
+/// Returns an error if the journal cannot be written.
+pub fn append(entry: &JournalEntry) -> Result<()> {
+    let path = journal_path()?;
+    let fs = crate::fs::current();
+    if let Some(dir) = path.parent() {
+        fs.create_dir_all(dir)?;
+    }
+    let mut line = serde_json::to_vec(entry)?;
+    line.push(b'\n');
+    fs.append(&path, &line)?;
+    Ok(())
+}
+
+/// Journals a change this crate just wrote to `file`, as [`TOOL`].
+///
+/// The change is already on disk by then, so a journal that cannot be
+/// written does not turn a successful write into an error.
+pub(crate) fn record(operation: &str, file: &Path, before: &str, after: &str) {
+    let _ = append(&JournalEntry::new(TOOL, operation, file, before, after));
+}
+
+/// Reads every entry in the journal, oldest first.
+///
+/// Returns an empty list if nothing has been journaled yet.
+///
+/// # Errors
+///
+/// Returns an error if the journal cannot be read or a line is not a
+/// valid entry.
+pub fn read_journal() -> Result<Vec<JournalEntry>> {
+    let content = match crate::fs::read_text(&journal_path()?) {
+        Ok(decoded) => decoded.text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| Ok(serde_json::from_str(line)?))
+        .collect()
+}
+
+/// Fingerprints a change from `before` to `after`.
+///
+/// The result is a 16-digit hex FNV-1a hash, stable across platforms and
+/// releases. It identifies a change without revealing the content.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::journal::diff_hash;
+///
+/// assert_eq!(diff_hash("a", "b"), diff_hash("a", "b"));
+/// assert_ne!(diff_hash("a", "b"), diff_hash("b", "a"));
+/// ```
+#[must_use]
+pub fn diff_hash(before: &str, after: &str) -> String {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    // Length-prefix `before` so that moving text across the boundary
+    // changes the hash.
+    let len = (before.len() as u64).to_le_bytes();
+    let hash = len
+        .iter()
+        .chain(before.as_bytes())
+        .chain(after.as_bytes())
+        .fold(OFFSET, |hash, &byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    #[test]
+    fn journal_path_is_under_data_dir() {
+        let ctx = PathContext::fake("/tmp/fake");
+        let path = ctx.scope(journal_path).unwrap();
+        assert_eq!(
+            path,
+            PathBuf::from("/tmp/fake/data/harness-locate/journal.jsonl")
+        );
+    }
+
+    #[test]
+    fn append_then_read_round_trips() {
+        let ctx = PathContext::fake("/tmp/fake").with_fs(MemoryFs::new());
+        let first = JournalEntry::new("installer", "mcp.add", "/p/.mcp.json", "", "{}");
+        let second = JournalEntry::new("installer", "mcp.remove", "/p/.mcp.json", "{}", "");
+
+        ctx.scope(|| {
+            assert!(read_journal().unwrap().is_empty());
+            append(&first).unwrap();
+            append(&second).unwrap();
+            assert_eq!(read_journal().unwrap(), vec![first, second]);
+        });
+    }
+
+    #[test]
+    fn malformed_lines_are_errors() {
+        let ctx = PathContext::fake("/tmp/fake");
+        let path = ctx.scope(journal_path).unwrap();
+        let ctx = ctx.with_fs(MemoryFs::new().with_file(&path, "{\"timestamp\": 1}\n"));
+
+        assert!(ctx.scope(read_journal).is_err());
+    }
+
+    #[test]
+    fn diff_hash_is_stable() {
+        assert_eq!(diff_hash("a", "b"), "519e2ac89e064887");
+        assert_eq!(diff_hash("ab", "c").len(), 16);
+        assert_ne!(diff_hash("ab", "c"), diff_hash("a", "bc"));
+    }
+}
@@ -0,0 +1,127 @@
+//! JSON with comments, as accepted by `.jsonc` configuration files.
+//!
+//! [`strip`] blanks out `//` and `/* */` comments and drops trailing commas
+//! so the result parses with `serde_json`. Comments are replaced by spaces
+//! and their newlines are kept, so parse errors still point at the line and
+//! column of the original file.
+
+use std::borrow::Cow;
+
+/// Returns `content` with comments and trailing commas removed.
+///
+/// Borrows `content` unchanged when it has neither.
+pub(crate) fn strip(content: &str) -> Cow<'_, str> {
+    if !content.contains('/') && !content.contains(',') {
+        return Cow::Borrowed(content);
+    }
+
+    let bytes = content.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut changed = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+                out.extend_from_slice(&bytes[start..i]);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                changed = true;
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    out.push(b' ');
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                changed = true;
+                let end = content[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |offset| i + 2 + offset + 2);
+                out.extend(
+                    bytes[i..end]
+                        .iter()
+                        .map(|&b| if b == b'\n' || b == b'\r' { b } else { b' ' }),
+                );
+                i = end;
+            }
+            b',' if closes_after_comma(bytes, i + 1) => {
+                changed = true;
+                out.push(b' ');
+                i += 1;
+            }
+            b => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+
+    if !changed {
+        return Cow::Borrowed(content);
+    }
+    // Only ASCII was replaced, and only outside strings, so the result is
+    // still valid UTF-8.
+    Cow::Owned(String::from_utf8(out).expect("ASCII replacements keep UTF-8 valid"))
+}
+
+/// Reports whether the next token after `from`, skipping whitespace and
+/// comments, closes an object or array.
+fn closes_after_comma(bytes: &[u8], mut from: usize) -> bool {
+    while from < bytes.len() {
+        match bytes[from] {
+            b' ' | b'\t' | b'\n' | b'\r' => from += 1,
+            b'/' if bytes.get(from + 1) == Some(&b'/') => {
+                while from < bytes.len() && bytes[from] != b'\n' {
+                    from += 1;
+                }
+            }
+            b'/' if bytes.get(from + 1) == Some(&b'*') => {
+                from += 2;
+                while from < bytes.len() && !bytes[from..].starts_with(b"*/") {
+                    from += 1;
+                }
+                from += 2;
+            }
+            b'}' | b']' => return true,
+            _ => return false,
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_json_is_borrowed() {
+        let content = r#"{"a": [1, 2], "b": "x"}"#;
+        assert!(matches!(strip(content), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn strips_comments_and_trailing_commas() {
+        let content = "{\n  // line\n  \"a\": [1, 2,], /* block\n */ \"b\": \"//x, /*y*/\",\n}\n";
+        let value: serde_json::Value = serde_json::from_str(&strip(content)).unwrap();
+        assert_eq!(value, serde_json::json!({"a": [1, 2], "b": "//x, /*y*/"}));
+    }
+
+    #[test]
+    fn keeps_line_numbers() {
+        let content = "{\n  // comment\n  \"a\": nope\n}";
+        let error = serde_json::from_str::<serde_json::Value>(&strip(content)).unwrap_err();
+        assert_eq!(error.line(), 3);
+    }
+
+    #[test]
+    fn escaped_quotes_stay_inside_strings() {
+        let content = r#"{"a": "say \"// hi\"", }"#;
+        let value: serde_json::Value = serde_json::from_str(&strip(content)).unwrap();
+        assert_eq!(value, serde_json::json!({"a": "say \"// hi\""}));
+    }
+}
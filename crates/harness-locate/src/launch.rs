@@ -0,0 +1,608 @@
+//! Starting stdio MCP servers.
+//!
+//! [`launch`] spawns a stdio [`McpServer`] the way a harness would: the
+//! command is resolved on `PATH` (including Windows `.cmd` shims), env
+//! references are resolved, and stdin and stdout are piped for JSON-RPC.
+//! The returned [`McpProcess`] exchanges messages with timeouts, performs
+//! the MCP [`handshake`](McpProcess::handshake), and kills the server when
+//! dropped.
+//!
+//! [`SandboxOptions`] narrow what the server sees: a minimal environment,
+//! proxy settings that fail network access for well-behaved clients, and a
+//! working directory confined to a root. They are guard rails for probing
+//! unknown configs, not a security boundary; a server can still open
+//! sockets or files directly.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use harness_locate::launch::{LaunchOptions, SandboxOptions, launch};
+//! use harness_locate::mcp::{McpServer, StdioMcpServer};
+//!
+//! let server = McpServer::Stdio(StdioMcpServer {
+//!     command: "npx".into(),
+//!     args: vec!["-y".into(), "@modelcontextprotocol/server-memory".into()],
+//!     env: Default::default(),
+//!     cwd: None,
+//!     enabled: true,
+//!     timeout_ms: None,
+//! });
+//! let options = LaunchOptions::default()
+//!     .with_timeout(Duration::from_secs(30))
+//!     .with_sandbox(SandboxOptions::default().with_no_network(true));
+//! let mut process = launch(&server, &options)?;
+//! let info = process.handshake()?;
+//! println!("{}", info["serverInfo"]["name"]);
+//! process.kill()?;
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde_json::{Value, json};
+
+use crate::error::{Error, Result};
+use crate::mcp::{McpServer, StdioMcpServer};
+use crate::types::EnvValue;
+
+/// Protocol version sent in [`McpProcess::handshake`].
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Default time to wait for a response or for the server to exit.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long [`McpProcess::wait`] keeps reading stderr after the server
+/// exits.
+const STDERR_GRACE: Duration = Duration::from_millis(200);
+
+/// Proxy address that refuses connections, used by
+/// [`SandboxOptions::no_network`].
+const BLACKHOLE_PROXY: &str = "http://127.0.0.1:9";
+
+/// Variables passed through by [`SandboxOptions::clear_env`].
+const BASE_ENV: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USERPROFILE",
+    "TMPDIR",
+    "TEMP",
+    "TMP",
+    "LANG",
+    "SystemRoot",
+    "ComSpec",
+    "PATHEXT",
+];
+
+/// Restrictions applied to a launched server.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SandboxOptions {
+    /// Start from an empty environment, passing through only `PATH`, the
+    /// home and temp directories and the Windows essentials, plus the
+    /// server's own `env`.
+    pub clear_env: bool,
+    /// Point `HTTP_PROXY`, `HTTPS_PROXY` and `ALL_PROXY` at a closed port
+    /// and put npm, pip and uv in offline mode.
+    pub no_network: bool,
+    /// Require the working directory to be inside this directory. A server
+    /// without `cwd` starts in it.
+    pub restrict_cwd: Option<PathBuf>,
+}
+
+impl SandboxOptions {
+    /// Sets whether the environment is cleared.
+    #[must_use]
+    pub fn with_clear_env(mut self, clear_env: bool) -> Self {
+        self.clear_env = clear_env;
+        self
+    }
+
+    /// Sets whether network access is discouraged.
+    #[must_use]
+    pub fn with_no_network(mut self, no_network: bool) -> Self {
+        self.no_network = no_network;
+        self
+    }
+
+    /// Confines the working directory to `root`.
+    #[must_use]
+    pub fn with_restricted_cwd(mut self, root: impl Into<PathBuf>) -> Self {
+        self.restrict_cwd = Some(root.into());
+        self
+    }
+}
+
+/// How [`launch`] starts a server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LaunchOptions {
+    /// How long to wait for a response, or for the server to exit in
+    /// [`McpProcess::wait`].
+    pub timeout: Duration,
+    /// Directory a relative `cwd` is resolved against; the current
+    /// directory if unset.
+    pub project_root: Option<PathBuf>,
+    /// Restrictions on the process.
+    pub sandbox: SandboxOptions,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            project_root: None,
+            sandbox: SandboxOptions::default(),
+        }
+    }
+}
+
+impl LaunchOptions {
+    /// Sets how long to wait for responses and exit.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the directory a relative `cwd` is resolved against.
+    #[must_use]
+    pub fn with_project_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.project_root = Some(root.into());
+        self
+    }
+
+    /// Sets the restrictions on the process.
+    #[must_use]
+    pub fn with_sandbox(mut self, sandbox: SandboxOptions) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+}
+
+/// How a launched server ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchExit {
+    /// The exit code, or `None` if the process was killed or timed out.
+    pub code: Option<i32>,
+    /// Whether the server was killed after [`LaunchOptions::timeout`].
+    pub timed_out: bool,
+    /// Everything the server wrote to stderr.
+    pub stderr: String,
+}
+
+/// A running stdio MCP server, killed when dropped.
+#[derive(Debug)]
+pub struct McpProcess {
+    child: Child,
+    stdin: Option<ChildStdin>,
+    messages: Receiver<String>,
+    stderr: Arc<Mutex<Vec<u8>>>,
+    stderr_reader: Option<JoinHandle<()>>,
+    timeout: Duration,
+    next_id: u64,
+}
+
+/// Starts `server` with stdin and stdout piped.
+///
+/// # Errors
+///
+/// Returns [`Error::UnsupportedMcpConfig`] for servers that are not stdio,
+/// [`Error::MissingEnvVar`] if an env reference is not set,
+/// [`Error::InvalidPath`] if the working directory is outside
+/// [`SandboxOptions::restrict_cwd`], or [`Error::Io`] if the process
+/// cannot be spawned.
+pub fn launch(server: &McpServer, options: &LaunchOptions) -> Result<McpProcess> {
+    let McpServer::Stdio(stdio) = server else {
+        return Err(Error::UnsupportedMcpConfig {
+            harness: "launch".to_string(),
+            reason: "only stdio servers can be launched".to_string(),
+        });
+    };
+    let mut command = build_command(stdio, options)?;
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = command.spawn()?;
+
+    let (sender, messages) = mpsc::channel();
+    let stdout = child.stdout.take();
+    std::thread::spawn(move || {
+        let Some(stdout) = stdout else { return };
+        for line in BufReader::new(stdout).lines() {
+            let Ok(line) = line else { break };
+            if !line.trim().is_empty() && sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    // Collected incrementally: a grandchild that outlives the server can
+    // keep the pipe open, and `wait` must not block on it.
+    let stderr = Arc::new(Mutex::new(Vec::new()));
+    let stderr_reader = child.stderr.take().map(|mut pipe| {
+        let stderr = Arc::clone(&stderr);
+        std::thread::spawn(move || {
+            let mut buf = [0; 4096];
+            while let Ok(n @ 1..) = pipe.read(&mut buf) {
+                if let Ok(mut stderr) = stderr.lock() {
+                    stderr.extend_from_slice(&buf[..n]);
+                }
+            }
+        })
+    });
+
+    Ok(McpProcess {
+        stdin: child.stdin.take(),
+        child,
+        messages,
+        stderr,
+        stderr_reader,
+        timeout: options.timeout,
+        next_id: 0,
+    })
+}
+
+/// Builds the command for `server` without spawning it.
+fn build_command(server: &StdioMcpServer, options: &LaunchOptions) -> Result<Command> {
+    let program = crate::detection::find_binary(&server.command)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| PathBuf::from(&server.command));
+    let mut command = Command::new(program);
+    command.args(&server.args);
+
+    let sandbox = &options.sandbox;
+    if sandbox.clear_env {
+        command.env_clear();
+        for name in BASE_ENV {
+            if let Some(value) = std::env::var_os(name) {
+                command.env(name, value);
+            }
+        }
+    }
+    if sandbox.no_network {
+        for name in ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY"] {
+            command.env(name, BLACKHOLE_PROXY);
+            command.env(name.to_ascii_lowercase(), BLACKHOLE_PROXY);
+        }
+        command
+            .env_remove("NO_PROXY")
+            .env_remove("no_proxy")
+            .env("npm_config_offline", "true")
+            .env("PIP_NO_INDEX", "1")
+            .env("UV_OFFLINE", "1");
+    }
+    for (name, value) in &server.env {
+        let resolved = value.resolve().ok_or_else(|| Error::MissingEnvVar {
+            name: match value {
+                EnvValue::EnvRef { env, .. } => env.clone(),
+                EnvValue::Plain(_) => name.clone(),
+            },
+        })?;
+        command.env(name, resolved);
+    }
+
+    if let Some(cwd) = working_dir(server.cwd.as_deref(), options)? {
+        command.current_dir(cwd);
+    }
+    Ok(command)
+}
+
+/// Resolves the working directory and checks it against the sandbox root.
+fn working_dir(cwd: Option<&Path>, options: &LaunchOptions) -> Result<Option<PathBuf>> {
+    let root = options.sandbox.restrict_cwd.as_deref();
+    let Some(cwd) = cwd else {
+        return Ok(root.map(Path::to_path_buf));
+    };
+    let cwd = crate::paths::expand_tilde(cwd);
+    let cwd = match (&options.project_root, root) {
+        (_, _) if cwd.is_absolute() => cwd,
+        (Some(project), _) => project.join(cwd),
+        (None, Some(root)) => root.join(cwd),
+        (None, None) => cwd,
+    };
+    if let Some(root) = root {
+        let inside = crate::paths::normalize_for_compare(&cwd)
+            .starts_with(crate::paths::normalize_for_compare(root));
+        if !inside {
+            return Err(Error::InvalidPath(cwd));
+        }
+    }
+    Ok(Some(cwd))
+}
+
+impl McpProcess {
+    /// Returns the operating system process id.
+    #[must_use]
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Writes `message` as one line of JSON to the server's stdin.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server closed its stdin.
+    pub fn send(&mut self, message: &Value) -> Result<()> {
+        let stdin = self
+            .stdin
+            .as_mut()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::BrokenPipe, "stdin closed"))?;
+        writeln!(stdin, "{message}")?;
+        stdin.flush()?;
+        Ok(())
+    }
+
+    /// Returns the next message from the server's stdout.
+    ///
+    /// Returns `Ok(None)` if the server closed stdout, typically by exiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::ErrorKind::TimedOut`] error if nothing arrives
+    /// within [`LaunchOptions::timeout`], or [`Error::JsonParse`] if a
+    /// line is not JSON.
+    pub fn recv(&mut self) -> Result<Option<Value>> {
+        match self.messages.recv_timeout(self.timeout) {
+            Ok(line) => Ok(Some(serde_json::from_str(&line)?)),
+            Err(RecvTimeoutError::Disconnected) => Ok(None),
+            Err(RecvTimeoutError::Timeout) => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("no message within {:?}", self.timeout),
+            )
+            .into()),
+        }
+    }
+
+    /// Sends a request and returns its result, skipping notifications and
+    /// other messages in between.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::McpProtocol`] if the server answers with a JSON-RPC
+    /// error or exits first, or an error from [`send`](Self::send) or
+    /// [`recv`](Self::recv).
+    pub fn request(&mut self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(&json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))?;
+        let deadline = Instant::now() + self.timeout;
+        while Instant::now() < deadline {
+            let Some(message) = self.recv()? else {
+                return Err(Error::McpProtocol {
+                    method: method.to_string(),
+                    code: None,
+                    message: "server exited before answering".to_string(),
+                });
+            };
+            if message.get("id") != Some(&json!(id)) {
+                continue;
+            }
+            if let Some(error) = message.get("error") {
+                return Err(Error::McpProtocol {
+                    method: method.to_string(),
+                    code: error.get("code").and_then(Value::as_i64),
+                    message: match error.get("message").and_then(Value::as_str) {
+                        Some(message) => message.to_string(),
+                        None => error.to_string(),
+                    },
+                });
+            }
+            return Ok(message.get("result").cloned().unwrap_or(Value::Null));
+        }
+        Err(io::Error::new(io::ErrorKind::TimedOut, format!("no answer to {method}")).into())
+    }
+
+    /// Performs the MCP initialization handshake and returns the server's
+    /// `initialize` result, with its capabilities and `serverInfo`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server does not answer in time or rejects
+    /// the request; see [`request`](Self::request).
+    pub fn handshake(&mut self) -> Result<Value> {
+        let result = self.request(
+            "initialize",
+            json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": {"name": "harness-locate", "version": env!("CARGO_PKG_VERSION")}
+            }),
+        )?;
+        self.send(&json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))?;
+        Ok(result)
+    }
+
+    /// Returns `true` if the server has not exited.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process status cannot be read.
+    pub fn is_running(&mut self) -> Result<bool> {
+        Ok(self.child.try_wait()?.is_none())
+    }
+
+    /// Kills the server. Killing an exited server is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process cannot be signalled.
+    pub fn kill(&mut self) -> Result<()> {
+        match self.child.kill() {
+            Err(e) if e.kind() != io::ErrorKind::InvalidInput => Err(e.into()),
+            _ => {
+                self.child.wait()?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Closes stdin, which asks a stdio server to exit, and waits up to
+    /// [`LaunchOptions::timeout`] for it before killing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process status cannot be read.
+    pub fn wait(mut self) -> Result<LaunchExit> {
+        self.stdin = None;
+        let deadline = Instant::now() + self.timeout;
+        let status = loop {
+            if let Some(status) = self.child.try_wait()? {
+                break Some(status);
+            }
+            if Instant::now() >= deadline {
+                self.kill()?;
+                break None;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        };
+        if let Some(reader) = self.stderr_reader.take() {
+            let grace = Instant::now() + STDERR_GRACE;
+            while !reader.is_finished() && Instant::now() < grace {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+        let stderr = self
+            .stderr
+            .lock()
+            .map(|buf| String::from_utf8_lossy(&buf).into_owned())
+            .unwrap_or_default();
+        Ok(LaunchExit {
+            code: status.and_then(|status| status.code()),
+            timed_out: status.is_none(),
+            stderr,
+        })
+    }
+}
+
+impl Drop for McpProcess {
+    fn drop(&mut self) {
+        if matches!(self.child.try_wait(), Ok(None)) {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn stdio(command: &str, args: &[&str]) -> StdioMcpServer {
+        StdioMcpServer {
+            command: command.to_string(),
+            args: args.iter().map(ToString::to_string).collect(),
+            env: Default::default(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        }
+    }
+
+    fn shell(script: &str) -> McpServer {
+        McpServer::Stdio(stdio("sh", &["-c", script]))
+    }
+
+    #[test]
+    fn handshakes_with_a_stdio_server() {
+        let server = shell(
+            r#"read line; echo '{"jsonrpc":"2.0","method":"notifications/message"}'
+echo '{"jsonrpc":"2.0","id":0,"result":{"serverInfo":{"name":"echo"}}}'
+read line; echo "$line" >&2"#,
+        );
+        let mut process = launch(&server, &LaunchOptions::default()).unwrap();
+        let result = process.handshake().unwrap();
+        assert_eq!(result["serverInfo"]["name"], "echo");
+
+        let exit = process.wait().unwrap();
+        assert_eq!(exit.code, Some(0));
+        assert!(exit.stderr.contains("notifications/initialized"));
+    }
+
+    #[test]
+    fn reports_protocol_errors_and_early_exits() {
+        let server = shell(
+            r#"read line; echo '{"jsonrpc":"2.0","id":0,"error":{"code":-32601,"message":"no such method"}}'"#,
+        );
+        let mut process = launch(&server, &LaunchOptions::default()).unwrap();
+        let err = process.request("tools/list", json!({})).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::McpProtocol { method, code: Some(-32601), message }
+                if method == "tools/list" && message == "no such method"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "MCP request tools/list failed: no such method"
+        );
+
+        let mut process = launch(&shell("read line"), &LaunchOptions::default()).unwrap();
+        let err = process.handshake().unwrap_err();
+        assert!(matches!(
+            err,
+            Error::McpProtocol { method, code: None, .. } if method == "initialize"
+        ));
+    }
+
+    #[test]
+    fn times_out_and_kills_silent_servers() {
+        let options = LaunchOptions::default().with_timeout(Duration::from_millis(200));
+        let mut process = launch(&shell("sleep 30"), &options).unwrap();
+        let err = process.recv().unwrap_err();
+        assert!(matches!(err, Error::Io(e) if e.kind() == io::ErrorKind::TimedOut));
+
+        let exit = launch(&shell("trap '' TERM; sleep 30"), &options)
+            .unwrap()
+            .wait()
+            .unwrap();
+        assert!(exit.timed_out);
+    }
+
+    #[test]
+    fn sandbox_restricts_env_and_cwd() {
+        let root = std::env::temp_dir();
+        let sandbox = SandboxOptions::default()
+            .with_clear_env(true)
+            .with_no_network(true)
+            .with_restricted_cwd(&root);
+        let options = LaunchOptions::default().with_sandbox(sandbox);
+
+        let exit = launch(
+            &shell("echo \"$HTTPS_PROXY|$SECRET_FROM_PARENT|$(pwd)\" >&2"),
+            &options,
+        )
+        .unwrap()
+        .wait()
+        .unwrap();
+        let expected_cwd = std::fs::canonicalize(&root).unwrap();
+        assert_eq!(
+            exit.stderr.trim(),
+            format!("{BLACKHOLE_PROXY}||{}", expected_cwd.display())
+        );
+
+        let mut outside = stdio("true", &[]);
+        outside.cwd = Some(PathBuf::from("/"));
+        let err = launch(&McpServer::Stdio(outside), &options).unwrap_err();
+        assert!(matches!(err, Error::InvalidPath(_)));
+    }
+
+    #[test]
+    fn reports_unset_env_references() {
+        let mut server = stdio("true", &[]);
+        server.env.insert(
+            "TOKEN".to_string(),
+            EnvValue::env("HARNESS_LOCATE_LAUNCH_UNSET"),
+        );
+        let err = launch(&McpServer::Stdio(server), &LaunchOptions::default()).unwrap_err();
+        assert!(
+            matches!(err, Error::MissingEnvVar { name } if name == "HARNESS_LOCATE_LAUNCH_UNSET")
+        );
+    }
+}
@@ -2,41 +2,165 @@
 //!
 //! ## Modules
 //!
+//! - [`argv`] - Splitting, joining and normalizing stdio command lines
+//! - [`cleanup`] - Stale sessions, plugin clones and caches to delete
+//! - [`command`] - Command file parsing utilities (`validation` feature)
 //! - [`detection`] - Binary detection utilities
+//! - [`document`] - Editable MCP config documents
+//! - [`environment`] - Execution environment detection
+//! - [`envreport`] - Environment variables referenced across a scope (`validation` feature)
 //! - [`error`] - Error types
+//! - [`fs`] - Filesystem abstraction
+//! - [`gitignore`] - Which agent files to commit and which to ignore
+//! - [`graph`] - Resource dependency graphs (`validation` feature)
 //! - [`harness`] - Harness discovery and path resolution
+//! - [`ignore`] - Ignore rules for resource directories
+//! - [`init`] - Scaffolding project config for new repositories
+//! - [`interpolate`] - Expanding and contracting paths in server configs
+//! - [`journal`] - Journal of configuration changes
+//! - [`launch`] - Spawning stdio MCP servers (`launch` feature)
+//! - [`locator`] - Cached harness lookups
 //! - [`mcp`] - MCP server type definitions
+//! - [`metrics`] - Opt-in anonymous counters for embedding applications
+//! - [`migration`] - MCP servers left in legacy config locations
+//! - [`meta`] - Typed skill, agent, and command frontmatter (`yaml` feature)
+//! - [`naming`] - Slugs and display names for resources
+//! - [`observer`] - Events streamed from lookups as they happen
+//! - [`paths`] - Expanding, comparing and displaying paths
+//! - [`plugin`] - Claude Code plugin manifests and installed plugins
+//! - [`portable`] - Home-relative paths for dotfiles repositories
+//! - [`pure`] - Config translation without filesystem or environment access
+//! - [`rename`] - Renaming skills across harnesses (`yaml` feature)
+//! - [`render`] - Human-readable rendering for command-line tools
+//! - [`serveraudit`] - Configured MCP servers that cannot start
+//! - [`target`] - Operating systems and architectures resources run on
+//! - [`templates`] - Curated MCP server templates
+//! - [`text`] - Tolerant text decoding
 //! - [`types`] - Core type definitions
-//! - [`skill`] - Skill file parsing utilities
+//! - [`skill`] - Skill file parsing utilities (`yaml` feature)
+//! - [`skilltest`] - Running checks declared in skill frontmatter (`yaml` feature)
+//! - [`strategy`] - Proptest strategies for MCP servers (`test-util` feature)
+//! - [`usage`] - Disk space taken up by each kind of harness data
 //! - [`validation`] - MCP server validation utilities
+//! - [`workspace`] - Multi-project workspaces
+//! - [`yamllint`] - Diagnostics for hand-edited YAML configs (`yaml` feature)
 
+pub mod argv;
+pub mod cleanup;
+#[cfg(feature = "validation")]
+pub mod command;
 pub mod detection;
+pub mod document;
+pub mod environment;
+#[cfg(feature = "validation")]
+pub mod envreport;
 pub mod error;
+pub mod fs;
+pub mod gitignore;
+mod glob;
+#[cfg(feature = "validation")]
+pub mod graph;
 pub mod harness;
+pub mod ignore;
+pub mod init;
+pub mod interpolate;
+pub mod journal;
+mod jsonc;
+#[cfg(feature = "launch")]
+pub mod launch;
+pub mod locator;
 pub mod mcp;
+#[cfg(feature = "yaml")]
+pub mod meta;
+pub mod metrics;
+pub mod migration;
+pub mod naming;
+pub mod observer;
+pub mod paths;
 pub mod platform;
+pub mod plugin;
+pub mod portable;
+pub mod pure;
+#[cfg(feature = "yaml")]
+pub mod rename;
+pub mod render;
+pub mod serveraudit;
+#[cfg(feature = "yaml")]
 pub mod skill;
+#[cfg(feature = "yaml")]
+pub mod skilltest;
+#[cfg(any(test, feature = "test-util"))]
+pub mod strategy;
+pub mod target;
+pub mod templates;
+pub mod text;
 pub mod types;
+pub mod usage;
 pub mod validation;
+pub mod workspace;
+#[cfg(feature = "yaml")]
+pub mod yamllint;
 
-pub use detection::find_binary;
+#[cfg(feature = "validation")]
+pub use command::{CommandSignature, parse_command_signature};
+pub use detection::{find_binaries, find_binary, install_source};
+pub use document::McpConfigDocument;
+pub use environment::Environment;
+#[cfg(feature = "validation")]
+pub use envreport::{EnvReference, EnvReport, EnvVarStatus, EnvVarUsage, ReferenceKind};
 pub use error::{Error, Result};
-pub use harness::Harness;
+#[cfg(feature = "registry")]
+pub use harness::HarnessDefinition;
+pub use harness::{Harness, HarnessBackend, HarnessRegistry};
+pub use journal::{JournalEntry, read_journal};
+pub use locator::Locator;
 pub use mcp::{
-    HttpMcpServer, McpCapabilities, McpServer, OAuthConfig, SseMcpServer, StdioMcpServer,
+    AuthPreset, DockerMcpServer, EffectiveConfig, EffectiveSetting, EnabledSemantics,
+    GooseExtension, GooseExtensionKind, HttpMcpServer, McpCapabilities, McpParseReport, McpServer,
+    NativeOptions, OAuthConfig, SettingSource, SseMcpServer, StdioMcpServer,
 };
-pub use skill::{Frontmatter, Skill, parse_frontmatter, parse_skill};
+#[cfg(feature = "yaml")]
+pub use meta::{AgentMeta, ArgumentHint, CommandMeta, SkillMeta, ToolList};
+pub use naming::{display_name, safe_name, slugify, unique_name};
+pub use observer::{LocateEvent, LocateObserver, ResolvedKind};
+pub use platform::PathContext;
+#[cfg(feature = "yaml")]
+pub use rename::{RenamePlan, RenameStep, rename_skill};
+pub use render::Style;
+pub use serveraudit::{
+    PackageEcosystem, PackageRef, ServerAuditIssue, ServerProblem, audit_mcp_servers,
+    audit_mcp_servers_with,
+};
+#[cfg(feature = "yaml")]
+pub use skill::{
+    Frontmatter, FrontmatterSyntax, LocalizedText, Skill, parse_frontmatter, parse_skill,
+};
+#[cfg(feature = "yaml")]
+pub use skilltest::{SkillTest, SkillTestReport, TestOutcome, run_skill_tests};
 pub use types::{
     ConfigResource, DirectoryResource, DirectoryStructure, EnvValue, FileFormat, HarnessKind,
-    InstallationStatus, PathType, ResourceKind, Scope,
+    InstallSource, InstallationStatus, PathType, ResourceKind, ResourceLookup, ResourceMatch,
+    Scope, TokenStore,
 };
 pub use validation::{
     AgentCapabilities, CODE_AGENT_COLOR_FORMAT, CODE_AGENT_MODE_UNSUPPORTED,
     CODE_AGENT_PARSE_ERROR, CODE_AGENT_TOOLS_FORMAT, CODE_AGENT_UNSUPPORTED,
+    CODE_COMMAND_HINT_MISMATCH, CODE_COMMAND_HINT_MISSING, CODE_COMMAND_HINT_UNSUPPORTED,
+    CODE_COMMAND_PARSE_ERROR, CODE_COMMAND_PLACEHOLDER_UNSUPPORTED, CODE_COMMAND_POSITIONAL_GAP,
+    CODE_COMMAND_UNSUPPORTED, CODE_ENV_GLOBAL_EPHEMERAL, CODE_ENV_NOT_INSTALLED_CI,
     CODE_SKILL_DESCRIPTION_LENGTH, CODE_SKILL_DESCRIPTION_MISSING,
     CODE_SKILL_NAME_DIRECTORY_MISMATCH, CODE_SKILL_NAME_FORMAT, CODE_SKILL_NAME_LENGTH,
-    CODE_SKILL_PARSE_ERROR, CODE_SKILL_UNSUPPORTED, ColorFormat, NameFormat,
-    SKILL_DESCRIPTION_MAX_LEN, SKILL_NAME_MAX_LEN, SKILL_NAME_REGEX, Severity, SkillCapabilities,
-    ToolsFormat, ValidationIssue, validate_agent_for_harness, validate_mcp_server,
+    CODE_SKILL_PARSE_ERROR, CODE_SKILL_UNSUPPORTED, ColorFormat, CommandCapabilities,
+    CompatibilityLevel, CompatibilityScore, NameFormat, SKILL_DESCRIPTION_MAX_LEN,
+    SKILL_NAME_MAX_LEN, SKILL_NAME_REGEX, Severity, SkillCapabilities, ToolsFormat,
+    ValidationIssue, ValidationIssueRef,
+};
+#[cfg(feature = "validation")]
+pub use validation::{
+    score_skill_compatibility, validate_agent_for_harness, validate_command_for_harness,
+    validate_docker_installed, validate_mcp_server, validate_skill_borrowed,
     validate_skill_for_harness,
 };
+pub use workspace::{ProjectRoot, Workspace, find_project_scopes};
+#[cfg(feature = "yaml")]
+pub use yamllint::{YamlDiagnostic, YamlProblem, lint_yaml};
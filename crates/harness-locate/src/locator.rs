@@ -0,0 +1,348 @@
+//! Cached harness lookups.
+//!
+//! [`Harness`] methods hit the filesystem on every call. A [`Locator`]
+//! remembers their results for a fixed time-to-live, so callers that poll
+//! (such as UIs refreshing several times a second) only pay for IO once per
+//! interval. Call [`Locator::invalidate`] after changing files on disk to
+//! see the new state immediately.
+//!
+//! Cached results are not tied to the active
+//! [`PathContext`](crate::platform::PathContext); use a separate locator per
+//! context.
+//!
+//! # Example
+//!
+//! ```
+//! use std::time::Duration;
+//! use harness_locate::{HarnessKind, Locator};
+//!
+//! let locator = Locator::new(Duration::from_secs(5));
+//! let first = locator.is_installed(HarnessKind::ClaudeCode);
+//! // Served from the cache until the TTL expires.
+//! assert_eq!(locator.is_installed(HarnessKind::ClaudeCode), first);
+//!
+//! locator.invalidate();
+//! ```
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::hash::Hash;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use crate::error::Result;
+use crate::harness::Harness;
+use crate::types::{DirectoryResource, HarnessKind, InstallationStatus, ResourceKind, Scope};
+
+/// Time-to-live used by [`Locator::default`].
+pub const DEFAULT_TTL: Duration = Duration::from_secs(2);
+
+/// Directory lookup key: `None` as the resource stands for rules.
+type DirectoryKey = (HarnessKind, Option<ResourceKind>, Scope);
+
+/// A caching facade over [`Harness`] lookups.
+///
+/// Each query is answered from the cache if an entry younger than the TTL
+/// exists, and from the filesystem otherwise. Errors are never cached.
+///
+/// A locator is `Send + Sync` and can be shared between threads.
+#[derive(Debug)]
+pub struct Locator {
+    ttl: Duration,
+    installed: Cache<HarnessKind, bool>,
+    status: Cache<HarnessKind, InstallationStatus>,
+    all_installed: Cache<(), Vec<Harness>>,
+    exists: Cache<PathBuf, bool>,
+    directories: Cache<DirectoryKey, Option<DirectoryResource>>,
+}
+
+impl Default for Locator {
+    fn default() -> Self {
+        Self::new(DEFAULT_TTL)
+    }
+}
+
+impl Locator {
+    /// Creates a locator whose entries expire after `ttl`.
+    ///
+    /// A zero TTL disables caching.
+    #[must_use]
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            installed: Cache::default(),
+            status: Cache::default(),
+            all_installed: Cache::default(),
+            exists: Cache::default(),
+            directories: Cache::default(),
+        }
+    }
+
+    /// Returns the time-to-live of cached entries.
+    #[must_use]
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Cached [`Harness::is_installed`].
+    #[must_use]
+    pub fn is_installed(&self, kind: HarnessKind) -> bool {
+        self.installed
+            .get_or_insert(kind, self.ttl, || Harness::new(kind).is_installed())
+    }
+
+    /// Cached [`Harness::installation_status`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`Harness::installation_status`]; failed
+    /// lookups are retried on the next call.
+    pub fn installation_status(&self, kind: HarnessKind) -> Result<InstallationStatus> {
+        self.status
+            .get_or_try_insert(kind, self.ttl, || Harness::new(kind).installation_status())
+    }
+
+    /// Cached [`Harness::installed`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`Harness::installed`].
+    pub fn installed(&self) -> Result<Vec<Harness>> {
+        self.all_installed
+            .get_or_try_insert((), self.ttl, Harness::installed)
+    }
+
+    /// Checks whether `path` exists, through the cache.
+    #[must_use]
+    pub fn exists(&self, path: &Path) -> bool {
+        self.exists.get_or_insert(path.to_path_buf(), self.ttl, || {
+            crate::fs::current().exists(path)
+        })
+    }
+
+    /// Cached [`Harness::resource`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from the underlying [`Harness`] method.
+    pub fn resource(
+        &self,
+        kind: HarnessKind,
+        resource: ResourceKind,
+        scope: &Scope,
+    ) -> Result<Option<DirectoryResource>> {
+        let key = (kind, Some(resource), scope.clone());
+        self.directories.get_or_try_insert(key, self.ttl, || {
+            Harness::new(kind).resource(resource, scope)
+        })
+    }
+
+    /// Cached [`Harness::rules`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from [`Harness::rules`].
+    pub fn rules(&self, kind: HarnessKind, scope: &Scope) -> Result<Option<DirectoryResource>> {
+        let key = (kind, None, scope.clone());
+        self.directories
+            .get_or_try_insert(key, self.ttl, || Harness::new(kind).rules(scope))
+    }
+
+    /// Drops every cached entry.
+    pub fn invalidate(&self) {
+        self.installed.clear();
+        self.status.clear();
+        self.all_installed.clear();
+        self.exists.clear();
+        self.directories.clear();
+    }
+
+    /// Drops cached entries for a single harness.
+    ///
+    /// The result of [`Locator::installed`] is dropped as well, since it
+    /// depends on every harness. Entries cached by [`Locator::exists`] are
+    /// kept; use [`Locator::invalidate`] to clear them.
+    pub fn invalidate_kind(&self, kind: HarnessKind) {
+        self.installed.retain(|k| *k != kind);
+        self.status.retain(|k| *k != kind);
+        self.all_installed.clear();
+        self.directories.retain(|(k, _, _)| *k != kind);
+    }
+}
+
+#[derive(Debug)]
+struct Cache<K, V> {
+    entries: Mutex<HashMap<K, (Instant, V)>>,
+}
+
+impl<K, V> Default for Cache<K, V> {
+    fn default() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K: Eq + Hash, V: Clone> Cache<K, V> {
+    fn lock(&self) -> MutexGuard<'_, HashMap<K, (Instant, V)>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Returns the fresh entry for `key`, or computes and stores a new one.
+    ///
+    /// The lock is not held while `load` runs, so concurrent misses may
+    /// compute the same value twice.
+    fn get_or_try_insert<E>(
+        &self,
+        key: K,
+        ttl: Duration,
+        load: impl FnOnce() -> std::result::Result<V, E>,
+    ) -> std::result::Result<V, E> {
+        if let Some((at, value)) = self.lock().get(&key)
+            && at.elapsed() < ttl
+        {
+            return Ok(value.clone());
+        }
+        let value = load()?;
+        if !ttl.is_zero() {
+            self.lock().insert(key, (Instant::now(), value.clone()));
+        }
+        Ok(value)
+    }
+
+    fn get_or_insert(&self, key: K, ttl: Duration, load: impl FnOnce() -> V) -> V {
+        match self.get_or_try_insert(key, ttl, || Ok::<_, Infallible>(load())) {
+            Ok(value) => value,
+        }
+    }
+
+    fn retain(&self, mut keep: impl FnMut(&K) -> bool) {
+        self.lock().retain(|k, _| keep(k));
+    }
+
+    fn clear(&self) {
+        self.lock().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::{AccessLog, AuditFs, MemoryFs};
+    use crate::platform::PathContext;
+    use std::sync::Arc;
+
+    fn audited(fs: MemoryFs) -> (PathContext, Arc<AccessLog>) {
+        let log = Arc::new(AccessLog::new());
+        let ctx = PathContext::fake(std::env::temp_dir().join("harness-locate-locator"))
+            .with_fs(AuditFs::new(fs, Arc::clone(&log)));
+        (ctx, log)
+    }
+
+    #[test]
+    fn repeated_queries_are_served_from_cache() {
+        let (ctx, log) = audited(MemoryFs::new());
+        let locator = Locator::new(Duration::from_secs(3600));
+
+        ctx.scope(|| {
+            let first = locator.is_installed(HarnessKind::ClaudeCode);
+            let accesses = log.records().len();
+            assert!(accesses > 0);
+
+            assert_eq!(locator.is_installed(HarnessKind::ClaudeCode), first);
+            assert_eq!(log.records().len(), accesses);
+        });
+    }
+
+    #[test]
+    fn zero_ttl_disables_caching() {
+        let (ctx, log) = audited(MemoryFs::new());
+        let locator = Locator::new(Duration::ZERO);
+
+        ctx.scope(|| {
+            let path = Path::new("/nowhere");
+            assert!(!locator.exists(path));
+            let accesses = log.records().len();
+            assert!(!locator.exists(path));
+            assert!(log.records().len() > accesses);
+        });
+    }
+
+    #[test]
+    fn invalidate_forces_a_fresh_lookup() {
+        let (ctx, log) = audited(MemoryFs::new());
+        let locator = Locator::default();
+
+        ctx.scope(|| {
+            let skills = locator
+                .resource(
+                    HarnessKind::ClaudeCode,
+                    ResourceKind::Skills,
+                    &Scope::Global,
+                )
+                .unwrap()
+                .unwrap();
+            let accesses = log.records().len();
+
+            let cached = locator
+                .resource(
+                    HarnessKind::ClaudeCode,
+                    ResourceKind::Skills,
+                    &Scope::Global,
+                )
+                .unwrap()
+                .unwrap();
+            assert_eq!(cached.path, skills.path);
+            assert_eq!(log.records().len(), accesses);
+
+            locator.invalidate();
+            locator
+                .resource(
+                    HarnessKind::ClaudeCode,
+                    ResourceKind::Skills,
+                    &Scope::Global,
+                )
+                .unwrap();
+            assert!(log.records().len() > accesses);
+        });
+    }
+
+    #[test]
+    fn invalidate_kind_keeps_other_harnesses() {
+        let (ctx, log) = audited(MemoryFs::new());
+        let locator = Locator::default();
+
+        ctx.scope(|| {
+            let _ = locator.is_installed(HarnessKind::ClaudeCode);
+            let _ = locator.is_installed(HarnessKind::Goose);
+            locator.invalidate_kind(HarnessKind::ClaudeCode);
+
+            log.clear();
+            let _ = locator.is_installed(HarnessKind::Goose);
+            assert!(log.records().is_empty());
+            let _ = locator.is_installed(HarnessKind::ClaudeCode);
+            assert!(!log.records().is_empty());
+        });
+    }
+
+    #[test]
+    fn scopes_are_cached_separately() {
+        let (ctx, _log) = audited(MemoryFs::new());
+        let locator = Locator::default();
+
+        ctx.scope(|| {
+            let global = locator
+                .rules(HarnessKind::ClaudeCode, &Scope::Global)
+                .unwrap()
+                .map(|r| r.path);
+            let project = locator
+                .rules(HarnessKind::ClaudeCode, &Scope::Project("/work".into()))
+                .unwrap()
+                .map(|r| r.path);
+            assert_ne!(global, project);
+        });
+    }
+}
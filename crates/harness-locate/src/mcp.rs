@@ -3,19 +3,32 @@
 //! This module defines normalized types for MCP server configurations
 //! that work across all harnesses (Claude Code, OpenCode, Goose).
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
 use crate::Error;
 use crate::types::{EnvValue, HarnessKind};
+use crate::validation::is_sensitive_name;
+
+/// Placeholder written in place of secret values when redacting.
+pub const REDACTED: &str = "***";
 
 /// Returns `true` for serde default.
 fn default_true() -> bool {
     true
 }
 
+/// Serializes `map` with its keys sorted, so output is stable across runs.
+fn serialize_sorted<S, V>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+    V: Serialize,
+{
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
 /// A normalized MCP server configuration.
 ///
 /// MCP servers can use different transport mechanisms:
@@ -28,6 +41,7 @@ fn default_true() -> bool {
 /// { "transport": "stdio", "command": "node", "args": ["server.js"] }
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "transport", rename_all = "snake_case")]
 #[derive(PartialEq, Eq)]
 pub enum McpServer {
@@ -37,6 +51,9 @@ pub enum McpServer {
     Sse(SseMcpServer),
     /// HTTP/Streamable HTTP MCP server.
     Http(HttpMcpServer),
+    /// Goose extension that runs inside Goose rather than as a standalone
+    /// server. Only Goose can load these.
+    GooseNative(GooseExtension),
 }
 
 impl McpServer {
@@ -45,30 +62,31 @@ impl McpServer {
         match self {
             Self::Stdio(s) => {
                 for value in s.env.values() {
-                    if let EnvValue::EnvRef { env } = value {
+                    if let EnvValue::EnvRef { env, .. } = value {
                         names.push(env.as_str());
                     }
                 }
             }
             Self::Sse(s) => {
                 for value in s.headers.values() {
-                    if let EnvValue::EnvRef { env } = value {
+                    if let EnvValue::EnvRef { env, .. } = value {
                         names.push(env.as_str());
                     }
                 }
             }
             Self::Http(h) => {
                 for value in h.headers.values() {
-                    if let EnvValue::EnvRef { env } = value {
+                    if let EnvValue::EnvRef { env, .. } = value {
                         names.push(env.as_str());
                     }
                 }
                 if let Some(oauth) = &h.oauth
-                    && let Some(EnvValue::EnvRef { env }) = &oauth.client_secret
+                    && let Some(EnvValue::EnvRef { env, .. }) = &oauth.client_secret
                 {
                     names.push(env.as_str());
                 }
             }
+            Self::GooseNative(_) => {}
         }
         names
     }
@@ -80,28 +98,276 @@ impl McpServer {
             .collect()
     }
 
+    /// Returns the env vars and headers that hold a literal secret rather
+    /// than an env reference, sorted by name.
+    ///
+    /// These are the values [`redacted`](Self::redacted) masks. A plain
+    /// OAuth client secret is reported as `client_secret`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::mcp::{McpServer, StdioMcpServer};
+    /// use harness_locate::types::EnvValue;
+    ///
+    /// let server = McpServer::Stdio(StdioMcpServer {
+    ///     command: "gh-mcp".into(),
+    ///     args: vec![],
+    ///     env: [
+    ///         ("GITHUB_TOKEN".to_string(), EnvValue::plain("ghp_123")),
+    ///         ("API_KEY".to_string(), EnvValue::env("API_KEY")),
+    ///         ("LOG_LEVEL".to_string(), EnvValue::plain("debug")),
+    ///     ]
+    ///     .into(),
+    ///     cwd: None,
+    ///     enabled: true,
+    ///     timeout_ms: None,
+    /// });
+    /// assert_eq!(server.plain_secret_names(), ["GITHUB_TOKEN"]);
+    /// ```
+    pub fn plain_secret_names(&self) -> Vec<&str> {
+        let mut names = match self {
+            Self::Stdio(s) => plain_secrets(&s.env),
+            Self::Sse(s) => plain_secrets(&s.headers),
+            Self::Http(h) => {
+                let mut names = plain_secrets(&h.headers);
+                if let Some(oauth) = &h.oauth
+                    && let Some(EnvValue::Plain(s)) = &oauth.client_secret
+                    && !s.is_empty()
+                {
+                    names.push("client_secret");
+                }
+                names
+            }
+            Self::GooseNative(_) => Vec::new(),
+        };
+        names.sort_unstable();
+        names
+    }
+
+    /// Returns whether the server is enabled.
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Self::Stdio(s) => s.enabled,
+            Self::Sse(s) => s.enabled,
+            Self::Http(h) => h.enabled,
+            Self::GooseNative(g) => g.enabled,
+        }
+    }
+
+    /// Enables or disables the server.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        match self {
+            Self::Stdio(s) => s.enabled = enabled,
+            Self::Sse(s) => s.enabled = enabled,
+            Self::Http(h) => h.enabled = enabled,
+            Self::GooseNative(g) => g.enabled = enabled,
+        }
+    }
+
+    /// Returns the fields that converting to `kind` would silently discard.
+    ///
+    /// Fields are named as on the server types (`cwd`, `timeout_ms`,
+    /// `enabled`, `headers`, `oauth`). A timeout counts as dropped for Goose
+    /// when it is not a whole number of seconds, and `enabled` only when the
+    /// server is disabled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::mcp::{McpServer, StdioMcpServer};
+    /// use harness_locate::types::HarnessKind;
+    ///
+    /// let server = McpServer::Stdio(StdioMcpServer {
+    ///     command: "node".to_string(),
+    ///     args: vec![],
+    ///     env: Default::default(),
+    ///     cwd: Some("/srv/app".into()),
+    ///     enabled: true,
+    ///     timeout_ms: Some(5000),
+    /// });
+    ///
+    /// assert_eq!(server.dropped_fields(HarnessKind::ClaudeCode), ["cwd", "timeout_ms"]);
+    /// assert_eq!(server.dropped_fields(HarnessKind::OpenCode), ["cwd"]);
+    /// ```
+    #[must_use]
+    pub fn dropped_fields(&self, kind: HarnessKind) -> Vec<&'static str> {
+        let mut dropped = Vec::new();
+        let timeout_ms = match self {
+            Self::Stdio(s) => s.timeout_ms,
+            Self::Sse(s) => s.timeout_ms,
+            Self::Http(h) => h.timeout_ms,
+            // Goose writes every field of its own extensions.
+            Self::GooseNative(_) => return dropped,
+        };
+
+        if let Self::Stdio(s) = self
+            && s.cwd.is_some()
+        {
+            dropped.push("cwd");
+        }
+        if let Self::Sse(SseMcpServer { headers, .. }) | Self::Http(HttpMcpServer { headers, .. }) =
+            self
+            && !headers.is_empty()
+            && kind == HarnessKind::Goose
+        {
+            dropped.push("headers");
+        }
+        if let Some(timeout_ms) = timeout_ms {
+            let kept = match kind {
+                HarnessKind::OpenCode | HarnessKind::CopilotCli => true,
+                HarnessKind::Goose => timeout_ms % 1000 == 0,
+                HarnessKind::ClaudeCode | HarnessKind::AmpCode => false,
+            };
+            if !kept {
+                dropped.push("timeout_ms");
+            }
+        }
+        if !self.is_enabled()
+            && !McpCapabilities::for_kind(kind)
+                .enabled_semantics
+                .honors_flag()
+        {
+            dropped.push("enabled");
+        }
+        if let Self::Http(h) = self
+            && h.oauth.is_some()
+            && kind != HarnessKind::OpenCode
+        {
+            dropped.push("oauth");
+        }
+        dropped
+    }
+
+    /// Returns the settings `kind` will actually use for this server, with
+    /// harness defaults filled in.
+    ///
+    /// Unset values fall back to the harness default
+    /// ([`SettingSource::HarnessDefault`]), and values the harness cannot
+    /// represent (see [`dropped_fields`](Self::dropped_fields)) are replaced
+    /// by it ([`SettingSource::Ignored`]). Harnesses without a per-server
+    /// toggle always run their configured servers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::mcp::{McpServer, SettingSource, StdioMcpServer};
+    /// use harness_locate::types::HarnessKind;
+    ///
+    /// let server = McpServer::Stdio(StdioMcpServer {
+    ///     command: "node".to_string(),
+    ///     args: vec![],
+    ///     env: Default::default(),
+    ///     cwd: None,
+    ///     enabled: true,
+    ///     timeout_ms: None,
+    /// });
+    ///
+    /// let config = server.effective_config(HarnessKind::OpenCode);
+    /// assert_eq!(config.timeout_ms.value, Some(5_000));
+    /// assert_eq!(config.timeout_ms.source, SettingSource::HarnessDefault);
+    /// ```
+    #[must_use]
+    pub fn effective_config(&self, kind: HarnessKind) -> EffectiveConfig {
+        let dropped = self.dropped_fields(kind);
+        let default_timeout = McpCapabilities::default_timeout(kind);
+        let timeout_ms = match self.timeout_ms() {
+            None => EffectiveSetting::new(default_timeout, SettingSource::HarnessDefault),
+            Some(_) if dropped.contains(&"timeout_ms") => {
+                EffectiveSetting::new(default_timeout, SettingSource::Ignored)
+            }
+            configured => EffectiveSetting::new(configured, SettingSource::Configured),
+        };
+        let enabled = if dropped.contains(&"enabled") {
+            EffectiveSetting::new(true, SettingSource::Ignored)
+        } else {
+            EffectiveSetting::new(self.is_enabled(), SettingSource::Configured)
+        };
+        EffectiveConfig {
+            timeout_ms,
+            enabled,
+        }
+    }
+
+    /// Returns whether `kind` will start this server once written to its
+    /// config.
+    ///
+    /// A disabled server stays disabled only where the harness honors a
+    /// flag in the server entry (see [`EnabledSemantics`]); elsewhere the
+    /// flag is dropped and the server runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::mcp::{McpServer, StdioMcpServer};
+    /// use harness_locate::types::HarnessKind;
+    ///
+    /// let mut server = McpServer::Stdio(StdioMcpServer {
+    ///     command: "node".to_string(),
+    ///     args: vec![],
+    ///     env: Default::default(),
+    ///     cwd: None,
+    ///     enabled: true,
+    ///     timeout_ms: None,
+    /// });
+    /// server.set_enabled(false);
+    ///
+    /// assert!(!server.effective_enabled(HarnessKind::OpenCode));
+    /// assert!(server.effective_enabled(HarnessKind::ClaudeCode));
+    /// ```
+    #[must_use]
+    pub fn effective_enabled(&self, kind: HarnessKind) -> bool {
+        self.effective_config(kind).enabled.value
+    }
+
+    fn timeout_ms(&self) -> Option<u64> {
+        match self {
+            Self::Stdio(s) => s.timeout_ms,
+            Self::Sse(s) => s.timeout_ms,
+            Self::Http(h) => h.timeout_ms,
+            Self::GooseNative(g) => g.timeout_ms,
+        }
+    }
+
     pub fn validate_capabilities(&self, kind: HarnessKind) -> Result<(), Error> {
-        let caps = McpCapabilities::for_kind(kind);
+        self.check_capabilities(&McpCapabilities::for_kind(kind), &format!("{kind:?}"))
+    }
 
+    /// Like [`validate_capabilities`](Self::validate_capabilities), against
+    /// `caps` of the harness named `harness`.
+    pub(crate) fn check_capabilities(
+        &self,
+        caps: &McpCapabilities,
+        harness: &str,
+    ) -> Result<(), Error> {
         match self {
             Self::Sse(_) if !caps.sse => {
                 return Err(Error::UnsupportedMcpConfig {
-                    harness: format!("{kind:?}"),
+                    harness: harness.to_string(),
                     reason: "SSE transport not supported".into(),
                 });
             }
             Self::Http(h) if !caps.http => {
                 return Err(Error::UnsupportedMcpConfig {
-                    harness: format!("{kind:?}"),
+                    harness: harness.to_string(),
                     reason: "HTTP transport not supported".into(),
                 });
             }
             Self::Http(h) if h.oauth.is_some() && !caps.oauth => {
                 return Err(Error::UnsupportedMcpConfig {
-                    harness: format!("{kind:?}"),
+                    harness: harness.to_string(),
                     reason: "OAuth not supported".into(),
                 });
             }
+            Self::GooseNative(g) if !caps.goose_native => {
+                return Err(Error::UnsupportedMcpConfig {
+                    harness: harness.to_string(),
+                    reason: format!("Goose {:?} extension '{}' not supported", g.kind, g.name),
+                });
+            }
+            // Goose writes timeout and enabled for its own extensions.
+            Self::GooseNative(_) => return Ok(()),
             _ => {}
         }
 
@@ -109,10 +375,11 @@ impl McpServer {
             Self::Stdio(s) => s.timeout_ms.is_some(),
             Self::Sse(s) => s.timeout_ms.is_some(),
             Self::Http(h) => h.timeout_ms.is_some(),
+            Self::GooseNative(_) => false,
         };
         if has_timeout && !caps.timeout {
             return Err(Error::UnsupportedMcpConfig {
-                harness: format!("{kind:?}"),
+                harness: harness.to_string(),
                 reason: "timeout not supported".into(),
             });
         }
@@ -121,10 +388,11 @@ impl McpServer {
             Self::Stdio(s) => !s.enabled,
             Self::Sse(s) => !s.enabled,
             Self::Http(h) => !h.enabled,
+            Self::GooseNative(_) => false,
         };
         if has_toggle && !caps.toggle {
             return Err(Error::UnsupportedMcpConfig {
-                harness: format!("{kind:?}"),
+                harness: harness.to_string(),
                 reason: "toggle (enabled field) not supported".into(),
             });
         }
@@ -137,18 +405,160 @@ impl McpServer {
         kind: HarnessKind,
         name: &str,
     ) -> Result<serde_json::Value, Error> {
-        self.validate_capabilities(kind)?;
+        crate::harness::backend::builtin(kind).mcp_to_native(name, self)
+    }
 
-        match kind {
-            HarnessKind::ClaudeCode => self.to_claude_code_value(kind),
-            HarnessKind::CopilotCli => self.to_copilot_cli_value(kind),
-            HarnessKind::OpenCode => self.to_opencode_value(kind),
-            HarnessKind::Goose => self.to_goose_value(kind, name),
-            HarnessKind::AmpCode => self.to_ampcode_value(kind),
+    /// Like [`to_native_value`](Self::to_native_value), with `options`
+    /// controlling how values are written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UnsupportedMcpConfig` if the server uses features the
+    /// harness does not support, `Error::MissingEnvVar` if Goose needs an
+    /// unset variable and redaction is off, `Error::LossyConversion` in
+    /// strict mode if any [`dropped_fields`](Self::dropped_fields) are set,
+    /// or `Error::NotFound` in portable mode if the home directory is
+    /// unknown.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::mcp::{McpServer, NativeOptions, StdioMcpServer};
+    /// use harness_locate::types::{EnvValue, HarnessKind};
+    ///
+    /// let server = McpServer::Stdio(StdioMcpServer {
+    ///     command: "node".to_string(),
+    ///     args: vec!["server.js".to_string()],
+    ///     env: [("API_TOKEN".to_string(), EnvValue::plain("tok_123"))].into(),
+    ///     cwd: None,
+    ///     enabled: true,
+    ///     timeout_ms: None,
+    /// });
+    ///
+    /// let options = NativeOptions::default().with_redact(true);
+    /// let native = server
+    ///     .to_native_value_with(HarnessKind::ClaudeCode, "my-server", options)
+    ///     .unwrap();
+    /// assert_eq!(native["env"]["API_TOKEN"], "***");
+    /// ```
+    pub fn to_native_value_with(
+        &self,
+        kind: HarnessKind,
+        name: &str,
+        options: NativeOptions,
+    ) -> Result<serde_json::Value, Error> {
+        let mut value = if options.redact {
+            self.redacted(kind).to_native_value(kind, name)?
+        } else {
+            self.to_native_value(kind, name)?
+        };
+        if options.portable {
+            crate::portable::make_portable(&mut value, &crate::platform::home_dir()?, kind);
+        }
+        if options.strict {
+            let dropped = self.dropped_fields(kind);
+            if !dropped.is_empty() {
+                return Err(Error::LossyConversion {
+                    harness: kind.to_string(),
+                    fields: dropped.into_iter().map(String::from).collect(),
+                });
+            }
         }
+        Ok(value)
     }
 
-    fn to_claude_code_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+    /// Returns the JSON payload for `claude mcp add-json <name> <json>`.
+    ///
+    /// Letting the official CLI add the server keeps Claude Code's own
+    /// scope handling and approval prompts. The payload is Claude Code's
+    /// native config with the transport always given as `type`, first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::UnsupportedMcpConfig` if `name` is empty or starts
+    /// with `-`, which the CLI would read as a flag, or if Claude Code
+    /// cannot represent the server.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::mcp::{McpServer, StdioMcpServer};
+    ///
+    /// let server = McpServer::Stdio(StdioMcpServer {
+    ///     command: "npx".to_string(),
+    ///     args: vec!["-y".to_string(), "mcp-fs".to_string()],
+    ///     env: Default::default(),
+    ///     cwd: None,
+    ///     enabled: true,
+    ///     timeout_ms: None,
+    /// });
+    /// let json = server.to_claude_cli_json("fs")?;
+    /// assert_eq!(json, r#"{"type":"stdio","command":"npx","args":["-y","mcp-fs"]}"#);
+    /// assert_eq!(McpServer::from_claude_cli_json(&json)?, server);
+    /// # Ok::<(), harness_locate::Error>(())
+    /// ```
+    pub fn to_claude_cli_json(&self, name: &str) -> Result<String, Error> {
+        if name.is_empty() || name.starts_with('-') {
+            return Err(Error::UnsupportedMcpConfig {
+                harness: HarnessKind::ClaudeCode.to_string(),
+                reason: format!("'{name}' is not a valid server name for `claude mcp add-json`"),
+            });
+        }
+        let mut native = match self.to_native_value(HarnessKind::ClaudeCode, name)? {
+            serde_json::Value::Object(native) => native,
+            other => return Ok(serde_json::to_string(&other)?),
+        };
+        let transport = native.remove("type").unwrap_or_else(|| "stdio".into());
+        let mut payload = serde_json::Map::from_iter([("type".to_string(), transport)]);
+        payload.append(&mut native);
+        Ok(serde_json::to_string(&payload)?)
+    }
+
+    /// Parses a payload in the shape `claude mcp add-json` accepts, as
+    /// produced by [`to_claude_cli_json`](Self::to_claude_cli_json) or
+    /// printed by `claude mcp get`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::JsonParse` if `json` is not valid JSON, or
+    /// `Error::UnsupportedMcpConfig` if it is not a valid server entry.
+    pub fn from_claude_cli_json(json: &str) -> Result<Self, Error> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        crate::harness::claude_code::parse_mcp_server(&value)
+    }
+
+    /// Returns a copy with secret values replaced by [`REDACTED`].
+    ///
+    /// Plain env, header, and OAuth secret values are masked when their name
+    /// suggests sensitive data. For Goose, which writes resolved values
+    /// instead of references, every env reference is masked as well.
+    #[must_use]
+    pub fn redacted(&self, kind: HarnessKind) -> Self {
+        self.redacted_with(kind == HarnessKind::Goose)
+    }
+
+    /// Like [`redacted`](Self::redacted), masking every env reference if
+    /// `resolves_env_refs` is set.
+    pub(crate) fn redacted_with(&self, resolves_env_refs: bool) -> Self {
+        let mut server = self.clone();
+        match &mut server {
+            Self::Stdio(s) => redact_values(&mut s.env, resolves_env_refs),
+            Self::Sse(s) => redact_values(&mut s.headers, resolves_env_refs),
+            Self::Http(h) => {
+                redact_values(&mut h.headers, resolves_env_refs);
+                if let Some(secret) = h.oauth.as_mut().and_then(|o| o.client_secret.as_mut()) {
+                    redact_value("client_secret", secret, resolves_env_refs);
+                }
+            }
+            Self::GooseNative(_) => {}
+        }
+        server
+    }
+
+    pub(crate) fn to_claude_code_value(
+        &self,
+        kind: HarnessKind,
+    ) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let mut obj = serde_json::json!({
@@ -156,7 +566,7 @@ impl McpServer {
                     "args": s.args,
                 });
                 if !s.env.is_empty() {
-                    let env: std::collections::HashMap<String, String> = s
+                    let env: BTreeMap<String, String> = s
                         .env
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -168,9 +578,10 @@ impl McpServer {
             Self::Sse(s) => {
                 let mut obj = serde_json::json!({
                     "url": s.url,
+                    "type": "sse",
                 });
                 if !s.headers.is_empty() {
-                    let headers: std::collections::HashMap<String, String> = s
+                    let headers: BTreeMap<String, String> = s
                         .headers
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -185,7 +596,7 @@ impl McpServer {
                     "type": "http",
                 });
                 if !h.headers.is_empty() {
-                    let headers: std::collections::HashMap<String, String> = h
+                    let headers: BTreeMap<String, String> = h
                         .headers
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -194,10 +605,14 @@ impl McpServer {
                 }
                 Ok(obj)
             }
+            Self::GooseNative(g) => Err(goose_native_unsupported(kind, g)),
         }
     }
 
-    fn to_copilot_cli_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+    pub(crate) fn to_copilot_cli_value(
+        &self,
+        kind: HarnessKind,
+    ) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let mut obj = serde_json::json!({
@@ -205,7 +620,7 @@ impl McpServer {
                     "args": s.args,
                 });
                 if !s.env.is_empty() {
-                    let env: std::collections::HashMap<String, String> = s
+                    let env: BTreeMap<String, String> = s
                         .env
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -223,7 +638,7 @@ impl McpServer {
                     "url": s.url,
                 });
                 if !s.headers.is_empty() {
-                    let headers: std::collections::HashMap<String, String> = s
+                    let headers: BTreeMap<String, String> = s
                         .headers
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -241,7 +656,7 @@ impl McpServer {
                     "url": h.url,
                 });
                 if !h.headers.is_empty() {
-                    let headers: std::collections::HashMap<String, String> = h
+                    let headers: BTreeMap<String, String> = h
                         .headers
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -253,10 +668,11 @@ impl McpServer {
                 }
                 Ok(obj)
             }
+            Self::GooseNative(g) => Err(goose_native_unsupported(kind, g)),
         }
     }
 
-    fn to_opencode_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+    pub(crate) fn to_opencode_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let mut command = vec![s.command.clone()];
@@ -267,7 +683,7 @@ impl McpServer {
                     "command": command,
                 });
                 if !s.env.is_empty() {
-                    let env: std::collections::HashMap<String, String> = s
+                    let env: BTreeMap<String, String> = s
                         .env
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -286,7 +702,7 @@ impl McpServer {
                     "url": s.url,
                 });
                 if !s.headers.is_empty() {
-                    let headers: std::collections::HashMap<String, String> = s
+                    let headers: BTreeMap<String, String> = s
                         .headers
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -305,7 +721,7 @@ impl McpServer {
                     "url": h.url,
                 });
                 if !h.headers.is_empty() {
-                    let headers: std::collections::HashMap<String, String> = h
+                    let headers: BTreeMap<String, String> = h
                         .headers
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -334,23 +750,28 @@ impl McpServer {
                 }
                 Ok(obj)
             }
+            Self::GooseNative(g) => Err(goose_native_unsupported(kind, g)),
         }
     }
 
-    fn to_goose_value(&self, kind: HarnessKind, name: &str) -> Result<serde_json::Value, Error> {
+    pub(crate) fn to_goose_value(
+        &self,
+        kind: HarnessKind,
+        name: &str,
+    ) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let args: Vec<String> = s.args.to_vec();
                 let mut obj = serde_json::json!({
                     "name": name,
                     "description": "",
-                    "enabled": true,
+                    "enabled": s.enabled,
                     "type": "stdio",
                     "cmd": s.command,
                     "args": args,
                 });
                 if !s.env.is_empty() {
-                    let envs: std::collections::HashMap<String, String> = s
+                    let envs: BTreeMap<String, String> = s
                         .env
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -366,7 +787,7 @@ impl McpServer {
                 let mut obj = serde_json::json!({
                     "name": name,
                     "description": "",
-                    "enabled": true,
+                    "enabled": h.enabled,
                     "type": "streamable_http",
                     "uri": h.url,
                 });
@@ -379,10 +800,33 @@ impl McpServer {
                 harness: kind.to_string(),
                 reason: "SSE transport not supported".into(),
             }),
+            Self::GooseNative(g) => {
+                let mut obj = serde_json::json!({
+                    "name": g.name,
+                    "enabled": g.enabled,
+                    "type": g.kind,
+                });
+                if let Some(display_name) = &g.display_name {
+                    obj["display_name"] = serde_json::json!(display_name);
+                }
+                if let Some(description) = &g.description {
+                    obj["description"] = serde_json::json!(description);
+                }
+                if let Some(code) = &g.code {
+                    obj["code"] = serde_json::json!(code);
+                }
+                if !g.dependencies.is_empty() {
+                    obj["dependencies"] = serde_json::json!(g.dependencies);
+                }
+                if let Some(timeout_ms) = g.timeout_ms {
+                    obj["timeout"] = serde_json::json!(timeout_ms / 1000);
+                }
+                Ok(obj)
+            }
         }
     }
 
-    fn to_ampcode_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
+    pub(crate) fn to_ampcode_value(&self, kind: HarnessKind) -> Result<serde_json::Value, Error> {
         match self {
             Self::Stdio(s) => {
                 let mut obj = serde_json::json!({
@@ -390,7 +834,7 @@ impl McpServer {
                     "args": s.args,
                 });
                 if !s.env.is_empty() {
-                    let env: std::collections::HashMap<String, String> = s
+                    let env: BTreeMap<String, String> = s
                         .env
                         .iter()
                         .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
@@ -399,14 +843,22 @@ impl McpServer {
                 }
                 Ok(obj)
             }
-            Self::Sse(_) => Err(Error::UnsupportedMcpConfig {
-                harness: kind.to_string(),
-                reason: "SSE transport not supported".into(),
-            }),
-            Self::Http(_) => Err(Error::UnsupportedMcpConfig {
-                harness: kind.to_string(),
-                reason: "HTTP transport not supported".into(),
-            }),
+            // Amp has no transport field; both are written as a bare URL.
+            Self::Sse(SseMcpServer { url, headers, .. })
+            | Self::Http(HttpMcpServer { url, headers, .. }) => {
+                let mut obj = serde_json::json!({
+                    "url": url,
+                });
+                if !headers.is_empty() {
+                    let headers: BTreeMap<String, String> = headers
+                        .iter()
+                        .map(|(k, v)| Ok((k.clone(), v.try_to_native(kind)?)))
+                        .collect::<Result<_, Error>>()?;
+                    obj["headers"] = serde_json::to_value(headers).unwrap();
+                }
+                Ok(obj)
+            }
+            Self::GooseNative(g) => Err(goose_native_unsupported(kind, g)),
         }
     }
 }
@@ -431,6 +883,7 @@ impl McpServer {
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct StdioMcpServer {
     /// The command to execute (e.g., `"node"`, `"npx"`).
     pub command: String,
@@ -442,7 +895,11 @@ pub struct StdioMcpServer {
     /// Environment variables for the process.
     ///
     /// Values can be plain strings or environment variable references.
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub env: HashMap<String, EnvValue>,
 
     /// Working directory for the process.
@@ -479,6 +936,7 @@ pub struct StdioMcpServer {
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct SseMcpServer {
     /// The SSE endpoint URL.
     pub url: String,
@@ -487,7 +945,11 @@ pub struct SseMcpServer {
     ///
     /// Values can be plain strings or environment variable references,
     /// useful for authentication tokens.
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub headers: HashMap<String, EnvValue>,
 
     /// Whether this server is enabled.
@@ -527,6 +989,7 @@ pub struct SseMcpServer {
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct HttpMcpServer {
     /// The HTTP endpoint URL.
     pub url: String,
@@ -534,7 +997,11 @@ pub struct HttpMcpServer {
     /// HTTP headers to include in requests.
     ///
     /// Values can be plain strings or environment variable references.
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde(
+        default,
+        skip_serializing_if = "HashMap::is_empty",
+        serialize_with = "serialize_sorted"
+    )]
     pub headers: HashMap<String, EnvValue>,
 
     /// OAuth configuration for authentication.
@@ -556,10 +1023,221 @@ pub struct HttpMcpServer {
     pub timeout_ms: Option<u64>,
 }
 
+/// The kind of a [`GooseExtension`], matching Goose's `type` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum GooseExtensionKind {
+    /// An extension bundled with Goose, such as `developer` or `memory`.
+    Builtin,
+    /// An extension provided by the Goose agent itself, such as `todo`.
+    Platform,
+    /// Python source that Goose runs with `uvx`.
+    InlinePython,
+}
+
+/// A Goose extension with no equivalent in other harnesses.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::mcp::{GooseExtension, GooseExtensionKind};
+///
+/// let developer = GooseExtension::builtin("developer");
+/// assert_eq!(developer.kind, GooseExtensionKind::Builtin);
+/// assert!(developer.enabled);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct GooseExtension {
+    /// The extension type.
+    pub kind: GooseExtensionKind,
+
+    /// The name Goose resolves the extension by.
+    pub name: String,
+
+    /// Human-readable name shown in Goose's UI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+
+    /// Description of the extension.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Python source code, for [`GooseExtensionKind::InlinePython`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+
+    /// Python packages installed before running `code`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+
+    /// Whether this extension is enabled.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Tool call timeout in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+}
+
+impl GooseExtension {
+    /// Creates an enabled builtin extension reference.
+    #[must_use]
+    pub fn builtin(name: impl Into<String>) -> Self {
+        Self {
+            kind: GooseExtensionKind::Builtin,
+            name: name.into(),
+            display_name: None,
+            description: None,
+            code: None,
+            dependencies: Vec::new(),
+            enabled: true,
+            timeout_ms: None,
+        }
+    }
+}
+
+/// Result of parsing an MCP config entry by entry, from
+/// [`Harness::parse_mcp_config_lenient`](crate::Harness::parse_mcp_config_lenient).
+#[derive(Debug, Default)]
+pub struct McpParseReport {
+    /// Servers that parsed successfully, sorted by name.
+    pub servers: BTreeMap<String, McpServer>,
+    /// Servers that failed to parse, with the reason, in config order.
+    pub errors: Vec<(String, Error)>,
+}
+
+impl McpParseReport {
+    /// Returns `true` if every server parsed.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Where the value of an [`EffectiveSetting`] comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum SettingSource {
+    /// Set on the server and honored by the harness.
+    Configured,
+    /// Not set on the server; the harness default applies.
+    HarnessDefault,
+    /// Set on the server but not representable by the harness, so the
+    /// harness default applies instead.
+    Ignored,
+}
+
+/// A setting as a harness will apply it, with where its value comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EffectiveSetting<T> {
+    /// The value the harness uses.
+    pub value: T,
+    /// Where the value comes from.
+    pub source: SettingSource,
+}
+
+impl<T> EffectiveSetting<T> {
+    fn new(value: T, source: SettingSource) -> Self {
+        Self { value, source }
+    }
+}
+
+/// Server settings as a harness will apply them, from
+/// [`McpServer::effective_config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct EffectiveConfig {
+    /// Timeout in milliseconds, `None` if neither the server nor the
+    /// harness documentation sets one.
+    pub timeout_ms: EffectiveSetting<Option<u64>>,
+    /// Whether the harness starts the server.
+    pub enabled: EffectiveSetting<bool>,
+}
+
+/// Options for [`McpServer::to_native_value_with`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NativeOptions {
+    /// Replace secret values with [`REDACTED`], for printing configs in
+    /// logs and bug reports. See [`McpServer::redacted`].
+    pub redact: bool,
+
+    /// Fail with `Error::LossyConversion` instead of discarding fields the
+    /// harness cannot represent. See [`McpServer::dropped_fields`].
+    pub strict: bool,
+
+    /// Write paths under the home directory relative to it, for configs
+    /// checked into a dotfiles repository. See [`crate::portable`].
+    pub portable: bool,
+}
+
+impl NativeOptions {
+    /// Sets whether secret values are redacted.
+    #[must_use]
+    pub fn with_redact(mut self, redact: bool) -> Self {
+        self.redact = redact;
+        self
+    }
+
+    /// Sets whether conversions that would drop fields are rejected.
+    #[must_use]
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets whether home directory paths are written relative to it.
+    #[must_use]
+    pub fn with_portable(mut self, portable: bool) -> Self {
+        self.portable = portable;
+        self
+    }
+}
+
+fn goose_native_unsupported(kind: HarnessKind, extension: &GooseExtension) -> Error {
+    Error::UnsupportedMcpConfig {
+        harness: kind.to_string(),
+        reason: format!(
+            "Goose extension '{}' only runs inside Goose",
+            extension.name
+        ),
+    }
+}
+
+fn redact_values(values: &mut HashMap<String, EnvValue>, resolves_env_refs: bool) {
+    for (name, value) in values.iter_mut() {
+        redact_value(name, value, resolves_env_refs);
+    }
+}
+
+fn plain_secrets(values: &HashMap<String, EnvValue>) -> Vec<&str> {
+    values
+        .iter()
+        .filter(|(name, value)| {
+            matches!(value, EnvValue::Plain(s) if !s.is_empty()) && is_sensitive_name(name)
+        })
+        .map(|(name, _)| name.as_str())
+        .collect()
+}
+
+fn redact_value(name: &str, value: &mut EnvValue, resolves_env_refs: bool) {
+    let secret = match value {
+        EnvValue::Plain(s) => !s.is_empty() && is_sensitive_name(name),
+        EnvValue::EnvRef { .. } => resolves_env_refs,
+    };
+    if secret {
+        *value = EnvValue::plain(REDACTED);
+    }
+}
+
 /// OAuth configuration for HTTP MCP servers.
 ///
 /// All fields are optional to support different OAuth flows.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct OAuthConfig {
     /// OAuth client ID.
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -571,9 +1249,445 @@ pub struct OAuthConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub client_secret: Option<EnvValue>,
 
-    /// OAuth scope(s) to request.
-    #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub scope: Option<String>,
+    /// OAuth scope(s) to request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+/// Builder for stdio MCP servers distributed as Docker images.
+///
+/// Produces a [`StdioMcpServer`] that runs `docker run -i --rm ...`, with
+/// volume mounts and `-e NAME` flags for every environment variable so the
+/// values set by the harness reach the container.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::mcp::DockerMcpServer;
+/// use harness_locate::types::EnvValue;
+///
+/// let server = DockerMcpServer::new("ghcr.io/github/github-mcp-server")
+///     .with_env("GITHUB_TOKEN", EnvValue::env("GITHUB_TOKEN"))
+///     .with_volume("/home/me/src", "/workspace")
+///     .into_stdio();
+///
+/// assert_eq!(server.command, "docker");
+/// assert_eq!(
+///     server.args,
+///     [
+///         "run", "-i", "--rm",
+///         "-v", "/home/me/src:/workspace",
+///         "-e", "GITHUB_TOKEN",
+///         "ghcr.io/github/github-mcp-server",
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DockerMcpServer {
+    /// The image reference (e.g., `"mcp/fetch"` or `"ghcr.io/org/img:tag"`).
+    pub image: String,
+
+    /// Volume mounts as `(host, container, read_only)`.
+    pub volumes: Vec<(PathBuf, String, bool)>,
+
+    /// Environment variables passed through to the container.
+    pub env: HashMap<String, EnvValue>,
+
+    /// Extra `docker run` options placed before the image.
+    pub docker_args: Vec<String>,
+
+    /// Arguments passed to the container after the image.
+    pub args: Vec<String>,
+}
+
+impl DockerMcpServer {
+    /// Creates a builder for the given image.
+    #[must_use]
+    pub fn new(image: impl Into<String>) -> Self {
+        Self {
+            image: image.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Mounts a host path into the container.
+    #[must_use]
+    pub fn with_volume(mut self, host: impl Into<PathBuf>, container: impl Into<String>) -> Self {
+        self.volumes.push((host.into(), container.into(), false));
+        self
+    }
+
+    /// Mounts a host path into the container read-only.
+    #[must_use]
+    pub fn with_read_only_volume(
+        mut self,
+        host: impl Into<PathBuf>,
+        container: impl Into<String>,
+    ) -> Self {
+        self.volumes.push((host.into(), container.into(), true));
+        self
+    }
+
+    /// Sets an environment variable and passes it through to the container.
+    #[must_use]
+    pub fn with_env(mut self, name: impl Into<String>, value: EnvValue) -> Self {
+        self.env.insert(name.into(), value);
+        self
+    }
+
+    /// Adds a `docker run` option placed before the image (e.g., `--network=host`).
+    #[must_use]
+    pub fn with_docker_arg(mut self, arg: impl Into<String>) -> Self {
+        self.docker_args.push(arg.into());
+        self
+    }
+
+    /// Adds an argument passed to the container after the image.
+    #[must_use]
+    pub fn with_arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Builds the `docker run` invocation.
+    ///
+    /// Environment flags are emitted in sorted order so the output is stable.
+    #[must_use]
+    pub fn into_stdio(self) -> StdioMcpServer {
+        let mut args: Vec<String> = ["run", "-i", "--rm"].map(String::from).into();
+
+        for (host, container, read_only) in &self.volumes {
+            args.push("-v".into());
+            let mut mount = format!("{}:{container}", host.display());
+            if *read_only {
+                mount.push_str(":ro");
+            }
+            args.push(mount);
+        }
+
+        let mut names: Vec<&String> = self.env.keys().collect();
+        names.sort();
+        for name in names {
+            args.push("-e".into());
+            args.push(name.clone());
+        }
+
+        args.extend(self.docker_args);
+        args.push(self.image);
+        args.extend(self.args);
+
+        StdioMcpServer {
+            command: "docker".into(),
+            args,
+            env: self.env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        }
+    }
+}
+
+/// Common authentication header shapes for remote MCP servers.
+///
+/// Each preset reads its secret from an environment variable, so the
+/// generated headers hold [`EnvValue`] references rather than credentials.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::mcp::{AuthPreset, HttpMcpServer, McpServer};
+/// use harness_locate::types::{EnvValue, HarnessKind};
+///
+/// let mut server = McpServer::Http(HttpMcpServer {
+///     url: "https://api.githubcopilot.com/mcp/".to_string(),
+///     headers: Default::default(),
+///     oauth: None,
+///     enabled: true,
+///     timeout_ms: None,
+/// });
+///
+/// let auth = AuthPreset::bearer("GITHUB_PAT");
+/// auth.check(HarnessKind::ClaudeCode)?;
+/// auth.apply(&mut server)?;
+///
+/// let McpServer::Http(http) = &server else { unreachable!() };
+/// assert_eq!(
+///     http.headers["Authorization"],
+///     EnvValue::env_with_prefix("Bearer ", "GITHUB_PAT")
+/// );
+/// assert!(auth.check(HarnessKind::Goose).is_err());
+/// # Ok::<(), harness_locate::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scheme", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum AuthPreset {
+    /// `Authorization: Bearer <token>`.
+    Bearer {
+        /// Variable holding the token.
+        env: String,
+    },
+    /// `Authorization: Basic <credentials>`.
+    Basic {
+        /// Variable holding the base64-encoded `user:password` pair.
+        env: String,
+    },
+    /// A custom header carrying the key as-is (e.g., `X-API-Key`).
+    ApiKey {
+        /// Header name.
+        header: String,
+        /// Variable holding the key.
+        env: String,
+    },
+}
+
+impl AuthPreset {
+    /// Bearer token authentication, read from `env`.
+    #[must_use]
+    pub fn bearer(env: impl Into<String>) -> Self {
+        Self::Bearer { env: env.into() }
+    }
+
+    /// Basic authentication, with the base64-encoded `user:password` pair
+    /// read from `env`.
+    #[must_use]
+    pub fn basic(env: impl Into<String>) -> Self {
+        Self::Basic { env: env.into() }
+    }
+
+    /// An API key sent in `header`, read from `env`.
+    #[must_use]
+    pub fn api_key(header: impl Into<String>, env: impl Into<String>) -> Self {
+        Self::ApiKey {
+            header: header.into(),
+            env: env.into(),
+        }
+    }
+
+    /// The name of the header this preset sets.
+    #[must_use]
+    pub fn header_name(&self) -> &str {
+        match self {
+            Self::Bearer { .. } | Self::Basic { .. } => "Authorization",
+            Self::ApiKey { header, .. } => header,
+        }
+    }
+
+    /// The headers this preset adds to a remote server.
+    #[must_use]
+    pub fn headers(&self) -> HashMap<String, EnvValue> {
+        let value = match self {
+            Self::Bearer { env } => EnvValue::env_with_prefix("Bearer ", env.as_str()),
+            Self::Basic { env } => EnvValue::env_with_prefix("Basic ", env.as_str()),
+            Self::ApiKey { env, .. } => EnvValue::env(env.as_str()),
+        };
+        HashMap::from([(self.header_name().to_string(), value)])
+    }
+
+    /// Adds this preset's headers to a remote server, replacing any header
+    /// of the same name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMcpConfig`] if `server` is not an SSE or
+    /// HTTP server, or the API-key header name is not a valid HTTP token.
+    pub fn apply(&self, server: &mut McpServer) -> Result<(), Error> {
+        self.check_header_name()?;
+        let headers = match server {
+            McpServer::Sse(s) => &mut s.headers,
+            McpServer::Http(h) => &mut h.headers,
+            McpServer::Stdio(_) | McpServer::GooseNative(_) => {
+                return Err(Error::UnsupportedMcpConfig {
+                    harness: "auth preset".into(),
+                    reason: "authentication headers only apply to remote servers".into(),
+                });
+            }
+        };
+        headers.extend(self.headers());
+        Ok(())
+    }
+
+    /// Checks that the harness `kind` can send this preset's headers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedMcpConfig`] if the harness does not
+    /// support custom headers, or the header name is not a valid HTTP token.
+    pub fn check(&self, kind: HarnessKind) -> Result<(), Error> {
+        self.check_header_name()?;
+        if !McpCapabilities::for_kind(kind).headers {
+            return Err(Error::UnsupportedMcpConfig {
+                harness: kind.as_str().into(),
+                reason: format!(
+                    "custom headers are not supported, so '{}' cannot be sent",
+                    self.header_name()
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn check_header_name(&self) -> Result<(), Error> {
+        let name = self.header_name();
+        // RFC 9110 token characters.
+        let valid = !name.is_empty()
+            && name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b));
+        if valid {
+            Ok(())
+        } else {
+            Err(Error::UnsupportedMcpConfig {
+                harness: "auth preset".into(),
+                reason: format!("'{name}' is not a valid HTTP header name"),
+            })
+        }
+    }
+}
+
+/// Options and image parsed from a `docker run` / `podman run` command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DockerRun<'a> {
+    /// Options before the image, with their values.
+    pub options: Vec<&'a str>,
+    /// The image reference, if present.
+    pub image: Option<&'a str>,
+}
+
+/// `docker run` options that consume the following argument.
+const DOCKER_VALUE_OPTIONS: &[&str] = &[
+    "-e",
+    "--env",
+    "--env-file",
+    "-v",
+    "--volume",
+    "--mount",
+    "-p",
+    "--publish",
+    "--name",
+    "--network",
+    "--net",
+    "-w",
+    "--workdir",
+    "-u",
+    "--user",
+    "--entrypoint",
+    "--platform",
+    "-l",
+    "--label",
+    "-h",
+    "--hostname",
+    "--add-host",
+    "--cap-add",
+    "--cap-drop",
+    "--pull",
+];
+
+impl StdioMcpServer {
+    /// Parses this server's command as `docker run` or `podman run`.
+    ///
+    /// Returns `None` for any other command.
+    pub(crate) fn docker_run(&self) -> Option<DockerRun<'_>> {
+        let program = std::path::Path::new(&self.command)
+            .file_stem()
+            .and_then(|s| s.to_str())?;
+        if !matches!(program, "docker" | "podman") {
+            return None;
+        }
+
+        let mut iter = self.args.iter().map(String::as_str);
+        if iter.next()? != "run" {
+            return None;
+        }
+
+        let mut options = Vec::new();
+        while let Some(arg) = iter.next() {
+            if !arg.starts_with('-') {
+                return Some(DockerRun {
+                    options,
+                    image: Some(arg),
+                });
+            }
+            options.push(arg);
+            if DOCKER_VALUE_OPTIONS.contains(&arg)
+                && let Some(value) = iter.next()
+            {
+                options.push(value);
+            }
+        }
+
+        Some(DockerRun {
+            options,
+            image: None,
+        })
+    }
+
+    /// Returns `true` if this server runs a container via `docker run` or `podman run`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::mcp::DockerMcpServer;
+    ///
+    /// assert!(DockerMcpServer::new("mcp/fetch").into_stdio().is_docker());
+    /// ```
+    #[must_use]
+    pub fn is_docker(&self) -> bool {
+        self.docker_run().is_some()
+    }
+}
+
+#[cfg(feature = "validation")]
+impl DockerRun<'_> {
+    /// Returns `true` if stdin is kept open (`-i`, `--interactive`, or a
+    /// short-flag cluster such as `-it`).
+    pub fn is_interactive(&self) -> bool {
+        self.flags().any(|opt| {
+            opt == "--interactive"
+                || opt == "--interactive=true"
+                || (opt.len() > 1
+                    && !opt.starts_with("--")
+                    && opt[1..].contains('i')
+                    && opt[1..].chars().all(|c| matches!(c, 'i' | 't' | 'd')))
+        })
+    }
+
+    /// Returns `true` if an `--env-file` is passed.
+    pub fn has_env_file(&self) -> bool {
+        self.flags()
+            .any(|opt| opt == "--env-file" || opt.starts_with("--env-file="))
+    }
+
+    /// Returns the variable names passed with `-e`/`--env`.
+    pub fn passed_env(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        let mut iter = self.options.iter();
+        while let Some(opt) = iter.next() {
+            let spec = match *opt {
+                "-e" | "--env" => iter.next().copied(),
+                _ => opt
+                    .strip_prefix("--env=")
+                    .or_else(|| opt.strip_prefix("-e").filter(|s| !s.is_empty())),
+            };
+            if let Some(spec) = spec {
+                names.push(spec.split('=').next().unwrap_or(spec));
+            }
+        }
+        names
+    }
+
+    /// Iterates over options, skipping the values of value-taking options.
+    fn flags(&self) -> impl Iterator<Item = &str> {
+        let mut skip = false;
+        self.options.iter().copied().filter(move |opt| {
+            if skip {
+                skip = false;
+                return false;
+            }
+            skip = DOCKER_VALUE_OPTIONS.contains(opt);
+            true
+        })
+    }
 }
 
 /// Describes what MCP features a harness supports.
@@ -599,7 +1713,8 @@ pub struct OAuthConfig {
 /// assert!(caps.stdio);
 /// assert!(caps.oauth);  // OpenCode supports OAuth
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
 #[non_exhaustive]
 pub struct McpCapabilities {
     /// Supports local stdio servers.
@@ -625,6 +1740,80 @@ pub struct McpCapabilities {
 
     /// Supports working directory (cwd) for stdio servers.
     pub cwd: bool,
+
+    /// Supports Goose builtin, platform, and inline Python extensions.
+    pub goose_native: bool,
+
+    /// Timeout in milliseconds the harness applies to servers without one,
+    /// if documented.
+    pub default_timeout_ms: Option<u64>,
+
+    /// How a server is disabled without removing it.
+    pub enabled_semantics: EnabledSemantics,
+}
+
+/// How a harness disables a configured MCP server.
+///
+/// Harnesses disagree on what `enabled: false` means: OpenCode skips the
+/// server, Claude Code ignores the key and Crush expects `disabled: true`
+/// instead. Only [`EnabledKey`](Self::EnabledKey) and
+/// [`DisabledKey`](Self::DisabledKey) honor a flag written into the server
+/// entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EnabledSemantics {
+    /// Every configured server runs; remove the entry to disable it.
+    #[default]
+    Unsupported,
+    /// `"enabled": false` in the server entry disables it.
+    EnabledKey,
+    /// `"disabled": true` in the server entry disables it.
+    DisabledKey,
+    /// Servers are disabled from a list kept outside their entry, such as
+    /// Claude Code's `disabledMcpjsonServers`; flags in the entry are
+    /// ignored.
+    ExternalList,
+}
+
+impl EnabledSemantics {
+    /// Returns the key of the flag the harness reads from the server
+    /// entry, if any.
+    #[must_use]
+    pub fn flag_key(self) -> Option<&'static str> {
+        match self {
+            Self::EnabledKey => Some("enabled"),
+            Self::DisabledKey => Some("disabled"),
+            Self::Unsupported | Self::ExternalList => None,
+        }
+    }
+
+    /// Returns `true` if a flag in the server entry is honored.
+    #[must_use]
+    pub fn honors_flag(self) -> bool {
+        self.flag_key().is_some()
+    }
+
+    /// Returns the native value of the flag for a server that is
+    /// `enabled`, or `None` if the entry has no flag.
+    #[must_use]
+    pub fn flag_value(self, enabled: bool) -> Option<bool> {
+        match self {
+            Self::EnabledKey => Some(enabled),
+            Self::DisabledKey => Some(!enabled),
+            Self::Unsupported | Self::ExternalList => None,
+        }
+    }
+
+    /// Reads whether a server is enabled from its native entry. Servers
+    /// without a flag, or whose harness ignores it, are enabled.
+    #[must_use]
+    pub fn is_enabled(self, entry: &serde_json::Value) -> bool {
+        self.flag_key()
+            .and_then(|key| entry.get(key))
+            .and_then(serde_json::Value::as_bool)
+            .is_none_or(|flag| self.flag_value(true) == Some(flag))
+    }
 }
 
 impl McpCapabilities {
@@ -652,6 +1841,9 @@ impl McpCapabilities {
                 toggle: false,
                 headers: true,
                 cwd: false,
+                goose_native: false,
+                default_timeout_ms: None,
+                enabled_semantics: EnabledSemantics::ExternalList,
             },
             HarnessKind::OpenCode => Self {
                 stdio: true,
@@ -662,6 +1854,9 @@ impl McpCapabilities {
                 toggle: true,
                 headers: true,
                 cwd: false,
+                goose_native: false,
+                default_timeout_ms: Some(5_000),
+                enabled_semantics: EnabledSemantics::EnabledKey,
             },
             HarnessKind::Goose => Self {
                 stdio: true,
@@ -669,19 +1864,26 @@ impl McpCapabilities {
                 http: true,
                 oauth: false,
                 timeout: false,
-                toggle: false,
+                toggle: true,
                 headers: false,
                 cwd: false,
+                goose_native: true,
+                default_timeout_ms: Some(300_000),
+                enabled_semantics: EnabledSemantics::EnabledKey,
             },
+            // Amp picks streamable HTTP or SSE from the URL itself.
             HarnessKind::AmpCode => Self {
                 stdio: true,
-                sse: false,
-                http: false,
+                sse: true,
+                http: true,
                 oauth: false,
                 timeout: false,
                 toggle: false,
-                headers: false,
+                headers: true,
                 cwd: false,
+                goose_native: false,
+                default_timeout_ms: None,
+                enabled_semantics: EnabledSemantics::Unsupported,
             },
             HarnessKind::CopilotCli => Self {
                 stdio: true,
@@ -692,15 +1894,158 @@ impl McpCapabilities {
                 toggle: false,
                 headers: true,
                 cwd: false,
+                goose_native: false,
+                default_timeout_ms: None,
+                enabled_semantics: EnabledSemantics::Unsupported,
             },
         }
     }
+
+    /// Returns the timeout in milliseconds `kind` applies to servers that do
+    /// not set one, or `None` if the harness does not document it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::mcp::McpCapabilities;
+    /// use harness_locate::types::HarnessKind;
+    ///
+    /// assert_eq!(McpCapabilities::default_timeout(HarnessKind::OpenCode), Some(5_000));
+    /// ```
+    #[must_use]
+    pub fn default_timeout(kind: HarnessKind) -> Option<u64> {
+        Self::for_kind(kind).default_timeout_ms
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn auth_presets_build_headers() {
+        let cases = [
+            (
+                AuthPreset::bearer("TOKEN"),
+                "Authorization",
+                "Bearer ${TOKEN}",
+            ),
+            (
+                AuthPreset::basic("CREDS"),
+                "Authorization",
+                "Basic ${CREDS}",
+            ),
+            (
+                AuthPreset::api_key("X-API-Key", "KEY"),
+                "X-API-Key",
+                "${KEY}",
+            ),
+        ];
+        for (preset, header, native) in cases {
+            let headers = preset.headers();
+            assert_eq!(headers.len(), 1);
+            assert_eq!(headers[header].to_native(HarnessKind::ClaudeCode), native);
+        }
+    }
+
+    #[test]
+    fn auth_presets_apply_to_remote_servers_only() {
+        let mut sse = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".into(),
+            headers: HashMap::from([("Authorization".into(), EnvValue::plain("old"))]),
+            enabled: true,
+            timeout_ms: None,
+        });
+        AuthPreset::bearer("TOKEN").apply(&mut sse).unwrap();
+        let McpServer::Sse(s) = &sse else {
+            unreachable!()
+        };
+        assert_eq!(
+            s.headers["Authorization"],
+            EnvValue::env_with_prefix("Bearer ", "TOKEN")
+        );
+
+        let mut stdio = McpServer::Stdio(StdioMcpServer {
+            command: "node".into(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        assert!(AuthPreset::bearer("TOKEN").apply(&mut stdio).is_err());
+        assert!(AuthPreset::api_key("X Key", "KEY").apply(&mut sse).is_err());
+    }
+
+    #[test]
+    fn auth_preset_check_follows_header_capability() {
+        let preset = AuthPreset::api_key("X-API-Key", "KEY");
+        for kind in HarnessKind::ALL {
+            let supported = McpCapabilities::for_kind(*kind).headers;
+            assert_eq!(preset.check(*kind).is_ok(), supported, "{kind:?}");
+        }
+        assert!(matches!(
+            preset.check(HarnessKind::Goose),
+            Err(Error::UnsupportedMcpConfig { .. })
+        ));
+    }
+
+    #[test]
+    fn docker_builder_mounts_volumes() {
+        let server = DockerMcpServer::new("mcp/filesystem")
+            .with_read_only_volume("/data", "/projects/data")
+            .with_docker_arg("--network=none")
+            .with_arg("/projects")
+            .into_stdio();
+
+        assert_eq!(
+            server.args,
+            vec![
+                "run",
+                "-i",
+                "--rm",
+                "-v",
+                "/data:/projects/data:ro",
+                "--network=none",
+                "mcp/filesystem",
+                "/projects",
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "validation")]
+    fn docker_run_parses_image_after_value_options() {
+        let server = StdioMcpServer {
+            command: "/usr/local/bin/podman".to_string(),
+            args: ["run", "-i", "-v", "/a:/b", "-eTOKEN", "img:1", "--flag"]
+                .map(String::from)
+                .into(),
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        };
+
+        let run = server.docker_run().unwrap();
+        assert_eq!(run.image, Some("img:1"));
+        assert!(run.is_interactive());
+        assert_eq!(run.passed_env(), vec!["TOKEN"]);
+    }
+
+    #[test]
+    fn non_docker_command_is_not_docker() {
+        let server = StdioMcpServer {
+            command: "npx".to_string(),
+            args: vec!["run".to_string()],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        };
+        assert!(!server.is_docker());
+    }
+
     #[test]
     fn stdio_server_serialization_roundtrip() {
         let server = McpServer::Stdio(StdioMcpServer {
@@ -915,11 +2260,37 @@ mod tests {
         assert!(caps.http);
         assert!(!caps.oauth); // Goose doesn't support OAuth
         assert!(!caps.timeout); // Goose doesn't support timeout
-        assert!(!caps.toggle); // Goose doesn't support toggle
+        assert!(caps.toggle); // Goose reads `enabled` from each extension
         assert!(!caps.headers); // Goose doesn't support headers
         assert!(!caps.cwd);
     }
 
+    #[test]
+    fn enabled_semantics_read_and_write_the_entry_flag() {
+        let opencode = McpCapabilities::for_kind(HarnessKind::OpenCode).enabled_semantics;
+        assert_eq!(opencode.flag_key(), Some("enabled"));
+        assert!(!opencode.is_enabled(&serde_json::json!({"enabled": false})));
+        assert!(opencode.is_enabled(&serde_json::json!({"disabled": true})));
+
+        let crush = EnabledSemantics::DisabledKey;
+        assert_eq!(crush.flag_value(false), Some(true));
+        assert!(!crush.is_enabled(&serde_json::json!({"disabled": true})));
+        assert!(crush.is_enabled(&serde_json::json!({"command": "x"})));
+
+        let claude = McpCapabilities::for_kind(HarnessKind::ClaudeCode).enabled_semantics;
+        assert_eq!(claude, EnabledSemantics::ExternalList);
+        assert!(claude.is_enabled(&serde_json::json!({"enabled": false})));
+
+        for kind in HarnessKind::ALL {
+            let caps = McpCapabilities::for_kind(*kind);
+            assert_eq!(
+                caps.toggle,
+                caps.enabled_semantics.honors_flag(),
+                "{kind:?}"
+            );
+        }
+    }
+
     #[test]
     fn mcp_capabilities_serialization() {
         let caps = McpCapabilities::for_kind(HarnessKind::OpenCode);
@@ -998,6 +2369,31 @@ mod tests {
         assert_eq!(names, vec!["AUTH_TOKEN", "OAUTH_SECRET"]);
     }
 
+    #[test]
+    fn plain_secret_names_http_includes_oauth_secret() {
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), EnvValue::plain("Bearer abc"));
+        headers.insert("X-Api-Key".to_string(), EnvValue::env("API_KEY"));
+        headers.insert("Accept".to_string(), EnvValue::plain("text/event-stream"));
+
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://example.com".to_string(),
+            headers,
+            oauth: Some(OAuthConfig {
+                client_id: Some("app".to_string()),
+                client_secret: Some(EnvValue::plain("s3cret")),
+                scope: None,
+            }),
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        assert_eq!(
+            server.plain_secret_names(),
+            vec!["Authorization", "client_secret"]
+        );
+    }
+
     #[test]
     fn missing_env_vars_returns_unset_vars() {
         let mut env = HashMap::new();
@@ -1057,11 +2453,11 @@ mod tests {
         );
         assert!(server.validate_capabilities(HarnessKind::OpenCode).is_ok());
         assert!(server.validate_capabilities(HarnessKind::Goose).is_err());
-        assert!(server.validate_capabilities(HarnessKind::AmpCode).is_err());
+        assert!(server.validate_capabilities(HarnessKind::AmpCode).is_ok());
     }
 
     #[test]
-    fn validate_capabilities_http_rejected_by_ampcode() {
+    fn validate_capabilities_http_accepted_by_ampcode() {
         let server = McpServer::Http(HttpMcpServer {
             url: "http://localhost".to_string(),
             headers: HashMap::new(),
@@ -1077,7 +2473,7 @@ mod tests {
         );
         assert!(server.validate_capabilities(HarnessKind::OpenCode).is_ok());
         assert!(server.validate_capabilities(HarnessKind::Goose).is_ok());
-        assert!(server.validate_capabilities(HarnessKind::AmpCode).is_err());
+        assert!(server.validate_capabilities(HarnessKind::AmpCode).is_ok());
     }
 
     #[test]
@@ -1102,6 +2498,29 @@ mod tests {
         assert_eq!(value["env"]["KEY"], "value");
     }
 
+    #[test]
+    fn serialize_writes_env_in_sorted_order() {
+        let env: HashMap<_, _> = ["ZETA", "ALPHA", "MID"]
+            .into_iter()
+            .map(|key| (key.to_string(), EnvValue::plain("v")))
+            .collect();
+
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "npx".to_string(),
+            args: vec![],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        let json = serde_json::to_string(&server).unwrap();
+        let alpha = json.find("ALPHA").unwrap();
+        let mid = json.find("MID").unwrap();
+        let zeta = json.find("ZETA").unwrap();
+        assert!(alpha < mid && mid < zeta);
+    }
+
     #[test]
     fn to_native_value_stdio_opencode() {
         let server = McpServer::Stdio(StdioMcpServer {
@@ -1141,6 +2560,21 @@ mod tests {
         assert_eq!(value["args"], serde_json::json!(["-y", "server"]));
     }
 
+    #[test]
+    fn to_native_value_goose_keeps_enabled_flag() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "http://localhost:8080".to_string(),
+            headers: HashMap::new(),
+            oauth: None,
+            enabled: false,
+            timeout_ms: None,
+        });
+
+        let value = server.to_native_value(HarnessKind::Goose, "off").unwrap();
+        assert_eq!(value["enabled"], false);
+        assert!(server.dropped_fields(HarnessKind::Goose).is_empty());
+    }
+
     #[test]
     fn to_native_value_http_claude_code() {
         let server = McpServer::Http(HttpMcpServer {
@@ -1157,4 +2591,216 @@ mod tests {
         assert_eq!(value["type"], "http");
         assert_eq!(value["url"], "http://localhost:8080");
     }
+
+    #[test]
+    fn to_native_value_sse_claude_code() {
+        let server = McpServer::Sse(SseMcpServer {
+            url: "http://localhost:8080/sse".to_string(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        let value = server
+            .to_native_value(HarnessKind::ClaudeCode, "test-server")
+            .unwrap();
+        assert_eq!(value["type"], "sse");
+        assert_eq!(value["url"], "http://localhost:8080/sse");
+    }
+
+    #[test]
+    fn claude_cli_json_round_trips_remote_servers() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://mcp.example.com".to_string(),
+            headers: [("Authorization".to_string(), EnvValue::env("GITHUB_TOKEN"))].into(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        let json = server.to_claude_cli_json("github").unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["type"], "http");
+        assert_eq!(value["headers"]["Authorization"], "${GITHUB_TOKEN}");
+        assert_eq!(McpServer::from_claude_cli_json(&json).unwrap(), server);
+
+        assert!(server.to_claude_cli_json("--scope").is_err());
+        assert!(McpServer::from_claude_cli_json(r#"{"type": "ws"}"#).is_err());
+    }
+
+    fn stdio_with_env(env: &[(&str, EnvValue)]) -> McpServer {
+        McpServer::Stdio(StdioMcpServer {
+            command: "npx".to_string(),
+            args: vec![],
+            env: env
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        })
+    }
+
+    #[test]
+    fn dropped_fields_lists_unwritten_fields() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "https://example.com/mcp".to_string(),
+            headers: [("X-Key".to_string(), EnvValue::plain("k"))].into(),
+            oauth: Some(OAuthConfig {
+                client_id: Some("client".to_string()),
+                client_secret: None,
+                scope: None,
+            }),
+            enabled: false,
+            timeout_ms: Some(1500),
+        });
+
+        assert_eq!(
+            server.dropped_fields(HarnessKind::ClaudeCode),
+            ["timeout_ms", "enabled", "oauth"]
+        );
+        assert_eq!(
+            server.dropped_fields(HarnessKind::Goose),
+            ["headers", "timeout_ms", "oauth"]
+        );
+        assert!(server.dropped_fields(HarnessKind::OpenCode).is_empty());
+    }
+
+    #[test]
+    fn effective_config_fills_harness_defaults() {
+        let mut server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: false,
+            timeout_ms: None,
+        });
+
+        let opencode = server.effective_config(HarnessKind::OpenCode);
+        assert_eq!(opencode.timeout_ms.value, Some(5_000));
+        assert_eq!(opencode.timeout_ms.source, SettingSource::HarnessDefault);
+        assert!(!opencode.enabled.value);
+        assert_eq!(opencode.enabled.source, SettingSource::Configured);
+
+        let claude = server.effective_config(HarnessKind::ClaudeCode);
+        assert_eq!(claude.timeout_ms.value, None);
+        assert!(claude.enabled.value);
+        assert_eq!(claude.enabled.source, SettingSource::Ignored);
+
+        if let McpServer::Stdio(s) = &mut server {
+            s.timeout_ms = Some(1500);
+        }
+        let goose = server.effective_config(HarnessKind::Goose);
+        assert_eq!(goose.timeout_ms.value, Some(300_000));
+        assert_eq!(goose.timeout_ms.source, SettingSource::Ignored);
+        assert!(!goose.enabled.value);
+        assert_eq!(goose.enabled.source, SettingSource::Configured);
+        let copilot = server.effective_config(HarnessKind::CopilotCli);
+        assert_eq!(copilot.timeout_ms.value, Some(1500));
+        assert_eq!(copilot.timeout_ms.source, SettingSource::Configured);
+    }
+
+    #[test]
+    fn strict_conversion_rejects_dropped_fields() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: Some(PathBuf::from("/srv/app")),
+            enabled: true,
+            timeout_ms: Some(5000),
+        });
+        let strict = NativeOptions::default().with_strict(true);
+
+        assert!(
+            server
+                .to_native_value_with(HarnessKind::ClaudeCode, "app", NativeOptions::default())
+                .is_ok()
+        );
+        let err = server
+            .to_native_value_with(HarnessKind::ClaudeCode, "app", strict)
+            .unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::LossyConversion { harness, fields }
+                if harness == "Claude Code" && fields == &["cwd", "timeout_ms"]
+        ));
+        assert_eq!(
+            err.to_string(),
+            "lossy MCP conversion for Claude Code: drops cwd, timeout_ms"
+        );
+    }
+
+    #[test]
+    fn strict_conversion_keeps_unsupported_error() {
+        let server = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+            enabled: false,
+            timeout_ms: None,
+        });
+        let strict = NativeOptions::default().with_strict(true);
+
+        let err = server
+            .to_native_value_with(HarnessKind::Goose, "remote", strict)
+            .unwrap_err();
+        assert!(matches!(err, Error::UnsupportedMcpConfig { .. }));
+    }
+
+    #[test]
+    fn redacted_masks_sensitive_plain_values_only() {
+        let server = stdio_with_env(&[
+            ("API_TOKEN", EnvValue::plain("tok_123")),
+            ("DEBUG", EnvValue::plain("true")),
+            ("GITHUB_TOKEN", EnvValue::env("GITHUB_TOKEN")),
+        ]);
+        let options = NativeOptions::default().with_redact(true);
+
+        let value = server
+            .to_native_value_with(HarnessKind::ClaudeCode, "test-server", options)
+            .unwrap();
+        assert_eq!(value["env"]["API_TOKEN"], REDACTED);
+        assert_eq!(value["env"]["DEBUG"], "true");
+        assert_eq!(value["env"]["GITHUB_TOKEN"], "${GITHUB_TOKEN}");
+    }
+
+    #[test]
+    fn redacted_masks_resolved_goose_env_refs() {
+        let server = stdio_with_env(&[("PORT", EnvValue::env("HARNESS_LOCATE_UNSET_REDACT"))]);
+        assert!(
+            server
+                .to_native_value(HarnessKind::Goose, "test-server")
+                .is_err()
+        );
+
+        let options = NativeOptions::default().with_redact(true);
+        let value = server
+            .to_native_value_with(HarnessKind::Goose, "test-server", options)
+            .unwrap();
+        assert_eq!(value["envs"]["PORT"], REDACTED);
+    }
+
+    #[test]
+    fn redacted_masks_headers_and_oauth_secret() {
+        let server = McpServer::Http(HttpMcpServer {
+            url: "http://localhost:8080".to_string(),
+            headers: HashMap::from([("Authorization".to_string(), EnvValue::plain("Bearer abc"))]),
+            oauth: Some(OAuthConfig {
+                client_id: Some("client".to_string()),
+                client_secret: Some(EnvValue::plain("shh")),
+                scope: None,
+            }),
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        let McpServer::Http(redacted) = server.redacted(HarnessKind::OpenCode) else {
+            unreachable!();
+        };
+        assert_eq!(redacted.headers["Authorization"], EnvValue::plain(REDACTED));
+        let oauth = redacted.oauth.unwrap();
+        assert_eq!(oauth.client_id.as_deref(), Some("client"));
+        assert_eq!(oauth.client_secret, Some(EnvValue::plain(REDACTED)));
+    }
 }
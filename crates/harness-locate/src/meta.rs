@@ -0,0 +1,297 @@
+//! Typed frontmatter for skills, agents, and commands.
+//!
+//! [`SkillMeta`], [`AgentMeta`], and [`CommandMeta`] deserialize the known
+//! frontmatter keys of each resource kind into typed fields. Keys they do
+//! not know are kept in `metadata`, so converting back to YAML loses
+//! nothing.
+//!
+//! # Examples
+//!
+//! ```
+//! use harness_locate::meta::AgentMeta;
+//! use harness_locate::skill::parse_frontmatter;
+//!
+//! let content = "---\ndescription: Reviews code\ntools: Read, Grep\nmodel: sonnet\n---\nYou review code.";
+//! let agent = AgentMeta::try_from(&parse_frontmatter(content)?)?;
+//!
+//! assert_eq!(agent.model.as_deref(), Some("sonnet"));
+//! assert_eq!(agent.tools.unwrap().names(), ["Read", "Grep"]);
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::skill::{Frontmatter, LocalizedText};
+use crate::target::Platforms;
+use crate::{Error, Result};
+
+/// A `tools` or `allowed-tools` value in one of the shapes harnesses use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ToolList {
+    /// A comma- or space-separated string, as Claude Code writes it.
+    Text(String),
+    /// A YAML sequence of tool names.
+    List(Vec<String>),
+    /// Tool names mapped to whether they are enabled, as OpenCode writes it.
+    Record(BTreeMap<String, bool>),
+}
+
+impl ToolList {
+    /// Returns the enabled tool names in order.
+    #[must_use]
+    pub fn names(&self) -> Vec<&str> {
+        match self {
+            Self::Text(text) => split(text),
+            Self::List(items) => items.iter().flat_map(|item| split(item)).collect(),
+            Self::Record(tools) => tools
+                .iter()
+                .filter(|(_, enabled)| **enabled)
+                .map(|(name, _)| name.as_str())
+                .collect(),
+        }
+    }
+}
+
+fn split(s: &str) -> Vec<&str> {
+    s.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// A command's `argument-hint`, written as a string or a list of names.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ArgumentHint {
+    /// A hint such as `[pr-number] [priority]`.
+    Text(String),
+    /// Argument names, such as `[pr-number, priority]`.
+    List(Vec<String>),
+}
+
+impl ArgumentHint {
+    /// Returns the hint as Claude Code displays it, bracketing list items.
+    #[must_use]
+    pub fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone(),
+            Self::List(items) => items
+                .iter()
+                .map(|item| format!("[{item}]"))
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+/// Frontmatter of a skill's `SKILL.md`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillMeta {
+    /// The skill name (required).
+    pub name: String,
+    /// Optional name shown to users, possibly translated.
+    #[serde(
+        default,
+        rename = "display-name",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub display_name: Option<LocalizedText>,
+    /// Optional description of the skill, possibly translated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<LocalizedText>,
+    /// Trigger phrases that activate this skill.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<String>,
+    /// Tools the skill may use without asking.
+    #[serde(
+        default,
+        rename = "allowed-tools",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub allowed_tools: Option<ToolList>,
+    /// License name or file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Platforms the skill works on; empty means all.
+    #[serde(default, skip_serializing_if = "Platforms::is_empty")]
+    pub platforms: Platforms,
+    /// Frontmatter fields not captured above, sorted by key.
+    #[serde(flatten)]
+    pub metadata: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Frontmatter of an agent file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AgentMeta {
+    /// The agent name; harnesses that name agents by file omit it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// When to use the agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Tools the agent may use.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tools: Option<ToolList>,
+    /// Model to run the agent with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Display color, named or hex.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// OpenCode agent mode (`subagent`, `primary`, or `all`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    /// Sampling temperature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Frontmatter fields not captured above, sorted by key.
+    #[serde(flatten)]
+    pub metadata: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Frontmatter of a command file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommandMeta {
+    /// What the command does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Arguments shown while typing the command (Claude Code).
+    #[serde(
+        default,
+        rename = "argument-hint",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub argument_hint: Option<ArgumentHint>,
+    /// Tools the command may use without asking.
+    #[serde(
+        default,
+        rename = "allowed-tools",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub allowed_tools: Option<ToolList>,
+    /// Model to run the command with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Agent that runs the command (OpenCode).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    /// Frontmatter fields not captured above, sorted by key.
+    #[serde(flatten)]
+    pub metadata: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Deserializes the frontmatter mapping, or `None` if there is none.
+fn deserialize<T: DeserializeOwned>(frontmatter: &Frontmatter<'_>) -> Result<Option<T>> {
+    match &frontmatter.yaml {
+        None | Some(serde_yaml::Value::Null) => Ok(None),
+        Some(yaml) => Ok(Some(serde_yaml::from_value(yaml.clone())?)),
+    }
+}
+
+impl TryFrom<&Frontmatter<'_>> for SkillMeta {
+    type Error = Error;
+
+    /// # Errors
+    ///
+    /// Returns `Error::MissingField` if there is no frontmatter, and
+    /// `Error::YamlParse` if a field has the wrong type or `name` is missing.
+    fn try_from(frontmatter: &Frontmatter<'_>) -> Result<Self> {
+        deserialize(frontmatter)?.ok_or_else(|| Error::MissingField("name".to_string()))
+    }
+}
+
+impl TryFrom<&Frontmatter<'_>> for AgentMeta {
+    type Error = Error;
+
+    /// Missing frontmatter yields an empty `AgentMeta`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::YamlParse` if a field has the wrong type.
+    fn try_from(frontmatter: &Frontmatter<'_>) -> Result<Self> {
+        Ok(deserialize(frontmatter)?.unwrap_or_default())
+    }
+}
+
+impl TryFrom<&Frontmatter<'_>> for CommandMeta {
+    type Error = Error;
+
+    /// Missing frontmatter yields an empty `CommandMeta`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::YamlParse` if a field has the wrong type.
+    fn try_from(frontmatter: &Frontmatter<'_>) -> Result<Self> {
+        Ok(deserialize(frontmatter)?.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skill::parse_frontmatter;
+
+    #[test]
+    fn skill_meta_keeps_unknown_fields() {
+        let content = "---\nname: pdf\ndescription: Edit PDFs\nallowed-tools: [Read, Bash]\nplatforms: [macos, linux-x86_64]\nversion: 2\n---\nBody";
+        let fm = parse_frontmatter(content).unwrap();
+        let meta = SkillMeta::try_from(&fm).unwrap();
+
+        assert_eq!(meta.name, "pdf");
+        assert_eq!(
+            meta.allowed_tools.as_ref().unwrap().names(),
+            ["Read", "Bash"]
+        );
+        assert_eq!(meta.platforms.to_string(), "macos, linux-x86_64");
+        assert_eq!(meta.metadata["version"], serde_yaml::Value::from(2));
+
+        let yaml = serde_yaml::to_value(&meta).unwrap();
+        assert_eq!(Some(yaml), fm.yaml);
+    }
+
+    #[test]
+    fn skill_meta_requires_name() {
+        let fm = parse_frontmatter("No frontmatter").unwrap();
+        assert!(matches!(
+            SkillMeta::try_from(&fm),
+            Err(Error::MissingField(_))
+        ));
+        let fm = parse_frontmatter("---\ndescription: x\n---\n").unwrap();
+        assert!(matches!(SkillMeta::try_from(&fm), Err(Error::YamlParse(_))));
+    }
+
+    #[test]
+    fn agent_meta_reads_every_tools_shape() {
+        let cases = [
+            ("tools: Read, Grep", vec!["Read", "Grep"]),
+            ("tools: [Read, Grep]", vec!["Read", "Grep"]),
+            ("tools:\n  write: false\n  bash: true", vec!["bash"]),
+        ];
+        for (yaml, expected) in cases {
+            let content = format!("---\n{yaml}\nmode: subagent\n---\n");
+            let meta = AgentMeta::try_from(&parse_frontmatter(&content).unwrap()).unwrap();
+            assert_eq!(meta.tools.unwrap().names(), expected, "{yaml}");
+            assert_eq!(meta.mode.as_deref(), Some("subagent"));
+        }
+
+        let empty = AgentMeta::try_from(&parse_frontmatter("Body").unwrap()).unwrap();
+        assert_eq!(empty, AgentMeta::default());
+    }
+
+    #[test]
+    fn command_meta_reads_argument_hints() {
+        let content = "---\nargument-hint: [pr-number, priority]\nagent: build\n---\nReview $1";
+        let meta = CommandMeta::try_from(&parse_frontmatter(content).unwrap()).unwrap();
+        assert_eq!(
+            meta.argument_hint.unwrap().to_text(),
+            "[pr-number] [priority]"
+        );
+        assert_eq!(meta.agent.as_deref(), Some("build"));
+
+        let content = "---\nmodel: [not, a, string]\n---\n";
+        assert!(CommandMeta::try_from(&parse_frontmatter(content).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+//! Anonymous operational counters for embedding applications.
+//!
+//! This crate never sends anything anywhere. Applications that want
+//! counts of what lookups find register a [`MetricsSink`] on a
+//! [`PathContext`] with [`PathContext::with_metrics`]; without one,
+//! recording a metric does nothing. A [`Metric`] carries a counter, a
+//! value and at most the built-in harness it is about: never paths, server
+//! names or the names of custom harnesses.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::Arc;
+//! use harness_locate::metrics::{MetricKind, MetricsCounter};
+//! use harness_locate::platform::PathContext;
+//! use harness_locate::{Harness, HarnessKind};
+//!
+//! let counter = Arc::new(MetricsCounter::new());
+//! let ctx = PathContext::new().with_metrics(Arc::clone(&counter));
+//!
+//! # #[cfg(feature = "validation")]
+//! ctx.scope(|| Harness::new(HarnessKind::OpenCode).validate_skill("---\nname: Bad Name\n---\n", "x"));
+//! # #[cfg(feature = "validation")]
+//! assert!(counter.get(MetricKind::ValidationErrors) > 0);
+//! ```
+//!
+//! [`PathContext`]: crate::platform::PathContext
+//! [`PathContext::with_metrics`]: crate::platform::PathContext::with_metrics
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::types::HarnessKind;
+
+/// What a [`Metric`] counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum MetricKind {
+    /// A harness was found installed.
+    HarnessDetected,
+    /// An MCP config file was read and parsed.
+    ConfigParsed,
+    /// MCP servers defined in a parsed config file.
+    McpServersParsed,
+    /// Validation errors found.
+    ValidationErrors,
+    /// Validation warnings found.
+    ValidationWarnings,
+    /// Plugins found by a discovery.
+    PluginsDiscovered,
+    /// Skills found by a discovery.
+    SkillsDiscovered,
+}
+
+impl MetricKind {
+    /// Returns a stable dotted name, such as `harness.detected`, for
+    /// metrics backends keyed by string.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::HarnessDetected => "harness.detected",
+            Self::ConfigParsed => "config.parsed",
+            Self::McpServersParsed => "mcp.servers_parsed",
+            Self::ValidationErrors => "validation.errors",
+            Self::ValidationWarnings => "validation.warnings",
+            Self::PluginsDiscovered => "discovery.plugins",
+            Self::SkillsDiscovered => "discovery.skills",
+        }
+    }
+}
+
+impl fmt::Display for MetricKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One increment of a counter, reported to a [`MetricsSink`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Metric {
+    /// The counter.
+    pub kind: MetricKind,
+    /// The built-in harness it is about, or `None` for custom harnesses
+    /// and metrics not tied to a harness.
+    pub harness: Option<HarnessKind>,
+    /// The amount to add, at least 1.
+    pub value: u64,
+}
+
+/// Receives [`Metric`]s as lookups run.
+///
+/// Every method has a no-op default, and the trait is implemented for
+/// closures taking a [`Metric`] and for `Arc`s of sinks. Metrics are
+/// recorded on the thread doing the lookup, so sinks should return
+/// quickly, for example by updating atomics or queueing to a channel.
+pub trait MetricsSink: Send + Sync {
+    /// Records one metric.
+    fn record(&self, metric: &Metric) {
+        let _ = metric;
+    }
+}
+
+impl<F: Fn(&Metric) + Send + Sync> MetricsSink for F {
+    fn record(&self, metric: &Metric) {
+        self(metric);
+    }
+}
+
+impl<S: MetricsSink + ?Sized> MetricsSink for Arc<S> {
+    fn record(&self, metric: &Metric) {
+        (**self).record(metric);
+    }
+}
+
+impl fmt::Debug for dyn MetricsSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MetricsSink")
+    }
+}
+
+/// A sink that adds up each counter in memory.
+///
+/// Useful for applications that flush totals periodically rather than
+/// forwarding each metric.
+#[derive(Debug, Default)]
+pub struct MetricsCounter {
+    totals: Mutex<BTreeMap<MetricKind, u64>>,
+}
+
+impl MetricsCounter {
+    /// Creates a counter with every total at zero.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the total of `kind` across all harnesses.
+    #[must_use]
+    pub fn get(&self, kind: MetricKind) -> u64 {
+        self.snapshot().get(&kind).copied().unwrap_or(0)
+    }
+
+    /// Returns the non-zero totals.
+    #[must_use]
+    pub fn snapshot(&self) -> BTreeMap<MetricKind, u64> {
+        self.totals.lock().map(|t| t.clone()).unwrap_or_default()
+    }
+
+    /// Returns the non-zero totals and resets them to zero.
+    pub fn take(&self) -> BTreeMap<MetricKind, u64> {
+        self.totals
+            .lock()
+            .map(|mut t| std::mem::take(&mut *t))
+            .unwrap_or_default()
+    }
+}
+
+impl MetricsSink for MetricsCounter {
+    fn record(&self, metric: &Metric) {
+        if let Ok(mut totals) = self.totals.lock() {
+            *totals.entry(metric.kind).or_default() += metric.value;
+        }
+    }
+}
+
+/// Records `value` of `kind` with the current context's sink, if any.
+///
+/// Zero values are not recorded. Crates building on this one, such as
+/// `skills-locate`, use this to report their own counters to the same
+/// sink.
+pub fn record(kind: MetricKind, harness: Option<HarnessKind>, value: u64) {
+    if value == 0 {
+        return;
+    }
+    if let Some(sink) = crate::platform::metrics() {
+        sink.record(&Metric {
+            kind,
+            harness,
+            value,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::harness::Harness;
+    use crate::platform::PathContext;
+    use crate::types::Scope;
+
+    #[test]
+    fn counts_parsed_configs_only_with_a_sink() {
+        let fs = MemoryFs::new().with_file(
+            "/project/.mcp.json",
+            r#"{"mcpServers": {"a": {"command": "a"}, "b": {"command": "b"}}}"#,
+        );
+        let metrics = Arc::new(Mutex::new(Vec::new()));
+        let ctx = PathContext::fake("/fake").with_fs(fs).with_metrics({
+            let metrics = Arc::clone(&metrics);
+            move |metric: &Metric| metrics.lock().unwrap().push(*metric)
+        });
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let project = Scope::Project(PathBuf::from("/project"));
+
+        ctx.scope(|| harness.read_mcp_servers(&project).unwrap());
+        // Not recorded outside the context.
+        record(MetricKind::ConfigParsed, None, 1);
+
+        let metrics = metrics.lock().unwrap();
+        let claude = Some(HarnessKind::ClaudeCode);
+        assert_eq!(
+            *metrics,
+            [
+                Metric {
+                    kind: MetricKind::ConfigParsed,
+                    harness: claude,
+                    value: 1
+                },
+                Metric {
+                    kind: MetricKind::McpServersParsed,
+                    harness: claude,
+                    value: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn counter_adds_up_and_skips_zero() {
+        let counter = Arc::new(MetricsCounter::new());
+        PathContext::new()
+            .with_metrics(Arc::clone(&counter))
+            .scope(|| {
+                record(MetricKind::SkillsDiscovered, None, 3);
+                record(MetricKind::SkillsDiscovered, None, 2);
+                record(MetricKind::PluginsDiscovered, None, 0);
+            });
+        assert_eq!(counter.get(MetricKind::SkillsDiscovered), 5);
+        assert_eq!(
+            counter.take().into_iter().collect::<Vec<_>>(),
+            [(MetricKind::SkillsDiscovered, 5)]
+        );
+        assert!(counter.snapshot().is_empty());
+        assert_eq!(MetricKind::SkillsDiscovered.to_string(), "discovery.skills");
+    }
+}
@@ -0,0 +1,242 @@
+//! Detecting MCP servers left behind in legacy config locations.
+//!
+//! Harnesses move their config files between releases: Claude Code once
+//! kept user-scoped servers in `~/.claude.json`, early OpenCode used
+//! `config.json`. Servers still defined only in the old location are
+//! silently ignored. [`detect_migration`] reads each legacy location a
+//! backend reports, lists the servers the current config lacks, and
+//! [`MigrationReport::plan`] turns them into a [`MigrationPlan`] that
+//! copies them forward.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::migration::detect_migration;
+//! use harness_locate::{Harness, HarnessKind, Scope};
+//!
+//! let harness = Harness::new(HarnessKind::ClaudeCode);
+//! let report = detect_migration(&harness, &Scope::Global)?;
+//! if report.needs_migration() {
+//!     let plan = report.plan();
+//!     for entry in &plan.entries {
+//!         println!("{} from {}", entry.name, entry.source.display());
+//!     }
+//!     plan.apply()?;
+//! }
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde_json::Value;
+
+use crate::document::McpConfigDocument;
+use crate::error::{Error, Result};
+use crate::harness::Harness;
+use crate::paths::normalize_for_compare;
+use crate::types::{ConfigResource, Scope};
+
+/// A legacy MCP config location that exists.
+#[derive(Debug, Clone)]
+pub struct LegacyConfig {
+    /// The legacy file and the key path of its servers.
+    pub resource: ConfigResource,
+    /// Names of every server defined there.
+    pub servers: Vec<String>,
+    /// Native entries of the servers the current config lacks, by name.
+    pub missing: BTreeMap<String, Value>,
+}
+
+/// What [`detect_migration`] found for one harness and scope.
+#[derive(Debug, Clone)]
+pub struct MigrationReport {
+    /// Name of the harness.
+    pub harness: String,
+    /// The config the harness reads now.
+    pub current: ConfigResource,
+    /// Legacy locations that exist, in the order the backend lists them.
+    pub legacy: Vec<LegacyConfig>,
+}
+
+impl MigrationReport {
+    /// Returns `true` if a legacy location defines a server the current
+    /// config lacks.
+    #[must_use]
+    pub fn needs_migration(&self) -> bool {
+        self.legacy.iter().any(|legacy| !legacy.missing.is_empty())
+    }
+
+    /// Returns the plan copying every missing server into the current
+    /// config.
+    ///
+    /// A server missing from several legacy locations is taken from the
+    /// first.
+    #[must_use]
+    pub fn plan(&self) -> MigrationPlan {
+        let mut entries: Vec<MigrationEntry> = Vec::new();
+        for legacy in &self.legacy {
+            for (name, value) in &legacy.missing {
+                if entries.iter().all(|entry| &entry.name != name) {
+                    entries.push(MigrationEntry {
+                        name: name.clone(),
+                        source: legacy.resource.file.clone(),
+                        value: value.clone(),
+                    });
+                }
+            }
+        }
+        MigrationPlan {
+            target: self.current.clone(),
+            entries,
+        }
+    }
+}
+
+/// A server to copy into the current config.
+#[derive(Debug, Clone)]
+pub struct MigrationEntry {
+    /// The server name.
+    pub name: String,
+    /// The legacy file it is copied from.
+    pub source: PathBuf,
+    /// The native entry, copied as is.
+    pub value: Value,
+}
+
+/// Servers to copy from legacy locations into the current config.
+#[derive(Debug, Clone)]
+pub struct MigrationPlan {
+    /// The current config.
+    pub target: ConfigResource,
+    /// The servers to add.
+    pub entries: Vec<MigrationEntry>,
+}
+
+impl MigrationPlan {
+    /// Returns `true` if there is nothing to copy.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Adds the servers to the current config and saves it, creating the
+    /// file if needed. Legacy files are left untouched.
+    ///
+    /// Servers added to the current config since the plan was made are
+    /// kept rather than overwritten. Returns the number of servers added.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current config cannot be read, parsed or
+    /// written.
+    pub fn apply(&self) -> Result<usize> {
+        let mut document = McpConfigDocument::load(&self.target)?;
+        let mut added = 0;
+        for entry in &self.entries {
+            if document.get(&entry.name).is_none() {
+                document.set(&entry.name, entry.value.clone());
+                added += 1;
+            }
+        }
+        document.save()?;
+        Ok(added)
+    }
+}
+
+/// Checks the legacy MCP config locations of `harness` for `scope`.
+///
+/// # Errors
+///
+/// Returns [`Error::NotFound`] if the harness has no MCP config for
+/// `scope`, or an error if a config file cannot be read or parsed.
+pub fn detect_migration(harness: &Harness, scope: &Scope) -> Result<MigrationReport> {
+    let current = harness.mcp(scope)?.ok_or_else(|| {
+        Error::NotFound(format!(
+            "{} has no MCP config for this scope",
+            harness.name()
+        ))
+    })?;
+    let present = McpConfigDocument::load(&current)?;
+
+    let mut legacy = Vec::new();
+    for resource in harness.backend().legacy_mcp_configs(scope)? {
+        if !resource.file_exists
+            || normalize_for_compare(&resource.file) == normalize_for_compare(&current.file)
+        {
+            continue;
+        }
+        let document = McpConfigDocument::load(&resource)?;
+        let servers = document.servers().cloned().unwrap_or_default();
+        let missing = servers
+            .iter()
+            .filter(|(name, _)| present.get(name).is_none())
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        legacy.push(LegacyConfig {
+            servers: servers.keys().cloned().collect(),
+            missing,
+            resource,
+        });
+    }
+
+    Ok(MigrationReport {
+        harness: harness.name().to_string(),
+        current,
+        legacy,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+    use crate::types::HarnessKind;
+
+    #[test]
+    fn copies_servers_only_in_the_legacy_file_forward() {
+        let fs = MemoryFs::new()
+            .with_file(
+                "/fake/home/.claude.json",
+                r#"{"numStartups": 3, "mcpServers": {
+                    "github": {"command": "old-gh"},
+                    "linear": {"type": "http", "url": "https://mcp.linear.app"}
+                }}"#,
+            )
+            .with_file(
+                "/fake/home/.claude/.mcp.json",
+                r#"{"mcpServers": {"github": {"command": "gh-mcp"}}}"#,
+            );
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+
+        let report = ctx
+            .scope(|| detect_migration(&harness, &Scope::Global))
+            .unwrap();
+        assert!(report.needs_migration());
+        assert_eq!(report.legacy.len(), 1);
+        assert_eq!(report.legacy[0].servers, ["github", "linear"]);
+        let plan = report.plan();
+        let names: Vec<_> = plan.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["linear"]);
+        assert_eq!(
+            plan.entries[0].source,
+            PathBuf::from("/fake/home/.claude.json")
+        );
+
+        assert_eq!(ctx.scope(|| plan.apply()).unwrap(), 1);
+        let servers = ctx
+            .scope(|| harness.read_mcp_servers(&Scope::Global))
+            .unwrap();
+        assert_eq!(servers.keys().collect::<Vec<_>>(), ["github", "linear"]);
+        let report = ctx
+            .scope(|| detect_migration(&harness, &Scope::Global))
+            .unwrap();
+        assert!(!report.needs_migration());
+
+        let project = Scope::Project(PathBuf::from("/project"));
+        let report = ctx.scope(|| detect_migration(&harness, &project)).unwrap();
+        assert!(report.legacy.is_empty());
+    }
+}
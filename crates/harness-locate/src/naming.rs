@@ -0,0 +1,234 @@
+//! Turning resource names into directory names and back.
+//!
+//! Skill, command and agent names end up as directory names, file names
+//! and config keys, and harnesses disagree on what they accept: OpenCode
+//! and Copilot CLI require lowercase hyphenated skill names, while every
+//! harness needs names that are valid path segments. [`slugify`] makes a
+//! name acceptable to a harness, [`unique_name`] avoids collisions with
+//! names already taken and [`display_name`] goes back from a directory
+//! name to something readable.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::HarnessKind;
+//! use harness_locate::naming::{display_name, slugify, unique_name};
+//!
+//! let slug = slugify("Deploy to Prod!", HarnessKind::OpenCode);
+//! assert_eq!(slug, "deploy-to-prod");
+//! assert_eq!(unique_name(&slug, |name| name == "deploy-to-prod"), "deploy-to-prod-2");
+//! assert_eq!(display_name(&slug), "Deploy To Prod");
+//! ```
+
+use crate::types::HarnessKind;
+use crate::validation::{NameFormat, SKILL_NAME_MAX_LEN, SkillCapabilities};
+
+/// Used when nothing of a name survives slugification.
+const FALLBACK_NAME: &str = "unnamed";
+
+/// Device names Windows reserves in every directory.
+const WINDOWS_RESERVED: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Returns the name a resource called `name` should be installed under
+/// for `kind`.
+///
+/// Harnesses requiring lowercase hyphenated skill names get a slug
+/// matching [`SKILL_NAME_REGEX`](crate::SKILL_NAME_REGEX): ASCII letters
+/// are lowercased, word boundaries including `camelCase` humps become
+/// single hyphens and the result is cut to [`SKILL_NAME_MAX_LEN`]. Other
+/// harnesses keep the name as written, made safe with [`safe_name`].
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::HarnessKind;
+/// use harness_locate::naming::slugify;
+///
+/// assert_eq!(slugify("runTests", HarnessKind::CopilotCli), "run-tests");
+/// assert_eq!(slugify("Run Tests", HarnessKind::ClaudeCode), "Run Tests");
+/// ```
+#[must_use]
+pub fn slugify(name: &str, kind: HarnessKind) -> String {
+    match SkillCapabilities::for_kind(kind).map(|caps| caps.name_format) {
+        Some(NameFormat::LowercaseHyphenated) => hyphenated_slug(name),
+        _ => safe_name(name),
+    }
+}
+
+/// Makes `name` usable as a single path segment on every platform.
+///
+/// Path separators, characters Windows forbids in file names and control
+/// characters become `-`. Leading and trailing dots and spaces are
+/// trimmed, and Windows device names such as `CON` get a `_` appended.
+/// Everything else, including case and spaces, is kept.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::naming::safe_name;
+///
+/// assert_eq!(safe_name("review: PRs/issues"), "review- PRs-issues");
+/// assert_eq!(safe_name(".."), "unnamed");
+/// ```
+#[must_use]
+pub fn safe_name(name: &str) -> String {
+    let replaced: String = name
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | '<' | '>' | ':' | '"' | '|' | '?' | '*') {
+                '-'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let trimmed = replaced.trim_matches(['.', ' ']);
+    if trimmed.is_empty() {
+        return FALLBACK_NAME.to_string();
+    }
+    let stem = trimmed.split('.').next().unwrap_or(trimmed);
+    if WINDOWS_RESERVED
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+    {
+        return format!("{stem}_{}", &trimmed[stem.len()..]);
+    }
+    trimmed.to_string()
+}
+
+fn hyphenated_slug(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    let mut prev: Option<char> = None;
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            let hump = c.is_ascii_uppercase() && prev.is_some_and(|p| p.is_ascii_lowercase());
+            let gap = prev.is_some_and(|p| !p.is_ascii_alphanumeric());
+            if (hump || gap) && !slug.is_empty() {
+                slug.push('-');
+            }
+            slug.push(c.to_ascii_lowercase());
+        }
+        prev = Some(c);
+    }
+    if slug.len() > SKILL_NAME_MAX_LEN {
+        slug.truncate(SKILL_NAME_MAX_LEN);
+        slug.truncate(slug.trim_end_matches('-').len());
+    }
+    if slug.is_empty() {
+        FALLBACK_NAME.to_string()
+    } else {
+        slug
+    }
+}
+
+/// Returns `base`, or `base` with the smallest numeric suffix (`-2`,
+/// `-3`, …) that `taken` reports free.
+///
+/// If `base` fits in [`SKILL_NAME_MAX_LEN`], it is shortened as needed to
+/// keep the suffixed name within the limit.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::naming::unique_name;
+///
+/// let existing = ["deploy", "deploy-2"];
+/// assert_eq!(unique_name("deploy", |name| existing.contains(&name)), "deploy-3");
+/// assert_eq!(unique_name("lint", |name| existing.contains(&name)), "lint");
+/// ```
+#[must_use]
+pub fn unique_name(base: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(base) {
+        return base.to_string();
+    }
+    let limit = (base.len() <= SKILL_NAME_MAX_LEN).then_some(SKILL_NAME_MAX_LEN);
+    (2..)
+        .map(|n| {
+            let suffix = format!("-{n}");
+            let mut end = limit.map_or(base.len(), |limit| {
+                base.len().min(limit.saturating_sub(suffix.len()))
+            });
+            while !base.is_char_boundary(end) {
+                end -= 1;
+            }
+            format!("{}{suffix}", base[..end].trim_end_matches('-'))
+        })
+        .find(|candidate| !taken(candidate))
+        .expect("suffixes are unbounded")
+}
+
+/// Turns a directory or file name back into a readable name.
+///
+/// Hyphens and underscores become spaces and each word is capitalized.
+/// Names that already contain spaces or uppercase letters are returned
+/// unchanged, as they were not slugified.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::naming::display_name;
+///
+/// assert_eq!(display_name("code_review-bot"), "Code Review Bot");
+/// assert_eq!(display_name("Code Review"), "Code Review");
+/// ```
+#[must_use]
+pub fn display_name(dir_name: &str) -> String {
+    if dir_name.contains(' ') || dir_name.chars().any(char::is_uppercase) {
+        return dir_name.to_string();
+    }
+    dir_name
+        .split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_follow_each_harness_name_format() {
+        let name = "  Deploy  to AWS/prod (v2) ";
+        assert_eq!(
+            slugify(name, HarnessKind::OpenCode),
+            "deploy-to-aws-prod-v2"
+        );
+        assert_eq!(
+            slugify(name, HarnessKind::ClaudeCode),
+            "Deploy  to AWS-prod (v2)"
+        );
+        assert_eq!(
+            slugify(name, HarnessKind::Goose),
+            "Deploy  to AWS-prod (v2)"
+        );
+        assert_eq!(slugify("HTTPServer", HarnessKind::OpenCode), "httpserver");
+        assert_eq!(slugify("déjà vu", HarnessKind::OpenCode), "d-j-vu");
+        assert_eq!(slugify("***", HarnessKind::OpenCode), "unnamed");
+        assert_eq!(safe_name("nul.md"), "nul_.md");
+
+        let long = slugify(&"ab-".repeat(40), HarnessKind::OpenCode);
+        assert!(long.len() <= SKILL_NAME_MAX_LEN);
+        assert!(!long.ends_with('-'));
+    }
+
+    #[test]
+    fn unique_names_stay_within_the_length_limit() {
+        let base = "a".repeat(SKILL_NAME_MAX_LEN);
+        let unique = unique_name(&base, |name| name == base);
+        assert_eq!(unique.len(), SKILL_NAME_MAX_LEN);
+        assert!(unique.ends_with("a-2"));
+
+        let long = "b".repeat(SKILL_NAME_MAX_LEN + 1);
+        assert_eq!(unique_name(&long, |name| name == long), format!("{long}-2"));
+    }
+}
@@ -0,0 +1,237 @@
+//! Streaming events from harness lookups.
+//!
+//! Tools that show results as they arrive, such as IDE extensions, can
+//! register a [`LocateObserver`] on a [`PathContext`] with
+//! [`PathContext::with_observer`]. While the context is current, lookups
+//! report each harness located, resource resolved, validation issue found
+//! and config file parsed as a [`LocateEvent`], before returning their
+//! aggregate result.
+//!
+//! # Example
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//! use harness_locate::observer::LocateEvent;
+//! use harness_locate::platform::PathContext;
+//! use harness_locate::{Harness, HarnessKind, Scope};
+//!
+//! let events = Arc::new(Mutex::new(Vec::new()));
+//! let sink = Arc::clone(&events);
+//! let ctx = PathContext::fake(std::env::temp_dir().join("harness-locate-observer-doc"))
+//!     .with_observer(move |event: &LocateEvent| sink.lock().unwrap().push(event.clone()));
+//!
+//! ctx.scope(|| Harness::new(HarnessKind::ClaudeCode).skills(&Scope::Global))?;
+//! assert!(matches!(
+//!     events.lock().unwrap()[0],
+//!     LocateEvent::ResourceResolved { .. }
+//! ));
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+//!
+//! [`PathContext`]: crate::platform::PathContext
+//! [`PathContext::with_observer`]: crate::platform::PathContext::with_observer
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::types::{ResourceKind, Scope};
+use crate::validation::ValidationIssue;
+
+/// Which resource a [`LocateEvent::ResourceResolved`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ResolvedKind {
+    /// A skills, commands, agents or plugins directory.
+    Directory(ResourceKind),
+    /// The rules directory or file.
+    Rules,
+    /// The MCP config file.
+    McpConfig,
+}
+
+/// Something a lookup found, reported to a [`LocateObserver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LocateEvent {
+    /// A harness was found installed.
+    HarnessLocated {
+        /// Name of the harness.
+        harness: String,
+    },
+    /// A resource path was resolved for a scope. The path may not exist.
+    ResourceResolved {
+        /// Name of the harness.
+        harness: String,
+        /// The resource resolved.
+        resource: ResolvedKind,
+        /// The scope it was resolved for.
+        scope: Scope,
+        /// The directory or file.
+        path: PathBuf,
+    },
+    /// Validation found a problem.
+    ValidationIssue {
+        /// Name of the harness validated against.
+        harness: String,
+        /// The problem found.
+        issue: ValidationIssue,
+    },
+    /// A config file was read and parsed.
+    ConfigParsed {
+        /// Name of the harness.
+        harness: String,
+        /// The config file.
+        path: PathBuf,
+        /// Names of the MCP servers it defines.
+        servers: Vec<String>,
+    },
+}
+
+/// Receives [`LocateEvent`]s as lookups run.
+///
+/// Implemented for closures taking a [`LocateEvent`]. Events are delivered
+/// on the thread doing the lookup, so observers should return quickly.
+pub trait LocateObserver: Send + Sync {
+    /// Handles one event.
+    fn on_event(&self, event: &LocateEvent);
+}
+
+impl<F: Fn(&LocateEvent) + Send + Sync> LocateObserver for F {
+    fn on_event(&self, event: &LocateEvent) {
+        self(event);
+    }
+}
+
+impl fmt::Debug for dyn LocateObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LocateObserver")
+    }
+}
+
+/// Reports the event built by `event` to the current context's observer,
+/// if any. The event is only built when someone is listening.
+pub(crate) fn emit(event: impl FnOnce() -> LocateEvent) {
+    if let Some(observer) = crate::platform::observer() {
+        observer.on_event(&event());
+    }
+}
+
+/// Reports each of `issues` for `harness`, counts them in the current
+/// metrics sink, and returns them.
+#[cfg(feature = "validation")]
+pub(crate) fn emit_issues(
+    harness: &crate::harness::Harness,
+    issues: Vec<ValidationIssue>,
+) -> Vec<ValidationIssue> {
+    use crate::metrics::{MetricKind, record};
+    use crate::validation::Severity;
+
+    if let Some(observer) = crate::platform::observer() {
+        for issue in &issues {
+            observer.on_event(&LocateEvent::ValidationIssue {
+                harness: harness.name().to_string(),
+                issue: issue.clone(),
+            });
+        }
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity == Severity::Error)
+        .count();
+    record(MetricKind::ValidationErrors, harness.kind(), errors as u64);
+    record(
+        MetricKind::ValidationWarnings,
+        harness.kind(),
+        (issues.len() - errors) as u64,
+    );
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::harness::Harness;
+    use crate::platform::PathContext;
+    use crate::types::HarnessKind;
+
+    #[test]
+    fn reports_lookups_to_the_context_observer() {
+        let fs = MemoryFs::new().with_file(
+            "/project/.mcp.json",
+            r#"{"mcpServers": {"github": {"command": "gh-mcp"}}}"#,
+        );
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let ctx = PathContext::fake("/fake").with_fs(fs).with_observer({
+            let events = Arc::clone(&events);
+            move |event: &LocateEvent| events.lock().unwrap().push(event.clone())
+        });
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let project = Scope::Project(PathBuf::from("/project"));
+
+        ctx.scope(|| {
+            harness.skills(&project).unwrap();
+            harness.read_mcp_servers(&project).unwrap();
+        });
+        // Not reported outside the context.
+        harness.skills(&project).unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(
+            events[0],
+            LocateEvent::ResourceResolved {
+                harness: "Claude Code".into(),
+                resource: ResolvedKind::Directory(ResourceKind::Skills),
+                scope: project.clone(),
+                path: PathBuf::from("/project/.claude/skills"),
+            }
+        );
+        assert!(matches!(
+            &events[1],
+            LocateEvent::ResourceResolved {
+                resource: ResolvedKind::McpConfig,
+                ..
+            }
+        ));
+        assert_eq!(
+            events[2],
+            LocateEvent::ConfigParsed {
+                harness: "Claude Code".into(),
+                path: PathBuf::from("/project/.mcp.json"),
+                servers: vec!["github".into()],
+            }
+        );
+        assert_eq!(events.len(), 3);
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn reports_validation_issues() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let ctx = PathContext::new().with_observer({
+            let events = Arc::clone(&events);
+            move |event: &LocateEvent| events.lock().unwrap().push(event.clone())
+        });
+        let issues = ctx.scope(|| {
+            Harness::new(HarnessKind::OpenCode)
+                .validate_skill("---\nname: Bad Name\n---\n", "deploy")
+        });
+
+        let reported: Vec<_> = events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|event| match event {
+                LocateEvent::ValidationIssue { harness, issue } => {
+                    assert_eq!(harness, "OpenCode");
+                    Some(issue.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        assert!(!issues.is_empty());
+        assert_eq!(reported, issues);
+    }
+}
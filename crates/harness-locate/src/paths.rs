@@ -0,0 +1,166 @@
+//! Comparing and displaying paths across platforms.
+//!
+//! Paths from configs and from this crate's resolvers can name the same
+//! file in different spellings: `~/.claude` and `/home/me/.claude`,
+//! `C:\Users\Me` and `c:\users\me`, or a Windows verbatim
+//! `\\?\C:\Users\Me`. [`expand_tilde`] resolves the home shorthand,
+//! [`normalize_for_compare`] reduces a path to one spelling for equality
+//! checks, and [`display_relative_to_home`] shortens a path for output.
+//!
+//! Each honors the [`PathContext`](crate::platform::PathContext) entered
+//! on the current thread and never touches the filesystem.
+//!
+//! # Example
+//!
+//! ```
+//! use std::path::Path;
+//! use harness_locate::paths::{display_relative_to_home, expand_tilde, normalize_for_compare};
+//! use harness_locate::platform::PathContext;
+//!
+//! PathContext::fake("/fake").scope(|| {
+//!     let skills = expand_tilde("~/.claude/skills");
+//!     assert_eq!(skills, Path::new("/fake/home/.claude/skills"));
+//!     assert_eq!(
+//!         normalize_for_compare("/fake/home/.claude/./skills/"),
+//!         normalize_for_compare("~/.claude/skills"),
+//!     );
+//!     assert_eq!(display_relative_to_home(&skills), "~/.claude/skills");
+//! });
+//! ```
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::platform::{home_dir, strip_verbatim_prefix};
+
+/// Whether the platform's default filesystems ignore case.
+const CASE_INSENSITIVE: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+
+/// Replaces a leading `~` in `path` with the home directory.
+///
+/// Only `~` alone or followed by a separator is expanded; `~alice/x` and
+/// `a/~` are returned unchanged, as is every path when the home directory
+/// is unknown.
+pub fn expand_tilde(path: impl AsRef<Path>) -> PathBuf {
+    let path = path.as_ref();
+    let Some(s) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    let rest = match s.strip_prefix('~') {
+        Some("") => "",
+        Some(rest) if rest.starts_with(std::path::is_separator) => &rest[1..],
+        _ => return path.to_path_buf(),
+    };
+    match home_dir() {
+        Ok(home) if rest.is_empty() => home,
+        Ok(home) => home.join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Returns a form of `path` that equals the form of every other spelling
+/// of the same location.
+///
+/// The path is [tilde-expanded](expand_tilde), stripped of the Windows
+/// verbatim prefix, and `.` and `..` components and trailing separators
+/// are removed lexically, without resolving symlinks. On macOS and Windows,
+/// whose filesystems ignore case by default, it is also lowercased.
+///
+/// The result is for comparison only and may not name an existing file.
+#[must_use]
+pub fn normalize_for_compare(path: impl AsRef<Path>) -> PathBuf {
+    let path = clean(&strip_verbatim_prefix(&expand_tilde(path)));
+    if CASE_INSENSITIVE {
+        PathBuf::from(path.to_string_lossy().to_lowercase())
+    } else {
+        path
+    }
+}
+
+/// Returns `path` with the home directory written as `~`, or the whole
+/// path if it is outside the home directory.
+///
+/// Separators after `~` are `/` on every platform except Windows. The
+/// home prefix is matched as by [`normalize_for_compare`].
+#[must_use]
+pub fn display_relative_to_home(path: &Path) -> String {
+    let Ok(home) = home_dir() else {
+        return path.display().to_string();
+    };
+    let cleaned = clean(&strip_verbatim_prefix(path));
+    let home_len = normalize_for_compare(&home).components().count();
+    if !normalize_for_compare(&cleaned).starts_with(normalize_for_compare(&home)) {
+        return path.display().to_string();
+    }
+    let rest: PathBuf = cleaned.components().skip(home_len).collect();
+    if rest.as_os_str().is_empty() {
+        return "~".to_string();
+    }
+    let sep = if cfg!(windows) { '\\' } else { '/' };
+    format!("~{sep}{}", rest.display())
+}
+
+/// Removes `.` components and resolves `..` lexically.
+fn clean(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(
+                    out.components().next_back(),
+                    None | Some(Component::ParentDir)
+                ) {
+                    out.push("..");
+                } else {
+                    out.pop();
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::platform::PathContext;
+
+    #[test]
+    fn expands_only_a_leading_home_tilde() {
+        PathContext::fake("/fake").scope(|| {
+            assert_eq!(expand_tilde("~"), Path::new("/fake/home"));
+            assert_eq!(expand_tilde("~/a/b"), Path::new("/fake/home/a/b"));
+            assert_eq!(expand_tilde("~alice/a"), Path::new("~alice/a"));
+            assert_eq!(expand_tilde("a/~/b"), Path::new("a/~/b"));
+        });
+    }
+
+    #[test]
+    fn normalizes_dots_and_trailing_separators() {
+        assert_eq!(
+            normalize_for_compare("/a/./b/../c/"),
+            normalize_for_compare("/a/c")
+        );
+        assert_eq!(clean(Path::new("../a/../../b")), Path::new("../../b"));
+        assert_eq!(
+            normalize_for_compare("/A/b") == normalize_for_compare("/a/b"),
+            CASE_INSENSITIVE
+        );
+    }
+
+    #[test]
+    fn displays_home_as_tilde() {
+        PathContext::fake("/fake").scope(|| {
+            assert_eq!(display_relative_to_home(Path::new("/fake/home")), "~");
+            assert_eq!(
+                display_relative_to_home(Path::new("/fake/home/x/../.claude")),
+                "~/.claude"
+            );
+            assert_eq!(
+                display_relative_to_home(Path::new("/fake/homework")),
+                "/fake/homework"
+            );
+        });
+    }
+}
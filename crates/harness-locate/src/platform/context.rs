@@ -0,0 +1,272 @@
+//! Injectable base directories for path resolution.
+//!
+//! By default, every resolver in this crate reads the real home, config,
+//! and data directories along with the live process environment. A
+//! [`PathContext`] overrides any of those for the current thread, which
+//! keeps tests independent of the machine they run on.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::fs::Fs;
+use crate::metrics::MetricsSink;
+use crate::observer::LocateObserver;
+
+thread_local! {
+    static CURRENT: RefCell<Option<PathContext>> = const { RefCell::new(None) };
+}
+
+/// Overrides for the base directories and environment used during path resolution.
+///
+/// Unset fields fall back to the real system values. A context only takes
+/// effect while it is entered with [`PathContext::enter`] or
+/// [`PathContext::scope`], and only on the thread that entered it.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::platform::{self, PathContext};
+/// use harness_locate::{Harness, HarnessKind};
+///
+/// let root = std::env::temp_dir().join("harness-locate-doc");
+/// let ctx = PathContext::fake(&root);
+///
+/// ctx.scope(|| {
+///     assert_eq!(platform::home_dir().unwrap(), root.join("home"));
+///     assert!(!Harness::new(HarnessKind::Goose).is_installed());
+/// });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PathContext {
+    home: Option<PathBuf>,
+    config: Option<PathBuf>,
+    data: Option<PathBuf>,
+    env: Option<HashMap<String, String>>,
+    fs: Option<Arc<dyn Fs>>,
+    observer: Option<Arc<dyn LocateObserver>>,
+    metrics: Option<Arc<dyn MetricsSink>>,
+}
+
+impl PathContext {
+    /// Creates a context with no overrides.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a fully isolated context rooted at `root`.
+    ///
+    /// The home, config, and data directories become `root/home`,
+    /// `root/config`, and `root/data`, and the environment snapshot is
+    /// empty, so no binaries are found on `PATH`. Directories are not
+    /// created.
+    #[must_use]
+    pub fn fake(root: impl AsRef<Path>) -> Self {
+        let root = root.as_ref();
+        Self {
+            home: Some(root.join("home")),
+            config: Some(root.join("config")),
+            data: Some(root.join("data")),
+            env: Some(HashMap::new()),
+            fs: None,
+            observer: None,
+            metrics: None,
+        }
+    }
+
+    /// Overrides the home directory.
+    #[must_use]
+    pub fn with_home(mut self, path: impl Into<PathBuf>) -> Self {
+        self.home = Some(path.into());
+        self
+    }
+
+    /// Overrides the config directory returned by [`super::config_dir`].
+    #[must_use]
+    pub fn with_config_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config = Some(path.into());
+        self
+    }
+
+    /// Overrides the data directory returned by [`super::data_dir`].
+    #[must_use]
+    pub fn with_data_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.data = Some(path.into());
+        self
+    }
+
+    /// Replaces the process environment with a fixed snapshot.
+    ///
+    /// Variables missing from the snapshot are treated as unset.
+    #[must_use]
+    pub fn with_env<K, V>(mut self, vars: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.env = Some(
+            vars.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+        self
+    }
+
+    /// Sets a single variable, starting from an empty snapshot if none exists.
+    #[must_use]
+    pub fn with_var(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env
+            .get_or_insert_with(HashMap::new)
+            .insert(key.into(), value.into());
+        self
+    }
+
+    /// Replaces the filesystem used to check and read harness paths.
+    ///
+    /// Combine with [`PathContext::fake`] and
+    /// [`MemoryFs`](crate::fs::MemoryFs) (behind the `test-util` feature)
+    /// to test without touching the disk.
+    #[must_use]
+    pub fn with_fs(mut self, fs: impl Fs + 'static) -> Self {
+        self.fs = Some(Arc::new(fs));
+        self
+    }
+
+    /// Reports lookups made in this context to `observer` as
+    /// [`LocateEvent`](crate::observer::LocateEvent)s.
+    ///
+    /// See the [`observer`](crate::observer) module.
+    #[must_use]
+    pub fn with_observer(mut self, observer: impl LocateObserver + 'static) -> Self {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Records counters for lookups made in this context with `sink`.
+    ///
+    /// Without a sink nothing is recorded. See the
+    /// [`metrics`](crate::metrics) module.
+    #[must_use]
+    pub fn with_metrics(mut self, sink: impl MetricsSink + 'static) -> Self {
+        self.metrics = Some(Arc::new(sink));
+        self
+    }
+
+    /// Makes this context current for the calling thread.
+    ///
+    /// The previous context is restored when the returned guard is dropped.
+    #[must_use = "the context is only active while the guard is alive"]
+    pub fn enter(&self) -> PathContextGuard {
+        let previous = CURRENT.with(|c| c.replace(Some(self.clone())));
+        PathContextGuard { previous }
+    }
+
+    /// Runs `f` with this context current for the calling thread.
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        let _guard = self.enter();
+        f()
+    }
+}
+
+/// Restores the previous [`PathContext`] when dropped.
+#[derive(Debug)]
+pub struct PathContextGuard {
+    previous: Option<PathContext>,
+}
+
+impl Drop for PathContextGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT.with(|c| *c.borrow_mut() = previous);
+    }
+}
+
+fn with_current<T>(f: impl FnOnce(&PathContext) -> Option<T>) -> Option<T> {
+    CURRENT.with(|c| c.borrow().as_ref().and_then(f))
+}
+
+pub(crate) fn home_override() -> Option<PathBuf> {
+    with_current(|ctx| ctx.home.clone())
+}
+
+pub(crate) fn config_override() -> Option<PathBuf> {
+    with_current(|ctx| ctx.config.clone())
+}
+
+pub(crate) fn data_override() -> Option<PathBuf> {
+    with_current(|ctx| ctx.data.clone())
+}
+
+pub(crate) fn fs_override() -> Option<Arc<dyn Fs>> {
+    with_current(|ctx| ctx.fs.clone())
+}
+
+pub(crate) fn observer() -> Option<Arc<dyn LocateObserver>> {
+    with_current(|ctx| ctx.observer.clone())
+}
+
+pub(crate) fn metrics() -> Option<Arc<dyn MetricsSink>> {
+    with_current(|ctx| ctx.metrics.clone())
+}
+
+/// Returns `true` if the current context replaces the process environment.
+pub(crate) fn has_env_snapshot() -> bool {
+    CURRENT.with(|c| c.borrow().as_ref().is_some_and(|ctx| ctx.env.is_some()))
+}
+
+/// Reads an environment variable through the current context.
+pub(crate) fn var(name: &str) -> Option<String> {
+    CURRENT.with(
+        |c| match c.borrow().as_ref().and_then(|ctx| ctx.env.as_ref()) {
+            Some(env) => env.get(name).cloned(),
+            None => std::env::var(name).ok(),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fake_roots_directories_under_root() {
+        let ctx = PathContext::fake("/tmp/fake");
+        ctx.scope(|| {
+            assert_eq!(home_override(), Some(PathBuf::from("/tmp/fake/home")));
+            assert_eq!(config_override(), Some(PathBuf::from("/tmp/fake/config")));
+            assert_eq!(data_override(), Some(PathBuf::from("/tmp/fake/data")));
+            assert!(has_env_snapshot());
+            assert_eq!(var("PATH"), None);
+        });
+    }
+
+    #[test]
+    fn guard_restores_previous_context() {
+        let outer = PathContext::new().with_home("/outer");
+        let inner = PathContext::new().with_home("/inner");
+
+        let _outer = outer.enter();
+        {
+            let _inner = inner.enter();
+            assert_eq!(home_override(), Some(PathBuf::from("/inner")));
+        }
+        assert_eq!(home_override(), Some(PathBuf::from("/outer")));
+    }
+
+    #[test]
+    fn no_context_means_no_overrides() {
+        assert_eq!(home_override(), None);
+        assert!(!has_env_snapshot());
+    }
+
+    #[test]
+    fn with_var_reads_from_snapshot() {
+        let ctx = PathContext::new().with_var("XDG_CONFIG_HOME", "/xdg");
+        ctx.scope(|| {
+            assert_eq!(var("XDG_CONFIG_HOME"), Some("/xdg".to_string()));
+            assert_eq!(var("HOME"), None);
+        });
+    }
+}
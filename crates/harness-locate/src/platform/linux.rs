@@ -5,7 +5,7 @@ use std::path::PathBuf;
 use crate::error::Result;
 
 fn xdg_path_if_valid(var_name: &str) -> Option<PathBuf> {
-    std::env::var(var_name).ok().and_then(|val| {
+    super::var(var_name).and_then(|val| {
         let path = PathBuf::from(&val);
         if !val.is_empty() && path.is_absolute() {
             Some(path)
@@ -24,6 +24,9 @@ fn xdg_path_if_valid(var_name: &str) -> Option<PathBuf> {
 ///
 /// Returns an error if the home directory cannot be determined.
 pub fn config_dir() -> Result<PathBuf> {
+    if let Some(path) = super::context::config_override() {
+        return Ok(path);
+    }
     if let Some(path) = xdg_path_if_valid("XDG_CONFIG_HOME") {
         return Ok(path);
     }
@@ -39,6 +42,9 @@ pub fn config_dir() -> Result<PathBuf> {
 ///
 /// Returns an error if the home directory cannot be determined.
 pub fn data_dir() -> Result<PathBuf> {
+    if let Some(path) = super::context::data_override() {
+        return Ok(path);
+    }
     if let Some(path) = xdg_path_if_valid("XDG_DATA_HOME") {
         return Ok(path);
     }
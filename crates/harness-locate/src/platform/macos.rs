@@ -13,6 +13,9 @@ use crate::error::Result;
 ///
 /// Returns an error if the home directory cannot be determined.
 pub fn config_dir() -> Result<PathBuf> {
+    if let Some(path) = super::context::config_override() {
+        return Ok(path);
+    }
     Ok(super::home_dir()?.join(".config"))
 }
 
@@ -24,9 +27,23 @@ pub fn config_dir() -> Result<PathBuf> {
 ///
 /// Returns an error if the home directory cannot be determined.
 pub fn data_dir() -> Result<PathBuf> {
+    if let Some(path) = super::context::data_override() {
+        return Ok(path);
+    }
     Ok(super::home_dir()?.join("Library/Application Support"))
 }
 
+/// Returns the user's cache directory on macOS.
+///
+/// Returns `~/Library/Caches/`.
+///
+/// # Errors
+///
+/// Returns an error if the home directory cannot be determined.
+pub fn cache_dir() -> Result<PathBuf> {
+    Ok(super::home_dir()?.join("Library/Caches"))
+}
+
 /// Returns the Application Support directory on macOS.
 ///
 /// This is `~/Library/Application Support/`, used by native macOS applications.
@@ -2,11 +2,18 @@
 //!
 //! This module provides functions to resolve base configuration directories
 //! on each supported platform (macOS, Linux, Windows).
+//!
+//! All resolvers honor the [`PathContext`] entered on the current thread,
+//! if any.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::error::{Error, Result};
 
+mod context;
+pub use context::{PathContext, PathContextGuard};
+pub(crate) use context::{fs_override, has_env_snapshot, metrics, observer, var};
+
 #[cfg(target_os = "macos")]
 mod macos;
 #[cfg(target_os = "macos")]
@@ -29,6 +36,9 @@ pub use windows::*;
 /// Returns [`Error::NotFound`] if the home directory cannot be determined.
 #[cfg(any(target_os = "macos", target_os = "linux", target_os = "windows"))]
 pub fn home_dir() -> Result<PathBuf> {
+    if let Some(path) = context::home_override() {
+        return Ok(path);
+    }
     home::home_dir().ok_or_else(|| Error::NotFound("home directory".into()))
 }
 
@@ -72,6 +82,98 @@ pub fn data_dir() -> Result<PathBuf> {
     Err(Error::UnsupportedPlatform)
 }
 
+/// Returns the user's cache directory.
+///
+/// Platform-specific behavior:
+/// - **macOS**: `~/Library/Caches/`
+/// - **Linux**: `$XDG_CACHE_HOME` or `~/.cache/`
+/// - **Windows**: `%LOCALAPPDATA%`
+///
+/// # Errors
+///
+/// Returns an error if the cache directory cannot be determined.
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+pub fn cache_dir() -> Result<PathBuf> {
+    Err(Error::UnsupportedPlatform)
+}
+
+/// Returns the XDG base directory named by `var`, or `fallback` under the
+/// home directory, on every platform.
+///
+/// Node-based harnesses resolve their data, state and cache directories
+/// this way even on macOS and Windows. Relative values of `var` are
+/// ignored, as the XDG specification requires.
+pub(crate) fn xdg_dir(var: &str, fallback: &str) -> Result<PathBuf> {
+    if let Some(dir) = self::var(var) {
+        let path = PathBuf::from(dir);
+        if path.is_absolute() {
+            return Ok(path);
+        }
+    }
+    Ok(home_dir()?.join(fallback))
+}
+
+/// Returns the `~/.config` style directory used by XDG-minded CLI tools.
+///
+/// Platform-specific behavior:
+/// - **macOS**: `~/.config/`
+/// - **Linux**: `$XDG_CONFIG_HOME` or `~/.config/`
+/// - **Windows**: `%USERPROFILE%\.config\` (not `%APPDATA%`)
+///
+/// Node-based harnesses (OpenCode, AMP Code) resolve their configuration
+/// through XDG helpers that fall back to the home directory on Windows,
+/// so [`config_dir`] would point them at the wrong place there.
+///
+/// # Errors
+///
+/// Returns an error if the home or config directory cannot be determined.
+pub fn dot_config_dir() -> Result<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        Ok(home_dir()?.join(".config"))
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        config_dir()
+    }
+}
+
+/// Strips the Windows extended-length prefix (`\\?\`) from a path.
+///
+/// Canonicalized paths on Windows come back in verbatim form, which most
+/// tools and users do not expect. Verbatim UNC paths (`\\?\UNC\server\share`)
+/// are converted back to `\\server\share`. Any other path is returned unchanged,
+/// so this is safe to call on every platform.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::{Path, PathBuf};
+/// use harness_locate::platform::strip_verbatim_prefix;
+///
+/// assert_eq!(
+///     strip_verbatim_prefix(Path::new(r"\\?\C:\Users\me\claude.cmd")),
+///     PathBuf::from(r"C:\Users\me\claude.cmd")
+/// );
+/// assert_eq!(
+///     strip_verbatim_prefix(Path::new("/usr/bin/claude")),
+///     PathBuf::from("/usr/bin/claude")
+/// );
+/// ```
+#[must_use]
+pub fn strip_verbatim_prefix(path: &Path) -> PathBuf {
+    let Some(s) = path.to_str() else {
+        return path.to_path_buf();
+    };
+    if let Some(rest) = s.strip_prefix(r"\\?\UNC\") {
+        PathBuf::from(format!(r"\\{rest}"))
+    } else if let Some(rest) = s.strip_prefix(r"\\?\") {
+        PathBuf::from(rest)
+    } else {
+        path.to_path_buf()
+    }
+}
+
 #[cfg(all(test, any(target_os = "linux", target_os = "windows")))]
 pub(crate) mod test_utils {
     use std::sync::Mutex;
@@ -167,4 +269,37 @@ mod tests {
             "data_dir should return an absolute path"
         );
     }
+
+    #[test]
+    fn dot_config_dir_is_absolute() {
+        #[cfg(any(target_os = "linux", target_os = "windows"))]
+        let _env = EnvGuard::new();
+
+        let path = dot_config_dir().unwrap();
+        assert!(path.is_absolute());
+    }
+
+    #[test]
+    fn strip_verbatim_prefix_handles_drive_paths() {
+        assert_eq!(
+            strip_verbatim_prefix(Path::new(r"\\?\C:\tools\amp.exe")),
+            PathBuf::from(r"C:\tools\amp.exe")
+        );
+    }
+
+    #[test]
+    fn strip_verbatim_prefix_handles_unc_paths() {
+        assert_eq!(
+            strip_verbatim_prefix(Path::new(r"\\?\UNC\server\share\bin\goose.exe")),
+            PathBuf::from(r"\\server\share\bin\goose.exe")
+        );
+    }
+
+    #[test]
+    fn strip_verbatim_prefix_leaves_plain_paths_alone() {
+        assert_eq!(
+            strip_verbatim_prefix(Path::new("/usr/local/bin/opencode")),
+            PathBuf::from("/usr/local/bin/opencode")
+        );
+    }
 }
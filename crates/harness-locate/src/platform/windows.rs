@@ -12,9 +12,12 @@ use crate::error::{Error, Result};
 ///
 /// Returns an error if the `APPDATA` environment variable is not set.
 pub fn config_dir() -> Result<PathBuf> {
-    std::env::var("APPDATA")
+    if let Some(path) = super::context::config_override() {
+        return Ok(path);
+    }
+    super::var("APPDATA")
         .map(PathBuf::from)
-        .map_err(Error::from)
+        .ok_or(Error::EnvVar(std::env::VarError::NotPresent))
 }
 
 /// Returns the user's data directory on Windows.
@@ -25,9 +28,23 @@ pub fn config_dir() -> Result<PathBuf> {
 ///
 /// Returns an error if the `LOCALAPPDATA` environment variable is not set.
 pub fn data_dir() -> Result<PathBuf> {
-    std::env::var("LOCALAPPDATA")
+    if let Some(path) = super::context::data_override() {
+        return Ok(path);
+    }
+    super::var("LOCALAPPDATA")
         .map(PathBuf::from)
-        .map_err(Error::from)
+        .ok_or(Error::EnvVar(std::env::VarError::NotPresent))
+}
+
+/// Returns the user's cache directory on Windows.
+///
+/// Returns `%LOCALAPPDATA%`, which Windows apps also use for caches.
+///
+/// # Errors
+///
+/// Returns an error if the `LOCALAPPDATA` environment variable is not set.
+pub fn cache_dir() -> Result<PathBuf> {
+    data_dir()
 }
 
 /// Returns the roaming app data directory on Windows.
@@ -0,0 +1,621 @@
+//! Claude Code plugin manifests and installed plugins.
+//!
+//! A Claude Code plugin is a directory with a `.claude-plugin/plugin.json`
+//! manifest ([`ClaudePluginManifest`]) next to its components: commands in
+//! `commands/`, agents in `agents/`, skills in `skills/`, hooks in
+//! `hooks/hooks.json` and MCP servers in `.mcp.json`. The manifest can
+//! list extra command, agent and skill paths, and inline hooks and MCP
+//! servers.
+//!
+//! [`installed_plugins`] lists the plugins Claude Code loads for a scope,
+//! from `plugins/installed_plugins.json` (marketplace installs) and from
+//! plugin directories placed directly in the plugins directory, with the
+//! component files each one bundles.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::plugin::parse_plugin_manifest;
+//!
+//! let manifest = parse_plugin_manifest(r#"{
+//!     "name": "release-tools",
+//!     "version": "1.2.0",
+//!     "commands": ["./extra/ship.md"],
+//!     "mcpServers": "./servers.json"
+//! }"#)?;
+//! assert_eq!(manifest.commands.paths(), ["./extra/ship.md"]);
+//! assert!(manifest.validate().is_empty());
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::types::Scope;
+use crate::validation::{
+    CODE_PLUGIN_NAME_FORMAT, CODE_PLUGIN_PATH_INVALID, CODE_PLUGIN_PATH_MISSING,
+    CODE_PLUGIN_VERSION_FORMAT, ValidationIssue,
+};
+
+/// Directory holding the manifest, relative to the plugin root.
+pub const MANIFEST_DIR: &str = ".claude-plugin";
+
+/// The manifest file name inside [`MANIFEST_DIR`].
+pub const MANIFEST_FILE: &str = "plugin.json";
+
+/// Directories in the plugins directory that hold Claude Code's own state
+/// rather than plugins.
+const STATE_DIRS: &[&str] = &["cache", "marketplaces", "repos"];
+
+/// The author of a plugin.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginAuthor {
+    /// The author's name.
+    pub name: String,
+    /// Contact email.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// Home page of the author.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// Component paths in a manifest, written as one path or a list.
+///
+/// Paths are relative to the plugin root and start with `./`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PathList {
+    /// No extra paths.
+    #[default]
+    None,
+    /// A single file or directory.
+    One(String),
+    /// Several files or directories.
+    Many(Vec<String>),
+}
+
+impl PathList {
+    /// Returns the paths in order.
+    #[must_use]
+    pub fn paths(&self) -> Vec<&str> {
+        match self {
+            Self::None => Vec::new(),
+            Self::One(path) => vec![path.as_str()],
+            Self::Many(paths) => paths.iter().map(String::as_str).collect(),
+        }
+    }
+
+    /// Returns `true` if no paths are listed.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths().is_empty()
+    }
+}
+
+/// Hooks or MCP servers, either in a separate file or inline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ComponentConfig {
+    /// Path of a JSON file relative to the plugin root.
+    Path(String),
+    /// The configuration itself, as it would appear in the file.
+    Inline(serde_json::Map<String, serde_json::Value>),
+}
+
+/// Where a marketplace fetches a plugin from, as listed in a
+/// `marketplace.json` plugin entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ClaudePluginSource {
+    /// A directory relative to the marketplace root, such as
+    /// `./plugins/release-tools`.
+    Path(String),
+    /// A repository elsewhere.
+    Remote(RemotePluginSource),
+}
+
+/// A plugin repository outside the marketplace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum RemotePluginSource {
+    /// A GitHub repository.
+    Github {
+        /// The repository as `owner/name`.
+        repo: String,
+        /// Branch, tag or commit to check out.
+        #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+    },
+    /// Any git URL.
+    Url {
+        /// The clone URL.
+        url: String,
+        /// Branch, tag or commit to check out.
+        #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+        git_ref: Option<String>,
+    },
+}
+
+/// A Claude Code `.claude-plugin/plugin.json` manifest.
+///
+/// Also reads plugin entries of a `marketplace.json`, which add
+/// [`source`](Self::source).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudePluginManifest {
+    /// The plugin name, in kebab-case (required).
+    pub name: String,
+    /// Semantic version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// What the plugin does.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Who maintains it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<PluginAuthor>,
+    /// Documentation page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// Source repository URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// License identifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Keywords for marketplace search.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// Command files or directories besides `commands/`.
+    #[serde(default, skip_serializing_if = "PathList::is_empty")]
+    pub commands: PathList,
+    /// Agent files or directories besides `agents/`.
+    #[serde(default, skip_serializing_if = "PathList::is_empty")]
+    pub agents: PathList,
+    /// Skill directories, or directories of skills, besides `skills/`.
+    #[serde(default, skip_serializing_if = "PathList::is_empty")]
+    pub skills: PathList,
+    /// Hooks, replacing `hooks/hooks.json`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hooks: Option<ComponentConfig>,
+    /// MCP servers, replacing `.mcp.json`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_servers: Option<ComponentConfig>,
+    /// Where a marketplace fetches the plugin from; only in
+    /// `marketplace.json` entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ClaudePluginSource>,
+    /// Fields not captured above, sorted by key.
+    #[serde(flatten)]
+    pub metadata: BTreeMap<String, serde_json::Value>,
+}
+
+/// Parses a `plugin.json` manifest.
+///
+/// # Errors
+///
+/// Returns [`Error::JsonParse`] if the content is not JSON or lacks a
+/// `name`.
+pub fn parse_plugin_manifest(content: &str) -> Result<ClaudePluginManifest> {
+    Ok(serde_json::from_str(content)?)
+}
+
+impl ClaudePluginManifest {
+    /// Creates a manifest with only a name, as Claude Code assumes for
+    /// plugins without `plugin.json`.
+    #[must_use]
+    pub fn named(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            description: None,
+            author: None,
+            homepage: None,
+            repository: None,
+            license: None,
+            keywords: Vec::new(),
+            commands: PathList::None,
+            agents: PathList::None,
+            skills: PathList::None,
+            hooks: None,
+            mcp_servers: None,
+            source: None,
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Checks the manifest on its own: a kebab-case name, a semantic
+    /// version, and component paths that start with `./` and stay inside
+    /// the plugin.
+    #[must_use]
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        if !is_kebab_case(&self.name) {
+            issues.push(ValidationIssue::error(
+                "name",
+                format!("plugin name '{}' must be kebab-case", self.name),
+                Some(CODE_PLUGIN_NAME_FORMAT),
+            ));
+        }
+        if let Some(version) = &self.version
+            && !is_semver(version)
+        {
+            issues.push(ValidationIssue::warning(
+                "version",
+                format!("version '{version}' is not a semantic version such as 1.0.0"),
+                Some(CODE_PLUGIN_VERSION_FORMAT),
+            ));
+        }
+        for (field, path) in self.component_paths() {
+            if !path.starts_with("./") || Path::new(path).components().any(is_parent) {
+                issues.push(ValidationIssue::error(
+                    field,
+                    format!("'{path}' must be relative to the plugin root and start with ./"),
+                    Some(CODE_PLUGIN_PATH_INVALID),
+                ));
+            }
+        }
+        issues
+    }
+
+    /// Runs [`validate`](Self::validate) and also reports component paths
+    /// missing from the plugin at `root`.
+    #[must_use]
+    pub fn validate_at(&self, root: &Path) -> Vec<ValidationIssue> {
+        let mut issues = self.validate();
+        let fs = crate::fs::current();
+        for (field, path) in self.component_paths() {
+            if !fs.exists(&root.join(path)) {
+                issues.push(ValidationIssue::warning(
+                    field,
+                    format!("'{path}' does not exist"),
+                    Some(CODE_PLUGIN_PATH_MISSING),
+                ));
+            }
+        }
+        issues
+    }
+
+    /// Returns every component path with the field it comes from.
+    fn component_paths(&self) -> Vec<(&'static str, &str)> {
+        let mut paths = Vec::new();
+        for (field, list) in [
+            ("commands", &self.commands),
+            ("agents", &self.agents),
+            ("skills", &self.skills),
+        ] {
+            paths.extend(list.paths().into_iter().map(|path| (field, path)));
+        }
+        for (field, config) in [("hooks", &self.hooks), ("mcpServers", &self.mcp_servers)] {
+            if let Some(ComponentConfig::Path(path)) = config {
+                paths.push((field, path.as_str()));
+            }
+        }
+        paths
+    }
+}
+
+fn is_parent(component: std::path::Component<'_>) -> bool {
+    matches!(component, std::path::Component::ParentDir)
+}
+
+fn is_kebab_case(name: &str) -> bool {
+    !name.is_empty()
+        && name.split('-').all(|part| {
+            !part.is_empty()
+                && part
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        })
+}
+
+fn is_semver(version: &str) -> bool {
+    let core = version.split(['-', '+']).next().unwrap_or_default();
+    let parts: Vec<&str> = core.split('.').collect();
+    parts.len() == 3
+        && parts
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// A plugin Claude Code loads, with the components it bundles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstalledPlugin {
+    /// The manifest, or one with just the directory name if the plugin
+    /// has no `plugin.json`.
+    pub manifest: ClaudePluginManifest,
+    /// The marketplace the plugin was installed from, `None` for plugins
+    /// placed in the plugins directory by hand.
+    pub marketplace: Option<String>,
+    /// The plugin root.
+    pub path: PathBuf,
+    /// Command files.
+    pub commands: Vec<PathBuf>,
+    /// Agent files.
+    pub agents: Vec<PathBuf>,
+    /// Skill directories, each with a `SKILL.md`.
+    pub skills: Vec<PathBuf>,
+}
+
+impl InstalledPlugin {
+    /// Reads the plugin at `root` and lists its components.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `plugin.json` exists but cannot be read or
+    /// parsed, or a component directory cannot be listed.
+    pub fn load(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        let fs = crate::fs::current();
+        let manifest_path = root.join(MANIFEST_DIR).join(MANIFEST_FILE);
+        let manifest = if fs.exists(&manifest_path) {
+            parse_plugin_manifest(&crate::fs::read_text(&manifest_path)?.text)?
+        } else {
+            let name = root
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .ok_or_else(|| Error::InvalidPath(root.clone()))?;
+            ClaudePluginManifest::named(name)
+        };
+
+        let commands = markdown_files(&root, "commands", &manifest.commands)?;
+        let agents = markdown_files(&root, "agents", &manifest.agents)?;
+        let skills = skill_dirs(&root, &manifest.skills)?;
+        Ok(Self {
+            manifest,
+            marketplace: None,
+            path: root,
+            commands,
+            agents,
+            skills,
+        })
+    }
+
+    /// Returns the plugin name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.manifest.name
+    }
+}
+
+/// Returns the `.md` files in the default directory and the manifest
+/// paths, which may be files or directories.
+fn markdown_files(root: &Path, default: &str, extra: &PathList) -> Result<Vec<PathBuf>> {
+    let fs = crate::fs::current();
+    let mut files = Vec::new();
+    let sources = std::iter::once(default).chain(extra.paths());
+    for source in sources {
+        let path = clean_join(root, source);
+        if fs.is_dir(&path) {
+            files.extend(
+                fs.read_dir(&path)?
+                    .into_iter()
+                    .filter(|file| file.extension().is_some_and(|ext| ext == "md")),
+            );
+        } else if fs.exists(&path) {
+            files.push(path);
+        }
+    }
+    files.dedup();
+    Ok(files)
+}
+
+/// Returns the skill directories in `skills/` and the manifest paths, each
+/// of which is a skill or a directory of skills.
+fn skill_dirs(root: &Path, extra: &PathList) -> Result<Vec<PathBuf>> {
+    let fs = crate::fs::current();
+    let mut dirs = Vec::new();
+    for source in std::iter::once("skills").chain(extra.paths()) {
+        let path = clean_join(root, source);
+        if fs.exists(&path.join("SKILL.md")) {
+            dirs.push(path);
+        } else if fs.is_dir(&path) {
+            dirs.extend(
+                fs.read_dir(&path)?
+                    .into_iter()
+                    .filter(|dir| fs.exists(&dir.join("SKILL.md"))),
+            );
+        }
+    }
+    dirs.dedup();
+    Ok(dirs)
+}
+
+fn clean_join(root: &Path, relative: &str) -> PathBuf {
+    root.join(relative.strip_prefix("./").unwrap_or(relative))
+}
+
+/// Lists the Claude Code plugins installed in `scope`, sorted by name.
+///
+/// Plugins come from `plugins/installed_plugins.json`, in both its
+/// original form and the newer one with a list of installs per plugin,
+/// and from directories in the plugins directory with a
+/// `.claude-plugin/` marker. Registered installs whose directory is gone
+/// are left out.
+///
+/// # Errors
+///
+/// Returns an error if the plugins directory cannot be determined, or a
+/// registry or manifest cannot be read or parsed.
+pub fn installed_plugins(scope: &Scope) -> Result<Vec<InstalledPlugin>> {
+    let Some(dir) = crate::harness::claude_code::plugins_dir(scope) else {
+        return Err(Error::NotFound("plugins directory".into()));
+    };
+    let fs = crate::fs::current();
+    let mut plugins: Vec<InstalledPlugin> = Vec::new();
+
+    let registry = dir.join("installed_plugins.json");
+    if fs.exists(&registry) {
+        let registry: serde_json::Value =
+            serde_json::from_str(&crate::fs::read_text(&registry)?.text)?;
+        let entries = registry
+            .get("plugins")
+            .and_then(serde_json::Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        for (key, installs) in entries {
+            let installs = match installs {
+                serde_json::Value::Array(installs) => installs,
+                install => vec![install],
+            };
+            let marketplace = key.split_once('@').map(|(_, m)| m.to_string());
+            for install in installs {
+                let Some(path) = install.get("installPath").and_then(|p| p.as_str()) else {
+                    continue;
+                };
+                let path = crate::paths::expand_tilde(path);
+                if !fs.is_dir(&path) {
+                    continue;
+                }
+                let mut plugin = InstalledPlugin::load(path)?;
+                plugin.marketplace.clone_from(&marketplace);
+                plugins.push(plugin);
+            }
+        }
+    }
+
+    if fs.is_dir(&dir) {
+        for path in fs.read_dir(&dir)? {
+            let state = path
+                .file_name()
+                .is_some_and(|name| STATE_DIRS.iter().any(|dir| name == *dir));
+            let known = plugins.iter().any(|plugin| {
+                crate::paths::normalize_for_compare(&plugin.path)
+                    == crate::paths::normalize_for_compare(&path)
+            });
+            if !state && !known && fs.is_dir(&path.join(MANIFEST_DIR)) {
+                plugins.push(InstalledPlugin::load(path)?);
+            }
+        }
+    }
+
+    plugins.sort_by(|a, b| a.name().cmp(b.name()).then_with(|| a.path.cmp(&b.path)));
+    Ok(plugins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    #[test]
+    fn parses_marketplace_entries() {
+        let manifest = parse_plugin_manifest(
+            r#"{
+                "name": "release-tools",
+                "author": {"name": "Acme"},
+                "agents": "./reviewers",
+                "hooks": {"Stop": []},
+                "source": {"source": "github", "repo": "acme/release-tools", "ref": "v2"},
+                "category": "devops"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.agents.paths(), ["./reviewers"]);
+        assert!(matches!(manifest.hooks, Some(ComponentConfig::Inline(_))));
+        assert_eq!(
+            manifest.source,
+            Some(ClaudePluginSource::Remote(RemotePluginSource::Github {
+                repo: "acme/release-tools".into(),
+                git_ref: Some("v2".into()),
+            }))
+        );
+        assert_eq!(manifest.metadata["category"], "devops");
+
+        let local = parse_plugin_manifest(r#"{"name": "x", "source": "./plugins/x"}"#).unwrap();
+        assert_eq!(
+            local.source,
+            Some(ClaudePluginSource::Path("./plugins/x".into()))
+        );
+        assert!(parse_plugin_manifest(r#"{"version": "1.0.0"}"#).is_err());
+    }
+
+    #[test]
+    fn validates_names_versions_and_paths() {
+        let mut manifest = ClaudePluginManifest::named("Release Tools");
+        manifest.version = Some("v1".into());
+        manifest.commands = PathList::Many(vec!["./ok.md".into(), "../escape.md".into()]);
+        manifest.mcp_servers = Some(ComponentConfig::Path("servers.json".into()));
+
+        let codes: Vec<_> = manifest.validate().iter().map(|i| i.code).collect();
+        assert_eq!(
+            codes,
+            [
+                Some(CODE_PLUGIN_NAME_FORMAT),
+                Some(CODE_PLUGIN_VERSION_FORMAT),
+                Some(CODE_PLUGIN_PATH_INVALID),
+                Some(CODE_PLUGIN_PATH_INVALID),
+            ]
+        );
+
+        let fs = MemoryFs::new().with_file("/p/ok.md", "");
+        let mut manifest = ClaudePluginManifest::named("tools");
+        manifest.commands = PathList::Many(vec!["./ok.md".into(), "./gone.md".into()]);
+        let issues = PathContext::fake("/fake")
+            .with_fs(fs)
+            .scope(|| manifest.validate_at(Path::new("/p")));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_PLUGIN_PATH_MISSING));
+    }
+
+    #[test]
+    fn lists_installed_plugins_with_components() {
+        let plugins = Path::new("/fake/home/.claude/plugins");
+        let cached = plugins.join("cache/acme/deploy/1.0.0");
+        let fs = MemoryFs::new()
+            .with_file(
+                plugins.join("installed_plugins.json"),
+                format!(
+                    r#"{{"version": 2, "plugins": {{
+                        "deploy@acme": [{{"scope": "user", "installPath": "{}"}}],
+                        "gone@acme": [{{"installPath": "/nowhere"}}]
+                    }}}}"#,
+                    cached.display()
+                ),
+            )
+            .with_file(
+                cached.join(".claude-plugin/plugin.json"),
+                r#"{"name": "deploy", "version": "1.0.0", "commands": "./extra/ship.md"}"#,
+            )
+            .with_file(cached.join("commands/rollback.md"), "")
+            .with_file(cached.join("commands/README.txt"), "")
+            .with_file(cached.join("extra/ship.md"), "")
+            .with_file(cached.join("agents/reviewer.md"), "")
+            .with_file(cached.join("skills/canary/SKILL.md"), "")
+            .with_file(
+                plugins.join("local/.claude-plugin/plugin.json"),
+                r#"{"name": "local"}"#,
+            )
+            .with_file(
+                plugins.join("marketplaces/acme/.claude-plugin/marketplace.json"),
+                "{}",
+            );
+
+        let installed = PathContext::fake("/fake")
+            .with_fs(fs)
+            .scope(|| installed_plugins(&Scope::Global))
+            .unwrap();
+        let names: Vec<_> = installed.iter().map(InstalledPlugin::name).collect();
+        assert_eq!(names, ["deploy", "local"]);
+
+        let deploy = &installed[0];
+        assert_eq!(deploy.marketplace.as_deref(), Some("acme"));
+        assert_eq!(deploy.manifest.version.as_deref(), Some("1.0.0"));
+        assert_eq!(
+            deploy.commands,
+            [
+                cached.join("commands/rollback.md"),
+                cached.join("extra/ship.md")
+            ]
+        );
+        assert_eq!(deploy.agents, [cached.join("agents/reviewer.md")]);
+        assert_eq!(deploy.skills, [cached.join("skills/canary")]);
+        assert_eq!(installed[1].marketplace, None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Home-relative paths for configs checked into dotfiles repositories.
+//!
+//! A config exported with `/Users/alice/...` paths only works for Alice.
+//! [`make_portable`] rewrites the home directory in every string of a
+//! native config to the placeholder the harness expands itself, and
+//! [`expand_portable`] turns any placeholder back into a path on import.
+//! [`NativeOptions::with_portable`](crate::mcp::NativeOptions::with_portable)
+//! applies [`make_portable`] when converting a server.
+//!
+//! # Example
+//!
+//! ```
+//! use std::path::Path;
+//! use harness_locate::portable::{expand_portable, make_portable};
+//! use harness_locate::HarnessKind;
+//! use serde_json::json;
+//!
+//! let home = Path::new("/Users/alice");
+//! let mut native = json!({"command": "/Users/alice/bin/mcp", "args": ["--root=/Users/alice/notes"]});
+//! make_portable(&mut native, home, HarnessKind::ClaudeCode);
+//! assert_eq!(native["args"][0], "--root=${HOME}/notes");
+//!
+//! expand_portable(&mut native, Path::new("/home/bob"));
+//! assert_eq!(native["command"], "/home/bob/bin/mcp");
+//! ```
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::types::HarnessKind;
+
+/// Spellings of the home directory that [`expand_portable`] and
+/// [`InterpolationContext::expand`](crate::interpolate::InterpolationContext::expand)
+/// recognize.
+const PLACEHOLDERS: &[&str] = &[
+    "${env:HOME}",
+    "{env:HOME}",
+    "${HOME}",
+    "$HOME",
+    "${USERPROFILE}",
+    "$USERPROFILE",
+    "${userHome}",
+    "~",
+];
+
+/// Returns the placeholder for the home directory in `kind`'s configs.
+///
+/// Claude Code, Amp and Copilot CLI expand `${HOME}` and OpenCode
+/// `{env:HOME}`. Goose expands neither, so its configs use `~`, which
+/// [`expand_portable`] must resolve before the config is installed.
+#[must_use]
+pub fn home_placeholder(kind: HarnessKind) -> &'static str {
+    match kind {
+        HarnessKind::ClaudeCode | HarnessKind::AmpCode | HarnessKind::CopilotCli => "${HOME}",
+        HarnessKind::OpenCode => "{env:HOME}",
+        HarnessKind::Goose => "~",
+    }
+}
+
+/// Replaces the home directory `home` with [`home_placeholder`] in every
+/// string value of `value`. Object keys are left alone.
+///
+/// Only whole path components match: `/home/al` is not rewritten inside
+/// `/home/alice`. Paths may start a string or follow `=`, `:`, `,` or
+/// whitespace, as in `--root=/home/alice/notes`.
+pub fn make_portable(value: &mut Value, home: &Path, kind: HarnessKind) {
+    let home = home.to_string_lossy();
+    let home = home.trim_end_matches(['/', '\\']);
+    if home.is_empty() {
+        return;
+    }
+    let placeholder = home_placeholder(kind);
+    map_strings(value, &mut |s| replace_paths(s, home, placeholder));
+}
+
+/// Replaces every home placeholder in the strings of `value` with `home`.
+///
+/// All placeholders are recognized, whichever harness or editor wrote
+/// them: `${HOME}`, `{env:HOME}`, `${env:HOME}`, `$HOME`, `$USERPROFILE`,
+/// `${userHome}` and `~`. Like paths in [`make_portable`], a placeholder
+/// must be a whole leading path component, so `a~b` and `$HOMEDIR` are
+/// left alone.
+pub fn expand_portable(value: &mut Value, home: &Path) {
+    let home = home.to_string_lossy();
+    let home = home.trim_end_matches(['/', '\\']);
+    map_strings(value, &mut |s| expand_home(s, home));
+}
+
+/// Replaces every home placeholder in `s` with `home`, which must not end
+/// in a separator.
+pub(crate) fn expand_home(s: &str, home: &str) -> String {
+    PLACEHOLDERS.iter().fold(s.to_string(), |s, placeholder| {
+        replace_paths(&s, placeholder, home)
+    })
+}
+
+pub(crate) fn map_strings(value: &mut Value, f: &mut dyn FnMut(&str) -> String) {
+    match value {
+        Value::String(s) => *s = f(s),
+        Value::Array(items) => items.iter_mut().for_each(|item| map_strings(item, f)),
+        Value::Object(map) => map.values_mut().for_each(|item| map_strings(item, f)),
+        _ => {}
+    }
+}
+
+/// Replaces `from` with `to` where `from` is a whole leading path.
+pub(crate) fn replace_paths(s: &str, from: &str, to: &str) -> String {
+    let starts_path = |c: char| c.is_whitespace() || matches!(c, '=' | ':' | ',');
+    let ends_path = |c: char| matches!(c, '/' | '\\' | '"' | '\'') || starts_path(c);
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    let mut at_boundary = true;
+    while let Some(index) = rest.find(from) {
+        let before = &rest[..index];
+        let after = &rest[index + from.len()..];
+        let boundary = before.chars().next_back().map_or(at_boundary, starts_path);
+        out.push_str(before);
+        if boundary && after.chars().next().is_none_or(ends_path) {
+            out.push_str(to);
+        } else {
+            out.push_str(from);
+        }
+        at_boundary = from.chars().next_back().is_some_and(starts_path);
+        rest = after;
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn rewrites_whole_home_paths_only() {
+        let home = Path::new("/home/al/");
+        let mut value = json!({
+            "command": "/home/al/.local/bin/mcp",
+            "args": ["--data=/home/al", "/home/alice/x", "x:/home/al/y", "/srv/home/al"],
+            "env": {"PATH": "/usr/bin:/home/al/bin"},
+            "timeout": 5
+        });
+        make_portable(&mut value, home, HarnessKind::OpenCode);
+        assert_eq!(
+            value,
+            json!({
+                "command": "{env:HOME}/.local/bin/mcp",
+                "args": ["--data={env:HOME}", "/home/alice/x", "x:{env:HOME}/y", "/srv/home/al"],
+                "env": {"PATH": "/usr/bin:{env:HOME}/bin"},
+                "timeout": 5
+            })
+        );
+
+        expand_portable(&mut value, home);
+        assert_eq!(value["command"], "/home/al/.local/bin/mcp");
+        assert_eq!(value["args"][2], "x:/home/al/y");
+    }
+
+    #[test]
+    fn native_options_write_portable_configs() {
+        use crate::mcp::{McpServer, NativeOptions, StdioMcpServer};
+        use crate::platform::PathContext;
+
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "/fake/home/.cargo/bin/mcp".to_string(),
+            args: vec!["--db=/fake/home/data.db".to_string()],
+            env: Default::default(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        let options = NativeOptions::default().with_portable(true);
+        let native = PathContext::fake("/fake")
+            .scope(|| server.to_native_value_with(HarnessKind::OpenCode, "db", options))
+            .unwrap();
+        assert_eq!(
+            native["command"],
+            json!(["{env:HOME}/.cargo/bin/mcp", "--db={env:HOME}/data.db"])
+        );
+    }
+
+    #[test]
+    fn expands_every_placeholder_style() {
+        let mut value = json!([
+            "~/a",
+            "${HOME}/b",
+            "$HOME/c",
+            "$HOMEDIR/d",
+            "a~b",
+            "~",
+            "${env:HOME}/e",
+            "$USERPROFILE\\f"
+        ]);
+        expand_portable(&mut value, Path::new(r"C:\Users\bob"));
+        assert_eq!(
+            value,
+            json!([
+                r"C:\Users\bob/a",
+                r"C:\Users\bob/b",
+                r"C:\Users\bob/c",
+                "$HOMEDIR/d",
+                "a~b",
+                r"C:\Users\bob",
+                r"C:\Users\bob/e",
+                r"C:\Users\bob\f"
+            ])
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Config translation without filesystem or environment access.
+//!
+//! The functions here take every input explicitly and read nothing from
+//! the machine they run on, so `build.rs` scripts and servers get the same
+//! output everywhere. Goose has no syntax for environment references and
+//! stores their values, so conversions to Goose resolve references from
+//! the `env` map passed in rather than from the process environment.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//! use harness_locate::mcp::{McpServer, StdioMcpServer};
+//! use harness_locate::pure;
+//! use harness_locate::types::{EnvValue, HarnessKind};
+//!
+//! let server = McpServer::Stdio(StdioMcpServer {
+//!     command: "gh-mcp".to_string(),
+//!     args: vec![],
+//!     env: [("TOKEN".to_string(), EnvValue::env("GITHUB_TOKEN"))].into(),
+//!     cwd: None,
+//!     enabled: true,
+//!     timeout_ms: None,
+//! });
+//! let env = BTreeMap::from([("GITHUB_TOKEN".to_string(), "ghp_123".to_string())]);
+//!
+//! let claude = pure::mcp_to_native(HarnessKind::ClaudeCode, "github", &server, &env)?;
+//! assert_eq!(claude["env"]["TOKEN"], "${GITHUB_TOKEN}");
+//! let goose = pure::mcp_to_native(HarnessKind::Goose, "github", &server, &env)?;
+//! assert_eq!(goose["envs"]["TOKEN"], "ghp_123");
+//!
+//! assert_eq!(
+//!     pure::convert_env("{env:GITHUB_TOKEN}", HarnessKind::OpenCode, HarnessKind::ClaudeCode, &env)?,
+//!     "${GITHUB_TOKEN}"
+//! );
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::error::Result;
+use crate::mcp::McpServer;
+use crate::platform::PathContext;
+use crate::types::{EnvValue, HarnessKind};
+
+/// Runs `f` with `env` as the whole environment.
+fn with_env<R>(env: &BTreeMap<String, String>, f: impl FnOnce() -> R) -> R {
+    PathContext::new().with_env(env.clone()).scope(f)
+}
+
+/// Converts `server` to `kind`'s native config value, as
+/// [`McpServer::to_native_value`] does.
+///
+/// # Errors
+///
+/// Returns `Error::UnsupportedMcpConfig` if `kind` does not support the
+/// server, or `Error::MissingEnvVar` if the server is converted for Goose
+/// and references a variable missing from `env`.
+pub fn mcp_to_native(
+    kind: HarnessKind,
+    name: &str,
+    server: &McpServer,
+    env: &BTreeMap<String, String>,
+) -> Result<Value> {
+    with_env(env, || server.to_native_value(kind, name))
+}
+
+/// Parses one server from `kind`'s native config value.
+///
+/// # Errors
+///
+/// Returns an error if `value` is not a valid server config for `kind`.
+pub fn mcp_from_native(kind: HarnessKind, value: &Value) -> Result<McpServer> {
+    with_env(&BTreeMap::new(), || {
+        crate::harness::backend::builtin(kind).parse_mcp_server(value)
+    })
+}
+
+/// Writes `value` in `kind`'s syntax, as [`EnvValue::try_to_native`] does.
+///
+/// # Errors
+///
+/// Returns `Error::MissingEnvVar` if `kind` is Goose and `value` references
+/// a variable missing from `env`.
+pub fn env_to_native(
+    value: &EnvValue,
+    kind: HarnessKind,
+    env: &BTreeMap<String, String>,
+) -> Result<String> {
+    with_env(env, || value.try_to_native(kind))
+}
+
+/// Rewrites the value `s`, written in `from`'s syntax, in `to`'s syntax.
+///
+/// # Errors
+///
+/// Returns `Error::MissingEnvVar` if `to` is Goose and `s` references a
+/// variable missing from `env`.
+pub fn convert_env(
+    s: &str,
+    from: HarnessKind,
+    to: HarnessKind,
+    env: &BTreeMap<String, String>,
+) -> Result<String> {
+    env_to_native(&EnvValue::from_native(s, from), to, env)
+}
+
+/// Rewrites the agent file `content` for `kind`: the `tools` list is
+/// written in the shape `kind` expects, and everything else is kept.
+///
+/// Harnesses without agent frontmatter conventions, such as Goose, get
+/// the frontmatter unchanged.
+///
+/// # Errors
+///
+/// Returns `Error::YamlParse` if the frontmatter is not valid YAML or has
+/// a field of the wrong type.
+#[cfg(feature = "yaml")]
+pub fn convert_agent(content: &str, kind: HarnessKind) -> Result<String> {
+    use crate::meta::AgentMeta;
+
+    let frontmatter = crate::skill::parse_frontmatter(content)?;
+    if frontmatter.yaml.is_none() {
+        return Ok(content.to_string());
+    }
+    let meta = convert_agent_meta(AgentMeta::try_from(&frontmatter)?, kind);
+    let yaml = serde_yaml::to_string(&meta)?;
+    Ok(format!(
+        "---\n{}\n---\n{}",
+        yaml.trim_end(),
+        frontmatter.body
+    ))
+}
+
+/// Writes `meta`'s `tools` in the shape `kind` expects.
+#[cfg(feature = "yaml")]
+#[must_use]
+pub fn convert_agent_meta(
+    mut meta: crate::meta::AgentMeta,
+    kind: HarnessKind,
+) -> crate::meta::AgentMeta {
+    use crate::meta::ToolList;
+    use crate::validation::{AgentCapabilities, ToolsFormat};
+
+    let (Some(tools), Some(caps)) = (&meta.tools, AgentCapabilities::for_kind(kind)) else {
+        return meta;
+    };
+    let names = tools.names();
+    meta.tools = Some(match caps.tools_format {
+        ToolsFormat::BooleanRecord => ToolList::Record(
+            names
+                .into_iter()
+                .map(|name| (name.to_string(), true))
+                .collect(),
+        ),
+        ToolsFormat::CommaSeparatedString => ToolList::Text(names.join(", ")),
+    });
+    meta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::StdioMcpServer;
+
+    fn server() -> McpServer {
+        McpServer::Stdio(StdioMcpServer {
+            command: "srv".to_string(),
+            args: vec![],
+            env: [("KEY".to_string(), EnvValue::env("PURE_TEST_KEY"))].into(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        })
+    }
+
+    #[test]
+    fn goose_resolves_from_the_given_env_only() {
+        // SAFETY: only this test uses the variable.
+        unsafe { std::env::set_var("PURE_TEST_KEY", "from-process") };
+        let env = BTreeMap::from([("PURE_TEST_KEY".to_string(), "given".to_string())]);
+
+        let native = mcp_to_native(HarnessKind::Goose, "srv", &server(), &env).unwrap();
+        assert_eq!(native["envs"]["KEY"], "given");
+        assert!(matches!(
+            mcp_to_native(HarnessKind::Goose, "srv", &server(), &BTreeMap::new()),
+            Err(crate::Error::MissingEnvVar { name }) if name == "PURE_TEST_KEY"
+        ));
+
+        let claude = mcp_to_native(HarnessKind::ClaudeCode, "srv", &server(), &env).unwrap();
+        assert_eq!(
+            mcp_from_native(HarnessKind::ClaudeCode, &claude).unwrap(),
+            server()
+        );
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn converts_agent_tools_between_shapes() {
+        let claude = "---\ndescription: Reviews\ntools: Read, Grep\n---\nBody\n";
+        let opencode = convert_agent(claude, HarnessKind::OpenCode).unwrap();
+        assert_eq!(
+            opencode,
+            "---\ndescription: Reviews\ntools:\n  Grep: true\n  Read: true\n---\nBody\n"
+        );
+        let back = convert_agent(&opencode, HarnessKind::ClaudeCode).unwrap();
+        assert!(back.contains("tools: Grep, Read\n"), "{back}");
+        assert_eq!(
+            convert_agent("No frontmatter", HarnessKind::OpenCode).unwrap(),
+            "No frontmatter"
+        );
+    }
+}
@@ -0,0 +1,552 @@
+//! Renaming a skill across harnesses.
+//!
+//! A skill installed into several harnesses lives in one directory per
+//! harness and scope, its frontmatter `name` must often match that
+//! directory, and commands and agents refer to it by name, in a `skills:`
+//! frontmatter list or as a `/name` invocation. [`rename_skill`]
+//! works out every change needed as a [`RenamePlan`] without touching the
+//! filesystem, so it can be shown as a dry run; [`RenamePlan::apply`]
+//! carries it out.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::rename::rename_skill;
+//! use harness_locate::{Harness, HarnessKind, Scope};
+//!
+//! let targets = [
+//!     (Harness::new(HarnessKind::ClaudeCode), Scope::Global),
+//!     (Harness::new(HarnessKind::OpenCode), Scope::Global),
+//! ];
+//! let plan = rename_skill("deploy", "Ship It", &targets)?;
+//! for step in &plan.steps {
+//!     println!("{step}");
+//! }
+//! plan.apply()?;
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+use crate::harness::Harness;
+use crate::naming::{safe_name, slugify};
+use crate::skill::split_frontmatter;
+use crate::text::encode;
+use crate::types::{ResourceKind, Scope};
+
+/// A change made by a [`RenamePlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RenameStep {
+    /// Move a skill directory.
+    MoveDirectory {
+        /// Name of the harness.
+        harness: String,
+        /// The current directory.
+        from: PathBuf,
+        /// The new directory.
+        to: PathBuf,
+    },
+    /// Set the `name` in a skill's frontmatter. `path` is where the file
+    /// is once its directory has moved.
+    SetName {
+        /// Name of the harness.
+        harness: String,
+        /// The skill file.
+        path: PathBuf,
+        /// The new file contents.
+        contents: String,
+    },
+    /// Replace references to the old name in a command or agent file:
+    /// entries of its frontmatter `skills:` list and `/name` invocations.
+    /// Other mentions, such as the name in prose, are left alone.
+    UpdateReferences {
+        /// Name of the harness.
+        harness: String,
+        /// The command or agent file.
+        path: PathBuf,
+        /// How many references are replaced.
+        count: usize,
+        /// The new file contents.
+        contents: String,
+    },
+}
+
+impl fmt::Display for RenameStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MoveDirectory { harness, from, to } => {
+                write!(f, "{harness}: move {} to {}", from.display(), to.display())
+            }
+            Self::SetName { harness, path, .. } => {
+                write!(f, "{harness}: set name in {}", path.display())
+            }
+            Self::UpdateReferences {
+                harness,
+                path,
+                count,
+                ..
+            } => write!(
+                f,
+                "{harness}: update {count} reference{} in {}",
+                if *count == 1 { "" } else { "s" },
+                path.display()
+            ),
+        }
+    }
+}
+
+/// Every change needed to rename a skill, from [`rename_skill`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamePlan {
+    /// The current name.
+    pub old: String,
+    /// The requested name, before adapting it to each harness.
+    pub new: String,
+    /// Changes in the order [`apply`](Self::apply) makes them.
+    pub steps: Vec<RenameStep>,
+    /// Target directories that already exist. A plan with conflicts
+    /// cannot be applied.
+    pub conflicts: Vec<PathBuf>,
+    /// Harness names and scopes where the skill was not found.
+    pub missing: Vec<(String, Scope)>,
+}
+
+impl RenamePlan {
+    /// Returns `true` if there is nothing to change.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Moves the directories, then rewrites the files.
+    ///
+    /// Rewritten files keep their encoding, byte order mark and line
+    /// endings.
+    ///
+    /// Each step is recorded in the [journal](crate::journal) as
+    /// `skill.move`, `skill.set_name` or `skill.update_references`. A move
+    /// is journaled under its old directory, with the old and new paths as
+    /// its before and after.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidPath`] with the first conflict, without
+    /// changing anything, if the plan has conflicts, or an error if a
+    /// directory cannot be moved or a file cannot be written. Steps made
+    /// before a failure are not undone.
+    pub fn apply(&self) -> Result<()> {
+        if let Some(conflict) = self.conflicts.first() {
+            return Err(Error::InvalidPath(conflict.clone()));
+        }
+        let fs = crate::fs::current();
+        for step in &self.steps {
+            if let RenameStep::MoveDirectory { from, to, .. } = step {
+                fs.rename(from, to)?;
+                let (before, after) = (from.to_string_lossy(), to.to_string_lossy());
+                crate::journal::record("skill.move", from, &before, &after);
+            }
+        }
+        for step in &self.steps {
+            let (operation, path, contents) = match step {
+                RenameStep::SetName { path, contents, .. } => ("skill.set_name", path, contents),
+                RenameStep::UpdateReferences { path, contents, .. } => {
+                    ("skill.update_references", path, contents)
+                }
+                RenameStep::MoveDirectory { .. } => continue,
+            };
+            let before = fs.read_text(path).unwrap_or_default();
+            fs.write(path, &encode(contents, before.normalization))?;
+            crate::journal::record(operation, path, &before.text, contents);
+        }
+        Ok(())
+    }
+}
+
+/// Plans renaming the skill `old` to `new` in each harness and scope of
+/// `targets`.
+///
+/// In each harness the new name is adapted with
+/// [`slugify`](crate::naming::slugify), so harnesses requiring lowercase
+/// hyphenated names get `ship-it` for `Ship It`. The skill directory is
+/// renamed to it, the frontmatter `name` set to it, and references to the
+/// old directory name in the commands and agents of the same scope
+/// replaced by it. Only explicit references are rewritten: entries of a
+/// frontmatter `skills:` list and `/name` invocations. The skill is looked
+/// up by `old` as given and as slugified for the harness.
+///
+/// Nothing is written; see [`RenamePlan::apply`].
+///
+/// # Errors
+///
+/// Returns an error if a directory cannot be resolved or listed, or a file
+/// cannot be read.
+pub fn rename_skill(old: &str, new: &str, targets: &[(Harness, Scope)]) -> Result<RenamePlan> {
+    let mut plan = RenamePlan {
+        old: old.to_string(),
+        new: new.to_string(),
+        steps: Vec::new(),
+        conflicts: Vec::new(),
+        missing: Vec::new(),
+    };
+    let fs = crate::fs::current();
+
+    for (harness, scope) in targets {
+        let adapt = |name: &str| match harness.kind() {
+            Some(kind) => slugify(name, kind),
+            None => safe_name(name),
+        };
+        let new_name = adapt(new);
+        let mut found = None;
+        for candidate in [old.to_string(), adapt(old)] {
+            let lookup = harness.find_resource(
+                ResourceKind::Skills,
+                &candidate,
+                std::slice::from_ref(scope),
+            )?;
+            if let Some(active) = lookup.active() {
+                found = Some((candidate, active.path.clone()));
+                break;
+            }
+        }
+        let Some((old_name, skill_file)) = found else {
+            plan.missing
+                .push((harness.name().to_string(), scope.clone()));
+            continue;
+        };
+        let (Some(dir), Some(file_name)) = (skill_file.parent(), skill_file.file_name()) else {
+            continue;
+        };
+        let target = dir.with_file_name(&new_name);
+        let name = harness.name().to_string();
+
+        if target != dir {
+            if fs.exists(&target) {
+                plan.conflicts.push(target.clone());
+            }
+            plan.steps.push(RenameStep::MoveDirectory {
+                harness: name.clone(),
+                from: dir.to_path_buf(),
+                to: target.clone(),
+            });
+        }
+        let content = crate::fs::read_text(&skill_file)?.text;
+        if let Some(contents) = set_frontmatter_name(&content, &new_name) {
+            plan.steps.push(RenameStep::SetName {
+                harness: name.clone(),
+                path: target.join(file_name),
+                contents,
+            });
+        }
+
+        if old_name == new_name {
+            continue;
+        }
+        for kind in [ResourceKind::Commands, ResourceKind::Agents] {
+            let dir = match harness.resource(kind, scope) {
+                Ok(Some(dir)) => dir,
+                Ok(None) | Err(Error::UnsupportedScope { .. }) => continue,
+                Err(e) => return Err(e),
+            };
+            for path in dir.enumerate()? {
+                let text = crate::fs::read_text(&path)?.text;
+                let (contents, count) = replace_references(&text, &old_name, &new_name);
+                if count > 0 {
+                    plan.steps.push(RenameStep::UpdateReferences {
+                        harness: name.clone(),
+                        path,
+                        count,
+                        contents,
+                    });
+                }
+            }
+        }
+    }
+    Ok(plan)
+}
+
+/// Returns `content` with its frontmatter `name` set to `name`, or `None`
+/// if it has no frontmatter or already has that name.
+fn set_frontmatter_name(content: &str, name: &str) -> Option<String> {
+    let (yaml, _) = split_frontmatter(content)?;
+    let value = yaml_string(name);
+    let line_ending = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+
+    if !yaml.is_empty() {
+        let start = yaml.as_ptr() as usize - content.as_ptr() as usize;
+        let mut offset = start;
+        for line in yaml.split_inclusive('\n') {
+            if let Some(rest) = line.strip_prefix("name:") {
+                let current = rest.trim();
+                if current == value || current.trim_matches(['"', '\'']) == name {
+                    return None;
+                }
+                let end = offset + line.trim_end_matches(['\r', '\n']).len();
+                return Some(format!(
+                    "{}name: {value}{}",
+                    &content[..offset],
+                    &content[end..]
+                ));
+            }
+            offset += line.len();
+        }
+    }
+
+    let opener = content.find("---")? + "---".len() + line_ending.len();
+    Some(format!(
+        "{}name: {value}{line_ending}{}",
+        &content[..opener],
+        &content[opener..]
+    ))
+}
+
+/// Formats `value` as a YAML scalar, quoting it unless it is plain text.
+fn yaml_string(value: &str) -> String {
+    let plain = value.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && !value.ends_with(' ')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '.' | '-' | '_'))
+        && !matches!(value, "true" | "false" | "null" | "yes" | "no");
+    if plain {
+        value.to_string()
+    } else {
+        serde_json::Value::from(value).to_string()
+    }
+}
+
+/// Replaces the references to skill `old` in a command or agent file:
+/// entries of its frontmatter `skills:` list and `/old` invocations.
+/// Returns the new text and the number of replacements.
+fn replace_references(text: &str, old: &str, new: &str) -> (String, usize) {
+    let Some((yaml, _)) = split_frontmatter(text) else {
+        return replace_invocations(text, old, new);
+    };
+    let start = yaml.as_ptr() as usize - text.as_ptr() as usize;
+    let (skills, entries) = replace_skills_entries(yaml, old, new);
+    let (body, invocations) = replace_invocations(&text[start + yaml.len()..], old, new);
+    (
+        format!("{}{skills}{body}", &text[..start]),
+        entries + invocations,
+    )
+}
+
+/// Replaces `old` in the value of the top-level `skills:` key of `yaml`,
+/// whether it is written inline or as a block list.
+fn replace_skills_entries(yaml: &str, old: &str, new: &str) -> (String, usize) {
+    let mut out = String::with_capacity(yaml.len());
+    let mut count = 0;
+    let mut in_skills = false;
+    for line in yaml.split_inclusive('\n') {
+        let (key, value) = match line.strip_prefix("skills:") {
+            Some(value) => ("skills:", value),
+            None if in_skills && line.starts_with([' ', '\t', '-']) => ("", line),
+            None => {
+                in_skills = false;
+                out.push_str(line);
+                continue;
+            }
+        };
+        in_skills = true;
+        let (value, n) = replace_entry(value, old, new);
+        out.push_str(key);
+        out.push_str(&value);
+        count += n;
+    }
+    (out, count)
+}
+
+/// Replaces the YAML scalar `old`, plain or quoted, in `value`.
+fn replace_entry(value: &str, old: &str, new: &str) -> (String, usize) {
+    let quoted = serde_json::Value::from(new).to_string();
+    let (value, double) = replace_where(value, &format!("\"{old}\""), &quoted, |_, _| true);
+    let (value, single) = replace_where(&value, &format!("'{old}'"), &quoted, |_, _| true);
+    let (value, plain) = replace_where(&value, old, &yaml_string(new), |before, after| {
+        is_word_boundary(before, after) && before != Some('"') && after != Some('"')
+    });
+    (value, double + single + plain)
+}
+
+/// Replaces `/old` invocations in `text`: the slash must start a word, and
+/// the name must not continue into a longer name or a path.
+fn replace_invocations(text: &str, old: &str, new: &str) -> (String, usize) {
+    replace_where(
+        text,
+        &format!("/{old}"),
+        &format!("/{new}"),
+        |before, after| {
+            before.is_none_or(|c| c.is_whitespace() || matches!(c, '`' | '(' | '[' | '"' | '\''))
+                && after.is_none_or(|c| !is_word(c) && c != '/')
+        },
+    )
+}
+
+/// Returns `true` if `c` is part of a skill name.
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_')
+}
+
+/// Accepts a match that is a whole word.
+fn is_word_boundary(before: Option<char>, after: Option<char>) -> bool {
+    !before.is_some_and(is_word) && !after.is_some_and(is_word)
+}
+
+/// Replaces the occurrences of `old` in `text` that `accept` approves,
+/// given the characters before and after each. Returns the new text and
+/// the number of replacements.
+fn replace_where(
+    text: &str,
+    old: &str,
+    new: &str,
+    accept: impl Fn(Option<char>, Option<char>) -> bool,
+) -> (String, usize) {
+    let mut out = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut rest = text;
+    while let Some(pos) = rest.find(old) {
+        let before = rest[..pos]
+            .chars()
+            .next_back()
+            .or_else(|| out.chars().next_back());
+        let after = rest[pos + old.len()..].chars().next();
+        out.push_str(&rest[..pos]);
+        if accept(before, after) {
+            out.push_str(new);
+            count += 1;
+        } else {
+            out.push_str(old);
+        }
+        rest = &rest[pos + old.len()..];
+    }
+    out.push_str(rest);
+    (out, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+    use crate::types::HarnessKind;
+
+    #[test]
+    fn renames_directories_names_and_references() {
+        let claude = Harness::new(HarnessKind::ClaudeCode);
+        let opencode = Harness::new(HarnessKind::OpenCode);
+        let ctx = PathContext::fake("/fake");
+        let dirs = ctx.scope(|| {
+            (
+                claude.skills(&Scope::Global).unwrap().unwrap().path,
+                claude.commands(&Scope::Global).unwrap().unwrap().path,
+                opencode.skills(&Scope::Global).unwrap().unwrap().path,
+            )
+        });
+        let (claude_skills, claude_commands, opencode_skills) = dirs;
+        let skill = "---\nname: deploy\ndescription: Deploys\n---\nBody\n";
+        let ctx = ctx.with_fs(
+            MemoryFs::new()
+                .with_file(claude_skills.join("deploy/SKILL.md"), skill)
+                .with_file(claude_skills.join("deploy/scripts/run.sh"), "echo")
+                .with_file(
+                    claude_commands.join("release.md"),
+                    "\u{feff}---\r\nskills: [deploy, lint]\r\n---\r\nRun /deploy, then check the deploy log.\r\n",
+                )
+                .with_file(opencode_skills.join("deploy/SKILL.md"), skill),
+        );
+        let targets = [(claude, Scope::Global), (opencode, Scope::Global)];
+
+        let plan = ctx
+            .scope(|| rename_skill("deploy", "Ship It", &targets))
+            .unwrap();
+        assert!(plan.conflicts.is_empty());
+        assert!(plan.missing.is_empty());
+        assert_eq!(plan.steps.len(), 5);
+        assert!(plan.steps.contains(&RenameStep::MoveDirectory {
+            harness: "OpenCode".into(),
+            from: opencode_skills.join("deploy"),
+            to: opencode_skills.join("ship-it"),
+        }));
+        let RenameStep::UpdateReferences { count, .. } = &plan.steps[2] else {
+            panic!("expected reference update, got {}", plan.steps[2]);
+        };
+        assert_eq!(*count, 2);
+
+        ctx.scope(|| plan.apply()).unwrap();
+        let read = |path: PathBuf| ctx.scope(|| crate::fs::read_text(&path).unwrap().text);
+        assert_eq!(
+            read(opencode_skills.join("ship-it/SKILL.md")),
+            "---\nname: ship-it\ndescription: Deploys\n---\nBody\n"
+        );
+        assert!(read(claude_skills.join("Ship It/SKILL.md")).starts_with("---\nname: Ship It\n"));
+        assert_eq!(read(claude_skills.join("Ship It/scripts/run.sh")), "echo");
+        assert_eq!(
+            read(claude_commands.join("release.md")),
+            "---\nskills: [Ship It, lint]\n---\nRun /Ship It, then check the deploy log.\n"
+        );
+        let bytes = ctx.scope(|| crate::fs::current().read(&claude_commands.join("release.md")));
+        assert!(
+            bytes
+                .unwrap()
+                .starts_with("\u{feff}---\r\nskills:".as_bytes())
+        );
+
+        let journal = ctx.scope(crate::journal::read_journal).unwrap();
+        let ops: Vec<_> = journal
+            .iter()
+            .map(|e| (e.operation.as_str(), e.file.clone()))
+            .collect();
+        assert_eq!(
+            ops,
+            [
+                ("skill.move", claude_skills.join("deploy")),
+                ("skill.move", opencode_skills.join("deploy")),
+                ("skill.set_name", claude_skills.join("Ship It/SKILL.md")),
+                (
+                    "skill.update_references",
+                    claude_commands.join("release.md")
+                ),
+                ("skill.set_name", opencode_skills.join("ship-it/SKILL.md")),
+            ]
+        );
+        assert_eq!(
+            journal[4].diff_hash,
+            crate::journal::diff_hash(skill, &read(opencode_skills.join("ship-it/SKILL.md")))
+        );
+
+        let again = ctx
+            .scope(|| rename_skill("deploy", "Ship It", &targets))
+            .unwrap();
+        assert_eq!(again.missing.len(), 2);
+    }
+
+    #[test]
+    fn frontmatter_name_is_inserted_or_quoted() {
+        assert_eq!(
+            set_frontmatter_name("---\ndescription: x\n---\n", "a: b").unwrap(),
+            "---\nname: \"a: b\"\ndescription: x\n---\n"
+        );
+        assert_eq!(set_frontmatter_name("---\nname: same\n---\n", "same"), None);
+        assert_eq!(set_frontmatter_name("no frontmatter", "x"), None);
+    }
+
+    #[test]
+    fn only_explicit_references_are_replaced() {
+        let text = "---\ndescription: deploy things\nskills:\n  - deploy\n  - 'deploy'\n  - deploy-extra\ntools: deploy\n---\nUse /deploy or (/deploy), not /deploy/x, a/deploy or /deployer; deploy stays.\n";
+        let (out, count) = replace_references(text, "deploy", "a: b");
+        assert_eq!(count, 4);
+        assert_eq!(
+            out,
+            "---\ndescription: deploy things\nskills:\n  - \"a: b\"\n  - \"a: b\"\n  - deploy-extra\ntools: deploy\n---\nUse /a: b or (/a: b), not /deploy/x, a/deploy or /deployer; deploy stays.\n"
+        );
+        assert_eq!(
+            replace_references("Run /deploy", "deploy", "ship"),
+            ("Run /ship".to_string(), 1)
+        );
+    }
+}
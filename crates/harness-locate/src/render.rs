@@ -0,0 +1,245 @@
+//! Human-readable rendering of core types for command-line tools.
+//!
+//! [`ValidationIssue::render`], [`InstallationStatus::summary`] and
+//! [`McpCapabilities::table`] give every CLI built on this crate the same
+//! wording. The `*_styled` variants take a [`Style`]; with the `color`
+//! feature, [`Style::Color`] highlights severities and states with ANSI
+//! escape codes.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::{InstallationStatus, ValidationIssue};
+//!
+//! let issue = ValidationIssue::error("command", "Command must not be empty", None);
+//! assert_eq!(issue.render(), "error: command: Command must not be empty");
+//! assert_eq!(InstallationStatus::NotInstalled.summary(), "not installed");
+//! ```
+
+use crate::mcp::McpCapabilities;
+use crate::types::InstallationStatus;
+use crate::validation::{Severity, ValidationIssue};
+
+/// How rendered text is decorated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum Style {
+    /// Plain text.
+    #[default]
+    Plain,
+    /// ANSI colors, for terminals.
+    #[cfg(feature = "color")]
+    Color,
+}
+
+const RED: &str = "1;31";
+const YELLOW: &str = "1;33";
+const GREEN: &str = "32";
+const DIM: &str = "2";
+
+impl Style {
+    #[cfg_attr(not(feature = "color"), allow(unused_variables))]
+    fn paint(self, color: &str, text: &str) -> String {
+        match self {
+            Self::Plain => text.to_string(),
+            #[cfg(feature = "color")]
+            Self::Color => format!("\x1b[{color}m{text}\x1b[0m"),
+        }
+    }
+}
+
+impl ValidationIssue {
+    /// Renders the issue on one line, as
+    /// `<severity>: <field>: <message> [<code>]`.
+    ///
+    /// The field and code are left out when empty or unset.
+    #[must_use]
+    pub fn render(&self) -> String {
+        self.render_styled(Style::Plain)
+    }
+
+    /// Like [`render`](Self::render), with the severity and code styled.
+    #[must_use]
+    pub fn render_styled(&self, style: Style) -> String {
+        let severity = match self.severity {
+            Severity::Error => style.paint(RED, "error"),
+            Severity::Warning => style.paint(YELLOW, "warning"),
+        };
+        let mut line = severity + ": ";
+        if !self.field.is_empty() {
+            line.push_str(&self.field);
+            line.push_str(": ");
+        }
+        line.push_str(&self.message);
+        if let Some(code) = self.code {
+            line.push(' ');
+            line.push_str(&style.paint(DIM, &format!("[{code}]")));
+        }
+        line
+    }
+}
+
+impl InstallationStatus {
+    /// Summarizes the status on one line, naming the binary and config
+    /// directory found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::InstallationStatus;
+    ///
+    /// let status = InstallationStatus::BinaryOnly {
+    ///     binary_path: "/usr/bin/goose".into(),
+    /// };
+    /// assert_eq!(status.summary(), "binary only: /usr/bin/goose (no config directory)");
+    /// ```
+    #[must_use]
+    pub fn summary(&self) -> String {
+        self.summary_styled(Style::Plain)
+    }
+
+    /// Like [`summary`](Self::summary), with the state styled.
+    #[must_use]
+    pub fn summary_styled(&self, style: Style) -> String {
+        match self {
+            Self::NotInstalled => style.paint(RED, "not installed"),
+            Self::ConfigOnly { config_path } => format!(
+                "{}: {} (binary not on PATH)",
+                style.paint(YELLOW, "config only"),
+                config_path.display()
+            ),
+            Self::BinaryOnly { binary_path } => format!(
+                "{}: {} (no config directory)",
+                style.paint(YELLOW, "binary only"),
+                binary_path.display()
+            ),
+            Self::FullyInstalled {
+                binary_path,
+                config_path,
+            } => format!(
+                "{}: binary {}, config {}",
+                style.paint(GREEN, "installed"),
+                binary_path.display(),
+                config_path.display()
+            ),
+        }
+    }
+}
+
+impl McpCapabilities {
+    /// Renders the capabilities as an aligned two-column table, one
+    /// feature per line, marked `✓` or `✗`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::{HarnessKind, McpCapabilities};
+    ///
+    /// let table = McpCapabilities::for_kind(HarnessKind::Goose).table();
+    /// assert!(table.starts_with("stdio             ✓\n"));
+    /// assert!(table.ends_with("default timeout   300000 ms"));
+    /// ```
+    #[must_use]
+    pub fn table(&self) -> String {
+        self.table_styled(Style::Plain)
+    }
+
+    /// Like [`table`](Self::table), with the marks styled.
+    #[must_use]
+    pub fn table_styled(&self, style: Style) -> String {
+        let rows = [
+            ("stdio", self.stdio),
+            ("SSE", self.sse),
+            ("HTTP", self.http),
+            ("OAuth", self.oauth),
+            ("timeout", self.timeout),
+            ("enable/disable", self.toggle),
+            ("headers", self.headers),
+            ("cwd", self.cwd),
+            ("Goose extensions", self.goose_native),
+        ];
+        let mut lines: Vec<(&str, String)> = rows
+            .iter()
+            .map(|&(label, supported)| {
+                let mark = if supported {
+                    style.paint(GREEN, "✓")
+                } else {
+                    style.paint(RED, "✗")
+                };
+                (label, mark)
+            })
+            .collect();
+        if let Some(ms) = self.default_timeout_ms {
+            lines.push(("default timeout", format!("{ms} ms")));
+        }
+        let width = lines
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or(0);
+        lines
+            .iter()
+            .map(|(label, value)| format!("{label:<width$}  {value}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::types::HarnessKind;
+
+    #[test]
+    fn renders_issues_statuses_and_tables() {
+        let issue =
+            ValidationIssue::warning("env.API_KEY", "Looks like a secret", Some("env.secret"));
+        assert_eq!(
+            issue.render(),
+            "warning: env.API_KEY: Looks like a secret [env.secret]"
+        );
+        assert_eq!(
+            ValidationIssue::error("", "Unreadable", None).render(),
+            "error: Unreadable"
+        );
+
+        let status = InstallationStatus::FullyInstalled {
+            binary_path: PathBuf::from("/usr/bin/claude"),
+            config_path: PathBuf::from("/home/u/.claude"),
+        };
+        assert_eq!(
+            status.summary(),
+            "installed: binary /usr/bin/claude, config /home/u/.claude"
+        );
+        assert_eq!(
+            InstallationStatus::ConfigOnly {
+                config_path: PathBuf::from("/home/u/.claude"),
+            }
+            .summary(),
+            "config only: /home/u/.claude (binary not on PATH)"
+        );
+
+        let table = McpCapabilities::for_kind(HarnessKind::ClaudeCode).table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "stdio             ✓");
+        assert_eq!(lines[5], "enable/disable    ✗");
+        assert_eq!(lines.len(), 9);
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn color_wraps_states_in_ansi_codes() {
+        let issue = ValidationIssue::error("url", "Invalid", Some("url.invalid"));
+        assert_eq!(
+            issue.render_styled(Style::Color),
+            "\x1b[1;31merror\x1b[0m: url: Invalid \x1b[2m[url.invalid]\x1b[0m"
+        );
+        assert!(
+            InstallationStatus::NotInstalled
+                .summary_styled(Style::Color)
+                .starts_with("\x1b[1;31m")
+        );
+    }
+}
@@ -0,0 +1,475 @@
+//! Finding configured MCP servers that cannot start.
+//!
+//! A server entry whose binary was uninstalled, whose working directory
+//! was moved or whose `npx` package no longer exists fails quietly: the
+//! harness starts without its tools. [`audit_mcp_servers`] checks every
+//! enabled stdio server of a scope and reports each problem as a
+//! [`ServerAuditIssue`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::serveraudit::audit_mcp_servers;
+//! use harness_locate::{Harness, HarnessKind, Scope};
+//!
+//! let harness = Harness::new(HarnessKind::ClaudeCode);
+//! for issue in audit_mcp_servers(&harness, &Scope::Global)? {
+//!     eprintln!("{issue}");
+//! }
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::detection::find_binaries;
+use crate::error::{Error, Result};
+use crate::fs::{current, exists_for};
+use crate::harness::Harness;
+use crate::mcp::{McpServer, StdioMcpServer};
+use crate::paths::expand_tilde;
+use crate::types::Scope;
+
+/// `npx` flags followed by a value.
+const NPX_VALUE_FLAGS: &[&str] = &["-p", "--package", "-c", "--call"];
+
+/// `uvx` flags followed by a value.
+const UVX_VALUE_FLAGS: &[&str] = &[
+    "--from",
+    "--with",
+    "--with-editable",
+    "--with-requirements",
+    "-p",
+    "--python",
+    "--index",
+    "--index-url",
+    "--extra-index-url",
+];
+
+/// The registry a package runner downloads from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum PackageEcosystem {
+    /// npm, run with `npx`.
+    Npm,
+    /// PyPI, run with `uvx`.
+    PyPi,
+}
+
+/// A package a server runs through `npx` or `uvx`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PackageRef {
+    /// Where the package comes from.
+    pub ecosystem: PackageEcosystem,
+    /// The package name, e.g. `@modelcontextprotocol/server-github`.
+    pub name: String,
+    /// The requested version, if pinned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+impl PackageRef {
+    /// Returns the package a stdio server runs, or `None` if it does not
+    /// use `npx` or `uvx`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::StdioMcpServer;
+    /// use harness_locate::serveraudit::PackageRef;
+    ///
+    /// let server: StdioMcpServer = serde_json::from_str(
+    ///     r#"{"command": "npx", "args": ["-y", "@acme/mcp@1.2.0"]}"#,
+    /// )?;
+    /// let package = PackageRef::from_server(&server).unwrap();
+    /// assert_eq!(package.name, "@acme/mcp");
+    /// assert_eq!(package.version.as_deref(), Some("1.2.0"));
+    /// # Ok::<(), serde_json::Error>(())
+    /// ```
+    #[must_use]
+    pub fn from_server(server: &StdioMcpServer) -> Option<Self> {
+        let runner = Path::new(&server.command).file_stem()?.to_str()?;
+        match runner {
+            "npx" => {
+                let spec = package_arg(&server.args, &["-p", "--package"], NPX_VALUE_FLAGS)?;
+                Some(Self::npm(spec))
+            }
+            "uvx" => {
+                let spec = package_arg(&server.args, &["--from"], UVX_VALUE_FLAGS)?;
+                Some(Self::pypi(spec))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if a version is given and is not a moving tag like
+    /// `latest`.
+    #[must_use]
+    pub fn is_pinned(&self) -> bool {
+        self.version
+            .as_deref()
+            .is_some_and(|v| !matches!(v, "" | "latest" | "next"))
+    }
+
+    fn npm(spec: &str) -> Self {
+        // The version separator is the last `@` not starting a scope.
+        let (name, version) = match spec.rfind('@') {
+            Some(at) if at > 0 => (&spec[..at], Some(spec[at + 1..].to_string())),
+            _ => (spec, None),
+        };
+        Self {
+            ecosystem: PackageEcosystem::Npm,
+            name: name.to_string(),
+            version,
+        }
+    }
+
+    fn pypi(spec: &str) -> Self {
+        let (name, version) = match spec.split_once("==").or_else(|| spec.split_once('@')) {
+            Some((name, version)) => (name, Some(version.to_string())),
+            None => (spec, None),
+        };
+        let name = name.split('[').next().unwrap_or(name);
+        Self {
+            ecosystem: PackageEcosystem::PyPi,
+            name: name.trim().to_string(),
+            version,
+        }
+    }
+
+    /// Returns `true` if the package is installed in `scope`'s project or
+    /// cached by the package runner.
+    fn resolves_locally(&self, scope: &Scope) -> bool {
+        let fs = current();
+        let home = crate::platform::home_dir().ok();
+        match self.ecosystem {
+            PackageEcosystem::Npm => {
+                let installed = |root: &Path| {
+                    exists_for(
+                        &root
+                            .join("node_modules")
+                            .join(&self.name)
+                            .join("package.json"),
+                        None,
+                    )
+                };
+                if let Scope::Project(root) = scope
+                    && installed(root)
+                {
+                    return true;
+                }
+                home.map(|home| home.join(".npm").join("_npx"))
+                    .and_then(|cache| fs.read_dir(&cache).ok())
+                    .is_some_and(|entries| entries.iter().any(|entry| installed(entry)))
+            }
+            PackageEcosystem::PyPi => {
+                let tools = crate::platform::var("UV_TOOL_DIR")
+                    .map(PathBuf::from)
+                    .or_else(|| home.map(|home| home.join(".local/share/uv/tools")));
+                tools.is_some_and(|tools| fs.is_dir(&tools.join(&self.name)))
+            }
+        }
+    }
+}
+
+impl fmt::Display for PackageRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.version, self.ecosystem) {
+            (Some(version), PackageEcosystem::Npm) => write!(f, "{}@{version}", self.name),
+            (Some(version), PackageEcosystem::PyPi) => write!(f, "{}=={version}", self.name),
+            (None, _) => f.write_str(&self.name),
+        }
+    }
+}
+
+/// Returns the package argument: the value of one of `package_flags`, or
+/// the first positional argument.
+fn package_arg<'a>(
+    args: &'a [String],
+    package_flags: &[&str],
+    value_flags: &[&str],
+) -> Option<&'a str> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            return args.next().map(String::as_str);
+        }
+        if let Some((flag, value)) = arg.split_once('=')
+            && package_flags.contains(&flag)
+        {
+            return Some(value);
+        }
+        if package_flags.contains(&arg.as_str()) {
+            return args.next().map(String::as_str);
+        }
+        if value_flags.contains(&arg.as_str()) {
+            args.next();
+        } else if !arg.starts_with('-') {
+            return Some(arg);
+        }
+    }
+    None
+}
+
+/// What is wrong with a server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ServerProblem {
+    /// The command is not on `PATH`, or the absolute path does not exist.
+    MissingBinary {
+        /// The configured command.
+        command: String,
+    },
+    /// The package could not be found locally or in its registry.
+    UnresolvedPackage {
+        /// The package.
+        package: PackageRef,
+    },
+    /// The package has no fixed version, so each start may run a
+    /// different release.
+    UnpinnedPackage {
+        /// The package.
+        package: PackageRef,
+    },
+    /// The working directory does not exist.
+    MissingCwd {
+        /// The directory, resolved against the project root.
+        cwd: PathBuf,
+    },
+}
+
+/// A problem found with a configured MCP server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerAuditIssue {
+    /// The server's name in the config.
+    pub server: String,
+    /// The problem.
+    #[serde(flatten)]
+    pub problem: ServerProblem,
+}
+
+impl ServerAuditIssue {
+    /// Returns `true` if the server cannot start. Unpinned packages still
+    /// start and are only a warning.
+    #[must_use]
+    pub fn is_error(&self) -> bool {
+        !matches!(self.problem, ServerProblem::UnpinnedPackage { .. })
+    }
+}
+
+impl fmt::Display for ServerAuditIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.server)?;
+        match &self.problem {
+            ServerProblem::MissingBinary { command } => {
+                write!(f, "command `{command}` not found")
+            }
+            ServerProblem::UnresolvedPackage { package } => {
+                write!(f, "package `{package}` not found")
+            }
+            ServerProblem::UnpinnedPackage { package } => {
+                write!(f, "package `{package}` has no pinned version")
+            }
+            ServerProblem::MissingCwd { cwd } => {
+                write!(f, "working directory {} does not exist", cwd.display())
+            }
+        }
+    }
+}
+
+/// Audits every enabled stdio server configured for `harness` in `scope`.
+///
+/// Reports commands missing from `PATH`, absolute commands and working
+/// directories that do not exist, and `npx`/`uvx` packages without a
+/// pinned version. Packages are not looked up in their registry; use
+/// [`audit_mcp_servers_with`] for that. A scope without an MCP config file
+/// has no issues.
+///
+/// # Errors
+///
+/// Returns an error if the MCP config cannot be located or parsed.
+pub fn audit_mcp_servers(harness: &Harness, scope: &Scope) -> Result<Vec<ServerAuditIssue>> {
+    audit(harness, scope, None)
+}
+
+/// Like [`audit_mcp_servers`], also reporting `npx`/`uvx` packages that
+/// are not installed locally and for which `resolve` returns `false`.
+///
+/// `resolve` is only called for packages not found in the project's
+/// `node_modules`, the `npx` cache or the `uv` tools directory, and
+/// typically asks the package registry.
+///
+/// # Errors
+///
+/// Returns an error if the MCP config cannot be located or parsed.
+pub fn audit_mcp_servers_with(
+    harness: &Harness,
+    scope: &Scope,
+    resolve: impl Fn(&PackageRef) -> bool,
+) -> Result<Vec<ServerAuditIssue>> {
+    audit(harness, scope, Some(&resolve))
+}
+
+fn audit(
+    harness: &Harness,
+    scope: &Scope,
+    resolve: Option<&dyn Fn(&PackageRef) -> bool>,
+) -> Result<Vec<ServerAuditIssue>> {
+    let servers = match harness.read_mcp_servers(scope) {
+        Ok(servers) => servers,
+        Err(Error::NotFound(_)) => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    let stdio: Vec<(String, StdioMcpServer)> = servers
+        .into_iter()
+        .filter_map(|(name, server)| match server {
+            McpServer::Stdio(stdio) if stdio.enabled => Some((name, stdio)),
+            _ => None,
+        })
+        .collect();
+    let commands: Vec<&str> = stdio
+        .iter()
+        .map(|(_, server)| server.command.as_str())
+        .filter(|command| !Path::new(command).is_absolute())
+        .collect();
+    let found = find_binaries(&commands);
+    let reason = harness.access_reason(None, "server audit");
+
+    let mut issues = Vec::new();
+    for (name, server) in &stdio {
+        let mut report = |problem| {
+            issues.push(ServerAuditIssue {
+                server: name.clone(),
+                problem,
+            });
+        };
+        let command = Path::new(&server.command);
+        let present = if server.command.contains('$') {
+            true
+        } else if command.is_absolute() {
+            exists_for(command, reason)
+        } else {
+            found
+                .get(server.command.as_str())
+                .is_some_and(Option::is_some)
+        };
+        if !present {
+            report(ServerProblem::MissingBinary {
+                command: server.command.clone(),
+            });
+        }
+
+        if let Some(package) = PackageRef::from_server(server) {
+            if !package.is_pinned() {
+                report(ServerProblem::UnpinnedPackage {
+                    package: package.clone(),
+                });
+            }
+            if let Some(resolve) = resolve
+                && !package.resolves_locally(scope)
+                && !resolve(&package)
+            {
+                report(ServerProblem::UnresolvedPackage { package });
+            }
+        }
+
+        if let Some(cwd) = &server.cwd {
+            let cwd = expand_tilde(cwd);
+            let cwd = match scope {
+                Scope::Project(root) if cwd.is_relative() => root.join(cwd),
+                _ => cwd,
+            };
+            if !cwd.to_string_lossy().contains('$') && !current().is_dir(&cwd) {
+                report(ServerProblem::MissingCwd { cwd });
+            }
+        }
+    }
+    Ok(issues)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+    use crate::types::HarnessKind;
+
+    fn stdio(command: &str, args: &[&str]) -> StdioMcpServer {
+        serde_json::from_value(serde_json::json!({ "command": command, "args": args })).unwrap()
+    }
+
+    #[test]
+    fn parses_runner_package_arguments() {
+        let npm = PackageRef::from_server(&stdio("npx", &["-y", "@scope/pkg@^1.0", "--port"]));
+        assert_eq!(npm.as_ref().map(|p| p.name.as_str()), Some("@scope/pkg"));
+        assert!(npm.unwrap().is_pinned());
+
+        let npm = PackageRef::from_server(&stdio("npx", &["--package=@scope/pkg", "pkg-bin"]));
+        assert_eq!(npm.as_ref().map(|p| p.name.as_str()), Some("@scope/pkg"));
+        assert!(!npm.unwrap().is_pinned());
+
+        let pypi = PackageRef::from_server(&stdio(
+            "/usr/bin/uvx",
+            &[
+                "--python",
+                "3.12",
+                "--from",
+                "mcp-git[extra]==0.6",
+                "mcp-server-git",
+            ],
+        ))
+        .unwrap();
+        assert_eq!(pypi.name, "mcp-git");
+        assert_eq!(pypi.to_string(), "mcp-git==0.6");
+        assert!(PackageRef::from_server(&stdio("node", &["server.js"])).is_none());
+    }
+
+    #[test]
+    fn reports_missing_binaries_packages_and_directories() {
+        let fs = MemoryFs::new()
+            .with_file(
+                "/project/.mcp.json",
+                r#"{"mcpServers": {
+                    "local": {"command": "/opt/mcp/server", "cwd": "tools"},
+                    "gone": {"command": "/opt/gone/server"},
+                    "npm": {"command": "npx", "args": ["-y", "@acme/mcp"]},
+                    "cached": {"command": "npx", "args": ["@acme/cached@1.0.0"]}
+                }}"#,
+            )
+            .with_file("/opt/mcp/server", "")
+            .with_dir("/project/tools")
+            .with_file(
+                "/fake/home/.npm/_npx/1a2b/node_modules/@acme/cached/package.json",
+                "{}",
+            );
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let scope = Scope::Project(PathBuf::from("/project"));
+
+        let issues = PathContext::fake("/fake")
+            .with_fs(fs)
+            .scope(|| audit_mcp_servers_with(&harness, &scope, |_| false))
+            .unwrap();
+        let found: Vec<String> = issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            found,
+            [
+                "cached: command `npx` not found",
+                "gone: command `/opt/gone/server` not found",
+                "npm: command `npx` not found",
+                "npm: package `@acme/mcp` has no pinned version",
+                "npm: package `@acme/mcp` not found",
+            ]
+        );
+        assert!(!issues[3].is_error());
+
+        let scope = Scope::Project(PathBuf::from("/elsewhere"));
+        let issues = PathContext::fake("/fake")
+            .scope(|| audit_mcp_servers(&harness, &scope))
+            .unwrap();
+        assert!(issues.is_empty());
+    }
+}
@@ -1,83 +1,503 @@
 //! Skill file parsing utilities.
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::target::Platforms;
 use crate::{Error, Result};
 
 /// Parsed frontmatter result.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Frontmatter<'a> {
-    /// Parsed YAML frontmatter, if present.
+    /// Parsed frontmatter, if present. TOML frontmatter is converted to the
+    /// equivalent YAML value.
     pub yaml: Option<serde_yaml::Value>,
+    /// The syntax the frontmatter was written in, `None` if there is none.
+    pub syntax: Option<FrontmatterSyntax>,
     /// The markdown body after the frontmatter.
     pub body: &'a str,
 }
 
+/// The syntax of a frontmatter block, as detected by [`parse_frontmatter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FrontmatterSyntax {
+    /// A single YAML document between `---` lines.
+    Yaml,
+    /// Several YAML documents between `---` lines, separated by `...` or a
+    /// `--- ` line with content after the marker.
+    YamlMultiDocument,
+    /// TOML between `+++` lines, as used by Hugo and Zola.
+    Toml,
+}
+
+/// Locale used when a requested locale has no translation.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Frontmatter text that is either one string or a map of translations.
+///
+/// ```yaml
+/// description:
+///   en: Fill in PDF forms
+///   fr: Remplir des formulaires PDF
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LocalizedText {
+    /// The same text in every locale.
+    Plain(String),
+    /// Text by locale tag, such as `en` or `pt-BR`.
+    Localized(BTreeMap<String, String>),
+}
+
+impl LocalizedText {
+    /// Returns the text for `locale`, falling back as described in
+    /// [`resolve_locale`]. A plain string is returned for every locale.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::skill::LocalizedText;
+    ///
+    /// let text = LocalizedText::Localized(
+    ///     [("en".into(), "Hello".into()), ("fr".into(), "Bonjour".into())].into(),
+    /// );
+    /// assert_eq!(text.resolve(Some("fr-CA")), Some("Bonjour"));
+    /// assert_eq!(text.resolve(Some("ja")), Some("Hello"));
+    /// assert_eq!(text.resolve(None), Some("Hello"));
+    /// ```
+    #[must_use]
+    pub fn resolve(&self, locale: Option<&str>) -> Option<&str> {
+        match self {
+            Self::Plain(text) => Some(text),
+            Self::Localized(texts) => resolve_locale(texts, locale),
+        }
+    }
+
+    /// Iterates every translation with its locale, `None` for plain text.
+    pub fn variants(&self) -> Box<dyn Iterator<Item = (Option<&str>, &str)> + '_> {
+        match self {
+            Self::Plain(text) => Box::new(std::iter::once((None, text.as_str()))),
+            Self::Localized(texts) => Box::new(
+                texts
+                    .iter()
+                    .map(|(locale, text)| (Some(locale.as_str()), text.as_str())),
+            ),
+        }
+    }
+}
+
+impl From<&str> for LocalizedText {
+    fn from(text: &str) -> Self {
+        Self::Plain(text.to_string())
+    }
+}
+
+impl From<String> for LocalizedText {
+    fn from(text: String) -> Self {
+        Self::Plain(text)
+    }
+}
+
+/// Picks the translation for `locale` from `texts`.
+///
+/// Tries the exact tag (ignoring case), then its language (`fr` for
+/// `fr-CA`), then [`DEFAULT_LOCALE`], then the first locale in order.
+/// Returns `None` only if `texts` is empty.
+#[must_use]
+pub fn resolve_locale<'a>(
+    texts: &'a BTreeMap<String, String>,
+    locale: Option<&str>,
+) -> Option<&'a str> {
+    let find = |tag: &str| {
+        texts
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(tag))
+            .map(|(_, text)| text.as_str())
+    };
+    let requested = locale.and_then(|locale| {
+        find(locale).or_else(|| {
+            let language = locale.split(['-', '_']).next()?;
+            find(language)
+        })
+    });
+    requested
+        .or_else(|| find(DEFAULT_LOCALE))
+        .or_else(|| texts.values().next().map(String::as_str))
+}
+
 /// A parsed skill file with typed frontmatter fields.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Skill {
     /// The skill name (required).
     pub name: String,
-    /// Optional description of the skill.
+    /// Optional name shown to users, possibly translated.
+    #[serde(
+        default,
+        rename = "display-name",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub display_name: Option<LocalizedText>,
+    /// Optional description of the skill, possibly translated.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+    pub description: Option<LocalizedText>,
     /// Trigger phrases that activate this skill.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub triggers: Vec<String>,
+    /// Platforms the skill works on; empty means all.
+    #[serde(default, skip_serializing_if = "Platforms::is_empty")]
+    pub platforms: Platforms,
     /// The markdown body content.
     #[serde(skip)]
     pub body: String,
-    /// Additional frontmatter fields not captured above.
+    /// Additional frontmatter fields not captured above, sorted by key.
     #[serde(flatten)]
-    pub metadata: HashMap<String, serde_yaml::Value>,
+    pub metadata: BTreeMap<String, serde_yaml::Value>,
 }
 
-/// Parse YAML frontmatter from markdown content.
+/// Parse frontmatter from markdown content.
+///
+/// Accepts YAML between `---` lines and TOML between `+++` lines. A YAML
+/// block holding several documents yields its first mapping, or its first
+/// document if none is a mapping. [`Frontmatter::syntax`] records which
+/// form was found.
 ///
 /// # Errors
 ///
-/// Returns `Error::YamlParse` if frontmatter exists but contains invalid YAML.
+/// Returns `Error::YamlParse` or `Error::TomlParse` if frontmatter exists
+/// but is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::skill::{FrontmatterSyntax, parse_frontmatter};
+///
+/// let fm = parse_frontmatter("+++\nname = \"demo\"\n+++\n# Demo").unwrap();
+/// assert_eq!(fm.syntax, Some(FrontmatterSyntax::Toml));
+/// assert_eq!(fm.yaml.unwrap()["name"], "demo");
+/// ```
 pub fn parse_frontmatter(content: &str) -> Result<Frontmatter<'_>> {
-    let (opener, line_ending) = if content.starts_with("---\r\n") {
-        ("---\r\n", "\r\n")
-    } else if content.starts_with("---\n") {
-        ("---\n", "\n")
-    } else {
+    if let Some((yaml_content, body)) = split_frontmatter(content) {
+        let (yaml, syntax) = parse_yaml_documents(yaml_content)?;
         return Ok(Frontmatter {
-            yaml: None,
-            body: content,
+            yaml: Some(yaml),
+            syntax: Some(syntax),
+            body,
+        });
+    }
+    if let Some((toml_content, body)) = split_block(content, "+++") {
+        let table: toml::Table = toml::from_str(toml_content)?;
+        return Ok(Frontmatter {
+            yaml: Some(toml_to_yaml(toml::Value::Table(table))),
+            syntax: Some(FrontmatterSyntax::Toml),
+            body,
         });
+    }
+    Ok(Frontmatter {
+        yaml: None,
+        syntax: None,
+        body: content,
+    })
+}
+
+/// Parses every YAML document in `yaml` and picks the first mapping.
+fn parse_yaml_documents(yaml: &str) -> Result<(serde_yaml::Value, FrontmatterSyntax)> {
+    let mut documents = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(yaml) {
+        documents.push(serde_yaml::Value::deserialize(document)?);
+    }
+    if documents.len() <= 1 {
+        let value = documents.pop().unwrap_or(serde_yaml::Value::Null);
+        return Ok((value, FrontmatterSyntax::Yaml));
+    }
+    let first = documents
+        .iter()
+        .position(serde_yaml::Value::is_mapping)
+        .unwrap_or(0);
+    Ok((
+        documents.swap_remove(first),
+        FrontmatterSyntax::YamlMultiDocument,
+    ))
+}
+
+/// Converts TOML to YAML, writing dates and times as strings.
+fn toml_to_yaml(value: toml::Value) -> serde_yaml::Value {
+    match value {
+        toml::Value::String(s) => serde_yaml::Value::String(s),
+        toml::Value::Integer(i) => i.into(),
+        toml::Value::Float(f) => f.into(),
+        toml::Value::Boolean(b) => b.into(),
+        toml::Value::Datetime(dt) => serde_yaml::Value::String(dt.to_string()),
+        toml::Value::Array(items) => items.into_iter().map(toml_to_yaml).collect(),
+        toml::Value::Table(table) => serde_yaml::Value::Mapping(
+            table
+                .into_iter()
+                .map(|(key, value)| (key.into(), toml_to_yaml(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Splits markdown content into its raw frontmatter block and body
+/// without parsing any YAML.
+///
+/// Returns `None` if the content has no closed `---` frontmatter block.
+/// A leading byte order mark is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::skill::split_frontmatter;
+///
+/// let (yaml, body) = split_frontmatter("---\nname: demo\n---\n# Demo").unwrap();
+/// assert_eq!(yaml, "name: demo");
+/// assert_eq!(body, "# Demo");
+/// ```
+#[must_use]
+pub fn split_frontmatter(content: &str) -> Option<(&str, &str)> {
+    split_block(content, "---")
+}
+
+/// Splits off a block opened and closed by `delimiter` lines.
+fn split_block<'a>(content: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let rest = content.strip_prefix(delimiter)?;
+    let line_ending = if rest.starts_with("\r\n") {
+        "\r\n"
+    } else if rest.starts_with('\n') {
+        "\n"
+    } else {
+        return None;
     };
 
-    let after_opener = &content[opener.len()..];
-    let empty_closer = format!("---{line_ending}");
-    let closer = format!("{line_ending}---{line_ending}");
-    let closer_eof = format!("{line_ending}---");
+    let after_opener = &rest[line_ending.len()..];
+    let empty_closer = format!("{delimiter}{line_ending}");
+    let closer = format!("{line_ending}{delimiter}{line_ending}");
+    let closer_eof = format!("{line_ending}{delimiter}");
 
-    let (yaml_content, body) = if after_opener.starts_with(&empty_closer) {
-        ("", &after_opener[empty_closer.len()..])
+    if after_opener.starts_with(&empty_closer) {
+        Some(("", &after_opener[empty_closer.len()..]))
     } else if let Some(pos) = after_opener.find(&closer) {
-        (&after_opener[..pos], &after_opener[pos + closer.len()..])
+        Some((&after_opener[..pos], &after_opener[pos + closer.len()..]))
     } else if after_opener.ends_with(&closer_eof) {
-        (&after_opener[..after_opener.len() - closer_eof.len()], "")
-    } else if after_opener == "---" {
-        ("", "")
+        Some((&after_opener[..after_opener.len() - closer_eof.len()], ""))
+    } else if after_opener == delimiter {
+        Some(("", ""))
     } else {
-        return Ok(Frontmatter {
-            yaml: None,
+        None
+    }
+}
+
+/// The `name` and `description` of a skill, read without a full YAML
+/// parse.
+///
+/// Produced by [`scan_skill_header`]. The rest of the frontmatter is kept
+/// as raw text and only parsed on demand by [`parse_yaml`](Self::parse_yaml).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillHeader<'a> {
+    /// The `name` field, if present and a string.
+    pub name: Option<Cow<'a, str>>,
+    /// The `description` field, if present and a string, or its
+    /// [`DEFAULT_LOCALE`] translation.
+    pub description: Option<Cow<'a, str>>,
+    /// Every translation when `description` is a locale map, otherwise
+    /// empty.
+    pub descriptions: BTreeMap<String, String>,
+    /// The raw frontmatter YAML, empty if there is none.
+    pub yaml: &'a str,
+    /// The markdown body after the frontmatter.
+    pub body: &'a str,
+}
+
+impl SkillHeader<'_> {
+    /// Returns `true` if the frontmatter has a top-level `key`.
+    #[must_use]
+    pub fn has_key(&self, key: &str) -> bool {
+        top_level_entries(self.yaml).any(|(k, _, _)| k == key)
+    }
+
+    /// Iterates the top-level keys of the frontmatter in file order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        top_level_entries(self.yaml).map(|(key, _, _)| key)
+    }
+
+    /// Parses the full frontmatter.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::YamlParse` if the frontmatter is invalid YAML.
+    pub fn parse_yaml(&self) -> Result<serde_yaml::Value> {
+        Ok(parse_yaml_documents(self.yaml)?.0)
+    }
+}
+
+/// Reads a skill's `name` and `description` by scanning its frontmatter
+/// line by line.
+///
+/// Simple scalar values on a single line, plain or quoted, are borrowed
+/// straight from `content`. Anything else, such as block scalars, escape
+/// sequences, multi-line values, or non-string scalars, falls back to a
+/// full YAML parse, so the result always matches [`parse_frontmatter`].
+/// Errors elsewhere in the frontmatter are not detected unless a fallback
+/// parse happens; use [`SkillHeader::parse_yaml`] to check. TOML
+/// frontmatter is not scanned and reads as no frontmatter.
+///
+/// # Errors
+///
+/// Returns `Error::YamlParse` if a fallback parse is needed and the
+/// frontmatter is invalid YAML.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::skill::scan_skill_header;
+///
+/// let header = scan_skill_header("---\nname: pdf\ndescription: \"Edit PDFs\"\n---\nBody").unwrap();
+/// assert_eq!(header.name.as_deref(), Some("pdf"));
+/// assert_eq!(header.description.as_deref(), Some("Edit PDFs"));
+/// ```
+pub fn scan_skill_header(content: &str) -> Result<SkillHeader<'_>> {
+    let Some((yaml, body)) = split_frontmatter(content) else {
+        return Ok(SkillHeader {
+            name: None,
+            description: None,
+            descriptions: BTreeMap::new(),
+            yaml: "",
             body: content,
         });
     };
 
-    let yaml_value: serde_yaml::Value = serde_yaml::from_str(yaml_content)?;
-    Ok(Frontmatter {
-        yaml: Some(yaml_value),
+    let mut name = Scan::Missing;
+    let mut description = Scan::Missing;
+    for (key, value, continued) in top_level_entries(yaml) {
+        let slot = match key {
+            "name" => &mut name,
+            "description" => &mut description,
+            _ => continue,
+        };
+        *slot = match slot {
+            Scan::Missing if !continued => simple_scalar(value).map_or(Scan::Complex, Scan::Found),
+            _ => Scan::Complex,
+        };
+    }
+
+    // A later document may hold the fields, so several documents always
+    // take the full parse.
+    let multi_document = yaml
+        .lines()
+        .any(|line| line.starts_with("...") || line.starts_with("--- "));
+    if multi_document || matches!(name, Scan::Complex) || matches!(description, Scan::Complex) {
+        let (value, _) = parse_yaml_documents(yaml)?;
+        let field = |key: &str| {
+            value
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| Cow::Owned(s.to_string()))
+        };
+        let text = value
+            .get("description")
+            .and_then(|v| serde_yaml::from_value::<LocalizedText>(v.clone()).ok());
+        let description = text
+            .as_ref()
+            .and_then(|text| text.resolve(None))
+            .map(|s| Cow::Owned(s.to_string()));
+        let descriptions = match text {
+            Some(LocalizedText::Localized(texts)) => texts,
+            _ => BTreeMap::new(),
+        };
+        return Ok(SkillHeader {
+            name: field("name"),
+            description,
+            descriptions,
+            yaml,
+            body,
+        });
+    }
+
+    Ok(SkillHeader {
+        name: name.found(),
+        description: description.found(),
+        descriptions: BTreeMap::new(),
+        yaml,
         body,
     })
 }
 
+enum Scan<'a> {
+    Missing,
+    Found(&'a str),
+    Complex,
+}
+
+impl<'a> Scan<'a> {
+    fn found(self) -> Option<Cow<'a, str>> {
+        match self {
+            Self::Found(value) => Some(Cow::Borrowed(value)),
+            _ => None,
+        }
+    }
+}
+
+/// Iterates `(key, raw value, continued)` for each top-level `key: value`
+/// line, where `continued` means indented lines follow the entry.
+pub(crate) fn top_level_entries(yaml: &str) -> impl Iterator<Item = (&str, &str, bool)> {
+    let mut lines = yaml
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .peekable();
+    std::iter::from_fn(move || {
+        loop {
+            let line = lines.next()?;
+            if line.is_empty() || line.starts_with([' ', '\t', '#', '-']) {
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let continued = lines
+                .peek()
+                .is_some_and(|next| next.starts_with([' ', '\t']) && !next.trim().is_empty());
+            return Some((key.trim_end(), value, continued));
+        }
+    })
+}
+
+/// Returns the string a single-line YAML scalar denotes, if it can be read
+/// without a YAML parser and is a string.
+fn simple_scalar(raw: &str) -> Option<&str> {
+    let value = raw.trim();
+    if let Some(inner) = value.strip_prefix('"') {
+        let inner = inner.strip_suffix('"')?;
+        return (!inner.contains(['"', '\\'])).then_some(inner);
+    }
+    if let Some(inner) = value.strip_prefix('\'') {
+        let inner = inner.strip_suffix('\'')?;
+        return (!inner.contains('\'')).then_some(inner);
+    }
+    if !raw.is_empty() && !raw.starts_with([' ', '\t']) {
+        return None;
+    }
+
+    let value = value.split_once(" #").map_or(value, |(v, _)| v.trim_end());
+    let special = value.is_empty()
+        || value.starts_with([
+            '|', '>', '[', ']', '{', '}', '&', '*', '!', '%', '@', '`', '#', ',', '?', ':', '-',
+            '"', '\'',
+        ])
+        || value.contains(": ")
+        || value.ends_with(':')
+        || value.contains('\t')
+        || value.starts_with(|c: char| c.is_ascii_digit() || c == '+' || c == '.')
+        || value.parse::<f64>().is_ok()
+        || matches!(
+            value.to_ascii_lowercase().as_str(),
+            "~" | "null" | "true" | "false" | "yes" | "no" | "on" | "off" | ".inf" | ".nan"
+        );
+    (!special).then_some(value)
+}
+
 /// Parse a skill file from markdown content with YAML frontmatter.
 ///
 /// # Errors
@@ -97,6 +517,22 @@ pub fn parse_skill(content: &str) -> Result<Skill> {
 }
 
 impl Skill {
+    /// Returns the description in `locale`, or the default one for `None`.
+    #[must_use]
+    pub fn description(&self, locale: Option<&str>) -> Option<&str> {
+        self.description.as_ref()?.resolve(locale)
+    }
+
+    /// Returns the display name in `locale`, falling back to
+    /// [`name`](Self::name).
+    #[must_use]
+    pub fn display_name(&self, locale: Option<&str>) -> &str {
+        self.display_name
+            .as_ref()
+            .and_then(|name| name.resolve(locale))
+            .unwrap_or(&self.name)
+    }
+
     /// Convert the skill back to markdown format with YAML frontmatter.
     #[must_use]
     pub fn to_markdown(&self) -> String {
@@ -138,6 +574,118 @@ mod tests {
         assert_eq!(result.body, "Body content");
     }
 
+    #[test]
+    fn split_frontmatter_without_block_is_none() {
+        assert_eq!(split_frontmatter("# Title"), None);
+        assert_eq!(split_frontmatter("---\nname: open"), None);
+        assert_eq!(
+            split_frontmatter("---\r\nname: x\r\n---\r\nBody"),
+            Some(("name: x", "Body"))
+        );
+    }
+
+    #[test]
+    fn split_frontmatter_ignores_bom() {
+        assert_eq!(
+            split_frontmatter("\u{feff}---\nname: x\n---\nBody"),
+            Some(("name: x", "Body"))
+        );
+    }
+
+    #[test]
+    fn parse_frontmatter_reads_toml() {
+        let content = "+++\r\nname = \"pdf\"\r\ntags = [\"docs\"]\r\nupdated = 2024-05-01\r\n[extra]\r\nlevel = 2\r\n+++\r\nBody";
+        let fm = parse_frontmatter(content).unwrap();
+        assert_eq!(fm.syntax, Some(FrontmatterSyntax::Toml));
+        assert_eq!(fm.body, "Body");
+        let yaml = fm.yaml.unwrap();
+        assert_eq!(yaml["name"], "pdf");
+        assert_eq!(yaml["tags"][0], "docs");
+        assert_eq!(yaml["updated"], "2024-05-01");
+        assert_eq!(yaml["extra"]["level"], 2);
+
+        assert!(matches!(
+            parse_frontmatter("+++\nname = \n+++\n"),
+            Err(Error::TomlParse(_))
+        ));
+    }
+
+    #[test]
+    fn parse_frontmatter_takes_first_yaml_mapping() {
+        let content =
+            "---\njust a note\n--- # second\nname: pdf\n...\n--- # third\nname: other\n---\nBody";
+        let fm = parse_frontmatter(content).unwrap();
+        assert_eq!(fm.syntax, Some(FrontmatterSyntax::YamlMultiDocument));
+        assert_eq!(fm.yaml.unwrap()["name"], "pdf");
+        assert_eq!(fm.body, "Body");
+
+        let header = scan_skill_header(content).unwrap();
+        assert_eq!(header.name.as_deref(), Some("pdf"));
+
+        let single = parse_frontmatter("---\nname: pdf\n---\n").unwrap();
+        assert_eq!(single.syntax, Some(FrontmatterSyntax::Yaml));
+        assert_eq!(parse_frontmatter("Body").unwrap().syntax, None);
+    }
+
+    #[test]
+    fn scan_skill_header_borrows_simple_values() {
+        let content = "---\nname: pdf-tools # comment\ndescription: 'Fill PDF forms'\nallowed-tools: [Read]\n---\nBody";
+        let header = scan_skill_header(content).unwrap();
+        assert!(matches!(header.name, Some(Cow::Borrowed("pdf-tools"))));
+        assert!(matches!(
+            header.description,
+            Some(Cow::Borrowed("Fill PDF forms"))
+        ));
+        assert!(header.has_key("allowed-tools"));
+        assert!(!header.has_key("triggers"));
+        assert_eq!(
+            header.keys().collect::<Vec<_>>(),
+            ["name", "description", "allowed-tools"]
+        );
+        assert_eq!(header.body, "Body");
+    }
+
+    #[test]
+    fn scan_skill_header_matches_full_parse() {
+        let cases = [
+            "---\nname: plain\ndescription: see http://x.y/z#frag\n---\n",
+            "---\nname: \"esc\\\"aped\"\ndescription: >\n  folded\n  text\n---\n",
+            "---\nname: 'it''s'\ndescription: |\n  literal\n---\n",
+            "---\nname: multi\n  line\ndescription: ok\n---\n",
+            "---\nname: 42\ndescription: true\n---\n",
+            "---\nname: null\ndescription: ~\n---\n",
+            "---\nmetadata:\n  name: nested\n---\n",
+            "---\r\nname: crlf\r\ndescription: \"quoted\"\r\n---\r\n",
+            "---\n---\nempty",
+            "no frontmatter",
+        ];
+        for content in cases {
+            let header = scan_skill_header(content).unwrap();
+            let yaml = parse_frontmatter(content).unwrap().yaml;
+            let field = |key: &str| {
+                yaml.as_ref()
+                    .and_then(|y| y.get(key))
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+            };
+            assert_eq!(
+                header.name.as_deref(),
+                field("name").as_deref(),
+                "{content:?}"
+            );
+            assert_eq!(
+                header.description.as_deref(),
+                field("description").as_deref(),
+                "{content:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn scan_skill_header_reports_yaml_errors_on_fallback() {
+        assert!(scan_skill_header("---\nname: [unclosed\n---\n").is_err());
+    }
+
     #[test]
     fn returns_error_for_malformed_yaml() {
         let content = "---\ninvalid: yaml: content:\n---\nBody";
@@ -185,7 +733,7 @@ mod tests {
         let skill = parse_skill(content).unwrap();
 
         assert_eq!(skill.name, "my-skill");
-        assert_eq!(skill.description, Some("A test skill".to_string()));
+        assert_eq!(skill.description, Some("A test skill".into()));
         assert_eq!(skill.triggers, vec!["hello", "hi"]);
         assert_eq!(skill.body, "# Body content\n");
         assert!(skill.metadata.contains_key("custom_key"));
@@ -247,4 +795,56 @@ mod tests {
         assert_eq!(skill.triggers, reparsed.triggers);
         assert_eq!(skill.body, reparsed.body);
     }
+
+    #[test]
+    fn parse_skill_with_localized_description() {
+        let content = "---\nname: pdf\ndisplay-name:\n  en: PDF tools\n  ja: PDFツール\ndescription:\n  en: Fill PDF forms\n  fr: Remplir des formulaires PDF\n---\n";
+        let skill = parse_skill(content).unwrap();
+
+        assert_eq!(
+            skill.description(Some("fr")),
+            Some("Remplir des formulaires PDF")
+        );
+        assert_eq!(
+            skill.description(Some("fr-BE")),
+            Some("Remplir des formulaires PDF")
+        );
+        assert_eq!(skill.description(Some("de")), Some("Fill PDF forms"));
+        assert_eq!(skill.description(None), Some("Fill PDF forms"));
+        assert_eq!(skill.display_name(Some("ja")), "PDFツール");
+        assert_eq!(skill.display_name(Some("fr")), "PDF tools");
+        assert!(skill.metadata.is_empty());
+
+        let reparsed = parse_skill(&skill.to_markdown()).unwrap();
+        assert_eq!(reparsed.description, skill.description);
+        assert_eq!(reparsed.display_name, skill.display_name);
+    }
+
+    #[test]
+    fn display_name_defaults_to_name() {
+        let skill = parse_skill("---\nname: plain\ndescription: Plain text\n---\n").unwrap();
+        assert_eq!(skill.display_name(Some("fr")), "plain");
+        assert_eq!(skill.description(Some("fr")), Some("Plain text"));
+    }
+
+    #[test]
+    fn resolve_locale_falls_back_to_first_locale() {
+        let texts: BTreeMap<String, String> = [
+            ("fr".into(), "Bonjour".into()),
+            ("ja".into(), "こんにちは".into()),
+        ]
+        .into();
+        assert_eq!(resolve_locale(&texts, Some("JA")), Some("こんにちは"));
+        assert_eq!(resolve_locale(&texts, Some("de")), Some("Bonjour"));
+        assert_eq!(resolve_locale(&BTreeMap::new(), Some("de")), None);
+    }
+
+    #[test]
+    fn scan_skill_header_resolves_localized_description() {
+        let header =
+            scan_skill_header("---\nname: pdf\ndescription:\n  fr: Remplir\n  en: Fill\n---\n")
+                .unwrap();
+        assert_eq!(header.description.as_deref(), Some("Fill"));
+        assert_eq!(header.descriptions.len(), 2);
+    }
 }
@@ -0,0 +1,560 @@
+//! Running checks declared in skill frontmatter.
+//!
+//! A skill can list shell commands under a `tests:` frontmatter key to
+//! prove that the scripts its instructions reference actually work:
+//!
+//! ```yaml
+//! ---
+//! name: pdf-tools
+//! tests:
+//!   - name: extractor runs
+//!     run: python scripts/extract.py --help
+//!     stdout_contains: usage
+//!   - run: test -f templates/report.md
+//! ---
+//! ```
+//!
+//! [`run_skill_tests`] copies the skill directory into a fresh temporary
+//! directory and runs each command there with a minimal environment, so
+//! files a command writes relative to its working directory land in the
+//! copy, and the caller's variables are not passed on. This is not a
+//! security sandbox: commands run with the caller's permissions and can
+//! reach any absolute path, including the installed skill. Commands run
+//! through `sh -c` (`cmd /C` on Windows); on Unix each runs in its own
+//! process group, which is killed when the command exits or times out.
+//!
+//! The skill is read through the current [`Fs`](crate::fs::Fs); the copy
+//! is always written to the real temporary directory, where the commands
+//! run.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+use crate::skill::parse_frontmatter;
+
+/// Seconds a test command may run before it is killed.
+pub const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// How long output is still collected after a command exits or is
+/// killed. Processes it started in the background can hold its pipes open
+/// long after, so output is not read to the end.
+const OUTPUT_GRACE: Duration = Duration::from_millis(200);
+
+/// A single check from a skill's `tests:` frontmatter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillTest {
+    /// Optional label; defaults to the command in reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Shell command, run from the skill directory.
+    pub run: String,
+    /// Expected exit code.
+    #[serde(default)]
+    pub exit_code: i32,
+    /// Text that must appear in standard output.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdout_contains: Option<String>,
+    /// Text that must appear in standard error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr_contains: Option<String>,
+    /// Seconds before the command is killed; defaults to
+    /// [`DEFAULT_TIMEOUT_SECS`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+}
+
+impl SkillTest {
+    /// Returns the test's name, or its command if unnamed.
+    #[must_use]
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.run)
+    }
+}
+
+/// The result of running one [`SkillTest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TestOutcome {
+    /// The test that was run.
+    pub test: SkillTest,
+    /// Exit code, or `None` if the command was killed or failed to start.
+    pub exit_code: Option<i32>,
+    /// Standard output, decoded lossily as UTF-8.
+    pub stdout: String,
+    /// Standard error, decoded lossily as UTF-8.
+    pub stderr: String,
+    /// Why the test failed; empty if it passed.
+    pub failures: Vec<String>,
+}
+
+impl TestOutcome {
+    /// Returns `true` if every expectation held.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Test results for one skill.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkillTestReport {
+    /// The skill's `name` frontmatter field, or its directory name.
+    pub skill: String,
+    /// The skill directory the tests were declared in.
+    pub path: PathBuf,
+    /// One outcome per declared test, in declaration order.
+    pub outcomes: Vec<TestOutcome>,
+}
+
+impl SkillTestReport {
+    /// Returns `true` if every test passed, including when there are none.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.outcomes.iter().all(TestOutcome::passed)
+    }
+
+    /// Returns the outcomes of failed tests.
+    pub fn failures(&self) -> impl Iterator<Item = &TestOutcome> {
+        self.outcomes.iter().filter(|outcome| !outcome.passed())
+    }
+}
+
+/// Parses the `tests:` section of a skill file.
+///
+/// Returns an empty list if the skill declares no tests.
+///
+/// # Errors
+///
+/// Returns `Error::YamlParse` if the frontmatter is invalid or `tests` is
+/// not a list of tests.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::skilltest::parse_skill_tests;
+///
+/// let content = "---\nname: demo\ntests:\n  - run: ./check.sh\n    exit_code: 0\n---\n";
+/// let tests = parse_skill_tests(content).unwrap();
+/// assert_eq!(tests[0].run, "./check.sh");
+/// ```
+pub fn parse_skill_tests(content: &str) -> Result<Vec<SkillTest>> {
+    let frontmatter = parse_frontmatter(content)?;
+    match frontmatter.yaml.as_ref().and_then(|yaml| yaml.get("tests")) {
+        Some(tests) => Ok(serde_yaml::from_value(tests.clone())?),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Runs the tests declared in `skill_dir/SKILL.md` in a temporary copy of
+/// the skill directory.
+///
+/// Failing tests are reported in the returned [`SkillTestReport`], not as
+/// errors.
+///
+/// # Errors
+///
+/// Returns an error if `SKILL.md` cannot be read or parsed, or the
+/// skill cannot be copied to a temporary directory.
+pub fn run_skill_tests(skill_dir: &Path) -> Result<SkillTestReport> {
+    let content = crate::fs::read_text(&skill_dir.join("SKILL.md"))?.text;
+    let tests = parse_skill_tests(&content)?;
+    let skill = parse_frontmatter(&content)?
+        .yaml
+        .as_ref()
+        .and_then(|yaml| yaml.get("name"))
+        .and_then(|name| name.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| {
+            skill_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+
+    let mut outcomes = Vec::with_capacity(tests.len());
+    if !tests.is_empty() {
+        let sandbox = tempfile::Builder::new()
+            .prefix("harness-locate-skilltest-")
+            .tempdir()?;
+        copy_dir(&*crate::fs::current(), skill_dir, sandbox.path())?;
+        for test in tests {
+            outcomes.push(run_test(test, sandbox.path()));
+        }
+    }
+
+    Ok(SkillTestReport {
+        skill,
+        path: skill_dir.to_path_buf(),
+        outcomes,
+    })
+}
+
+/// Runs the tests of every skill under `skills_dir`, one report per
+/// subdirectory containing a `SKILL.md`, sorted by path.
+///
+/// # Errors
+///
+/// Returns an error if `skills_dir` cannot be read or a skill's tests
+/// cannot be run; see [`run_skill_tests`].
+pub fn run_all_skill_tests(skills_dir: &Path) -> Result<Vec<SkillTestReport>> {
+    let fs = crate::fs::current();
+    fs.read_dir(skills_dir)?
+        .into_iter()
+        .filter(|path| fs.is_dir(path) && fs.exists(&path.join("SKILL.md")))
+        .map(|dir| run_skill_tests(&dir))
+        .collect()
+}
+
+fn run_test(test: SkillTest, cwd: &Path) -> TestOutcome {
+    let timeout = Duration::from_secs(test.timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS));
+    let (exit_code, stdout, stderr, mut failures) = match execute(&test.run, cwd, timeout) {
+        Ok(Execution::Exited(code, stdout, stderr)) => (code, stdout, stderr, Vec::new()),
+        Ok(Execution::TimedOut(stdout, stderr)) => (
+            None,
+            stdout,
+            stderr,
+            vec![format!("timed out after {}s", timeout.as_secs())],
+        ),
+        Err(e) => (
+            None,
+            String::new(),
+            String::new(),
+            vec![format!("failed to start: {e}")],
+        ),
+    };
+
+    if failures.is_empty() && exit_code != Some(test.exit_code) {
+        failures.push(match exit_code {
+            Some(code) => format!("expected exit code {}, got {code}", test.exit_code),
+            None => format!("expected exit code {}, killed by signal", test.exit_code),
+        });
+    }
+    if let Some(expected) = &test.stdout_contains
+        && !stdout.contains(expected.as_str())
+    {
+        failures.push(format!("stdout does not contain {expected:?}"));
+    }
+    if let Some(expected) = &test.stderr_contains
+        && !stderr.contains(expected.as_str())
+    {
+        failures.push(format!("stderr does not contain {expected:?}"));
+    }
+
+    TestOutcome {
+        test,
+        exit_code,
+        stdout,
+        stderr,
+        failures,
+    }
+}
+
+enum Execution {
+    Exited(Option<i32>, String, String),
+    TimedOut(String, String),
+}
+
+fn execute(script: &str, cwd: &Path, timeout: Duration) -> std::io::Result<Execution> {
+    let mut command = shell(script);
+    command
+        .current_dir(cwd)
+        .env_clear()
+        .env("HOME", cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(path) = std::env::var_os("PATH") {
+        command.env("PATH", path);
+    }
+    #[cfg(target_os = "windows")]
+    for name in ["SystemRoot", "ComSpec", "PATHEXT"] {
+        if let Some(value) = std::env::var_os(name) {
+            command.env(name, value);
+        }
+    }
+
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+
+    let mut child = command.spawn()?;
+    let stdout = Output::capture(child.stdout.take());
+    let stderr = Output::capture(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            kill_process_group(&child);
+            break Some(status);
+        }
+        if Instant::now() >= deadline {
+            kill_process_group(&child);
+            let _ = child.kill();
+            let _ = child.wait();
+            break None;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    };
+
+    let grace = Instant::now() + OUTPUT_GRACE;
+    let stdout = stdout.finish(grace);
+    let stderr = stderr.finish(grace);
+    Ok(match status {
+        Some(status) => Execution::Exited(status.code(), stdout, stderr),
+        None => Execution::TimedOut(stdout, stderr),
+    })
+}
+
+/// Kills every process left in the process group `child` leads, such as
+/// commands it started in the background.
+#[cfg(unix)]
+fn kill_process_group(child: &Child) {
+    if let Ok(pgid) = libc::pid_t::try_from(child.id()) {
+        // SAFETY: `kill` has no memory-safety preconditions; a negative pid
+        // addresses the group the child was spawned to lead.
+        unsafe {
+            libc::kill(-pgid, libc::SIGKILL);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_: &Child) {}
+
+fn shell(script: &str) -> Command {
+    #[cfg(target_os = "windows")]
+    {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(script);
+        command
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+}
+
+/// A pipe read on a background thread as output arrives.
+struct Output {
+    buf: Arc<Mutex<Vec<u8>>>,
+    reader: JoinHandle<()>,
+}
+
+impl Output {
+    fn capture(pipe: Option<impl Read + Send + 'static>) -> Self {
+        let buf = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&buf);
+        let reader = std::thread::spawn(move || {
+            let Some(mut pipe) = pipe else {
+                return;
+            };
+            let mut chunk = [0; 4096];
+            while let Ok(n @ 1..) = pipe.read(&mut chunk) {
+                if let Ok(mut buf) = sink.lock() {
+                    buf.extend_from_slice(&chunk[..n]);
+                }
+            }
+        });
+        Self { buf, reader }
+    }
+
+    /// Returns the output read so far, waiting until `grace` for the pipe
+    /// to close.
+    fn finish(self, grace: Instant) -> String {
+        while !self.reader.is_finished() && Instant::now() < grace {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        self.buf
+            .lock()
+            .map(|buf| String::from_utf8_lossy(&buf).into_owned())
+            .unwrap_or_default()
+    }
+}
+
+/// Copies the directory `from`, read through `fs`, to `to` on disk.
+fn copy_dir(fs: &dyn crate::fs::Fs, from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for path in fs.read_dir(from)? {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let target = to.join(name);
+        if fs.is_dir(&path) {
+            copy_dir(fs, &path, &target)?;
+        } else {
+            std::fs::write(target, fs.read(&path)?)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn skill_dir(name: &str, skill_md: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "harness-locate-skilltest-src-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("scripts")).unwrap();
+        std::fs::write(dir.join("SKILL.md"), skill_md).unwrap();
+        std::fs::write(dir.join("scripts/hello.sh"), "echo hello from $1\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn parses_tests_with_defaults() {
+        let content = "---\nname: demo\ntests:\n  - name: hello\n    run: echo hi\n    stdout_contains: hi\n---\nBody";
+        let tests = parse_skill_tests(content).unwrap();
+        assert_eq!(tests.len(), 1);
+        assert_eq!(tests[0].label(), "hello");
+        assert_eq!(tests[0].exit_code, 0);
+        assert_eq!(tests[0].stdout_contains.as_deref(), Some("hi"));
+        assert_eq!(tests[0].timeout_secs, None);
+    }
+
+    #[test]
+    fn no_tests_section_is_empty() {
+        assert!(
+            parse_skill_tests("---\nname: demo\n---\n")
+                .unwrap()
+                .is_empty()
+        );
+        assert!(parse_skill_tests("# No frontmatter").unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_tests_section_errors() {
+        assert!(parse_skill_tests("---\ntests: not-a-list\n---\n").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn runs_tests_in_sandbox_copy() {
+        let dir = skill_dir(
+            "pass",
+            "---\nname: greeter\ntests:\n  - run: sh scripts/hello.sh sandbox\n    stdout_contains: hello from sandbox\n  - run: touch created && test -f scripts/hello.sh\n---\n",
+        );
+
+        let report = run_skill_tests(&dir).unwrap();
+        assert_eq!(report.skill, "greeter");
+        assert!(report.passed(), "{:?}", report.outcomes);
+        assert_eq!(report.outcomes.len(), 2);
+        assert!(!dir.join("created").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_failed_expectations() {
+        let dir = skill_dir(
+            "fail",
+            "---\nname: broken\ntests:\n  - run: sh scripts/missing.sh\n  - run: exit 3\n    exit_code: 3\n    stderr_contains: oops\n---\n",
+        );
+
+        let report = run_skill_tests(&dir).unwrap();
+        assert!(!report.passed());
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 2);
+        assert!(failures[0].failures[0].starts_with("expected exit code 0"));
+        assert_eq!(failures[1].exit_code, Some(3));
+        assert_eq!(
+            failures[1].failures,
+            vec!["stderr does not contain \"oops\""]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kills_commands_after_timeout() {
+        let dir = skill_dir(
+            "timeout",
+            "---\nname: slow\ntests:\n  - run: sleep 5\n    timeout_secs: 0\n---\n",
+        );
+
+        let report = run_skill_tests(&dir).unwrap();
+        assert_eq!(report.outcomes[0].exit_code, None);
+        assert!(report.outcomes[0].failures[0].starts_with("timed out"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn timeout_does_not_wait_for_grandchildren() {
+        let dir = skill_dir(
+            "grandchild",
+            "---\nname: slow\ntests:\n  - run: echo hi; sleep 8; echo done\n    timeout_secs: 1\n  - run: sleep 8 & echo started\n    stdout_contains: started\n---\n",
+        );
+
+        let start = Instant::now();
+        let report = run_skill_tests(&dir).unwrap();
+        assert!(
+            start.elapsed() < Duration::from_secs(4),
+            "{:?}",
+            start.elapsed()
+        );
+        assert_eq!(report.outcomes[0].exit_code, None);
+        assert_eq!(report.outcomes[0].stdout, "hi\n");
+        assert!(
+            report.outcomes[1].failures.is_empty(),
+            "{:?}",
+            report.outcomes[1]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn timeout_kills_the_process_group() {
+        let dir = skill_dir(
+            "group",
+            "---\nname: slow\ntests:\n  - run: sleep 30 & echo $!; sleep 8\n    timeout_secs: 1\n---\n",
+        );
+
+        let report = run_skill_tests(&dir).unwrap();
+        let pid = report.outcomes[0].stdout.trim().to_string();
+        assert!(!pid.is_empty());
+        let stat = Path::new("/proc").join(&pid).join("stat");
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let alive = || {
+            std::fs::read_to_string(&stat).is_ok_and(|stat| {
+                stat.rsplit(')')
+                    .next()
+                    .is_some_and(|s| !s.starts_with(" Z"))
+            })
+        };
+        while alive() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(!alive(), "background process {pid} outlived its test");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reads_the_skill_through_current_fs() {
+        let fs = crate::fs::MemoryFs::new().with_file(
+            "/skills/echo/SKILL.md",
+            "---\nname: echo\ntests:\n  - run: cat data.txt\n    stdout_contains: from memory\n---\n",
+        )
+        .with_file("/skills/echo/data.txt", "from memory\n");
+        let ctx = crate::platform::PathContext::fake("/fake").with_fs(fs);
+
+        let reports = ctx
+            .scope(|| run_all_skill_tests(Path::new("/skills")))
+            .unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].skill, "echo");
+        #[cfg(unix)]
+        assert!(reports[0].passed(), "{:?}", reports[0].outcomes);
+    }
+}
@@ -0,0 +1,305 @@
+//! [`proptest`] strategies for generating MCP servers.
+//!
+//! Available with the `test-util` feature, so integrations can property-test
+//! their own handling of [`McpServer`] values. [`mcp_server_for`] only yields
+//! servers a harness can write and read back unchanged, which makes
+//! round-trip tests a one-liner:
+//!
+//! ```
+//! # #[cfg(feature = "test-util")]
+//! # {
+//! use harness_locate::strategy::mcp_server_for;
+//! use harness_locate::{Harness, HarnessKind};
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(server in mcp_server_for(HarnessKind::OpenCode))| {
+//!     let harness = Harness::new(HarnessKind::OpenCode);
+//!     let native = harness.mcp_to_native("server", &server).unwrap();
+//!     let parsed = harness.parse_mcp_server_config("server", &native).unwrap();
+//!     prop_assert_eq!(parsed, server);
+//! });
+//! # }
+//! ```
+
+use std::path::PathBuf;
+
+use proptest::collection::{hash_map, vec};
+use proptest::option;
+use proptest::prelude::*;
+
+use crate::mcp::{
+    GooseExtension, GooseExtensionKind, HttpMcpServer, McpCapabilities, McpServer, OAuthConfig,
+    SseMcpServer, StdioMcpServer,
+};
+use crate::types::{EnvValue, HarnessKind};
+
+/// Environment variable names such as `API_KEY`.
+pub fn env_name() -> impl Strategy<Value = String> {
+    "[A-Z][A-Z0-9_]{0,15}"
+}
+
+/// Plain values and environment variable references.
+///
+/// Plain values never contain `$`, `{` or `}`, which every harness reads
+/// back as a reference.
+pub fn env_value() -> impl Strategy<Value = EnvValue> {
+    prop_oneof![
+        "[a-zA-Z0-9 _./:=-]{0,24}".prop_map(EnvValue::Plain),
+        (proptest::option::of("(Bearer |Token |sk-)"), env_name())
+            .prop_map(|(prefix, env)| EnvValue::EnvRef { env, prefix }),
+    ]
+}
+
+fn env_map() -> impl Strategy<Value = std::collections::HashMap<String, EnvValue>> {
+    hash_map(env_name(), env_value(), 0..4)
+}
+
+fn word() -> impl Strategy<Value = String> {
+    "[a-z][a-z0-9-]{0,11}"
+}
+
+fn url() -> impl Strategy<Value = String> {
+    (word(), vec(word(), 0..3))
+        .prop_map(|(host, path)| format!("https://{host}.example.com/{}", path.join("/")))
+}
+
+/// Stdio servers with arbitrary arguments, env, cwd, and timeout.
+pub fn stdio_server() -> impl Strategy<Value = StdioMcpServer> {
+    (
+        word(),
+        vec("[a-zA-Z0-9_./=-]{1,12}", 0..4),
+        env_map(),
+        option::of("/[a-z]{1,8}(/[a-z]{1,8}){0,2}".prop_map(PathBuf::from)),
+        any::<bool>(),
+        option::of(1..600_000u64),
+    )
+        .prop_map(
+            |(command, args, env, cwd, enabled, timeout_ms)| StdioMcpServer {
+                command,
+                args,
+                env,
+                cwd,
+                enabled,
+                timeout_ms,
+            },
+        )
+}
+
+/// SSE servers with arbitrary headers and timeout.
+pub fn sse_server() -> impl Strategy<Value = SseMcpServer> {
+    (url(), env_map(), any::<bool>(), option::of(1..600_000u64)).prop_map(
+        |(url, headers, enabled, timeout_ms)| SseMcpServer {
+            url,
+            headers,
+            enabled,
+            timeout_ms,
+        },
+    )
+}
+
+/// OAuth settings with any combination of fields.
+pub fn oauth_config() -> impl Strategy<Value = OAuthConfig> {
+    (
+        option::of(word()),
+        option::of(env_value()),
+        option::of("[a-z:]{1,12}"),
+    )
+        .prop_map(|(client_id, client_secret, scope)| OAuthConfig {
+            client_id,
+            client_secret,
+            scope,
+        })
+}
+
+/// HTTP servers with arbitrary headers, OAuth, and timeout.
+pub fn http_server() -> impl Strategy<Value = HttpMcpServer> {
+    (
+        url(),
+        env_map(),
+        option::of(oauth_config()),
+        any::<bool>(),
+        option::of(1..600_000u64),
+    )
+        .prop_map(|(url, headers, oauth, enabled, timeout_ms)| HttpMcpServer {
+            url,
+            headers,
+            oauth,
+            enabled,
+            timeout_ms,
+        })
+}
+
+/// Goose builtin, platform, and inline Python extensions.
+pub fn goose_extension() -> impl Strategy<Value = GooseExtension> {
+    let kind = prop_oneof![
+        Just(GooseExtensionKind::Builtin),
+        Just(GooseExtensionKind::Platform),
+        Just(GooseExtensionKind::InlinePython),
+    ];
+    (
+        kind,
+        word(),
+        option::of("[A-Za-z ]{1,12}"),
+        option::of("[A-Za-z .]{1,24}"),
+        any::<bool>(),
+        option::of((1..600u64).prop_map(|secs| secs * 1000)),
+    )
+        .prop_flat_map(
+            |(kind, name, display_name, description, enabled, timeout_ms)| {
+                let python = kind == GooseExtensionKind::InlinePython;
+                // Goose requires `code` for inline Python and ignores it otherwise.
+                let code = if python {
+                    "print\\([0-9]{1,3}\\)".prop_map(Some).boxed()
+                } else {
+                    Just(None).boxed()
+                };
+                let dependencies = vec(word(), 0..if python { 3 } else { 1 });
+                (code, dependencies).prop_map(move |(code, dependencies)| GooseExtension {
+                    kind,
+                    name: name.clone(),
+                    display_name: display_name.clone(),
+                    description: description.clone(),
+                    code,
+                    dependencies,
+                    enabled,
+                    timeout_ms,
+                })
+            },
+        )
+}
+
+/// Any stdio, SSE, or HTTP server.
+///
+/// Goose-native extensions are left out since only Goose accepts them; use
+/// [`goose_extension`] for those.
+pub fn mcp_server() -> impl Strategy<Value = McpServer> {
+    prop_oneof![
+        stdio_server().prop_map(McpServer::Stdio),
+        sse_server().prop_map(McpServer::Sse),
+        http_server().prop_map(McpServer::Http),
+    ]
+}
+
+/// Servers that `kind` converts to its native format without loss.
+///
+/// Transports the harness rejects or cannot tell apart are skipped, and
+/// fields it cannot represent are cleared: no
+/// [`dropped_fields`](McpServer::dropped_fields), no timeout or disabled
+/// state unless supported, and only plain values for Goose, which writes
+/// resolved values instead of references.
+pub fn mcp_server_for(kind: HarnessKind) -> impl Strategy<Value = McpServer> {
+    let caps = McpCapabilities::for_kind(kind);
+    let servers = if kind == HarnessKind::Goose {
+        prop_oneof![
+            3 => mcp_server(),
+            1 => goose_extension().prop_map(McpServer::GooseNative),
+        ]
+        .boxed()
+    } else {
+        mcp_server().boxed()
+    };
+    servers.prop_filter_map("transport not supported", move |mut server| {
+        match &server {
+            McpServer::Sse(_) if !caps.sse => return None,
+            // OpenCode and Amp write SSE servers as plain remote URLs, which
+            // read back as HTTP.
+            McpServer::Sse(_) if matches!(kind, HarnessKind::OpenCode | HarnessKind::AmpCode) => {
+                return None;
+            }
+            McpServer::Http(_) if !caps.http => return None,
+            McpServer::GooseNative(_) => return Some(server),
+            _ => {}
+        }
+        make_lossless(&mut server, kind, &caps);
+        Some(server)
+    })
+}
+
+fn make_lossless(server: &mut McpServer, kind: HarnessKind, caps: &McpCapabilities) {
+    if !caps.toggle {
+        server.set_enabled(true);
+    }
+    let (env, timeout_ms) = match server {
+        McpServer::Stdio(s) => (&mut s.env, &mut s.timeout_ms),
+        McpServer::Sse(s) => (&mut s.headers, &mut s.timeout_ms),
+        McpServer::Http(h) => {
+            if !caps.oauth {
+                h.oauth = None;
+            }
+            (&mut h.headers, &mut h.timeout_ms)
+        }
+        McpServer::GooseNative(_) => return,
+    };
+    if !caps.timeout {
+        *timeout_ms = None;
+    }
+    if kind == HarnessKind::Goose {
+        env.retain(|_, value| matches!(value, EnvValue::Plain(_)));
+    }
+
+    for field in server.dropped_fields(kind) {
+        match (field, &mut *server) {
+            ("cwd", McpServer::Stdio(s)) => s.cwd = None,
+            ("timeout_ms", McpServer::Stdio(s)) => s.timeout_ms = None,
+            ("timeout_ms", McpServer::Sse(s)) => s.timeout_ms = None,
+            ("timeout_ms", McpServer::Http(h)) => h.timeout_ms = None,
+            ("headers", McpServer::Sse(s)) => s.headers.clear(),
+            ("headers", McpServer::Http(h)) => h.headers.clear(),
+            ("oauth", McpServer::Http(h)) => h.oauth = None,
+            ("enabled", _) => server.set_enabled(true),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Harness;
+
+    fn round_trip(kind: HarnessKind, server: &McpServer) -> McpServer {
+        let harness = Harness::new(kind);
+        let native = harness.mcp_to_native("server", server).unwrap();
+        harness.parse_mcp_server_config("server", &native).unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn serde_round_trip(server in mcp_server()) {
+            let json = serde_json::to_string(&server).unwrap();
+            let parsed: McpServer = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(parsed, server);
+        }
+
+        #[test]
+        fn generated_servers_are_lossless(server in mcp_server_for(HarnessKind::ClaudeCode)) {
+            prop_assert!(server.dropped_fields(HarnessKind::ClaudeCode).is_empty());
+            prop_assert!(server.validate_capabilities(HarnessKind::ClaudeCode).is_ok());
+        }
+
+        #[test]
+        fn claude_code_round_trip(server in mcp_server_for(HarnessKind::ClaudeCode)) {
+            prop_assert_eq!(round_trip(HarnessKind::ClaudeCode, &server), server);
+        }
+
+        #[test]
+        fn opencode_round_trip(server in mcp_server_for(HarnessKind::OpenCode)) {
+            prop_assert_eq!(round_trip(HarnessKind::OpenCode, &server), server);
+        }
+
+        #[test]
+        fn goose_round_trip(server in mcp_server_for(HarnessKind::Goose)) {
+            prop_assert_eq!(round_trip(HarnessKind::Goose, &server), server);
+        }
+
+        #[test]
+        fn amp_code_round_trip(server in mcp_server_for(HarnessKind::AmpCode)) {
+            prop_assert_eq!(round_trip(HarnessKind::AmpCode, &server), server);
+        }
+
+        #[test]
+        fn copilot_cli_round_trip(server in mcp_server_for(HarnessKind::CopilotCli)) {
+            prop_assert_eq!(round_trip(HarnessKind::CopilotCli, &server), server);
+        }
+    }
+}
@@ -0,0 +1,362 @@
+//! Operating systems and architectures skills and MCP servers run on.
+//!
+//! Some skills drive macOS-only tools, and some MCP servers ship x86_64
+//! binaries only. Both can say so with a `platforms` list, in skill
+//! frontmatter and in [MCP templates](crate::templates::McpTemplate):
+//!
+//! ```yaml
+//! platforms: [macos, linux-x86_64]
+//! ```
+//!
+//! Each entry names an operating system, an architecture, or both as
+//! `os-arch`. Common aliases are accepted: `darwin` for `macos`, `win32`
+//! for `windows`, `x64` and `amd64` for `x86_64`, and `arm64` for
+//! `aarch64`. A missing or empty list means every platform.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::target::{Arch, Os, Platform, Platforms};
+//!
+//! let platforms: Platforms = serde_json::from_str(r#"["macos", "linux-x86_64"]"#)?;
+//! assert!(platforms.allows(&Platform::new(Os::Macos, Arch::Aarch64)));
+//! assert!(platforms.allows(&Platform::new(Os::Linux, Arch::X86_64)));
+//! assert!(!platforms.allows(&Platform::new(Os::Linux, Arch::Aarch64)));
+//! assert!(Platforms::default().allows(&Platform::new(Os::Windows, Arch::X86_64)));
+//! # Ok::<(), serde_json::Error>(())
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// An operating system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Os {
+    /// macOS.
+    Macos,
+    /// Linux, including WSL.
+    Linux,
+    /// Windows.
+    Windows,
+}
+
+impl Os {
+    /// Returns the operating system this crate was compiled for, or `None`
+    /// if it is not one of the known ones.
+    #[must_use]
+    pub fn current() -> Option<Self> {
+        Self::from_name(std::env::consts::OS)
+    }
+
+    /// Returns the canonical name, as written in `platforms` lists.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Macos => "macos",
+            Self::Linux => "linux",
+            Self::Windows => "windows",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "macos" | "darwin" | "osx" | "mac" => Some(Self::Macos),
+            "linux" => Some(Self::Linux),
+            "windows" | "win32" | "win" => Some(Self::Windows),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Os {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A CPU architecture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Arch {
+    /// 64-bit x86.
+    X86_64,
+    /// 64-bit ARM.
+    Aarch64,
+}
+
+impl Arch {
+    /// Returns the architecture this crate was compiled for, or `None` if
+    /// it is not one of the known ones.
+    #[must_use]
+    pub fn current() -> Option<Self> {
+        Self::from_name(std::env::consts::ARCH)
+    }
+
+    /// Returns the canonical name, as written in `platforms` lists.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::X86_64 => "x86_64",
+            Self::Aarch64 => "aarch64",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "x86_64" | "x64" | "amd64" => Some(Self::X86_64),
+            "aarch64" | "arm64" => Some(Self::Aarch64),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Arch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An operating system and architecture pair.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Platform {
+    /// The operating system.
+    pub os: Os,
+    /// The CPU architecture.
+    pub arch: Arch,
+}
+
+impl Platform {
+    /// Creates a platform.
+    #[must_use]
+    pub fn new(os: Os, arch: Arch) -> Self {
+        Self { os, arch }
+    }
+
+    /// Returns the platform this crate was compiled for, or `None` if its
+    /// operating system or architecture is not a known one.
+    #[must_use]
+    pub fn current() -> Option<Self> {
+        Some(Self::new(Os::current()?, Arch::current()?))
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.os, self.arch)
+    }
+}
+
+/// One entry of a `platforms` list: an operating system, an architecture,
+/// or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PlatformSpec {
+    /// The required operating system, or `None` for any.
+    pub os: Option<Os>,
+    /// The required architecture, or `None` for any.
+    pub arch: Option<Arch>,
+}
+
+impl PlatformSpec {
+    /// Returns `true` if `platform` satisfies this entry.
+    #[must_use]
+    pub fn matches(&self, platform: &Platform) -> bool {
+        self.os.is_none_or(|os| os == platform.os)
+            && self.arch.is_none_or(|arch| arch == platform.arch)
+    }
+}
+
+impl From<Os> for PlatformSpec {
+    fn from(os: Os) -> Self {
+        Self {
+            os: Some(os),
+            arch: None,
+        }
+    }
+}
+
+impl From<Arch> for PlatformSpec {
+    fn from(arch: Arch) -> Self {
+        Self {
+            os: None,
+            arch: Some(arch),
+        }
+    }
+}
+
+impl From<Platform> for PlatformSpec {
+    fn from(platform: Platform) -> Self {
+        Self {
+            os: Some(platform.os),
+            arch: Some(platform.arch),
+        }
+    }
+}
+
+impl FromStr for PlatformSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        if let Some(os) = Os::from_name(s) {
+            return Ok(os.into());
+        }
+        if let Some(arch) = Arch::from_name(s) {
+            return Ok(arch.into());
+        }
+        s.split_once('-')
+            .and_then(|(os, arch)| Some(Platform::new(Os::from_name(os)?, Arch::from_name(arch)?)))
+            .map(Self::from)
+            .ok_or_else(|| Error::InvalidPlatform(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for PlatformSpec {
+    type Error = Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<PlatformSpec> for String {
+    fn from(spec: PlatformSpec) -> Self {
+        spec.to_string()
+    }
+}
+
+impl fmt::Display for PlatformSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.os, self.arch) {
+            (Some(os), Some(arch)) => write!(f, "{os}-{arch}"),
+            (Some(os), None) => write!(f, "{os}"),
+            (None, Some(arch)) => write!(f, "{arch}"),
+            (None, None) => f.write_str("any"),
+        }
+    }
+}
+
+/// The platforms a skill or MCP server supports; empty means all.
+///
+/// Deserializes from a single entry such as `macos` or from a list, and
+/// serializes as a list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "OneOrMany")]
+pub struct Platforms(Vec<PlatformSpec>);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(PlatformSpec),
+    Many(Vec<PlatformSpec>),
+}
+
+impl From<OneOrMany> for Platforms {
+    fn from(value: OneOrMany) -> Self {
+        match value {
+            OneOrMany::One(spec) => Self(vec![spec]),
+            OneOrMany::Many(specs) => Self(specs),
+        }
+    }
+}
+
+impl<S: Into<PlatformSpec>> FromIterator<S> for Platforms {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl Platforms {
+    /// Returns `true` if no platforms are listed, so every platform is
+    /// supported.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the listed entries.
+    #[must_use]
+    pub fn specs(&self) -> &[PlatformSpec] {
+        &self.0
+    }
+
+    /// Returns `true` if `platform` is supported.
+    #[must_use]
+    pub fn allows(&self, platform: &Platform) -> bool {
+        self.is_empty() || self.0.iter().any(|spec| spec.matches(platform))
+    }
+
+    /// Returns `true` if the platform this crate was compiled for is
+    /// supported. A constrained list never allows an unknown platform.
+    #[must_use]
+    pub fn allows_current(&self) -> bool {
+        match Platform::current() {
+            Some(platform) => self.allows(&platform),
+            None => self.is_empty(),
+        }
+    }
+}
+
+impl fmt::Display for Platforms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("any");
+        }
+        for (index, spec) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{spec}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_entries_and_aliases() {
+        let spec = |s: &str| s.parse::<PlatformSpec>().unwrap().to_string();
+        assert_eq!(spec("darwin"), "macos");
+        assert_eq!(spec("arm64"), "aarch64");
+        assert_eq!(spec("Linux-x64"), "linux-x86_64");
+        assert_eq!(spec("win32-amd64"), "windows-x86_64");
+        assert!(matches!(
+            "freebsd".parse::<PlatformSpec>(),
+            Err(Error::InvalidPlatform(name)) if name == "freebsd"
+        ));
+    }
+
+    #[test]
+    fn deserializes_one_entry_or_a_list() {
+        let one: Platforms = serde_json::from_str(r#""macos""#).unwrap();
+        assert_eq!(one, [Os::Macos].into_iter().collect());
+
+        let many: Platforms = serde_json::from_str(r#"["linux", "windows-x64"]"#).unwrap();
+        assert_eq!(many.to_string(), "linux, windows-x86_64");
+        assert_eq!(
+            serde_json::to_string(&many).unwrap(),
+            r#"["linux","windows-x86_64"]"#
+        );
+        assert!(serde_json::from_str::<Platforms>(r#"["plan9"]"#).is_err());
+    }
+
+    #[test]
+    fn current_platform_is_known_on_ci_targets() {
+        let current = Platform::current().unwrap();
+        assert!(Platforms::default().allows_current());
+        assert!(
+            [PlatformSpec::from(current)]
+                .into_iter()
+                .collect::<Platforms>()
+                .allows_current()
+        );
+    }
+}
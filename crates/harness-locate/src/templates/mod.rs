@@ -0,0 +1,203 @@
+//! Curated templates for popular MCP servers.
+//!
+//! Installer UIs offer one-click setup for well-known servers. The catalog
+//! returned by [`mcp_catalog`] is data-driven, loaded from a JSON file
+//! bundled with the crate, and each [`McpTemplate`] holds a ready
+//! [`McpServer`]. Secrets such as API tokens are never literal: the server
+//! reads them from environment variable references, listed in
+//! [`McpTemplate::env`] so the UI can ask for them.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::templates::{find_template, search_templates};
+//! use harness_locate::{Harness, HarnessKind};
+//!
+//! let github = find_template("github").unwrap();
+//! assert_eq!(github.env[0].name, "GITHUB_PERSONAL_ACCESS_TOKEN");
+//! assert!(github.is_supported_by(&Harness::new(HarnessKind::ClaudeCode)));
+//!
+//! let databases = search_templates("database");
+//! assert!(databases.iter().any(|t| t.name == "postgres"));
+//! ```
+
+use std::sync::LazyLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::harness::Harness;
+use crate::mcp::McpServer;
+use crate::target::{Platform, Platforms};
+
+static CATALOG: LazyLock<Vec<McpTemplate>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("mcp_catalog.json")).expect("bundled MCP catalog is valid")
+});
+
+/// An environment variable a template needs before it works.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateEnv {
+    /// The variable name, as referenced by the server.
+    pub name: String,
+    /// What to put in it.
+    pub description: String,
+    /// Whether the value is a credential to hide in UIs.
+    #[serde(default)]
+    pub secret: bool,
+}
+
+/// A ready-made configuration for a popular MCP server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpTemplate {
+    /// Short identifier, also the suggested server name.
+    pub name: String,
+    /// One-line summary for display.
+    pub description: String,
+    /// Keywords for [`search_templates`].
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Project page of the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    /// Environment variables the server references and the user must set.
+    #[serde(default)]
+    pub env: Vec<TemplateEnv>,
+    /// Platforms the server runs on; empty means all.
+    #[serde(default, skip_serializing_if = "Platforms::is_empty")]
+    pub platforms: Platforms,
+    /// The server configuration.
+    pub server: McpServer,
+}
+
+impl McpTemplate {
+    /// Returns `true` if `harness` can run the server as configured.
+    #[must_use]
+    pub fn is_supported_by(&self, harness: &Harness) -> bool {
+        harness.supports_mcp_server(&self.server)
+    }
+
+    /// Returns `true` if the server runs on `platform`, per
+    /// [`platforms`](Self::platforms).
+    #[must_use]
+    pub fn runs_on(&self, platform: &Platform) -> bool {
+        self.platforms.allows(platform)
+    }
+
+    /// Returns the required variables that are not set in the current
+    /// environment.
+    #[must_use]
+    pub fn missing_env(&self) -> Vec<&TemplateEnv> {
+        self.env
+            .iter()
+            .filter(|var| std::env::var_os(&var.name).is_none())
+            .collect()
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        self.name.to_lowercase().contains(query)
+            || self.description.to_lowercase().contains(query)
+            || self.tags.iter().any(|tag| tag.eq_ignore_ascii_case(query))
+    }
+}
+
+/// Returns every bundled template, sorted as listed in the catalog.
+#[must_use]
+pub fn mcp_catalog() -> &'static [McpTemplate] {
+    &CATALOG
+}
+
+/// Returns the template called `name`.
+#[must_use]
+pub fn find_template(name: &str) -> Option<&'static McpTemplate> {
+    CATALOG.iter().find(|template| template.name == name)
+}
+
+/// Returns the templates whose name or description contains `query`, or
+/// that have it as a tag, ignoring case.
+///
+/// An empty query returns the whole catalog.
+#[must_use]
+pub fn search_templates(query: &str) -> Vec<&'static McpTemplate> {
+    let query = query.trim().to_lowercase();
+    CATALOG
+        .iter()
+        .filter(|template| template.matches(&query))
+        .collect()
+}
+
+/// Returns the templates `harness` can run.
+#[must_use]
+pub fn templates_for(harness: &Harness) -> Vec<&'static McpTemplate> {
+    CATALOG
+        .iter()
+        .filter(|template| template.is_supported_by(harness))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::HarnessKind;
+
+    #[test]
+    fn catalog_servers_reference_their_declared_env() {
+        let catalog = mcp_catalog();
+        assert!(catalog.len() >= 5);
+        for template in catalog {
+            let mut referenced = template.server.env_var_names();
+            referenced.sort_unstable();
+            let mut declared: Vec<_> = template.env.iter().map(|v| v.name.as_str()).collect();
+            declared.sort_unstable();
+            assert_eq!(referenced, declared, "{}", template.name);
+        }
+    }
+
+    #[cfg(feature = "validation")]
+    #[test]
+    fn catalog_servers_pass_validation() {
+        use crate::validation::{Severity, validate_mcp_server};
+
+        for template in mcp_catalog() {
+            let errors: Vec<_> = validate_mcp_server(&template.server)
+                .into_iter()
+                .filter(|issue| issue.severity == Severity::Error)
+                .collect();
+            assert!(errors.is_empty(), "{}: {errors:?}", template.name);
+        }
+    }
+
+    #[test]
+    fn searches_by_name_description_and_tag() {
+        let names = |query| {
+            search_templates(query)
+                .into_iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names("GitHub"), ["github"]);
+        assert_eq!(names("sql"), ["postgres"]);
+        assert!(names("web").contains(&"fetch"));
+        assert_eq!(names("").len(), mcp_catalog().len());
+        assert!(find_template("nope").is_none());
+
+        let claude = Harness::new(HarnessKind::ClaudeCode);
+        assert_eq!(templates_for(&claude).len(), mcp_catalog().len());
+    }
+
+    #[test]
+    fn templates_can_be_limited_to_platforms() {
+        use crate::target::{Arch, Os};
+
+        let template: McpTemplate = serde_json::from_str(
+            r#"{
+                "name": "notes",
+                "description": "Apple Notes",
+                "platforms": "macos",
+                "server": {"transport": "stdio", "command": "notes-mcp"}
+            }"#,
+        )
+        .unwrap();
+        assert!(template.runs_on(&Platform::new(Os::Macos, Arch::Aarch64)));
+        assert!(!template.runs_on(&Platform::new(Os::Linux, Arch::X86_64)));
+        assert!(mcp_catalog().iter().all(|t| t.platforms.is_empty()));
+    }
+}
@@ -0,0 +1,338 @@
+//! Tolerant text decoding.
+//!
+//! Skill and config files authored on Windows or exported from editors
+//! often carry a byte order mark, CRLF line endings, or UTF-16 encoding.
+//! [`decode`] turns such files into plain UTF-8 with `\n` line endings and
+//! reports what it changed in a [`Normalization`], so callers can explain
+//! why a file looks different from its bytes on disk. [`encode`] undoes it
+//! when writing the file back.
+//!
+//! Every file this crate parses is read through [`decode`].
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::text::{TextEncoding, decode};
+//!
+//! let decoded = decode(b"\xEF\xBB\xBFname: demo\r\n").unwrap();
+//! assert_eq!(decoded.text, "name: demo\n");
+//! assert_eq!(decoded.normalization.encoding, TextEncoding::Utf8);
+//! assert!(decoded.normalization.bom_stripped);
+//! assert!(decoded.normalization.crlf_normalized);
+//! ```
+
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+const UTF16LE_BOM: &[u8] = b"\xFF\xFE";
+const UTF16BE_BOM: &[u8] = b"\xFE\xFF";
+
+/// Number of leading bytes inspected when guessing BOM-less UTF-16.
+const SNIFF_LEN: usize = 512;
+
+/// Encoding a file was decoded from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TextEncoding {
+    /// UTF-8, with or without a byte order mark.
+    #[default]
+    Utf8,
+    /// UTF-16, little-endian.
+    Utf16Le,
+    /// UTF-16, big-endian.
+    Utf16Be,
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+        })
+    }
+}
+
+/// What [`decode`] changed to produce its text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Normalization {
+    /// The encoding the bytes were decoded from.
+    pub encoding: TextEncoding,
+    /// Whether a leading byte order mark was removed.
+    pub bom_stripped: bool,
+    /// Whether `\r\n` line endings were replaced with `\n`.
+    pub crlf_normalized: bool,
+}
+
+impl Normalization {
+    /// Returns `true` if the text is exactly the file's bytes: UTF-8, no
+    /// BOM and no CRLF line endings.
+    #[must_use]
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut steps = Vec::new();
+        if self.encoding != TextEncoding::Utf8 {
+            steps.push(format!("decoded from {}", self.encoding));
+        }
+        if self.bom_stripped {
+            steps.push("stripped byte order mark".to_string());
+        }
+        if self.crlf_normalized {
+            steps.push("normalized CRLF line endings".to_string());
+        }
+        if steps.is_empty() {
+            f.write_str("unchanged")
+        } else {
+            f.write_str(&steps.join(", "))
+        }
+    }
+}
+
+/// Text produced by [`decode`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DecodedText {
+    /// UTF-8 text with `\n` line endings and no byte order mark.
+    pub text: String,
+    /// What was changed to produce `text`.
+    pub normalization: Normalization,
+}
+
+/// Decodes file contents into normalized UTF-8.
+///
+/// UTF-16 is recognized by its byte order mark, or, without one, by the
+/// pattern of zero bytes that ASCII text leaves in it.
+///
+/// # Errors
+///
+/// Returns an [`io::ErrorKind::InvalidData`] error if the bytes are not
+/// valid in the detected encoding.
+pub fn decode(bytes: &[u8]) -> io::Result<DecodedText> {
+    let (encoding, bom_stripped, body) = if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+        (TextEncoding::Utf8, true, rest)
+    } else if let Some(rest) = bytes.strip_prefix(UTF16LE_BOM) {
+        (TextEncoding::Utf16Le, true, rest)
+    } else if let Some(rest) = bytes.strip_prefix(UTF16BE_BOM) {
+        (TextEncoding::Utf16Be, true, rest)
+    } else {
+        (
+            sniff_utf16(bytes).unwrap_or(TextEncoding::Utf8),
+            false,
+            bytes,
+        )
+    };
+
+    let text = match encoding {
+        TextEncoding::Utf8 => String::from_utf8(body.to_vec()).map_err(invalid_data)?,
+        TextEncoding::Utf16Le => decode_utf16(body, u16::from_le_bytes)?,
+        TextEncoding::Utf16Be => decode_utf16(body, u16::from_be_bytes)?,
+    };
+
+    // A BOM-less UTF-16 file may still start with an encoded U+FEFF.
+    let (text, inner_bom) = match text.strip_prefix('\u{feff}') {
+        Some(rest) => (rest.to_string(), true),
+        None => (text, false),
+    };
+    let (text, crlf_normalized) = match normalize_newlines(&text) {
+        Cow::Borrowed(_) => (text, false),
+        Cow::Owned(normalized) => (normalized, true),
+    };
+
+    Ok(DecodedText {
+        text,
+        normalization: Normalization {
+            encoding,
+            bom_stripped: bom_stripped || inner_bom,
+            crlf_normalized,
+        },
+    })
+}
+
+/// Strips a leading byte order mark and replaces `\r\n` with `\n`.
+///
+/// This is [`decode`] for text that is already a string, such as content
+/// passed directly to a parser.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::text::normalize;
+///
+/// assert_eq!(normalize("\u{feff}a\r\nb"), "a\nb");
+/// assert_eq!(normalize("plain"), "plain");
+/// ```
+#[must_use]
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+    normalize_newlines(text)
+}
+
+/// Encodes `text` the way [`decode`] found it, undoing `normalization`.
+///
+/// Line endings become `\r\n` if they were normalized, and the byte order
+/// mark is restored if one was stripped.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::text::{decode, encode};
+///
+/// let bytes = b"\xEF\xBB\xBFname: demo\r\n";
+/// let decoded = decode(bytes).unwrap();
+/// assert_eq!(encode(&decoded.text, decoded.normalization), bytes);
+/// ```
+#[must_use]
+pub fn encode(text: &str, normalization: Normalization) -> Vec<u8> {
+    let mut text = Cow::Borrowed(text);
+    if normalization.crlf_normalized {
+        text = Cow::Owned(text.replace('\n', "\r\n"));
+    }
+    if normalization.bom_stripped {
+        text = Cow::Owned(format!("\u{feff}{text}"));
+    }
+    match normalization.encoding {
+        TextEncoding::Utf8 => text.into_owned().into_bytes(),
+        TextEncoding::Utf16Le => text.encode_utf16().flat_map(u16::to_le_bytes).collect(),
+        TextEncoding::Utf16Be => text.encode_utf16().flat_map(u16::to_be_bytes).collect(),
+    }
+}
+
+fn normalize_newlines(text: &str) -> Cow<'_, str> {
+    if text.contains("\r\n") {
+        Cow::Owned(text.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+fn sniff_utf16(bytes: &[u8]) -> Option<TextEncoding> {
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if sample.len() < 2 || !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let pairs = sample.len() / 2;
+    let (mut even_zeros, mut odd_zeros) = (0, 0);
+    for pair in sample.chunks_exact(2) {
+        even_zeros += usize::from(pair[0] == 0);
+        odd_zeros += usize::from(pair[1] == 0);
+    }
+    // Mostly-ASCII UTF-16 has a zero in the high byte of nearly every unit
+    // and almost never in the low byte.
+    if odd_zeros * 2 > pairs && even_zeros == 0 {
+        Some(TextEncoding::Utf16Le)
+    } else if even_zeros * 2 > pairs && odd_zeros == 0 {
+        Some(TextEncoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> io::Result<String> {
+    if !bytes.len().is_multiple_of(2) {
+        return Err(invalid_data("UTF-16 text has an odd number of bytes"));
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| unit([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).map_err(invalid_data)
+}
+
+fn invalid_data(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    fn utf16be(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_be_bytes).collect()
+    }
+
+    #[test]
+    fn plain_utf8_is_unchanged() {
+        let decoded = decode(b"---\nname: x\n---\n").unwrap();
+        assert_eq!(decoded.text, "---\nname: x\n---\n");
+        assert!(decoded.normalization.is_identity());
+    }
+
+    #[test]
+    fn utf16_with_bom_is_decoded() {
+        let mut bytes = UTF16LE_BOM.to_vec();
+        bytes.extend(utf16le("name: caf\u{e9}\r\n"));
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(decoded.text, "name: caf\u{e9}\n");
+        assert_eq!(
+            decoded.normalization,
+            Normalization {
+                encoding: TextEncoding::Utf16Le,
+                bom_stripped: true,
+                crlf_normalized: true,
+            }
+        );
+
+        let mut bytes = UTF16BE_BOM.to_vec();
+        bytes.extend(utf16be("{}"));
+        let decoded = decode(&bytes).unwrap();
+        assert_eq!(decoded.text, "{}");
+        assert_eq!(decoded.normalization.encoding, TextEncoding::Utf16Be);
+    }
+
+    #[test]
+    fn bomless_utf16_is_sniffed() {
+        let decoded = decode(&utf16le("{\"mcpServers\": {}}")).unwrap();
+        assert_eq!(decoded.text, "{\"mcpServers\": {}}");
+        assert_eq!(decoded.normalization.encoding, TextEncoding::Utf16Le);
+        assert!(!decoded.normalization.bom_stripped);
+
+        let decoded = decode(&utf16be("name: x")).unwrap();
+        assert_eq!(decoded.text, "name: x");
+        assert_eq!(decoded.normalization.encoding, TextEncoding::Utf16Be);
+    }
+
+    #[test]
+    fn encode_round_trips_decode() {
+        let mut utf16 = UTF16LE_BOM.to_vec();
+        utf16.extend(utf16le("a\r\nb\r\n"));
+        for bytes in [
+            b"plain\n".to_vec(),
+            b"\xEF\xBB\xBFa\r\nb".to_vec(),
+            utf16,
+            utf16be("name: x\r\n"),
+        ] {
+            let decoded = decode(&bytes).unwrap();
+            assert_eq!(encode(&decoded.text, decoded.normalization), bytes);
+        }
+    }
+
+    #[test]
+    fn invalid_bytes_are_reported() {
+        let err = decode(b"\xFF\xFE\x00").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = decode(b"name: \xC3").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn normalization_describes_itself() {
+        let decoded = decode(b"\xEF\xBB\xBFa\r\n").unwrap();
+        assert_eq!(
+            decoded.normalization.to_string(),
+            "stripped byte order mark, normalized CRLF line endings"
+        );
+        assert_eq!(Normalization::default().to_string(), "unchanged");
+    }
+}
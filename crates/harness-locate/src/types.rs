@@ -5,6 +5,9 @@ use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
 
+use crate::glob::Glob;
+use crate::ignore::{self, IgnoreRules};
+
 /// Supported AI coding harnesses.
 ///
 /// This enum represents the different AI coding assistants whose
@@ -14,7 +17,9 @@ use serde::{Deserialize, Serialize};
 ///
 /// This enum is marked `#[non_exhaustive]` to allow adding new
 /// harness types in future versions without breaking changes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Kinds order as in [`ALL`](Self::ALL).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub enum HarnessKind {
     /// Claude Code (Anthropic's CLI)
@@ -101,6 +106,32 @@ impl HarnessKind {
         }
     }
 
+    /// Returns the paths, relative to a project root, whose presence marks
+    /// the directory as a project configured for this harness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::HarnessKind;
+    ///
+    /// assert!(HarnessKind::ClaudeCode.project_markers().contains(&".claude"));
+    /// assert!(HarnessKind::OpenCode.project_markers().contains(&"opencode.json"));
+    /// ```
+    #[must_use]
+    pub fn project_markers(&self) -> &'static [&'static str] {
+        match self {
+            Self::ClaudeCode => &[".claude", ".mcp.json", "CLAUDE.md"],
+            Self::OpenCode => &[".opencode", "opencode.json", "opencode.jsonc"],
+            Self::Goose => &[".goose", ".goosehints"],
+            Self::AmpCode => &[".agents"],
+            Self::CopilotCli => &[
+                ".github/agents",
+                ".github/skills",
+                ".github/copilot-instructions.md",
+            ],
+        }
+    }
+
     /// Returns the expected directory name(s) for a resource kind.
     ///
     /// Different harnesses use different naming conventions:
@@ -151,9 +182,10 @@ impl HarnessKind {
             // Goose - limited support (skills only)
             (Self::Goose, ResourceKind::Skills) => Some(&["skills"]),
 
-            // AmpCode - plural names, limited support
+            // AmpCode - plural names, no plugins
             (Self::AmpCode, ResourceKind::Skills) => Some(&["skills"]),
             (Self::AmpCode, ResourceKind::Commands) => Some(&["commands"]),
+            (Self::AmpCode, ResourceKind::Agents) => Some(&["agents"]),
 
             // Copilot CLI - plural names, skills and agents only
             (Self::CopilotCli, ResourceKind::Skills) => Some(&["skills"]),
@@ -169,7 +201,7 @@ impl HarnessKind {
 ///
 /// Determines whether to look up global (user-level) or
 /// project-local configuration paths.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Scope {
     /// User-level global configuration (e.g., `~/.config/...`)
     Global,
@@ -283,6 +315,131 @@ impl InstallationStatus {
             _ => None,
         }
     }
+
+    /// Returns how the harness binary was installed, if a binary was found.
+    ///
+    /// Resolves symlinks on the binary path (e.g. `/opt/homebrew/bin/goose`
+    /// into the Cellar) before classifying it, so updaters can pick the
+    /// matching upgrade mechanism.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::{InstallSource, InstallationStatus};
+    /// use std::path::PathBuf;
+    ///
+    /// let status = InstallationStatus::BinaryOnly {
+    ///     binary_path: PathBuf::from("/nix/store/abc123-goose-1.0/bin/goose"),
+    /// };
+    /// assert_eq!(status.install_source(), Some(InstallSource::Nix));
+    ///
+    /// assert_eq!(InstallationStatus::NotInstalled.install_source(), None);
+    /// ```
+    #[must_use]
+    pub fn install_source(&self) -> Option<InstallSource> {
+        self.binary_path().map(crate::detection::install_source)
+    }
+}
+
+/// How a harness binary was installed.
+///
+/// Determined from the (symlink-resolved) location of the binary.
+/// See [`InstallationStatus::install_source`].
+///
+/// # Extensibility
+///
+/// This enum is marked `#[non_exhaustive]` to allow recognizing new
+/// package managers in future versions without breaking changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum InstallSource {
+    /// Homebrew (`/opt/homebrew`, `/usr/local/Cellar`, Linuxbrew).
+    Homebrew,
+    /// Global npm install (`node_modules` or an npm prefix shim).
+    Npm,
+    /// `cargo install` (`~/.cargo/bin`).
+    Cargo,
+    /// Nix store or a Nix profile.
+    Nix,
+    /// Distribution package manager (`/usr/bin`, `/bin`, ...).
+    SystemPackage,
+    /// Location did not match any known package manager.
+    Unknown,
+}
+
+impl InstallSource {
+    /// Classifies an install location from its path alone.
+    ///
+    /// Does not touch the filesystem; callers that want symlinks followed
+    /// should use [`InstallationStatus::install_source`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::InstallSource;
+    /// use std::path::Path;
+    ///
+    /// assert_eq!(
+    ///     InstallSource::from_path(Path::new("/opt/homebrew/Cellar/goose/1.0/bin/goose")),
+    ///     InstallSource::Homebrew
+    /// );
+    /// assert_eq!(
+    ///     InstallSource::from_path(Path::new("/home/me/.cargo/bin/goose")),
+    ///     InstallSource::Cargo
+    /// );
+    /// ```
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        let normalized = path.to_string_lossy().replace('\\', "/");
+        let p = normalized.as_str();
+
+        if p.starts_with("/nix/store/")
+            || p.contains("/.nix-profile/")
+            || p.starts_with("/run/current-system/sw/")
+            || p.starts_with("/etc/profiles/per-user/")
+        {
+            Self::Nix
+        } else if p.contains("/Cellar/")
+            || p.starts_with("/opt/homebrew/")
+            || p.starts_with("/home/linuxbrew/.linuxbrew/")
+        {
+            Self::Homebrew
+        } else if p.contains("/.cargo/bin/") {
+            Self::Cargo
+        } else if p.contains("/node_modules/")
+            || p.contains("/.npm-global/")
+            || p.contains("/AppData/Roaming/npm/")
+        {
+            Self::Npm
+        } else if ["/usr/bin/", "/usr/sbin/", "/bin/", "/sbin/"]
+            .iter()
+            .any(|prefix| p.starts_with(prefix))
+        {
+            Self::SystemPackage
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Returns a short, stable identifier for this install source.
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Homebrew => "homebrew",
+            Self::Npm => "npm",
+            Self::Cargo => "cargo",
+            Self::Nix => "nix",
+            Self::SystemPackage => "system_package",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for InstallSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Types of paths a harness may provide.
@@ -402,6 +559,204 @@ pub struct DirectoryResource {
     pub file_format: FileFormat,
 }
 
+impl DirectoryResource {
+    /// Returns `true` if `path` is a resource file laid out as this
+    /// directory's [`DirectoryStructure`] describes.
+    ///
+    /// For [`Flat`](DirectoryStructure::Flat) directories, `path` must be a
+    /// direct child whose name matches `file_pattern`. For
+    /// [`Nested`](DirectoryStructure::Nested) ones, it must be `file_name`
+    /// inside a subdirectory matching `subdir_pattern`. `path` may be
+    /// absolute or relative to the directory. The filesystem is not
+    /// consulted.
+    ///
+    /// Patterns support `*`, `?`, `[...]` and `{a,b}` alternation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::{Path, PathBuf};
+    /// use harness_locate::{DirectoryResource, DirectoryStructure, FileFormat};
+    ///
+    /// let plugins = DirectoryResource {
+    ///     path: PathBuf::from("/home/me/.config/opencode/plugin"),
+    ///     exists: true,
+    ///     structure: DirectoryStructure::Flat { file_pattern: "*.{js,ts}".into() },
+    ///     file_format: FileFormat::Json,
+    /// };
+    /// assert!(plugins.matches(Path::new("/home/me/.config/opencode/plugin/notify.ts")));
+    /// assert!(plugins.matches(Path::new("notify.js")));
+    /// assert!(!plugins.matches(Path::new("notify.tsx")));
+    /// ```
+    #[must_use]
+    pub fn matches(&self, path: &Path) -> bool {
+        let relative = match path.strip_prefix(&self.path) {
+            Ok(relative) => relative,
+            Err(_) if path.is_relative() => path,
+            Err(_) => return false,
+        };
+        let parts: Vec<&str> = match relative
+            .components()
+            .map(|c| c.as_os_str().to_str())
+            .collect::<Option<_>>()
+        {
+            Some(parts) => parts,
+            None => return false,
+        };
+        match (&self.structure, parts.as_slice()) {
+            (DirectoryStructure::Flat { file_pattern }, [name]) => {
+                Glob::new(file_pattern).matches(name)
+            }
+            (
+                DirectoryStructure::Nested {
+                    subdir_pattern,
+                    file_name,
+                },
+                [subdir, name],
+            ) => name == file_name && Glob::new(subdir_pattern).matches(subdir),
+            _ => false,
+        }
+    }
+
+    /// Lists the resource files in this directory, sorted by path.
+    ///
+    /// For [`Nested`](DirectoryStructure::Nested) directories, a
+    /// subdirectory only counts if its `file_name` exists. Entries matched by
+    /// the [`ignore`](crate::ignore) rules of this directory, such as hidden
+    /// files and `node_modules`, are skipped. Reads go through the current
+    /// [`Fs`](crate::fs::Fs).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory exists but cannot be listed.
+    pub fn enumerate(&self) -> std::io::Result<Vec<PathBuf>> {
+        let fs = crate::fs::current();
+        let entries = match fs.read_dir(&self.path) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let rules = IgnoreRules::load(&self.path)?;
+        let name_of = |path: &Path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .filter(|name| !rules.is_ignored(Path::new(name), fs.is_dir(path)))
+                .map(String::from)
+        };
+
+        let mut found: Vec<PathBuf> = match &self.structure {
+            DirectoryStructure::Flat { file_pattern } => {
+                let glob = Glob::new(file_pattern);
+                entries
+                    .into_iter()
+                    .filter(|entry| !fs.is_dir(entry))
+                    .filter(|entry| name_of(entry).is_some_and(|name| glob.matches(&name)))
+                    .collect()
+            }
+            DirectoryStructure::Nested {
+                subdir_pattern,
+                file_name,
+            } => {
+                let glob = Glob::new(subdir_pattern);
+                entries
+                    .into_iter()
+                    .filter(|entry| fs.is_dir(entry))
+                    .filter(|entry| name_of(entry).is_some_and(|name| glob.matches(&name)))
+                    .map(|entry| entry.join(file_name))
+                    .filter(|file| fs.exists(file))
+                    .collect()
+            }
+        };
+        found.sort();
+        Ok(found)
+    }
+
+    /// Lists every file belonging to the resources in this directory,
+    /// sorted by path.
+    ///
+    /// For [`Flat`](DirectoryStructure::Flat) directories these are the
+    /// files [`enumerate`](Self::enumerate) returns. For
+    /// [`Nested`](DirectoryStructure::Nested) ones, each resource
+    /// subdirectory is walked recursively, so supporting scripts and
+    /// references are included. Hidden files, `node_modules`, and anything
+    /// matched by a `.gitignore` or `.skillignore` in this directory or a
+    /// resource subdirectory are skipped; see [`ignore`](crate::ignore).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a directory or ignore file cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use harness_locate::{Harness, HarnessKind, ResourceKind, Scope};
+    ///
+    /// let harness = Harness::new(HarnessKind::ClaudeCode);
+    /// if let Some(skills) = harness.resource(ResourceKind::Skills, &Scope::Global)? {
+    ///     for file in skills.list_files()? {
+    ///         println!("{}", file.display());
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn list_files(&self) -> std::io::Result<Vec<PathBuf>> {
+        let resources = self.enumerate()?;
+        let DirectoryStructure::Nested { file_name, .. } = &self.structure else {
+            return Ok(resources);
+        };
+        let fs = crate::fs::current();
+        let mut rules = IgnoreRules::load(&self.path)?;
+        // Marker directories such as `.claude-plugin` are hidden but are the
+        // resource itself.
+        rules.extend(IgnoreRules::parse(&format!("!/*/{file_name}")));
+        let mut levels = vec![(self.path.clone(), rules)];
+        let mut files = Vec::new();
+        for resource in &resources {
+            if let Some(subdir) = resource.parent() {
+                ignore::walk(&*fs, subdir, &mut levels, &mut files)?;
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+}
+
+/// One copy of a named resource, from [`ResourceLookup`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceMatch {
+    /// The scope the copy was found in.
+    pub scope: Scope,
+    /// The resource file, such as `skills/deploy/SKILL.md`.
+    pub path: PathBuf,
+}
+
+/// Every copy of a named resource across scopes, from
+/// [`Harness::find_resource`](crate::Harness::find_resource).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceLookup {
+    /// The kind of resource searched for.
+    pub kind: ResourceKind,
+    /// The name searched for.
+    pub name: String,
+    /// Copies found, highest precedence first.
+    pub matches: Vec<ResourceMatch>,
+}
+
+impl ResourceLookup {
+    /// Returns the copy the harness loads, if any.
+    #[must_use]
+    pub fn active(&self) -> Option<&ResourceMatch> {
+        self.matches.first()
+    }
+
+    /// Returns the copies hidden by [`active`](Self::active). Edits to
+    /// these have no effect.
+    #[must_use]
+    pub fn shadowed(&self) -> &[ResourceMatch] {
+        self.matches.get(1..).unwrap_or_default()
+    }
+}
+
 /// A configuration file resource location.
 ///
 /// Represents a single configuration file that may contain
@@ -420,6 +775,31 @@ pub struct ConfigResource {
     pub schema_url: Option<String>,
 }
 
+/// Where a harness stores OAuth tokens for remote MCP servers.
+///
+/// This only describes the location; token contents are never read or
+/// decrypted by this crate beyond checking which servers have entries.
+///
+/// # Extensibility
+///
+/// This enum is marked `#[non_exhaustive]` to allow adding new storage
+/// backends in future versions without breaking changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum TokenStore {
+    /// A JSON file with one entry per server.
+    File {
+        /// Path to the credentials file.
+        path: PathBuf,
+    },
+    /// An entry in the operating system keychain.
+    Keychain {
+        /// The keychain service name.
+        service: String,
+    },
+}
+
 /// A value that may be a plain string or a reference to an environment variable.
 ///
 /// This type handles the different syntax each harness uses for environment
@@ -449,6 +829,7 @@ pub struct ConfigResource {
 /// assert_eq!(api_key.to_native(HarnessKind::OpenCode), "{env:MY_API_KEY}");
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum EnvValue {
     /// A plain string value.
@@ -457,6 +838,9 @@ pub enum EnvValue {
     EnvRef {
         /// The name of the environment variable.
         env: String,
+        /// Literal text before the variable's value, e.g. `"Bearer "`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prefix: Option<String>,
     },
 }
 
@@ -488,7 +872,30 @@ impl EnvValue {
     /// ```
     #[must_use]
     pub fn env(var: impl Into<String>) -> Self {
-        Self::EnvRef { env: var.into() }
+        Self::EnvRef {
+            env: var.into(),
+            prefix: None,
+        }
+    }
+
+    /// Creates an environment variable reference preceded by literal text,
+    /// such as an `Authorization` header's `Bearer ` scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use harness_locate::types::{EnvValue, HarnessKind};
+    ///
+    /// let value = EnvValue::env_with_prefix("Bearer ", "GITHUB_PAT");
+    /// assert_eq!(value.to_native(HarnessKind::ClaudeCode), "Bearer ${GITHUB_PAT}");
+    /// assert_eq!(value.to_native(HarnessKind::OpenCode), "Bearer {env:GITHUB_PAT}");
+    /// ```
+    #[must_use]
+    pub fn env_with_prefix(prefix: impl Into<String>, var: impl Into<String>) -> Self {
+        Self::EnvRef {
+            env: var.into(),
+            prefix: Some(prefix.into()).filter(|p| !p.is_empty()),
+        }
     }
 
     /// Converts to the harness-specific native string format.
@@ -502,7 +909,11 @@ impl EnvValue {
     /// - For `Plain`: Returns the string as-is
     /// - For `EnvRef` with Claude Code: Returns `${VAR}`
     /// - For `EnvRef` with OpenCode: Returns `{env:VAR}`
-    /// - For `EnvRef` with Goose: Resolves the env var immediately
+    /// - For `EnvRef` with Goose: Resolves the env var immediately, from the
+    ///   current [`PathContext`](crate::platform::PathContext)'s environment
+    ///   snapshot if it has one (see [`crate::pure`])
+    ///
+    /// An `EnvRef` prefix is written before the reference or resolved value.
     ///
     /// # Examples
     ///
@@ -517,13 +928,16 @@ impl EnvValue {
     pub fn to_native(&self, kind: HarnessKind) -> String {
         match self {
             Self::Plain(s) => s.clone(),
-            Self::EnvRef { env } => match kind {
-                HarnessKind::ClaudeCode | HarnessKind::AmpCode | HarnessKind::CopilotCli => {
-                    format!("${{{env}}}")
-                }
-                HarnessKind::OpenCode => format!("{{env:{env}}}"),
-                HarnessKind::Goose => std::env::var(env).unwrap_or_default(),
-            },
+            Self::EnvRef { env, prefix } => {
+                let value = match kind {
+                    HarnessKind::ClaudeCode | HarnessKind::AmpCode | HarnessKind::CopilotCli => {
+                        format!("${{{env}}}")
+                    }
+                    HarnessKind::OpenCode => format!("{{env:{env}}}"),
+                    HarnessKind::Goose => crate::platform::var(env).unwrap_or_default(),
+                };
+                with_prefix(prefix.as_deref(), value)
+            }
         }
     }
 
@@ -559,15 +973,10 @@ impl EnvValue {
     /// ```
     pub fn try_to_native(&self, kind: HarnessKind) -> crate::Result<String> {
         match self {
-            Self::Plain(s) => Ok(s.clone()),
-            Self::EnvRef { env } => match kind {
-                HarnessKind::ClaudeCode | HarnessKind::AmpCode | HarnessKind::CopilotCli => {
-                    Ok(format!("${{{env}}}"))
-                }
-                HarnessKind::OpenCode => Ok(format!("{{env:{env}}}")),
-                HarnessKind::Goose => std::env::var(env)
-                    .map_err(|_| crate::Error::MissingEnvVar { name: env.clone() }),
-            },
+            Self::EnvRef { env, prefix } if kind == HarnessKind::Goose => crate::platform::var(env)
+                .map(|value| with_prefix(prefix.as_deref(), value))
+                .ok_or_else(|| crate::Error::MissingEnvVar { name: env.clone() }),
+            _ => Ok(self.to_native(kind)),
         }
     }
 
@@ -585,6 +994,9 @@ impl EnvValue {
     /// - For Goose: Always returns `Plain` (Goose doesn't use inline syntax)
     /// - If no pattern matches, returns `Plain`
     ///
+    /// Literal text before a reference at the end of the string, as in
+    /// `Bearer ${TOKEN}`, becomes the reference's prefix.
+    ///
     /// # Examples
     ///
     /// ```
@@ -603,24 +1015,10 @@ impl EnvValue {
     pub fn from_native(s: &str, kind: HarnessKind) -> Self {
         match kind {
             HarnessKind::ClaudeCode | HarnessKind::AmpCode | HarnessKind::CopilotCli => {
-                if let Some(var) = s.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
-                    Self::EnvRef {
-                        env: var.to_string(),
-                    }
-                } else {
-                    Self::Plain(s.to_string())
-                }
-            }
-            HarnessKind::OpenCode => {
-                // Parse {env:VAR} pattern
-                if let Some(var) = s.strip_prefix("{env:").and_then(|s| s.strip_suffix('}')) {
-                    Self::EnvRef {
-                        env: var.to_string(),
-                    }
-                } else {
-                    Self::Plain(s.to_string())
-                }
+                parse_reference(s, "${", "}")
             }
+            // Parse {env:VAR} pattern
+            HarnessKind::OpenCode => parse_reference(s, "{env:", "}"),
             HarnessKind::Goose => {
                 // Goose doesn't use inline env var syntax; values are always plain
                 Self::Plain(s.to_string())
@@ -651,7 +1049,9 @@ impl EnvValue {
     pub fn resolve(&self) -> Option<String> {
         match self {
             Self::Plain(s) => Some(s.clone()),
-            Self::EnvRef { env } => std::env::var(env).ok(),
+            Self::EnvRef { env, prefix } => {
+                crate::platform::var(env).map(|value| with_prefix(prefix.as_deref(), value))
+            }
         }
     }
 
@@ -668,6 +1068,32 @@ impl EnvValue {
     }
 }
 
+fn with_prefix(prefix: Option<&str>, value: String) -> String {
+    match prefix {
+        Some(prefix) => format!("{prefix}{value}"),
+        None => value,
+    }
+}
+
+/// Parses `s` as an env reference written `{open}VAR{close}`, optionally
+/// preceded by literal text.
+pub(crate) fn parse_reference(s: &str, open: &str, close: &str) -> EnvValue {
+    if let Some(var) = s.strip_prefix(open).and_then(|s| s.strip_suffix(close)) {
+        return EnvValue::env(var);
+    }
+    s.strip_suffix(close)
+        .and_then(|rest| rest.rsplit_once(open))
+        .filter(|(prefix, var)| {
+            !prefix.contains(open)
+                && !var.is_empty()
+                && var.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .map_or_else(
+            || EnvValue::plain(s),
+            |(prefix, var)| EnvValue::env_with_prefix(prefix, var),
+        )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -688,11 +1114,33 @@ mod tests {
         assert_eq!(
             value,
             EnvValue::EnvRef {
-                env: "MY_VAR".to_string()
+                env: "MY_VAR".to_string(),
+                prefix: None,
             }
         );
     }
 
+    #[test]
+    fn from_native_reads_prefixed_references() {
+        let bearer = EnvValue::env_with_prefix("Bearer ", "TOKEN");
+        for kind in [HarnessKind::ClaudeCode, HarnessKind::OpenCode] {
+            let native = bearer.to_native(kind);
+            assert_eq!(EnvValue::from_native(&native, kind), bearer, "{native}");
+        }
+        assert_eq!(
+            EnvValue::from_native("x ${A}${B}", HarnessKind::ClaudeCode),
+            EnvValue::plain("x ${A}${B}")
+        );
+        assert_eq!(
+            EnvValue::from_native("cost: $5 {x}", HarnessKind::OpenCode),
+            EnvValue::plain("cost: $5 {x}")
+        );
+        assert_eq!(
+            EnvValue::env_with_prefix("", "TOKEN"),
+            EnvValue::env("TOKEN")
+        );
+    }
+
     #[test]
     fn to_native_plain_returns_value_unchanged() {
         let value = EnvValue::plain("hello world");
@@ -925,6 +1373,48 @@ mod tests {
         assert_eq!(status.config_path(), None);
     }
 
+    #[test]
+    fn install_source_from_path_classifies_known_managers() {
+        let cases = [
+            ("/nix/store/abc-opencode/bin/opencode", InstallSource::Nix),
+            ("/home/me/.nix-profile/bin/goose", InstallSource::Nix),
+            (
+                "/usr/local/Cellar/goose/1.0/bin/goose",
+                InstallSource::Homebrew,
+            ),
+            (
+                "/home/linuxbrew/.linuxbrew/bin/amp",
+                InstallSource::Homebrew,
+            ),
+            ("/home/me/.cargo/bin/goose", InstallSource::Cargo),
+            (
+                "/usr/local/lib/node_modules/@anthropic-ai/claude-code/cli.js",
+                InstallSource::Npm,
+            ),
+            (
+                r"C:\Users\me\AppData\Roaming\npm\claude.cmd",
+                InstallSource::Npm,
+            ),
+            ("/usr/bin/goose", InstallSource::SystemPackage),
+            ("/home/me/bin/claude", InstallSource::Unknown),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(
+                InstallSource::from_path(Path::new(path)),
+                expected,
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn install_source_none_without_binary() {
+        let status = InstallationStatus::ConfigOnly {
+            config_path: PathBuf::from("/home/user/.claude"),
+        };
+        assert_eq!(status.install_source(), None);
+    }
+
     #[test]
     fn directory_names_opencode_singular() {
         assert_eq!(
@@ -978,4 +1468,130 @@ mod tests {
             );
         }
     }
+
+    fn skills_dir() -> DirectoryResource {
+        DirectoryResource {
+            path: PathBuf::from("/p/.claude/skills"),
+            exists: true,
+            structure: DirectoryStructure::Nested {
+                subdir_pattern: "*".into(),
+                file_name: "SKILL.md".into(),
+            },
+            file_format: FileFormat::MarkdownWithFrontmatter,
+        }
+    }
+
+    #[test]
+    fn directory_resource_matches_nested_layout() {
+        let skills = skills_dir();
+        assert!(skills.matches(Path::new("/p/.claude/skills/review/SKILL.md")));
+        assert!(skills.matches(Path::new("review/SKILL.md")));
+        assert!(!skills.matches(Path::new("/p/.claude/skills/review/README.md")));
+        assert!(!skills.matches(Path::new("/p/.claude/skills/SKILL.md")));
+        assert!(!skills.matches(Path::new("/elsewhere/review/SKILL.md")));
+    }
+
+    #[test]
+    fn directory_resource_enumerates_through_fs() {
+        use crate::fs::MemoryFs;
+
+        let ctx = crate::platform::PathContext::fake("/tmp/fake").with_fs(
+            MemoryFs::new()
+                .with_file("/p/.claude/skills/review/SKILL.md", "")
+                .with_file("/p/.claude/skills/draft/notes.md", "")
+                .with_file("/p/.claude/skills/stray.md", "")
+                .with_file("/p/.opencode/plugin/a.ts", "")
+                .with_file("/p/.opencode/plugin/b.js", "")
+                .with_file("/p/.opencode/plugin/c.json", ""),
+        );
+        let plugins = DirectoryResource {
+            path: PathBuf::from("/p/.opencode/plugin"),
+            exists: true,
+            structure: DirectoryStructure::Flat {
+                file_pattern: "*.{js,ts}".into(),
+            },
+            file_format: FileFormat::Json,
+        };
+
+        ctx.scope(|| {
+            assert_eq!(
+                skills_dir().enumerate().unwrap(),
+                [PathBuf::from("/p/.claude/skills/review/SKILL.md")]
+            );
+            assert_eq!(
+                plugins.enumerate().unwrap(),
+                [
+                    PathBuf::from("/p/.opencode/plugin/a.ts"),
+                    PathBuf::from("/p/.opencode/plugin/b.js"),
+                ]
+            );
+            let missing = DirectoryResource {
+                path: PathBuf::from("/p/.claude/agents"),
+                ..skills_dir()
+            };
+            assert!(missing.enumerate().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn directory_resource_lists_files_without_ignored_entries() {
+        use crate::fs::MemoryFs;
+
+        let ctx = crate::platform::PathContext::fake("/tmp/fake").with_fs(
+            MemoryFs::new()
+                .with_file("/p/.claude/skills/.skillignore", "*.orig\n")
+                .with_file("/p/.claude/skills/review/SKILL.md", "")
+                .with_file("/p/.claude/skills/review/scripts/check.sh", "")
+                .with_file("/p/.claude/skills/review/scripts/check.sh.orig", "")
+                .with_file("/p/.claude/skills/review/.gitignore", "/dist/\n")
+                .with_file("/p/.claude/skills/review/dist/bundle.js", "")
+                .with_file("/p/.claude/skills/review/node_modules/a/index.js", "")
+                .with_file("/p/.claude/skills/.hidden/SKILL.md", "")
+                .with_file("/p/.claude/plugins/tools/.claude-plugin/plugin.json", "")
+                .with_file("/p/.claude/plugins/tools/.DS_Store", "")
+                .with_file("/p/.opencode/plugin/a.ts", "")
+                .with_file("/p/.opencode/plugin/.b.ts", ""),
+        );
+        let plugins = DirectoryResource {
+            path: PathBuf::from("/p/.claude/plugins"),
+            exists: true,
+            structure: DirectoryStructure::Nested {
+                subdir_pattern: "*".into(),
+                file_name: ".claude-plugin".into(),
+            },
+            file_format: FileFormat::Json,
+        };
+        let flat = DirectoryResource {
+            path: PathBuf::from("/p/.opencode/plugin"),
+            exists: true,
+            structure: DirectoryStructure::Flat {
+                file_pattern: "*.ts".into(),
+            },
+            file_format: FileFormat::Json,
+        };
+
+        ctx.scope(|| {
+            assert_eq!(
+                skills_dir().enumerate().unwrap(),
+                [PathBuf::from("/p/.claude/skills/review/SKILL.md")]
+            );
+            assert_eq!(
+                skills_dir().list_files().unwrap(),
+                [
+                    PathBuf::from("/p/.claude/skills/review/SKILL.md"),
+                    PathBuf::from("/p/.claude/skills/review/scripts/check.sh"),
+                ]
+            );
+            assert_eq!(
+                plugins.list_files().unwrap(),
+                [PathBuf::from(
+                    "/p/.claude/plugins/tools/.claude-plugin/plugin.json"
+                )]
+            );
+            assert_eq!(
+                flat.list_files().unwrap(),
+                [PathBuf::from("/p/.opencode/plugin/a.ts")]
+            );
+        });
+    }
 }
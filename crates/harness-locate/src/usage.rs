@@ -0,0 +1,289 @@
+//! Disk space taken up by a harness.
+//!
+//! [`disk_usage`] (also [`Harness::disk_usage`]) adds up the bytes and
+//! files of each kind of harness data for a scope: resource directories,
+//! session state, caches and logs, and the remaining configuration. Every
+//! byte is counted once, under the most specific category, so the
+//! categories sum to the [`DiskUsage::total`].
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::{Harness, HarnessKind, Scope};
+//!
+//! let usage = Harness::new(HarnessKind::ClaudeCode).disk_usage(&Scope::Global)?;
+//! for entry in &usage.entries {
+//!     println!("{:?}: {} bytes in {} files", entry.category, entry.usage.bytes, entry.usage.files);
+//! }
+//! println!("{}", serde_json::to_string_pretty(&usage)?);
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::ops::AddAssign;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::harness::Harness;
+use crate::types::{ResourceKind, Scope};
+
+/// What a [`UsageEntry`] counts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum UsageCategory {
+    /// The skills directory.
+    Skills,
+    /// The commands directory.
+    Commands,
+    /// The agents directory.
+    Agents,
+    /// The plugins directory.
+    Plugins,
+    /// Session transcripts and other per-session state.
+    Sessions,
+    /// The cache directory.
+    Cache,
+    /// The logs directory, when outside the cache.
+    Logs,
+    /// Everything else in the config directory, and the MCP config file.
+    Config,
+}
+
+impl UsageCategory {
+    fn for_resource(kind: ResourceKind) -> Self {
+        match kind {
+            ResourceKind::Skills => Self::Skills,
+            ResourceKind::Commands => Self::Commands,
+            ResourceKind::Agents => Self::Agents,
+            ResourceKind::Plugins => Self::Plugins,
+        }
+    }
+}
+
+/// Bytes and files counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Usage {
+    /// Total size of the files, in bytes.
+    pub bytes: u64,
+    /// Number of files, not counting directories.
+    pub files: u64,
+}
+
+impl AddAssign for Usage {
+    fn add_assign(&mut self, other: Self) {
+        self.bytes += other.bytes;
+        self.files += other.files;
+    }
+}
+
+/// The space one category takes up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageEntry {
+    /// What was counted.
+    pub category: UsageCategory,
+    /// The directories and files counted, which all exist.
+    pub paths: Vec<PathBuf>,
+    /// Their size.
+    pub usage: Usage,
+}
+
+/// The space a harness takes up in one scope.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskUsage {
+    /// Name of the harness.
+    pub harness: String,
+    /// One entry per category found, in [`UsageCategory`] order.
+    pub entries: Vec<UsageEntry>,
+}
+
+impl DiskUsage {
+    /// Returns the usage of `category`, or zero if nothing was found.
+    #[must_use]
+    pub fn get(&self, category: UsageCategory) -> Usage {
+        self.entries
+            .iter()
+            .find(|entry| entry.category == category)
+            .map(|entry| entry.usage)
+            .unwrap_or_default()
+    }
+
+    /// Returns the usage of all categories together.
+    #[must_use]
+    pub fn total(&self) -> Usage {
+        let mut total = Usage::default();
+        for entry in &self.entries {
+            total += entry.usage;
+        }
+        total
+    }
+}
+
+/// Adds up the space `harness` takes up in `scope`.
+///
+/// Sessions, caches and logs are per user, so they are only counted for
+/// [`Scope::Global`], except Claude Code's per-project transcripts. A
+/// directory nested in another category's directory, such as
+/// `~/.claude/skills/` in `~/.claude/`, is only counted once.
+///
+/// # Errors
+///
+/// Returns an error if a path cannot be resolved, a directory cannot be
+/// read, or the filesystem cannot report file sizes.
+pub fn disk_usage(harness: &Harness, scope: &Scope) -> Result<DiskUsage> {
+    let fs = crate::fs::current();
+    let mut roots: Vec<(UsageCategory, PathBuf)> = Vec::new();
+
+    for kind in [
+        ResourceKind::Skills,
+        ResourceKind::Commands,
+        ResourceKind::Agents,
+        ResourceKind::Plugins,
+    ] {
+        match harness.resource(kind, scope) {
+            Ok(Some(dir)) => roots.push((UsageCategory::for_resource(kind), dir.path)),
+            Ok(None) | Err(crate::Error::UnsupportedScope { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    match scope {
+        Scope::Global => {
+            if let Some(data) = harness.data(scope)? {
+                for dir in crate::cleanup::session_dirs(harness.kind()) {
+                    roots.push((UsageCategory::Sessions, data.join(dir)));
+                }
+            }
+            if let Some(cache) = harness.cache()? {
+                roots.push((UsageCategory::Cache, cache));
+            }
+            if let Some(logs) = harness.logs()? {
+                roots.push((UsageCategory::Logs, logs));
+            }
+        }
+        Scope::Project(_) => {
+            if harness.kind() == Some(crate::HarnessKind::ClaudeCode)
+                && let Some(data) = harness.data(scope)?
+            {
+                roots.push((UsageCategory::Sessions, data));
+            }
+        }
+        Scope::Custom(_) => {}
+    }
+    match harness.config(scope) {
+        Ok(config) => roots.push((UsageCategory::Config, config)),
+        Err(crate::Error::UnsupportedScope { .. }) => {}
+        Err(e) => return Err(e),
+    }
+    match harness.mcp(scope) {
+        Ok(Some(mcp)) => roots.push((UsageCategory::Config, mcp.file)),
+        Ok(None) | Err(crate::Error::UnsupportedScope { .. }) => {}
+        Err(e) => return Err(e),
+    }
+
+    let mut entries: Vec<UsageEntry> = Vec::new();
+    for (index, (category, path)) in roots.iter().enumerate() {
+        let repeated = roots[..index].iter().any(|(_, other)| other == path);
+        if repeated || !fs.exists(path) {
+            continue;
+        }
+        let nested: Vec<PathBuf> = roots
+            .iter()
+            .filter(|(_, other)| other != path && other.starts_with(path))
+            .map(|(_, other)| other.clone())
+            .collect();
+        let (usage, _) = measure(path, &nested)?;
+        match entries.iter_mut().find(|entry| entry.category == *category) {
+            Some(entry) => {
+                entry.paths.push(path.clone());
+                entry.usage += usage;
+            }
+            None => entries.push(UsageEntry {
+                category: *category,
+                paths: vec![path.clone()],
+                usage,
+            }),
+        }
+    }
+    entries.sort_by_key(|entry| entry.category as u8);
+
+    Ok(DiskUsage {
+        harness: harness.name().to_string(),
+        entries,
+    })
+}
+
+/// Returns the size of `path` and everything in it, skipping `exclude`,
+/// and the latest modification time of anything counted.
+pub(crate) fn measure(path: &Path, exclude: &[PathBuf]) -> Result<(Usage, Option<SystemTime>)> {
+    let fs = crate::fs::current();
+    let metadata = fs.metadata(path)?;
+    let mut modified = metadata.modified;
+    let mut usage = Usage::default();
+    if metadata.is_dir {
+        for entry in fs.read_dir(path)? {
+            if exclude.contains(&entry) {
+                continue;
+            }
+            let (entry_usage, entry_modified) = measure(&entry, exclude)?;
+            usage += entry_usage;
+            modified = modified.max(entry_modified);
+        }
+    } else {
+        usage = Usage {
+            bytes: metadata.len,
+            files: 1,
+        };
+    }
+    Ok((usage, modified))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+    use crate::types::HarnessKind;
+
+    #[test]
+    fn counts_each_file_once() {
+        let claude = Path::new("/fake/home/.claude");
+        let fs = MemoryFs::new()
+            .with_file(claude.join("skills/a/SKILL.md"), "12345")
+            .with_file(claude.join("skills/b/SKILL.md"), "123")
+            .with_file(claude.join("projects/-repo/s.jsonl"), "1234567890")
+            .with_file(claude.join("settings.json"), "{}")
+            .with_file(claude.join(".mcp.json"), "{}\n");
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+
+        let usage = ctx.scope(|| disk_usage(&harness, &Scope::Global)).unwrap();
+        assert_eq!(
+            usage.get(UsageCategory::Skills),
+            Usage { bytes: 8, files: 2 }
+        );
+        assert_eq!(
+            usage.get(UsageCategory::Sessions),
+            Usage {
+                bytes: 10,
+                files: 1
+            }
+        );
+        assert_eq!(
+            usage.get(UsageCategory::Config),
+            Usage { bytes: 5, files: 2 }
+        );
+        assert_eq!(usage.get(UsageCategory::Cache), Usage::default());
+        assert_eq!(
+            usage.total(),
+            Usage {
+                bytes: 23,
+                files: 5
+            }
+        );
+
+        let json = serde_json::to_value(&usage).unwrap();
+        assert_eq!(json["entries"][0]["category"], "skills");
+    }
+}
@@ -0,0 +1,251 @@
+//! Comparing validation results against a saved baseline.
+//!
+//! CI jobs that adopt validation on an existing tree cannot fix every
+//! issue at once. [`ValidationBaseline`] records the issues present today
+//! and [`diff_validation`] reports only those introduced since, so a check
+//! fails on new problems alone.
+//!
+//! Issues are matched by [`IssueId`]: their code, the resource they were
+//! found in and the field. Messages are ignored, so rewording a check does
+//! not make old issues look new.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{Finding, ValidationIssue};
+use crate::error::{Error, Result};
+
+/// Newest [`ValidationBaseline`] format version this crate reads and
+/// writes.
+pub const BASELINE_VERSION: u32 = 1;
+
+/// The stable identity of a validation issue.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct IssueId {
+    /// The issue code, if the issue has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// The resource the issue was found in, such as `skill:deploy`. Empty
+    /// for bare [`ValidationIssue`]s.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub resource: String,
+    /// The field path of the issue.
+    pub field: String,
+}
+
+/// Anything with an [`IssueId`].
+pub trait Identified {
+    /// Returns the identity used to match issues across runs.
+    fn issue_id(&self) -> IssueId;
+}
+
+impl Identified for IssueId {
+    fn issue_id(&self) -> IssueId {
+        self.clone()
+    }
+}
+
+impl Identified for ValidationIssue {
+    fn issue_id(&self) -> IssueId {
+        IssueId {
+            code: self.code.map(str::to_string),
+            resource: String::new(),
+            field: self.field.clone(),
+        }
+    }
+}
+
+impl Identified for Finding {
+    fn issue_id(&self) -> IssueId {
+        IssueId {
+            resource: format!("{}:{}", self.kind.as_str(), self.name),
+            ..self.issue.issue_id()
+        }
+    }
+}
+
+/// The result of [`diff_validation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationDiff<'a, T> {
+    /// Current issues not in the baseline, in their original order.
+    pub introduced: Vec<&'a T>,
+    /// Baseline issues no longer present, sorted.
+    pub resolved: Vec<IssueId>,
+}
+
+impl<T> ValidationDiff<'_, T> {
+    /// Returns `true` if no issue was introduced.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.introduced.is_empty()
+    }
+}
+
+/// Compares `current` issues against `baseline`.
+///
+/// Issues sharing an [`IssueId`] are counted, so a third copy of an issue
+/// the baseline has twice is reported as introduced.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::validation::{ValidationIssue, diff_validation};
+///
+/// let baseline = [ValidationIssue::warning("timeout", "too long", Some("timeout.excessive"))];
+/// let current = [
+///     ValidationIssue::warning("timeout", "very long", Some("timeout.excessive")),
+///     ValidationIssue::error("command", "empty", Some("stdio.command.empty")),
+/// ];
+///
+/// let diff = diff_validation(&baseline, &current);
+/// assert_eq!(diff.introduced, [&current[1]]);
+/// assert!(diff.resolved.is_empty());
+/// ```
+#[must_use]
+pub fn diff_validation<'a, B: Identified, T: Identified>(
+    baseline: &[B],
+    current: &'a [T],
+) -> ValidationDiff<'a, T> {
+    let mut remaining: HashMap<IssueId, usize> = HashMap::new();
+    for issue in baseline {
+        *remaining.entry(issue.issue_id()).or_default() += 1;
+    }
+    let introduced = current
+        .iter()
+        .filter(|issue| match remaining.get_mut(&issue.issue_id()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .collect();
+    let mut resolved: Vec<IssueId> = remaining
+        .into_iter()
+        .flat_map(|(id, count)| std::iter::repeat_n(id, count))
+        .collect();
+    resolved.sort();
+    ValidationDiff {
+        introduced,
+        resolved,
+    }
+}
+
+/// Issues accepted as known, saved as JSON between runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationBaseline {
+    /// Format version, [`BASELINE_VERSION`] when written by this crate.
+    pub version: u32,
+    /// The accepted issues, sorted.
+    pub issues: Vec<IssueId>,
+}
+
+impl ValidationBaseline {
+    /// Records `issues` as the baseline.
+    #[must_use]
+    pub fn from_issues<T: Identified>(issues: &[T]) -> Self {
+        let mut issues: Vec<_> = issues.iter().map(Identified::issue_id).collect();
+        issues.sort();
+        Self {
+            version: BASELINE_VERSION,
+            issues,
+        }
+    }
+
+    /// Returns the issues in `current` that are not in the baseline.
+    #[must_use]
+    pub fn diff<'a, T: Identified>(&self, current: &'a [T]) -> ValidationDiff<'a, T> {
+        diff_validation(&self.issues, current)
+    }
+
+    /// Reads a baseline file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or parsed, or
+    /// [`Error::BaselineVersion`] if it was written by a newer version.
+    pub fn load(path: &Path) -> Result<Self> {
+        let baseline: Self = serde_json::from_str(&crate::fs::read_text(path)?.text)?;
+        if baseline.version > BASELINE_VERSION {
+            return Err(Error::BaselineVersion {
+                found: baseline.version,
+                supported: BASELINE_VERSION,
+            });
+        }
+        Ok(baseline)
+    }
+
+    /// Writes the baseline to `path`, creating parent directories.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let fs = crate::fs::current();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs.create_dir_all(parent)?;
+        }
+        let mut json = serde_json::to_string_pretty(self)?;
+        json.push('\n');
+        fs.write(path, json.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+    use crate::validation::TargetKind;
+
+    fn finding(kind: TargetKind, name: &str, field: &str, code: &'static str) -> Finding {
+        Finding {
+            kind,
+            name: name.to_string(),
+            path: PathBuf::from("/cfg"),
+            issue: ValidationIssue::warning(field, "message", Some(code)),
+        }
+    }
+
+    #[test]
+    fn reports_only_issues_missing_from_the_baseline() {
+        let before = [
+            finding(TargetKind::McpServer, "github", "env.GH_TOKEN", "env"),
+            finding(TargetKind::Skill, "lint", "body", "safety"),
+            finding(TargetKind::Skill, "old", "body", "safety"),
+        ];
+        let baseline = ValidationBaseline::from_issues(&before);
+        let ctx = PathContext::fake("/fake").with_fs(MemoryFs::new());
+        let path = Path::new("/repo/.harness/baseline.json");
+        ctx.scope(|| baseline.save(path)).unwrap();
+        let loaded = ctx.scope(|| ValidationBaseline::load(path)).unwrap();
+        assert_eq!(loaded, baseline);
+
+        let after = [
+            finding(TargetKind::Skill, "lint", "body", "safety"),
+            finding(TargetKind::McpServer, "github", "env.GH_TOKEN", "env"),
+            finding(TargetKind::Skill, "deploy", "body", "safety"),
+            finding(TargetKind::McpServer, "github", "env.GH_TOKEN", "env"),
+        ];
+        let diff = loaded.diff(&after);
+        assert!(!diff.is_clean());
+        assert_eq!(diff.introduced, [&after[2], &after[3]]);
+        assert_eq!(diff.resolved.len(), 1);
+        assert_eq!(diff.resolved[0].resource, "skill:old");
+    }
+
+    #[test]
+    fn rejects_newer_baselines() {
+        let fs = MemoryFs::new().with_file("/b.json", r#"{"version": 2, "issues": []}"#);
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let err = ctx
+            .scope(|| ValidationBaseline::load(Path::new("/b.json")))
+            .unwrap_err();
+        assert!(matches!(err, Error::BaselineVersion { found: 2, .. }));
+    }
+}
@@ -0,0 +1,1987 @@
+//! Validation checks behind the `validation` feature.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use url::Url;
+
+use super::*;
+use crate::mcp::{
+    GooseExtension, GooseExtensionKind, HttpMcpServer, McpCapabilities, McpServer, SseMcpServer,
+    StdioMcpServer,
+};
+use crate::skill::LocalizedText;
+use crate::target::{Platform, PlatformSpec, Platforms};
+use crate::types::EnvValue;
+
+static SKILL_NAME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(SKILL_NAME_REGEX).expect("invalid skill name regex"));
+
+/// Maximum recommended timeout in milliseconds (5 minutes).
+const MAX_RECOMMENDED_TIMEOUT_MS: u64 = 300_000;
+
+/// Validates an MCP server configuration.
+///
+/// Checks for structural issues like empty commands, invalid URLs,
+/// excessive timeouts, and suspicious environment variable names.
+/// Returns all issues found, allowing callers to see the complete picture.
+///
+/// # Arguments
+///
+/// * `server` - The MCP server configuration to validate
+///
+/// # Returns
+///
+/// A vector of validation issues. An empty vector means no issues were found.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::mcp::{McpServer, StdioMcpServer};
+/// use harness_locate::validation::validate_mcp_server;
+///
+/// let server = McpServer::Stdio(StdioMcpServer {
+///     command: "node".to_string(),
+///     args: vec!["server.js".to_string()],
+///     env: std::collections::HashMap::new(),
+///     cwd: None,
+///     enabled: true,
+///     timeout_ms: None,
+/// });
+///
+/// let issues = validate_mcp_server(&server);
+/// assert!(issues.is_empty()); // Valid configuration
+/// ```
+#[must_use]
+pub fn validate_mcp_server(server: &McpServer) -> Vec<ValidationIssue> {
+    match server {
+        McpServer::Stdio(s) => validate_stdio(s),
+        McpServer::Sse(s) => validate_sse(s),
+        McpServer::Http(s) => validate_http(s),
+        McpServer::GooseNative(g) => validate_goose_native(g),
+    }
+}
+
+/// Validates an MCP server configuration for a specific harness.
+///
+/// Combines base validation with harness-specific capability checks.
+/// Returns all issues found, including structural problems and harness incompatibilities.
+#[must_use]
+pub fn validate_for_harness(server: &McpServer, kind: HarnessKind) -> Vec<ValidationIssue> {
+    let mut issues = validate_mcp_server(server);
+    let caps = McpCapabilities::for_kind(kind);
+    let harness_name = kind.as_str();
+
+    match server {
+        McpServer::Stdio(s) => {
+            if s.cwd.is_some() && !caps.cwd {
+                issues.push(ValidationIssue::error(
+                    "cwd",
+                    format!("Working directory not supported by {harness_name}"),
+                    Some(CODE_CWD_UNSUPPORTED),
+                ));
+            }
+            if !s.enabled && !caps.toggle {
+                issues.push(ValidationIssue::warning(
+                    "enabled",
+                    format!("{harness_name} ignores the enabled field; server will always run"),
+                    Some(CODE_TOGGLE_UNSUPPORTED),
+                ));
+            }
+        }
+        McpServer::Sse(s) => {
+            if kind == HarnessKind::ClaudeCode {
+                issues.push(ValidationIssue::warning(
+                    "transport",
+                    "SSE transport works but HTTP is preferred for Claude Code",
+                    Some(CODE_SSE_DEPRECATED),
+                ));
+            }
+            if !s.enabled && !caps.toggle {
+                issues.push(ValidationIssue::warning(
+                    "enabled",
+                    format!("{harness_name} ignores the enabled field; server will always run"),
+                    Some(CODE_TOGGLE_UNSUPPORTED),
+                ));
+            }
+        }
+        McpServer::Http(s) => {
+            if !s.enabled && !caps.toggle {
+                issues.push(ValidationIssue::warning(
+                    "enabled",
+                    format!("{harness_name} ignores the enabled field; server will always run"),
+                    Some(CODE_TOGGLE_UNSUPPORTED),
+                ));
+            }
+        }
+        McpServer::GooseNative(g) => {
+            if !caps.goose_native {
+                issues.push(ValidationIssue::error(
+                    "transport",
+                    format!(
+                        "Goose extension '{}' only runs inside Goose, not {harness_name}",
+                        g.name
+                    ),
+                    Some(CODE_GOOSE_NATIVE_UNSUPPORTED),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Validates agent frontmatter content for a specific harness.
+///
+/// Returns an empty vector if valid, or a list of issues found.
+/// Returns a single `CODE_AGENT_UNSUPPORTED` error if harness doesn't support agents.
+#[must_use]
+pub fn validate_agent_for_harness(content: &str, kind: HarnessKind) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    let caps = match AgentCapabilities::for_kind(kind) {
+        Some(c) => c,
+        None => {
+            issues.push(ValidationIssue::error(
+                "agent",
+                format!("{} does not support agents", kind.as_str()),
+                Some(CODE_AGENT_UNSUPPORTED),
+            ));
+            return issues;
+        }
+    };
+
+    let frontmatter = match crate::skill::parse_frontmatter(content) {
+        Ok(fm) => fm,
+        Err(e) => {
+            issues.push(ValidationIssue::error(
+                "frontmatter",
+                format!("failed to parse frontmatter: {e}"),
+                Some(CODE_AGENT_PARSE_ERROR),
+            ));
+            return issues;
+        }
+    };
+
+    let yaml = match &frontmatter.yaml {
+        Some(y) => y,
+        None => return issues,
+    };
+
+    if let Some(tools) = yaml.get("tools") {
+        issues.extend(validate_tools_format(tools, caps.tools_format, kind));
+    }
+
+    if let Some(color) = yaml.get("color").and_then(|v| v.as_str()) {
+        issues.extend(validate_color_format(color, caps.color_format, kind));
+    }
+
+    if let Some(mode) = yaml.get("mode").and_then(|v| v.as_str())
+        && !caps.supported_modes.contains(&mode)
+    {
+        issues.push(ValidationIssue::error(
+            "mode",
+            format!(
+                "mode '{}' not supported by {}; valid: {:?}",
+                mode,
+                kind.as_str(),
+                caps.supported_modes
+            ),
+            Some(CODE_AGENT_MODE_UNSUPPORTED),
+        ));
+    }
+
+    issues
+}
+
+/// Validates skill frontmatter content for a specific harness.
+///
+/// Returns an empty vector if valid, or a list of issues found.
+/// Returns a single `CODE_SKILL_UNSUPPORTED` error if harness doesn't support skills.
+#[must_use]
+pub fn validate_skill_for_harness(
+    content: &str,
+    directory_name: &str,
+    kind: HarnessKind,
+) -> Vec<ValidationIssue> {
+    validate_skill_with(
+        content,
+        directory_name,
+        kind.as_str(),
+        SkillCapabilities::for_kind(kind),
+    )
+}
+
+/// Validates skill frontmatter against `caps`, naming the harness `harness`
+/// in messages.
+#[must_use]
+pub(crate) fn validate_skill_with(
+    content: &str,
+    directory_name: &str,
+    harness: &str,
+    caps: Option<SkillCapabilities>,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    validate_skill_parsed(content, directory_name, harness, caps, &mut issues);
+    issues.into_iter().map(ValidationIssue::from).collect()
+}
+
+/// Validates skill frontmatter for a specific harness like
+/// [`validate_skill_for_harness`], allocating as little as possible.
+///
+/// The `name` and `description` are borrowed from `content` and the rest
+/// of the frontmatter is only checked for valid YAML, without building a
+/// document tree. Skills using frontmatter this fast path does not cover,
+/// such as TOML, several YAML documents, localized descriptions or a
+/// `platforms` list, are validated in full. The issues are the same as
+/// [`validate_skill_for_harness`] returns, except that duplicate keys
+/// below the top level are not reported.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::types::HarnessKind;
+/// use harness_locate::validation::{CODE_SKILL_NAME_FORMAT, validate_skill_borrowed};
+///
+/// let issues = validate_skill_borrowed("---\nname: Bad Name\n---\n", "bad", HarnessKind::OpenCode);
+/// assert!(issues.iter().any(|issue| issue.code == Some(CODE_SKILL_NAME_FORMAT)));
+/// ```
+#[must_use]
+pub fn validate_skill_borrowed<'a>(
+    content: &'a str,
+    directory_name: &str,
+    kind: HarnessKind,
+) -> Vec<ValidationIssueRef<'a>> {
+    let harness = kind.as_str();
+    let mut issues = Vec::new();
+    let Some(caps) = SkillCapabilities::for_kind(kind) else {
+        issues.push(skill_unsupported(harness));
+        return issues;
+    };
+
+    let header = match crate::skill::scan_skill_header(content) {
+        Ok(header) if scanned_header_is_complete(&header) => header,
+        _ => {
+            validate_skill_parsed(content, directory_name, harness, Some(caps), &mut issues);
+            return issues;
+        }
+    };
+    if let Some(name) = &header.name {
+        check_skill_name(name, directory_name, &caps, &mut issues);
+    }
+    check_skill_description(
+        header.description.as_deref().map(|text| (None, text)),
+        header.description.is_some(),
+        harness,
+        &caps,
+        &mut issues,
+    );
+    issues
+}
+
+/// Returns `true` if `header` holds everything skill validation checks,
+/// and its frontmatter is a single valid YAML document.
+fn scanned_header_is_complete(header: &crate::skill::SkillHeader<'_>) -> bool {
+    if header.yaml.is_empty() || !header.descriptions.is_empty() || header.has_key("platforms") {
+        return false;
+    }
+    let mut keys: Vec<&str> = crate::skill::top_level_entries(header.yaml)
+        .map(|(key, _, _)| key)
+        .collect();
+    keys.sort_unstable();
+    let unique = keys.windows(2).all(|pair| pair[0] != pair[1]);
+    unique && serde_yaml::from_str::<serde::de::IgnoredAny>(header.yaml).is_ok()
+}
+
+fn skill_unsupported<'a>(harness: &str) -> ValidationIssueRef<'a> {
+    ValidationIssueRef::error(
+        "skill",
+        format!("{} does not support skills", harness),
+        Some(CODE_SKILL_UNSUPPORTED),
+    )
+}
+
+/// Validates skill frontmatter against `caps` with a full parse.
+fn validate_skill_parsed(
+    content: &str,
+    directory_name: &str,
+    harness: &str,
+    caps: Option<SkillCapabilities>,
+    issues: &mut Vec<ValidationIssueRef<'_>>,
+) {
+    let Some(caps) = caps else {
+        issues.push(skill_unsupported(harness));
+        return;
+    };
+
+    let frontmatter = match crate::skill::parse_frontmatter(content) {
+        Ok(fm) => fm,
+        Err(e) => {
+            issues.push(ValidationIssueRef::error(
+                "frontmatter",
+                format!("failed to parse frontmatter: {e}"),
+                Some(CODE_SKILL_PARSE_ERROR),
+            ));
+            return;
+        }
+    };
+
+    let Some(yaml) = &frontmatter.yaml else {
+        return;
+    };
+
+    if let Some(name) = yaml.get("name").and_then(|v| v.as_str()) {
+        check_skill_name(name, directory_name, &caps, issues);
+    }
+
+    if let Some(platforms) = yaml.get("platforms") {
+        let entries = match platforms {
+            serde_yaml::Value::Sequence(items) => items.iter().collect(),
+            other => vec![other],
+        };
+        for entry in entries {
+            let text = entry.as_str().map_or_else(
+                || serde_yaml::to_string(entry).unwrap_or_default(),
+                String::from,
+            );
+            if text.parse::<PlatformSpec>().is_err() {
+                issues.push(ValidationIssueRef::error(
+                    "platforms",
+                    format!(
+                        "unknown platform '{}', expected an OS (macos, linux, windows), \
+                         an architecture (x86_64, aarch64) or os-arch",
+                        text.trim()
+                    ),
+                    Some(CODE_PLATFORM_INVALID),
+                ));
+            }
+        }
+    }
+
+    let description = yaml
+        .get("description")
+        .and_then(|v| serde_yaml::from_value::<LocalizedText>(v.clone()).ok());
+    check_skill_description(
+        description.iter().flat_map(LocalizedText::variants),
+        description.is_some(),
+        harness,
+        &caps,
+        issues,
+    );
+}
+
+fn check_skill_name(
+    name: &str,
+    directory_name: &str,
+    caps: &SkillCapabilities,
+    issues: &mut Vec<ValidationIssueRef<'_>>,
+) {
+    if caps.name_format == NameFormat::LowercaseHyphenated && !SKILL_NAME_RE.is_match(name) {
+        issues.push(ValidationIssueRef::error(
+            "name",
+            format!(
+                "name '{}' must be lowercase alphanumeric with hyphens (regex: {})",
+                name, SKILL_NAME_REGEX
+            ),
+            Some(CODE_SKILL_NAME_FORMAT),
+        ));
+    }
+
+    if name.len() > SKILL_NAME_MAX_LEN {
+        issues.push(ValidationIssueRef::error(
+            "name",
+            format!("name exceeds {} characters", SKILL_NAME_MAX_LEN),
+            Some(CODE_SKILL_NAME_LENGTH),
+        ));
+    }
+
+    if caps.name_must_match_directory && name != directory_name {
+        issues.push(ValidationIssueRef::error(
+            "name",
+            format!(
+                "name '{}' must match directory name '{}'",
+                name, directory_name
+            ),
+            Some(CODE_SKILL_NAME_DIRECTORY_MISMATCH),
+        ));
+    }
+}
+
+/// Checks the length of each `(locale, text)` description variant, or
+/// warns if there is no description and `caps` wants one.
+fn check_skill_description<'t>(
+    variants: impl IntoIterator<Item = (Option<&'t str>, &'t str)>,
+    present: bool,
+    harness: &str,
+    caps: &SkillCapabilities,
+    issues: &mut Vec<ValidationIssueRef<'_>>,
+) {
+    if !present {
+        if caps.description_required {
+            issues.push(ValidationIssueRef::warning(
+                "description",
+                format!("{} recommends a description field", harness),
+                Some(CODE_SKILL_DESCRIPTION_MISSING),
+            ));
+        }
+        return;
+    }
+    for (locale, text) in variants {
+        if text.len() > SKILL_DESCRIPTION_MAX_LEN {
+            let label = match locale {
+                Some(locale) => format!("description ({locale})"),
+                None => "description".to_string(),
+            };
+            issues.push(ValidationIssueRef::error(
+                "description",
+                format!("{label} exceeds {SKILL_DESCRIPTION_MAX_LEN} characters"),
+                Some(CODE_SKILL_DESCRIPTION_LENGTH),
+            ));
+        }
+    }
+}
+
+/// Validates a command file's argument placeholders for a specific harness.
+///
+/// Returns an empty vector if valid, or a list of issues found.
+/// Returns a single `CODE_COMMAND_UNSUPPORTED` error if harness doesn't support commands.
+#[must_use]
+pub fn validate_command_for_harness(content: &str, kind: HarnessKind) -> Vec<ValidationIssue> {
+    validate_command_with(content, kind.as_str(), CommandCapabilities::for_kind(kind))
+}
+
+/// Validates a command file against `caps`, naming the harness `harness`
+/// in messages.
+#[must_use]
+pub(crate) fn validate_command_with(
+    content: &str,
+    harness: &str,
+    caps: Option<CommandCapabilities>,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    let Some(caps) = caps else {
+        issues.push(ValidationIssue::error(
+            "command",
+            format!("{} does not support commands", harness),
+            Some(CODE_COMMAND_UNSUPPORTED),
+        ));
+        return issues;
+    };
+
+    let signature = match crate::command::parse_command_signature(content) {
+        Ok(sig) => sig,
+        Err(e) => {
+            issues.push(ValidationIssue::error(
+                "frontmatter",
+                format!("failed to parse frontmatter: {e}"),
+                Some(CODE_COMMAND_PARSE_ERROR),
+            ));
+            return issues;
+        }
+    };
+
+    if signature.uses_arguments && !caps.arguments_placeholder {
+        issues.push(ValidationIssue::warning(
+            "body",
+            format!("{} does not substitute $ARGUMENTS", harness),
+            Some(CODE_COMMAND_PLACEHOLDER_UNSUPPORTED),
+        ));
+    }
+
+    if !signature.positional.is_empty() {
+        if !caps.positional_placeholders {
+            issues.push(ValidationIssue::warning(
+                "body",
+                format!(
+                    "{} does not substitute positional placeholders (${})",
+                    harness, signature.positional[0]
+                ),
+                Some(CODE_COMMAND_PLACEHOLDER_UNSUPPORTED),
+            ));
+        }
+
+        let missing = signature.missing_positionals();
+        if !missing.is_empty() {
+            let missing: Vec<String> = missing.iter().map(|n| format!("${n}")).collect();
+            issues.push(ValidationIssue::warning(
+                "body",
+                format!(
+                    "uses ${} but never reads {}",
+                    signature.arity(),
+                    missing.join(", ")
+                ),
+                Some(CODE_COMMAND_POSITIONAL_GAP),
+            ));
+        }
+    }
+
+    match &signature.argument_hint {
+        Some(_) if !caps.argument_hint => {
+            issues.push(ValidationIssue::warning(
+                "argument-hint",
+                format!("{} ignores argument-hint", harness),
+                Some(CODE_COMMAND_HINT_UNSUPPORTED),
+            ));
+        }
+        Some(_) => {
+            let hinted = signature.hint_arguments.len();
+            if hinted > 0 && !signature.positional.is_empty() && hinted != signature.arity() {
+                issues.push(ValidationIssue::warning(
+                    "argument-hint",
+                    format!(
+                        "argument-hint names {} argument(s) but the body reads {}",
+                        hinted,
+                        signature.arity()
+                    ),
+                    Some(CODE_COMMAND_HINT_MISMATCH),
+                ));
+            }
+        }
+        None if caps.argument_hint && !signature.positional.is_empty() => {
+            issues.push(ValidationIssue::warning(
+                "argument-hint",
+                format!(
+                    "command reads {} positional argument(s) but has no argument-hint",
+                    signature.arity()
+                ),
+                Some(CODE_COMMAND_HINT_MISSING),
+            ));
+        }
+        None => {}
+    }
+
+    issues
+}
+
+impl CompatibilityScore {
+    fn from_issues(issues: Vec<ValidationIssue>) -> Self {
+        let (blocking, warnings): (Vec<_>, Vec<_>) = issues
+            .into_iter()
+            .partition(|i| i.severity == Severity::Error);
+
+        let unusable = blocking.iter().any(|i| {
+            matches!(
+                i.code,
+                Some(CODE_SKILL_UNSUPPORTED | CODE_SKILL_PARSE_ERROR)
+            )
+        });
+        let score = if unusable {
+            0
+        } else {
+            let penalty = blocking.len() * usize::from(COMPATIBILITY_ERROR_PENALTY)
+                + warnings.len() * usize::from(COMPATIBILITY_WARNING_PENALTY);
+            100u8.saturating_sub(u8::try_from(penalty).unwrap_or(u8::MAX))
+        };
+        let level = match score {
+            100 => CompatibilityLevel::Compatible,
+            0 => CompatibilityLevel::Incompatible,
+            _ => CompatibilityLevel::Partial,
+        };
+
+        Self {
+            score,
+            level,
+            blocking,
+            warnings,
+        }
+    }
+}
+
+/// Scores a skill against every harness in [`HarnessKind::ALL`].
+///
+/// The scores are keyed, and iterate, in [`HarnessKind::ALL`] order.
+///
+/// Runs [`validate_skill_for_harness`] for each harness and summarizes the
+/// result, e.g. to show which harnesses a published skill works with.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::types::HarnessKind;
+/// use harness_locate::validation::{CompatibilityLevel, score_skill_compatibility};
+///
+/// let content = "---\nname: my-skill\ndescription: Does things\n---\n# Body";
+/// let scores = score_skill_compatibility(content, "my-skill");
+///
+/// assert_eq!(scores[&HarnessKind::OpenCode].level, CompatibilityLevel::Compatible);
+/// assert_eq!(scores[&HarnessKind::Goose].level, CompatibilityLevel::Incompatible);
+/// ```
+#[must_use]
+pub fn score_skill_compatibility(
+    content: &str,
+    directory_name: &str,
+) -> BTreeMap<HarnessKind, CompatibilityScore> {
+    HarnessKind::ALL
+        .iter()
+        .map(|&kind| {
+            let issues = validate_skill_for_harness(content, directory_name, kind);
+            (kind, CompatibilityScore::from_issues(issues))
+        })
+        .collect()
+}
+
+fn validate_tools_format(
+    tools: &serde_yaml::Value,
+    expected: ToolsFormat,
+    kind: HarnessKind,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    match expected {
+        ToolsFormat::BooleanRecord => {
+            if !tools.is_mapping() {
+                issues.push(ValidationIssue::error(
+                    "tools",
+                    format!(
+                        "{} requires tools as object (e.g., {{ bash: true }}), got {}",
+                        kind.as_str(),
+                        yaml_type_name(tools)
+                    ),
+                    Some(CODE_AGENT_TOOLS_FORMAT),
+                ));
+            }
+        }
+        ToolsFormat::CommaSeparatedString => {
+            if !tools.is_string() {
+                issues.push(ValidationIssue::error(
+                    "tools",
+                    format!(
+                        "{} requires tools as comma-separated string, got {}",
+                        kind.as_str(),
+                        yaml_type_name(tools)
+                    ),
+                    Some(CODE_AGENT_TOOLS_FORMAT),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+fn validate_color_format(
+    color: &str,
+    expected: ColorFormat,
+    kind: HarnessKind,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    match expected {
+        ColorFormat::HexOnly => {
+            if !is_hex_color(color) {
+                issues.push(ValidationIssue::error(
+                    "color",
+                    format!(
+                        "{} requires hex color (#RRGGBB), got '{}'",
+                        kind.as_str(),
+                        color
+                    ),
+                    Some(CODE_AGENT_COLOR_FORMAT),
+                ));
+            }
+        }
+        ColorFormat::NamedOrHex => {}
+    }
+
+    issues
+}
+
+fn yaml_type_name(value: &serde_yaml::Value) -> &'static str {
+    match value {
+        serde_yaml::Value::Null => "null",
+        serde_yaml::Value::Bool(_) => "boolean",
+        serde_yaml::Value::Number(_) => "number",
+        serde_yaml::Value::String(_) => "string",
+        serde_yaml::Value::Sequence(_) => "array",
+        serde_yaml::Value::Mapping(_) => "object",
+        serde_yaml::Value::Tagged(_) => "tagged",
+    }
+}
+
+fn is_hex_color(s: &str) -> bool {
+    s.len() == 7 && s.starts_with('#') && s[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn validate_stdio(server: &StdioMcpServer) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    if server.command.trim().is_empty() {
+        issues.push(ValidationIssue::error(
+            "command",
+            "Command must not be empty",
+            Some(CODE_EMPTY_COMMAND),
+        ));
+    }
+
+    issues.extend(validate_timeout(server.timeout_ms, "timeout_ms"));
+    issues.extend(validate_env(&server.env, "env"));
+    issues.extend(validate_docker(server));
+    issues.extend(validate_argv(server));
+    issues
+}
+
+/// Flags command lines that were written for a shell, since harnesses
+/// start stdio servers without one.
+fn validate_argv(server: &StdioMcpServer) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let shell = crate::argv::is_shell(&server.command);
+
+    let normalized = crate::argv::normalize_command(&server.command, &[]);
+    if normalized.unchanged_reason.is_none() && normalized.command != server.command {
+        issues.push(ValidationIssue::warning(
+            "command",
+            format!(
+                "'{}' is run as a single program name; use command '{}' with args {:?}",
+                server.command, normalized.command, normalized.args
+            ),
+            Some(CODE_COMMAND_CONTAINS_ARGS),
+        ));
+    }
+
+    let fields = std::iter::once(("command".to_string(), server.command.as_str())).chain(
+        server
+            .args
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| (format!("args[{i}]"), arg.as_str())),
+    );
+    for (field, arg) in fields {
+        if !shell && crate::argv::has_shell_syntax(arg) {
+            issues.push(ValidationIssue::warning(
+                field.clone(),
+                format!("'{arg}' is passed literally; wrap the command in `sh -c` to use a shell"),
+                Some(CODE_ARGS_SHELL_SYNTAX),
+            ));
+        }
+        if field == "command" {
+            continue;
+        }
+        let option = arg.split_once('=').map_or(arg, |(name, _)| name);
+        if arg.starts_with('-') && option.trim().contains(char::is_whitespace) {
+            issues.push(ValidationIssue::warning(
+                field.clone(),
+                format!("'{arg}' is passed as one argument; split it into separate args"),
+                Some(CODE_ARGS_EMBEDDED_WHITESPACE),
+            ));
+        }
+        let quoted = arg.len() >= 2
+            && ['"', '\'']
+                .iter()
+                .any(|&q| arg.starts_with(q) && arg.ends_with(q));
+        if quoted && !shell {
+            issues.push(ValidationIssue::warning(
+                field,
+                format!("the quotes in {arg} are passed to the program; remove them"),
+                Some(CODE_ARGS_LITERAL_QUOTES),
+            ));
+        }
+    }
+    issues
+}
+
+/// Returns how `kind` references environment variables inside stdio
+/// arguments, as an opening and closing delimiter, or `None` if it does
+/// not expand them.
+fn arg_env_syntax(kind: HarnessKind) -> Option<(&'static str, &'static str)> {
+    match kind {
+        HarnessKind::ClaudeCode | HarnessKind::AmpCode | HarnessKind::CopilotCli => {
+            Some(("${", "}"))
+        }
+        HarnessKind::OpenCode => Some(("{env:", "}")),
+        HarnessKind::Goose => None,
+    }
+}
+
+/// Warns about stdio arguments that would run differently after moving
+/// `server` from the harness `from` to `to`.
+///
+/// Environment variable references such as `${TOKEN}` in the command or
+/// arguments are expanded by some harnesses only, each with its own
+/// syntax, so a reference written for `from` may reach the program
+/// literally under `to`.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::mcp::{McpServer, StdioMcpServer};
+/// use harness_locate::types::HarnessKind;
+/// use harness_locate::validation::{CODE_ARGS_ENV_SYNTAX, validate_argv_conversion};
+///
+/// let server = McpServer::Stdio(StdioMcpServer {
+///     command: "mcp-db".to_string(),
+///     args: vec!["--url=${DATABASE_URL}".to_string()],
+///     env: Default::default(),
+///     cwd: None,
+///     enabled: true,
+///     timeout_ms: None,
+/// });
+///
+/// let issues = validate_argv_conversion(&server, HarnessKind::ClaudeCode, HarnessKind::OpenCode);
+/// assert_eq!(issues[0].code, Some(CODE_ARGS_ENV_SYNTAX));
+/// ```
+#[must_use]
+pub fn validate_argv_conversion(
+    server: &McpServer,
+    from: HarnessKind,
+    to: HarnessKind,
+) -> Vec<ValidationIssue> {
+    let McpServer::Stdio(stdio) = server else {
+        return Vec::new();
+    };
+    let Some((open, close)) = arg_env_syntax(from) else {
+        return Vec::new();
+    };
+    if arg_env_syntax(to) == Some((open, close)) {
+        return Vec::new();
+    }
+    let fields = std::iter::once(("command".to_string(), &stdio.command)).chain(
+        stdio
+            .args
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| (format!("args[{i}]"), arg)),
+    );
+    fields
+        .filter(|(_, arg)| {
+            arg.split_once(open)
+                .is_some_and(|(_, rest)| rest.contains(close))
+        })
+        .map(|(field, arg)| {
+            ValidationIssue::warning(
+                field,
+                format!(
+                    "'{arg}' references a variable as {} does, which {} passes literally",
+                    from.as_str(),
+                    to.as_str()
+                ),
+                Some(CODE_ARGS_ENV_SYNTAX),
+            )
+        })
+        .collect()
+}
+
+fn validate_goose_native(extension: &GooseExtension) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    if extension.name.trim().is_empty() {
+        issues.push(ValidationIssue::error(
+            "name",
+            "Extension name must not be empty",
+            Some(CODE_GOOSE_NAME_EMPTY),
+        ));
+    }
+    if extension.kind == GooseExtensionKind::InlinePython
+        && extension
+            .code
+            .as_deref()
+            .is_none_or(|c| c.trim().is_empty())
+    {
+        issues.push(ValidationIssue::error(
+            "code",
+            "Inline Python extension must have code",
+            Some(CODE_GOOSE_CODE_MISSING),
+        ));
+    }
+
+    issues.extend(validate_timeout(extension.timeout_ms, "timeout_ms"));
+    issues
+}
+
+fn validate_docker(server: &StdioMcpServer) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let Some(run) = server.docker_run() else {
+        return issues;
+    };
+
+    if run.image.is_none() {
+        issues.push(ValidationIssue::error(
+            "args",
+            "docker run is missing an image",
+            Some(CODE_DOCKER_MISSING_IMAGE),
+        ));
+    }
+
+    if !run.is_interactive() {
+        issues.push(ValidationIssue::warning(
+            "args",
+            "docker run without -i closes stdin; add -i so the server can receive requests",
+            Some(CODE_DOCKER_NOT_INTERACTIVE),
+        ));
+    }
+
+    if !run.has_env_file() {
+        let passed = run.passed_env();
+        let mut missing: Vec<&String> = server
+            .env
+            .keys()
+            .filter(|name| !passed.contains(&name.as_str()))
+            .collect();
+        missing.sort();
+        for name in missing {
+            issues.push(ValidationIssue::warning(
+                format!("env.{name}"),
+                format!("{name} is not passed into the container; add -e {name}"),
+                Some(CODE_DOCKER_ENV_NOT_PASSED),
+            ));
+        }
+    }
+
+    issues
+}
+
+/// Checks that the container runtime used by a Docker-based server is installed.
+///
+/// Returns no issues for servers that do not run `docker` or `podman`.
+/// Unlike [`validate_mcp_server`], this searches `PATH`.
+///
+/// # Example
+///
+/// ```no_run
+/// use harness_locate::mcp::{DockerMcpServer, McpServer};
+/// use harness_locate::validation::validate_docker_installed;
+///
+/// let server = McpServer::Stdio(DockerMcpServer::new("mcp/fetch").into_stdio());
+/// for issue in validate_docker_installed(&server) {
+///     eprintln!("{}", issue.message);
+/// }
+/// ```
+#[must_use]
+pub fn validate_docker_installed(server: &McpServer) -> Vec<ValidationIssue> {
+    let McpServer::Stdio(stdio) = server else {
+        return Vec::new();
+    };
+    if !stdio.is_docker() {
+        return Vec::new();
+    }
+    match crate::detection::find_binary(&stdio.command) {
+        Ok(Some(_)) => Vec::new(),
+        _ => vec![ValidationIssue::error(
+            "command",
+            format!("{} is not installed or not in PATH", stdio.command),
+            Some(CODE_DOCKER_NOT_INSTALLED),
+        )],
+    }
+}
+
+/// Warns if a skill or MCP server limited to `platforms` is installed onto
+/// `platform`.
+///
+/// Pass [`Platform::current`] to check the machine the crate runs on.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::target::{Arch, Os, Platform, Platforms};
+/// use harness_locate::validation::{CODE_PLATFORM_UNSUPPORTED, validate_platform_support};
+///
+/// let macos_only: Platforms = [Os::Macos].into_iter().collect();
+/// let linux = Platform::new(Os::Linux, Arch::X86_64);
+///
+/// let issues = validate_platform_support(&macos_only, &linux);
+/// assert_eq!(issues[0].code, Some(CODE_PLATFORM_UNSUPPORTED));
+/// ```
+#[must_use]
+pub fn validate_platform_support(
+    platforms: &Platforms,
+    platform: &Platform,
+) -> Vec<ValidationIssue> {
+    if platforms.allows(platform) {
+        return Vec::new();
+    }
+    vec![ValidationIssue::warning(
+        "platforms",
+        format!("supports {platforms}, not {platform}"),
+        Some(CODE_PLATFORM_UNSUPPORTED),
+    )]
+}
+
+fn validate_sse(server: &SseMcpServer) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    issues.extend(validate_url(&server.url, "url"));
+    issues.extend(validate_timeout(server.timeout_ms, "timeout_ms"));
+    issues.extend(validate_env(&server.headers, "headers"));
+    issues
+}
+
+fn validate_http(server: &HttpMcpServer) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    issues.extend(validate_url(&server.url, "url"));
+    issues.extend(validate_timeout(server.timeout_ms, "timeout_ms"));
+    issues.extend(validate_env(&server.headers, "headers"));
+    issues
+}
+fn validate_url(url: &str, field: &str) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    match Url::parse(url) {
+        Ok(parsed) => {
+            let scheme = parsed.scheme();
+            if scheme != "http" && scheme != "https" {
+                issues.push(ValidationIssue::error(
+                    field,
+                    format!("URL scheme must be http or https, got '{scheme}'"),
+                    Some(CODE_INVALID_SCHEME),
+                ));
+            }
+        }
+        Err(e) => {
+            issues.push(ValidationIssue::error(
+                field,
+                format!("Invalid URL: {e}"),
+                Some(CODE_INVALID_URL),
+            ));
+        }
+    }
+
+    issues
+}
+
+fn validate_timeout(timeout_ms: Option<u64>, field: &str) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    if let Some(ms) = timeout_ms
+        && ms > MAX_RECOMMENDED_TIMEOUT_MS
+    {
+        issues.push(ValidationIssue::warning(
+            field,
+            format!(
+                "Timeout of {}ms exceeds recommended maximum of {}ms (5 minutes)",
+                ms, MAX_RECOMMENDED_TIMEOUT_MS
+            ),
+            Some(CODE_TIMEOUT_EXCESSIVE),
+        ));
+    }
+
+    issues
+}
+
+fn validate_env(env: &HashMap<String, EnvValue>, field_prefix: &str) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    let mut keys: Vec<&String> = env.keys().filter(|key| is_sensitive_name(key)).collect();
+    keys.sort();
+    for key in keys {
+        issues.push(ValidationIssue::warning(
+            format!("{field_prefix}.{key}"),
+            format!(
+                "Variable name '{key}' suggests sensitive data; \
+                 consider using environment variable references"
+            ),
+            Some(CODE_SUSPICIOUS_ENV),
+        ));
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_stdio(command: &str) -> McpServer {
+        McpServer::Stdio(StdioMcpServer {
+            command: command.to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        })
+    }
+
+    fn make_sse(url: &str) -> McpServer {
+        McpServer::Sse(SseMcpServer {
+            url: url.to_string(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+        })
+    }
+
+    fn make_http(url: &str) -> McpServer {
+        McpServer::Http(HttpMcpServer {
+            url: url.to_string(),
+            headers: HashMap::new(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+        })
+    }
+
+    fn make_docker(args: &[&str], env: &[&str]) -> McpServer {
+        McpServer::Stdio(StdioMcpServer {
+            command: "docker".to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            env: env
+                .iter()
+                .map(|k| (k.to_string(), EnvValue::env(*k)))
+                .collect(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        })
+    }
+
+    #[test]
+    fn docker_builder_output_is_valid() {
+        let server = McpServer::Stdio(
+            crate::mcp::DockerMcpServer::new("mcp/github")
+                .with_env("LOG_LEVEL", EnvValue::plain("debug"))
+                .into_stdio(),
+        );
+        assert!(validate_mcp_server(&server).is_empty());
+    }
+
+    #[test]
+    fn docker_without_interactive_warns() {
+        let issues = validate_mcp_server(&make_docker(&["run", "--rm", "mcp/fetch"], &[]));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].code, Some(CODE_DOCKER_NOT_INTERACTIVE));
+    }
+
+    #[test]
+    fn docker_accepts_combined_interactive_flags() {
+        let issues = validate_mcp_server(&make_docker(&["run", "-it", "--rm", "mcp/fetch"], &[]));
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn docker_without_image_errors() {
+        let issues = validate_mcp_server(&make_docker(&["run", "-i", "-e", "TOKEN"], &["TOKEN"]));
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_DOCKER_MISSING_IMAGE))
+        );
+    }
+
+    #[test]
+    fn docker_env_not_passed_warns() {
+        let server = make_docker(
+            &["run", "-i", "-e", "A", "--env=B", "img"],
+            &["A", "B", "C"],
+        );
+        let issues = validate_mcp_server(&server);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "env.C");
+        assert_eq!(issues[0].code, Some(CODE_DOCKER_ENV_NOT_PASSED));
+    }
+
+    #[test]
+    fn docker_env_file_suppresses_env_warning() {
+        let server = make_docker(&["run", "-i", "--env-file", ".env", "img"], &["A"]);
+        assert!(validate_mcp_server(&server).is_empty());
+    }
+
+    #[test]
+    fn flags_command_lines_written_for_a_shell() {
+        let codes = |command: &str, args: &[&str]| {
+            let server = McpServer::Stdio(StdioMcpServer {
+                args: args.iter().map(|a| a.to_string()).collect(),
+                ..match make_stdio(command) {
+                    McpServer::Stdio(s) => s,
+                    _ => unreachable!(),
+                }
+            });
+            validate_mcp_server(&server)
+                .into_iter()
+                .map(|issue| (issue.field, issue.code.unwrap()))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(codes("npx", &["-y", "@scope/pkg", "--root=/my files"]).is_empty());
+        assert_eq!(
+            codes("npx -y @scope/pkg", &[]),
+            [("command".to_string(), CODE_COMMAND_CONTAINS_ARGS)]
+        );
+        assert!(codes(r"C:\Program Files\nodejs\node.exe", &["server.js"]).is_empty());
+        assert_eq!(
+            codes(
+                "node",
+                &["server.js", "| tee log", "--port 8080", "'/tmp/x'"]
+            ),
+            [
+                ("args[1]".to_string(), CODE_ARGS_SHELL_SYNTAX),
+                ("args[2]".to_string(), CODE_ARGS_EMBEDDED_WHITESPACE),
+                ("args[3]".to_string(), CODE_ARGS_LITERAL_QUOTES),
+            ]
+        );
+        assert!(codes("bash", &["-c", "server | tee log"]).is_empty());
+    }
+
+    #[test]
+    fn warns_when_args_reference_variables_the_target_does_not_expand() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            args: vec!["--token=${TOKEN}".to_string(), "plain".to_string()],
+            ..match make_stdio("mcp") {
+                McpServer::Stdio(s) => s,
+                _ => unreachable!(),
+            }
+        });
+        let issues = validate_argv_conversion(&server, HarnessKind::ClaudeCode, HarnessKind::Goose);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].field, "args[0]");
+        assert_eq!(issues[0].code, Some(CODE_ARGS_ENV_SYNTAX));
+        assert!(
+            validate_argv_conversion(&server, HarnessKind::ClaudeCode, HarnessKind::AmpCode)
+                .is_empty()
+        );
+        assert!(
+            validate_argv_conversion(&server, HarnessKind::OpenCode, HarnessKind::ClaudeCode)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn validate_docker_installed_ignores_non_docker() {
+        assert!(validate_docker_installed(&make_stdio("node")).is_empty());
+    }
+
+    #[test]
+    fn goose_native_only_valid_for_goose() {
+        let server = McpServer::GooseNative(GooseExtension::builtin("developer"));
+        assert!(validate_for_harness(&server, HarnessKind::Goose).is_empty());
+
+        let issues = validate_for_harness(&server, HarnessKind::ClaudeCode);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_GOOSE_NATIVE_UNSUPPORTED));
+    }
+
+    #[test]
+    fn goose_inline_python_without_code_errors() {
+        let mut extension = GooseExtension::builtin("word_count");
+        extension.kind = GooseExtensionKind::InlinePython;
+        let issues = validate_mcp_server(&McpServer::GooseNative(extension));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_GOOSE_CODE_MISSING));
+    }
+
+    #[test]
+    fn empty_command_returns_error() {
+        let server = make_stdio("");
+        let issues = validate_mcp_server(&server);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].field, "command");
+        assert_eq!(issues[0].code, Some(CODE_EMPTY_COMMAND));
+    }
+
+    #[test]
+    fn valid_command_returns_no_issues() {
+        let server = make_stdio("node");
+        let issues = validate_mcp_server(&server);
+
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn invalid_url_returns_error() {
+        let server = make_sse("not-a-valid-url");
+        let issues = validate_mcp_server(&server);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].field, "url");
+        assert_eq!(issues[0].code, Some(CODE_INVALID_URL));
+    }
+
+    #[test]
+    fn valid_https_url_returns_no_issues() {
+        let server = make_http("https://example.com/mcp");
+        let issues = validate_mcp_server(&server);
+
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn ftp_scheme_returns_error() {
+        let server = make_sse("ftp://files.example.com");
+        let issues = validate_mcp_server(&server);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].field, "url");
+        assert_eq!(issues[0].code, Some(CODE_INVALID_SCHEME));
+        assert!(issues[0].message.contains("ftp"));
+    }
+
+    #[test]
+    fn excessive_timeout_returns_warning() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: Some(600_000),
+        });
+        let issues = validate_mcp_server(&server);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].field, "timeout_ms");
+        assert_eq!(issues[0].code, Some(CODE_TIMEOUT_EXCESSIVE));
+    }
+
+    #[test]
+    fn normal_timeout_returns_no_issues() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: Some(30_000),
+        });
+        let issues = validate_mcp_server(&server);
+
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn suspicious_env_name_returns_warning() {
+        let mut env = HashMap::new();
+        env.insert("DB_PASSWORD".to_string(), EnvValue::plain("secret123"));
+
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        let issues = validate_mcp_server(&server);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].field, "env.DB_PASSWORD");
+        assert_eq!(issues[0].code, Some(CODE_SUSPICIOUS_ENV));
+    }
+
+    #[test]
+    fn suspicious_env_issues_are_sorted_by_name() {
+        let env: HashMap<_, _> = ["Z_TOKEN", "A_SECRET", "M_PASSWORD"]
+            .into_iter()
+            .map(|name| (name.to_string(), EnvValue::plain("x")))
+            .collect();
+
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        let fields: Vec<_> = validate_mcp_server(&server)
+            .into_iter()
+            .map(|issue| issue.field)
+            .collect();
+
+        assert_eq!(fields, ["env.A_SECRET", "env.M_PASSWORD", "env.Z_TOKEN"]);
+    }
+
+    #[test]
+    fn normal_env_name_returns_no_issues() {
+        let mut env = HashMap::new();
+        env.insert("NODE_ENV".to_string(), EnvValue::plain("production"));
+        env.insert("PORT".to_string(), EnvValue::plain("3000"));
+
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        let issues = validate_mcp_server(&server);
+
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn multiple_issues_collected() {
+        let mut env = HashMap::new();
+        env.insert("API_TOKEN".to_string(), EnvValue::plain("tok_123"));
+
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "".to_string(),
+            args: vec![],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: Some(600_000),
+        });
+        let issues = validate_mcp_server(&server);
+
+        assert_eq!(issues.len(), 3);
+        let error_count = issues
+            .iter()
+            .filter(|i| i.severity == Severity::Error)
+            .count();
+        let warning_count = issues
+            .iter()
+            .filter(|i| i.severity == Severity::Warning)
+            .count();
+        assert_eq!(error_count, 1);
+        assert_eq!(warning_count, 2);
+        assert!(issues.iter().any(|i| i.code == Some(CODE_EMPTY_COMMAND)));
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_TIMEOUT_EXCESSIVE))
+        );
+        assert!(issues.iter().any(|i| i.code == Some(CODE_SUSPICIOUS_ENV)));
+    }
+
+    #[test]
+    fn valid_config_returns_empty_vec() {
+        let mut env = HashMap::new();
+        env.insert("NODE_ENV".to_string(), EnvValue::plain("production"));
+
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec!["server.js".to_string()],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: Some(30_000),
+        });
+        let issues = validate_mcp_server(&server);
+
+        assert!(issues.is_empty());
+    }
+
+    // Harness-specific validation tests
+
+    #[test]
+    fn cwd_on_any_harness_returns_error() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: Some(std::path::PathBuf::from("/tmp")),
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        for kind in HarnessKind::ALL {
+            let issues = validate_for_harness(&server, *kind);
+            assert!(issues.iter().any(|i| i.code == Some(CODE_CWD_UNSUPPORTED)));
+        }
+    }
+
+    #[test]
+    fn disabled_on_claude_code_returns_warning() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: false,
+            timeout_ms: None,
+        });
+
+        let issues = validate_for_harness(&server, HarnessKind::ClaudeCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_TOGGLE_UNSUPPORTED))
+        );
+    }
+
+    #[test]
+    fn disabled_on_opencode_returns_no_warning() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "node".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: false,
+            timeout_ms: None,
+        });
+
+        let issues = validate_for_harness(&server, HarnessKind::OpenCode);
+        assert!(
+            !issues
+                .iter()
+                .any(|i| i.code == Some(CODE_TOGGLE_UNSUPPORTED))
+        );
+    }
+
+    #[test]
+    fn sse_on_claude_code_returns_warning() {
+        let server = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        let issues = validate_for_harness(&server, HarnessKind::ClaudeCode);
+        assert!(issues.iter().any(|i| i.code == Some(CODE_SSE_DEPRECATED)));
+    }
+
+    #[test]
+    fn sse_on_opencode_returns_no_warning() {
+        let server = McpServer::Sse(SseMcpServer {
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        let issues = validate_for_harness(&server, HarnessKind::OpenCode);
+        assert!(!issues.iter().any(|i| i.code == Some(CODE_SSE_DEPRECATED)));
+    }
+
+    #[test]
+    fn validate_for_harness_includes_base_validation() {
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "".to_string(),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: Some(std::path::PathBuf::from("/tmp")),
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        let issues = validate_for_harness(&server, HarnessKind::ClaudeCode);
+        assert!(issues.iter().any(|i| i.code == Some(CODE_EMPTY_COMMAND)));
+        assert!(issues.iter().any(|i| i.code == Some(CODE_CWD_UNSUPPORTED)));
+    }
+
+    // Agent validation tests
+
+    #[test]
+    fn opencode_rejects_comma_string_tools() {
+        let content = "---\ntools: Glob, Grep, Read\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_TOOLS_FORMAT))
+        );
+    }
+
+    #[test]
+    fn opencode_accepts_boolean_record_tools() {
+        let content = "---\ntools:\n  bash: true\n  edit: false\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::OpenCode);
+        assert!(
+            !issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_TOOLS_FORMAT))
+        );
+    }
+
+    #[test]
+    fn opencode_rejects_named_color() {
+        let content = "---\ncolor: red\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_COLOR_FORMAT))
+        );
+    }
+
+    #[test]
+    fn opencode_accepts_hex_color() {
+        let content = "---\ncolor: \"#FF5733\"\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::OpenCode);
+        assert!(
+            !issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_COLOR_FORMAT))
+        );
+    }
+
+    #[test]
+    fn claude_code_accepts_comma_string_tools() {
+        let content = "---\ntools: Glob, Grep, Read\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::ClaudeCode);
+        assert!(
+            !issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_TOOLS_FORMAT))
+        );
+    }
+
+    #[test]
+    fn claude_code_accepts_named_color() {
+        let content = "---\ncolor: red\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::ClaudeCode);
+        assert!(
+            !issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_COLOR_FORMAT))
+        );
+    }
+
+    #[test]
+    fn goose_returns_unsupported_error() {
+        let content = "---\nname: test\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::Goose);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_UNSUPPORTED))
+        );
+    }
+
+    #[test]
+    fn invalid_yaml_returns_parse_error() {
+        let content = "---\ntools: [unclosed bracket\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_PARSE_ERROR))
+        );
+    }
+
+    #[test]
+    fn missing_frontmatter_is_valid() {
+        let content = "Just the agent prompt, no frontmatter";
+        let issues = validate_agent_for_harness(content, HarnessKind::OpenCode);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn invalid_mode_returns_error() {
+        let content = "---\nmode: invalid_mode\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_MODE_UNSUPPORTED))
+        );
+    }
+
+    #[test]
+    fn valid_mode_accepted() {
+        let content = "---\nmode: subagent\n---\nAgent prompt";
+        let issues = validate_agent_for_harness(content, HarnessKind::OpenCode);
+        assert!(
+            !issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_MODE_UNSUPPORTED))
+        );
+    }
+
+    #[test]
+    fn is_hex_color_validates_correctly() {
+        assert!(is_hex_color("#FF5733"));
+        assert!(is_hex_color("#000000"));
+        assert!(is_hex_color("#ffffff"));
+        assert!(!is_hex_color("red"));
+        assert!(!is_hex_color("#FFF"));
+        assert!(!is_hex_color("FF5733"));
+        assert!(!is_hex_color("#GGGGGG"));
+    }
+
+    #[test]
+    fn opencode_rejects_uppercase_skill_name() {
+        let content = "---\nname: Hook Development\ndescription: test\n---\nSkill content";
+        let issues = validate_skill_for_harness(content, "hook-development", HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_SKILL_NAME_FORMAT))
+        );
+    }
+
+    #[test]
+    fn opencode_rejects_name_directory_mismatch() {
+        let content = "---\nname: other-name\ndescription: test\n---\nSkill content";
+        let issues = validate_skill_for_harness(content, "actual-directory", HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_SKILL_NAME_DIRECTORY_MISMATCH))
+        );
+    }
+
+    #[test]
+    fn opencode_accepts_valid_skill() {
+        let content = "---\nname: my-skill\ndescription: A valid skill\n---\nSkill content";
+        let issues = validate_skill_for_harness(content, "my-skill", HarnessKind::OpenCode);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn claude_code_accepts_any_skill_name() {
+        let content = "---\nname: Hook Development\ndescription: test\n---\nSkill content";
+        let issues =
+            validate_skill_for_harness(content, "Hook Development", HarnessKind::ClaudeCode);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn opencode_warns_missing_description() {
+        let content = "---\nname: my-skill\n---\nSkill content";
+        let issues = validate_skill_for_harness(content, "my-skill", HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_SKILL_DESCRIPTION_MISSING))
+        );
+        assert!(issues.iter().all(|i| i.severity == Severity::Warning));
+    }
+
+    #[test]
+    fn opencode_rejects_long_skill_name() {
+        let long_name = "a".repeat(65);
+        let content = format!(
+            "---\nname: {}\ndescription: test\n---\nSkill content",
+            long_name
+        );
+        let issues = validate_skill_for_harness(&content, &long_name, HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_SKILL_NAME_LENGTH))
+        );
+    }
+
+    #[test]
+    fn opencode_rejects_long_description() {
+        let long_desc = "a".repeat(1025);
+        let content = format!(
+            "---\nname: my-skill\ndescription: {}\n---\nSkill content",
+            long_desc
+        );
+        let issues = validate_skill_for_harness(&content, "my-skill", HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_SKILL_DESCRIPTION_LENGTH))
+        );
+    }
+
+    #[test]
+    fn localized_descriptions_are_validated_per_locale() {
+        let content = format!(
+            "---\nname: my-skill\ndescription:\n  en: Short\n  fr: {}\n---\nSkill content",
+            "a".repeat(1025)
+        );
+        let issues = validate_skill_for_harness(&content, "my-skill", HarnessKind::OpenCode);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_SKILL_DESCRIPTION_LENGTH));
+        assert!(issues[0].message.starts_with("description (fr)"));
+
+        let content = "---\nname: my-skill\ndescription:\n  en: Short\n---\nSkill content";
+        assert!(validate_skill_for_harness(content, "my-skill", HarnessKind::OpenCode).is_empty());
+    }
+
+    #[test]
+    fn skill_name_regex_validates_correctly() {
+        assert!(SKILL_NAME_RE.is_match("my-skill"));
+        assert!(SKILL_NAME_RE.is_match("a"));
+        assert!(SKILL_NAME_RE.is_match("skill123"));
+        assert!(SKILL_NAME_RE.is_match("my-long-skill-name"));
+        assert!(!SKILL_NAME_RE.is_match("My-Skill"));
+        assert!(!SKILL_NAME_RE.is_match("my--skill"));
+        assert!(!SKILL_NAME_RE.is_match("-my-skill"));
+        assert!(!SKILL_NAME_RE.is_match("my-skill-"));
+        assert!(!SKILL_NAME_RE.is_match("my skill"));
+    }
+
+    #[test]
+    fn skill_capabilities_for_opencode() {
+        let caps = SkillCapabilities::for_kind(HarnessKind::OpenCode).unwrap();
+        assert_eq!(caps.name_format, NameFormat::LowercaseHyphenated);
+        assert!(caps.name_must_match_directory);
+        assert!(caps.description_required);
+    }
+
+    #[test]
+    fn skill_capabilities_for_claude_code() {
+        let caps = SkillCapabilities::for_kind(HarnessKind::ClaudeCode).unwrap();
+        assert_eq!(caps.name_format, NameFormat::Any);
+        assert!(!caps.name_must_match_directory);
+        assert!(!caps.description_required);
+    }
+
+    #[test]
+    fn goose_returns_skill_unsupported() {
+        let content = "---\nname: test\n---\nSkill content";
+        let issues = validate_skill_for_harness(content, "test", HarnessKind::Goose);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_SKILL_UNSUPPORTED))
+        );
+    }
+
+    #[test]
+    fn skill_invalid_yaml_returns_parse_error() {
+        let content = "---\nname: [unclosed\n---\nSkill content";
+        let issues = validate_skill_for_harness(content, "test", HarnessKind::OpenCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_SKILL_PARSE_ERROR))
+        );
+    }
+
+    #[test]
+    fn skill_reports_unknown_platforms() {
+        let content =
+            "---\nname: my-skill\ndescription: A skill\nplatforms: [darwin, plan9-x64]\n---\n";
+        let issues = validate_skill_for_harness(content, "my-skill", HarnessKind::ClaudeCode);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_PLATFORM_INVALID));
+        assert!(issues[0].message.contains("'plan9-x64'"));
+    }
+
+    #[test]
+    fn borrowed_skill_validation_matches_owned() {
+        let long = "x".repeat(SKILL_DESCRIPTION_MAX_LEN + 1);
+        let cases = [
+            "---\nname: my-skill\ndescription: Does things\n---\nBody".to_string(),
+            "---\nname: Bad Name\n---\n".to_string(),
+            format!("---\nname: my-skill\ndescription: \"{long}\"\n---\n"),
+            "---\nname: [unclosed\n---\n".to_string(),
+            "---\nname: my-skill\nextra: [unclosed\n---\n".to_string(),
+            "---\nname: my-skill\nname: other\n---\n".to_string(),
+            "---\nname: my-skill\n...\n---\nname: other\n".to_string(),
+            "---\nname: other\nplatforms: plan9\n---\n".to_string(),
+            "---\nname: a\ndescription:\n  en: Hi\n  fr: Salut\n---\n".to_string(),
+            "+++\nname = \"Toml Skill\"\n+++\n".to_string(),
+            "No frontmatter".to_string(),
+        ];
+        for content in &cases {
+            for kind in HarnessKind::ALL {
+                let owned = validate_skill_for_harness(content, "my-skill", *kind);
+                let borrowed: Vec<ValidationIssue> =
+                    validate_skill_borrowed(content, "my-skill", *kind)
+                        .into_iter()
+                        .map(ValidationIssue::from)
+                        .collect();
+                assert_eq!(borrowed, owned, "{kind}: {content}");
+            }
+        }
+        let issue = ValidationIssue::warning("name", "msg", None);
+        assert_eq!(issue.as_borrowed().into_owned(), issue);
+    }
+
+    #[test]
+    fn warns_on_unsupported_platform() {
+        let platforms: Platforms = [PlatformSpec::from(crate::target::Arch::X86_64)]
+            .into_iter()
+            .collect();
+        let arm = Platform::new(crate::target::Os::Macos, crate::target::Arch::Aarch64);
+        let issues = validate_platform_support(&platforms, &arm);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].message, "supports x86_64, not macos-aarch64");
+        assert!(validate_platform_support(&Platforms::default(), &arm).is_empty());
+    }
+
+    #[test]
+    fn score_compatibility_for_valid_skill() {
+        let content = "---\nname: my-skill\ndescription: A skill\n---\n# Body";
+        let scores = score_skill_compatibility(content, "my-skill");
+
+        assert_eq!(scores.len(), HarnessKind::ALL.len());
+        let opencode = &scores[&HarnessKind::OpenCode];
+        assert_eq!(opencode.score, 100);
+        assert_eq!(opencode.level, CompatibilityLevel::Compatible);
+        assert!(opencode.blocking.is_empty());
+        assert_eq!(scores[&HarnessKind::ClaudeCode].score, 100);
+    }
+
+    #[test]
+    fn score_compatibility_penalizes_name_format() {
+        let content = "---\nname: My Skill\ndescription: A skill\n---\n";
+        let scores = score_skill_compatibility(content, "my-skill");
+
+        let opencode = &scores[&HarnessKind::OpenCode];
+        assert_eq!(opencode.level, CompatibilityLevel::Partial);
+        assert!(opencode.score < 100 && opencode.score > 0);
+        assert!(
+            opencode
+                .blocking
+                .iter()
+                .any(|i| i.code == Some(CODE_SKILL_NAME_FORMAT))
+        );
+        assert_eq!(
+            scores[&HarnessKind::ClaudeCode].level,
+            CompatibilityLevel::Compatible
+        );
+    }
+
+    #[test]
+    fn score_compatibility_zero_when_unsupported_or_unparseable() {
+        let scores = score_skill_compatibility("---\nname: x\n---\n", "x");
+        let goose = &scores[&HarnessKind::Goose];
+        assert_eq!(goose.score, 0);
+        assert_eq!(goose.level, CompatibilityLevel::Incompatible);
+        assert_eq!(goose.blocking[0].code, Some(CODE_SKILL_UNSUPPORTED));
+
+        let scores = score_skill_compatibility("---\nname: [unclosed\n---\n", "x");
+        assert_eq!(scores[&HarnessKind::OpenCode].score, 0);
+    }
+
+    #[test]
+    fn command_with_matching_hint_is_valid() {
+        let content = "---\nargument-hint: \"[pr] [priority]\"\n---\nReview $1 at $2.";
+        assert!(validate_command_for_harness(content, HarnessKind::ClaudeCode).is_empty());
+    }
+
+    #[test]
+    fn command_hint_mismatch_and_missing() {
+        let content = "---\nargument-hint: \"[pr]\"\n---\nReview $1 at $2.";
+        let issues = validate_command_for_harness(content, HarnessKind::ClaudeCode);
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.code == Some(CODE_COMMAND_HINT_MISMATCH))
+        );
+
+        let issues = validate_command_for_harness("Review $1.", HarnessKind::ClaudeCode);
+        assert_eq!(issues[0].code, Some(CODE_COMMAND_HINT_MISSING));
+        assert_eq!(issues[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn command_positional_gap() {
+        let content = "---\nargument-hint: \"[a] [b] [c]\"\n---\n$1 and $3";
+        let issues = validate_command_for_harness(content, HarnessKind::ClaudeCode);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_COMMAND_POSITIONAL_GAP));
+    }
+
+    #[test]
+    fn opencode_ignores_argument_hint() {
+        let content = "---\nargument-hint: \"[file]\"\n---\nOpen $1.";
+        let issues = validate_command_for_harness(content, HarnessKind::OpenCode);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_COMMAND_HINT_UNSUPPORTED));
+
+        let issues = validate_command_for_harness("Open $ARGUMENTS.", HarnessKind::OpenCode);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn amp_warns_on_placeholders() {
+        let issues = validate_command_for_harness("Fix $ARGUMENTS", HarnessKind::AmpCode);
+        assert_eq!(issues[0].code, Some(CODE_COMMAND_PLACEHOLDER_UNSUPPORTED));
+    }
+
+    #[test]
+    fn goose_returns_command_unsupported() {
+        let issues = validate_command_for_harness("Fix $ARGUMENTS", HarnessKind::Goose);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_COMMAND_UNSUPPORTED));
+    }
+}
@@ -0,0 +1,627 @@
+//! MCP server configuration validation.
+//!
+//! This module provides validation for [`McpServer`] configurations,
+//! checking for structural issues like empty commands, invalid URLs,
+//! excessive timeouts, and suspicious environment variable names.
+//!
+//! Unlike the fail-fast error handling elsewhere in this crate,
+//! validation collects all issues found, allowing callers to see
+//! the complete picture rather than stopping at the first problem.
+//!
+//! House rules plug in as [`ValidationRule`]s registered with a
+//! [`Validator`], whose [`validate_all`](Validator::validate_all) reports
+//! them next to the built-in checks for every resource in a scope.
+//! [`diff_validation`] and [`ValidationBaseline`] narrow the results to
+//! issues introduced since a saved baseline, and
+//! [`ValidationReport::exit_code`] turns them into a process exit status.
+//!
+//! The issue and capability types are always available; the validation
+//! functions require the `validation` feature.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "validation")] {
+//! use harness_locate::mcp::{McpServer, StdioMcpServer};
+//! use harness_locate::validation::{validate_mcp_server, Severity};
+//!
+//! let server = McpServer::Stdio(StdioMcpServer {
+//!     command: String::new(), // Empty command - will be flagged
+//!     args: vec![],
+//!     env: std::collections::HashMap::new(),
+//!     cwd: None,
+//!     enabled: true,
+//!     timeout_ms: None,
+//! });
+//!
+//! let issues = validate_mcp_server(&server);
+//! assert!(!issues.is_empty());
+//! assert!(issues.iter().any(|i| i.severity == Severity::Error));
+//! # }
+//! ```
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::HarnessKind;
+
+#[cfg(feature = "validation")]
+mod baseline;
+#[cfg(feature = "validation")]
+mod checks;
+#[cfg(feature = "validation")]
+mod report;
+#[cfg(feature = "validation")]
+mod rules;
+#[cfg(feature = "validation")]
+mod schema;
+
+#[cfg(feature = "validation")]
+pub use baseline::{
+    BASELINE_VERSION, Identified, IssueId, ValidationBaseline, ValidationDiff, diff_validation,
+};
+#[cfg(feature = "validation")]
+pub use checks::{
+    score_skill_compatibility, validate_agent_for_harness, validate_argv_conversion,
+    validate_command_for_harness, validate_docker_installed, validate_for_harness,
+    validate_mcp_server, validate_platform_support, validate_skill_borrowed,
+    validate_skill_for_harness,
+};
+#[cfg(feature = "validation")]
+pub(crate) use checks::{validate_command_with, validate_skill_with};
+#[cfg(feature = "validation")]
+pub use report::{EXIT_ERRORS, EXIT_OK, EXIT_WARNINGS, ExitPolicy, ValidationReport};
+#[cfg(feature = "validation")]
+pub use rules::{Finding, TargetKind, ValidationRule, ValidationTarget, Validator};
+#[cfg(feature = "validation")]
+pub use schema::{validate_config_against_schema, validate_json_schema};
+
+// Issue code constants for machine-readable classification.
+
+/// Empty command in stdio transport.
+pub const CODE_EMPTY_COMMAND: &str = "stdio.command.empty";
+
+/// URL failed to parse.
+pub const CODE_INVALID_URL: &str = "url.invalid";
+
+/// URL has non-http(s) scheme.
+pub const CODE_INVALID_SCHEME: &str = "url.scheme.invalid";
+
+/// Timeout exceeds recommended maximum.
+pub const CODE_TIMEOUT_EXCESSIVE: &str = "timeout.excessive";
+
+/// Environment variable name suggests sensitive data.
+pub const CODE_SUSPICIOUS_ENV: &str = "env.suspicious_name";
+
+/// Working directory (cwd) not supported by harness.
+pub const CODE_CWD_UNSUPPORTED: &str = "harness.cwd.unsupported";
+
+/// Toggle (enabled field) not supported by harness.
+pub const CODE_TOGGLE_UNSUPPORTED: &str = "harness.toggle.unsupported";
+
+/// SSE transport deprecated for this harness (prefer HTTP).
+pub const CODE_SSE_DEPRECATED: &str = "harness.transport.sse_deprecated";
+
+/// `docker run` without `-i`; the server cannot read requests from stdin.
+pub const CODE_DOCKER_NOT_INTERACTIVE: &str = "stdio.docker.not_interactive";
+
+/// `docker run` without an image reference.
+pub const CODE_DOCKER_MISSING_IMAGE: &str = "stdio.docker.missing_image";
+
+/// Environment variable is set for `docker` but not passed into the container.
+pub const CODE_DOCKER_ENV_NOT_PASSED: &str = "stdio.docker.env_not_passed";
+
+/// The container runtime binary was not found in PATH.
+pub const CODE_DOCKER_NOT_INSTALLED: &str = "stdio.docker.not_installed";
+
+/// Command contains arguments that are run as part of the program name.
+pub const CODE_COMMAND_CONTAINS_ARGS: &str = "stdio.command.contains_args";
+
+/// Argument uses shell syntax but no shell runs the command.
+pub const CODE_ARGS_SHELL_SYNTAX: &str = "stdio.args.shell_syntax";
+
+/// Option argument contains whitespace and is passed as one argument.
+pub const CODE_ARGS_EMBEDDED_WHITESPACE: &str = "stdio.args.embedded_whitespace";
+
+/// Argument is wrapped in quotes that are passed to the program.
+pub const CODE_ARGS_LITERAL_QUOTES: &str = "stdio.args.literal_quotes";
+
+/// Argument references an environment variable in syntax the target harness does not expand.
+pub const CODE_ARGS_ENV_SYNTAX: &str = "stdio.args.env_syntax";
+
+/// Goose extension has an empty name.
+pub const CODE_GOOSE_NAME_EMPTY: &str = "goose.name.empty";
+
+/// Goose inline Python extension has no code.
+pub const CODE_GOOSE_CODE_MISSING: &str = "goose.code.missing";
+
+/// Goose builtin, platform, or inline Python extension used with another harness.
+pub const CODE_GOOSE_NATIVE_UNSUPPORTED: &str = "harness.goose_native.unsupported";
+
+/// OAuth is configured but the harness has no stored credentials for the server.
+pub const CODE_OAUTH_NO_CREDENTIALS: &str = "http.oauth.no_stored_credentials";
+
+// Agent validation codes.
+
+/// Agent tools field has wrong type for harness.
+pub const CODE_AGENT_TOOLS_FORMAT: &str = "agent.tools.format";
+
+/// Agent color field has invalid format for harness.
+pub const CODE_AGENT_COLOR_FORMAT: &str = "agent.color.format";
+
+/// Agent mode value not supported by harness.
+pub const CODE_AGENT_MODE_UNSUPPORTED: &str = "agent.mode.unsupported";
+
+/// Harness does not support agents.
+pub const CODE_AGENT_UNSUPPORTED: &str = "agent.unsupported";
+
+/// Agent frontmatter failed to parse.
+pub const CODE_AGENT_PARSE_ERROR: &str = "agent.parse_error";
+
+// Skill validation codes.
+
+/// Skill name has invalid format for harness.
+pub const CODE_SKILL_NAME_FORMAT: &str = "skill.name.invalid_format";
+
+/// Skill name exceeds maximum length.
+pub const CODE_SKILL_NAME_LENGTH: &str = "skill.name.length";
+
+/// Skill description exceeds maximum length.
+pub const CODE_SKILL_DESCRIPTION_LENGTH: &str = "skill.description.length";
+
+/// Skill name does not match directory name.
+pub const CODE_SKILL_NAME_DIRECTORY_MISMATCH: &str = "skill.name.directory_mismatch";
+
+/// Harness does not support skills.
+pub const CODE_SKILL_UNSUPPORTED: &str = "skill.unsupported";
+
+/// Skill frontmatter failed to parse.
+pub const CODE_SKILL_PARSE_ERROR: &str = "skill.parse_error";
+
+/// Skill is missing required description field.
+pub const CODE_SKILL_DESCRIPTION_MISSING: &str = "skill.description.missing";
+
+// Command validation codes.
+
+/// Harness does not support custom commands.
+pub const CODE_COMMAND_UNSUPPORTED: &str = "command.unsupported";
+
+/// Command frontmatter failed to parse.
+pub const CODE_COMMAND_PARSE_ERROR: &str = "command.parse_error";
+
+/// Placeholder is not substituted by the harness.
+pub const CODE_COMMAND_PLACEHOLDER_UNSUPPORTED: &str = "command.placeholder.unsupported";
+
+/// Positional placeholders skip a number, e.g. `$1` and `$3` without `$2`.
+pub const CODE_COMMAND_POSITIONAL_GAP: &str = "command.placeholder.positional_gap";
+
+/// `argument-hint` is not read by the harness.
+pub const CODE_COMMAND_HINT_UNSUPPORTED: &str = "command.argument_hint.unsupported";
+
+/// Command reads positional arguments but has no `argument-hint`.
+pub const CODE_COMMAND_HINT_MISSING: &str = "command.argument_hint.missing";
+
+/// `argument-hint` names a different number of arguments than the body reads.
+pub const CODE_COMMAND_HINT_MISMATCH: &str = "command.argument_hint.mismatch";
+
+// Environment issue codes.
+
+/// Global configuration is discarded when the environment is torn down.
+pub const CODE_ENV_GLOBAL_EPHEMERAL: &str = "environment.global_config.ephemeral";
+
+/// Harness is not installed on a CI runner.
+pub const CODE_ENV_NOT_INSTALLED_CI: &str = "environment.ci.not_installed";
+
+// Config schema codes.
+
+/// Config file value violates the harness's JSON Schema.
+pub const CODE_SCHEMA_VIOLATION: &str = "config.schema.violation";
+
+/// JSON Schema `$ref` points outside the schema or cannot be resolved.
+pub const CODE_SCHEMA_REF_UNRESOLVED: &str = "config.schema.ref_unresolved";
+
+// Platform codes.
+
+/// Resource does not run on the platform it is installed onto.
+pub const CODE_PLATFORM_UNSUPPORTED: &str = "platform.unsupported";
+
+/// `platforms` entry names no known operating system or architecture.
+pub const CODE_PLATFORM_INVALID: &str = "platform.invalid";
+
+// Plugin manifest codes.
+
+/// Plugin name is not kebab-case.
+pub const CODE_PLUGIN_NAME_FORMAT: &str = "plugin.name.invalid_format";
+
+/// Plugin version is not a semantic version.
+pub const CODE_PLUGIN_VERSION_FORMAT: &str = "plugin.version.invalid_format";
+
+/// Component path is not relative to the plugin root.
+pub const CODE_PLUGIN_PATH_INVALID: &str = "plugin.path.invalid";
+
+/// Component path does not exist in the plugin.
+pub const CODE_PLUGIN_PATH_MISSING: &str = "plugin.path.missing";
+
+/// Skill name validation regex: lowercase alphanumeric with single hyphens.
+pub const SKILL_NAME_REGEX: &str = r"^[a-z0-9]+(-[a-z0-9]+)*$";
+
+/// Maximum length for skill name.
+pub const SKILL_NAME_MAX_LEN: usize = 64;
+
+/// Maximum length for skill description.
+pub const SKILL_DESCRIPTION_MAX_LEN: usize = 1024;
+
+/// Severity level for validation issues.
+///
+/// Determines how the issue should be treated by callers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    /// Critical issue that will likely cause the server to fail.
+    ///
+    /// Examples: empty command, unparseable URL.
+    Error,
+
+    /// Non-critical issue that may cause problems or is worth reviewing.
+    ///
+    /// Examples: very long timeout, suspicious environment variable name.
+    Warning,
+}
+
+/// Expected format for agent `tools` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToolsFormat {
+    /// `Record<string, boolean>` - OpenCode style: `{ bash: true, edit: false }`
+    BooleanRecord,
+    /// Comma-separated string - Claude Code style: `"Glob, Grep, Read"`
+    CommaSeparatedString,
+}
+
+/// Expected format for agent `color` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorFormat {
+    /// Only hex colors: `#RRGGBB`
+    HexOnly,
+    /// Named colors (red, blue) or hex - accepts any string.
+    NamedOrHex,
+}
+
+/// Describes agent validation requirements for a harness.
+#[derive(Debug, Clone)]
+pub struct AgentCapabilities {
+    /// Expected format for `tools` field.
+    pub tools_format: ToolsFormat,
+    /// Expected format for `color` field.
+    pub color_format: ColorFormat,
+    /// Supported mode values.
+    pub supported_modes: &'static [&'static str],
+}
+
+impl AgentCapabilities {
+    #[must_use]
+    pub fn for_kind(kind: HarnessKind) -> Option<Self> {
+        match kind {
+            HarnessKind::OpenCode => Some(Self {
+                tools_format: ToolsFormat::BooleanRecord,
+                color_format: ColorFormat::HexOnly,
+                supported_modes: &["subagent", "primary", "all"],
+            }),
+            HarnessKind::ClaudeCode | HarnessKind::AmpCode => Some(Self {
+                tools_format: ToolsFormat::CommaSeparatedString,
+                color_format: ColorFormat::NamedOrHex,
+                supported_modes: &["subagent", "primary"],
+            }),
+            HarnessKind::CopilotCli => Some(Self {
+                tools_format: ToolsFormat::CommaSeparatedString,
+                color_format: ColorFormat::NamedOrHex,
+                supported_modes: &["subagent", "primary"],
+            }),
+            HarnessKind::Goose => None,
+        }
+    }
+}
+
+/// Expected format for skill `name` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameFormat {
+    /// Lowercase alphanumeric with hyphens only: `^[a-z0-9]+(-[a-z0-9]+)*$`
+    LowercaseHyphenated,
+    /// Any string format accepted.
+    Any,
+}
+
+/// Describes skill validation requirements for a harness.
+#[derive(Debug, Clone)]
+pub struct SkillCapabilities {
+    /// Expected format for `name` field.
+    pub name_format: NameFormat,
+    /// Whether skill name must match parent directory name.
+    pub name_must_match_directory: bool,
+    /// Whether description field is required.
+    pub description_required: bool,
+}
+
+impl SkillCapabilities {
+    #[must_use]
+    pub fn for_kind(kind: HarnessKind) -> Option<Self> {
+        match kind {
+            HarnessKind::OpenCode => Some(Self {
+                name_format: NameFormat::LowercaseHyphenated,
+                name_must_match_directory: true,
+                description_required: true,
+            }),
+            HarnessKind::ClaudeCode | HarnessKind::AmpCode => Some(Self {
+                name_format: NameFormat::Any,
+                name_must_match_directory: false,
+                description_required: false,
+            }),
+            // Copilot CLI follows agentskills.io spec: lowercase hyphenated names,
+            // name must match directory, description required
+            HarnessKind::CopilotCli => Some(Self {
+                name_format: NameFormat::LowercaseHyphenated,
+                name_must_match_directory: true,
+                description_required: true,
+            }),
+            HarnessKind::Goose => None,
+        }
+    }
+}
+
+/// Describes command argument handling for a harness.
+#[derive(Debug, Clone)]
+pub struct CommandCapabilities {
+    /// Whether `$ARGUMENTS` is replaced with the argument string.
+    pub arguments_placeholder: bool,
+    /// Whether `$1`, `$2`, … are replaced with positional arguments.
+    pub positional_placeholders: bool,
+    /// Whether the `argument-hint` frontmatter field is shown to users.
+    pub argument_hint: bool,
+}
+
+impl CommandCapabilities {
+    #[must_use]
+    pub fn for_kind(kind: HarnessKind) -> Option<Self> {
+        match kind {
+            HarnessKind::ClaudeCode => Some(Self {
+                arguments_placeholder: true,
+                positional_placeholders: true,
+                argument_hint: true,
+            }),
+            HarnessKind::OpenCode => Some(Self {
+                arguments_placeholder: true,
+                positional_placeholders: true,
+                argument_hint: false,
+            }),
+            // Amp appends the arguments to the prompt instead of substituting
+            HarnessKind::AmpCode => Some(Self {
+                arguments_placeholder: false,
+                positional_placeholders: false,
+                argument_hint: false,
+            }),
+            HarnessKind::Goose | HarnessKind::CopilotCli => None,
+        }
+    }
+}
+
+/// A validation issue found in an MCP server configuration.
+///
+/// Issues are collected by [`validate_mcp_server`] and returned as a `Vec`.
+/// An empty result means the configuration passed all checks.
+///
+/// # Extensibility
+///
+/// This struct is marked `#[non_exhaustive]` to allow adding new fields
+/// in future versions without breaking changes. Use the constructor
+/// methods [`ValidationIssue::error`] and [`ValidationIssue::warning`]
+/// rather than constructing directly.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// Severity of the issue.
+    pub severity: Severity,
+
+    /// The field path where the issue was found (e.g., "command", "url", "env.SECRET_KEY").
+    pub field: String,
+
+    /// Human-readable description of the issue.
+    pub message: String,
+
+    /// Machine-readable issue code for programmatic filtering.
+    ///
+    /// See the `CODE_*` constants in this module.
+    pub code: Option<&'static str>,
+}
+
+impl ValidationIssue {
+    /// Creates an error-level validation issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The field path where the issue was found
+    /// * `message` - Human-readable description
+    /// * `code` - Optional machine-readable code
+    #[must_use]
+    pub fn error(
+        field: impl Into<String>,
+        message: impl Into<String>,
+        code: Option<&'static str>,
+    ) -> Self {
+        Self {
+            severity: Severity::Error,
+            field: field.into(),
+            message: message.into(),
+            code,
+        }
+    }
+
+    /// Creates a warning-level validation issue.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The field path where the issue was found
+    /// * `message` - Human-readable description
+    /// * `code` - Optional machine-readable code
+    #[must_use]
+    pub fn warning(
+        field: impl Into<String>,
+        message: impl Into<String>,
+        code: Option<&'static str>,
+    ) -> Self {
+        Self {
+            severity: Severity::Warning,
+            field: field.into(),
+            message: message.into(),
+            code,
+        }
+    }
+}
+
+/// A [`ValidationIssue`] whose field and message may be borrowed.
+///
+/// Returned by the allocation-light validation paths, such as
+/// [`validate_skill_borrowed`], for services validating at high volume.
+/// Field names are borrowed rather than allocated for every issue; convert
+/// with [`into_owned`](Self::into_owned) or `From` to keep an issue.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssueRef<'a> {
+    /// Severity of the issue.
+    pub severity: Severity,
+
+    /// The field path where the issue was found.
+    pub field: Cow<'a, str>,
+
+    /// Human-readable description of the issue.
+    pub message: Cow<'a, str>,
+
+    /// Machine-readable issue code for programmatic filtering.
+    pub code: Option<&'static str>,
+}
+
+impl<'a> ValidationIssueRef<'a> {
+    /// Creates an error-level validation issue.
+    #[must_use]
+    pub fn error(
+        field: impl Into<Cow<'a, str>>,
+        message: impl Into<Cow<'a, str>>,
+        code: Option<&'static str>,
+    ) -> Self {
+        Self {
+            severity: Severity::Error,
+            field: field.into(),
+            message: message.into(),
+            code,
+        }
+    }
+
+    /// Creates a warning-level validation issue.
+    #[must_use]
+    pub fn warning(
+        field: impl Into<Cow<'a, str>>,
+        message: impl Into<Cow<'a, str>>,
+        code: Option<&'static str>,
+    ) -> Self {
+        Self {
+            severity: Severity::Warning,
+            field: field.into(),
+            message: message.into(),
+            code,
+        }
+    }
+
+    /// Converts to an owned [`ValidationIssue`].
+    #[must_use]
+    pub fn into_owned(self) -> ValidationIssue {
+        ValidationIssue {
+            severity: self.severity,
+            field: self.field.into_owned(),
+            message: self.message.into_owned(),
+            code: self.code,
+        }
+    }
+}
+
+impl From<ValidationIssueRef<'_>> for ValidationIssue {
+    fn from(issue: ValidationIssueRef<'_>) -> Self {
+        issue.into_owned()
+    }
+}
+
+impl ValidationIssue {
+    /// Borrows this issue as a [`ValidationIssueRef`].
+    #[must_use]
+    pub fn as_borrowed(&self) -> ValidationIssueRef<'_> {
+        ValidationIssueRef {
+            severity: self.severity,
+            field: Cow::Borrowed(&self.field),
+            message: Cow::Borrowed(&self.message),
+            code: self.code,
+        }
+    }
+}
+
+/// Patterns that suggest an environment variable contains sensitive data.
+///
+/// These are checked case-insensitively against variable names.
+const SUSPICIOUS_ENV_PATTERNS: &[&str] = &[
+    "PASSWORD",
+    "PASSWD",
+    "SECRET",
+    "TOKEN",
+    "API_KEY",
+    "PRIVATE_KEY",
+    "ACCESS_KEY",
+    "CREDENTIAL",
+    "BEARER",
+    "AUTH",
+];
+
+/// How well a skill works with a harness, derived from its [`CompatibilityScore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CompatibilityLevel {
+    /// The skill validated without any issues.
+    Compatible,
+
+    /// The skill loads, but some fields are rejected or not recommended.
+    Partial,
+
+    /// The harness cannot load the skill at all.
+    Incompatible,
+}
+
+/// Result of validating a skill against a single harness.
+///
+/// The score starts at 100 and drops by [`COMPATIBILITY_ERROR_PENALTY`]
+/// per error and [`COMPATIBILITY_WARNING_PENALTY`] per warning. Harnesses
+/// without skill support, and skills whose frontmatter cannot be parsed,
+/// score 0.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CompatibilityScore {
+    /// Score from 0 (unusable) to 100 (no issues).
+    pub score: u8,
+
+    /// Summary level for display.
+    pub level: CompatibilityLevel,
+
+    /// Error-level issues that prevent the skill from working as written.
+    pub blocking: Vec<ValidationIssue>,
+
+    /// Warning-level issues.
+    pub warnings: Vec<ValidationIssue>,
+}
+
+/// Score deducted for each error-level issue.
+pub const COMPATIBILITY_ERROR_PENALTY: u8 = 30;
+
+/// Score deducted for each warning-level issue.
+pub const COMPATIBILITY_WARNING_PENALTY: u8 = 10;
+
+/// Returns `true` if a variable or header name matches one of the
+/// [`SUSPICIOUS_ENV_PATTERNS`].
+pub(crate) fn is_sensitive_name(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    SUSPICIOUS_ENV_PATTERNS
+        .iter()
+        .any(|pattern| upper.contains(pattern))
+}
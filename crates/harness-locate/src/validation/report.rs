@@ -0,0 +1,124 @@
+//! Summarizing validation findings as a process exit status.
+
+use super::{Finding, Severity, ValidationIssue};
+
+/// Exit status when nothing was found, or only what the policy ignores.
+pub const EXIT_OK: i32 = 0;
+
+/// Exit status when the worst finding is a warning.
+pub const EXIT_WARNINGS: i32 = 1;
+
+/// Exit status when at least one finding is an error.
+pub const EXIT_ERRORS: i32 = 2;
+
+/// How [`ValidationReport::exit_code`] treats warnings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum ExitPolicy {
+    /// Errors exit with [`EXIT_ERRORS`] and warnings with
+    /// [`EXIT_WARNINGS`].
+    #[default]
+    Standard,
+    /// Warnings fail like errors, exiting with [`EXIT_ERRORS`].
+    Strict,
+    /// Warnings are ignored; only errors fail.
+    ErrorsOnly,
+}
+
+/// The findings of a validation run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Every finding, in the order it was reported.
+    pub findings: Vec<Finding>,
+}
+
+impl ValidationReport {
+    /// Returns every issue, without its resource.
+    pub fn issues(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.findings.iter().map(|finding| &finding.issue)
+    }
+
+    /// Returns the severity of the worst finding, or `None` if there are
+    /// none.
+    #[must_use]
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.issues()
+            .map(|issue| issue.severity)
+            .reduce(|worst, severity| {
+                if severity == Severity::Error {
+                    severity
+                } else {
+                    worst
+                }
+            })
+    }
+
+    /// Maps the report to a conventional exit status under `policy`:
+    /// [`EXIT_OK`], [`EXIT_WARNINGS`] or [`EXIT_ERRORS`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use harness_locate::validation::{ExitPolicy, ValidationReport};
+    ///
+    /// let report = ValidationReport::default();
+    /// assert_eq!(report.exit_code(ExitPolicy::Strict), 0);
+    /// ```
+    #[must_use]
+    pub fn exit_code(&self, policy: ExitPolicy) -> i32 {
+        match (self.max_severity(), policy) {
+            (None, _) | (Some(Severity::Warning), ExitPolicy::ErrorsOnly) => EXIT_OK,
+            (Some(Severity::Warning), ExitPolicy::Standard) => EXIT_WARNINGS,
+            (Some(Severity::Warning), ExitPolicy::Strict) | (Some(Severity::Error), _) => {
+                EXIT_ERRORS
+            }
+        }
+    }
+}
+
+impl From<Vec<Finding>> for ValidationReport {
+    fn from(findings: Vec<Finding>) -> Self {
+        Self { findings }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::validation::TargetKind;
+
+    fn report(issues: Vec<ValidationIssue>) -> ValidationReport {
+        issues
+            .into_iter()
+            .map(|issue| Finding {
+                kind: TargetKind::Skill,
+                name: "deploy".to_string(),
+                path: PathBuf::from("/skills/deploy/SKILL.md"),
+                issue,
+            })
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    #[test]
+    fn maps_worst_severity_to_exit_codes() {
+        let clean = report(Vec::new());
+        let warned = report(vec![ValidationIssue::warning("name", "odd", None)]);
+        let failed = report(vec![
+            ValidationIssue::warning("name", "odd", None),
+            ValidationIssue::error("description", "missing", None),
+            ValidationIssue::warning("body", "long", None),
+        ]);
+
+        assert_eq!(clean.max_severity(), None);
+        assert_eq!(warned.max_severity(), Some(Severity::Warning));
+        assert_eq!(failed.max_severity(), Some(Severity::Error));
+
+        let codes = |policy| [&clean, &warned, &failed].map(|report| report.exit_code(policy));
+        assert_eq!(codes(ExitPolicy::Standard), [0, 1, 2]);
+        assert_eq!(codes(ExitPolicy::Strict), [0, 2, 2]);
+        assert_eq!(codes(ExitPolicy::ErrorsOnly), [0, 0, 2]);
+    }
+}
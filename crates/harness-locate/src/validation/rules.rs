@@ -0,0 +1,442 @@
+//! Custom validation rules run alongside the built-in checks.
+
+use std::path::{Path, PathBuf};
+
+use super::{ValidationBaseline, ValidationIssue, ValidationReport, validate_agent_for_harness};
+use crate::error::{Error, Result};
+use crate::harness::Harness;
+use crate::mcp::McpServer;
+use crate::types::{DirectoryStructure, ResourceKind, Scope};
+
+/// The kind of resource a [`ValidationTarget`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TargetKind {
+    /// An MCP server from the harness's MCP config.
+    McpServer,
+    /// A skill file.
+    Skill,
+    /// A custom command file.
+    Command,
+    /// An agent definition file.
+    Agent,
+}
+
+impl TargetKind {
+    /// Returns the kind as a `snake_case` identifier.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::McpServer => "mcp_server",
+            Self::Skill => "skill",
+            Self::Command => "command",
+            Self::Agent => "agent",
+        }
+    }
+}
+
+/// A resource handed to each [`ValidationRule`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum ValidationTarget<'a> {
+    /// An MCP server and its name in the config.
+    McpServer {
+        /// The server name.
+        name: &'a str,
+        /// The parsed server.
+        server: &'a McpServer,
+    },
+    /// A skill, named after its directory.
+    Skill {
+        /// The directory name.
+        name: &'a str,
+        /// The `SKILL.md` content.
+        content: &'a str,
+    },
+    /// A command, named after its file.
+    Command {
+        /// The file stem.
+        name: &'a str,
+        /// The file content.
+        content: &'a str,
+    },
+    /// An agent, named after its file.
+    Agent {
+        /// The file stem.
+        name: &'a str,
+        /// The file content.
+        content: &'a str,
+    },
+}
+
+impl ValidationTarget<'_> {
+    /// Returns the kind of resource.
+    #[must_use]
+    pub fn kind(&self) -> TargetKind {
+        match self {
+            Self::McpServer { .. } => TargetKind::McpServer,
+            Self::Skill { .. } => TargetKind::Skill,
+            Self::Command { .. } => TargetKind::Command,
+            Self::Agent { .. } => TargetKind::Agent,
+        }
+    }
+
+    /// Returns the resource name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::McpServer { name, .. }
+            | Self::Skill { name, .. }
+            | Self::Command { name, .. }
+            | Self::Agent { name, .. } => name,
+        }
+    }
+}
+
+/// A house rule checked by a [`Validator`].
+///
+/// Rules see every target and ignore the kinds they do not care about.
+///
+/// # Example
+///
+/// ```
+/// use harness_locate::types::EnvValue;
+/// use harness_locate::validation::{ValidationIssue, ValidationRule, ValidationTarget};
+/// use harness_locate::mcp::McpServer;
+///
+/// struct EnvRefsOnly;
+///
+/// impl ValidationRule for EnvRefsOnly {
+///     fn code(&self) -> &'static str {
+///         "acme.env.literal"
+///     }
+///
+///     fn check(&self, target: &ValidationTarget<'_>) -> Vec<ValidationIssue> {
+///         let ValidationTarget::McpServer { server: McpServer::Stdio(server), .. } = target else {
+///             return Vec::new();
+///         };
+///         server
+///             .env
+///             .iter()
+///             .filter(|(_, value)| matches!(value, EnvValue::Plain(_)))
+///             .map(|(name, _)| {
+///                 ValidationIssue::error(
+///                     format!("env.{name}"),
+///                     "use an environment variable reference",
+///                     None,
+///                 )
+///             })
+///             .collect()
+///     }
+/// }
+/// ```
+pub trait ValidationRule: Send + Sync {
+    /// The code given to issues the rule returns without one.
+    fn code(&self) -> &'static str;
+
+    /// Checks `target`, returning an empty vector if it passes.
+    fn check(&self, target: &ValidationTarget<'_>) -> Vec<ValidationIssue>;
+}
+
+/// An issue found by [`Validator::validate_all`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    /// The kind of resource.
+    pub kind: TargetKind,
+    /// The resource name.
+    pub name: String,
+    /// The file the resource was read from.
+    pub path: PathBuf,
+    /// The issue.
+    pub issue: ValidationIssue,
+}
+
+/// Runs the built-in checks and any registered [`ValidationRule`]s.
+#[derive(Default)]
+pub struct Validator {
+    rules: Vec<Box<dyn ValidationRule>>,
+    skip_builtins: bool,
+}
+
+impl std::fmt::Debug for Validator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let codes: Vec<_> = self.rules.iter().map(|rule| rule.code()).collect();
+        f.debug_struct("Validator")
+            .field("rules", &codes)
+            .field("skip_builtins", &self.skip_builtins)
+            .finish()
+    }
+}
+
+impl Validator {
+    /// Creates a validator running only the built-in checks.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a rule, run after the built-in checks.
+    #[must_use]
+    pub fn with_rule(mut self, rule: impl ValidationRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Runs only the registered rules.
+    #[must_use]
+    pub fn without_builtins(mut self) -> Self {
+        self.skip_builtins = true;
+        self
+    }
+
+    /// Validates one target for `harness`.
+    ///
+    /// Built-in issues come first, then each rule's issues in the order
+    /// the rules were registered.
+    #[must_use]
+    pub fn validate(
+        &self,
+        harness: &Harness,
+        target: &ValidationTarget<'_>,
+    ) -> Vec<ValidationIssue> {
+        let mut issues = if self.skip_builtins {
+            Vec::new()
+        } else {
+            builtin(harness, target)
+        };
+        for rule in &self.rules {
+            issues.extend(rule.check(target).into_iter().map(|mut issue| {
+                issue.code.get_or_insert(rule.code());
+                issue
+            }));
+        }
+        issues
+    }
+
+    /// Validates every MCP server, skill, command and agent `harness` has
+    /// in `scope`.
+    ///
+    /// Resources the harness does not support in `scope` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configuration directory cannot be determined,
+    /// or a config or resource file cannot be read or parsed.
+    pub fn validate_all(&self, harness: &Harness, scope: &Scope) -> Result<ValidationReport> {
+        let mut findings = Vec::new();
+        let mut push = |kind, name: &str, path: &Path, issues: Vec<ValidationIssue>| {
+            findings.extend(issues.into_iter().map(|issue| Finding {
+                kind,
+                name: name.to_string(),
+                path: path.to_path_buf(),
+                issue,
+            }));
+        };
+
+        if let Some(config) = supported(harness.mcp(scope))? {
+            for (name, server) in harness.read_mcp_servers(scope)? {
+                let target = ValidationTarget::McpServer {
+                    name: &name,
+                    server: &server,
+                };
+                push(
+                    TargetKind::McpServer,
+                    &name,
+                    &config.file,
+                    self.validate(harness, &target),
+                );
+            }
+        }
+
+        for (kind, resource) in [
+            (TargetKind::Skill, ResourceKind::Skills),
+            (TargetKind::Command, ResourceKind::Commands),
+            (TargetKind::Agent, ResourceKind::Agents),
+        ] {
+            let Some(dir) = supported(harness.resource(resource, scope))? else {
+                continue;
+            };
+            let nested = matches!(dir.structure, DirectoryStructure::Nested { .. });
+            for path in dir.enumerate()? {
+                let name = if nested {
+                    path.parent().and_then(Path::file_name)
+                } else {
+                    path.file_stem()
+                };
+                let name = name.map(|n| n.to_string_lossy()).unwrap_or_default();
+                let content = crate::fs::read_text(&path)?.text;
+                let target = match kind {
+                    TargetKind::Skill => ValidationTarget::Skill {
+                        name: &name,
+                        content: &content,
+                    },
+                    TargetKind::Command => ValidationTarget::Command {
+                        name: &name,
+                        content: &content,
+                    },
+                    _ => ValidationTarget::Agent {
+                        name: &name,
+                        content: &content,
+                    },
+                };
+                push(kind, &name, &path, self.validate(harness, &target));
+            }
+        }
+
+        Ok(findings.into())
+    }
+
+    /// Like [`validate_all`](Self::validate_all), but returns only the
+    /// findings not accepted by `baseline`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of [`validate_all`](Self::validate_all).
+    pub fn validate_changed(
+        &self,
+        harness: &Harness,
+        scope: &Scope,
+        baseline: &ValidationBaseline,
+    ) -> Result<ValidationReport> {
+        let report = self.validate_all(harness, scope)?;
+        let introduced = baseline.diff(&report.findings).introduced;
+        Ok(introduced.into_iter().cloned().collect::<Vec<_>>().into())
+    }
+}
+
+/// Treats a scope the harness does not support like a missing resource.
+fn supported<T>(resource: Result<Option<T>>) -> Result<Option<T>> {
+    match resource {
+        Err(Error::UnsupportedScope { .. }) => Ok(None),
+        other => other,
+    }
+}
+
+fn builtin(harness: &Harness, target: &ValidationTarget<'_>) -> Vec<ValidationIssue> {
+    match *target {
+        ValidationTarget::McpServer { server, .. } => harness.validate_mcp_server(server),
+        ValidationTarget::Skill { name, content } => harness.validate_skill(content, name),
+        ValidationTarget::Command { content, .. } => harness.validate_command(content),
+        ValidationTarget::Agent { content, .. } => harness
+            .kind()
+            .map(|kind| validate_agent_for_harness(content, kind))
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+    use crate::types::{EnvValue, HarnessKind};
+
+    struct EnvRefsOnly;
+
+    impl ValidationRule for EnvRefsOnly {
+        fn code(&self) -> &'static str {
+            "acme.env.literal"
+        }
+
+        fn check(&self, target: &ValidationTarget<'_>) -> Vec<ValidationIssue> {
+            let ValidationTarget::McpServer {
+                server: McpServer::Stdio(server),
+                ..
+            } = target
+            else {
+                return Vec::new();
+            };
+            server
+                .env
+                .iter()
+                .filter(|(_, value)| matches!(value, EnvValue::Plain(_)))
+                .map(|(name, _)| ValidationIssue::error(format!("env.{name}"), "literal", None))
+                .collect()
+        }
+    }
+
+    struct SafetySection;
+
+    impl ValidationRule for SafetySection {
+        fn code(&self) -> &'static str {
+            "acme.skill.safety"
+        }
+
+        fn check(&self, target: &ValidationTarget<'_>) -> Vec<ValidationIssue> {
+            match target {
+                ValidationTarget::Skill { content, .. }
+                    if !content.lines().any(|line| line.trim() == "## Safety") =>
+                {
+                    vec![ValidationIssue::warning(
+                        "body",
+                        "no ## Safety section",
+                        None,
+                    )]
+                }
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    #[test]
+    fn custom_rules_join_builtin_findings() {
+        let fs = MemoryFs::new()
+            .with_file(
+                "/fake/home/.claude/.mcp.json",
+                r#"{"mcpServers": {
+                    "github": {"command": "gh-mcp", "env": {"GH_TOKEN": "ghp_literal"}},
+                    "linear": {"command": "linear-mcp", "env": {"LINEAR": "${LINEAR_KEY}"}}
+                }}"#,
+            )
+            .with_file(
+                "/fake/home/.claude/skills/deploy/SKILL.md",
+                "---\nname: deploy\ndescription: Ship it\n---\n\n## Safety\n\nDry run first.\n",
+            )
+            .with_file(
+                "/fake/home/.claude/skills/lint/SKILL.md",
+                "---\nname: lint\ndescription: Lint it\n---\n",
+            );
+        let ctx = PathContext::fake("/fake").with_fs(fs);
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let validator = Validator::new()
+            .with_rule(EnvRefsOnly)
+            .with_rule(SafetySection);
+
+        let findings = ctx
+            .scope(|| validator.validate_all(&harness, &Scope::Global))
+            .unwrap()
+            .findings;
+        let custom: Vec<_> = findings
+            .iter()
+            .filter(|f| f.issue.code.is_some_and(|code| code.starts_with("acme.")))
+            .map(|f| (f.kind, f.name.as_str(), f.issue.code.unwrap()))
+            .collect();
+        assert_eq!(
+            custom,
+            [
+                (TargetKind::McpServer, "github", "acme.env.literal"),
+                (TargetKind::Skill, "lint", "acme.skill.safety"),
+            ]
+        );
+        assert!(
+            findings
+                .iter()
+                .any(|f| f.issue.code == Some(super::super::CODE_SUSPICIOUS_ENV))
+        );
+        let github = findings.iter().find(|f| f.name == "github").unwrap();
+        assert_eq!(github.path, PathBuf::from("/fake/home/.claude/.mcp.json"));
+
+        let only_custom = validator.without_builtins();
+        let findings = ctx
+            .scope(|| only_custom.validate_all(&harness, &Scope::Global))
+            .unwrap()
+            .findings;
+        assert_eq!(findings.len(), 2);
+
+        let baseline = ValidationBaseline::from_issues(&findings);
+        let introduced = ctx
+            .scope(|| only_custom.validate_changed(&harness, &Scope::Global, &baseline))
+            .unwrap();
+        assert!(introduced.findings.is_empty());
+    }
+}
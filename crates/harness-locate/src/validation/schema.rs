@@ -0,0 +1,477 @@
+//! Validation of config files against JSON Schema.
+//!
+//! Supports the keywords harness schemas use: `type`, `enum`, `const`,
+//! object and array keywords, string and number bounds, `pattern`, the
+//! `allOf`/`anyOf`/`oneOf`/`not` combinators, and `$ref` to definitions
+//! within the same schema. Other keywords are ignored.
+
+use serde_json::Value;
+
+use super::{CODE_SCHEMA_REF_UNRESOLVED, CODE_SCHEMA_VIOLATION, ValidationIssue};
+use crate::Result;
+use crate::document::McpConfigDocument;
+use crate::types::ConfigResource;
+
+/// Nesting limit for `$ref` resolution, guarding against reference cycles.
+const MAX_DEPTH: usize = 64;
+
+/// Validates `instance` against the JSON Schema `schema`.
+///
+/// Each violation is reported as an error whose field is the JSON pointer
+/// of the offending value. `$ref`s that cannot be resolved within
+/// `schema` are reported as warnings and otherwise treated as valid.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::validation::validate_json_schema;
+/// use serde_json::json;
+///
+/// let schema = json!({
+///     "type": "object",
+///     "properties": { "model": { "type": "string" } },
+///     "additionalProperties": false
+/// });
+/// let issues = validate_json_schema(&json!({ "model": 4, "theme": "dark" }), &schema);
+///
+/// assert_eq!(issues.len(), 2);
+/// assert_eq!(issues[0].field, "/model");
+/// ```
+#[must_use]
+pub fn validate_json_schema(instance: &Value, schema: &Value) -> Vec<ValidationIssue> {
+    let mut validator = Validator {
+        root: schema,
+        issues: Vec::new(),
+    };
+    validator.validate(instance, schema, "", 0);
+    validator.issues
+}
+
+/// Validates the config file of `resource` against the JSON Schema
+/// `schema`, typically fetched from [`ConfigResource::schema_url`].
+///
+/// A missing file is validated as an empty object.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or parsed.
+pub fn validate_config_against_schema(
+    resource: &ConfigResource,
+    schema: &Value,
+) -> Result<Vec<ValidationIssue>> {
+    let document = McpConfigDocument::load(resource)?;
+    Ok(validate_json_schema(document.root(), schema))
+}
+
+struct Validator<'s> {
+    root: &'s Value,
+    issues: Vec<ValidationIssue>,
+}
+
+impl<'s> Validator<'s> {
+    /// Returns `true` if `instance` matches `schema`, without recording
+    /// issues.
+    fn matches(&self, instance: &Value, schema: &'s Value, depth: usize) -> bool {
+        let mut probe = Validator {
+            root: self.root,
+            issues: Vec::new(),
+        };
+        probe.validate(instance, schema, "", depth);
+        probe
+            .issues
+            .iter()
+            .all(|issue| issue.code != Some(CODE_SCHEMA_VIOLATION))
+    }
+
+    fn violation(&mut self, path: &str, message: String) {
+        self.issues.push(ValidationIssue::error(
+            path,
+            message,
+            Some(CODE_SCHEMA_VIOLATION),
+        ));
+    }
+
+    fn validate(&mut self, instance: &Value, schema: &'s Value, path: &str, depth: usize) {
+        let schema = match schema {
+            Value::Bool(true) => return,
+            Value::Bool(false) => {
+                self.violation(path, "no value is allowed here".into());
+                return;
+            }
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            match resolve_ref(self.root, reference) {
+                Some(target) if depth < MAX_DEPTH => {
+                    self.validate(instance, target, path, depth + 1);
+                }
+                _ => self.issues.push(ValidationIssue::warning(
+                    path,
+                    format!("cannot resolve schema reference '{reference}'"),
+                    Some(CODE_SCHEMA_REF_UNRESOLVED),
+                )),
+            }
+        }
+
+        if let Some(expected) = schema.get("type")
+            && !type_matches(instance, expected)
+        {
+            self.violation(
+                path,
+                format!(
+                    "expected {}, got {}",
+                    type_list(expected),
+                    type_name(instance)
+                ),
+            );
+            return;
+        }
+        if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+            && !allowed.contains(instance)
+        {
+            self.violation(
+                path,
+                format!("{instance} is not one of {}", Value::from(allowed.clone())),
+            );
+        }
+        if let Some(expected) = schema.get("const")
+            && expected != instance
+        {
+            self.violation(path, format!("expected {expected}, got {instance}"));
+        }
+
+        match instance {
+            Value::Object(object) => self.validate_object(object, schema, path, depth),
+            Value::Array(items) => self.validate_array(items, schema, path, depth),
+            Value::String(s) => self.validate_string(s, schema, path),
+            Value::Number(n) => {
+                if let Some(n) = n.as_f64() {
+                    self.validate_number(n, schema, path);
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(all) = schema.get("allOf").and_then(Value::as_array) {
+            for sub in all {
+                self.validate(instance, sub, path, depth + 1);
+            }
+        }
+        if let Some(any) = schema.get("anyOf").and_then(Value::as_array)
+            && !any.iter().any(|sub| self.matches(instance, sub, depth + 1))
+        {
+            self.violation(path, "does not match any of the allowed schemas".into());
+        }
+        if let Some(one) = schema.get("oneOf").and_then(Value::as_array) {
+            let count = one
+                .iter()
+                .filter(|sub| self.matches(instance, sub, depth + 1))
+                .count();
+            if count != 1 {
+                self.violation(
+                    path,
+                    format!("must match exactly one allowed schema, matches {count}"),
+                );
+            }
+        }
+        if let Some(not) = schema.get("not")
+            && self.matches(instance, not, depth + 1)
+        {
+            self.violation(path, "matches a disallowed schema".into());
+        }
+    }
+
+    fn validate_object(
+        &mut self,
+        object: &serde_json::Map<String, Value>,
+        schema: &'s serde_json::Map<String, Value>,
+        path: &str,
+        depth: usize,
+    ) {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(key) {
+                    self.violation(path, format!("missing required property '{key}'"));
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let patterns: Vec<(regex::Regex, &'s Value)> = schema
+            .get("patternProperties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .filter_map(|(pattern, sub)| Some((regex::Regex::new(pattern).ok()?, sub)))
+            .collect();
+        let additional = schema.get("additionalProperties");
+
+        for (key, value) in object {
+            let child = format!("{path}/{}", escape_pointer(key));
+            let mut known = false;
+            if let Some(sub) = properties.and_then(|p| p.get(key)) {
+                known = true;
+                self.validate(value, sub, &child, depth + 1);
+            }
+            for (pattern, sub) in &patterns {
+                if pattern.is_match(key) {
+                    known = true;
+                    self.validate(value, sub, &child, depth + 1);
+                }
+            }
+            if !known && let Some(additional) = additional {
+                if additional == &Value::Bool(false) {
+                    self.violation(&child, format!("unknown property '{key}'"));
+                } else {
+                    self.validate(value, additional, &child, depth + 1);
+                }
+            }
+        }
+    }
+
+    fn validate_array(
+        &mut self,
+        items: &[Value],
+        schema: &'s serde_json::Map<String, Value>,
+        path: &str,
+        depth: usize,
+    ) {
+        let bound = |key: &str| schema.get(key).and_then(Value::as_u64);
+        if let Some(min) = bound("minItems")
+            && (items.len() as u64) < min
+        {
+            self.violation(
+                path,
+                format!("expected at least {min} items, got {}", items.len()),
+            );
+        }
+        if let Some(max) = bound("maxItems")
+            && (items.len() as u64) > max
+        {
+            self.violation(
+                path,
+                format!("expected at most {max} items, got {}", items.len()),
+            );
+        }
+
+        // Draft 2020-12 `prefixItems`, or the older array form of `items`.
+        let prefix = schema
+            .get("prefixItems")
+            .or_else(|| schema.get("items").filter(|items| items.is_array()))
+            .and_then(Value::as_array);
+        let rest = schema.get("items").filter(|items| !items.is_array());
+        for (i, item) in items.iter().enumerate() {
+            let sub = prefix.and_then(|prefix| prefix.get(i)).or(rest);
+            if let Some(sub) = sub {
+                self.validate(item, sub, &format!("{path}/{i}"), depth + 1);
+            }
+        }
+    }
+
+    fn validate_string(&mut self, s: &str, schema: &serde_json::Map<String, Value>, path: &str) {
+        let len = s.chars().count() as u64;
+        if let Some(min) = schema.get("minLength").and_then(Value::as_u64)
+            && len < min
+        {
+            self.violation(
+                path,
+                format!("expected at least {min} characters, got {len}"),
+            );
+        }
+        if let Some(max) = schema.get("maxLength").and_then(Value::as_u64)
+            && len > max
+        {
+            self.violation(
+                path,
+                format!("expected at most {max} characters, got {len}"),
+            );
+        }
+        if let Some(pattern) = schema.get("pattern").and_then(Value::as_str)
+            && let Ok(re) = regex::Regex::new(pattern)
+            && !re.is_match(s)
+        {
+            self.violation(path, format!("'{s}' does not match pattern '{pattern}'"));
+        }
+    }
+
+    fn validate_number(&mut self, n: f64, schema: &serde_json::Map<String, Value>, path: &str) {
+        let bound = |key: &str| schema.get(key).and_then(Value::as_f64);
+        if let Some(min) = bound("minimum")
+            && n < min
+        {
+            self.violation(path, format!("{n} is less than the minimum {min}"));
+        }
+        if let Some(max) = bound("maximum")
+            && n > max
+        {
+            self.violation(path, format!("{n} is greater than the maximum {max}"));
+        }
+        if let Some(min) = bound("exclusiveMinimum")
+            && n <= min
+        {
+            self.violation(path, format!("{n} must be greater than {min}"));
+        }
+        if let Some(max) = bound("exclusiveMaximum")
+            && n >= max
+        {
+            self.violation(path, format!("{n} must be less than {max}"));
+        }
+    }
+}
+
+/// Resolves a `#`-relative JSON pointer reference within `root`.
+fn resolve_ref<'s>(root: &'s Value, reference: &str) -> Option<&'s Value> {
+    root.pointer(reference.strip_prefix('#')?)
+}
+
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn type_matches(instance: &Value, expected: &Value) -> bool {
+    let matches = |name: &str| match name {
+        "null" => instance.is_null(),
+        "boolean" => instance.is_boolean(),
+        "object" => instance.is_object(),
+        "array" => instance.is_array(),
+        "string" => instance.is_string(),
+        "number" => instance.is_number(),
+        "integer" => {
+            instance.is_i64()
+                || instance.is_u64()
+                || instance.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        _ => true,
+    };
+    match expected {
+        Value::String(name) => matches(name),
+        Value::Array(names) => names.iter().filter_map(Value::as_str).any(matches),
+        _ => true,
+    }
+}
+
+fn type_list(expected: &Value) -> String {
+    match expected {
+        Value::Array(names) => names
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        other => other.as_str().unwrap_or("any").to_string(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(issues: &[ValidationIssue]) -> Vec<&str> {
+        issues.iter().map(|issue| issue.field.as_str()).collect()
+    }
+
+    #[test]
+    fn reports_violations_by_pointer() {
+        let schema = json!({
+            "type": "object",
+            "required": ["mcp"],
+            "properties": {
+                "mcp": {
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/$defs/server" }
+                }
+            },
+            "$defs": {
+                "server": {
+                    "type": "object",
+                    "required": ["type"],
+                    "properties": {
+                        "type": { "enum": ["local", "remote"] },
+                        "command": { "type": "array", "items": { "type": "string" }, "minItems": 1 },
+                        "timeout": { "type": "integer", "exclusiveMinimum": 0 }
+                    }
+                }
+            }
+        });
+        let config = json!({
+            "mcp": {
+                "ok": { "type": "local", "command": ["npx"] },
+                "a/b": { "type": "stdio", "command": ["npx", 1], "timeout": 0 },
+                "none": {}
+            }
+        });
+
+        let issues = validate_json_schema(&config, &schema);
+        assert_eq!(
+            fields(&issues),
+            [
+                "/mcp/a~1b/type",
+                "/mcp/a~1b/command/1",
+                "/mcp/a~1b/timeout",
+                "/mcp/none"
+            ]
+        );
+        assert!(issues.iter().all(|i| i.code == Some(CODE_SCHEMA_VIOLATION)));
+        assert!(
+            validate_json_schema(&json!({}), &schema)[0]
+                .message
+                .contains("'mcp'")
+        );
+    }
+
+    #[test]
+    fn combinators_and_patterns() {
+        let schema = json!({
+            "patternProperties": { "^x-": { "type": "string", "pattern": "^[a-z]+$" } },
+            "additionalProperties": false,
+            "properties": {
+                "port": { "anyOf": [{ "type": "integer" }, { "type": "string", "maxLength": 5 }] },
+                "mode": { "oneOf": [{ "const": "a" }, { "enum": ["a", "b"] }] },
+                "name": { "not": { "const": "" } }
+            }
+        });
+        let config = json!({
+            "x-ok": "abc",
+            "x-bad": "ABC",
+            "port": "too long",
+            "mode": "a",
+            "name": "",
+            "extra": true
+        });
+        let issues = validate_json_schema(&config, &schema);
+        let mut found = fields(&issues);
+        found.sort_unstable();
+        assert_eq!(found, ["/extra", "/mode", "/name", "/port", "/x-bad"]);
+
+        let valid = json!({ "port": 8080, "mode": "b", "name": "srv" });
+        assert!(validate_json_schema(&valid, &schema).is_empty());
+    }
+
+    #[test]
+    fn unresolved_refs_are_warnings() {
+        let schema = json!({ "$ref": "https://example.com/other.json" });
+        let issues = validate_json_schema(&json!({}), &schema);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, Some(CODE_SCHEMA_REF_UNRESOLVED));
+
+        let cyclic = json!({ "$defs": { "a": { "$ref": "#/$defs/a" } }, "$ref": "#/$defs/a" });
+        let issues = validate_json_schema(&json!(1), &cyclic);
+        assert_eq!(
+            issues.last().unwrap().code,
+            Some(CODE_SCHEMA_REF_UNRESOLVED)
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! Multi-project workspaces.
+//!
+//! Monorepos often contain nested projects, each with its own `.claude/`,
+//! `opencode.json` or similar. [`find_project_scopes`] discovers every such
+//! project under a workspace root, and [`Workspace`] aggregates harness
+//! resources across them, tagging each result with the project it came
+//! from.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use harness_locate::workspace::Workspace;
+//! use harness_locate::{Harness, HarnessKind, ResourceKind};
+//!
+//! let workspace = Workspace::discover(".")?;
+//! let harness = Harness::new(HarnessKind::ClaudeCode);
+//! for skills in workspace.resources(&harness, ResourceKind::Skills)? {
+//!     println!("{}: {}", skills.project.display(), skills.value.path.display());
+//! }
+//! # Ok::<(), harness_locate::Error>(())
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::harness::{Harness, HarnessRegistry};
+use crate::types::{ConfigResource, DirectoryResource, HarnessKind, ResourceKind, Scope};
+
+/// How many directory levels below the workspace root are searched.
+pub const MAX_DEPTH: usize = 8;
+
+/// Directories never searched for nested projects.
+///
+/// Hidden directories are skipped as well.
+const IGNORED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "vendor",
+    "dist",
+    "build",
+    "__pycache__",
+];
+
+/// A project inside a workspace that has harness configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectRoot {
+    /// The project directory.
+    pub path: PathBuf,
+    /// Harnesses with configuration in this project, in
+    /// [`HarnessRegistry::harnesses`] order.
+    pub harnesses: Vec<Harness>,
+}
+
+impl ProjectRoot {
+    /// Returns the project scope for this project.
+    #[must_use]
+    pub fn scope(&self) -> Scope {
+        Scope::Project(self.path.clone())
+    }
+
+    /// Returns `true` if `kind` has configuration in this project.
+    #[must_use]
+    pub fn has(&self, kind: HarnessKind) -> bool {
+        self.harnesses.iter().any(|h| h.kind() == Some(kind))
+    }
+}
+
+/// A value found in one project of a workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sourced<T> {
+    /// The project the value belongs to.
+    pub project: PathBuf,
+    /// The value itself.
+    pub value: T,
+}
+
+/// Discovers projects with harness configuration under `root`.
+///
+/// A directory is a project if it contains any of a harness's
+/// [`project_markers`](crate::HarnessBackend::project_markers), including
+/// those of custom harnesses in the
+/// [global registry](HarnessRegistry::global). `root` itself is included if
+/// it qualifies. Hidden directories, dependency and build
+/// output directories, and anything deeper than [`MAX_DEPTH`] are not
+/// searched.
+///
+/// Projects are returned sorted by path.
+///
+/// # Errors
+///
+/// Returns an error if `root` is not a readable directory.
+pub fn find_project_scopes(root: &Path) -> Result<Vec<ProjectRoot>> {
+    let fs = crate::fs::current();
+    if !fs.is_dir(root) {
+        return Err(Error::NotFound(root.display().to_string()));
+    }
+
+    let candidates = HarnessRegistry::global().harnesses();
+    let mut projects = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        let harnesses: Vec<Harness> = candidates
+            .iter()
+            .copied()
+            .filter(|harness| {
+                harness
+                    .backend()
+                    .project_markers()
+                    .iter()
+                    .any(|marker| fs.exists(&dir.join(marker)))
+            })
+            .collect();
+        if !harnesses.is_empty() {
+            projects.push(ProjectRoot {
+                path: dir.clone(),
+                harnesses,
+            });
+        }
+
+        if depth == MAX_DEPTH {
+            continue;
+        }
+        let children = match fs.read_dir(&dir) {
+            Ok(children) => children,
+            Err(e) if depth == 0 => return Err(e.into()),
+            Err(_) => continue,
+        };
+        for child in children {
+            if is_searchable(&child) && fs.is_dir(&child) {
+                pending.push((child, depth + 1));
+            }
+        }
+    }
+
+    projects.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(projects)
+}
+
+fn is_searchable(dir: &Path) -> bool {
+    dir.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| !name.starts_with('.') && !IGNORED_DIRS.contains(&name))
+}
+
+/// A workspace root and the projects discovered under it.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    root: PathBuf,
+    projects: Vec<ProjectRoot>,
+}
+
+impl Workspace {
+    /// Discovers the projects under `root`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `root` is not a readable directory.
+    pub fn discover(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        let projects = find_project_scopes(&root)?;
+        Ok(Self { root, projects })
+    }
+
+    /// Returns the workspace root.
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the discovered projects, sorted by path.
+    #[must_use]
+    pub fn projects(&self) -> &[ProjectRoot] {
+        &self.projects
+    }
+
+    /// Returns the `kind` directory of `harness` in every project configured
+    /// for it.
+    ///
+    /// Projects where the harness does not support `kind` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`Harness::resource`].
+    pub fn resources(
+        &self,
+        harness: &Harness,
+        kind: ResourceKind,
+    ) -> Result<Vec<Sourced<DirectoryResource>>> {
+        self.collect(harness, |scope| harness.resource(kind, scope))
+    }
+
+    /// Returns the rules directory of `harness` in every project configured
+    /// for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`Harness::rules`].
+    pub fn rules(&self, harness: &Harness) -> Result<Vec<Sourced<DirectoryResource>>> {
+        self.collect(harness, |scope| harness.rules(scope))
+    }
+
+    /// Returns the MCP configuration of `harness` in every project
+    /// configured for it.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`Harness::mcp`].
+    pub fn mcp(&self, harness: &Harness) -> Result<Vec<Sourced<ConfigResource>>> {
+        self.collect(harness, |scope| harness.mcp(scope))
+    }
+
+    fn collect<T>(
+        &self,
+        harness: &Harness,
+        lookup: impl Fn(&Scope) -> Result<Option<T>>,
+    ) -> Result<Vec<Sourced<T>>> {
+        let mut found = Vec::new();
+        for project in self
+            .projects
+            .iter()
+            .filter(|p| p.harnesses.contains(harness))
+        {
+            match lookup(&project.scope()) {
+                Ok(Some(value)) => found.push(Sourced {
+                    project: project.path.clone(),
+                    value,
+                }),
+                Ok(None) | Err(Error::UnsupportedScope { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::MemoryFs;
+    use crate::platform::PathContext;
+
+    fn monorepo() -> PathContext {
+        PathContext::fake("/tmp/fake").with_fs(
+            MemoryFs::new()
+                .with_file("/repo/CLAUDE.md", "# Root")
+                .with_dir("/repo/apps/web/.claude/skills")
+                .with_file("/repo/apps/api/opencode.json", "{}")
+                .with_file("/repo/apps/api/src/main.rs", "")
+                .with_dir("/repo/node_modules/pkg/.claude")
+                .with_dir("/repo/.git/hooks/.claude"),
+        )
+    }
+
+    #[test]
+    fn finds_nested_projects() {
+        let projects = monorepo()
+            .scope(|| find_project_scopes(Path::new("/repo")))
+            .unwrap();
+
+        let paths: Vec<_> = projects.iter().map(|p| p.path.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("/repo"),
+                PathBuf::from("/repo/apps/api"),
+                PathBuf::from("/repo/apps/web"),
+            ]
+        );
+        assert_eq!(projects[1].harnesses, [Harness::new(HarnessKind::OpenCode)]);
+        assert!(projects[2].has(HarnessKind::ClaudeCode));
+        assert_eq!(projects[2].scope(), Scope::Project("/repo/apps/web".into()));
+    }
+
+    #[test]
+    fn missing_root_is_an_error() {
+        let result = monorepo().scope(|| find_project_scopes(Path::new("/elsewhere")));
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn aggregates_resources_with_provenance() {
+        let ctx = monorepo();
+        let workspace = ctx.scope(|| Workspace::discover("/repo")).unwrap();
+        let claude = Harness::new(HarnessKind::ClaudeCode);
+
+        let skills = ctx
+            .scope(|| workspace.resources(&claude, ResourceKind::Skills))
+            .unwrap();
+        assert_eq!(skills.len(), 2);
+        assert_eq!(skills[0].project, Path::new("/repo"));
+        assert!(!skills[0].value.exists);
+        assert_eq!(skills[1].project, Path::new("/repo/apps/web"));
+        assert!(skills[1].value.exists);
+
+        let opencode = Harness::new(HarnessKind::OpenCode);
+        let mcp = ctx.scope(|| workspace.mcp(&opencode)).unwrap();
+        assert_eq!(mcp.len(), 1);
+        assert_eq!(mcp[0].project, Path::new("/repo/apps/api"));
+    }
+}
@@ -0,0 +1,326 @@
+//! Targeted diagnostics for hand-edited YAML config files.
+//!
+//! Strict YAML parsing stops at the first problem it trips over, with a
+//! message about scanner states for a tab or a bare "duplicate entry with
+//! key" for a repeated key, neither pointing at the earlier line that
+//! conflicts. [`lint_yaml`] looks for the mistakes people make when
+//! editing configs such as Goose's `config.yaml` by hand and reports each
+//! with its line and a suggested fix. [`McpConfigDocument::parse`] runs it
+//! before strict parsing and fails with [`Error::YamlLint`] if it finds
+//! anything.
+//!
+//! # Example
+//!
+//! ```
+//! use harness_locate::yamllint::{YamlProblem, lint_yaml};
+//!
+//! let diagnostics = lint_yaml("extensions:\n\tgithub:\n    enabled: true\n");
+//! assert_eq!(diagnostics[0].line, 2);
+//! assert_eq!(diagnostics[0].problem, YamlProblem::TabIndent);
+//! ```
+//!
+//! [`McpConfigDocument::parse`]: crate::McpConfigDocument::parse
+//! [`Error::YamlLint`]: crate::Error::YamlLint
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// A problem found by [`lint_yaml`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum YamlProblem {
+    /// A line is indented with a tab, which YAML does not allow.
+    TabIndent,
+    /// A key appears twice in the same mapping.
+    DuplicateKey {
+        /// The repeated key.
+        key: String,
+        /// Line of the first occurrence, starting at 1.
+        first_line: usize,
+    },
+}
+
+/// A problem at a location in a YAML document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YamlDiagnostic {
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column number, starting at 1.
+    pub column: usize,
+    /// What is wrong.
+    pub problem: YamlProblem,
+}
+
+impl YamlDiagnostic {
+    /// Describes the problem.
+    #[must_use]
+    pub fn message(&self) -> String {
+        match &self.problem {
+            YamlProblem::TabIndent => "tab used for indentation".to_string(),
+            YamlProblem::DuplicateKey { key, first_line } => {
+                format!("duplicate key `{key}` (first defined on line {first_line})")
+            }
+        }
+    }
+
+    /// Suggests a fix.
+    #[must_use]
+    pub fn suggestion(&self) -> String {
+        match &self.problem {
+            YamlProblem::TabIndent => "indent with spaces instead".to_string(),
+            YamlProblem::DuplicateKey { .. } => {
+                "merge the entries or remove one; only the last would be kept".to_string()
+            }
+        }
+    }
+}
+
+impl fmt::Display for YamlDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}; {}",
+            self.line,
+            self.column,
+            self.message(),
+            self.suggestion()
+        )
+    }
+}
+
+/// A block mapping being read: its key column and the keys seen so far.
+struct Mapping {
+    column: usize,
+    keys: HashMap<String, usize>,
+}
+
+/// Checks `content` for tab indentation and duplicate keys.
+///
+/// Only block-style mappings are checked for duplicates; flow mappings
+/// (`{ ... }`) and the contents of block scalars (`|`, `>`) are skipped.
+/// Returns an empty list if nothing was found, which does not mean the
+/// document is valid YAML.
+#[must_use]
+pub fn lint_yaml(content: &str) -> Vec<YamlDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut mappings: Vec<Mapping> = Vec::new();
+    // Column of the node owning the block scalar being read, if any.
+    let mut block_scalar: Option<usize> = None;
+    let mut flow_depth = 0usize;
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = index + 1;
+        let body = raw.trim_start_matches([' ', '\t']);
+        if body.is_empty() {
+            continue;
+        }
+        let spaces = raw.len() - raw.trim_start_matches(' ').len();
+        if let Some(owner) = block_scalar {
+            if spaces > owner {
+                continue;
+            }
+            block_scalar = None;
+        }
+        let indent = &raw[..raw.len() - body.len()];
+        if let Some(tab) = indent.find('\t') {
+            diagnostics.push(YamlDiagnostic {
+                line,
+                column: tab + 1,
+                problem: YamlProblem::TabIndent,
+            });
+            continue;
+        }
+        if body.starts_with('#') {
+            continue;
+        }
+        if flow_depth > 0 {
+            flow_depth = track_flow(flow_depth, body);
+            continue;
+        }
+        if spaces == 0 && (body.starts_with("---") || body.starts_with("...")) {
+            mappings.clear();
+            continue;
+        }
+
+        // Step past sequence markers; each starts a new mapping.
+        let mut column = spaces;
+        let mut rest = body;
+        let mut item_column = None;
+        while let Some(after) = rest.strip_prefix('-')
+            && (after.is_empty() || after.starts_with(' '))
+        {
+            item_column = Some(column);
+            let trimmed = after.trim_start_matches(' ');
+            column += rest.len() - trimmed.len();
+            rest = trimmed;
+        }
+
+        if let Some(item_column) = item_column {
+            while mappings.last().is_some_and(|m| m.column > item_column) {
+                mappings.pop();
+            }
+        }
+        let Some((key, value)) = split_key(rest) else {
+            if starts_block_scalar(rest) {
+                block_scalar = item_column;
+            }
+            flow_depth = track_flow(0, rest);
+            continue;
+        };
+
+        while mappings.last().is_some_and(|m| m.column > column) {
+            mappings.pop();
+        }
+        match mappings.last_mut() {
+            Some(mapping) if mapping.column == column => {
+                if key != "<<"
+                    && let Some(&first_line) = mapping.keys.get(&key)
+                {
+                    diagnostics.push(YamlDiagnostic {
+                        line,
+                        column: column + 1,
+                        problem: YamlProblem::DuplicateKey { key, first_line },
+                    });
+                } else {
+                    mapping.keys.insert(key, line);
+                }
+            }
+            _ => mappings.push(Mapping {
+                column,
+                keys: HashMap::from([(key, line)]),
+            }),
+        }
+
+        if starts_block_scalar(value) {
+            block_scalar = Some(column);
+        }
+        flow_depth = track_flow(0, value);
+    }
+    diagnostics
+}
+
+/// Splits `key: value` into the unquoted key and the value, or returns
+/// `None` if the text is not a block mapping entry.
+fn split_key(text: &str) -> Option<(String, &str)> {
+    let (key, rest) = match text.chars().next()? {
+        quote @ ('"' | '\'') => {
+            let end = text[1..].find(quote)? + 1;
+            (text[1..end].to_string(), &text[end + 1..])
+        }
+        '{' | '[' | '#' | '&' | '*' | '!' | '|' | '>' | '%' | '@' | '`' => return None,
+        _ => {
+            let end = text
+                .match_indices(':')
+                .map(|(i, _)| i)
+                .find(|&i| text[i + 1..].is_empty() || text[i + 1..].starts_with([' ', '\t']))?;
+            let key = &text[..end];
+            if key.contains(" #") {
+                return None;
+            }
+            (key.trim_end().to_string(), &text[end..])
+        }
+    };
+    let value = rest.strip_prefix(':')?;
+    if !value.is_empty() && !value.starts_with([' ', '\t']) {
+        return None;
+    }
+    Some((key, value.trim()))
+}
+
+fn starts_block_scalar(value: &str) -> bool {
+    value.starts_with(['|', '>'])
+}
+
+/// Returns the flow collection depth after `text`, starting from `depth`.
+fn track_flow(mut depth: usize, text: &str) -> usize {
+    let mut quote = None;
+    for c in text.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') if depth > 0 => quote = Some(c),
+            (None, '#') => break,
+            (None, '{' | '[') => depth += 1,
+            (None, '}' | ']') if depth > 0 => depth -= 1,
+            (None, _) if depth == 0 => return 0,
+            _ => {}
+        }
+    }
+    depth
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_tabs_and_duplicate_keys_with_locations() {
+        let content = "\
+GOOSE_PROVIDER: openai
+extensions:
+  github:
+    cmd: gh-mcp
+    args: [\"--verbose\",
+      \"--port\"]
+    description: |
+      enabled: true
+      enabled: true
+    enabled: true
+  github:
+    cmd: other
+\tdeveloper:
+    enabled: false
+GOOSE_PROVIDER: anthropic
+";
+        let diagnostics = lint_yaml(content);
+        let found: Vec<_> = diagnostics
+            .iter()
+            .map(|d| (d.line, d.column, d.problem.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    11,
+                    3,
+                    YamlProblem::DuplicateKey {
+                        key: "github".into(),
+                        first_line: 3,
+                    }
+                ),
+                (13, 1, YamlProblem::TabIndent),
+                (
+                    15,
+                    1,
+                    YamlProblem::DuplicateKey {
+                        key: "GOOSE_PROVIDER".into(),
+                        first_line: 1,
+                    }
+                ),
+            ]
+        );
+        assert_eq!(
+            diagnostics[1].to_string(),
+            "line 13, column 1: tab used for indentation; indent with spaces instead"
+        );
+    }
+
+    #[test]
+    fn sequence_items_start_new_mappings() {
+        let content = "\
+servers:
+  - name: a
+    url: http://a
+  - name: b
+    url: http://b
+  -
+    name: c
+\"quoted\": 1
+quoted: 2
+";
+        let diagnostics = lint_yaml(content);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].line, 9);
+        assert!(lint_yaml("a: 1\n---\na: 2\n").is_empty());
+    }
+}
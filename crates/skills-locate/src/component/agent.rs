@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use crate::{Error, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct AgentDescriptor {
     pub name: String,
@@ -15,6 +16,9 @@ pub struct AgentDescriptor {
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
+    /// File relative to the plugin root, set during discovery.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -75,6 +79,7 @@ pub fn parse_agent_descriptor(content: &str) -> Result<AgentDescriptor> {
         tools: frontmatter.tools.into_vec(),
         model: frontmatter.model,
         color: frontmatter.color,
+        path: None,
     })
 }
 
@@ -4,6 +4,7 @@ use crate::{Error, Result};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct CommandDescriptor {
     pub name: String,
@@ -11,6 +12,9 @@ pub struct CommandDescriptor {
     pub description: Option<String>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub allowed_tools: Vec<String>,
+    /// File relative to the plugin root, set during discovery.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -52,6 +56,7 @@ pub fn parse_command_descriptor(content: &str, filename: &str) -> Result<Command
         name,
         description: frontmatter.description,
         allowed_tools: frontmatter.allowed_tools,
+        path: None,
     })
 }
 
@@ -1,13 +1,14 @@
 //! Hook types and parsing for plugin hooks.json files.
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
 use crate::{Error, Result};
 
 /// Hook event types that trigger hook execution.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(rename_all = "PascalCase")]
 #[non_exhaustive]
 pub enum HookEvent {
@@ -25,6 +26,7 @@ pub enum HookEvent {
 
 /// A hook action to execute.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 #[non_exhaustive]
 pub enum HookAction {
@@ -45,6 +47,7 @@ pub enum HookAction {
 
 /// A group of hooks with optional matcher.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct HookGroup {
     /// Optional matcher pattern (e.g., tool name).
@@ -54,12 +57,24 @@ pub struct HookGroup {
     pub hooks: Vec<HookAction>,
 }
 
-/// Parsed hooks.json file structure.
-pub type HooksConfig = HashMap<HookEvent, Vec<HookGroup>>;
+/// Parsed hooks.json file structure, with events in declaration order.
+pub type HooksConfig = BTreeMap<HookEvent, Vec<HookGroup>>;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum HooksFile {
+    Wrapped { hooks: HooksConfig },
+    Bare(HooksConfig),
+}
 
 /// Parse a hooks.json file content into a HooksConfig.
+///
+/// Accepts events at the top level or under a `hooks` key, as Claude Code
+/// plugins write them.
 pub fn parse_hooks_json(content: &str) -> Result<HooksConfig> {
-    serde_json::from_str(content).map_err(Error::JsonParse)
+    match serde_json::from_str(content).map_err(Error::JsonParse)? {
+        HooksFile::Wrapped { hooks } | HooksFile::Bare(hooks) => Ok(hooks),
+    }
 }
 
 #[cfg(test)]
@@ -130,6 +145,16 @@ mod tests {
         assert_eq!(config[&HookEvent::PreToolUse].len(), 1);
     }
 
+    #[test]
+    fn parse_hooks_json_under_hooks_key() {
+        let content = r#"{
+            "description": "Formatting hooks",
+            "hooks": {"Stop": [{"hooks": ["cargo fmt"]}]}
+        }"#;
+        let config = parse_hooks_json(content).unwrap();
+        assert_eq!(config.keys().collect::<Vec<_>>(), [&HookEvent::Stop]);
+    }
+
     #[test]
     fn parse_empty_hooks_json() {
         let content = "{}";
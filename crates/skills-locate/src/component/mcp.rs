@@ -2,13 +2,14 @@
 //!
 //! Re-exports types from `harness-locate` for unified MCP representation.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::Deserialize;
 
 pub use harness_locate::{EnvValue, HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
 
-use crate::{Error, Result};
+use crate::Result;
+use crate::schema::{SchemaKind, parse_json};
 
 #[derive(Debug, Deserialize)]
 struct McpServerEntry {
@@ -80,13 +81,14 @@ fn entry_to_mcp_server(name: String, entry: McpServerEntry) -> Option<(String, M
     }
 }
 
-fn convert_entries(map: HashMap<String, McpServerEntry>) -> HashMap<String, McpServer> {
+fn convert_entries(map: HashMap<String, McpServerEntry>) -> BTreeMap<String, McpServer> {
     map.into_iter()
         .filter_map(|(name, entry)| entry_to_mcp_server(name, entry))
         .collect()
 }
 
-/// Parse a .mcp.json file content into a map of MCP servers.
+/// Parse a .mcp.json file content into a map of MCP servers, sorted by
+/// name.
 ///
 /// Supports both formats:
 /// - Wrapped: `{ "mcpServers": { "name": { ... } } }` (Claude's format)
@@ -96,13 +98,12 @@ fn convert_entries(map: HashMap<String, McpServerEntry>) -> HashMap<String, McpS
 /// - `"sse"` → SSE transport
 /// - `"http"` or `"streamable-http"` → HTTP transport
 /// - anything else or missing → Stdio transport
-pub fn parse_mcp_json(content: &str) -> Result<HashMap<String, McpServer>> {
+pub fn parse_mcp_json(content: &str) -> Result<BTreeMap<String, McpServer>> {
     if let Ok(wrapped) = serde_json::from_str::<McpJsonWrapped>(content) {
         return Ok(convert_entries(wrapped.mcp_servers));
     }
 
-    let map: HashMap<String, McpServerEntry> =
-        serde_json::from_str(content).map_err(Error::JsonParse)?;
+    let map: HashMap<String, McpServerEntry> = parse_json(SchemaKind::McpJson, content)?;
 
     Ok(convert_entries(map))
 }
@@ -143,6 +144,18 @@ mod tests {
         assert_eq!(servers.len(), 2);
     }
 
+    #[test]
+    fn parse_mcp_json_returns_servers_sorted_by_name() {
+        let content = r#"{
+            "zeta": {"command": "z"},
+            "alpha": {"command": "a"},
+            "mid": {"command": "m"}
+        }"#;
+        let servers = parse_mcp_json(content).unwrap();
+        let names: Vec<_> = servers.keys().map(String::as_str).collect();
+        assert_eq!(names, ["alpha", "mid", "zeta"]);
+    }
+
     #[test]
     fn parse_empty_mcp_json() {
         let content = "{}";
@@ -4,6 +4,7 @@ mod hook;
 mod manifest;
 mod mcp;
 mod npm;
+mod plugin;
 mod python;
 mod skill;
 
@@ -14,5 +15,6 @@ pub use hook::{HookAction, HookEvent, HookGroup, HooksConfig, parse_hooks_json};
 pub use manifest::{ManifestConfig, parse_manifest};
 pub use mcp::{McpServer, parse_mcp_json};
 pub use npm::detect_npm_mcp;
+pub use plugin::{ComponentConfig, PluginManifest, parse_plugin_manifest};
 pub use python::detect_python_mcp;
 pub use skill::parse_skill_descriptor;
@@ -0,0 +1,146 @@
+//! Plugin manifest (`.claude-plugin/plugin.json`) parsing.
+
+use serde::{Deserialize, Deserializer};
+
+use crate::Result;
+use crate::schema::{SchemaKind, parse_json};
+
+/// A plugin's `plugin.json`.
+///
+/// Besides metadata, a manifest can point at command and agent files
+/// outside the default `commands/` and `agents/` directories, and give
+/// hooks and MCP servers either as a path to a config file or inline.
+/// Declared paths are relative to the plugin root and add to the default
+/// locations rather than replacing them.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct PluginManifest {
+    /// Plugin name.
+    pub name: String,
+
+    /// Plugin version.
+    #[serde(default)]
+    pub version: Option<String>,
+
+    /// Description of the plugin.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Additional command files or directories.
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub commands: Vec<String>,
+
+    /// Additional agent files or directories.
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub agents: Vec<String>,
+
+    /// Hooks config file, or the hooks themselves.
+    #[serde(default)]
+    pub hooks: Option<ComponentConfig>,
+
+    /// MCP config file, or the servers themselves.
+    #[serde(default)]
+    pub mcp_servers: Option<ComponentConfig>,
+}
+
+/// A manifest field holding either a path to a config file or the config.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum ComponentConfig {
+    /// Path relative to the plugin root, e.g. `"./config/hooks.json"`.
+    Path(String),
+    /// The config, in the same shape as the file it replaces.
+    Inline(serde_json::Value),
+}
+
+fn one_or_many<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(path) => vec![path],
+        OneOrMany::Many(paths) => paths,
+    })
+}
+
+/// Parses a `plugin.json` file.
+///
+/// # Errors
+///
+/// Returns [`Error::Schema`](crate::Error::Schema) listing every violation
+/// if the manifest does not have the expected structure.
+///
+/// # Examples
+///
+/// ```
+/// use skills_locate::{ComponentConfig, parse_plugin_manifest};
+///
+/// let manifest = parse_plugin_manifest(r#"{
+///     "name": "deploy-kit",
+///     "commands": ["./extra/release.md"],
+///     "hooks": "./config/hooks.json",
+///     "mcpServers": {"kube": {"command": "kube-mcp"}}
+/// }"#)?;
+/// assert_eq!(manifest.commands, ["./extra/release.md"]);
+/// assert!(matches!(manifest.mcp_servers, Some(ComponentConfig::Inline(_))));
+/// # Ok::<(), skills_locate::Error>(())
+/// ```
+pub fn parse_plugin_manifest(content: &str) -> Result<PluginManifest> {
+    parse_json(SchemaKind::PluginManifest, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_metadata_only_manifest() {
+        let manifest = parse_plugin_manifest(r#"{"name": "review"}"#).unwrap();
+        assert_eq!(manifest.name, "review");
+        assert!(manifest.commands.is_empty());
+        assert!(manifest.agents.is_empty());
+        assert_eq!(manifest.hooks, None);
+        assert_eq!(manifest.mcp_servers, None);
+    }
+
+    #[test]
+    fn parses_component_paths_and_inline_config() {
+        let manifest = parse_plugin_manifest(
+            r#"{
+                "name": "kit",
+                "version": "1.2.0",
+                "commands": "./custom/commands",
+                "agents": ["./agents/reviewer.md", "./more-agents/"],
+                "hooks": {"Stop": [{"hooks": ["notify"]}]},
+                "mcpServers": "./config/mcp.json"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(manifest.version.as_deref(), Some("1.2.0"));
+        assert_eq!(manifest.commands, ["./custom/commands"]);
+        assert_eq!(manifest.agents, ["./agents/reviewer.md", "./more-agents/"]);
+        assert!(matches!(manifest.hooks, Some(ComponentConfig::Inline(_))));
+        assert_eq!(
+            manifest.mcp_servers,
+            Some(ComponentConfig::Path("./config/mcp.json".into()))
+        );
+    }
+
+    #[test]
+    fn invalid_component_fields_are_schema_errors() {
+        let err =
+            parse_plugin_manifest(r#"{"name": "kit", "commands": 3, "hooks": true}"#).unwrap_err();
+        assert!(
+            matches!(err, crate::Error::Schema { ref violations, .. } if violations.len() == 2),
+            "{err:?}"
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Python pyproject.toml MCP server detection.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use harness_locate::mcp::{McpServer, StdioMcpServer};
 
@@ -11,9 +11,9 @@ use harness_locate::mcp::{McpServer, StdioMcpServer};
 /// - `mcp-*` (MCP-prefixed packages)
 /// - `*-mcp` (MCP-suffixed packages)
 ///
-/// Returns a HashMap of server name to McpServer configuration.
-pub fn detect_python_mcp(content: &str) -> HashMap<String, McpServer> {
-    let mut servers = HashMap::new();
+/// Returns a map of server name to McpServer configuration, sorted by name.
+pub fn detect_python_mcp(content: &str) -> BTreeMap<String, McpServer> {
+    let mut servers = BTreeMap::new();
 
     let Ok(doc) = content.parse::<toml::Table>() else {
         return servers;
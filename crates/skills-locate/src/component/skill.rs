@@ -1,36 +1,59 @@
+use std::collections::BTreeMap;
+
+use harness_locate::skill::{SkillHeader, parse_frontmatter, resolve_locale, scan_skill_header};
+use harness_locate::target::Platforms;
 use serde::de::Error as _;
 
 use crate::{Error, Result, SkillDescriptor};
 
+/// Parses a skill's frontmatter into a descriptor.
+///
+/// Frontmatter is read as [`parse_frontmatter`] reads it: YAML between
+/// `---` lines, possibly holding several documents, or TOML between `+++`
+/// lines. Skills whose YAML frontmatter has only `name`, `description`,
+/// `license`, `allowed-tools` and `metadata` keys take the fast path of
+/// [`scan_skill_header`], which reads simple `name` and `description`
+/// values without building a YAML tree. The frontmatter is still checked to be valid YAML, so both paths
+/// return the same result.
+///
+/// A `description` given as a map of locales to text fills
+/// [`SkillDescriptor::descriptions`], and `description` holds the default
+/// translation.
 pub fn parse_skill_descriptor(content: &str) -> Result<SkillDescriptor> {
-    let content = content.replace("\r\n", "\n");
-
-    if !content.starts_with("---\n") {
-        return Err(Error::YamlParse(serde_yaml::Error::custom(
-            "missing frontmatter",
-        )));
-    }
-
-    let after_opener = &content[4..];
-
-    let yaml_end = if after_opener.starts_with("---\n") {
-        0
-    } else if let Some(pos) = after_opener.find("\n---\n") {
-        pos
-    } else if after_opener.ends_with("\n---") {
-        after_opener.len() - 4
-    } else if after_opener == "---" {
-        0
-    } else {
-        return Err(Error::YamlParse(serde_yaml::Error::custom(
-            "unclosed frontmatter",
-        )));
+    let header = scan_skill_header(content).map_err(yaml_error)?;
+    let descriptor = match &header.name {
+        Some(name) if fast_path_applies(&header) => SkillDescriptor {
+            name: name.to_string(),
+            description: header.description.as_deref().map(String::from),
+            descriptions: header.descriptions,
+            triggers: Vec::new(),
+            platforms: Platforms::default(),
+            namespace: None,
+            path: None,
+            hash: None,
+            score: None,
+        },
+        _ => {
+            let Some(mut yaml) = parse_frontmatter(content).map_err(yaml_error)?.yaml else {
+                let reason = if content.starts_with("---") || content.starts_with("+++") {
+                    "unclosed frontmatter"
+                } else {
+                    "missing frontmatter"
+                };
+                return Err(Error::YamlParse(serde_yaml::Error::custom(reason)));
+            };
+            let descriptions = take_localized_description(&mut yaml);
+            let mut descriptor: SkillDescriptor = serde_yaml::from_value(yaml)?;
+            // Scores come from the embedding application, not the skill.
+            descriptor.score = None;
+            if !descriptions.is_empty() {
+                descriptor.description = resolve_locale(&descriptions, None).map(String::from);
+                descriptor.descriptions = descriptions;
+            }
+            descriptor
+        }
     };
 
-    let yaml_content = &after_opener[..yaml_end];
-
-    let descriptor: SkillDescriptor = serde_yaml::from_str(yaml_content)?;
-
     if descriptor.name.is_empty() {
         return Err(Error::YamlParse(serde_yaml::Error::custom(
             "missing required field: name",
@@ -40,6 +63,46 @@ pub fn parse_skill_descriptor(content: &str) -> Result<SkillDescriptor> {
     Ok(descriptor)
 }
 
+/// Top-level frontmatter keys the fast path of [`parse_skill_descriptor`]
+/// handles: `name` and `description`, which it reads, and common keys
+/// that a [`SkillDescriptor`] does not have.
+const FAST_PATH_KEYS: &[&str] = &[
+    "name",
+    "description",
+    "license",
+    "allowed-tools",
+    "metadata",
+];
+
+/// Returns `true` if `header` holds the whole descriptor: every top-level
+/// key is in [`FAST_PATH_KEYS`] and the frontmatter is a single valid YAML
+/// document.
+fn fast_path_applies(header: &SkillHeader<'_>) -> bool {
+    header.keys().all(|key| FAST_PATH_KEYS.contains(&key))
+        && serde_yaml::from_str::<serde::de::IgnoredAny>(header.yaml).is_ok()
+}
+
+/// Removes a locale-map `description` from `yaml` and returns it.
+fn take_localized_description(yaml: &mut serde_yaml::Value) -> BTreeMap<String, String> {
+    let Some(mapping) = yaml.as_mapping_mut() else {
+        return BTreeMap::new();
+    };
+    if !mapping.get("description").is_some_and(|v| v.is_mapping()) {
+        return BTreeMap::new();
+    }
+    mapping
+        .remove("description")
+        .and_then(|v| serde_yaml::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+fn yaml_error(e: harness_locate::Error) -> Error {
+    match e {
+        harness_locate::Error::YamlParse(e) => Error::YamlParse(e),
+        other => Error::YamlParse(serde_yaml::Error::custom(other)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,6 +117,16 @@ mod tests {
         assert_eq!(desc.triggers, vec!["/test"]);
     }
 
+    #[test]
+    fn parse_skill_platforms() {
+        let content = "---\nname: notes\nplatforms: darwin\n---\nBody";
+        let desc = parse_skill_descriptor(content).unwrap();
+        assert_eq!(desc.platforms.to_string(), "macos");
+
+        let content = "---\nname: notes\nplatforms: [beos]\n---\nBody";
+        assert!(parse_skill_descriptor(content).is_err());
+    }
+
     #[test]
     fn parse_minimal_skill() {
         let content = "---\nname: minimal\n---\nBody";
@@ -82,9 +155,64 @@ mod tests {
         assert!(parse_skill_descriptor(content).is_err());
     }
 
+    #[test]
+    fn fast_path_matches_full_parse() {
+        let content = "---\nname: fast\ndescription: \"Quick\"\nlicense: MIT\n---\nBody";
+        let desc = parse_skill_descriptor(content).unwrap();
+        assert_eq!(desc.name, "fast");
+        assert_eq!(desc.description.as_deref(), Some("Quick"));
+
+        let content = "---\nname: folded\ndescription: >\n  Two\n  lines\n---\n";
+        let desc = parse_skill_descriptor(content).unwrap();
+        assert_eq!(desc.description.as_deref(), Some("Two lines"));
+    }
+
+    #[test]
+    fn error_unclosed_frontmatter() {
+        assert!(parse_skill_descriptor("---\nname: open\n").is_err());
+    }
+
     #[test]
     fn error_empty_name() {
         let content = "---\nname: \"\"\n---\nBody";
         assert!(parse_skill_descriptor(content).is_err());
     }
+
+    #[test]
+    fn parse_localized_description() {
+        let content = "---\nname: pdf\ndescription:\n  en: Fill forms\n  fr: Remplir\n---\n";
+        let desc = parse_skill_descriptor(content).unwrap();
+        assert_eq!(desc.description.as_deref(), Some("Fill forms"));
+        assert_eq!(desc.description_for(Some("fr-FR")), Some("Remplir"));
+
+        let content = "---\nname: pdf\ntriggers: [/pdf]\ndescription:\n  fr: Remplir\n---\n";
+        let desc = parse_skill_descriptor(content).unwrap();
+        assert_eq!(desc.description.as_deref(), Some("Remplir"));
+        assert_eq!(desc.description_for(Some("de")), Some("Remplir"));
+        assert_eq!(desc.triggers, ["/pdf"]);
+    }
+
+    #[test]
+    fn parse_toml_and_multi_document_frontmatter() {
+        let content = "+++\nname = \"hugo\"\ndescription = \"From TOML\"\n+++\nBody";
+        let desc = parse_skill_descriptor(content).unwrap();
+        assert_eq!(desc.name, "hugo");
+        assert_eq!(desc.description.as_deref(), Some("From TOML"));
+
+        let content = "---\njust a note\n--- # second\nname: multi\ndescription: Second\n---\nBody";
+        let desc = parse_skill_descriptor(content).unwrap();
+        assert_eq!(desc.name, "multi");
+        assert_eq!(desc.description.as_deref(), Some("Second"));
+    }
+
+    #[test]
+    fn fast_path_only_takes_known_keys() {
+        let content = "---\nname: located\nnamespace: devops\npath: skills/located\n---\n";
+        let desc = parse_skill_descriptor(content).unwrap();
+        assert_eq!(desc.namespace.as_deref(), Some("devops"));
+        assert_eq!(desc.path.as_deref(), Some("skills/located"));
+
+        let content = "---\nname: broken\nlicense: [unclosed\n---\n";
+        assert!(parse_skill_descriptor(content).is_err());
+    }
 }
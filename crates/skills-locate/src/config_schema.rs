@@ -0,0 +1,202 @@
+//! Validation of harness config files against their published JSON Schema.
+//!
+//! [`ConfigResource::schema_url`] names the schema a harness publishes for
+//! its config file. [`validate_against_schema`] fetches it through a
+//! [`SchemaCache`], which keeps a copy on disk so repeated validation works
+//! offline and does not refetch on every run.
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use harness_locate::ConfigResource;
+use harness_locate::text::decode;
+use harness_locate::validation::{ValidationIssue, validate_config_against_schema};
+use serde_json::Value;
+
+use crate::error::Result;
+use crate::fetch::fetch_json;
+
+/// How long a cached schema is used before it is fetched again.
+pub const DEFAULT_SCHEMA_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// An on-disk cache of fetched JSON Schemas, one file per URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaCache {
+    dir: PathBuf,
+    max_age: Duration,
+}
+
+impl SchemaCache {
+    /// Creates a cache storing schemas in `dir`, refetching them after
+    /// [`DEFAULT_SCHEMA_MAX_AGE`].
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            max_age: DEFAULT_SCHEMA_MAX_AGE,
+        }
+    }
+
+    /// Sets how long a cached schema is used before it is fetched again.
+    #[must_use]
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Returns the schema at `url`, from the cache if it is fresh.
+    ///
+    /// A stale copy is used if fetching fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the schema is not cached and cannot be fetched,
+    /// or the cache cannot be written.
+    pub fn get(&self, url: &str) -> Result<Value> {
+        self.get_with(url, fetch_json)
+    }
+
+    fn get_with<F>(&self, url: &str, fetch: F) -> Result<Value>
+    where
+        F: FnOnce(&str) -> Result<Value>,
+    {
+        let path = self.path(url);
+        let cached = std::fs::read(&path).ok().and_then(|bytes| {
+            let age = std::fs::metadata(&path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .unwrap_or(Duration::MAX);
+            let schema: Value = serde_json::from_str(&decode(&bytes).ok()?.text).ok()?;
+            Some((schema, age <= self.max_age))
+        });
+        match cached {
+            Some((schema, true)) => Ok(schema),
+            Some((stale, false)) => Ok(self.store(url, fetch).unwrap_or(stale)),
+            None => self.store(url, fetch),
+        }
+    }
+
+    fn store<F>(&self, url: &str, fetch: F) -> Result<Value>
+    where
+        F: FnOnce(&str) -> Result<Value>,
+    {
+        let schema = fetch(url)?;
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(self.path(url), serde_json::to_vec(&schema)?)?;
+        Ok(schema)
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        let digest = ring::digest::digest(&ring::digest::SHA256, url.as_bytes());
+        let name: String = digest
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+        self.dir.join(format!("{name}.json"))
+    }
+}
+
+/// Validates the config file of `resource` against the schema at its
+/// [`schema_url`](ConfigResource::schema_url), fetched through `cache`.
+///
+/// Returns no issues if the resource has no schema URL. Violations are
+/// reported with the `CODE_SCHEMA_*` codes from
+/// [`harness_locate::validation`].
+///
+/// # Errors
+///
+/// Returns an error if the schema cannot be fetched, or the config file
+/// cannot be read or parsed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use harness_locate::{Harness, HarnessKind, Scope};
+/// use skills_locate::{SchemaCache, validate_against_schema};
+///
+/// let cache = SchemaCache::new("/home/me/.cache/skills-locate/schemas");
+/// let harness = Harness::new(HarnessKind::OpenCode);
+/// if let Some(resource) = harness.mcp(&Scope::Global)? {
+///     for issue in validate_against_schema(&resource, &cache)? {
+///         println!("{}: {}", issue.field, issue.message);
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn validate_against_schema(
+    resource: &ConfigResource,
+    cache: &SchemaCache,
+) -> Result<Vec<ValidationIssue>> {
+    let Some(url) = &resource.schema_url else {
+        return Ok(Vec::new());
+    };
+    let schema = cache.get(url)?;
+    Ok(validate_config_against_schema(resource, &schema)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use std::cell::Cell;
+
+    const URL: &str = "https://example.com/config.json";
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "skills-locate-schema-cache-{}-{name}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn fetches_once_while_fresh() {
+        let dir = cache_dir("fresh");
+        let cache = SchemaCache::new(&dir);
+        let fetches = Cell::new(0);
+        let fetch = |_: &str| {
+            fetches.set(fetches.get() + 1);
+            Ok(serde_json::json!({ "type": "object" }))
+        };
+
+        assert_eq!(cache.get_with(URL, fetch).unwrap()["type"], "object");
+        assert_eq!(cache.get_with(URL, fetch).unwrap()["type"], "object");
+        assert_eq!(fetches.get(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn stale_copy_is_refetched_or_kept_on_failure() {
+        let dir = cache_dir("stale");
+        let cache = SchemaCache::new(&dir).with_max_age(Duration::ZERO);
+        cache
+            .get_with(URL, |_| Ok(serde_json::json!({ "version": 1 })))
+            .unwrap();
+        std::thread::sleep(Duration::from_millis(10));
+
+        let offline = cache.get_with(URL, |_| {
+            Err(Error::Network {
+                status: None,
+                message: "offline".into(),
+            })
+        });
+        assert_eq!(offline.unwrap()["version"], 1);
+        let refreshed = cache.get_with(URL, |_| Ok(serde_json::json!({ "version": 2 })));
+        assert_eq!(refreshed.unwrap()["version"], 2);
+
+        let missing = SchemaCache::new(dir.join("empty")).get_with(URL, |_| {
+            Err(Error::Network {
+                status: None,
+                message: "offline".into(),
+            })
+        });
+        assert!(missing.is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
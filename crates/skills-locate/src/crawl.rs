@@ -0,0 +1,658 @@
+//! Organization-wide discovery across every repository of a GitHub org.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use harness_locate::text::decode;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::discovery::discover_all;
+use crate::error::{Error, Result};
+use crate::fetch::status_error;
+use crate::index::save_json;
+use crate::types::DiscoveryResult;
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const PER_PAGE: usize = 100;
+const DEFAULT_CONCURRENCY: usize = 4;
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_millis(500);
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(15 * 60);
+/// Times a rate-limited API request is retried after the pause.
+const MAX_RATE_LIMIT_RETRIES: usize = 3;
+
+/// Topics that mark a repository as containing skills or plugins.
+pub const DEFAULT_TOPICS: &[&str] = &[
+    "claude-code-plugin",
+    "claude-code-skills",
+    "claude-skills",
+    "agent-skills",
+];
+
+/// Paths whose presence marks a repository as containing skills or plugins.
+const MANIFEST_PATHS: &[&str] = &[".claude-plugin", "skills"];
+
+/// Which repositories of an organization to crawl.
+#[derive(Debug, Clone)]
+pub struct OrgFilters {
+    /// A repository with any of these topics is crawled without probing
+    /// for manifests.
+    pub topics: Vec<String>,
+    /// Probe repositories without a matching topic for a `.claude-plugin`
+    /// or `skills` directory.
+    pub probe_manifests: bool,
+    pub include_forks: bool,
+    pub include_archived: bool,
+    /// Only crawl repositories whose name contains this string.
+    pub name_contains: Option<String>,
+}
+
+impl Default for OrgFilters {
+    fn default() -> Self {
+        Self {
+            topics: DEFAULT_TOPICS.iter().map(|t| t.to_string()).collect(),
+            probe_manifests: true,
+            include_forks: false,
+            include_archived: false,
+            name_contains: None,
+        }
+    }
+}
+
+impl OrgFilters {
+    /// Returns whether the repository passes the fork, archive, and name
+    /// filters.
+    fn admits(&self, repo: &OrgRepo) -> bool {
+        (self.include_forks || !repo.fork)
+            && (self.include_archived || !repo.archived)
+            && self
+                .name_contains
+                .as_deref()
+                .is_none_or(|needle| repo.name.contains(needle))
+    }
+
+    fn has_topic(&self, repo: &OrgRepo) -> bool {
+        repo.topics.iter().any(|t| self.topics.contains(t))
+    }
+}
+
+/// A repository as listed by the GitHub organization repositories API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrgRepo {
+    pub name: String,
+    pub html_url: String,
+    #[serde(default = "default_branch")]
+    pub default_branch: String,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub fork: bool,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+impl OrgRepo {
+    fn tree_url(&self) -> String {
+        format!("{}/tree/{}", self.html_url, self.default_branch)
+    }
+}
+
+/// Progress of an organization crawl, persisted between runs.
+///
+/// Repositories in `completed` are skipped on the next run; failed ones
+/// are retried.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CrawlState {
+    #[serde(default)]
+    pub completed: BTreeMap<String, DiscoveryResult>,
+    #[serde(default)]
+    pub failed: BTreeMap<String, String>,
+}
+
+impl CrawlState {
+    /// Loads state from `path`, or returns empty state if the file does
+    /// not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_str(&decode(&bytes)?.text)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes state to `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save_json(path, self)
+    }
+}
+
+/// Spaces requests at least `interval` apart across all threads.
+#[derive(Debug)]
+pub struct RateLimiter {
+    interval: Duration,
+    next: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Blocks until the caller may send its next request.
+    pub fn wait(&self) {
+        let slot = {
+            let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+            let slot = (*next).max(Instant::now());
+            *next = slot + self.interval;
+            slot
+        };
+        let now = Instant::now();
+        if slot > now {
+            thread::sleep(slot - now);
+        }
+    }
+
+    /// Delays every caller until `until`, e.g. when the API quota is spent.
+    fn pause_until(&self, until: Instant) {
+        let mut next = self.next.lock().unwrap_or_else(|e| e.into_inner());
+        *next = (*next).max(until);
+    }
+}
+
+/// Result of [`discover_org`].
+#[derive(Debug, Default)]
+pub struct OrgDiscovery {
+    /// Discovery results keyed by repository name, including repositories
+    /// completed in a previous run.
+    pub results: BTreeMap<String, DiscoveryResult>,
+    /// Repositories whose discovery failed in this run.
+    pub errors: Vec<(String, Error)>,
+    /// Repositories listed but not identified as containing skills or plugins.
+    pub skipped: Vec<String>,
+}
+
+/// Crawls every repository of a GitHub organization.
+///
+/// Requests go through a shared [`RateLimiter`], at most `concurrency`
+/// repositories are downloaded at once, and progress is saved to the
+/// state file after each repository when one is configured.
+#[derive(Debug, Clone)]
+pub struct OrgCrawler {
+    api_base_url: String,
+    token: Option<String>,
+    concurrency: usize,
+    min_interval: Duration,
+    state_path: Option<PathBuf>,
+}
+
+impl Default for OrgCrawler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OrgCrawler {
+    pub fn new() -> Self {
+        Self {
+            api_base_url: GITHUB_API_URL.to_string(),
+            token: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            min_interval: DEFAULT_MIN_INTERVAL,
+            state_path: None,
+        }
+    }
+
+    pub fn with_api_base_url(mut self, url: impl Into<String>) -> Self {
+        self.api_base_url = url.into();
+        self
+    }
+
+    /// Authenticates API requests, raising GitHub's rate limit.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Sets the maximum number of repositories processed at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the minimum delay between any two requests.
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Persists progress to `path` so a re-run skips completed repositories.
+    pub fn with_state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_path = Some(path.into());
+        self
+    }
+
+    /// Lists the organization's repositories and runs discovery on those
+    /// that contain skills or plugins.
+    pub fn discover(&self, github_org: &str, filters: &OrgFilters) -> Result<OrgDiscovery> {
+        let limiter = RateLimiter::new(self.min_interval);
+        let agent = self.agent();
+        let mut state = match &self.state_path {
+            Some(path) => CrawlState::load(path)?,
+            None => CrawlState::default(),
+        };
+
+        let mut skipped = Vec::new();
+        let mut pending = Vec::new();
+        for repo in self.list_repos(&agent, &limiter, github_org)? {
+            if !filters.admits(&repo) {
+                continue;
+            }
+            if state.completed.contains_key(&repo.name) {
+                continue;
+            }
+            let identified = filters.has_topic(&repo)
+                || (filters.probe_manifests
+                    && self.has_manifest(&agent, &limiter, github_org, &repo)?);
+            if identified {
+                pending.push(repo);
+            } else {
+                skipped.push(repo.name);
+            }
+        }
+
+        let errors = run_queue(
+            pending,
+            self.concurrency,
+            &mut state,
+            |state| match &self.state_path {
+                Some(path) => state.save(path),
+                None => Ok(()),
+            },
+            |repo| {
+                limiter.wait();
+                discover_all(&repo.tree_url())
+            },
+        )?;
+
+        Ok(OrgDiscovery {
+            results: state.completed,
+            errors,
+            skipped,
+        })
+    }
+
+    fn agent(&self) -> ureq::Agent {
+        ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into()
+    }
+
+    fn list_repos(
+        &self,
+        agent: &ureq::Agent,
+        limiter: &RateLimiter,
+        org: &str,
+    ) -> Result<Vec<OrgRepo>> {
+        let mut repos = Vec::new();
+        for page in 1.. {
+            let url = format!(
+                "{}/orgs/{org}/repos?type=public&per_page={PER_PAGE}&page={page}",
+                self.api_base_url
+            );
+            let batch: Vec<OrgRepo> =
+                self.get(agent, limiter, &url)?
+                    .ok_or_else(|| Error::NotFound {
+                        what: format!("GitHub organization {org}"),
+                    })?;
+            let done = batch.len() < PER_PAGE;
+            repos.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(repos)
+    }
+
+    fn has_manifest(
+        &self,
+        agent: &ureq::Agent,
+        limiter: &RateLimiter,
+        org: &str,
+        repo: &OrgRepo,
+    ) -> Result<bool> {
+        for path in MANIFEST_PATHS {
+            let url = format!(
+                "{}/repos/{org}/{}/contents/{path}?ref={}",
+                self.api_base_url, repo.name, repo.default_branch
+            );
+            if self
+                .get::<serde_json::Value>(agent, limiter, &url)?
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Sends a rate-limited API request, returning `None` on 404.
+    ///
+    /// When GitHub reports the quota as spent, every thread is paused
+    /// until the reset time and the request is retried, up to
+    /// [`MAX_RATE_LIMIT_RETRIES`] times before the error is returned.
+    fn get<T: DeserializeOwned>(
+        &self,
+        agent: &ureq::Agent,
+        limiter: &RateLimiter,
+        url: &str,
+    ) -> Result<Option<T>> {
+        let mut retries = 0;
+        loop {
+            match self.get_once(agent, limiter, url) {
+                Err(Error::RateLimited { reset, .. }) if retries < MAX_RATE_LIMIT_RETRIES => {
+                    if let Some(reset) = reset {
+                        limiter.pause_until(Instant::now() + reset_delay(reset, SystemTime::now()));
+                    }
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends one API request, pausing `limiter` if the quota is spent.
+    fn get_once<T: DeserializeOwned>(
+        &self,
+        agent: &ureq::Agent,
+        limiter: &RateLimiter,
+        url: &str,
+    ) -> Result<Option<T>> {
+        limiter.wait();
+        let mut request = agent
+            .get(url)
+            .header("Accept", "application/vnd.github+json");
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", &format!("Bearer {token}"));
+        }
+        let mut response = request.call().map_err(|e| Error::Network {
+            status: None,
+            message: e.to_string(),
+        })?;
+
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+        };
+        if header("x-ratelimit-remaining") == Some(0)
+            && let Some(reset) = header("x-ratelimit-reset")
+        {
+            limiter.pause_until(Instant::now() + reset_delay(reset, SystemTime::now()));
+        }
+
+        match response.status().as_u16() {
+            404 => Ok(None),
+            200..=299 => {
+                let bytes = response
+                    .body_mut()
+                    .read_to_vec()
+                    .map_err(|e| Error::Network {
+                        status: None,
+                        message: format!("read error: {e}"),
+                    })?;
+                serde_json::from_slice(&bytes)
+                    .map(Some)
+                    .map_err(|e| Error::Parse {
+                        path: url.to_string(),
+                        message: e.to_string(),
+                    })
+            }
+            code => Err(status_error(url, code, response.headers())),
+        }
+    }
+}
+
+/// Lists all repositories of `github_org` and runs [`discover_all`] on the
+/// ones that contain skills or plugins, using [`OrgCrawler`]'s defaults.
+pub fn discover_org(github_org: &str, filters: &OrgFilters) -> Result<OrgDiscovery> {
+    OrgCrawler::new().discover(github_org, filters)
+}
+
+/// Time to wait for a rate limit reset given as a Unix timestamp, capped
+/// at [`MAX_RATE_LIMIT_WAIT`].
+fn reset_delay(reset_epoch_secs: u64, now: SystemTime) -> Duration {
+    let now = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+    Duration::from_secs(reset_epoch_secs)
+        .saturating_sub(now)
+        .min(MAX_RATE_LIMIT_WAIT)
+}
+
+/// Runs `discover` over `repos` on up to `concurrency` threads, recording
+/// each outcome in `state` and calling `save` after every repository.
+fn run_queue<D, S>(
+    repos: Vec<OrgRepo>,
+    concurrency: usize,
+    state: &mut CrawlState,
+    save: S,
+    discover: D,
+) -> Result<Vec<(String, Error)>>
+where
+    D: Fn(&OrgRepo) -> Result<DiscoveryResult> + Sync,
+    S: Fn(&CrawlState) -> Result<()> + Sync,
+{
+    let workers = concurrency.max(1).min(repos.len());
+    let queue = Mutex::new(VecDeque::from(repos));
+    let shared = Mutex::new((std::mem::take(state), Vec::new(), None));
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                loop {
+                    let Some(repo) = queue.lock().unwrap_or_else(|e| e.into_inner()).pop_front()
+                    else {
+                        break;
+                    };
+                    let outcome = discover(&repo);
+
+                    let mut guard = shared.lock().unwrap_or_else(|e| e.into_inner());
+                    let (state, errors, save_error) = &mut *guard;
+                    match outcome {
+                        Ok(result) => {
+                            state.failed.remove(&repo.name);
+                            state.completed.insert(repo.name, result);
+                        }
+                        Err(e) => {
+                            state.failed.insert(repo.name.clone(), e.to_string());
+                            errors.push((repo.name, e));
+                        }
+                    }
+                    if let Err(e) = save(state) {
+                        *save_error = Some(e);
+                        queue.lock().unwrap_or_else(|e| e.into_inner()).clear();
+                    }
+                }
+            });
+        }
+    });
+
+    let (final_state, errors, save_error) = shared.into_inner().unwrap_or_else(|e| e.into_inner());
+    *state = final_state;
+    match save_error {
+        Some(e) => Err(e),
+        None => Ok(errors),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repo(name: &str) -> OrgRepo {
+        OrgRepo {
+            name: name.to_string(),
+            html_url: format!("https://github.com/acme/{name}"),
+            default_branch: "main".to_string(),
+            topics: vec![],
+            fork: false,
+            archived: false,
+        }
+    }
+
+    #[test]
+    fn org_repo_deserializes_from_api() {
+        let repos: Vec<OrgRepo> = serde_json::from_str(
+            r#"[{
+                "name": "skills",
+                "html_url": "https://github.com/acme/skills",
+                "default_branch": "trunk",
+                "topics": ["claude-skills"],
+                "fork": false,
+                "archived": false,
+                "stargazers_count": 12
+            }]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            repos[0].tree_url(),
+            "https://github.com/acme/skills/tree/trunk"
+        );
+        assert_eq!(repos[0].topics, vec!["claude-skills"]);
+    }
+
+    #[test]
+    fn filters_exclude_forks_archived_and_names() {
+        let filters = OrgFilters {
+            name_contains: Some("skill".to_string()),
+            ..OrgFilters::default()
+        };
+        assert!(filters.admits(&repo("team-skills")));
+        assert!(!filters.admits(&repo("website")));
+
+        let mut fork = repo("skills-fork");
+        fork.fork = true;
+        assert!(!filters.admits(&fork));
+
+        let mut archived = repo("old-skills");
+        archived.archived = true;
+        assert!(!filters.admits(&archived));
+
+        let mut tagged = repo("x");
+        tagged.topics = vec!["agent-skills".to_string()];
+        assert!(filters.has_topic(&tagged));
+        assert!(!filters.has_topic(&repo("x")));
+    }
+
+    #[test]
+    fn rate_limiter_spaces_requests() {
+        let limiter = RateLimiter::new(Duration::from_millis(20));
+        let start = Instant::now();
+        for _ in 0..3 {
+            limiter.wait();
+        }
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[test]
+    fn reset_delay_is_capped() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_000);
+        assert_eq!(reset_delay(1_030, now), Duration::from_secs(30));
+        assert_eq!(reset_delay(900, now), Duration::ZERO);
+        assert_eq!(reset_delay(u64::MAX / 2, now), MAX_RATE_LIMIT_WAIT);
+    }
+
+    #[test]
+    fn run_queue_records_results_and_failures() {
+        let mut state = CrawlState::default();
+        state
+            .failed
+            .insert("flaky".to_string(), "earlier error".to_string());
+        let saves = Mutex::new(0);
+
+        let errors = run_queue(
+            vec![repo("a"), repo("b"), repo("flaky"), repo("broken")],
+            2,
+            &mut state,
+            |_| {
+                *saves.lock().unwrap() += 1;
+                Ok(())
+            },
+            |repo| match repo.name.as_str() {
+                "broken" => Err(Error::NotFound {
+                    what: "marketplace.json".to_string(),
+                }),
+                _ => Ok(DiscoveryResult::from_plugins(vec![])),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(*saves.lock().unwrap(), 4);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "broken");
+        assert_eq!(
+            state.completed.keys().collect::<Vec<_>>(),
+            vec!["a", "b", "flaky"]
+        );
+        assert_eq!(state.failed.keys().collect::<Vec<_>>(), vec!["broken"]);
+    }
+
+    #[test]
+    fn crawl_state_roundtrip_and_missing_file() {
+        let path =
+            std::env::temp_dir().join(format!("skills-locate-crawl-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(CrawlState::load(&path).unwrap(), CrawlState::default());
+
+        let mut state = CrawlState::default();
+        state
+            .completed
+            .insert("skills".to_string(), DiscoveryResult::from_plugins(vec![]));
+        state.save(&path).unwrap();
+        assert_eq!(CrawlState::load(&path).unwrap(), state);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn get_retries_after_rate_limit() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let reset = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let limited = format!(
+            "HTTP/1.1 403 Forbidden\r\nx-ratelimit-remaining: 0\r\nx-ratelimit-reset: {reset}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        );
+        let ok = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n[]";
+        std::thread::spawn(move || {
+            for response in [limited.as_str(), limited.as_str(), ok] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = [0u8; 4096];
+                let _ = stream.read(&mut request);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        let crawler = OrgCrawler::new().with_api_base_url(format!("http://{addr}"));
+        let limiter = RateLimiter::new(Duration::ZERO);
+        let repos = crawler
+            .list_repos(&crawler.agent(), &limiter, "acme")
+            .unwrap();
+        assert!(repos.is_empty());
+    }
+}
@@ -0,0 +1,258 @@
+//! Resolving skills found in more than one source to a single copy.
+//!
+//! The same skill often shows up several times: listed by a marketplace
+//! and detected in its source repository, or read at a pinned commit and
+//! at a branch. [`DiscoveryResult::dedupe`] keeps the copy the
+//! [`DedupePolicy`] prefers and records the others as
+//! [`SuppressedSkill`]s.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{DiscoveryResult, PluginOrigin, SkillDescriptor};
+
+/// A property a copy of a skill is preferred for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Preference {
+    /// Listed in a `marketplace.json`.
+    Marketplace,
+    /// Detected from a repository's layout, without a marketplace.
+    Repository,
+    /// Read at a full commit SHA.
+    Pinned,
+    /// Read at a branch or tag.
+    Floating,
+}
+
+impl Preference {
+    fn satisfied_by(self, origin: Option<&PluginOrigin>) -> bool {
+        origin.is_some_and(|origin| match self {
+            Self::Marketplace => origin.marketplace,
+            Self::Repository => !origin.marketplace,
+            Self::Pinned => origin.is_pinned(),
+            Self::Floating => !origin.is_pinned(),
+        })
+    }
+}
+
+/// Which copy of a duplicated skill to keep.
+///
+/// Copies are compared by each [`Preference`] in order, the first that
+/// tells them apart deciding; copies no preference separates keep
+/// discovery order. The default prefers marketplace listings, then pinned
+/// commits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DedupePolicy {
+    order: Vec<Preference>,
+}
+
+impl Default for DedupePolicy {
+    fn default() -> Self {
+        Self::new([Preference::Marketplace, Preference::Pinned])
+    }
+}
+
+impl DedupePolicy {
+    /// Creates a policy comparing copies by `order`.
+    #[must_use]
+    pub fn new(order: impl IntoIterator<Item = Preference>) -> Self {
+        Self {
+            order: order.into_iter().collect(),
+        }
+    }
+
+    /// The preferences, most important first.
+    #[must_use]
+    pub fn order(&self) -> &[Preference] {
+        &self.order
+    }
+
+    /// One flag per preference, compared lexicographically.
+    fn rank(&self, origin: Option<&PluginOrigin>) -> Vec<bool> {
+        self.order
+            .iter()
+            .map(|preference| preference.satisfied_by(origin))
+            .collect()
+    }
+}
+
+/// A skill left out of a [`DiscoveryResult`] as a duplicate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct SuppressedSkill {
+    /// The suppressed copy.
+    pub skill: SkillDescriptor,
+    /// Name of the plugin it was found in.
+    pub plugin: String,
+    /// Where that plugin was discovered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<PluginOrigin>,
+    /// Name of the plugin whose copy was kept.
+    pub kept_plugin: String,
+    /// Where that plugin was discovered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kept_origin: Option<PluginOrigin>,
+}
+
+/// A copy of a skill: plugin index and skill index within it.
+type SkillAt = (usize, usize);
+
+impl DiscoveryResult {
+    /// Keeps one copy of every skill found more than once, preferring
+    /// copies as `policy` orders them.
+    ///
+    /// Two skills are the same if they share a name and either come from
+    /// the same repository or have the same content hash. Dropped copies
+    /// are removed from their plugins and listed in
+    /// [`suppressed`](Self::suppressed); plugins are kept even if left
+    /// without skills.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skills_locate::{DedupePolicy, DiscoveryResult, Preference};
+    ///
+    /// let result = DiscoveryResult::from_plugins(Vec::new())
+    ///     .dedupe(&DedupePolicy::new([Preference::Pinned, Preference::Marketplace]));
+    /// assert!(result.suppressed.is_empty());
+    /// ```
+    #[must_use]
+    pub fn dedupe(self, policy: &DedupePolicy) -> Self {
+        let mut groups: Vec<Vec<SkillAt>> = Vec::new();
+        for (p, plugin) in self.plugins.iter().enumerate() {
+            for (s, skill) in plugin.skills.iter().enumerate() {
+                let same = |&(gp, gs): &SkillAt| {
+                    let other = &self.plugins[gp];
+                    same_skill(
+                        skill,
+                        plugin.origin.as_ref(),
+                        &other.skills[gs],
+                        other.origin.as_ref(),
+                    )
+                };
+                match groups.iter_mut().find(|group| group.iter().any(same)) {
+                    Some(group) => group.push((p, s)),
+                    None => groups.push(vec![(p, s)]),
+                }
+            }
+        }
+
+        let mut dropped: Vec<(SkillAt, SkillAt)> = Vec::new();
+        for group in groups.into_iter().filter(|group| group.len() > 1) {
+            let rank = |&(p, _): &SkillAt| policy.rank(self.plugins[p].origin.as_ref());
+            // `max_by_key` keeps the last of equal copies; reverse so
+            // discovery order breaks ties.
+            let kept = *group
+                .iter()
+                .rev()
+                .max_by_key(|copy| rank(copy))
+                .expect("group is not empty");
+            dropped.extend(
+                group
+                    .into_iter()
+                    .filter(|&copy| copy != kept)
+                    .map(|copy| (copy, kept)),
+            );
+        }
+
+        let mut suppressed = self.suppressed;
+        suppressed.extend(dropped.iter().map(|&((p, s), (kp, _))| SuppressedSkill {
+            skill: self.plugins[p].skills[s].clone(),
+            plugin: self.plugins[p].name.clone(),
+            origin: self.plugins[p].origin.clone(),
+            kept_plugin: self.plugins[kp].name.clone(),
+            kept_origin: self.plugins[kp].origin.clone(),
+        }));
+
+        let mut plugins = self.plugins;
+        for (p, plugin) in plugins.iter_mut().enumerate() {
+            let mut s = 0;
+            plugin.skills.retain(|_| {
+                let keep = !dropped.iter().any(|&(copy, _)| copy == (p, s));
+                s += 1;
+                keep
+            });
+        }
+        Self {
+            suppressed,
+            ..Self::from_plugins(plugins)
+        }
+    }
+}
+
+fn same_skill(
+    a: &SkillDescriptor,
+    a_origin: Option<&PluginOrigin>,
+    b: &SkillDescriptor,
+    b_origin: Option<&PluginOrigin>,
+) -> bool {
+    let same_repo = a_origin.zip(b_origin).is_some_and(|(a, b)| a.same_repo(b));
+    let same_hash = a.hash.is_some() && a.hash == b.hash;
+    a.name == b.name && (same_repo || same_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PluginDescriptor;
+
+    fn plugin(name: &str, repo: &str, git_ref: &str, marketplace: bool) -> PluginDescriptor {
+        let mut plugin: PluginDescriptor =
+            serde_json::from_value(serde_json::json!({ "name": name })).unwrap();
+        plugin.origin = Some(PluginOrigin {
+            repo: repo.to_string(),
+            git_ref: git_ref.to_string(),
+            marketplace,
+        });
+        plugin
+    }
+
+    fn skill(name: &str, hash: &str) -> SkillDescriptor {
+        serde_json::from_value(serde_json::json!({ "name": name, "hash": hash })).unwrap()
+    }
+
+    #[test]
+    fn keeps_preferred_copy_and_records_the_rest() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let mut repo = plugin("skills", "https://github.com/acme/skills", "main", false);
+        repo.skills = vec![skill("deploy", "sha256:a"), skill("lint", "sha256:l")];
+        let mut pinned = plugin("pinned", "https://github.com/Acme/Skills", sha, false);
+        pinned.skills = vec![skill("deploy", "sha256:b")];
+        let mut market = plugin("market", "https://github.com/acme/market", "main", true);
+        market.skills = vec![skill("deploy", "sha256:a"), skill("lint", "sha256:other")];
+        let plugins = vec![repo, pinned, market];
+
+        let result =
+            DiscoveryResult::from_plugins(plugins.clone()).dedupe(&DedupePolicy::default());
+        let kept: Vec<_> = result
+            .plugins
+            .iter()
+            .flat_map(|p| {
+                p.skills
+                    .iter()
+                    .map(move |s| (p.name.as_str(), s.name.as_str()))
+            })
+            .collect();
+        assert_eq!(
+            kept,
+            [("skills", "lint"), ("market", "deploy"), ("market", "lint")]
+        );
+        assert_eq!(result.all_skills.len(), 3);
+        let suppressed: Vec<_> = result
+            .suppressed
+            .iter()
+            .map(|s| (s.plugin.as_str(), s.kept_plugin.as_str()))
+            .collect();
+        assert_eq!(suppressed, [("skills", "market"), ("pinned", "market")]);
+
+        let result = DiscoveryResult::from_plugins(plugins).dedupe(&DedupePolicy::new([
+            Preference::Pinned,
+            Preference::Repository,
+        ]));
+        assert_eq!(result.plugins[1].skills[0].name, "deploy");
+        assert!(result.plugins[2].skills.iter().all(|s| s.name == "lint"));
+        assert_eq!(result.suppressed.len(), 2);
+    }
+}
@@ -0,0 +1,294 @@
+//! Where a skill is deployed across harnesses and scopes.
+//!
+//! [`deployment_matrix`] looks for a skill by name in the skills directory
+//! of every installed harness, globally and in a project, and reports
+//! whether each copy was edited since it was installed (see
+//! [`ProvenanceManifest`](crate::ProvenanceManifest)) and its
+//! [`content_hash`], so sync tools can tell which copies have diverged.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use harness_locate::{DirectoryStructure, Harness, ResourceKind, Scope};
+
+use crate::error::Result;
+use crate::install::skip_reason;
+use crate::provenance::{Drift, detect_drift};
+use crate::update::content_hash;
+
+/// The state of a skill in one harness and scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DeploymentStatus {
+    /// The skill is present, unmodified since it was installed or without
+    /// a provenance record.
+    Deployed,
+    /// The skill is present and was edited after it was installed.
+    Modified,
+    /// The harness has no skill with this name in the scope.
+    Missing,
+    /// The harness has no skills directory for the scope.
+    Unsupported,
+}
+
+/// A skill's state in one harness and scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deployment {
+    /// Name of the harness.
+    pub harness: String,
+    /// The scope that was checked.
+    pub scope: Scope,
+    /// Whether and how the skill is deployed.
+    pub status: DeploymentStatus,
+    /// The skill directory or file, if present.
+    pub path: Option<PathBuf>,
+    /// [`content_hash`] of the skill, if present.
+    pub hash: Option<String>,
+}
+
+/// A skill's state in every harness and scope checked, from
+/// [`deployment_matrix`].
+///
+/// Displays as a single line grouped by harness, e.g.
+/// `Claude Code: global ✓, project ✗; OpenCode: project (modified)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeploymentMatrix {
+    /// The skill name.
+    pub skill: String,
+    /// One entry per harness and scope, in harness then scope order.
+    pub deployments: Vec<Deployment>,
+}
+
+impl DeploymentMatrix {
+    /// Returns the skill's state in the harness named `harness` and `scope`.
+    #[must_use]
+    pub fn get(&self, harness: &str, scope: &Scope) -> Option<&Deployment> {
+        self.deployments
+            .iter()
+            .find(|d| d.harness == harness && &d.scope == scope)
+    }
+
+    /// Returns the deployments where the skill is present.
+    pub fn present(&self) -> impl Iterator<Item = &Deployment> {
+        self.deployments.iter().filter(|d| d.hash.is_some())
+    }
+
+    /// Returns `true` if every present copy of the skill has the same
+    /// content.
+    #[must_use]
+    pub fn is_in_sync(&self) -> bool {
+        let mut hashes = self.present().filter_map(|d| d.hash.as_deref());
+        let first = hashes.next();
+        hashes.all(|hash| Some(hash) == first)
+    }
+}
+
+impl fmt::Display for DeploymentMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut harnesses: Vec<(&str, Vec<String>)> = Vec::new();
+        for deployment in &self.deployments {
+            let cell = match deployment.status {
+                DeploymentStatus::Deployed => "✓",
+                DeploymentStatus::Modified => "(modified)",
+                DeploymentStatus::Missing => "✗",
+                DeploymentStatus::Unsupported => continue,
+            };
+            let scope = match &deployment.scope {
+                Scope::Global => "global".to_string(),
+                Scope::Project(_) => "project".to_string(),
+                Scope::Custom(path) => path.display().to_string(),
+            };
+            let cell = format!("{scope} {cell}");
+            match harnesses.last_mut() {
+                Some((name, cells)) if *name == deployment.harness => cells.push(cell),
+                _ => harnesses.push((&deployment.harness, vec![cell])),
+            }
+        }
+        for (i, (name, cells)) in harnesses.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{name}: {}", cells.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks every installed harness for the skill named `skill_name`,
+/// globally and, if given, in the project at `project_root`.
+///
+/// See [`deployment_matrix_for`].
+///
+/// # Errors
+///
+/// Returns an error if the installed harnesses cannot be determined, or a
+/// skills directory, skill, or provenance file cannot be read.
+///
+/// # Examples
+///
+/// ```no_run
+/// use skills_locate::deployment_matrix;
+///
+/// let matrix = deployment_matrix("deploy", Some(".".as_ref()))?;
+/// println!("{matrix}");
+/// if !matrix.is_in_sync() {
+///     println!("copies of deploy differ");
+/// }
+/// # Ok::<(), skills_locate::Error>(())
+/// ```
+pub fn deployment_matrix(
+    skill_name: &str,
+    project_root: Option<&Path>,
+) -> Result<DeploymentMatrix> {
+    let mut scopes = vec![Scope::Global];
+    if let Some(root) = project_root {
+        scopes.push(Scope::Project(root.to_path_buf()));
+    }
+    deployment_matrix_for(&Harness::installed()?, skill_name, &scopes)
+}
+
+/// Checks each of `harnesses` in each of `scopes` for the skill named
+/// `skill_name`.
+///
+/// A skill is found by its directory name, or its file stem for harnesses
+/// with flat skill files. Scopes a harness has no skills directory for are
+/// [`Unsupported`](DeploymentStatus::Unsupported).
+///
+/// # Errors
+///
+/// Returns an error if a skills directory, skill, or provenance file
+/// cannot be read.
+pub fn deployment_matrix_for(
+    harnesses: &[Harness],
+    skill_name: &str,
+    scopes: &[Scope],
+) -> Result<DeploymentMatrix> {
+    let mut deployments = Vec::new();
+    for harness in harnesses {
+        for scope in scopes {
+            let (status, path, hash) = match find_skill(harness, scope, skill_name)? {
+                None => (DeploymentStatus::Unsupported, None, None),
+                Some(None) => (DeploymentStatus::Missing, None, None),
+                Some(Some(path)) => {
+                    let status = match detect_drift(&path)? {
+                        Drift::Modified { .. } => DeploymentStatus::Modified,
+                        _ => DeploymentStatus::Deployed,
+                    };
+                    let hash = content_hash(&path)?;
+                    (status, Some(path), Some(hash))
+                }
+            };
+            deployments.push(Deployment {
+                harness: harness.name().to_string(),
+                scope: scope.clone(),
+                status,
+                path,
+                hash,
+            });
+        }
+    }
+    Ok(DeploymentMatrix {
+        skill: skill_name.to_string(),
+        deployments,
+    })
+}
+
+/// The skill's directory or file, `Some(None)` if it is missing, or `None`
+/// if the harness has no skills directory for `scope`.
+fn find_skill(harness: &Harness, scope: &Scope, name: &str) -> Result<Option<Option<PathBuf>>> {
+    let dir = match harness.resource(ResourceKind::Skills, scope) {
+        Ok(Some(dir)) => dir,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            skip_reason(e)?;
+            return Ok(None);
+        }
+    };
+    let nested = matches!(dir.structure, DirectoryStructure::Nested { .. });
+    let found = dir.enumerate()?.into_iter().find_map(|file| {
+        let path = if nested {
+            file.parent()?.to_path_buf()
+        } else {
+            file
+        };
+        let stem = if nested {
+            path.file_name()
+        } else {
+            path.file_stem()
+        };
+        (stem? == name).then_some(path)
+    });
+    Ok(Some(found))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provenance::{Provenance, ProvenanceManifest};
+    use harness_locate::HarnessKind;
+    use harness_locate::platform::PathContext;
+
+    fn write_skill(dir: &Path, body: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(dir.join("SKILL.md"), body).unwrap();
+    }
+
+    #[test]
+    fn reports_deployed_missing_and_modified_copies() {
+        let root =
+            std::env::temp_dir().join(format!("skills-locate-deployment-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let project = Scope::Project(root.join("project"));
+        let scopes = [Scope::Global, project.clone()];
+        let harnesses = [
+            Harness::new(HarnessKind::ClaudeCode),
+            Harness::new(HarnessKind::OpenCode),
+        ];
+
+        let matrix = PathContext::fake(&root).scope(|| {
+            let claude = harnesses[0].skills(&Scope::Global).unwrap().unwrap().path;
+            write_skill(&claude.join("deploy"), "---\nname: deploy\n---\nShip it.");
+
+            let opencode = harnesses[1].skills(&project).unwrap().unwrap().path;
+            write_skill(&opencode.join("deploy"), "---\nname: deploy\n---\nShip it.");
+            let mut manifest = ProvenanceManifest::load(&opencode).unwrap();
+            manifest.insert(
+                "deploy",
+                Provenance {
+                    repo: "https://github.com/acme/skills".into(),
+                    git_ref: "main".into(),
+                    path: "skills/deploy".into(),
+                    hash: content_hash(&opencode.join("deploy")).unwrap(),
+                    installed_at: 0,
+                },
+            );
+            manifest.save().unwrap();
+            write_skill(
+                &opencode.join("deploy"),
+                "---\nname: deploy\n---\nShip it now.",
+            );
+
+            deployment_matrix_for(&harnesses, "deploy", &scopes).unwrap()
+        });
+
+        let status = |harness: &str, scope: &Scope| matrix.get(harness, scope).unwrap().status;
+        assert_eq!(
+            status("Claude Code", &Scope::Global),
+            DeploymentStatus::Deployed
+        );
+        assert_eq!(status("Claude Code", &project), DeploymentStatus::Missing);
+        assert_eq!(
+            status("OpenCode", &Scope::Global),
+            DeploymentStatus::Missing
+        );
+        assert_eq!(status("OpenCode", &project), DeploymentStatus::Modified);
+        assert_eq!(matrix.present().count(), 2);
+        assert!(!matrix.is_in_sync());
+        assert_eq!(
+            matrix.to_string(),
+            "Claude Code: global ✓, project ✗; OpenCode: global ✗, project (modified)"
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -3,6 +3,7 @@
 use std::collections::HashMap;
 
 use harness_locate::mcp::McpServer;
+use semver::{Version, VersionReq};
 
 use crate::component::{detect_npm_mcp, detect_python_mcp, parse_manifest, parse_mcp_json};
 
@@ -13,6 +14,9 @@ pub struct DetectedMcp {
     pub source: DetectionSource,
     pub required_env_vars: Vec<String>,
     pub confidence: DetectionConfidence,
+    /// The npm or PyPI package the server runs from, when detected from
+    /// `package.json` or `pyproject.toml`.
+    pub package: Option<PackageMetadata>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -30,6 +34,67 @@ pub enum DetectionConfidence {
     High,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PackageRegistry {
+    Npm,
+    PyPI,
+}
+
+/// Package information for a detected MCP server.
+///
+/// Detection fills in `registry`, `name`, and `current_version` offline;
+/// the remaining fields are populated by
+/// [`enrich_detected`](crate::enrich_detected) with the `fetch` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageMetadata {
+    pub registry: PackageRegistry,
+    pub name: String,
+    pub current_version: Option<String>,
+    pub latest_version: Option<String>,
+    pub description: Option<String>,
+    pub repository_url: Option<String>,
+}
+
+impl PackageMetadata {
+    pub fn new(registry: PackageRegistry, name: impl Into<String>) -> Self {
+        Self {
+            registry,
+            name: name.into(),
+            current_version: None,
+            latest_version: None,
+            description: None,
+            repository_url: None,
+        }
+    }
+
+    /// Returns `Some(true)` if the latest published version is newer than
+    /// the detected one, or `None` if either version is unknown or not
+    /// semver.
+    ///
+    /// A range such as `^1.0.0` is a requirement: it is outdated only when
+    /// the latest version is above it, not when it merely allows newer
+    /// versions than its lower bound.
+    pub fn is_outdated(&self) -> Option<bool> {
+        let current = self.current_version.as_deref()?.trim();
+        let latest = Version::parse(
+            self.latest_version
+                .as_deref()?
+                .trim()
+                .trim_start_matches('v'),
+        )
+        .ok()?;
+        if let Ok(current) = Version::parse(current.trim_start_matches(['=', 'v'])) {
+            return Some(current < latest);
+        }
+        let required = VersionReq::parse(current).ok()?;
+        let below_latest = required
+            .comparators
+            .iter()
+            .any(|c| Version::new(c.major, c.minor.unwrap_or(0), c.patch.unwrap_or(0)) < latest);
+        Some(!required.matches(&latest) && below_latest)
+    }
+}
+
 pub fn detect_mcp_from_files(files: &HashMap<String, String>) -> Vec<DetectedMcp> {
     let mut detected = Vec::new();
 
@@ -50,6 +115,7 @@ pub fn detect_mcp_from_files(files: &HashMap<String, String>) -> Vec<DetectedMcp
                 source: DetectionSource::Manifest,
                 required_env_vars: env_vars,
                 confidence: DetectionConfidence::High,
+                package: None,
             });
         }
     }
@@ -72,6 +138,7 @@ pub fn detect_mcp_from_files(files: &HashMap<String, String>) -> Vec<DetectedMcp
                     source: DetectionSource::McpJson,
                     required_env_vars: env_vars,
                     confidence: DetectionConfidence::High,
+                    package: None,
                 });
             }
         }
@@ -86,7 +153,12 @@ pub fn detect_mcp_from_files(files: &HashMap<String, String>) -> Vec<DetectedMcp
             .into_iter()
             .map(String::from)
             .collect();
+        let mut package = PackageMetadata::new(PackageRegistry::Npm, &name);
+        package.current_version = serde_json::from_str::<serde_json::Value>(content)
+            .ok()
+            .and_then(|v| v.get("version")?.as_str().map(String::from));
         detected.push(DetectedMcp {
+            package: Some(package),
             name,
             server,
             source: DetectionSource::PackageJson,
@@ -104,6 +176,7 @@ pub fn detect_mcp_from_files(files: &HashMap<String, String>) -> Vec<DetectedMcp
                 .map(String::from)
                 .collect();
             detected.push(DetectedMcp {
+                package: Some(PackageMetadata::new(PackageRegistry::PyPI, &name)),
                 name,
                 server,
                 source: DetectionSource::PyProject,
@@ -120,6 +193,30 @@ pub fn detect_mcp_from_files(files: &HashMap<String, String>) -> Vec<DetectedMcp
 mod tests {
     use super::*;
 
+    #[test]
+    fn is_outdated_compares_versions() {
+        let mut meta = PackageMetadata::new(PackageRegistry::Npm, "mcp-server");
+        assert_eq!(meta.is_outdated(), None);
+
+        let mut outdated = |current: &str, latest: &str| {
+            meta.current_version = Some(current.to_string());
+            meta.latest_version = Some(latest.to_string());
+            meta.is_outdated()
+        };
+        assert_eq!(outdated("1.0.0", "1.0.0"), Some(false));
+        assert_eq!(outdated("v1.0.0", "1.2.0"), Some(true));
+        assert_eq!(outdated("1.10.0", "1.9.0"), Some(false));
+        assert_eq!(outdated("2.0.0", "2.0.0-rc.1"), Some(false));
+
+        assert_eq!(outdated("^1.0.0", "1.2.0"), Some(false));
+        assert_eq!(outdated("^1.0.0", "2.0.0"), Some(true));
+        assert_eq!(outdated("~1.2", "1.3.0"), Some(true));
+        assert_eq!(outdated("^2.0.0", "1.9.0"), Some(false));
+
+        assert_eq!(outdated("latest", "1.0.0"), None);
+        assert_eq!(outdated("1.0.0", "1.0.0.post1"), None);
+    }
+
     #[test]
     fn detect_from_manifest_high_confidence() {
         let mut files = HashMap::new();
@@ -165,6 +262,22 @@ mod tests {
         assert_eq!(detected[0].confidence, DetectionConfidence::Medium);
     }
 
+    #[test]
+    fn detect_from_package_json_records_package() {
+        let mut files = HashMap::new();
+        files.insert(
+            "package.json".to_string(),
+            r#"{"name": "mcp-server-acme", "version": "0.3.1"}"#.to_string(),
+        );
+
+        let detected = detect_mcp_from_files(&files);
+        let package = detected[0].package.as_ref().unwrap();
+        assert_eq!(package.registry, PackageRegistry::Npm);
+        assert_eq!(package.name, "mcp-server-acme");
+        assert_eq!(package.current_version.as_deref(), Some("0.3.1"));
+        assert_eq!(package.latest_version, None);
+    }
+
     #[test]
     fn detect_from_pyproject_medium_confidence() {
         let mut files = HashMap::new();
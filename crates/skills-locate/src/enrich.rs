@@ -0,0 +1,199 @@
+//! npm and PyPI metadata lookup for detected MCP server packages.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::detect::{DetectedMcp, PackageMetadata, PackageRegistry};
+use crate::error::{Error, Result};
+use crate::fetch::fetch_json;
+
+const NPM_REGISTRY_URL: &str = "https://registry.npmjs.org";
+const PYPI_URL: &str = "https://pypi.org/pypi";
+
+impl PackageMetadata {
+    /// Queries the package's registry and fills in the published metadata.
+    pub fn fetch(&mut self) -> Result<()> {
+        let published = match self.registry {
+            PackageRegistry::Npm => {
+                let url = format!("{NPM_REGISTRY_URL}/{}/latest", encode_npm_name(&self.name));
+                fetch_json::<NpmLatest>(&url)?.into_published()
+            }
+            PackageRegistry::PyPI => {
+                let url = format!("{PYPI_URL}/{}/json", self.name);
+                fetch_json::<PypiProject>(&url)?.into_published()
+            }
+        };
+        self.latest_version = Some(published.version);
+        self.description = published.description;
+        self.repository_url = published.repository_url;
+        Ok(())
+    }
+}
+
+/// Fetches registry metadata for every detected server that has a package.
+///
+/// Lookups are best-effort: servers whose lookup fails keep their offline
+/// metadata, and the failures are returned keyed by server name.
+pub fn enrich_detected(detected: &mut [DetectedMcp]) -> Vec<(String, Error)> {
+    let mut errors = Vec::new();
+    for mcp in detected.iter_mut() {
+        if let Some(package) = mcp.package.as_mut()
+            && let Err(e) = package.fetch()
+        {
+            errors.push((mcp.name.clone(), e));
+        }
+    }
+    errors
+}
+
+struct Published {
+    version: String,
+    description: Option<String>,
+    repository_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmLatest {
+    version: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    repository: Option<NpmRepository>,
+    #[serde(default)]
+    homepage: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum NpmRepository {
+    Url(String),
+    Object { url: String },
+}
+
+impl NpmLatest {
+    fn into_published(self) -> Published {
+        let repository_url = match self.repository {
+            Some(NpmRepository::Url(url) | NpmRepository::Object { url }) => {
+                Some(normalize_repository_url(&url))
+            }
+            None => self.homepage,
+        };
+        Published {
+            version: self.version,
+            description: self.description,
+            repository_url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PypiProject {
+    info: PypiInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct PypiInfo {
+    version: String,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    project_urls: Option<HashMap<String, String>>,
+    #[serde(default)]
+    home_page: Option<String>,
+}
+
+impl PypiProject {
+    fn into_published(self) -> Published {
+        let info = self.info;
+        let urls = info.project_urls.unwrap_or_default();
+        let repository_url = ["Source", "Repository", "Source Code", "Homepage"]
+            .iter()
+            .find_map(|key| urls.get(*key).cloned())
+            .or(info.home_page.filter(|url| !url.is_empty()));
+        Published {
+            version: info.version,
+            description: info.summary.filter(|s| !s.is_empty()),
+            repository_url,
+        }
+    }
+}
+
+fn encode_npm_name(name: &str) -> String {
+    name.replacen('/', "%2F", 1)
+}
+
+fn normalize_repository_url(url: &str) -> String {
+    let url = url.strip_prefix("git+").unwrap_or(url);
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    if let Some(rest) = url.strip_prefix("git://") {
+        return format!("https://{rest}");
+    }
+    if let Some(repo) = url.strip_prefix("github:") {
+        return format!("https://github.com/{repo}");
+    }
+    url.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn npm_latest_with_repository_object() {
+        let latest: NpmLatest = serde_json::from_str(
+            r#"{
+                "version": "2.1.0",
+                "description": "GitHub MCP server",
+                "repository": {"type": "git", "url": "git+https://github.com/acme/server.git"}
+            }"#,
+        )
+        .unwrap();
+        let published = latest.into_published();
+        assert_eq!(published.version, "2.1.0");
+        assert_eq!(published.description.as_deref(), Some("GitHub MCP server"));
+        assert_eq!(
+            published.repository_url.as_deref(),
+            Some("https://github.com/acme/server")
+        );
+    }
+
+    #[test]
+    fn npm_latest_with_shorthand_repository() {
+        let latest: NpmLatest =
+            serde_json::from_str(r#"{"version": "1.0.0", "repository": "github:acme/server"}"#)
+                .unwrap();
+        assert_eq!(
+            latest.into_published().repository_url.as_deref(),
+            Some("https://github.com/acme/server")
+        );
+    }
+
+    #[test]
+    fn pypi_project_prefers_source_url() {
+        let project: PypiProject = serde_json::from_str(
+            r#"{"info": {
+                "version": "0.6.2",
+                "summary": "Fetch MCP server",
+                "home_page": "",
+                "project_urls": {"Homepage": "https://example.com", "Source": "https://github.com/acme/fetch"}
+            }}"#,
+        )
+        .unwrap();
+        let published = project.into_published();
+        assert_eq!(published.version, "0.6.2");
+        assert_eq!(
+            published.repository_url.as_deref(),
+            Some("https://github.com/acme/fetch")
+        );
+    }
+
+    #[test]
+    fn encode_scoped_npm_name() {
+        assert_eq!(
+            encode_npm_name("@modelcontextprotocol/server-github"),
+            "@modelcontextprotocol%2Fserver-github"
+        );
+        assert_eq!(encode_npm_name("mcp-server"), "mcp-server");
+    }
+}
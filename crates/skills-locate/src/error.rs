@@ -6,17 +6,46 @@ use thiserror::Error;
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum Error {
-    /// HTTP request failed.
-    #[error("HTTP request failed: {0}")]
-    Http(String),
+    /// A request failed in transit or with an unexpected HTTP status.
+    #[error("network error: {message}")]
+    Network {
+        /// The HTTP status, `None` if no response was received.
+        status: Option<u16>,
+        /// What went wrong.
+        message: String,
+    },
+
+    /// The server rate-limited the request (HTTP 429, or GitHub's 403 with
+    /// no quota left).
+    #[error("rate limited by {url}{}", reset_suffix(*.reset))]
+    RateLimited {
+        /// The request URL.
+        url: String,
+        /// Unix timestamp (seconds) at which the limit resets, if known.
+        reset: Option<u64>,
+    },
+
+    /// The server rejected the request's credentials (HTTP 401 or 403).
+    #[error("authentication failed for {url} (HTTP {status})")]
+    Auth {
+        /// The request URL.
+        url: String,
+        /// The HTTP status.
+        status: u16,
+    },
 
     /// Invalid URL provided.
     #[error("invalid URL: {0}")]
     InvalidUrl(String),
 
     /// GitHub reference could not be parsed.
+    ///
+    /// Held a `String` message up to 0.2.1; the [`GitHubRefError`] names
+    /// the part of the input that failed.
+    ///
+    /// [`GitHubRefError`]: crate::github::GitHubRefError
     #[error("GitHub reference parse error: {0}")]
-    GitHubParse(String),
+    GitHubParse(#[from] crate::github::GitHubRefError),
 
     /// ZIP archive extraction failed.
     #[error("ZIP extraction failed: {0}")]
@@ -26,18 +55,81 @@ pub enum Error {
     #[error("JSON parse error: {0}")]
     JsonParse(#[from] serde_json::Error),
 
+    /// A JSON file did not match its expected structure.
+    #[error("invalid {kind}: {}", crate::schema::join_violations(.violations))]
+    Schema {
+        /// The file format that was expected.
+        kind: crate::schema::SchemaKind,
+        /// Every violation found, with JSON pointers.
+        violations: Vec<crate::schema::SchemaViolation>,
+    },
+
     /// YAML parsing failed.
     #[error("YAML parse error: {0}")]
     YamlParse(#[from] serde_yaml::Error),
 
-    /// File not found in archive.
-    #[error("file not found in archive: {0}")]
-    NotFound(String),
+    /// A fetched or extracted file could not be parsed.
+    #[error("cannot parse {path}: {message}")]
+    Parse {
+        /// URL or archive path of the file.
+        path: String,
+        /// What is wrong with it.
+        message: String,
+    },
+
+    /// A URL, archive file, or other resource does not exist.
+    #[error("not found: {what}")]
+    NotFound {
+        /// What was looked for.
+        what: String,
+    },
 
     /// I/O operation failed.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    /// A harness directory or config could not be determined.
+    #[error(transparent)]
+    Harness(#[from] harness_locate::Error),
+
+    /// The trust policy blocks the source.
+    #[error("source blocked by trust policy: {0}")]
+    Blocked(String),
+
+    /// A downloaded archive did not match its pinned checksum.
+    #[error("checksum mismatch for {url}: expected {expected}, got {actual}")]
+    ChecksumMismatch {
+        /// The source the archive was downloaded from.
+        url: String,
+        /// The pinned SHA-256 checksum.
+        expected: String,
+        /// The archive's SHA-256 checksum.
+        actual: String,
+    },
+
+    /// An untrusted source failed the trust policy's review.
+    #[error("untrusted source {url} rejected: {}", crate::trust::join_violations(.violations))]
+    Untrusted {
+        /// The source that was rejected.
+        url: String,
+        /// Every violation found.
+        violations: Vec<crate::trust::TrustViolation>,
+    },
+
+    /// A remote URL answered neither like a streamable HTTP nor an SSE MCP
+    /// server.
+    #[error("could not negotiate MCP transport: {0}")]
+    Negotiation(String),
+
+    /// Serialized data was written by a newer version of this crate.
+    #[error("unsupported schema version {found}, this version reads up to {supported}")]
+    SchemaVersion {
+        /// The version found in the data.
+        found: u32,
+        /// The newest version this crate reads.
+        supported: u32,
+    },
+
     /// Download size limit exceeded.
     #[error("size limit exceeded: {size} bytes > {limit} bytes")]
     SizeLimit {
@@ -51,14 +143,98 @@ pub enum Error {
 /// A specialized Result type for skills operations.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Broad kind of an [`Error`], from [`Error::category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCategory {
+    /// The network failed or a server answered with an error; retrying
+    /// may help.
+    Network,
+    /// A rate limit was hit; retry after it resets.
+    RateLimited,
+    /// Credentials are missing or rejected.
+    Auth,
+    /// A file did not parse or did not have the expected structure.
+    Parse,
+    /// The requested resource does not exist, such as a bad ref or path.
+    NotFound,
+    /// Anything else: invalid input, policy rejections, local I/O.
+    Other,
+}
+
+impl Error {
+    /// Returns the broad kind of this error, so callers can tell a bad ref
+    /// from a rate limit or a malformed file without matching messages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skills_locate::{Error, ErrorCategory};
+    ///
+    /// let err = Error::NotFound { what: "https://github.com/acme/kit".into() };
+    /// assert_eq!(err.category(), ErrorCategory::NotFound);
+    /// ```
+    #[must_use]
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::Network { .. } | Self::Negotiation(_) => ErrorCategory::Network,
+            Self::RateLimited { .. } => ErrorCategory::RateLimited,
+            Self::Auth { .. } => ErrorCategory::Auth,
+            Self::Parse { .. }
+            | Self::JsonParse(_)
+            | Self::YamlParse(_)
+            | Self::Schema { .. }
+            | Self::ZipExtract(_) => ErrorCategory::Parse,
+            Self::NotFound { .. } => ErrorCategory::NotFound,
+            Self::Harness(harness_locate::Error::NotFound(_)) => ErrorCategory::NotFound,
+            Self::Harness(
+                harness_locate::Error::JsonParse(_)
+                | harness_locate::Error::YamlParse(_)
+                | harness_locate::Error::YamlLint { .. }
+                | harness_locate::Error::TomlParse(_),
+            ) => ErrorCategory::Parse,
+            _ => ErrorCategory::Other,
+        }
+    }
+
+    /// Returns `true` if retrying the same request may succeed: transport
+    /// failures and server errors (HTTP 5xx).
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Network { status, .. } if status.is_none_or(|s| s >= 500))
+    }
+}
+
+fn reset_suffix(reset: Option<u64>) -> String {
+    reset.map_or_else(String::new, |reset| format!(", resets at {reset}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn error_display_http() {
-        let err = Error::Http("connection refused".to_string());
-        assert_eq!(err.to_string(), "HTTP request failed: connection refused");
+    fn error_display_network() {
+        let err = Error::Network {
+            status: None,
+            message: "connection refused".to_string(),
+        };
+        assert_eq!(err.to_string(), "network error: connection refused");
+        assert!(err.is_transient());
+    }
+
+    #[test]
+    fn error_display_rate_limited() {
+        let err = Error::RateLimited {
+            url: "https://api.github.com/repos/acme/kit".to_string(),
+            reset: Some(1_700_000_000),
+        };
+        assert_eq!(
+            err.to_string(),
+            "rate limited by https://api.github.com/repos/acme/kit, resets at 1700000000"
+        );
+        assert_eq!(err.category(), ErrorCategory::RateLimited);
+        assert!(!err.is_transient());
     }
 
     #[test]
@@ -69,10 +245,12 @@ mod tests {
 
     #[test]
     fn error_display_github_parse() {
-        let err = Error::GitHubParse("missing owner".to_string());
+        let err = Error::GitHubParse(crate::github::GitHubRefError::MissingOwner {
+            input: "https://github.com/".to_string(),
+        });
         assert_eq!(
             err.to_string(),
-            "GitHub reference parse error: missing owner"
+            "GitHub reference parse error: missing owner in https://github.com/"
         );
     }
 
@@ -84,8 +262,10 @@ mod tests {
 
     #[test]
     fn error_display_not_found() {
-        let err = Error::NotFound("config.json".to_string());
-        assert_eq!(err.to_string(), "file not found in archive: config.json");
+        let err = Error::NotFound {
+            what: "config.json".to_string(),
+        };
+        assert_eq!(err.to_string(), "not found: config.json");
     }
 
     #[test]
@@ -100,6 +280,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_display_untrusted() {
+        let err = Error::Untrusted {
+            url: "https://github.com/acme/kit".to_string(),
+            violations: vec![
+                crate::trust::TrustViolation::MissingChecksum,
+                crate::trust::TrustViolation::Hooks {
+                    plugin: "kit".to_string(),
+                },
+            ],
+        };
+        assert_eq!(
+            err.to_string(),
+            "untrusted source https://github.com/acme/kit rejected: no checksum pinned; plugin kit ships hooks"
+        );
+    }
+
     #[test]
     fn error_from_io() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file missing");
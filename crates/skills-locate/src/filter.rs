@@ -0,0 +1,395 @@
+//! Path filters for sparse discovery in large repositories.
+//!
+//! A [`PathFilter`] selects which files of a repository discovery reads.
+//! With a filter set, the repository is listed through the GitHub trees
+//! API and only matching files are downloaded, instead of the whole
+//! archive.
+
+use std::io::{Cursor, Read, Write};
+
+use serde::{Deserialize, Serialize};
+use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+
+use crate::error::{Error, Result};
+use crate::fetch::{fetch_bytes, fetch_json};
+use crate::github::GitHubRef;
+
+/// Plugin metadata files read regardless of `include` patterns, so plugins
+/// are still detected when only component directories are included.
+const METADATA_FILES: &[&str] = &[
+    "marketplace.json",
+    "plugin.json",
+    "collection.json",
+    "hooks.json",
+    ".mcp.json",
+];
+
+/// Include and exclude glob patterns over repository paths.
+///
+/// Patterns are matched against paths relative to the repository root.
+/// `*` matches within a path segment, `?` matches one character and `**`
+/// matches any number of segments. A pattern matching a directory matches
+/// everything under it, so `skills` and `skills/**` are equivalent.
+///
+/// A path is selected if it matches an `include` pattern, or there are
+/// none, and matches no `exclude` pattern. Plugin metadata files
+/// (`marketplace.json`, `plugin.json`, `collection.json`, `hooks.json` and
+/// `.mcp.json`) are selected unless excluded.
+///
+/// # Examples
+///
+/// ```
+/// use skills_locate::PathFilter;
+///
+/// let filter = PathFilter::new()
+///     .include("skills/**")
+///     .exclude("**/examples/**");
+/// assert!(filter.matches("skills/deploy/SKILL.md"));
+/// assert!(!filter.matches("skills/deploy/examples/SKILL.md"));
+/// assert!(!filter.matches("docs/index.md"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PathFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl PathFilter {
+    /// Creates a filter selecting every path.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a pattern paths must match one of.
+    #[must_use]
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Adds a pattern no path may match.
+    #[must_use]
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Returns `true` if the filter has no patterns and selects every path.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Returns `true` if `path` matches the include and exclude patterns.
+    ///
+    /// Unlike discovery, this does not select metadata files that match no
+    /// `include` pattern.
+    #[must_use]
+    pub fn matches(&self, path: &str) -> bool {
+        (self.include.is_empty() || any_match(&self.include, path)) && !self.excludes(path)
+    }
+
+    /// Returns `true` if discovery should read the file at `path`.
+    pub(crate) fn selects(&self, path: &str) -> bool {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        self.matches(path) || (METADATA_FILES.contains(&name) && !self.excludes(path))
+    }
+
+    fn excludes(&self, path: &str) -> bool {
+        any_match(&self.exclude, path)
+    }
+}
+
+/// Returns `true` if any pattern matches `path` or one of its parent
+/// directories.
+fn any_match(patterns: &[String], path: &str) -> bool {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    patterns.iter().any(|pattern| {
+        let pattern: Vec<&str> = pattern
+            .trim_matches('/')
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect();
+        (1..=segments.len()).any(|len| glob_segments(&pattern, &segments[..len]))
+    })
+}
+
+fn glob_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| glob_segments(rest, &path[skip..])),
+        Some((segment, rest)) => path
+            .split_first()
+            .is_some_and(|(name, path)| glob_segment(segment, name) && glob_segments(rest, path)),
+    }
+}
+
+fn glob_segment(pattern: &str, name: &str) -> bool {
+    fn matches(pattern: &[char], name: &[char]) -> bool {
+        match pattern.split_first() {
+            None => name.is_empty(),
+            Some(('*', rest)) => (0..=name.len()).any(|skip| matches(rest, &name[skip..])),
+            Some(('?', rest)) => !name.is_empty() && matches(rest, &name[1..]),
+            Some((c, rest)) => name.first() == Some(c) && matches(rest, &name[1..]),
+        }
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    matches(&pattern, &name)
+}
+
+/// Response of the GitHub trees API.
+#[derive(Debug, Deserialize)]
+struct GitTree {
+    tree: Vec<GitTreeEntry>,
+    #[serde(default)]
+    truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitTreeEntry {
+    path: String,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+/// Fetches the repository as a ZIP archive holding only the files
+/// `filter` selects, laid out like GitHub's archives.
+///
+/// The file list comes from the trees API and each selected file is
+/// downloaded on its own, under [`GitHubRef::archive_prefix`]. Every
+/// directory is kept as an empty entry, so plugin detection sees the full
+/// layout. If the listing is truncated, the full archive is downloaded and
+/// filtered instead, keeping its own entry names; readers take the prefix
+/// from the first entry either way.
+///
+/// # Errors
+///
+/// Returns an error if the listing or a file cannot be fetched, including
+/// when the trees API is rate limited or denies access.
+pub(crate) fn fetch_filtered_archive(
+    github_ref: &GitHubRef,
+    filter: &PathFilter,
+) -> Result<Vec<u8>> {
+    let tree = fetch_json::<GitTree>(&github_ref.tree_api_url())?;
+    if tree.truncated {
+        let archive = fetch_bytes(&github_ref.archive_url())?;
+        return filter_archive(&archive, filter);
+    }
+
+    let prefix = github_ref.archive_prefix();
+    let mut entries = vec![(prefix.clone(), None)];
+    for entry in tree.tree {
+        match entry.kind.as_str() {
+            "tree" => entries.push((format!("{prefix}{}/", entry.path), None)),
+            "blob" if filter.selects(&entry.path) => {
+                let content = fetch_bytes(&github_ref.raw_url(&entry.path))?;
+                entries.push((format!("{prefix}{}", entry.path), Some(content)));
+            }
+            _ => {}
+        }
+    }
+    write_archive(entries)
+}
+
+/// Copies the entries of `archive` that `filter` selects, and every
+/// directory, into a new archive.
+fn filter_archive(archive: &[u8], filter: &PathFilter) -> Result<Vec<u8>> {
+    let mut archive = ZipArchive::new(Cursor::new(archive))
+        .map_err(|e| Error::ZipExtract(format!("invalid ZIP: {e}")))?;
+    let mut entries = Vec::new();
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| Error::ZipExtract(format!("read entry: {e}")))?;
+        let name = file.name().to_string();
+        let relative = name.split_once('/').map_or("", |(_, rest)| rest);
+        if file.is_dir() {
+            entries.push((name, None));
+        } else if filter.selects(relative) {
+            let mut bytes = Vec::new();
+            file.read_to_end(&mut bytes)
+                .map_err(|e| Error::ZipExtract(format!("read file: {e}")))?;
+            entries.push((name, Some(bytes)));
+        }
+    }
+    write_archive(entries)
+}
+
+/// Writes directory (`None`) and file entries to a ZIP archive.
+fn write_archive(entries: Vec<(String, Option<Vec<u8>>)>) -> Result<Vec<u8>> {
+    let write_error = |e: &dyn std::fmt::Display| Error::ZipExtract(format!("write archive: {e}"));
+    let mut buf = Cursor::new(Vec::new());
+    let mut zip = zip::ZipWriter::new(&mut buf);
+    let options = SimpleFileOptions::default();
+    for (name, content) in entries {
+        match content {
+            None => zip
+                .add_directory(name, options)
+                .map_err(|e| write_error(&e))?,
+            Some(content) => {
+                zip.start_file(name, options).map_err(|e| write_error(&e))?;
+                zip.write_all(&content).map_err(|e| write_error(&e))?;
+            }
+        }
+    }
+    zip.finish().map_err(|e| write_error(&e))?;
+    Ok(buf.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn globs_match_segments_and_parent_directories() {
+        let filter = PathFilter::new()
+            .include("skills/**")
+            .include("plugins/*/commands")
+            .exclude("**/examples/**")
+            .exclude("**/*.png");
+        assert!(filter.matches("skills/deploy/SKILL.md"));
+        assert!(filter.matches("plugins/ops/commands/release.md"));
+        assert!(!filter.matches("plugins/ops/agents/reviewer.md"));
+        assert!(!filter.matches("skills/deploy/examples/basic/SKILL.md"));
+        assert!(!filter.matches("skills/deploy/diagram.png"));
+        assert!(!filter.matches("skillset/SKILL.md"));
+
+        assert!(filter.selects(".claude-plugin/plugin.json"));
+        assert!(!filter.selects("README.md"));
+        assert!(
+            !PathFilter::new()
+                .exclude("**/*.json")
+                .selects("plugin.json")
+        );
+        assert!(PathFilter::new().matches("anything/at/all"));
+        assert!(glob_segment("SKILL.?d", "SKILL.md"));
+    }
+
+    #[cfg(feature = "registry")]
+    #[test]
+    fn sparse_discovery_skips_excluded_files() {
+        use std::sync::{Arc, Mutex};
+
+        use crate::fetch::{DebugSink, HttpExchange, with_debug_sink};
+        use crate::{MockServer, discover_all_filtered};
+
+        let mock = MockServer::start().with_repo(
+            "https://github.com/acme/monorepo",
+            &[
+                (
+                    "skills/deploy/SKILL.md",
+                    "---\nname: deploy\ndescription: Ship it\n---\n",
+                ),
+                (
+                    "skills/deploy/examples/demo/SKILL.md",
+                    "---\nname: demo\ndescription: Example\n---\n",
+                ),
+                (
+                    "commands/release.md",
+                    "---\ndescription: Cut a release\n---\n",
+                ),
+                ("docs/guide.md", "# Guide"),
+            ],
+        );
+        let urls = Arc::new(Mutex::new(Vec::new()));
+        let sink: Arc<dyn DebugSink> = {
+            let urls = Arc::clone(&urls);
+            Arc::new(move |exchange: &HttpExchange| {
+                urls.lock().unwrap().push(exchange.url.clone());
+            })
+        };
+        let filter = PathFilter::new()
+            .include("skills/**")
+            .exclude("**/examples/**");
+
+        let result = mock
+            .intercept(|| {
+                with_debug_sink(Some(&sink), || {
+                    discover_all_filtered("https://github.com/acme/monorepo", &filter)
+                })
+            })
+            .unwrap();
+
+        let skills: Vec<_> = result.all_skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(skills, ["deploy"]);
+        assert!(result.plugins[0].commands.is_empty());
+        let urls = urls.lock().unwrap();
+        assert!(
+            urls.iter()
+                .any(|url| url.ends_with("skills/deploy/SKILL.md"))
+        );
+        assert!(!urls.iter().any(|url| url.contains("examples")
+            || url.contains("docs/")
+            || url.contains("commands/")
+            || url.contains("/archive/")));
+    }
+
+    #[cfg(feature = "registry")]
+    #[test]
+    fn truncated_listing_filters_the_full_archive() {
+        use crate::mock::intercepted_path;
+        use crate::{MockServer, discover_all_filtered};
+
+        let repo = "https://github.com/acme/monorepo";
+        let github_ref = GitHubRef::parse(repo).unwrap();
+        let mock = MockServer::start()
+            .with_repo(
+                repo,
+                &[
+                    (
+                        "skills/deploy/SKILL.md",
+                        "---\nname: deploy\ndescription: Ship it\n---\n",
+                    ),
+                    (
+                        "skills/lint/SKILL.md",
+                        "---\nname: lint\ndescription: Check it\n---\n",
+                    ),
+                    (
+                        "commands/release.md",
+                        "---\ndescription: Cut a release\n---\n",
+                    ),
+                ],
+            )
+            .with_json(
+                &intercepted_path(&github_ref.tree_api_url()),
+                &serde_json::json!({ "tree": [], "truncated": true }),
+            );
+        let filter = PathFilter::new().include("skills/deploy");
+
+        let result = mock
+            .intercept(|| discover_all_filtered(repo, &filter))
+            .unwrap();
+        let skills: Vec<_> = result.all_skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(skills, ["deploy"]);
+    }
+
+    #[cfg(feature = "registry")]
+    #[test]
+    fn listing_errors_are_not_hidden_by_the_archive_fallback() {
+        use crate::mock::intercepted_path;
+        use crate::{MockServer, discover_all_filtered};
+
+        let repo = "https://github.com/acme/monorepo";
+        let github_ref = GitHubRef::parse(repo).unwrap();
+        let mock = MockServer::start()
+            .with_repo(
+                repo,
+                &[("skills/deploy/SKILL.md", "---\nname: deploy\n---\n")],
+            )
+            .with_response(
+                &intercepted_path(&github_ref.tree_api_url()),
+                401,
+                "application/json",
+                r#"{"message": "Bad credentials"}"#,
+            );
+
+        let err = mock
+            .intercept(|| discover_all_filtered(repo, &PathFilter::new().include("skills")))
+            .unwrap_err();
+        assert!(matches!(err, Error::Auth { status: 401, .. }), "{err:?}");
+    }
+}
@@ -1,48 +1,197 @@
 use crate::{Error, Result};
 
+/// Why a string could not be parsed as a [`GitHubRef`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum GitHubRefError {
+    /// The input is a URL on a host other than GitHub.
+    #[error("not a GitHub URL: {input}")]
+    NotGitHub {
+        /// The input as given.
+        input: String,
+    },
+    /// The owner is missing or empty.
+    #[error("missing owner in {input}")]
+    MissingOwner {
+        /// The input as given.
+        input: String,
+    },
+    /// The repository name is missing or empty.
+    #[error("missing owner/repo in {input}")]
+    MissingRepo {
+        /// The input as given.
+        input: String,
+    },
+    /// A `tree`/`blob` URL or `@` shorthand without a ref after it.
+    #[error("missing ref after '{after}' in {input}")]
+    MissingRef {
+        /// The input as given.
+        input: String,
+        /// The marker the ref should follow: `tree`, `blob` or `@`.
+        after: String,
+    },
+    /// A GitHub page that does not name a ref, such as `/issues/1`.
+    #[error("unsupported GitHub page '{page}' in {input}, expected 'tree' or 'blob'")]
+    UnsupportedPage {
+        /// The input as given.
+        input: String,
+        /// The path segment after `owner/repo`.
+        page: String,
+    },
+    /// The owner or repository contains characters GitHub does not allow.
+    #[error("invalid {part} '{value}' in {input}")]
+    InvalidName {
+        /// The input as given.
+        input: String,
+        /// `owner` or `repo`.
+        part: &'static str,
+        /// The rejected value.
+        value: String,
+    },
+}
+
+/// A GitHub repository at a ref, optionally narrowed to a path within it.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GitHubRef {
     pub owner: String,
     pub repo: String,
+    /// Branch, tag or commit SHA. `main` when the input names none.
     pub git_ref: String,
+    /// Directory or file within the repository, without surrounding
+    /// slashes.
+    pub path: Option<String>,
 }
 
 impl GitHubRef {
+    /// Parses a GitHub repository reference.
+    ///
+    /// Accepts:
+    ///
+    /// - repository URLs, with or without scheme, `www.` or `.git`:
+    ///   `https://github.com/owner/repo`
+    /// - tree URLs with a path: `https://github.com/owner/repo/tree/main/skills/foo`
+    /// - blob permalinks: `https://github.com/owner/repo/blob/<sha>/SKILL.md#L3`
+    /// - raw file URLs: `https://raw.githubusercontent.com/owner/repo/main/SKILL.md`
+    /// - shorthands: `owner/repo`, `owner/repo@v1.2`, `owner/repo@v1.2:skills/foo`,
+    ///   `owner/repo:skills/foo`
+    ///
+    /// A URL alone does not say where a branch name containing `/` ends and
+    /// the path begins. Unless the ref is a commit SHA or the only segment
+    /// after `tree`, the whole rest of a `tree` URL is taken as the ref,
+    /// and of a `blob` URL all but the file name; [`resolve`](Self::resolve)
+    /// asks GitHub where the branch ends. The shorthand is never ambiguous:
+    /// `owner/repo@feature/foo:skills/bar`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::GitHubParse`] with a [`GitHubRefError`] naming the
+    /// part that could not be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skills_locate::GitHubRef;
+    ///
+    /// let gh = GitHubRef::parse("https://github.com/acme/skills/tree/feature/foo")?;
+    /// assert_eq!((gh.git_ref.as_str(), gh.path), ("feature/foo", None));
+    ///
+    /// let gh = GitHubRef::parse("acme/skills@v2:skills/deploy")?;
+    /// assert_eq!((gh.git_ref.as_str(), gh.path.as_deref()), ("v2", Some("skills/deploy")));
+    /// # Ok::<(), skills_locate::Error>(())
+    /// ```
     pub fn parse(url: &str) -> Result<Self> {
-        let url = url.trim().trim_end_matches('/');
-
-        let path = url
-            .strip_prefix("https://github.com/")
-            .or_else(|| url.strip_prefix("http://github.com/"))
-            .ok_or_else(|| Error::GitHubParse(format!("not a GitHub URL: {url}")))?;
-
-        let parts: Vec<&str> = path.split('/').collect();
+        parse(url).map_err(Error::GitHubParse)
+    }
 
-        if parts.len() < 2 || parts[0].is_empty() || parts[1].is_empty() {
-            return Err(Error::GitHubParse(format!(
-                "missing owner/repo in URL: {url}"
-            )));
+    /// Splits a ref containing `/` into the branch or tag that exists on
+    /// GitHub and a path, so `main/skills/foo` from a `tree` URL becomes
+    /// ref `main` with path `skills/foo`.
+    ///
+    /// Each split is looked up through the commit API, shortest ref first,
+    /// and the rest of the ref is put in front of [`path`](Self::path).
+    /// Git does not allow both `feature` and `feature/foo` as branches, so
+    /// at most one split names a ref. If none does, the ref is kept whole.
+    /// Refs without `/` are returned without a request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a lookup fails for a reason other than the ref
+    /// not existing, such as a network error or rate limit.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use skills_locate::GitHubRef;
+    ///
+    /// let gh = GitHubRef::parse("https://github.com/acme/skills/tree/main/skills/deploy")?;
+    /// assert_eq!(gh.git_ref, "main/skills/deploy");
+    /// let gh = gh.resolve()?;
+    /// assert_eq!((gh.git_ref.as_str(), gh.path.as_deref()), ("main", Some("skills/deploy")));
+    /// # Ok::<(), skills_locate::Error>(())
+    /// ```
+    #[cfg(feature = "fetch")]
+    pub fn resolve(&self) -> Result<Self> {
+        let segments: Vec<&str> = self.git_ref.split('/').collect();
+        for split in 1..segments.len() {
+            let candidate = Self {
+                git_ref: segments[..split].join("/"),
+                path: Some(
+                    segments[split..]
+                        .iter()
+                        .copied()
+                        .chain(self.path.as_deref())
+                        .collect::<Vec<_>>()
+                        .join("/"),
+                ),
+                ..self.clone()
+            };
+            match crate::fetch::fetch_bytes(&candidate.commit_api_url()) {
+                Ok(_) => return Ok(candidate),
+                Err(Error::NotFound { .. }) => {}
+                Err(e) => return Err(e),
+            }
         }
+        Ok(self.clone())
+    }
 
-        let owner = parts[0].to_string();
-        let repo = parts[1].to_string();
+    /// URL of the ZIP archive of the repository at its ref.
+    ///
+    /// Refs other than a full commit SHA are taken to be branches.
+    pub fn archive_url(&self) -> String {
+        if is_commit_sha(&self.git_ref) {
+            return format!(
+                "https://github.com/{}/{}/archive/{}.zip",
+                self.owner, self.repo, self.git_ref
+            );
+        }
+        format!(
+            "https://github.com/{}/{}/archive/refs/heads/{}.zip",
+            self.owner, self.repo, self.git_ref
+        )
+    }
 
-        let git_ref = if parts.len() >= 4 && parts[2] == "tree" {
-            parts[3..].join("/")
-        } else {
-            "main".to_string()
-        };
+    /// Top-level directory of the [archive](Self::archive_url), with a
+    /// trailing `/`.
+    ///
+    /// GitHub names it after the repository and the ref, with each `/` in
+    /// the ref replaced by `-`.
+    pub fn archive_prefix(&self) -> String {
+        format!("{}-{}/", self.repo, self.git_ref.replace('/', "-"))
+    }
 
-        Ok(Self {
-            owner,
-            repo,
-            git_ref,
-        })
+    /// GitHub API URL resolving the ref to its current commit.
+    pub fn commit_api_url(&self) -> String {
+        format!(
+            "https://api.github.com/repos/{}/{}/commits/{}",
+            self.owner, self.repo, self.git_ref
+        )
     }
 
-    pub fn archive_url(&self) -> String {
+    /// URL of the GitHub trees API listing every path in the repository
+    /// at its ref.
+    pub fn tree_api_url(&self) -> String {
         format!(
-            "https://github.com/{}/{}/archive/refs/heads/{}.zip",
+            "https://api.github.com/repos/{}/{}/git/trees/{}?recursive=1",
             self.owner, self.repo, self.git_ref
         )
     }
@@ -56,6 +205,143 @@ impl GitHubRef {
     }
 }
 
+/// Returns `true` for a full 40-character commit SHA.
+fn is_commit_sha(git_ref: &str) -> bool {
+    git_ref.len() == 40 && git_ref.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn parse(input: &str) -> std::result::Result<GitHubRef, GitHubRefError> {
+    let trimmed = input.trim();
+    let without_scheme = trimmed
+        .strip_prefix("https://")
+        .or_else(|| trimmed.strip_prefix("http://"));
+    let (host, rest) = match without_scheme {
+        Some(rest) => rest.split_once('/').unwrap_or((rest, "")),
+        None => match trimmed.split_once('/') {
+            Some((host, rest)) if host.contains('.') => (host, rest),
+            _ => return parse_shorthand(input, trimmed),
+        },
+    };
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let raw = match host.to_ascii_lowercase().as_str() {
+        "github.com" | "www.github.com" => false,
+        "raw.githubusercontent.com" => true,
+        _ => {
+            return Err(GitHubRefError::NotGitHub {
+                input: input.to_string(),
+            });
+        }
+    };
+
+    let parts: Vec<&str> = rest.split('/').filter(|p| !p.is_empty()).collect();
+    let (owner, repo) = owner_repo(input, parts.first(), parts.get(1))?;
+    let (git_ref, path) = if raw {
+        let git_ref = parts.get(2).ok_or_else(|| GitHubRefError::MissingRef {
+            input: input.to_string(),
+            after: "repo".into(),
+        })?;
+        (git_ref.to_string(), &parts[3.min(parts.len())..])
+    } else {
+        match parts.get(2).copied() {
+            None => ("main".to_string(), &parts[2..]),
+            Some(page @ ("tree" | "blob")) => {
+                let rest = &parts[3..];
+                let Some(first) = rest.first() else {
+                    return Err(GitHubRefError::MissingRef {
+                        input: input.to_string(),
+                        after: page.to_string(),
+                    });
+                };
+                // Branches may contain `/`; see `GitHubRef::resolve`.
+                let ref_len = if rest.len() == 1 || is_commit_sha(first) {
+                    1
+                } else if page == "blob" {
+                    rest.len() - 1
+                } else {
+                    rest.len()
+                };
+                (rest[..ref_len].join("/"), &rest[ref_len..])
+            }
+            Some(page) => {
+                return Err(GitHubRefError::UnsupportedPage {
+                    input: input.to_string(),
+                    page: page.to_string(),
+                });
+            }
+        }
+    };
+    Ok(GitHubRef {
+        owner,
+        repo,
+        git_ref,
+        path: (!path.is_empty()).then(|| path.join("/")),
+    })
+}
+
+/// Parses `owner/repo[@ref][:path]`.
+fn parse_shorthand(input: &str, shorthand: &str) -> std::result::Result<GitHubRef, GitHubRefError> {
+    let (repo_part, path) = match shorthand.split_once(':') {
+        Some((repo_part, path)) => (repo_part, Some(path.trim_matches('/'))),
+        None => (shorthand, None),
+    };
+    let (repo_part, git_ref) = match repo_part.split_once('@') {
+        Some((_, "")) => {
+            return Err(GitHubRefError::MissingRef {
+                input: input.to_string(),
+                after: "@".into(),
+            });
+        }
+        Some((repo_part, git_ref)) => (repo_part, git_ref.to_string()),
+        None => (repo_part, "main".to_string()),
+    };
+    let mut parts = repo_part.split('/');
+    let (owner, repo) = owner_repo(input, parts.next().as_ref(), parts.next().as_ref())?;
+    if parts.next().is_some() {
+        return Err(GitHubRefError::InvalidName {
+            input: input.to_string(),
+            part: "repo",
+            value: repo_part.split_once('/').map_or("", |(_, r)| r).to_string(),
+        });
+    }
+    Ok(GitHubRef {
+        owner,
+        repo,
+        git_ref,
+        path: path.filter(|p| !p.is_empty()).map(String::from),
+    })
+}
+
+fn owner_repo(
+    input: &str,
+    owner: Option<&&str>,
+    repo: Option<&&str>,
+) -> std::result::Result<(String, String), GitHubRefError> {
+    let owner = owner
+        .filter(|o| !o.is_empty())
+        .ok_or_else(|| GitHubRefError::MissingOwner {
+            input: input.to_string(),
+        })?;
+    let repo = repo
+        .map(|r| r.strip_suffix(".git").unwrap_or(r))
+        .filter(|r| !r.is_empty())
+        .ok_or_else(|| GitHubRefError::MissingRepo {
+            input: input.to_string(),
+        })?;
+    for (part, value) in [("owner", *owner), ("repo", repo)] {
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(GitHubRefError::InvalidName {
+                input: input.to_string(),
+                part,
+                value: value.to_string(),
+            });
+        }
+    }
+    Ok((owner.to_string(), repo.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +366,139 @@ mod tests {
     fn parse_url_with_nested_branch() {
         let gh = GitHubRef::parse("https://github.com/owner/repo/tree/feature/foo").unwrap();
         assert_eq!(gh.git_ref, "feature/foo");
+        assert_eq!(gh.path, None);
+    }
+
+    #[test]
+    fn parse_tree_url_with_path() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let gh = GitHubRef::parse(&format!(
+            "https://github.com/owner/repo/tree/{sha}/skills/foo"
+        ))
+        .unwrap();
+        assert_eq!(gh.git_ref, sha);
+        assert_eq!(gh.path.as_deref(), Some("skills/foo"));
+
+        let gh =
+            GitHubRef::parse("https://github.com/owner/repo/blob/feature/foo/SKILL.md").unwrap();
+        assert_eq!(gh.git_ref, "feature/foo");
+        assert_eq!(gh.path.as_deref(), Some("SKILL.md"));
+    }
+
+    #[cfg(feature = "registry")]
+    #[test]
+    fn resolve_finds_where_the_branch_ends() {
+        use crate::MockServer;
+
+        let mock = MockServer::start()
+            .with_repo("https://github.com/owner/repo", &[("README.md", "")])
+            .with_repo(
+                "https://github.com/owner/repo/tree/feature/foo",
+                &[("README.md", "")],
+            );
+        let resolve = |url: &str| {
+            let gh = mock
+                .intercept(|| GitHubRef::parse(url).unwrap().resolve())
+                .unwrap();
+            (gh.git_ref, gh.path)
+        };
+
+        assert_eq!(
+            resolve("https://github.com/owner/repo/tree/main/skills/foo"),
+            ("main".into(), Some("skills/foo".into()))
+        );
+        assert_eq!(
+            resolve("https://github.com/owner/repo/blob/feature/foo/skills/SKILL.md"),
+            ("feature/foo".into(), Some("skills/SKILL.md".into()))
+        );
+        assert_eq!(
+            resolve("https://github.com/owner/repo/tree/feature/foo"),
+            ("feature/foo".into(), None)
+        );
+        assert_eq!(
+            resolve("https://github.com/owner/repo/tree/missing/branch"),
+            ("missing/branch".into(), None)
+        );
+    }
+
+    #[test]
+    fn parse_blob_permalink() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let gh = GitHubRef::parse(&format!(
+            "https://www.github.com/owner/repo/blob/{sha}/skills/foo/SKILL.md?plain=1#L3"
+        ))
+        .unwrap();
+        assert_eq!(gh.git_ref, sha);
+        assert_eq!(gh.path.as_deref(), Some("skills/foo/SKILL.md"));
+        assert_eq!(
+            gh.archive_url(),
+            format!("https://github.com/owner/repo/archive/{sha}.zip")
+        );
+    }
+
+    #[test]
+    fn parse_raw_url() {
+        let gh =
+            GitHubRef::parse("https://raw.githubusercontent.com/owner/repo/v1/SKILL.md").unwrap();
+        assert_eq!((gh.owner.as_str(), gh.repo.as_str()), ("owner", "repo"));
+        assert_eq!(gh.git_ref, "v1");
+        assert_eq!(gh.path.as_deref(), Some("SKILL.md"));
+    }
+
+    #[test]
+    fn parse_shorthand() {
+        let gh = GitHubRef::parse("owner/repo").unwrap();
+        assert_eq!((gh.git_ref.as_str(), gh.path), ("main", None));
+
+        let gh = GitHubRef::parse("owner/repo@feature/foo:skills/bar/").unwrap();
+        assert_eq!(gh.git_ref, "feature/foo");
+        assert_eq!(gh.path.as_deref(), Some("skills/bar"));
+
+        let gh = GitHubRef::parse("owner/repo:skills").unwrap();
+        assert_eq!(
+            (gh.git_ref.as_str(), gh.path.as_deref()),
+            ("main", Some("skills"))
+        );
+
+        let gh = GitHubRef::parse("github.com/owner/repo.git").unwrap();
+        assert_eq!(gh.repo, "repo");
+    }
+
+    #[test]
+    fn parse_errors_name_the_failing_part() {
+        let err = |input: &str| match GitHubRef::parse(input) {
+            Err(Error::GitHubParse(e)) => e,
+            other => panic!("expected parse error, got {other:?}"),
+        };
+        assert!(matches!(
+            err("https://gitlab.com/owner/repo"),
+            GitHubRefError::NotGitHub { .. }
+        ));
+        assert!(matches!(
+            err("https://github.com/"),
+            GitHubRefError::MissingOwner { .. }
+        ));
+        assert!(matches!(err("owner"), GitHubRefError::MissingRepo { .. }));
+        assert!(matches!(
+            err("https://github.com/owner/repo/tree/"),
+            GitHubRefError::MissingRef { after, .. } if after == "tree"
+        ));
+        assert!(matches!(
+            err("owner/repo@"),
+            GitHubRefError::MissingRef { .. }
+        ));
+        assert!(matches!(
+            err("https://github.com/owner/repo/issues/1"),
+            GitHubRefError::UnsupportedPage { page, .. } if page == "issues"
+        ));
+        assert!(matches!(
+            err("own er/repo"),
+            GitHubRefError::InvalidName { part: "owner", .. }
+        ));
+        assert_eq!(
+            err("https://github.com/owner/repo/blob").to_string(),
+            "missing ref after 'blob' in https://github.com/owner/repo/blob"
+        );
     }
 
     #[test]
@@ -102,12 +521,24 @@ mod tests {
         assert!(GitHubRef::parse("https://github.com/owner").is_err());
     }
 
+    #[test]
+    fn archive_prefix_replaces_slashes_in_the_ref() {
+        let gh = GitHubRef {
+            owner: "acme".into(),
+            repo: "kit".into(),
+            git_ref: "feature/sparse".into(),
+            path: None,
+        };
+        assert_eq!(gh.archive_prefix(), "kit-feature-sparse/");
+    }
+
     #[test]
     fn archive_url_format() {
         let gh = GitHubRef {
             owner: "anthropics".into(),
             repo: "claude-code".into(),
             git_ref: "main".into(),
+            path: None,
         };
         assert_eq!(
             gh.archive_url(),
@@ -121,6 +552,7 @@ mod tests {
             owner: "anthropics".into(),
             repo: "claude-code".into(),
             git_ref: "main".into(),
+            path: None,
         };
         assert_eq!(
             gh.raw_url("README.md"),
@@ -134,6 +566,7 @@ mod tests {
             owner: "o".into(),
             repo: "r".into(),
             git_ref: "main".into(),
+            path: None,
         };
         assert_eq!(
             gh.raw_url("/path/to/file.txt"),
@@ -0,0 +1,290 @@
+//! Persistent discovery index with incremental refresh.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use harness_locate::text::decode;
+use serde::{Deserialize, Serialize};
+
+use crate::dedupe::DedupePolicy;
+use crate::discovery::discover_all;
+use crate::error::{Error, Result};
+use crate::fetch::fetch_json;
+use crate::github::GitHubRef;
+use crate::types::DiscoveryResult;
+
+/// Cached discovery result for one source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Commit SHA the source's ref pointed at when it was discovered.
+    pub sha: String,
+    /// Unix timestamp (seconds) of the discovery.
+    pub fetched_at: u64,
+    pub result: DiscoveryResult,
+}
+
+/// Discovery results for a set of sources, keyed by source URL.
+///
+/// Load with [`DiscoveryIndex::load`] and bring up to date with
+/// [`DiscoveryIndex::refresh`], which only rediscovers sources whose ref
+/// has moved to a new commit.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscoveryIndex {
+    #[serde(default)]
+    pub entries: BTreeMap<String, IndexEntry>,
+}
+
+/// What [`DiscoveryIndex::refresh`] did for each source.
+#[derive(Debug, Default)]
+pub struct RefreshReport {
+    /// Sources discovered because they were new or their ref moved.
+    pub fetched: Vec<String>,
+    /// Sources whose ref still points at the indexed commit.
+    pub unchanged: Vec<String>,
+    /// Indexed sources no longer in the source list.
+    pub removed: Vec<String>,
+    /// Sources that failed to refresh; their previous entry, if any, is kept.
+    pub errors: Vec<(String, Error)>,
+}
+
+impl DiscoveryIndex {
+    /// Loads the index from `path`, or returns an empty index if the file
+    /// does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read(path) {
+            Ok(bytes) => Ok(serde_json::from_str(&decode(&bytes)?.text)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the index to `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save_json(path, self)
+    }
+
+    /// Returns the cached result for `source`.
+    pub fn get(&self, source: &str) -> Option<&DiscoveryResult> {
+        self.entries.get(source).map(|entry| &entry.result)
+    }
+
+    /// Combines the plugins of every indexed source into one result, with
+    /// skills found in several sources deduplicated by the default
+    /// [`DedupePolicy`].
+    pub fn merged(&self) -> DiscoveryResult {
+        self.merged_with(&DedupePolicy::default())
+    }
+
+    /// Combines the plugins of every indexed source into one result, with
+    /// skills found in several sources deduplicated by `policy`.
+    ///
+    /// Copies suppressed within a single source stay suppressed.
+    pub fn merged_with(&self, policy: &DedupePolicy) -> DiscoveryResult {
+        let mut result = DiscoveryResult::from_plugins(
+            self.entries
+                .values()
+                .flat_map(|entry| entry.result.plugins.clone())
+                .collect(),
+        );
+        result.suppressed = self
+            .entries
+            .values()
+            .flat_map(|entry| entry.result.suppressed.clone())
+            .collect();
+        result.dedupe(policy)
+    }
+
+    /// Brings the index in line with `sources`.
+    ///
+    /// Each source's ref is resolved to a commit SHA; only sources that are
+    /// new or whose SHA changed are rediscovered. Sources not in the list
+    /// are dropped.
+    pub fn refresh(&mut self, sources: &[&str]) -> RefreshReport {
+        self.refresh_with(sources, resolve_sha, discover_all)
+    }
+
+    fn refresh_with<R, D>(&mut self, sources: &[&str], resolve: R, discover: D) -> RefreshReport
+    where
+        R: Fn(&GitHubRef) -> Result<String>,
+        D: Fn(&str) -> Result<DiscoveryResult>,
+    {
+        let mut report = RefreshReport::default();
+
+        let stale: Vec<String> = self
+            .entries
+            .keys()
+            .filter(|key| !sources.contains(&key.as_str()))
+            .cloned()
+            .collect();
+        for key in stale {
+            self.entries.remove(&key);
+            report.removed.push(key);
+        }
+
+        for &source in sources {
+            let sha = match GitHubRef::parse(source).and_then(|r| resolve(&r)) {
+                Ok(sha) => sha,
+                Err(e) => {
+                    report.errors.push((source.to_string(), e));
+                    continue;
+                }
+            };
+            if self
+                .entries
+                .get(source)
+                .is_some_and(|entry| entry.sha == sha)
+            {
+                report.unchanged.push(source.to_string());
+                continue;
+            }
+            match discover(source) {
+                Ok(result) => {
+                    self.entries.insert(
+                        source.to_string(),
+                        IndexEntry {
+                            sha,
+                            fetched_at: unix_now(),
+                            result,
+                        },
+                    );
+                    report.fetched.push(source.to_string());
+                }
+                Err(e) => report.errors.push((source.to_string(), e)),
+            }
+        }
+
+        report
+    }
+}
+
+/// Loads the index at `path`, refreshes it against `sources`, and saves it.
+pub fn refresh_index(path: &Path, sources: &[&str]) -> Result<(DiscoveryIndex, RefreshReport)> {
+    let mut index = DiscoveryIndex::load(path)?;
+    let report = index.refresh(sources);
+    index.save(path)?;
+    Ok((index, report))
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitRef {
+    sha: String,
+}
+
+fn resolve_sha(github_ref: &GitHubRef) -> Result<String> {
+    Ok(fetch_json::<CommitRef>(&github_ref.commit_api_url())?.sha)
+}
+
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Writes `value` as JSON through a temporary file so an interrupted write
+/// never leaves a truncated file behind.
+pub(crate) fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, serde_json::to_vec_pretty(value)?)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    const A: &str = "https://github.com/acme/a";
+    const B: &str = "https://github.com/acme/b";
+
+    fn discover_counting<'a>(
+        calls: &'a RefCell<Vec<String>>,
+    ) -> impl Fn(&str) -> Result<DiscoveryResult> + 'a {
+        move |source| {
+            calls.borrow_mut().push(source.to_string());
+            Ok(DiscoveryResult::from_plugins(vec![]))
+        }
+    }
+
+    #[test]
+    fn refresh_only_fetches_moved_refs() {
+        let mut index = DiscoveryIndex::default();
+        let calls = RefCell::new(Vec::new());
+
+        let report = index.refresh_with(&[A, B], |_| Ok("sha1".into()), discover_counting(&calls));
+        assert_eq!(report.fetched, vec![A, B]);
+        assert_eq!(calls.borrow().len(), 2);
+
+        calls.borrow_mut().clear();
+        let report = index.refresh_with(
+            &[A, B],
+            |r| Ok(if r.repo == "b" { "sha2" } else { "sha1" }.into()),
+            discover_counting(&calls),
+        );
+        assert_eq!(report.unchanged, vec![A]);
+        assert_eq!(report.fetched, vec![B]);
+        assert_eq!(*calls.borrow(), vec![B]);
+        assert_eq!(index.entries[B].sha, "sha2");
+    }
+
+    #[test]
+    fn refresh_drops_removed_sources_and_keeps_failed_entries() {
+        let mut index = DiscoveryIndex::default();
+        let calls = RefCell::new(Vec::new());
+        index.refresh_with(&[A, B], |_| Ok("sha1".into()), discover_counting(&calls));
+
+        let report = index.refresh_with(
+            &[A],
+            |_| {
+                Err(Error::Network {
+                    status: Some(503),
+                    message: "HTTP 503".into(),
+                })
+            },
+            discover_counting(&calls),
+        );
+        assert_eq!(report.removed, vec![B]);
+        assert_eq!(report.errors.len(), 1);
+        assert!(index.get(A).is_some());
+        assert!(index.get(B).is_none());
+    }
+
+    #[test]
+    fn refresh_reports_invalid_sources() {
+        let mut index = DiscoveryIndex::default();
+        let calls = RefCell::new(Vec::new());
+        let report = index.refresh_with(
+            &["not-a-url"],
+            |_| Ok("sha".into()),
+            discover_counting(&calls),
+        );
+        assert!(matches!(report.errors[0].1, Error::GitHubParse(_)));
+        assert!(calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn index_save_and_load_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("skills-locate-index-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(
+            DiscoveryIndex::load(&path).unwrap(),
+            DiscoveryIndex::default()
+        );
+
+        let mut index = DiscoveryIndex::default();
+        index.entries.insert(
+            A.to_string(),
+            IndexEntry {
+                sha: "abc".to_string(),
+                fetched_at: 1,
+                result: DiscoveryResult::from_plugins(vec![]),
+            },
+        );
+        index.save(&path).unwrap();
+        assert_eq!(DiscoveryIndex::load(&path).unwrap(), index);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,536 @@
+//! Planning where a plugin's components go for a given harness.
+//!
+//! [`plan_install`] maps every skill, command, agent, hook event, and MCP
+//! server of a [`PluginDescriptor`] to a file or directory to copy, or a
+//! config value to write, without touching the filesystem. Components the
+//! harness has no place for are kept in the plan as
+//! [`InstallAction::Skip`] with the reason.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use harness_locate::naming::{safe_name, slugify, unique_name};
+use harness_locate::target::{Platform, Platforms};
+use harness_locate::types::{DirectoryResource, DirectoryStructure, FileFormat, ResourceKind};
+use harness_locate::{Harness, HarnessKind, Scope};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::types::PluginDescriptor;
+
+/// The kind of plugin component an [`InstallStep`] installs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ComponentKind {
+    /// A skill directory.
+    Skill,
+    /// A command file.
+    Command,
+    /// An agent file.
+    Agent,
+    /// The hook groups for one event.
+    Hook,
+    /// An MCP server.
+    McpServer,
+}
+
+/// What to do with one component.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum InstallAction {
+    /// Copy a file from the plugin to `target`.
+    CopyFile {
+        /// Path relative to the plugin root, if discovery recorded it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        source: Option<String>,
+        /// Destination file.
+        target: PathBuf,
+    },
+    /// Copy a directory from the plugin to `target`.
+    CopyDir {
+        /// Path relative to the plugin root, if discovery recorded it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        source: Option<String>,
+        /// Destination directory.
+        target: PathBuf,
+    },
+    /// Write `value` at the JSON pointer `pointer` in a config file.
+    ///
+    /// For hooks, `value` is an array of hook groups that should be
+    /// appended to any already configured for the event.
+    EditConfig {
+        /// The config file.
+        file: PathBuf,
+        /// Format of the config file.
+        format: FileFormat,
+        /// JSON pointer to write at.
+        pointer: String,
+        /// The value in the harness's native format.
+        value: serde_json::Value,
+    },
+    /// The component cannot be installed for this harness and scope.
+    Skip {
+        /// Why the component is skipped.
+        reason: String,
+    },
+}
+
+/// One component of a plugin and where it goes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallStep {
+    /// The kind of component.
+    pub kind: ComponentKind,
+    /// Component name, or the event name for hooks.
+    pub name: String,
+    /// What to do with it.
+    #[serde(flatten)]
+    pub action: InstallAction,
+}
+
+impl InstallStep {
+    /// Returns `true` if this component is skipped.
+    #[must_use]
+    pub fn is_skipped(&self) -> bool {
+        matches!(self.action, InstallAction::Skip { .. })
+    }
+}
+
+/// Where every component of a plugin goes for one harness and scope.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallPlan {
+    /// The plugin name.
+    pub plugin: String,
+    /// The harness name.
+    pub harness: String,
+    /// One step per component, in the order skills, commands, agents,
+    /// hooks, MCP servers.
+    pub steps: Vec<InstallStep>,
+}
+
+impl InstallPlan {
+    /// Returns the steps that are skipped.
+    pub fn skipped(&self) -> impl Iterator<Item = &InstallStep> {
+        self.steps.iter().filter(|step| step.is_skipped())
+    }
+
+    /// Returns `true` if every component can be installed.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.skipped().next().is_none()
+    }
+}
+
+/// Maps every component of `plugin` to its destination for `harness` in
+/// `scope`.
+///
+/// Skills become directory copies into the harness's skills directory,
+/// named with [`slugify`] so they meet the harness's naming rules,
+/// commands and agents file copies named after the component, MCP servers
+/// their native config under the harness's MCP key path, and hooks one
+/// entry per event under `hooks` in Claude Code's `settings.json`.
+/// Components the harness does not support, or cannot represent, are
+/// skipped with a reason. Names that collide once made safe for the
+/// filesystem get a numeric suffix. Skills whose `platforms` exclude the
+/// machine this runs on are skipped too.
+///
+/// # Errors
+///
+/// Returns an error if a harness directory cannot be determined, for
+/// example because the home directory is unknown. Scopes a harness does
+/// not support produce skipped steps instead.
+///
+/// # Examples
+///
+/// ```
+/// use harness_locate::{Harness, HarnessKind, Scope};
+/// use skills_locate::{InstallAction, PluginDescriptor, plan_install};
+///
+/// let plugin: PluginDescriptor = serde_json::from_str(r#"{
+///     "name": "kit",
+///     "commands": [{"name": "release", "path": "commands/release.md"}],
+///     "hooks": {"Stop": [{"hooks": ["notify"]}]}
+/// }"#)?;
+/// let scope = Scope::Project("/work/app".into());
+///
+/// let plan = plan_install(&plugin, &Harness::new(HarnessKind::Goose), &scope)?;
+/// assert!(plan.steps.iter().all(|step| step.is_skipped()));
+///
+/// let plan = plan_install(&plugin, &Harness::new(HarnessKind::ClaudeCode), &scope)?;
+/// assert!(plan.is_complete());
+/// assert!(matches!(
+///     &plan.steps[0].action,
+///     InstallAction::CopyFile { target, .. } if target.ends_with(".claude/commands/release.md")
+/// ));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn plan_install(
+    plugin: &PluginDescriptor,
+    harness: &Harness,
+    scope: &Scope,
+) -> Result<InstallPlan> {
+    let mut steps = Vec::new();
+
+    let skills = resource(harness, ResourceKind::Skills, scope)?;
+    let mut taken = HashSet::new();
+    for skill in &plugin.skills {
+        let action = match &skills {
+            _ if !skill.platforms.allows_current() => skip(&unsupported_platform(&skill.platforms)),
+            Ok(dir) => {
+                let slug = harness
+                    .kind()
+                    .map_or_else(|| safe_name(&skill.name), |kind| slugify(&skill.name, kind));
+                InstallAction::CopyDir {
+                    source: skill.path.clone(),
+                    target: dir.path.join(claim(slug, &mut taken)),
+                }
+            }
+            Err(reason) => skip(reason),
+        };
+        steps.push(step(ComponentKind::Skill, &skill.name, action));
+    }
+
+    let commands = resource(harness, ResourceKind::Commands, scope)?;
+    let mut taken = HashSet::new();
+    for command in &plugin.commands {
+        let action = match &commands {
+            Ok(dir) => InstallAction::CopyFile {
+                source: command.path.clone(),
+                target: file_target(dir, &claim(safe_name(&command.name), &mut taken)),
+            },
+            Err(reason) => skip(reason),
+        };
+        steps.push(step(ComponentKind::Command, &command.name, action));
+    }
+
+    let agents = resource(harness, ResourceKind::Agents, scope)?;
+    let mut taken = HashSet::new();
+    for agent in &plugin.agents {
+        let action = match &agents {
+            Ok(dir) => InstallAction::CopyFile {
+                source: agent.path.clone(),
+                target: file_target(dir, &claim(safe_name(&agent.name), &mut taken)),
+            },
+            Err(reason) => skip(reason),
+        };
+        steps.push(step(ComponentKind::Agent, &agent.name, action));
+    }
+
+    if let Some(hooks) = &plugin.hooks {
+        let settings = hooks_settings(harness, scope)?;
+        for (event, groups) in hooks {
+            let name = serde_json::to_value(event)?
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            let action = match &settings {
+                Ok(file) => InstallAction::EditConfig {
+                    file: file.clone(),
+                    format: FileFormat::Json,
+                    pointer: format!("/hooks/{}", escape_pointer(&name)),
+                    value: serde_json::to_value(groups)?,
+                },
+                Err(reason) => skip(reason),
+            };
+            steps.push(step(ComponentKind::Hook, &name, action));
+        }
+    }
+
+    if !plugin.mcp_servers.is_empty() {
+        let config = match harness.mcp(scope) {
+            Ok(Some(config)) => Ok(config),
+            Ok(None) => Err(format!("{} has no MCP configuration", harness.name())),
+            Err(e) => Err(skip_reason(e)?),
+        };
+        for (name, server) in &plugin.mcp_servers {
+            let action = match &config {
+                Ok(config) => match harness.mcp_to_native(name, server) {
+                    Ok(value) => InstallAction::EditConfig {
+                        file: config.file.clone(),
+                        format: config.format,
+                        pointer: format!("{}/{}", config.key_path, escape_pointer(name)),
+                        value,
+                    },
+                    Err(e) => skip(&skip_reason(e)?),
+                },
+                Err(reason) => skip(reason),
+            };
+            steps.push(step(ComponentKind::McpServer, name, action));
+        }
+    }
+
+    Ok(InstallPlan {
+        plugin: plugin.name.clone(),
+        harness: harness.name().to_string(),
+        steps,
+    })
+}
+
+/// Looks up a resource directory, or the reason it is unavailable.
+fn resource(
+    harness: &Harness,
+    kind: ResourceKind,
+    scope: &Scope,
+) -> Result<std::result::Result<DirectoryResource, String>> {
+    Ok(match harness.resource(kind, scope) {
+        Ok(Some(dir)) => Ok(dir),
+        Ok(None) => Err(format!(
+            "{} does not support {}",
+            harness.name(),
+            resource_label(kind)
+        )),
+        Err(e) => Err(skip_reason(e)?),
+    })
+}
+
+fn resource_label(kind: ResourceKind) -> &'static str {
+    match kind {
+        ResourceKind::Skills => "skills",
+        ResourceKind::Commands => "commands",
+        ResourceKind::Agents => "agents",
+        _ => "plugins",
+    }
+}
+
+/// Claude Code's `settings.json` for `scope`, or why hooks are unsupported.
+fn hooks_settings(
+    harness: &Harness,
+    scope: &Scope,
+) -> Result<std::result::Result<PathBuf, String>> {
+    if harness.kind() != Some(HarnessKind::ClaudeCode) {
+        return Ok(Err(format!("{} does not support hooks", harness.name())));
+    }
+    Ok(match harness.config(scope) {
+        Ok(dir) => Ok(dir.join("settings.json")),
+        Err(e) => Err(skip_reason(e)?),
+    })
+}
+
+/// Turns errors that mean "not here" into a skip reason, passing others on.
+pub(crate) fn skip_reason(error: harness_locate::Error) -> Result<String> {
+    match error {
+        harness_locate::Error::UnsupportedScope { .. }
+        | harness_locate::Error::UnsupportedMcpConfig { .. }
+        | harness_locate::Error::LossyConversion { .. }
+        | harness_locate::Error::NotFound(_) => Ok(error.to_string()),
+        other => Err(Error::Harness(other)),
+    }
+}
+
+/// Makes `slug` unique among `taken` and records it.
+fn claim(slug: String, taken: &mut HashSet<String>) -> String {
+    let name = unique_name(&slug, |candidate| taken.contains(candidate));
+    taken.insert(name.clone());
+    name
+}
+
+/// The file a command or agent named `name` is installed as.
+fn file_target(dir: &DirectoryResource, name: &str) -> PathBuf {
+    match &dir.structure {
+        DirectoryStructure::Flat { .. } => dir.path.join(format!("{name}.md")),
+        DirectoryStructure::Nested { file_name, .. } => dir.path.join(name).join(file_name),
+    }
+}
+
+fn escape_pointer(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+fn unsupported_platform(platforms: &Platforms) -> String {
+    match Platform::current() {
+        Some(current) => format!("supports {platforms}, not {current}"),
+        None => format!("supports {platforms}, not this platform"),
+    }
+}
+
+fn skip(reason: &str) -> InstallAction {
+    InstallAction::Skip {
+        reason: reason.to_string(),
+    }
+}
+
+fn step(kind: ComponentKind, name: &str, action: InstallAction) -> InstallStep {
+    InstallStep {
+        kind,
+        name: name.to_string(),
+        action,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin() -> PluginDescriptor {
+        serde_json::from_str(
+            r#"{
+                "name": "kit",
+                "skills": [{"name": "deploy", "path": "skills/deploy"}],
+                "commands": [{"name": "release", "path": "extra/release.md"}],
+                "agents": [{"name": "reviewer", "path": "agents/reviewer.md"}],
+                "hooks": {"PreToolUse": [{"matcher": "Bash", "hooks": ["check"]}]},
+                "mcp_servers": {
+                    "docs": {"transport": "sse", "url": "https://docs.example.com/sse"},
+                    "kube": {"transport": "stdio", "command": "kube-mcp"}
+                }
+            }"#,
+        )
+        .unwrap()
+    }
+
+    fn actions(plan: &InstallPlan) -> Vec<(ComponentKind, &str, &InstallAction)> {
+        plan.steps
+            .iter()
+            .map(|s| (s.kind, s.name.as_str(), &s.action))
+            .collect()
+    }
+
+    #[test]
+    fn claude_code_installs_every_component() {
+        let root = PathBuf::from("/work/app");
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let plan = plan_install(&plugin(), &harness, &Scope::Project(root.clone())).unwrap();
+
+        assert!(plan.is_complete());
+        assert_eq!(plan.plugin, "kit");
+        let claude = root.join(".claude");
+        assert_eq!(
+            actions(&plan)[..4],
+            [
+                (
+                    ComponentKind::Skill,
+                    "deploy",
+                    &InstallAction::CopyDir {
+                        source: Some("skills/deploy".into()),
+                        target: claude.join("skills/deploy"),
+                    }
+                ),
+                (
+                    ComponentKind::Command,
+                    "release",
+                    &InstallAction::CopyFile {
+                        source: Some("extra/release.md".into()),
+                        target: claude.join("commands/release.md"),
+                    }
+                ),
+                (
+                    ComponentKind::Agent,
+                    "reviewer",
+                    &InstallAction::CopyFile {
+                        source: Some("agents/reviewer.md".into()),
+                        target: claude.join("agents/reviewer.md"),
+                    }
+                ),
+                (
+                    ComponentKind::Hook,
+                    "PreToolUse",
+                    &InstallAction::EditConfig {
+                        file: claude.join("settings.json"),
+                        format: FileFormat::Json,
+                        pointer: "/hooks/PreToolUse".into(),
+                        value: serde_json::json!([{"matcher": "Bash", "hooks": ["check"]}]),
+                    }
+                ),
+            ]
+        );
+        let InstallAction::EditConfig {
+            file,
+            pointer,
+            value,
+            ..
+        } = &plan.steps[5].action
+        else {
+            panic!("expected a config edit: {:?}", plan.steps[5]);
+        };
+        assert_eq!(file, &root.join(".mcp.json"));
+        assert_eq!(pointer, "/mcpServers/kube");
+        assert_eq!(value["command"], "kube-mcp");
+    }
+
+    #[test]
+    fn goose_skips_unsupported_components_with_reasons() {
+        let harness = Harness::new(HarnessKind::Goose);
+        let plan = plan_install(&plugin(), &harness, &Scope::Custom("/profile".into())).unwrap();
+
+        let skipped: Vec<_> = plan.skipped().map(|s| (s.kind, s.name.as_str())).collect();
+        assert_eq!(
+            skipped,
+            [
+                (ComponentKind::Command, "release"),
+                (ComponentKind::Agent, "reviewer"),
+                (ComponentKind::Hook, "PreToolUse"),
+                (ComponentKind::McpServer, "docs"),
+            ]
+        );
+        assert!(matches!(
+            &plan.steps[3].action,
+            InstallAction::Skip { reason } if reason.contains("hooks")
+        ));
+        assert!(matches!(
+            &plan.steps[5].action,
+            InstallAction::EditConfig { pointer, .. } if pointer == "/extensions/kube"
+        ));
+    }
+
+    #[test]
+    fn skill_directories_follow_harness_naming_rules() {
+        let plugin: PluginDescriptor = serde_json::from_str(
+            r#"{
+                "name": "kit",
+                "skills": [{"name": "Deploy App"}, {"name": "deploy-app"}],
+                "commands": [{"name": "ship/it"}]
+            }"#,
+        )
+        .unwrap();
+        let root = PathBuf::from("/work/app");
+        let plan = plan_install(
+            &plugin,
+            &Harness::new(HarnessKind::OpenCode),
+            &Scope::Project(root.clone()),
+        )
+        .unwrap();
+
+        let targets: Vec<_> = plan
+            .steps
+            .iter()
+            .map(|step| match &step.action {
+                InstallAction::CopyDir { target, .. } | InstallAction::CopyFile { target, .. } => {
+                    target.strip_prefix(&root).unwrap().to_path_buf()
+                }
+                other => panic!("unexpected action: {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            targets,
+            [
+                PathBuf::from(".opencode/skill/deploy-app"),
+                PathBuf::from(".opencode/skill/deploy-app-2"),
+                PathBuf::from(".opencode/command/ship-it.md"),
+            ]
+        );
+        assert_eq!(plan.steps[0].name, "Deploy App");
+    }
+
+    #[test]
+    fn skips_skills_for_other_platforms() {
+        let other = if cfg!(windows) { "linux" } else { "windows" };
+        let plugin: PluginDescriptor = serde_json::from_value(serde_json::json!({
+            "name": "kit",
+            "skills": [
+                {"name": "native", "platforms": [other]},
+                {"name": "portable"}
+            ]
+        }))
+        .unwrap();
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let plan = plan_install(&plugin, &harness, &Scope::Project("/work/app".into())).unwrap();
+
+        let skipped: Vec<_> = plan.skipped().map(|step| step.name.as_str()).collect();
+        assert_eq!(skipped, ["native"]);
+        let InstallAction::Skip { reason } = &plan.steps[0].action else {
+            unreachable!()
+        };
+        assert!(reason.starts_with(&format!("supports {other}, not ")));
+    }
+}
@@ -1,25 +1,106 @@
 //! Skills discovery and fetching for AI coding agents.
 
 mod component;
+#[cfg(feature = "fetch")]
+mod config_schema;
+#[cfg(feature = "fetch")]
+mod crawl;
+mod dedupe;
+#[cfg(feature = "fetch")]
+mod deployment;
 mod detect;
+#[cfg(feature = "fetch")]
 mod discovery;
+#[cfg(feature = "fetch")]
+mod enrich;
 mod error;
+#[cfg(feature = "fetch")]
 mod fetch;
+#[cfg(feature = "fetch")]
+mod filter;
 mod github;
+#[cfg(feature = "fetch")]
+mod index;
+mod install;
 mod marketplace;
+#[cfg(any(feature = "test-fixtures", all(test, feature = "registry")))]
+mod mock;
+#[cfg(feature = "fetch")]
+mod provenance;
+#[cfg(feature = "registry")]
 mod registry;
+mod schema;
+mod score;
+#[cfg(feature = "fetch")]
+mod transport;
+mod trust;
 mod types;
+#[cfg(feature = "fetch")]
+mod update;
 
 pub use component::{
-    AgentDescriptor, CommandDescriptor, HooksConfig, ManifestConfig, McpServer, detect_npm_mcp,
-    detect_python_mcp, parse_agent_descriptor, parse_command_descriptor, parse_manifest,
-    parse_mcp_json, parse_skill_descriptor,
-};
-pub use detect::{DetectedMcp, DetectionConfidence, DetectionSource, detect_mcp_from_files};
-pub use discovery::{discover_all, discover_from_source, discover_plugins};
-pub use error::{Error, Result};
-pub use fetch::{extract_file, fetch_bytes, fetch_json, list_files};
-pub use github::GitHubRef;
+    AgentDescriptor, CommandDescriptor, ComponentConfig, HooksConfig, ManifestConfig, McpServer,
+    PluginManifest, detect_npm_mcp, detect_python_mcp, parse_agent_descriptor,
+    parse_command_descriptor, parse_hooks_json, parse_manifest, parse_mcp_json,
+    parse_plugin_manifest, parse_skill_descriptor,
+};
+#[cfg(feature = "fetch")]
+pub use config_schema::{DEFAULT_SCHEMA_MAX_AGE, SchemaCache, validate_against_schema};
+#[cfg(feature = "fetch")]
+pub use crawl::{
+    CrawlState, DEFAULT_TOPICS, OrgCrawler, OrgDiscovery, OrgFilters, OrgRepo, RateLimiter,
+    discover_org,
+};
+pub use dedupe::{DedupePolicy, Preference, SuppressedSkill};
+#[cfg(feature = "fetch")]
+pub use deployment::{
+    Deployment, DeploymentMatrix, DeploymentStatus, deployment_matrix, deployment_matrix_for,
+};
+pub use detect::{
+    DetectedMcp, DetectionConfidence, DetectionSource, PackageMetadata, PackageRegistry,
+    detect_mcp_from_files,
+};
+#[cfg(feature = "fetch")]
+pub use discovery::{
+    discover_all, discover_all_filtered, discover_all_scored, discover_all_with_policy,
+    discover_from_source, discover_from_source_filtered, discover_namespace, discover_plugins,
+};
+#[cfg(feature = "fetch")]
+pub use enrich::enrich_detected;
+pub use error::{Error, ErrorCategory, Result};
+#[cfg(feature = "fetch")]
+pub use fetch::{DebugSink, HttpExchange, extract_file, fetch_bytes, fetch_json, list_files};
+#[cfg(feature = "fetch")]
+pub use filter::PathFilter;
+pub use github::{GitHubRef, GitHubRefError};
+#[cfg(feature = "fetch")]
+pub use index::{DiscoveryIndex, IndexEntry, RefreshReport, refresh_index};
+pub use install::{ComponentKind, InstallAction, InstallPlan, InstallStep, plan_install};
 pub use marketplace::{Marketplace, MarketplaceEntry};
-pub use registry::{PackageEntry, RegistryClient, RemoteEntry, ServerEntry};
-pub use types::{DiscoveryResult, PluginDescriptor, PluginSource, SkillDescriptor};
+#[cfg(any(feature = "test-fixtures", all(test, feature = "registry")))]
+pub use mock::MockServer;
+#[cfg(feature = "fetch")]
+pub use provenance::{
+    Drift, PROVENANCE_FILE, Provenance, ProvenanceManifest, detect_drift, provenance,
+};
+#[cfg(feature = "registry")]
+pub use registry::{
+    FederatedResults, McpRunRegistry, OfficialRegistry, PackageEntry, RegistryClient,
+    RegistryResult, RegistrySource, RemoteEntry, ServerEntry, ServerJson, ServerJsonArgument,
+    ServerJsonInput, ServerJsonPackage, ServerJsonRemote, SmitheryRegistry,
+};
+#[cfg(feature = "schemars")]
+pub use schema::schemas;
+pub use schema::{SchemaKind, SchemaViolation, validate_schema, validate_value};
+pub use score::Scorer;
+#[cfg(feature = "fetch")]
+pub use transport::negotiate_transport;
+pub use trust::{TrustLevel, TrustPolicy, TrustViolation};
+pub use types::{
+    DISCOVERY_SCHEMA_VERSION, DiscoveryResult, PluginDescriptor, PluginOrigin, PluginSource,
+    SkillDescriptor,
+};
+#[cfg(feature = "fetch")]
+pub use update::{
+    InstalledSkill, SkillOrigin, SkillUpdate, UpdateReport, check_updates, content_hash,
+};
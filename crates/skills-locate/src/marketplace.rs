@@ -3,12 +3,14 @@ use serde::{Deserialize, Serialize};
 use crate::PluginSource;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct Marketplace {
     pub plugins: Vec<MarketplaceEntry>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct MarketplaceEntry {
     pub source: PluginSource,
@@ -0,0 +1,382 @@
+//! In-process mock of the registry and GitHub endpoints for tests.
+//!
+//! [`MockServer`] serves static fixtures over HTTP on a local port: the
+//! official registry API for [`RegistryClient`], and GitHub archives, raw
+//! files and commit lookups for discovery. [`MockServer::intercept`]
+//! redirects every `https://` request made through this crate's fetch
+//! functions to the mock, so `discover_all("https://github.com/...")`
+//! reads the fixtures instead of GitHub.
+//!
+//! Requests are matched by path and query first, then by path alone.
+//! Unknown paths return `404`.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use crate::fetch::with_base_url_override;
+use crate::github::GitHubRef;
+use crate::registry::{RegistryClient, encode_component};
+
+#[derive(Debug, Clone)]
+struct MockResponse {
+    status: u16,
+    content_type: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+type Routes = Arc<Mutex<HashMap<String, MockResponse>>>;
+
+/// A local HTTP server answering from static fixtures.
+///
+/// The server stops when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use skills_locate::{MockServer, discover_all};
+///
+/// let mock = MockServer::start().with_repo(
+///     "https://github.com/acme/skills",
+///     &[
+///         ("skills/deploy/SKILL.md", "---\nname: deploy\ndescription: Ship it\n---\n"),
+///         ("commands/release.md", "---\ndescription: Cut a release\n---\n"),
+///     ],
+/// );
+/// let result = mock.intercept(|| discover_all("https://github.com/acme/skills"))?;
+/// assert_eq!(result.all_skills[0].name, "deploy");
+/// # Ok::<(), skills_locate::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct MockServer {
+    addr: SocketAddr,
+    routes: Routes,
+    registry_servers: Vec<serde_json::Value>,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    /// Starts a server on a free local port.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no local port can be bound.
+    #[must_use]
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let addr = listener.local_addr().expect("mock server address");
+        let routes = Routes::default();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let handle = {
+            let routes = Arc::clone(&routes);
+            let shutdown = Arc::clone(&shutdown);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if shutdown.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        let _ = respond(stream, &routes);
+                    }
+                }
+            })
+        };
+        Self {
+            addr,
+            routes,
+            registry_servers: Vec::new(),
+            shutdown,
+            handle: Some(handle),
+        }
+    }
+
+    /// The server's base URL, such as `http://127.0.0.1:40123`.
+    #[must_use]
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Serves `body` at `path` with the given status and content type.
+    ///
+    /// `path` may include a query string to match only that query.
+    #[must_use]
+    pub fn with_response(
+        self,
+        path: &str,
+        status: u16,
+        content_type: &str,
+        body: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.routes.lock().unwrap().insert(
+            path.to_string(),
+            MockResponse {
+                status,
+                content_type: content_type.to_string(),
+                headers: Vec::new(),
+                body: body.into(),
+            },
+        );
+        self
+    }
+
+    /// Adds a header to the response served at `path`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if nothing is served at `path` yet.
+    #[must_use]
+    pub fn with_header(self, path: &str, name: &str, value: &str) -> Self {
+        self.routes
+            .lock()
+            .unwrap()
+            .get_mut(path)
+            .expect("with_header needs a response at the path")
+            .headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Serves `value` as JSON at `path`.
+    #[must_use]
+    pub fn with_json(self, path: &str, value: &serde_json::Value) -> Self {
+        self.with_response(path, 200, "application/json", value.to_string())
+    }
+
+    /// Adds a `server.json` document to the mock official registry.
+    ///
+    /// Every server is listed by `/v0/servers` regardless of the search
+    /// query, and served by name at `/v0/servers/<name>/versions/latest`.
+    #[must_use]
+    pub fn with_registry_server(mut self, server: serde_json::Value) -> Self {
+        let name = server["name"].as_str().unwrap_or_default().to_string();
+        self.registry_servers.push(server.clone());
+        let list = serde_json::json!({
+            "servers": self
+                .registry_servers
+                .iter()
+                .map(|server| serde_json::json!({ "server": server }))
+                .collect::<Vec<_>>(),
+        });
+        self.with_json("/v0/servers", &list).with_json(
+            &format!("/v0/servers/{}/versions/latest", encode_component(&name)),
+            &serde_json::json!({ "server": server }),
+        )
+    }
+
+    /// Serves a GitHub repository holding `files` at its ref.
+    ///
+    /// The repository is served as a ZIP archive laid out like GitHub's
+    /// (`<repo>-<ref>/...`), as raw files, and through the commit API with
+    /// a SHA derived from the content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `repo_url` is not a GitHub URL.
+    #[must_use]
+    pub fn with_repo(self, repo_url: &str, files: &[(&str, &str)]) -> Self {
+        let github_ref = GitHubRef::parse(repo_url).expect("GitHub repository URL");
+        let prefix = github_ref.archive_prefix();
+        let archive = zip_archive(&prefix, files);
+        let digest = ring::digest::digest(&ring::digest::SHA256, &archive);
+        let sha: String = digest.as_ref()[..20]
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect();
+
+        let mut mock = self
+            .with_response(
+                &intercepted_path(&github_ref.archive_url()),
+                200,
+                "application/zip",
+                archive,
+            )
+            .with_json(
+                &intercepted_path(&github_ref.commit_api_url()),
+                &serde_json::json!({ "sha": sha }),
+            )
+            .with_json(
+                &intercepted_path(&github_ref.tree_api_url()),
+                &git_tree(files),
+            );
+        for (path, content) in files {
+            mock = mock.with_response(
+                &intercepted_path(&github_ref.raw_url(path)),
+                200,
+                "text/plain",
+                *content,
+            );
+        }
+        mock
+    }
+
+    /// A [`RegistryClient`] for the mock official registry.
+    #[must_use]
+    pub fn registry_client(&self) -> RegistryClient {
+        RegistryClient::with_base_url(self.url())
+    }
+
+    /// Runs `f` with every `https://` request this crate makes on the
+    /// current thread sent to the mock instead.
+    ///
+    /// `https://github.com/acme/skills` is requested as
+    /// `<mock>/github.com/acme/skills`. Requests made on other threads are
+    /// not redirected, nor are those of [`OrgCrawler`](crate::OrgCrawler)
+    /// and [`negotiate_transport`](crate::negotiate_transport), which use
+    /// their own HTTP agents.
+    pub fn intercept<R>(&self, f: impl FnOnce() -> R) -> R {
+        with_base_url_override(&self.url(), f)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // Wake the accept loop so it sees the shutdown flag.
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// The mock path an intercepted `https://` URL is requested at.
+pub(crate) fn intercepted_path(url: &str) -> String {
+    format!("/{}", url.trim_start_matches("https://"))
+}
+
+fn respond(mut stream: TcpStream, routes: &Routes) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 4096];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            return Ok(());
+        }
+        request.extend_from_slice(&buf[..n]);
+    }
+    let request = String::from_utf8_lossy(&request);
+    let target = request.split_whitespace().nth(1).unwrap_or("/");
+    let response = {
+        let routes = routes.lock().unwrap();
+        let path = target.split('?').next().unwrap_or(target);
+        routes.get(target).or_else(|| routes.get(path)).cloned()
+    };
+    let response = response.unwrap_or_else(|| MockResponse {
+        status: 404,
+        content_type: "text/plain".to_string(),
+        headers: Vec::new(),
+        body: b"not found".to_vec(),
+    });
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        if response.status < 400 { "OK" } else { "Error" },
+        response.content_type,
+        response.body.len()
+    )?;
+    for (name, value) in &response.headers {
+        write!(stream, "{name}: {value}\r\n")?;
+    }
+    stream.write_all(b"\r\n")?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+/// A trees API response listing `files` and their parent directories.
+fn git_tree(files: &[(&str, &str)]) -> serde_json::Value {
+    let mut dirs = std::collections::BTreeSet::new();
+    for (path, _) in files {
+        let mut dir = *path;
+        while let Some((parent, _)) = dir.rsplit_once('/') {
+            dirs.insert(parent);
+            dir = parent;
+        }
+    }
+    let tree: Vec<_> = dirs
+        .iter()
+        .map(|path| serde_json::json!({ "path": path, "type": "tree" }))
+        .chain(
+            files
+                .iter()
+                .map(|(path, _)| serde_json::json!({ "path": path, "type": "blob" })),
+        )
+        .collect();
+    serde_json::json!({ "tree": tree, "truncated": false })
+}
+
+fn zip_archive(prefix: &str, files: &[(&str, &str)]) -> Vec<u8> {
+    let mut buf = std::io::Cursor::new(Vec::new());
+    {
+        let mut zip = zip::ZipWriter::new(&mut buf);
+        let options = zip::write::SimpleFileOptions::default();
+        // Like GitHub's archives, list the top-level directory first and
+        // every directory as an entry of its own.
+        zip.add_directory(prefix, options)
+            .expect("write mock archive");
+        let mut dirs = std::collections::BTreeSet::new();
+        for (path, _) in files {
+            let mut dir = *path;
+            while let Some((parent, _)) = dir.rsplit_once('/') {
+                dirs.insert(parent);
+                dir = parent;
+            }
+        }
+        for dir in dirs {
+            zip.add_directory(format!("{prefix}{dir}/"), options)
+                .expect("write mock archive");
+        }
+        for (name, content) in files {
+            zip.start_file(format!("{prefix}{name}"), options)
+                .expect("write mock archive");
+            zip.write_all(content.as_bytes())
+                .expect("write mock archive");
+        }
+        zip.finish().expect("write mock archive");
+    }
+    buf.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+    use crate::fetch::fetch_bytes;
+
+    #[test]
+    fn serves_registry_fixtures() {
+        let mock = MockServer::start().with_registry_server(serde_json::json!({
+            "name": "io.github.acme/weather",
+            "description": "Weather lookups",
+            "version": "1.0.0",
+            "remotes": [{ "type": "streamable-http", "url": "https://weather.example.com/mcp" }]
+        }));
+        let client = mock.registry_client();
+
+        let servers = client.search("weather").unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "io.github.acme/weather");
+        let server = client.fetch_server_json("io.github.acme/weather").unwrap();
+        assert_eq!(server.description.as_deref(), Some("Weather lookups"));
+        assert!(matches!(
+            client.fetch_server_json("io.github.acme/missing"),
+            Err(Error::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn intercepts_github_requests() {
+        let repo = "https://github.com/acme/skills";
+        let mock = MockServer::start().with_repo(repo, &[("README.md", "# Skills")]);
+        let github_ref = GitHubRef::parse(repo).unwrap();
+
+        let readme = mock.intercept(|| fetch_bytes(&github_ref.raw_url("README.md")));
+        assert_eq!(readme.unwrap(), b"# Skills");
+        assert!(fetch_bytes(&format!("{}/github.com/acme/skills/missing", mock.url())).is_err());
+    }
+}
@@ -0,0 +1,327 @@
+//! Provenance records for installed resources.
+//!
+//! Installing a skill, command, or agent records where it came from in a
+//! [`PROVENANCE_FILE`] next to it, in the resource directory. Each entry is
+//! keyed by the resource's file or directory name and holds the source
+//! repository, ref, path, and the [`content_hash`] at install time, so
+//! updates and uninstalls can tell whether the user edited the resource
+//! since.
+//!
+//! The file is hidden, so directory listings in `harness-locate` skip it.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use harness_locate::text::decode;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::github::GitHubRef;
+use crate::index::{save_json, unix_now};
+use crate::update::{InstalledSkill, SkillOrigin, content_hash};
+
+/// Name of the provenance file in each resource directory.
+pub const PROVENANCE_FILE: &str = ".harness-locate.json";
+
+/// Where an installed resource came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// GitHub URL of the source repository.
+    pub repo: String,
+    /// Branch or tag the resource was installed from.
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// The resource's path within the repository.
+    pub path: String,
+    /// [`content_hash`] of the resource when it was installed.
+    pub hash: String,
+    /// Unix timestamp (seconds) of the install.
+    pub installed_at: u64,
+}
+
+impl Provenance {
+    /// Records the current content of `installed`, copied from `path` in
+    /// `repo`, timestamped now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `repo` is not a GitHub URL or `installed` cannot
+    /// be read.
+    pub fn record(repo: &str, path: &str, installed: &Path) -> Result<Self> {
+        let github_ref = GitHubRef::parse(repo)?;
+        Ok(Self {
+            repo: format!(
+                "https://github.com/{}/{}",
+                github_ref.owner, github_ref.repo
+            ),
+            git_ref: github_ref.git_ref,
+            path: path.trim_matches('/').to_string(),
+            hash: content_hash(installed)?,
+            installed_at: unix_now(),
+        })
+    }
+
+    /// The source as a [`SkillOrigin`] for [`check_updates`](crate::check_updates).
+    #[must_use]
+    pub fn origin(&self) -> SkillOrigin {
+        SkillOrigin {
+            repo: format!("{}/tree/{}", self.repo, self.git_ref),
+            path: self.path.clone(),
+            hash: Some(self.hash.clone()),
+        }
+    }
+}
+
+/// How an installed resource compares to its provenance record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Drift {
+    /// The content matches the recorded hash.
+    Unmodified,
+    /// The content was changed after it was installed.
+    Modified {
+        /// The hash recorded at install time.
+        recorded: String,
+        /// The hash of the current content.
+        current: String,
+    },
+    /// The resource has a record but no longer exists.
+    Missing,
+    /// The resource has no provenance record, so it was not installed by
+    /// this crate or predates provenance tracking.
+    Untracked,
+}
+
+impl Drift {
+    /// Returns `true` if overwriting or removing the resource cannot lose
+    /// user edits.
+    #[must_use]
+    pub fn is_safe_to_overwrite(&self) -> bool {
+        matches!(self, Self::Unmodified | Self::Missing)
+    }
+}
+
+/// The provenance records of one resource directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::path::Path;
+/// use skills_locate::{Provenance, ProvenanceManifest};
+///
+/// let skills = Path::new("/home/me/.claude/skills");
+/// let mut manifest = ProvenanceManifest::load(skills)?;
+/// manifest.insert(
+///     "deploy",
+///     Provenance::record("https://github.com/acme/skills", "skills/deploy", &skills.join("deploy"))?,
+/// );
+/// manifest.save()?;
+///
+/// if !manifest.drift("deploy")?.is_safe_to_overwrite() {
+///     println!("deploy was edited locally");
+/// }
+/// # Ok::<(), skills_locate::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProvenanceManifest {
+    dir: PathBuf,
+    entries: BTreeMap<String, Provenance>,
+}
+
+impl ProvenanceManifest {
+    /// Loads the [`PROVENANCE_FILE`] in `dir`, or returns an empty manifest
+    /// if there is none.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let entries = match std::fs::read(dir.join(PROVENANCE_FILE)) {
+            Ok(bytes) => serde_json::from_str(&decode(&bytes)?.text)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// Writes the manifest to the [`PROVENANCE_FILE`] in its directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self) -> Result<()> {
+        save_json(&self.dir.join(PROVENANCE_FILE), &self.entries)
+    }
+
+    /// The resource directory this manifest describes.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the record for the resource named `name`.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Provenance> {
+        self.entries.get(name)
+    }
+
+    /// Iterates over the records, sorted by resource name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Provenance)> {
+        self.entries.iter().map(|(name, p)| (name.as_str(), p))
+    }
+
+    /// Records `provenance` for the resource named `name`, returning the
+    /// previous record.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        provenance: Provenance,
+    ) -> Option<Provenance> {
+        self.entries.insert(name.into(), provenance)
+    }
+
+    /// Forgets the resource named `name`, returning its record.
+    pub fn remove(&mut self, name: &str) -> Option<Provenance> {
+        self.entries.remove(name)
+    }
+
+    /// Compares the resource named `name` with its record.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the resource exists but cannot be read.
+    pub fn drift(&self, name: &str) -> Result<Drift> {
+        let Some(provenance) = self.entries.get(name) else {
+            return Ok(Drift::Untracked);
+        };
+        let path = self.dir.join(name);
+        if !path.exists() {
+            return Ok(Drift::Missing);
+        }
+        let current = content_hash(&path)?;
+        if current == provenance.hash {
+            Ok(Drift::Unmodified)
+        } else {
+            Ok(Drift::Modified {
+                recorded: provenance.hash.clone(),
+                current,
+            })
+        }
+    }
+
+    /// The recorded resources as skills for
+    /// [`check_updates`](crate::check_updates).
+    #[must_use]
+    pub fn installed_skills(&self) -> Vec<InstalledSkill> {
+        self.iter()
+            .map(|(name, provenance)| InstalledSkill {
+                name: name.to_string(),
+                dir: self.dir.join(name),
+                origin: provenance.origin(),
+            })
+            .collect()
+    }
+}
+
+/// Returns the provenance record of the resource at `path`.
+///
+/// # Errors
+///
+/// Returns an error if the [`PROVENANCE_FILE`] beside `path` cannot be
+/// read or parsed.
+pub fn provenance(path: &Path) -> Result<Option<Provenance>> {
+    let (dir, name) = split(path);
+    Ok(ProvenanceManifest::load(dir)?.get(&name).cloned())
+}
+
+/// Compares the resource at `path` with its provenance record.
+///
+/// # Errors
+///
+/// Returns an error if the [`PROVENANCE_FILE`] beside `path` or the
+/// resource itself cannot be read.
+pub fn detect_drift(path: &Path) -> Result<Drift> {
+    let (dir, name) = split(path);
+    ProvenanceManifest::load(dir)?.drift(&name)
+}
+
+fn split(path: &Path) -> (&Path, String) {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    (dir, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "skills-locate-provenance-{}-{name}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn records_round_trip_and_detect_drift() {
+        let dir = resource_dir("drift");
+        std::fs::create_dir_all(dir.join("deploy")).unwrap();
+        std::fs::write(dir.join("deploy/SKILL.md"), "v1").unwrap();
+        std::fs::write(dir.join("release.md"), "release").unwrap();
+
+        let mut manifest = ProvenanceManifest::load(&dir).unwrap();
+        for (name, path) in [
+            ("deploy", "skills/deploy"),
+            ("release.md", "commands/release.md"),
+        ] {
+            let record =
+                Provenance::record("https://github.com/acme/kit/tree/v2", path, &dir.join(name))
+                    .unwrap();
+            manifest.insert(name, record);
+        }
+        manifest.save().unwrap();
+
+        let loaded = ProvenanceManifest::load(&dir).unwrap();
+        assert_eq!(loaded, manifest);
+        let record = provenance(&dir.join("deploy")).unwrap().unwrap();
+        assert_eq!(record.repo, "https://github.com/acme/kit");
+        assert_eq!(record.git_ref, "v2");
+        assert_eq!(record.origin().repo, "https://github.com/acme/kit/tree/v2");
+
+        assert_eq!(
+            detect_drift(&dir.join("deploy")).unwrap(),
+            Drift::Unmodified
+        );
+        std::fs::write(dir.join("deploy/SKILL.md"), "v1, edited").unwrap();
+        let drift = detect_drift(&dir.join("deploy")).unwrap();
+        assert!(matches!(drift, Drift::Modified { ref recorded, .. } if *recorded == record.hash));
+        assert!(!drift.is_safe_to_overwrite());
+
+        std::fs::remove_file(dir.join("release.md")).unwrap();
+        assert_eq!(loaded.drift("release.md").unwrap(), Drift::Missing);
+        assert_eq!(loaded.drift("other").unwrap(), Drift::Untracked);
+        assert_eq!(loaded.installed_skills()[0].dir, dir.join("deploy"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn provenance_file_is_not_part_of_the_content_hash() {
+        let dir = resource_dir("hash");
+        std::fs::write(dir.join("SKILL.md"), "v1").unwrap();
+        let before = content_hash(&dir).unwrap();
+        ProvenanceManifest::load(&dir).unwrap().save().unwrap();
+        assert!(dir.join(PROVENANCE_FILE).exists());
+        assert_eq!(content_hash(&dir).unwrap(), before);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
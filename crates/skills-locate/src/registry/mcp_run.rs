@@ -0,0 +1,104 @@
+//! mcp.run registry adapter.
+
+use serde::Deserialize;
+
+use crate::error::Error;
+
+use super::source::RegistrySource;
+use super::{PackageEntry, ServerEntry, encode_component, fetch_json_with_auth};
+
+const MCP_RUN_BASE_URL: &str = "https://www.mcp.run";
+
+/// Searches the mcp.run servlet catalog.
+///
+/// mcp.run servlets are installed into a user profile and served by the
+/// `mcpx` gateway, so every result converts to the same
+/// `npx -y @dylibso/mcpx` server.
+#[derive(Debug, Clone)]
+pub struct McpRunRegistry {
+    base_url: String,
+}
+
+impl Default for McpRunRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl McpRunRegistry {
+    pub fn new() -> Self {
+        Self::with_base_url(MCP_RUN_BASE_URL)
+    }
+
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Servlet {
+    slug: String,
+    #[serde(default)]
+    meta: ServletMeta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ServletMeta {
+    #[serde(default)]
+    description: Option<String>,
+}
+
+impl Servlet {
+    fn into_entry(self) -> ServerEntry {
+        ServerEntry {
+            name: self.slug.clone(),
+            id: self.slug,
+            description: self.meta.description,
+            packages: vec![PackageEntry {
+                registry: "npm".to_string(),
+                name: "@dylibso/mcpx".to_string(),
+                version: None,
+                runtime: None,
+                arguments: vec![],
+                environment_variables: Default::default(),
+            }],
+            remotes: vec![],
+        }
+    }
+}
+
+impl RegistrySource for McpRunRegistry {
+    fn id(&self) -> &str {
+        "mcp.run"
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<ServerEntry>, Error> {
+        let url = format!(
+            "{}/api/servlets?q={}",
+            self.base_url,
+            encode_component(query)
+        );
+        let servlets: Vec<Servlet> = fetch_json_with_auth(&url, None)?;
+        Ok(servlets.into_iter().map(Servlet::into_entry).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn servlet_maps_to_gateway_package() {
+        let servlets: Vec<Servlet> = serde_json::from_str(
+            r#"[{"slug": "dylibso/fetch", "meta": {"description": "Fetch URLs"}}]"#,
+        )
+        .unwrap();
+        let entry = servlets.into_iter().next().unwrap().into_entry();
+
+        assert_eq!(entry.id, "dylibso/fetch");
+        assert_eq!(entry.description.as_deref(), Some("Fetch URLs"));
+        assert_eq!(entry.packages[0].name, "@dylibso/mcpx");
+    }
+}
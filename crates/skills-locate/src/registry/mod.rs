@@ -0,0 +1,872 @@
+//! MCP Registry API client for fetching server metadata.
+
+use harness_locate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
+use harness_locate::types::EnvValue;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use crate::error::Error;
+use crate::fetch::{DebugSink, fetch_json, get, with_debug_sink};
+use crate::trust::TrustPolicy;
+
+mod mcp_run;
+mod smithery;
+mod source;
+
+pub use mcp_run::McpRunRegistry;
+pub use smithery::SmitheryRegistry;
+pub use source::{FederatedResults, OfficialRegistry, RegistryResult, RegistrySource};
+
+const OFFICIAL_BASE_URL: &str = "https://registry.modelcontextprotocol.io";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServerEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub packages: Vec<PackageEntry>,
+    #[serde(default)]
+    pub remotes: Vec<RemoteEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackageEntry {
+    pub registry: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub runtime: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub environment_variables: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoteEntry {
+    pub transport_type: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+/// A server definition in the official MCP registry's `server.json` format.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerJson {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub packages: Vec<ServerJsonPackage>,
+    #[serde(default)]
+    pub remotes: Vec<ServerJsonRemote>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerJsonPackage {
+    pub registry_type: String,
+    pub identifier: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub runtime_hint: Option<String>,
+    #[serde(default)]
+    pub package_arguments: Vec<ServerJsonArgument>,
+    #[serde(default)]
+    pub environment_variables: Vec<ServerJsonInput>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerJsonRemote {
+    #[serde(rename = "type")]
+    pub transport_type: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: Vec<ServerJsonInput>,
+}
+
+/// A positional or named command-line argument.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerJsonArgument {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub value_hint: Option<String>,
+}
+
+/// A named input such as an environment variable or HTTP header.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerJsonInput {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+    #[serde(default)]
+    pub is_required: bool,
+    #[serde(default)]
+    pub is_secret: bool,
+}
+
+impl ServerEntry {
+    /// Converts every package and remote to a normalized MCP server, sorted
+    /// by name.
+    pub fn to_mcp_servers(&self) -> BTreeMap<String, McpServer> {
+        let mut servers = BTreeMap::new();
+
+        for (i, pkg) in self.packages.iter().enumerate() {
+            let name = if self.packages.len() == 1 {
+                self.id.clone()
+            } else {
+                format!("{}-{}", self.id, i)
+            };
+
+            if let Some(server) = pkg.to_mcp_server() {
+                servers.insert(name, server);
+            }
+        }
+
+        for (i, remote) in self.remotes.iter().enumerate() {
+            let name = if self.remotes.len() == 1 && self.packages.is_empty() {
+                self.id.clone()
+            } else {
+                format!("{}-remote-{}", self.id, i)
+            };
+
+            if let Some(server) = remote.to_mcp_server() {
+                servers.insert(name, server);
+            }
+        }
+
+        servers
+    }
+}
+
+impl ServerJson {
+    pub fn to_server_entry(&self) -> ServerEntry {
+        ServerEntry {
+            id: self.name.clone(),
+            name: self.name.clone(),
+            description: self.description.clone(),
+            packages: self.packages.iter().map(PackageEntry::from).collect(),
+            remotes: self.remotes.iter().map(RemoteEntry::from).collect(),
+        }
+    }
+
+    /// Converts every package and remote to a normalized MCP server.
+    ///
+    /// Server names follow [`ServerEntry::to_mcp_servers`]. Environment
+    /// variables and headers without a value or default are emitted as
+    /// references to a variable of the same name.
+    pub fn to_mcp_servers(&self) -> BTreeMap<String, McpServer> {
+        let mut servers = self.to_server_entry().to_mcp_servers();
+        let id = &self.name;
+
+        for (i, pkg) in self.packages.iter().enumerate() {
+            let name = if self.packages.len() == 1 {
+                id.clone()
+            } else {
+                format!("{id}-{i}")
+            };
+            if let Some(McpServer::Stdio(server)) = servers.get_mut(&name) {
+                fill_unset_inputs(&mut server.env, &pkg.environment_variables);
+            }
+        }
+
+        for (i, remote) in self.remotes.iter().enumerate() {
+            let name = if self.remotes.len() == 1 && self.packages.is_empty() {
+                id.clone()
+            } else {
+                format!("{id}-remote-{i}")
+            };
+            match servers.get_mut(&name) {
+                Some(McpServer::Sse(server)) => {
+                    fill_unset_inputs(&mut server.headers, &remote.headers)
+                }
+                Some(McpServer::Http(server)) => {
+                    fill_unset_inputs(&mut server.headers, &remote.headers)
+                }
+                _ => {}
+            }
+        }
+
+        servers
+    }
+}
+
+fn fill_unset_inputs(target: &mut HashMap<String, EnvValue>, inputs: &[ServerJsonInput]) {
+    for input in inputs {
+        if input.resolved_value().is_none() {
+            target.insert(input.name.clone(), EnvValue::env(&input.name));
+        }
+    }
+}
+
+impl ServerJsonInput {
+    fn resolved_value(&self) -> Option<&str> {
+        self.value.as_deref().or(self.default.as_deref())
+    }
+}
+
+impl ServerJsonArgument {
+    fn to_args(&self) -> Vec<String> {
+        let value = self
+            .value
+            .as_deref()
+            .or(self.default.as_deref())
+            .or(self.value_hint.as_deref());
+        match (self.kind.as_str(), &self.name) {
+            ("named", Some(name)) => match value {
+                Some(v) => vec![name.clone(), v.to_string()],
+                None => vec![name.clone()],
+            },
+            _ => value.map(str::to_string).into_iter().collect(),
+        }
+    }
+}
+
+impl From<&ServerJsonPackage> for PackageEntry {
+    fn from(pkg: &ServerJsonPackage) -> Self {
+        // `npx` and `uvx` are what `to_mcp_server` already runs for these
+        // registries; any other hint names an interpreter.
+        let runtime = pkg
+            .runtime_hint
+            .clone()
+            .filter(|hint| !matches!(hint.as_str(), "npx" | "uvx" | "docker"));
+
+        Self {
+            registry: pkg.registry_type.clone(),
+            name: pkg.identifier.clone(),
+            version: pkg.version.clone(),
+            runtime,
+            arguments: pkg
+                .package_arguments
+                .iter()
+                .flat_map(ServerJsonArgument::to_args)
+                .collect(),
+            environment_variables: pkg
+                .environment_variables
+                .iter()
+                .filter_map(|var| Some((var.name.clone(), var.resolved_value()?.to_string())))
+                .collect(),
+        }
+    }
+}
+
+impl From<&ServerJsonRemote> for RemoteEntry {
+    fn from(remote: &ServerJsonRemote) -> Self {
+        Self {
+            transport_type: remote.transport_type.clone(),
+            url: remote.url.clone(),
+            headers: remote
+                .headers
+                .iter()
+                .filter_map(|h| Some((h.name.clone(), h.resolved_value()?.to_string())))
+                .collect(),
+        }
+    }
+}
+
+impl PackageEntry {
+    /// Returns the identifier a [`TrustPolicy`](crate::TrustPolicy) matches
+    /// for this package: its registry and name, such as
+    /// `npm/@modelcontextprotocol/server-filesystem`.
+    #[must_use]
+    pub fn source(&self) -> String {
+        format!("{}/{}", self.registry, self.name)
+    }
+
+    pub fn to_mcp_server(&self) -> Option<McpServer> {
+        let (command, base_args) = match (self.registry.as_str(), self.runtime.as_deref()) {
+            ("npm", _) => {
+                let pkg = if let Some(v) = &self.version {
+                    format!("{}@{}", self.name, v)
+                } else {
+                    self.name.clone()
+                };
+                ("npx".to_string(), vec!["-y".to_string(), pkg])
+            }
+            ("pip" | "pypi", Some(runtime)) => {
+                let pkg = if let Some(v) = &self.version {
+                    format!("{}=={}", self.name, v)
+                } else {
+                    self.name.clone()
+                };
+                (
+                    runtime.to_string(),
+                    vec!["-m".to_string(), "pip".to_string(), "run".to_string(), pkg],
+                )
+            }
+            ("pip" | "pypi", None) => {
+                let pkg = if let Some(v) = &self.version {
+                    format!("{}=={}", self.name, v)
+                } else {
+                    self.name.clone()
+                };
+                ("uvx".to_string(), vec![pkg])
+            }
+            ("oci" | "docker", _) => {
+                let image = if let Some(v) = &self.version {
+                    format!("{}:{}", self.name, v)
+                } else {
+                    self.name.clone()
+                };
+                let mut args = vec!["run".to_string(), "-i".to_string(), "--rm".to_string()];
+                // Sorted so the same package always gives the same command.
+                let mut keys: Vec<_> = self.environment_variables.keys().collect();
+                keys.sort();
+                for key in keys {
+                    args.push("-e".to_string());
+                    args.push(key.clone());
+                }
+                args.push(image);
+                ("docker".to_string(), args)
+            }
+            _ => return None,
+        };
+
+        let mut args = base_args;
+        args.extend(self.arguments.iter().cloned());
+
+        let env: HashMap<String, EnvValue> = self
+            .environment_variables
+            .iter()
+            .map(|(k, v)| (k.clone(), EnvValue::plain(v)))
+            .collect();
+
+        Some(McpServer::Stdio(StdioMcpServer {
+            command,
+            args,
+            env,
+            timeout_ms: None,
+            enabled: true,
+            cwd: None,
+        }))
+    }
+}
+
+impl RemoteEntry {
+    pub fn to_mcp_server(&self) -> Option<McpServer> {
+        let headers: HashMap<String, EnvValue> = self
+            .headers
+            .iter()
+            .map(|(k, v)| (k.clone(), EnvValue::plain(v)))
+            .collect();
+
+        match self.transport_type.as_str() {
+            "sse" => Some(McpServer::Sse(SseMcpServer {
+                url: self.url.clone(),
+                headers,
+                timeout_ms: None,
+                enabled: true,
+            })),
+            "http" | "streamable-http" => Some(McpServer::Http(HttpMcpServer {
+                url: self.url.clone(),
+                headers,
+                timeout_ms: None,
+                enabled: true,
+                oauth: None,
+            })),
+            _ => None,
+        }
+    }
+}
+
+pub struct RegistryClient {
+    base_url: String,
+    sources: Vec<Box<dyn RegistrySource>>,
+    policy: Option<TrustPolicy>,
+    debug_sink: Option<Arc<dyn DebugSink>>,
+}
+
+impl Default for RegistryClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegistryClient {
+    pub fn new() -> Self {
+        Self::with_base_url(OFFICIAL_BASE_URL)
+    }
+
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            sources: Vec::new(),
+            policy: None,
+            debug_sink: None,
+        }
+    }
+
+    /// Adds a registry to query in [`search_all`](Self::search_all).
+    ///
+    /// Sources are queried in the order they are added. Without any added
+    /// sources, only the official registry at this client's base URL is used.
+    pub fn with_source(mut self, source: impl RegistrySource + 'static) -> Self {
+        self.sources.push(Box::new(source));
+        self
+    }
+
+    /// Filters [`search_all`](Self::search_all) results through `policy`.
+    ///
+    /// Servers with a remote URL the policy blocks are left out and their
+    /// ids listed in [`FederatedResults::blocked`].
+    pub fn with_policy(mut self, policy: TrustPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Sends the URL, status code, response size and timing of every
+    /// request this client makes to `sink`, including requests of added
+    /// sources.
+    ///
+    /// Bodies and headers are never recorded, so API keys and tokens stay
+    /// out of the log.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use skills_locate::{HttpExchange, RegistryClient};
+    ///
+    /// let client = RegistryClient::new().with_debug_sink(|exchange: &HttpExchange| {
+    ///     eprintln!("GET {} -> {:?} ({:?} bytes)", exchange.url, exchange.status, exchange.response_bytes);
+    /// });
+    /// let servers = client.search("github")?;
+    /// # Ok::<(), skills_locate::Error>(())
+    /// ```
+    pub fn with_debug_sink(mut self, sink: impl DebugSink + 'static) -> Self {
+        self.debug_sink = Some(Arc::new(sink));
+        self
+    }
+
+    /// Searches every configured registry and merges the results by server id.
+    pub fn search_all(&self, query: &str) -> FederatedResults {
+        let mut results = with_debug_sink(self.debug_sink.as_ref(), || {
+            if self.sources.is_empty() {
+                let official: Vec<Box<dyn RegistrySource>> =
+                    vec![Box::new(OfficialRegistry::with_base_url(&self.base_url))];
+                source::federate(&official, query)
+            } else {
+                source::federate(&self.sources, query)
+            }
+        });
+        if let Some(policy) = &self.policy {
+            results.apply_policy(policy);
+        }
+        results
+    }
+
+    pub fn fetch_server(&self, id: &str) -> Result<ServerEntry, Error> {
+        let url = format!("{}/servers/{}", self.base_url, id);
+        let bytes = with_debug_sink(self.debug_sink.as_ref(), || get(&url, None))?;
+        let entry: ServerEntry = serde_json::from_slice(&bytes).map_err(|e| Error::Parse {
+            path: url.clone(),
+            message: e.to_string(),
+        })?;
+        Ok(entry)
+    }
+
+    /// Searches the official registry for servers matching `query`.
+    ///
+    /// Returns the first page of results.
+    pub fn search(&self, query: &str) -> Result<Vec<ServerJson>, Error> {
+        let url = format!(
+            "{}/v0/servers?search={}",
+            self.base_url,
+            encode_component(query)
+        );
+        let list: ServerList = with_debug_sink(self.debug_sink.as_ref(), || fetch_json(&url))?;
+        Ok(list
+            .servers
+            .into_iter()
+            .map(ServerListItem::into_server)
+            .collect())
+    }
+
+    /// Fetches the latest `server.json` for a server name such as
+    /// `io.github.owner/server`.
+    pub fn fetch_server_json(&self, name: &str) -> Result<ServerJson, Error> {
+        let url = format!(
+            "{}/v0/servers/{}/versions/latest",
+            self.base_url,
+            encode_component(name)
+        );
+        let item: ServerListItem = with_debug_sink(self.debug_sink.as_ref(), || fetch_json(&url))?;
+        Ok(item.into_server())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ServerList {
+    #[serde(default)]
+    servers: Vec<ServerListItem>,
+}
+
+/// Registry responses wrap `server.json` alongside registry metadata; older
+/// deployments return it bare.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ServerListItem {
+    Wrapped { server: ServerJson },
+    Bare(ServerJson),
+}
+
+impl ServerListItem {
+    fn into_server(self) -> ServerJson {
+        match self {
+            Self::Wrapped { server } | Self::Bare(server) => server,
+        }
+    }
+}
+
+fn fetch_json_with_auth<T: serde::de::DeserializeOwned>(
+    url: &str,
+    token: Option<&str>,
+) -> Result<T, Error> {
+    let Some(token) = token else {
+        return fetch_json(url);
+    };
+    let bytes = get(url, Some(&format!("Bearer {token}")))?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+pub(crate) fn encode_component(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serves `response` to the first connection on a local port.
+    fn serve_once(response: &'static str) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request);
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    #[test]
+    fn debug_sink_records_request_metadata() {
+        let base_url = serve_once(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 15\r\nConnection: close\r\n\r\n{\"servers\": []}",
+        );
+        let exchanges = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&exchanges);
+        let client = RegistryClient::with_base_url(base_url.clone()).with_debug_sink(
+            move |exchange: &crate::HttpExchange| {
+                sink.lock().unwrap().push(exchange.clone());
+            },
+        );
+
+        assert!(client.search("git hub").unwrap().is_empty());
+
+        let exchanges = exchanges.lock().unwrap();
+        assert_eq!(exchanges.len(), 1);
+        assert_eq!(exchanges[0].method, "GET");
+        assert_eq!(
+            exchanges[0].url,
+            format!("{base_url}/v0/servers?search=git%20hub")
+        );
+        assert_eq!(exchanges[0].status, Some(200));
+        assert_eq!(exchanges[0].response_bytes, Some(15));
+        assert_eq!(exchanges[0].error, None);
+    }
+
+    #[test]
+    fn package_entry_npm_to_mcp_server() {
+        let pkg = PackageEntry {
+            registry: "npm".to_string(),
+            name: "@modelcontextprotocol/server-filesystem".to_string(),
+            version: Some("1.0.0".to_string()),
+            runtime: None,
+            arguments: vec!["--root".to_string(), "/tmp".to_string()],
+            environment_variables: HashMap::new(),
+        };
+
+        let server = pkg.to_mcp_server().expect("should create server");
+        match server {
+            McpServer::Stdio(s) => {
+                assert_eq!(s.command, "npx");
+                assert_eq!(
+                    s.args,
+                    vec![
+                        "-y",
+                        "@modelcontextprotocol/server-filesystem@1.0.0",
+                        "--root",
+                        "/tmp"
+                    ]
+                );
+            }
+            _ => panic!("expected Stdio"),
+        }
+    }
+
+    #[test]
+    fn package_entry_pip_to_mcp_server() {
+        let pkg = PackageEntry {
+            registry: "pip".to_string(),
+            name: "mcp-server-fetch".to_string(),
+            version: None,
+            runtime: None,
+            arguments: vec![],
+            environment_variables: HashMap::new(),
+        };
+
+        let server = pkg.to_mcp_server().expect("should create server");
+        match server {
+            McpServer::Stdio(s) => {
+                assert_eq!(s.command, "uvx");
+                assert_eq!(s.args, vec!["mcp-server-fetch"]);
+            }
+            _ => panic!("expected Stdio"),
+        }
+    }
+
+    #[test]
+    fn remote_entry_sse_to_mcp_server() {
+        let remote = RemoteEntry {
+            transport_type: "sse".to_string(),
+            url: "https://example.com/sse".to_string(),
+            headers: HashMap::new(),
+        };
+
+        let server = remote.to_mcp_server().expect("should create server");
+        match server {
+            McpServer::Sse(s) => {
+                assert_eq!(s.url, "https://example.com/sse");
+            }
+            _ => panic!("expected Sse"),
+        }
+    }
+
+    #[test]
+    fn remote_entry_http_to_mcp_server() {
+        let remote = RemoteEntry {
+            transport_type: "http".to_string(),
+            url: "https://example.com/mcp".to_string(),
+            headers: HashMap::new(),
+        };
+
+        let server = remote.to_mcp_server().expect("should create server");
+        match server {
+            McpServer::Http(s) => {
+                assert_eq!(s.url, "https://example.com/mcp");
+            }
+            _ => panic!("expected Http"),
+        }
+    }
+
+    #[test]
+    fn server_entry_to_mcp_servers() {
+        let entry = ServerEntry {
+            id: "test-server".to_string(),
+            name: "Test Server".to_string(),
+            description: None,
+            packages: vec![PackageEntry {
+                registry: "npm".to_string(),
+                name: "test-pkg".to_string(),
+                version: None,
+                runtime: None,
+                arguments: vec![],
+                environment_variables: HashMap::new(),
+            }],
+            remotes: vec![RemoteEntry {
+                transport_type: "http".to_string(),
+                url: "https://example.com".to_string(),
+                headers: HashMap::new(),
+            }],
+        };
+
+        let servers = entry.to_mcp_servers();
+        assert_eq!(servers.len(), 2);
+        assert!(servers.contains_key("test-server"));
+        assert!(servers.contains_key("test-server-remote-0"));
+    }
+
+    const SERVER_JSON: &str = r#"{
+        "name": "io.github.example/weather",
+        "description": "Weather data",
+        "version": "1.2.0",
+        "packages": [{
+            "registryType": "npm",
+            "identifier": "@example/weather-mcp",
+            "version": "1.2.0",
+            "runtimeHint": "npx",
+            "transport": {"type": "stdio"},
+            "packageArguments": [
+                {"type": "named", "name": "--units", "value": "metric"},
+                {"type": "positional", "valueHint": "city"}
+            ],
+            "environmentVariables": [
+                {"name": "WEATHER_API_KEY", "isRequired": true, "isSecret": true},
+                {"name": "LOG_LEVEL", "default": "info"}
+            ]
+        }],
+        "remotes": [{
+            "type": "streamable-http",
+            "url": "https://weather.example.com/mcp",
+            "headers": [{"name": "Authorization", "isSecret": true}]
+        }]
+    }"#;
+
+    #[test]
+    fn server_json_maps_to_package_entry() {
+        let server: ServerJson = serde_json::from_str(SERVER_JSON).unwrap();
+        let entry = server.to_server_entry();
+
+        assert_eq!(entry.id, "io.github.example/weather");
+        let pkg = &entry.packages[0];
+        assert_eq!(pkg.registry, "npm");
+        assert_eq!(pkg.name, "@example/weather-mcp");
+        assert_eq!(pkg.runtime, None);
+        assert_eq!(pkg.arguments, vec!["--units", "metric", "city"]);
+        assert_eq!(pkg.environment_variables.get("LOG_LEVEL").unwrap(), "info");
+        assert!(!pkg.environment_variables.contains_key("WEATHER_API_KEY"));
+        assert_eq!(entry.remotes[0].transport_type, "streamable-http");
+    }
+
+    #[test]
+    fn server_json_to_mcp_servers_references_unset_secrets() {
+        let server: ServerJson = serde_json::from_str(SERVER_JSON).unwrap();
+        let servers = server.to_mcp_servers();
+
+        match &servers["io.github.example/weather"] {
+            McpServer::Stdio(s) => {
+                assert_eq!(s.command, "npx");
+                assert_eq!(
+                    s.env.get("WEATHER_API_KEY"),
+                    Some(&EnvValue::env("WEATHER_API_KEY"))
+                );
+                assert_eq!(s.env.get("LOG_LEVEL"), Some(&EnvValue::plain("info")));
+            }
+            _ => panic!("expected Stdio"),
+        }
+        match &servers["io.github.example/weather-remote-0"] {
+            McpServer::Http(s) => {
+                assert_eq!(s.url, "https://weather.example.com/mcp");
+                assert_eq!(
+                    s.headers.get("Authorization"),
+                    Some(&EnvValue::env("Authorization"))
+                );
+            }
+            _ => panic!("expected Http"),
+        }
+    }
+
+    #[test]
+    fn server_list_accepts_wrapped_and_bare_entries() {
+        let json = format!(
+            r#"{{"servers": [{{"server": {SERVER_JSON}, "_meta": {{}}}}, {SERVER_JSON}]}}"#
+        );
+        let list: ServerList = serde_json::from_str(&json).unwrap();
+        assert_eq!(list.servers.len(), 2);
+        for item in list.servers {
+            assert_eq!(item.into_server().name, "io.github.example/weather");
+        }
+    }
+
+    #[test]
+    fn oci_package_runs_docker() {
+        let pkg = PackageEntry {
+            registry: "oci".to_string(),
+            name: "ghcr.io/example/server".to_string(),
+            version: Some("1.0".to_string()),
+            runtime: None,
+            arguments: vec![],
+            environment_variables: HashMap::new(),
+        };
+
+        match pkg.to_mcp_server().expect("should create server") {
+            McpServer::Stdio(s) => {
+                assert_eq!(s.command, "docker");
+                assert_eq!(
+                    s.args,
+                    vec!["run", "-i", "--rm", "ghcr.io/example/server:1.0"]
+                );
+            }
+            _ => panic!("expected Stdio"),
+        }
+    }
+
+    #[test]
+    fn oci_package_passes_env_in_sorted_order() {
+        let pkg = PackageEntry {
+            registry: "docker".to_string(),
+            name: "example/server".to_string(),
+            version: None,
+            runtime: None,
+            arguments: vec!["--stdio".to_string()],
+            environment_variables: ["ZETA", "ALPHA", "MIDDLE", "BETA"]
+                .into_iter()
+                .map(|key| (key.to_string(), String::new()))
+                .collect(),
+        };
+
+        let Some(McpServer::Stdio(s)) = pkg.to_mcp_server() else {
+            panic!("expected Stdio");
+        };
+        assert_eq!(
+            s.args,
+            [
+                "run",
+                "-i",
+                "--rm",
+                "-e",
+                "ALPHA",
+                "-e",
+                "BETA",
+                "-e",
+                "MIDDLE",
+                "-e",
+                "ZETA",
+                "example/server",
+                "--stdio"
+            ]
+        );
+    }
+
+    #[test]
+    fn encode_component_escapes_reserved_characters() {
+        assert_eq!(
+            encode_component("io.github.example/weather"),
+            "io.github.example%2Fweather"
+        );
+        assert_eq!(encode_component("a b"), "a%20b");
+    }
+}
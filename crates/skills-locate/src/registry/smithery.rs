@@ -0,0 +1,150 @@
+//! Smithery (smithery.ai) registry adapter.
+
+use serde::Deserialize;
+
+use crate::error::Error;
+
+use super::source::RegistrySource;
+use super::{PackageEntry, RemoteEntry, ServerEntry, encode_component, fetch_json_with_auth};
+
+const SMITHERY_BASE_URL: &str = "https://registry.smithery.ai";
+const SMITHERY_SERVER_URL: &str = "https://server.smithery.ai";
+
+/// Searches the Smithery registry.
+///
+/// Hosted servers map to a streamable HTTP remote; others run locally
+/// through the Smithery CLI (`npx -y @smithery/cli run <name>`).
+#[derive(Debug, Clone)]
+pub struct SmitheryRegistry {
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl Default for SmitheryRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SmitheryRegistry {
+    pub fn new() -> Self {
+        Self::with_base_url(SMITHERY_BASE_URL)
+    }
+
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            api_key: None,
+        }
+    }
+
+    /// Sets the bearer token sent with each request.
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SmitheryList {
+    #[serde(default)]
+    servers: Vec<SmitheryServer>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SmitheryServer {
+    qualified_name: String,
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default, alias = "isDeployed")]
+    remote: bool,
+}
+
+impl SmitheryServer {
+    fn into_entry(self) -> ServerEntry {
+        let (packages, remotes) = if self.remote {
+            let remote = RemoteEntry {
+                transport_type: "streamable-http".to_string(),
+                url: format!("{SMITHERY_SERVER_URL}/{}/mcp", self.qualified_name),
+                headers: Default::default(),
+            };
+            (vec![], vec![remote])
+        } else {
+            let package = PackageEntry {
+                registry: "npm".to_string(),
+                name: "@smithery/cli".to_string(),
+                version: None,
+                runtime: None,
+                arguments: vec!["run".to_string(), self.qualified_name.clone()],
+                environment_variables: Default::default(),
+            };
+            (vec![package], vec![])
+        };
+
+        ServerEntry {
+            name: self
+                .display_name
+                .unwrap_or_else(|| self.qualified_name.clone()),
+            id: self.qualified_name,
+            description: self.description,
+            packages,
+            remotes,
+        }
+    }
+}
+
+impl RegistrySource for SmitheryRegistry {
+    fn id(&self) -> &str {
+        "smithery"
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<ServerEntry>, Error> {
+        let url = format!("{}/servers?q={}", self.base_url, encode_component(query));
+        let list: SmitheryList = fetch_json_with_auth(&url, self.api_key.as_deref())?;
+        Ok(list
+            .servers
+            .into_iter()
+            .map(SmitheryServer::into_entry)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harness_locate::mcp::McpServer;
+
+    #[test]
+    fn hosted_server_maps_to_remote() {
+        let list: SmitheryList = serde_json::from_str(
+            r#"{"servers": [{"qualifiedName": "@acme/search", "displayName": "Search", "remote": true}]}"#,
+        )
+        .unwrap();
+        let entry = list.servers.into_iter().next().unwrap().into_entry();
+
+        assert_eq!(entry.id, "@acme/search");
+        assert_eq!(entry.name, "Search");
+        match &entry.to_mcp_servers()["@acme/search"] {
+            McpServer::Http(s) => assert_eq!(s.url, "https://server.smithery.ai/@acme/search/mcp"),
+            _ => panic!("expected Http"),
+        }
+    }
+
+    #[test]
+    fn local_server_runs_through_cli() {
+        let list: SmitheryList =
+            serde_json::from_str(r#"{"servers": [{"qualifiedName": "acme-files"}]}"#).unwrap();
+        let entry = list.servers.into_iter().next().unwrap().into_entry();
+
+        match &entry.to_mcp_servers()["acme-files"] {
+            McpServer::Stdio(s) => {
+                assert_eq!(s.command, "npx");
+                assert_eq!(s.args, vec!["-y", "@smithery/cli", "run", "acme-files"]);
+            }
+            _ => panic!("expected Stdio"),
+        }
+    }
+}
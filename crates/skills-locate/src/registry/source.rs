@@ -0,0 +1,308 @@
+//! Pluggable registry backends and federated search.
+
+use crate::error::Error;
+use crate::trust::{TrustLevel, TrustPolicy, TrustViolation, review_secrets};
+
+use super::{RegistryClient, ServerEntry};
+
+/// A registry that can be searched for MCP servers.
+pub trait RegistrySource: Send + Sync {
+    /// Short, stable identifier used to tag results (e.g., `"official"`).
+    fn id(&self) -> &str;
+
+    /// Searches this registry for servers matching `query`.
+    fn search(&self, query: &str) -> Result<Vec<ServerEntry>, Error>;
+}
+
+/// The official registry at registry.modelcontextprotocol.io.
+#[derive(Debug, Clone)]
+pub struct OfficialRegistry {
+    base_url: String,
+}
+
+impl Default for OfficialRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OfficialRegistry {
+    pub fn new() -> Self {
+        Self::with_base_url(super::OFFICIAL_BASE_URL)
+    }
+
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+        }
+    }
+}
+
+impl RegistrySource for OfficialRegistry {
+    fn id(&self) -> &str {
+        "official"
+    }
+
+    fn search(&self, query: &str) -> Result<Vec<ServerEntry>, Error> {
+        let servers = RegistryClient::with_base_url(&self.base_url).search(query)?;
+        Ok(servers.iter().map(|s| s.to_server_entry()).collect())
+    }
+}
+
+/// A search result tagged with the registries that returned it.
+#[derive(Debug, Clone)]
+pub struct RegistryResult {
+    pub entry: ServerEntry,
+    /// Source ids, in the order the sources were queried.
+    pub sources: Vec<String>,
+}
+
+/// The merged outcome of searching several registries.
+#[derive(Debug, Default)]
+pub struct FederatedResults {
+    pub results: Vec<RegistryResult>,
+    /// Sources that failed, with their errors. A failing source does not
+    /// prevent results from the others.
+    pub errors: Vec<(String, Error)>,
+    /// Ids of servers left out because the trust policy blocks one of their
+    /// remote URLs or packages.
+    pub blocked: Vec<String>,
+    /// Servers from untrusted sources left out because they failed
+    /// review, such as remotes with literal secrets in their headers.
+    pub rejected: Vec<TrustViolation>,
+}
+
+impl FederatedResults {
+    /// Applies `policy` to every remote URL and package of each server.
+    ///
+    /// Servers with a remote or package the policy blocks are moved into
+    /// `blocked`. Servers with an untrusted remote or package are reviewed
+    /// like plugins from untrusted sources, and moved into `rejected` if
+    /// they carry literal secrets.
+    pub(crate) fn apply_policy(&mut self, policy: &TrustPolicy) {
+        let (blocked, rejected) = (&mut self.blocked, &mut self.rejected);
+        self.results.retain(|result| {
+            let entry = &result.entry;
+            let levels: Option<Vec<TrustLevel>> = entry
+                .remotes
+                .iter()
+                .map(|remote| policy.level(&remote.url))
+                .chain(entry.packages.iter().map(|pkg| policy.level(&pkg.source())))
+                .collect();
+            let Some(levels) = levels else {
+                blocked.push(entry.id.clone());
+                return false;
+            };
+            if levels.contains(&TrustLevel::Untrusted) {
+                let violations = review_secrets(&entry.id, &entry.to_mcp_servers());
+                if !violations.is_empty() {
+                    rejected.extend(violations);
+                    return false;
+                }
+            }
+            true
+        });
+    }
+}
+
+/// Queries each source in order and merges results by server id.
+///
+/// When several sources return the same id, the entry from the first one
+/// wins and the other source ids are appended to its provenance.
+pub(crate) fn federate(sources: &[Box<dyn RegistrySource>], query: &str) -> FederatedResults {
+    let mut merged = FederatedResults::default();
+
+    for source in sources {
+        let entries = match source.search(query) {
+            Ok(entries) => entries,
+            Err(e) => {
+                merged.errors.push((source.id().to_string(), e));
+                continue;
+            }
+        };
+
+        for entry in entries {
+            match merged.results.iter_mut().find(|r| r.entry.id == entry.id) {
+                Some(existing) => {
+                    if !existing.sources.iter().any(|s| s == source.id()) {
+                        existing.sources.push(source.id().to_string());
+                    }
+                }
+                None => merged.results.push(RegistryResult {
+                    entry,
+                    sources: vec![source.id().to_string()],
+                }),
+            }
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Fixed {
+        id: &'static str,
+        ids: Vec<&'static str>,
+    }
+
+    impl RegistrySource for Fixed {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn search(&self, _query: &str) -> Result<Vec<ServerEntry>, Error> {
+            Ok(self
+                .ids
+                .iter()
+                .map(|id| ServerEntry {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    description: Some(self.id.to_string()),
+                    packages: vec![],
+                    remotes: vec![],
+                })
+                .collect())
+        }
+    }
+
+    struct Failing;
+
+    impl RegistrySource for Failing {
+        fn id(&self) -> &str {
+            "failing"
+        }
+
+        fn search(&self, _query: &str) -> Result<Vec<ServerEntry>, Error> {
+            Err(Error::Network {
+                status: Some(503),
+                message: "HTTP 503".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn federate_merges_duplicates_and_tags_provenance() {
+        let sources: Vec<Box<dyn RegistrySource>> = vec![
+            Box::new(Fixed {
+                id: "a",
+                ids: vec!["weather", "github"],
+            }),
+            Box::new(Fixed {
+                id: "b",
+                ids: vec!["github", "slack"],
+            }),
+        ];
+
+        let merged = federate(&sources, "q");
+        let ids: Vec<_> = merged.results.iter().map(|r| r.entry.id.as_str()).collect();
+        assert_eq!(ids, vec!["weather", "github", "slack"]);
+
+        let github = &merged.results[1];
+        assert_eq!(github.sources, vec!["a", "b"]);
+        assert_eq!(github.entry.description.as_deref(), Some("a"));
+        assert!(merged.errors.is_empty());
+    }
+
+    #[test]
+    fn federate_keeps_results_when_a_source_fails() {
+        let sources: Vec<Box<dyn RegistrySource>> = vec![
+            Box::new(Failing),
+            Box::new(Fixed {
+                id: "a",
+                ids: vec!["weather"],
+            }),
+        ];
+
+        let merged = federate(&sources, "q");
+        assert_eq!(merged.results.len(), 1);
+        assert_eq!(merged.errors.len(), 1);
+        assert_eq!(merged.errors[0].0, "failing");
+    }
+
+    #[test]
+    fn apply_policy_drops_servers_with_blocked_remotes() {
+        let entry = |id: &str, urls: &[&str], packages: &[&str]| RegistryResult {
+            entry: ServerEntry {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: None,
+                packages: packages
+                    .iter()
+                    .map(|name| super::super::PackageEntry {
+                        registry: "npm".to_string(),
+                        name: name.to_string(),
+                        version: None,
+                        runtime: None,
+                        arguments: vec![],
+                        environment_variables: Default::default(),
+                    })
+                    .collect(),
+                remotes: urls
+                    .iter()
+                    .map(|url| super::super::RemoteEntry {
+                        transport_type: "sse".to_string(),
+                        url: url.to_string(),
+                        headers: Default::default(),
+                    })
+                    .collect(),
+            },
+            sources: vec!["a".to_string()],
+        };
+        let mut merged = FederatedResults {
+            results: vec![
+                entry("local", &[], &[]),
+                entry("docs", &["https://mcp.docs.dev/sse"], &[]),
+                entry(
+                    "shady",
+                    &["https://mcp.acme.com/sse", "https://evil.example/sse"],
+                    &[],
+                ),
+                entry("fs", &[], &["@modelcontextprotocol/server-filesystem"]),
+                entry("typo", &[], &["@evil/server-filesystem"]),
+            ],
+            ..Default::default()
+        };
+
+        merged.apply_policy(&TrustPolicy::new().deny("evil.example").deny("npm/@evil"));
+        let ids: Vec<_> = merged.results.iter().map(|r| r.entry.id.as_str()).collect();
+        assert_eq!(ids, ["local", "docs", "fs"]);
+        assert_eq!(merged.blocked, ["shady", "typo"]);
+    }
+
+    #[test]
+    fn apply_policy_rejects_untrusted_remotes_with_plain_secrets() {
+        let entry = |id: &str, url: &str, token: &str| RegistryResult {
+            entry: ServerEntry {
+                id: id.to_string(),
+                name: id.to_string(),
+                description: None,
+                packages: vec![],
+                remotes: vec![super::super::RemoteEntry {
+                    transport_type: "sse".to_string(),
+                    url: url.to_string(),
+                    headers: [("Authorization".to_string(), token.to_string())].into(),
+                }],
+            },
+            sources: vec!["a".to_string()],
+        };
+        let mut merged = FederatedResults {
+            results: vec![
+                entry("leaky", "https://mcp.example/sse", "Bearer abc123"),
+                entry("ours", "https://mcp.acme.com/sse", "Bearer abc123"),
+            ],
+            ..Default::default()
+        };
+
+        merged.apply_policy(&TrustPolicy::new().trust("mcp.acme.com"));
+        let ids: Vec<_> = merged.results.iter().map(|r| r.entry.id.as_str()).collect();
+        assert_eq!(ids, ["ours"]);
+        assert!(matches!(
+            &merged.rejected[..],
+            [TrustViolation::PlainSecrets { plugin, names, .. }]
+                if plugin == "leaky" && names == &["Authorization"]
+        ));
+    }
+}
@@ -0,0 +1,607 @@
+//! Structural validation of marketplace, plugin, MCPB, and MCP JSON files.
+//!
+//! serde stops at the first mismatch and reports a line and column. These
+//! validators walk the whole document and report every violation with a
+//! JSON pointer to the offending value.
+//!
+//! With the `schemars` feature, [`schemas`] additionally exports JSON
+//! Schemas for this crate's normalized types.
+
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::error::{Error, Result};
+
+/// The file format to validate against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaKind {
+    /// `.claude-plugin/marketplace.json`
+    Marketplace,
+    /// `.claude-plugin/plugin.json`
+    PluginManifest,
+    /// MCPB `manifest.json`
+    McpbManifest,
+    /// `.mcp.json`, wrapped in `mcpServers` or flat
+    McpJson,
+}
+
+impl SchemaKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Marketplace => "marketplace.json",
+            Self::PluginManifest => "plugin.json",
+            Self::McpbManifest => "manifest.json",
+            Self::McpJson => ".mcp.json",
+        }
+    }
+}
+
+impl fmt::Display for SchemaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A single schema violation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value (`""` for the document root).
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pointer = if self.pointer.is_empty() {
+            "/"
+        } else {
+            &self.pointer
+        };
+        write!(f, "{pointer}: {}", self.message)
+    }
+}
+
+/// Validates `content` against the structure expected for `kind`.
+///
+/// Returns every violation found, or an empty vector if the document is
+/// valid. Invalid JSON yields a single violation at the root.
+///
+/// # Examples
+///
+/// ```
+/// use skills_locate::{SchemaKind, validate_schema};
+///
+/// let violations = validate_schema(
+///     SchemaKind::Marketplace,
+///     r#"{"plugins": [{"source": 42}, {}]}"#,
+/// );
+/// let pointers: Vec<_> = violations.iter().map(|v| v.pointer.as_str()).collect();
+/// assert_eq!(pointers, ["/plugins/0/source", "/plugins/1/source"]);
+/// ```
+pub fn validate_schema(kind: SchemaKind, content: &str) -> Vec<SchemaViolation> {
+    let value: Value = match serde_json::from_str(content) {
+        Ok(value) => value,
+        Err(e) => {
+            return vec![SchemaViolation {
+                pointer: String::new(),
+                message: format!("invalid JSON: {e}"),
+            }];
+        }
+    };
+    validate_value(kind, &value)
+}
+
+/// Validates an already-parsed document; see [`validate_schema`].
+pub fn validate_value(kind: SchemaKind, value: &Value) -> Vec<SchemaViolation> {
+    let mut checker = Checker::default();
+    match kind {
+        SchemaKind::Marketplace => checker.marketplace(value),
+        SchemaKind::PluginManifest => checker.plugin_manifest(value),
+        SchemaKind::McpbManifest => checker.mcpb_manifest(value),
+        SchemaKind::McpJson => checker.mcp_json(value),
+    }
+    checker.violations
+}
+
+/// Deserializes `content`, reporting every schema violation instead of
+/// only serde's first error when it doesn't match.
+pub(crate) fn parse_json<T: DeserializeOwned>(kind: SchemaKind, content: &str) -> Result<T> {
+    serde_json::from_str(content).map_err(|e| schema_error(kind, content, e))
+}
+
+/// Converts a serde error into [`Error::Schema`] when the validator finds
+/// violations, falling back to the serde error otherwise.
+pub(crate) fn schema_error(kind: SchemaKind, content: &str, error: serde_json::Error) -> Error {
+    let violations = validate_schema(kind, content);
+    if violations.is_empty() {
+        Error::JsonParse(error)
+    } else {
+        Error::Schema { kind, violations }
+    }
+}
+
+pub(crate) fn join_violations(violations: &[SchemaViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// JSON Schemas for the types this crate serializes, keyed by type name.
+///
+/// Intended for tools outside Rust that validate files written in this
+/// crate's normalized formats.
+///
+/// # Examples
+///
+/// ```
+/// let schemas = skills_locate::schemas();
+/// assert_eq!(schemas["SkillDescriptor"]["type"], "object");
+/// ```
+#[cfg(feature = "schemars")]
+pub fn schemas() -> std::collections::BTreeMap<&'static str, Value> {
+    use crate::component::{AgentDescriptor, CommandDescriptor, HookGroup};
+    use crate::marketplace::Marketplace;
+    use crate::types::{DiscoveryResult, PluginDescriptor, PluginSource, SkillDescriptor};
+    use harness_locate::mcp::McpServer;
+
+    fn schema<T: schemars::JsonSchema>() -> Value {
+        schemars::schema_for!(T).to_value()
+    }
+
+    [
+        ("AgentDescriptor", schema::<AgentDescriptor>()),
+        ("CommandDescriptor", schema::<CommandDescriptor>()),
+        ("DiscoveryResult", schema::<DiscoveryResult>()),
+        ("HookGroup", schema::<HookGroup>()),
+        ("Marketplace", schema::<Marketplace>()),
+        ("McpServer", schema::<McpServer>()),
+        ("PluginDescriptor", schema::<PluginDescriptor>()),
+        ("PluginSource", schema::<PluginSource>()),
+        ("SkillDescriptor", schema::<SkillDescriptor>()),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[derive(Default)]
+struct Checker {
+    violations: Vec<SchemaViolation>,
+}
+
+impl Checker {
+    fn violation(&mut self, pointer: &str, message: impl Into<String>) {
+        self.violations.push(SchemaViolation {
+            pointer: pointer.to_string(),
+            message: message.into(),
+        });
+    }
+
+    fn object<'v>(&mut self, value: &'v Value, pointer: &str) -> Option<&'v Map<String, Value>> {
+        let obj = value.as_object();
+        if obj.is_none() {
+            self.violation(
+                pointer,
+                format!("expected object, got {}", type_name(value)),
+            );
+        }
+        obj
+    }
+
+    fn array<'v>(&mut self, value: &'v Value, pointer: &str) -> Option<&'v Vec<Value>> {
+        let arr = value.as_array();
+        if arr.is_none() {
+            self.violation(pointer, format!("expected array, got {}", type_name(value)));
+        }
+        arr
+    }
+
+    /// Checks `obj[key]` is a string, reporting a missing required field.
+    fn string<'v>(
+        &mut self,
+        obj: &'v Map<String, Value>,
+        pointer: &str,
+        key: &str,
+        required: bool,
+    ) -> Option<&'v str> {
+        let child = child_pointer(pointer, key);
+        match obj.get(key) {
+            Some(Value::String(s)) => Some(s),
+            Some(other) => {
+                self.violation(&child, format!("expected string, got {}", type_name(other)));
+                None
+            }
+            None => {
+                if required {
+                    self.violation(&child, "missing required field");
+                }
+                None
+            }
+        }
+    }
+
+    fn optional_bool(&mut self, obj: &Map<String, Value>, pointer: &str, key: &str) {
+        if let Some(value) = obj.get(key)
+            && !value.is_boolean()
+        {
+            let child = child_pointer(pointer, key);
+            self.violation(
+                &child,
+                format!("expected boolean, got {}", type_name(value)),
+            );
+        }
+    }
+
+    fn string_array(&mut self, obj: &Map<String, Value>, pointer: &str, key: &str) {
+        let Some(value) = obj.get(key) else {
+            return;
+        };
+        let child = child_pointer(pointer, key);
+        if let Some(items) = self.array(value, &child) {
+            for (i, item) in items.iter().enumerate() {
+                if !item.is_string() {
+                    self.violation(
+                        &child_pointer(&child, &i.to_string()),
+                        format!("expected string, got {}", type_name(item)),
+                    );
+                }
+            }
+        }
+    }
+
+    fn string_map(&mut self, obj: &Map<String, Value>, pointer: &str, key: &str) {
+        let Some(value) = obj.get(key) else {
+            return;
+        };
+        let child = child_pointer(pointer, key);
+        if let Some(map) = self.object(value, &child) {
+            for (k, v) in map {
+                if !v.is_string() {
+                    self.violation(
+                        &child_pointer(&child, k),
+                        format!("expected string, got {}", type_name(v)),
+                    );
+                }
+            }
+        }
+    }
+
+    fn marketplace(&mut self, value: &Value) {
+        let Some(root) = self.object(value, "") else {
+            return;
+        };
+        let Some(plugins) = root.get("plugins") else {
+            self.violation("/plugins", "missing required field");
+            return;
+        };
+        let Some(plugins) = self.array(plugins, "/plugins") else {
+            return;
+        };
+        for (i, plugin) in plugins.iter().enumerate() {
+            let pointer = format!("/plugins/{i}");
+            let Some(entry) = self.object(plugin, &pointer) else {
+                continue;
+            };
+            self.string(entry, &pointer, "name", false);
+            self.string(entry, &pointer, "description", false);
+
+            let source_pointer = child_pointer(&pointer, "source");
+            match entry.get("source") {
+                None => self.violation(&source_pointer, "missing required field"),
+                Some(Value::String(_)) => {}
+                Some(Value::Object(source)) => {
+                    let keys = ["github", "repo", "url"];
+                    let present: Vec<_> =
+                        keys.iter().filter(|k| source.contains_key(**k)).collect();
+                    if present.is_empty() {
+                        self.violation(
+                            &source_pointer,
+                            "source object must have one of \"github\", \"repo\", or \"url\"",
+                        );
+                    }
+                    for key in present {
+                        self.string(source, &source_pointer, key, true);
+                    }
+                }
+                Some(other) => self.violation(
+                    &source_pointer,
+                    format!("expected string or object, got {}", type_name(other)),
+                ),
+            }
+        }
+    }
+
+    fn plugin_manifest(&mut self, value: &Value) {
+        let Some(root) = self.object(value, "") else {
+            return;
+        };
+        if self.string(root, "", "name", true) == Some("") {
+            self.violation("/name", "must not be empty");
+        }
+        self.string(root, "", "description", false);
+        self.string(root, "", "version", false);
+        self.string(root, "", "homepage", false);
+        self.string_array(root, "", "keywords");
+        match root.get("author") {
+            None | Some(Value::String(_)) => {}
+            Some(Value::Object(author)) => {
+                self.string(author, "/author", "name", true);
+                self.string(author, "/author", "email", false);
+            }
+            Some(other) => self.violation(
+                "/author",
+                format!("expected string or object, got {}", type_name(other)),
+            ),
+        }
+        for key in ["commands", "agents"] {
+            if !root.get(key).is_some_and(Value::is_string) {
+                self.string_array(root, "", key);
+            }
+        }
+        for key in ["hooks", "mcpServers"] {
+            match root.get(key) {
+                None | Some(Value::String(_) | Value::Object(_)) => {}
+                Some(other) => self.violation(
+                    &child_pointer("", key),
+                    format!("expected string or object, got {}", type_name(other)),
+                ),
+            }
+        }
+    }
+
+    fn mcpb_manifest(&mut self, value: &Value) {
+        let Some(root) = self.object(value, "") else {
+            return;
+        };
+        match root.get("server") {
+            None => self.violation("/server", "missing required field"),
+            Some(server) => {
+                if let Some(server) = self.object(server, "/server") {
+                    let server_type = self.string(server, "/server", "type", true);
+                    match server_type {
+                        Some("stdio") => {
+                            self.string(server, "/server", "command", true);
+                        }
+                        Some("http" | "streamable-http" | "sse") => {
+                            self.string(server, "/server", "url", true);
+                        }
+                        Some(other) => self
+                            .violation("/server/type", format!("unknown server type \"{other}\"")),
+                        None => {}
+                    }
+                    self.string_array(server, "/server", "args");
+                    self.string_map(server, "/server", "env");
+                }
+            }
+        }
+
+        if let Some(tools) = root.get("tools")
+            && let Some(tools) = self.array(tools, "/tools")
+        {
+            for (i, tool) in tools.iter().enumerate() {
+                let pointer = format!("/tools/{i}");
+                if let Some(tool) = self.object(tool, &pointer) {
+                    self.string(tool, &pointer, "name", true);
+                    self.string(tool, &pointer, "description", false);
+                }
+            }
+        }
+
+        if let Some(entries) = root.get("user_config")
+            && let Some(entries) = self.array(entries, "/user_config")
+        {
+            for (i, entry) in entries.iter().enumerate() {
+                let pointer = format!("/user_config/{i}");
+                if let Some(entry) = self.object(entry, &pointer) {
+                    self.string(entry, &pointer, "id", true);
+                    self.string(entry, &pointer, "name", true);
+                    self.string(entry, &pointer, "description", false);
+                    self.optional_bool(entry, &pointer, "required");
+                    self.optional_bool(entry, &pointer, "secret");
+                }
+            }
+        }
+    }
+
+    fn mcp_json(&mut self, value: &Value) {
+        let Some(root) = self.object(value, "") else {
+            return;
+        };
+        let (servers, base) = match root.get("mcpServers") {
+            Some(wrapped) => match self.object(wrapped, "/mcpServers") {
+                Some(servers) => (servers, "/mcpServers".to_string()),
+                None => return,
+            },
+            None => (root, String::new()),
+        };
+        for (name, server) in servers {
+            let pointer = child_pointer(&base, name);
+            let Some(server) = self.object(server, &pointer) else {
+                continue;
+            };
+            let transport = self.string(server, &pointer, "type", false);
+            let command = self.string(server, &pointer, "command", false);
+            let url = self.string(server, &pointer, "url", false);
+            match transport {
+                Some("sse" | "http" | "streamable-http") if url.is_none() && command.is_none() => {
+                    self.violation(&child_pointer(&pointer, "url"), "missing required field");
+                }
+                Some("sse" | "http" | "streamable-http") => {}
+                _ if command.is_none() && !server.contains_key("command") => {
+                    self.violation(
+                        &child_pointer(&pointer, "command"),
+                        "missing required field",
+                    );
+                }
+                _ => {}
+            }
+            self.string_array(server, &pointer, "args");
+            self.string_map(server, &pointer, "env");
+            self.string_map(server, &pointer, "headers");
+        }
+    }
+}
+
+/// Appends `key` to `pointer`, escaping it per RFC 6901.
+fn child_pointer(pointer: &str, key: &str) -> String {
+    format!("{pointer}/{}", key.replace('~', "~0").replace('/', "~1"))
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pointers(violations: &[SchemaViolation]) -> Vec<&str> {
+        violations.iter().map(|v| v.pointer.as_str()).collect()
+    }
+
+    #[test]
+    fn valid_marketplace_has_no_violations() {
+        let json = r#"{"plugins": [
+            {"source": "./plugins/a"},
+            {"source": {"github": "owner/repo"}, "name": "b"},
+            {"source": {"url": "https://example.com/c.zip"}}
+        ]}"#;
+        assert!(validate_schema(SchemaKind::Marketplace, json).is_empty());
+    }
+
+    #[test]
+    fn marketplace_reports_all_violations() {
+        let json = r#"{"plugins": [
+            {"source": {"github": 1}},
+            {"source": {}},
+            "not-an-object",
+            {"name": 5, "source": "ok"}
+        ]}"#;
+        let violations = validate_schema(SchemaKind::Marketplace, json);
+        assert_eq!(
+            pointers(&violations),
+            vec![
+                "/plugins/0/source/github",
+                "/plugins/1/source",
+                "/plugins/2",
+                "/plugins/3/name"
+            ]
+        );
+    }
+
+    #[test]
+    fn marketplace_requires_plugins_array() {
+        let violations = validate_schema(SchemaKind::Marketplace, "{}");
+        assert_eq!(
+            violations[0].to_string(),
+            "/plugins: missing required field"
+        );
+    }
+
+    #[test]
+    fn invalid_json_is_a_root_violation() {
+        let violations = validate_schema(SchemaKind::PluginManifest, "{");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].pointer, "");
+        assert!(violations[0].message.starts_with("invalid JSON"));
+    }
+
+    #[test]
+    fn plugin_manifest_checks_fields() {
+        let json = r#"{"name": "", "version": 1, "keywords": ["a", 2], "author": {"email": "x"}}"#;
+        let violations = validate_schema(SchemaKind::PluginManifest, json);
+        assert_eq!(
+            pointers(&violations),
+            vec!["/name", "/version", "/keywords/1", "/author/name"]
+        );
+    }
+
+    #[test]
+    fn plugin_manifest_checks_component_fields() {
+        let json = r#"{
+            "name": "kit",
+            "commands": "./cmds",
+            "agents": ["./a.md", 1],
+            "hooks": ["./hooks.json"],
+            "mcpServers": {"x": {"command": "x"}}
+        }"#;
+        let violations = validate_schema(SchemaKind::PluginManifest, json);
+        assert_eq!(pointers(&violations), vec!["/agents/1", "/hooks"]);
+    }
+
+    #[test]
+    fn mcpb_manifest_checks_server_and_user_config() {
+        let json = r#"{
+            "server": {"type": "stdio", "env": {"PORT": 3000}},
+            "tools": [{"description": "no name"}],
+            "user_config": [{"id": "KEY", "name": "Key", "required": "yes"}]
+        }"#;
+        let violations = validate_schema(SchemaKind::McpbManifest, json);
+        assert_eq!(
+            pointers(&violations),
+            vec![
+                "/server/command",
+                "/server/env/PORT",
+                "/tools/0/name",
+                "/user_config/0/required"
+            ]
+        );
+    }
+
+    #[test]
+    fn mcp_json_wrapped_and_flat() {
+        let wrapped = r#"{"mcpServers": {"a/b": {"args": "x"}, "remote": {"type": "http"}}}"#;
+        let violations = validate_schema(SchemaKind::McpJson, wrapped);
+        assert_eq!(
+            pointers(&violations),
+            vec![
+                "/mcpServers/a~1b/command",
+                "/mcpServers/a~1b/args",
+                "/mcpServers/remote/url"
+            ]
+        );
+
+        let flat = r#"{"local": {"command": "node", "args": ["s.js"]}}"#;
+        assert!(validate_schema(SchemaKind::McpJson, flat).is_empty());
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn schemas_describe_serialized_fields() {
+        let schemas = schemas();
+        let skill = &schemas["SkillDescriptor"];
+        assert_eq!(skill["required"], serde_json::json!(["name"]));
+        assert!(skill["properties"]["namespace"].is_object());
+
+        let mcp = serde_json::to_string(&schemas["McpServer"]).unwrap();
+        assert!(mcp.contains("\"transport\""));
+    }
+
+    #[test]
+    fn parse_json_returns_schema_error() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Plugin {
+            name: String,
+        }
+
+        let err = parse_json::<Plugin>(SchemaKind::PluginManifest, r#"{"name": 1, "version": 2}"#)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid plugin.json: /name: expected string, got number; /version: expected string, got number"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+//! Ranking discovered plugins and skills by application-supplied scores.
+//!
+//! Marketplace frontends rank entries by data this crate does not have,
+//! such as GitHub stars, download counts or internal ratings. A [`Scorer`]
+//! attaches such a score to each plugin and skill, and
+//! [`DiscoveryResult::score`] sorts the result by it.
+
+use crate::types::{DiscoveryResult, PluginDescriptor, SkillDescriptor};
+
+/// Attaches quality scores to discovered entries. Higher scores rank
+/// first.
+///
+/// Implemented for closures scoring plugins, whose skills then share their
+/// plugin's score.
+pub trait Scorer {
+    /// Scores a plugin, or returns `None` if there is nothing to go on.
+    fn score_plugin(&self, plugin: &PluginDescriptor) -> Option<u64>;
+
+    /// Scores a skill of `plugin`. Defaults to the plugin's score.
+    fn score_skill(&self, plugin: &PluginDescriptor, skill: &SkillDescriptor) -> Option<u64> {
+        let _ = skill;
+        self.score_plugin(plugin)
+    }
+}
+
+impl<F: Fn(&PluginDescriptor) -> Option<u64>> Scorer for F {
+    fn score_plugin(&self, plugin: &PluginDescriptor) -> Option<u64> {
+        self(plugin)
+    }
+}
+
+impl DiscoveryResult {
+    /// Sets the score of every plugin and skill from `scorer` and sorts
+    /// them, highest score first.
+    ///
+    /// Plugins, the skills within each plugin and
+    /// [`all_skills`](Self::all_skills) are sorted; unscored entries go
+    /// last and ties keep discovery order. Suppressed duplicates are not
+    /// scored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skills_locate::{DiscoveryResult, PluginDescriptor};
+    ///
+    /// let stars = |plugin: &PluginDescriptor| match plugin.name.as_str() {
+    ///     "popular" => Some(1200),
+    ///     _ => Some(3),
+    /// };
+    /// let result = DiscoveryResult::from_plugins(Vec::new()).score(&stars);
+    /// assert!(result.plugins.is_empty());
+    /// ```
+    #[must_use]
+    pub fn score(self, scorer: &dyn Scorer) -> Self {
+        let mut plugins = self.plugins;
+        for plugin in &mut plugins {
+            plugin.score = scorer.score_plugin(plugin);
+            let scores: Vec<_> = plugin
+                .skills
+                .iter()
+                .map(|skill| scorer.score_skill(plugin, skill))
+                .collect();
+            for (skill, score) in plugin.skills.iter_mut().zip(scores) {
+                skill.score = score;
+            }
+            plugin.skills.sort_by_key(|skill| rank(skill.score));
+        }
+        plugins.sort_by_key(|plugin| rank(plugin.score));
+
+        let mut result = Self {
+            suppressed: self.suppressed,
+            ..Self::from_plugins(plugins)
+        };
+        result.all_skills.sort_by_key(|skill| rank(skill.score));
+        result
+    }
+}
+
+/// Sort key putting higher scores first and unscored entries last.
+fn rank(score: Option<u64>) -> (bool, std::cmp::Reverse<u64>) {
+    (
+        score.is_none(),
+        std::cmp::Reverse(score.unwrap_or_default()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use harness_locate::target::Platforms;
+
+    use super::*;
+
+    fn plugin(name: &str, skills: &[&str]) -> PluginDescriptor {
+        PluginDescriptor {
+            name: name.to_string(),
+            path: None,
+            description: None,
+            skills: skills
+                .iter()
+                .map(|skill| SkillDescriptor {
+                    name: skill.to_string(),
+                    description: None,
+                    descriptions: BTreeMap::new(),
+                    triggers: Vec::new(),
+                    platforms: Platforms::default(),
+                    namespace: None,
+                    path: None,
+                    hash: None,
+                    score: None,
+                })
+                .collect(),
+            commands: Vec::new(),
+            agents: Vec::new(),
+            hooks: None,
+            mcp_servers: BTreeMap::new(),
+            origin: None,
+            score: None,
+        }
+    }
+
+    struct Downloads;
+
+    impl Scorer for Downloads {
+        fn score_plugin(&self, plugin: &PluginDescriptor) -> Option<u64> {
+            (plugin.name != "new").then_some(plugin.name.len() as u64)
+        }
+
+        fn score_skill(&self, _: &PluginDescriptor, skill: &SkillDescriptor) -> Option<u64> {
+            match skill.name.as_str() {
+                "lint" => Some(900),
+                "deploy" => Some(50),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn sorts_plugins_and_skills_by_score() {
+        let result = DiscoveryResult::from_plugins(vec![
+            plugin("new", &["draft"]),
+            plugin("ops", &["deploy", "rollback"]),
+            plugin("tooling", &["format", "lint"]),
+        ])
+        .score(&Downloads);
+
+        let plugins: Vec<_> = result.plugins.iter().map(|p| (&*p.name, p.score)).collect();
+        assert_eq!(
+            plugins,
+            [("tooling", Some(7)), ("ops", Some(3)), ("new", None)]
+        );
+        let skills: Vec<_> = result.all_skills.iter().map(|s| &*s.name).collect();
+        assert_eq!(skills, ["lint", "deploy", "format", "rollback", "draft"]);
+        assert_eq!(result.plugins[0].skills[0].name, "lint");
+    }
+}
@@ -0,0 +1,233 @@
+//! Transport negotiation for remote MCP servers.
+//!
+//! A remote MCP URL does not say which transport it speaks. Following the
+//! backwards-compatibility procedure in the MCP specification,
+//! [`negotiate_transport`] first POSTs an `initialize` request, which a
+//! streamable HTTP server accepts. If that fails with a client error, or
+//! succeeds without a JSON or event-stream body, it GETs the URL and checks
+//! for the event stream a legacy SSE server opens.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use harness_locate::mcp::{HttpMcpServer, McpServer, SseMcpServer};
+
+use crate::error::{Error, Result};
+
+/// Time allowed for each probe request.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// MCP protocol version sent in the `initialize` probe.
+const PROTOCOL_VERSION: &str = "2025-03-26";
+
+/// A probe request sent while negotiating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Probe {
+    /// POST an `initialize` request, as a streamable HTTP client would.
+    Initialize,
+    /// GET the URL, as a legacy SSE client would.
+    EventStream,
+}
+
+/// The parts of a probe response that decide the transport.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ProbeResponse {
+    status: u16,
+    content_type: Option<String>,
+}
+
+impl ProbeResponse {
+    fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    fn has_content_type(&self, prefix: &str) -> bool {
+        self.content_type
+            .as_deref()
+            .is_some_and(|ct| ct.starts_with(prefix))
+    }
+}
+
+/// Probes `url` and returns a server config with the transport it speaks.
+///
+/// Returns [`McpServer::Http`] if the endpoint answers an `initialize`
+/// POST with JSON or an event stream, and [`McpServer::Sse`] if it rejects
+/// the POST but answers a GET with `text/event-stream`. An endpoint that
+/// requires authorization for both probes cannot be negotiated. The
+/// returned config has no headers; add any the server needs.
+///
+/// # Errors
+///
+/// Returns [`Error::Network`] if the endpoint cannot be reached, and
+/// [`Error::Negotiation`] if it answers neither probe like an MCP server.
+pub fn negotiate_transport(url: &str) -> Result<McpServer> {
+    negotiate_with(url, |probe| send_probe(url, probe))
+}
+
+fn negotiate_with<F>(url: &str, send: F) -> Result<McpServer>
+where
+    F: Fn(Probe) -> Result<ProbeResponse>,
+{
+    let post = send(Probe::Initialize)?;
+    let is_streamable =
+        post.has_content_type("application/json") || post.has_content_type("text/event-stream");
+    if post.is_success() && is_streamable {
+        return Ok(McpServer::Http(HttpMcpServer {
+            url: url.to_string(),
+            headers: HashMap::new(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+        }));
+    }
+    if !post.is_success() && !(400..500).contains(&post.status) {
+        return Err(Error::Negotiation(format!(
+            "{url} answered the initialize request with HTTP {}",
+            post.status
+        )));
+    }
+
+    let get = send(Probe::EventStream)?;
+    if get.is_success() && get.has_content_type("text/event-stream") {
+        return Ok(McpServer::Sse(SseMcpServer {
+            url: url.to_string(),
+            headers: HashMap::new(),
+            enabled: true,
+            timeout_ms: None,
+        }));
+    }
+    Err(Error::Negotiation(format!(
+        "{url} speaks neither streamable HTTP (initialize: HTTP {}) nor SSE (GET: HTTP {}, {})",
+        post.status,
+        get.status,
+        get.content_type.as_deref().unwrap_or("no content type")
+    )))
+}
+
+fn send_probe(url: &str, probe: Probe) -> Result<ProbeResponse> {
+    let response = match probe {
+        Probe::Initialize => {
+            let body = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": {
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                },
+            });
+            ureq::post(url)
+                .config()
+                .timeout_global(Some(PROBE_TIMEOUT))
+                .http_status_as_error(false)
+                .build()
+                .header("Accept", "application/json, text/event-stream")
+                .header("Content-Type", "application/json")
+                .send(body.to_string())
+        }
+        Probe::EventStream => ureq::get(url)
+            .config()
+            .timeout_global(Some(PROBE_TIMEOUT))
+            .http_status_as_error(false)
+            .build()
+            .header("Accept", "text/event-stream")
+            .call(),
+    }
+    .map_err(|e| Error::Network {
+        status: None,
+        message: format!("probing {url}: {e}"),
+    })?;
+
+    // Only the status and headers are needed; the SSE body never ends, so
+    // the response is dropped unread.
+    Ok(ProbeResponse {
+        status: response.status().as_u16(),
+        content_type: response
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_ascii_lowercase),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://mcp.example.com/mcp";
+
+    fn respond(
+        post: (u16, &'static str),
+        get: Option<(u16, &'static str)>,
+    ) -> impl Fn(Probe) -> Result<ProbeResponse> {
+        move |probe| match probe {
+            Probe::Initialize => Ok(ProbeResponse {
+                status: post.0,
+                content_type: Some(post.1.into()),
+            }),
+            Probe::EventStream => {
+                let (status, content_type) = get.expect("unexpected GET probe");
+                Ok(ProbeResponse {
+                    status,
+                    content_type: Some(content_type.into()),
+                })
+            }
+        }
+    }
+
+    #[test]
+    fn accepted_initialize_is_streamable_http() {
+        for post in [
+            (200, "application/json"),
+            (200, "text/event-stream"),
+            (202, "application/json; charset=utf-8"),
+        ] {
+            let server = negotiate_with(URL, respond(post, None)).unwrap();
+            assert!(
+                matches!(&server, McpServer::Http(h) if h.url == URL),
+                "{post:?}: {server:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejected_initialize_with_event_stream_is_sse() {
+        for post in [
+            (401, "application/json"),
+            (405, "text/plain"),
+            (200, "text/html"),
+        ] {
+            let server = negotiate_with(
+                URL,
+                respond(post, Some((200, "text/event-stream; charset=utf-8"))),
+            )
+            .unwrap();
+            assert!(
+                matches!(&server, McpServer::Sse(s) if s.url == URL),
+                "{post:?}: {server:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn other_endpoints_fail_to_negotiate() {
+        let err =
+            negotiate_with(URL, respond((404, "text/html"), Some((200, "text/html")))).unwrap_err();
+        assert!(
+            matches!(&err, Error::Negotiation(msg) if msg.contains("text/html")),
+            "{err}"
+        );
+        let err = negotiate_with(
+            URL,
+            respond((401, "application/json"), Some((401, "application/json"))),
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::Negotiation(_)));
+        let err = negotiate_with(URL, respond((500, "text/html"), None)).unwrap_err();
+        assert!(matches!(err, Error::Negotiation(_)));
+    }
+}
@@ -0,0 +1,403 @@
+//! Trust levels and allowlisting for discovery sources.
+//!
+//! A [`TrustPolicy`] decides, per source, whether discovery may fetch it at
+//! all and whether its contents are trusted. Sources are identified by host
+//! and path, such as `github.com/acme/skills`, and policy patterns match a
+//! host, an organization, or a single repository.
+//!
+//! Plugins from untrusted sources must pass a stricter review: the archive
+//! must match a pinned SHA-256 checksum, and plugins may not ship hooks or
+//! MCP servers with literal secrets.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::component::McpServer;
+use crate::error::{Error, Result};
+use crate::types::PluginDescriptor;
+
+/// How far a source's contents are trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrustLevel {
+    /// Contents are used as discovered.
+    Trusted,
+    /// Contents must pass [`TrustPolicy::review`].
+    Untrusted,
+}
+
+/// Which sources may be discovered, and which of them are trusted.
+///
+/// Patterns are a host optionally followed by path segments, with or
+/// without a scheme: `github.com`, `github.com/acme`, or
+/// `https://github.com/acme/skills`. A pattern matches a source with the
+/// same leading segments, ignoring case.
+///
+/// The deny list always wins. If an allow list is given, sources matching
+/// neither it nor the trusted list are blocked. Sources not marked trusted
+/// are untrusted, so the default policy allows everything and trusts
+/// nothing.
+///
+/// Policies can be loaded from JSON, for example from a file maintained by
+/// a security team:
+///
+/// ```json
+/// {
+///   "trusted": ["github.com/acme"],
+///   "allow": ["github.com/anthropics"],
+///   "deny": ["github.com/acme/sandbox"],
+///   "checksums": {"github.com/anthropics/skills": "9f86d0…"}
+/// }
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use skills_locate::{TrustLevel, TrustPolicy};
+///
+/// let policy = TrustPolicy::new()
+///     .trust("github.com/acme")
+///     .allow("github.com/anthropics")
+///     .deny("github.com/acme/sandbox");
+///
+/// assert_eq!(policy.level("https://github.com/acme/tools"), Some(TrustLevel::Trusted));
+/// assert_eq!(policy.level("https://github.com/anthropics/skills"), Some(TrustLevel::Untrusted));
+/// assert_eq!(policy.level("https://github.com/acme/sandbox"), None);
+/// assert_eq!(policy.level("https://github.com/someone/else"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrustPolicy {
+    trusted: Vec<String>,
+    allow: Vec<String>,
+    deny: Vec<String>,
+    checksums: BTreeMap<String, String>,
+}
+
+impl TrustPolicy {
+    /// Creates a policy that allows every source and trusts none.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trusts sources matching `pattern`, which also allows them.
+    #[must_use]
+    pub fn trust(mut self, pattern: impl Into<String>) -> Self {
+        self.trusted.push(pattern.into());
+        self
+    }
+
+    /// Allows sources matching `pattern` as untrusted, and blocks sources
+    /// matching no allow or trust pattern.
+    #[must_use]
+    pub fn allow(mut self, pattern: impl Into<String>) -> Self {
+        self.allow.push(pattern.into());
+        self
+    }
+
+    /// Blocks sources matching `pattern`, even if trusted or allowed.
+    #[must_use]
+    pub fn deny(mut self, pattern: impl Into<String>) -> Self {
+        self.deny.push(pattern.into());
+        self
+    }
+
+    /// Pins the SHA-256 checksum, in hex, of the archive downloaded for
+    /// `source`.
+    ///
+    /// Discovery requires a pin for every untrusted source and verifies the
+    /// archive against pins for trusted ones.
+    #[must_use]
+    pub fn pin(mut self, source: impl AsRef<str>, sha256: impl Into<String>) -> Self {
+        self.checksums
+            .insert(source_key(source.as_ref()), sha256.into());
+        self
+    }
+
+    /// Returns the trust level of `source`, or `None` if it is blocked.
+    #[must_use]
+    pub fn level(&self, source: &str) -> Option<TrustLevel> {
+        let key = source_key(source);
+        let matches = |patterns: &[String]| patterns.iter().any(|p| matches_pattern(p, &key));
+        if matches(&self.deny) {
+            None
+        } else if matches(&self.trusted) {
+            Some(TrustLevel::Trusted)
+        } else if self.allow.is_empty() || matches(&self.allow) {
+            Some(TrustLevel::Untrusted)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the trust level of `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Blocked`] if the policy blocks `source`.
+    pub fn check_source(&self, source: &str) -> Result<TrustLevel> {
+        self.level(source)
+            .ok_or_else(|| Error::Blocked(source.to_string()))
+    }
+
+    /// Returns the checksum pinned for `source`, if any.
+    #[must_use]
+    pub fn checksum(&self, source: &str) -> Option<&str> {
+        let key = source_key(source);
+        self.checksums
+            .iter()
+            .find(|(pinned, _)| source_key(pinned) == key)
+            .map(|(_, sha256)| sha256.as_str())
+    }
+
+    /// Checks a plugin from an untrusted source.
+    ///
+    /// Reports hooks, which run arbitrary commands, and MCP servers with
+    /// literal secrets in their env vars or headers. Checksums are verified
+    /// separately when the archive is downloaded.
+    #[must_use]
+    pub fn review(&self, plugin: &PluginDescriptor) -> Vec<TrustViolation> {
+        let mut violations = Vec::new();
+        if plugin.hooks.as_ref().is_some_and(|hooks| !hooks.is_empty()) {
+            violations.push(TrustViolation::Hooks {
+                plugin: plugin.name.clone(),
+            });
+        }
+        violations.extend(review_secrets(&plugin.name, &plugin.mcp_servers));
+        violations
+    }
+}
+
+/// A reason an untrusted source was rejected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum TrustViolation {
+    /// No checksum is pinned for the source.
+    MissingChecksum,
+    /// A plugin ships hooks.
+    Hooks {
+        /// The plugin name.
+        plugin: String,
+    },
+    /// An MCP server has literal secrets instead of env references.
+    PlainSecrets {
+        /// The plugin name, or the registry server id.
+        plugin: String,
+        /// The server name.
+        server: String,
+        /// The env vars or headers holding secrets.
+        names: Vec<String>,
+    },
+}
+
+impl fmt::Display for TrustViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingChecksum => write!(f, "no checksum pinned"),
+            Self::Hooks { plugin } => write!(f, "plugin {plugin} ships hooks"),
+            Self::PlainSecrets {
+                plugin,
+                server,
+                names,
+            } => write!(
+                f,
+                "MCP server {server} in plugin {plugin} has plain secrets: {}",
+                names.join(", ")
+            ),
+        }
+    }
+}
+
+/// Reports the servers in `servers` with literal secrets in their env vars
+/// or headers, attributing them to `origin`.
+pub(crate) fn review_secrets<'a>(
+    origin: &str,
+    servers: impl IntoIterator<Item = (&'a String, &'a McpServer)>,
+) -> Vec<TrustViolation> {
+    servers
+        .into_iter()
+        .filter_map(|(name, server)| {
+            let secrets = server.plain_secret_names();
+            (!secrets.is_empty()).then(|| TrustViolation::PlainSecrets {
+                plugin: origin.to_string(),
+                server: name.clone(),
+                names: secrets.into_iter().map(String::from).collect(),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn join_violations(violations: &[TrustViolation]) -> String {
+    violations
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Verifies `bytes` against the checksum pinned for `source`.
+///
+/// Returns the violation to report if an untrusted source has no pin.
+#[cfg(feature = "fetch")]
+pub(crate) fn verify_checksum(
+    policy: &TrustPolicy,
+    source: &str,
+    level: TrustLevel,
+    bytes: &[u8],
+) -> Result<Option<TrustViolation>> {
+    let Some(expected) = policy.checksum(source) else {
+        return Ok((level == TrustLevel::Untrusted).then_some(TrustViolation::MissingChecksum));
+    };
+    let actual = sha256_hex(bytes);
+    let expected = expected.trim_start_matches("sha256:");
+    if !expected.eq_ignore_ascii_case(&actual) {
+        return Err(Error::ChecksumMismatch {
+            url: source.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(None)
+}
+
+#[cfg(feature = "fetch")]
+fn sha256_hex(bytes: &[u8]) -> String {
+    ring::digest::digest(&ring::digest::SHA256, bytes)
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Normalizes a URL or pattern to lowercase `host/path` without a scheme,
+/// trailing slash, or `.git` suffix.
+fn source_key(source: &str) -> String {
+    let source = source.trim();
+    let source = source
+        .split_once("://")
+        .map_or(source, |(_, rest)| rest)
+        .trim_end_matches('/');
+    let source = source.strip_suffix(".git").unwrap_or(source);
+    source.to_lowercase()
+}
+
+fn matches_pattern(pattern: &str, key: &str) -> bool {
+    let pattern = source_key(pattern);
+    key.strip_prefix(&pattern)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patterns_match_whole_segments() {
+        let policy = TrustPolicy::new().allow("GitHub.com/acme/");
+        assert!(
+            policy
+                .check_source("https://github.com/acme/tools.git")
+                .is_ok()
+        );
+        assert!(policy.check_source("github.com/acme").is_ok());
+        assert!(matches!(
+            policy.check_source("https://github.com/acme-evil/tools"),
+            Err(Error::Blocked(source)) if source == "https://github.com/acme-evil/tools"
+        ));
+    }
+
+    #[test]
+    fn deny_wins_and_default_trusts_nothing() {
+        let policy = TrustPolicy::new()
+            .trust("github.com")
+            .deny("https://github.com/mallory");
+        assert_eq!(policy.level("https://github.com/mallory/skills"), None);
+        assert_eq!(
+            policy.level("https://github.com/acme/skills"),
+            Some(TrustLevel::Trusted)
+        );
+        assert_eq!(
+            TrustPolicy::default().level("https://example.com/plugin.zip"),
+            Some(TrustLevel::Untrusted)
+        );
+    }
+
+    #[test]
+    fn deserializes_from_partial_json() {
+        let policy: TrustPolicy = serde_json::from_str(
+            r#"{"deny": ["github.com/mallory"], "checksums": {"https://github.com/a/b": "abc"}}"#,
+        )
+        .unwrap();
+        assert_eq!(policy.level("https://github.com/mallory/x"), None);
+        assert_eq!(policy.checksum("github.com/a/b/"), Some("abc"));
+        assert_eq!(policy.checksum("github.com/a"), None);
+    }
+
+    #[test]
+    fn review_reports_hooks_and_plain_secrets() {
+        let plugin: PluginDescriptor = serde_json::from_str(
+            r#"{
+                "name": "kit",
+                "hooks": {"Stop": [{"hooks": ["notify"]}]},
+                "mcp_servers": {
+                    "gh": {"transport": "stdio", "command": "gh-mcp", "env": {
+                        "GITHUB_TOKEN": "ghp_123",
+                        "API_KEY": {"env": "API_KEY"}
+                    }},
+                    "docs": {"transport": "http", "url": "https://docs.example.com"}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let violations = TrustPolicy::new().review(&plugin);
+        assert_eq!(
+            violations,
+            [
+                TrustViolation::Hooks {
+                    plugin: "kit".into()
+                },
+                TrustViolation::PlainSecrets {
+                    plugin: "kit".into(),
+                    server: "gh".into(),
+                    names: vec!["GITHUB_TOKEN".into()],
+                },
+            ]
+        );
+        assert_eq!(
+            join_violations(&violations),
+            "plugin kit ships hooks; MCP server gh in plugin kit has plain secrets: GITHUB_TOKEN"
+        );
+    }
+
+    #[cfg(feature = "fetch")]
+    #[test]
+    fn verify_checksum_requires_pins_for_untrusted_sources() {
+        let source = "https://github.com/acme/skills";
+        // SHA-256 of "test".
+        let digest = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+        let pinned = TrustPolicy::new().pin(source, format!("sha256:{digest}"));
+
+        assert_eq!(
+            verify_checksum(&pinned, source, TrustLevel::Untrusted, b"test").unwrap(),
+            None
+        );
+        assert!(matches!(
+            verify_checksum(&pinned, source, TrustLevel::Trusted, b"tampered"),
+            Err(Error::ChecksumMismatch { ref expected, .. }) if expected == digest
+        ));
+        let unpinned = TrustPolicy::new();
+        assert_eq!(
+            verify_checksum(&unpinned, source, TrustLevel::Untrusted, b"test").unwrap(),
+            Some(TrustViolation::MissingChecksum)
+        );
+        assert_eq!(
+            verify_checksum(&unpinned, source, TrustLevel::Trusted, b"test").unwrap(),
+            None
+        );
+    }
+}
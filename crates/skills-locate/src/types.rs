@@ -1,14 +1,25 @@
 //! Core type definitions for skills discovery.
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
 
+use harness_locate::target::{Platform, Platforms};
 use serde::{Deserialize, Serialize};
 
+use crate::error::{Error, Result};
+
+/// Version of the envelope written by [`DiscoveryResult::to_writer`].
+///
+/// Bumped whenever a change to [`DiscoveryResult`] or the types it
+/// contains would make older readers misinterpret the data.
+pub const DISCOVERY_SCHEMA_VERSION: u32 = 1;
+
 /// Source location for a plugin.
 ///
 /// Plugins can be sourced from GitHub repositories, direct URLs,
 /// or relative paths within a marketplace.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 #[non_exhaustive]
 pub enum PluginSource {
@@ -32,6 +43,7 @@ pub enum PluginSource {
 /// Represents a plugin as discovered from a repository,
 /// including its name, description, and contained skills.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct PluginDescriptor {
     /// Plugin name.
@@ -61,9 +73,47 @@ pub struct PluginDescriptor {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub hooks: Option<crate::component::HooksConfig>,
 
-    /// MCP server descriptors from .mcp.json, keyed by server name.
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub mcp_servers: HashMap<String, crate::component::McpServer>,
+    /// MCP server descriptors from .mcp.json, keyed and sorted by server
+    /// name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub mcp_servers: BTreeMap<String, crate::component::McpServer>,
+
+    /// Where the plugin was discovered, set during discovery.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<PluginOrigin>,
+
+    /// Quality score from a [`Scorer`](crate::Scorer), higher is better.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<u64>,
+}
+
+/// The repository and ref a plugin was discovered in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PluginOrigin {
+    /// Repository URL, normalized to `https://github.com/<owner>/<repo>`.
+    pub repo: String,
+    /// Branch, tag or commit SHA the repository was read at.
+    pub git_ref: String,
+    /// Whether the plugin was listed in a `marketplace.json` rather than
+    /// detected from the repository layout.
+    #[serde(default)]
+    pub marketplace: bool,
+}
+
+impl PluginOrigin {
+    /// Returns `true` if the ref is a full commit SHA.
+    #[must_use]
+    pub fn is_pinned(&self) -> bool {
+        self.git_ref.len() == 40 && self.git_ref.bytes().all(|b| b.is_ascii_hexdigit())
+    }
+
+    /// Returns `true` if both origins name the same repository, ignoring
+    /// case.
+    #[must_use]
+    pub fn same_repo(&self, other: &Self) -> bool {
+        self.repo.eq_ignore_ascii_case(&other.repo)
+    }
 }
 
 /// Skill metadata descriptor.
@@ -71,22 +121,89 @@ pub struct PluginDescriptor {
 /// Contains metadata extracted from SKILL.md frontmatter,
 /// without the full skill body content.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct SkillDescriptor {
     /// Skill name (required).
     pub name: String,
 
-    /// Optional description of the skill.
+    /// Optional description of the skill, in the default locale when the
+    /// frontmatter gives translations.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    /// Description translations by locale, empty when the frontmatter gives
+    /// a single string.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub descriptions: BTreeMap<String, String>,
+
     /// Trigger patterns that invoke this skill.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub triggers: Vec<String>,
+
+    /// Platforms the skill works on, from its `platforms` frontmatter;
+    /// empty means all.
+    #[serde(default, skip_serializing_if = "Platforms::is_empty")]
+    #[cfg_attr(feature = "schemars", schemars(with = "Vec<String>"))]
+    pub platforms: Platforms,
+
+    /// Collection path the skill was discovered under (e.g., "devops/k8s"),
+    /// or `None` for skills in a plugin's top-level `skills/` directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+
+    /// Skill directory relative to the plugin root (e.g., "skills/deploy"),
+    /// set during discovery.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// Content hash of the skill directory, as computed by `content_hash`,
+    /// set during discovery.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+
+    /// Quality score from a [`Scorer`](crate::Scorer), higher is better.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<u64>,
+}
+
+impl SkillDescriptor {
+    /// Returns the description in `locale`, falling back like
+    /// [`resolve_locale`](harness_locate::skill::resolve_locale).
+    #[must_use]
+    pub fn description_for(&self, locale: Option<&str>) -> Option<&str> {
+        harness_locate::skill::resolve_locale(&self.descriptions, locale)
+            .or(self.description.as_deref())
+    }
+
+    /// Returns `true` if the skill works on `platform`, per
+    /// [`platforms`](Self::platforms).
+    #[must_use]
+    pub fn runs_on(&self, platform: &Platform) -> bool {
+        self.platforms.allows(platform)
+    }
+
+    /// Returns `true` if the skill is in `namespace` or one of its nested
+    /// collections.
+    #[must_use]
+    pub fn in_namespace(&self, namespace: &str) -> bool {
+        let namespace = namespace.trim_matches('/');
+        self.namespace.as_deref().is_some_and(|ns| {
+            ns == namespace
+                || ns
+                    .strip_prefix(namespace)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    }
 }
 
 /// Result of plugin discovery with both grouped and flat access.
+///
+/// Use [`to_writer`](Self::to_writer) and [`from_reader`](Self::from_reader)
+/// to cache results or pass them between processes; they wrap the result
+/// in an envelope carrying [`DISCOVERY_SCHEMA_VERSION`].
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[non_exhaustive]
 pub struct DiscoveryResult {
     /// All discovered plugins, grouped with their components.
@@ -105,12 +222,76 @@ pub struct DiscoveryResult {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub all_agents: Vec<crate::component::AgentDescriptor>,
 
-    /// Flat list of all MCP servers across all plugins, keyed by server name.
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub all_mcp_servers: HashMap<String, crate::component::McpServer>,
+    /// Flat list of all MCP servers across all plugins, keyed and sorted by
+    /// server name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub all_mcp_servers: BTreeMap<String, crate::component::McpServer>,
+
+    /// Skills left out of `plugins` and `all_skills` as duplicates of
+    /// another, see [`DiscoveryResult::dedupe`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<crate::dedupe::SuppressedSkill>,
+}
+
+/// The versioned envelope written by [`DiscoveryResult::to_writer`].
+#[derive(Serialize)]
+struct EnvelopeRef<'a> {
+    schema_version: u32,
+    result: &'a DiscoveryResult,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    schema_version: u32,
+    #[serde(default)]
+    result: serde_json::Value,
 }
 
 impl DiscoveryResult {
+    /// Writes the result as JSON, wrapped in an envelope with its
+    /// `schema_version`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if writing fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use skills_locate::DiscoveryResult;
+    ///
+    /// let result = DiscoveryResult::from_plugins(Vec::new());
+    /// let mut bytes = Vec::new();
+    /// result.to_writer(&mut bytes)?;
+    /// assert_eq!(DiscoveryResult::from_reader(bytes.as_slice())?, result);
+    /// # Ok::<(), skills_locate::Error>(())
+    /// ```
+    pub fn to_writer(&self, writer: impl Write) -> Result<()> {
+        let envelope = EnvelopeRef {
+            schema_version: DISCOVERY_SCHEMA_VERSION,
+            result: self,
+        };
+        serde_json::to_writer(writer, &envelope)?;
+        Ok(())
+    }
+
+    /// Reads a result written by [`to_writer`](Self::to_writer).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::SchemaVersion`] if the data was written by a newer
+    /// version of this crate, or an error if reading or parsing fails.
+    pub fn from_reader(reader: impl Read) -> Result<Self> {
+        let envelope: Envelope = serde_json::from_reader(reader)?;
+        if envelope.schema_version > DISCOVERY_SCHEMA_VERSION {
+            return Err(Error::SchemaVersion {
+                found: envelope.schema_version,
+                supported: DISCOVERY_SCHEMA_VERSION,
+            });
+        }
+        Ok(serde_json::from_value(envelope.result)?)
+    }
+
     /// Create from a list of plugins, populating flat lists.
     #[must_use]
     pub fn from_plugins(plugins: Vec<PluginDescriptor>) -> Self {
@@ -125,6 +306,52 @@ impl DiscoveryResult {
             all_commands,
             all_agents,
             all_mcp_servers,
+            suppressed: Vec::new(),
+        }
+    }
+
+    /// Keeps only skills in `namespace` or its nested collections.
+    ///
+    /// Plugins left without skills are dropped; commands, agents, and MCP
+    /// servers of the remaining plugins are kept.
+    #[must_use]
+    pub fn filter_namespace(self, namespace: &str) -> Self {
+        let plugins = self
+            .plugins
+            .into_iter()
+            .filter_map(|mut plugin| {
+                plugin.skills.retain(|s| s.in_namespace(namespace));
+                (!plugin.skills.is_empty()).then_some(plugin)
+            })
+            .collect();
+        let mut suppressed = self.suppressed;
+        suppressed.retain(|s| s.skill.in_namespace(namespace));
+        Self {
+            suppressed,
+            ..Self::from_plugins(plugins)
+        }
+    }
+
+    /// Keeps only skills that work on `platform`, dropping plugins left
+    /// without skills like [`filter_namespace`](Self::filter_namespace).
+    ///
+    /// Pass [`Platform::current`] to hide skills that cannot run on this
+    /// machine.
+    #[must_use]
+    pub fn filter_platform(self, platform: &Platform) -> Self {
+        let plugins = self
+            .plugins
+            .into_iter()
+            .filter_map(|mut plugin| {
+                plugin.skills.retain(|s| s.runs_on(platform));
+                (!plugin.skills.is_empty()).then_some(plugin)
+            })
+            .collect();
+        let mut suppressed = self.suppressed;
+        suppressed.retain(|s| s.skill.runs_on(platform));
+        Self {
+            suppressed,
+            ..Self::from_plugins(plugins)
         }
     }
 }
@@ -185,18 +412,66 @@ mod tests {
             skills: vec![SkillDescriptor {
                 name: "test-skill".to_string(),
                 description: Some("A test skill".to_string()),
+                descriptions: BTreeMap::new(),
                 triggers: vec!["/test".to_string()],
+                platforms: Platforms::default(),
+                namespace: None,
+                path: None,
+                hash: None,
+                score: None,
             }],
             commands: vec![],
             agents: vec![],
             hooks: None,
-            mcp_servers: HashMap::new(),
+            mcp_servers: BTreeMap::new(),
+            origin: None,
+            score: None,
         };
         let json = serde_json::to_string(&plugin).unwrap();
         let parsed: PluginDescriptor = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed, plugin);
     }
 
+    #[test]
+    fn discovery_result_round_trips_through_the_envelope() {
+        let result = DiscoveryResult::from_plugins(vec![PluginDescriptor {
+            name: "ops".to_string(),
+            path: Some("plugins/ops".to_string()),
+            description: None,
+            skills: vec![SkillDescriptor {
+                name: "deploy".to_string(),
+                description: Some("Deploys".to_string()),
+                descriptions: BTreeMap::from([("fr".to_string(), "Déploie".to_string())]),
+                triggers: vec![],
+                platforms: Platforms::default(),
+                namespace: Some("devops".to_string()),
+                path: Some("skills/deploy".to_string()),
+                hash: None,
+                score: None,
+            }],
+            commands: vec![],
+            agents: vec![],
+            hooks: None,
+            mcp_servers: BTreeMap::new(),
+            origin: None,
+            score: None,
+        }]);
+        let mut bytes = Vec::new();
+        result.to_writer(&mut bytes).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["schema_version"], DISCOVERY_SCHEMA_VERSION);
+        assert_eq!(json["result"]["all_skills"][0]["name"], "deploy");
+        assert_eq!(
+            DiscoveryResult::from_reader(bytes.as_slice()).unwrap(),
+            result
+        );
+
+        let newer = r#"{"schema_version": 99, "result": {"future": true}}"#;
+        let err = DiscoveryResult::from_reader(newer.as_bytes()).unwrap_err();
+        assert!(matches!(err, Error::SchemaVersion { found: 99, .. }));
+        assert!(DiscoveryResult::from_reader(r#"{"plugins": []}"#.as_bytes()).is_err());
+    }
+
     #[test]
     fn plugin_descriptor_minimal_serde_roundtrip() {
         let plugin = PluginDescriptor {
@@ -207,7 +482,9 @@ mod tests {
             commands: vec![],
             agents: vec![],
             hooks: None,
-            mcp_servers: HashMap::new(),
+            mcp_servers: BTreeMap::new(),
+            origin: None,
+            score: None,
         };
         let json = serde_json::to_string(&plugin).unwrap();
         let parsed: PluginDescriptor = serde_json::from_str(&json).unwrap();
@@ -224,7 +501,9 @@ mod tests {
             commands: vec![],
             agents: vec![],
             hooks: None,
-            mcp_servers: HashMap::new(),
+            mcp_servers: BTreeMap::new(),
+            origin: None,
+            score: None,
         };
         let json = serde_json::to_string(&plugin).unwrap();
         assert_eq!(json, r#"{"name":"minimal"}"#);
@@ -237,7 +516,13 @@ mod tests {
         let skill = SkillDescriptor {
             name: "code-review".to_string(),
             description: Some("Reviews code for issues".to_string()),
+            descriptions: BTreeMap::new(),
             triggers: vec!["/review".to_string(), "/cr".to_string()],
+            platforms: Platforms::default(),
+            namespace: Some("devops".to_string()),
+            path: None,
+            hash: Some("sha256:00".to_string()),
+            score: None,
         };
         let json = serde_json::to_string(&skill).unwrap();
         let parsed: SkillDescriptor = serde_json::from_str(&json).unwrap();
@@ -249,7 +534,13 @@ mod tests {
         let skill = SkillDescriptor {
             name: "minimal-skill".to_string(),
             description: None,
+            descriptions: BTreeMap::new(),
             triggers: vec![],
+            platforms: Platforms::default(),
+            namespace: None,
+            path: None,
+            hash: None,
+            score: None,
         };
         let json = serde_json::to_string(&skill).unwrap();
         assert_eq!(json, r#"{"name":"minimal-skill"}"#);
@@ -287,21 +578,36 @@ mod tests {
                 skills: vec![SkillDescriptor {
                     name: "skill-1".to_string(),
                     description: None,
+                    descriptions: BTreeMap::new(),
                     triggers: vec![],
+                    platforms: Platforms::default(),
+                    namespace: None,
+                    path: None,
+                    hash: None,
+                    score: None,
                 }],
                 commands: vec![],
                 agents: vec![],
                 hooks: None,
-                mcp_servers: HashMap::new(),
+                mcp_servers: BTreeMap::new(),
+                origin: None,
+                score: None,
             }],
             all_skills: vec![SkillDescriptor {
                 name: "skill-1".to_string(),
                 description: None,
+                descriptions: BTreeMap::new(),
                 triggers: vec![],
+                platforms: Platforms::default(),
+                namespace: None,
+                path: None,
+                hash: None,
+                score: None,
             }],
             all_commands: vec![],
             all_agents: vec![],
-            all_mcp_servers: HashMap::new(),
+            all_mcp_servers: BTreeMap::new(),
+            suppressed: vec![],
         };
         let json = serde_json::to_string(&result).unwrap();
         let parsed: DiscoveryResult = serde_json::from_str(&json).unwrap();
@@ -318,12 +624,20 @@ mod tests {
                 skills: vec![SkillDescriptor {
                     name: "skill-1".to_string(),
                     description: None,
+                    descriptions: BTreeMap::new(),
                     triggers: vec![],
+                    platforms: Platforms::default(),
+                    namespace: None,
+                    path: None,
+                    hash: None,
+                    score: None,
                 }],
                 commands: vec![],
                 agents: vec![],
                 hooks: None,
-                mcp_servers: HashMap::new(),
+                mcp_servers: BTreeMap::new(),
+                origin: None,
+                score: None,
             },
             PluginDescriptor {
                 name: "plugin-b".to_string(),
@@ -332,12 +646,20 @@ mod tests {
                 skills: vec![SkillDescriptor {
                     name: "skill-2".to_string(),
                     description: None,
+                    descriptions: BTreeMap::new(),
                     triggers: vec![],
+                    platforms: Platforms::default(),
+                    namespace: None,
+                    path: None,
+                    hash: None,
+                    score: None,
                 }],
                 commands: vec![],
                 agents: vec![],
                 hooks: None,
-                mcp_servers: HashMap::new(),
+                mcp_servers: BTreeMap::new(),
+                origin: None,
+                score: None,
             },
         ];
 
@@ -355,11 +677,115 @@ mod tests {
             all_skills: vec![],
             all_commands: vec![],
             all_agents: vec![],
-            all_mcp_servers: HashMap::new(),
+            all_mcp_servers: BTreeMap::new(),
+            suppressed: vec![],
         };
         let json = serde_json::to_string(&result).unwrap();
         assert_eq!(json, "{}");
         let parsed: DiscoveryResult = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed, result);
     }
+
+    fn namespaced_skill(name: &str, namespace: Option<&str>) -> SkillDescriptor {
+        SkillDescriptor {
+            name: name.to_string(),
+            description: None,
+            descriptions: BTreeMap::new(),
+            triggers: vec![],
+            platforms: Platforms::default(),
+            namespace: namespace.map(String::from),
+            path: None,
+            hash: None,
+            score: None,
+        }
+    }
+
+    #[test]
+    fn skill_descriptor_in_namespace_matches_nested_collections() {
+        let skill = namespaced_skill("deploy", Some("devops/k8s"));
+        assert!(skill.in_namespace("devops"));
+        assert!(skill.in_namespace("devops/k8s"));
+        assert!(skill.in_namespace("/devops/"));
+        assert!(!skill.in_namespace("dev"));
+        assert!(!skill.in_namespace("devops/k8s/helm"));
+        assert!(!namespaced_skill("root", None).in_namespace("devops"));
+    }
+
+    #[test]
+    fn discovery_result_filter_namespace() {
+        let plugins = vec![
+            PluginDescriptor {
+                name: "monorepo".to_string(),
+                path: None,
+                description: None,
+                skills: vec![
+                    namespaced_skill("deploy", Some("devops")),
+                    namespaced_skill("lint", Some("frontend")),
+                    namespaced_skill("root", None),
+                ],
+                commands: vec![],
+                agents: vec![],
+                hooks: None,
+                mcp_servers: BTreeMap::new(),
+                origin: None,
+                score: None,
+            },
+            PluginDescriptor {
+                name: "other".to_string(),
+                path: Some("plugins/other".to_string()),
+                description: None,
+                skills: vec![namespaced_skill("style", Some("frontend"))],
+                commands: vec![],
+                agents: vec![],
+                hooks: None,
+                mcp_servers: BTreeMap::new(),
+                origin: None,
+                score: None,
+            },
+        ];
+
+        let result = DiscoveryResult::from_plugins(plugins).filter_namespace("devops");
+        assert_eq!(result.plugins.len(), 1);
+        assert_eq!(result.all_skills.len(), 1);
+        assert_eq!(result.all_skills[0].name, "deploy");
+    }
+
+    #[test]
+    fn discovery_result_filter_platform() {
+        use harness_locate::target::{Arch, Os};
+
+        let mut mac = namespaced_skill("notes", None);
+        mac.platforms = [Os::Macos].into_iter().collect();
+        let mut intel = namespaced_skill("cuda", None);
+        intel.platforms = [Arch::X86_64].into_iter().collect();
+        let plugin = PluginDescriptor {
+            name: "kit".to_string(),
+            path: None,
+            description: None,
+            skills: vec![mac, intel, namespaced_skill("lint", None)],
+            commands: vec![],
+            agents: vec![],
+            hooks: None,
+            mcp_servers: BTreeMap::new(),
+            origin: None,
+            score: None,
+        };
+
+        let names = |platform| {
+            DiscoveryResult::from_plugins(vec![plugin.clone()])
+                .filter_platform(&platform)
+                .all_skills
+                .into_iter()
+                .map(|s| s.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            names(Platform::new(Os::Macos, Arch::Aarch64)),
+            ["notes", "lint"]
+        );
+        assert_eq!(
+            names(Platform::new(Os::Linux, Arch::X86_64)),
+            ["cuda", "lint"]
+        );
+    }
 }
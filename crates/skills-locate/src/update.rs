@@ -0,0 +1,363 @@
+//! Update checks for installed skills against their upstream repositories.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use harness_locate::ignore::{IGNORE_FILES, IgnoreRules};
+use harness_locate::text::decode;
+use serde::{Deserialize, Serialize};
+
+use crate::discovery::extract_archive_prefix;
+use crate::error::{Error, Result};
+use crate::fetch::{extract_dir, fetch_bytes};
+use crate::github::GitHubRef;
+
+/// Maximum number of lines kept in [`SkillUpdate::changelog`].
+const CHANGELOG_LINES: usize = 20;
+
+/// Where an installed skill was installed from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillOrigin {
+    /// GitHub URL of the source repository, optionally with `/tree/<ref>`.
+    pub repo: String,
+    /// The skill's directory within the repository, e.g. `skills/deploy`.
+    pub path: String,
+    /// [`content_hash`] of the skill when it was installed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+/// A skill directory on disk and where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledSkill {
+    /// Skill name.
+    pub name: String,
+    /// The installed skill directory.
+    pub dir: PathBuf,
+    /// The upstream source.
+    pub origin: SkillOrigin,
+}
+
+/// A skill whose upstream content differs from the installed version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillUpdate {
+    /// Skill name.
+    pub name: String,
+    /// Hash of the installed version: the recorded install hash, or the
+    /// current content if none was recorded.
+    pub installed_hash: String,
+    /// Hash of the upstream skill directory.
+    pub upstream_hash: String,
+    /// `true` if the local copy was edited since it was installed.
+    pub locally_modified: bool,
+    /// The latest section of the upstream `CHANGELOG.md`, from the skill
+    /// directory or else the repository root.
+    pub changelog: Option<String>,
+}
+
+/// What [`check_updates`] found for each skill.
+#[derive(Debug, Default)]
+pub struct UpdateReport {
+    /// Skills with newer upstream content.
+    pub updates: Vec<SkillUpdate>,
+    /// Skills matching upstream.
+    pub up_to_date: Vec<String>,
+    /// Skills that could not be checked.
+    pub errors: Vec<(String, Error)>,
+}
+
+/// Checks installed skills for newer versions upstream.
+///
+/// Each repository is downloaded once at the ref in its URL, and the skill
+/// directory is hashed with [`content_hash`]. A skill has an update when
+/// that hash differs from the one recorded at install time, or from its
+/// current content if no hash was recorded.
+pub fn check_updates(skills: &[InstalledSkill]) -> UpdateReport {
+    check_updates_with(skills, |github_ref| fetch_bytes(&github_ref.archive_url()))
+}
+
+fn check_updates_with<F>(skills: &[InstalledSkill], fetch: F) -> UpdateReport
+where
+    F: Fn(&GitHubRef) -> Result<Vec<u8>>,
+{
+    let mut report = UpdateReport::default();
+    let mut archives: HashMap<&str, Vec<u8>> = HashMap::new();
+
+    for skill in skills {
+        let repo = skill.origin.repo.as_str();
+        if !archives.contains_key(repo) {
+            match GitHubRef::parse(repo).and_then(|github_ref| fetch(&github_ref)) {
+                Ok(archive) => {
+                    archives.insert(repo, archive);
+                }
+                Err(e) => {
+                    report.errors.push((skill.name.clone(), e));
+                    continue;
+                }
+            }
+        }
+
+        match check_skill(skill, &archives[repo]) {
+            Ok(Some(update)) => report.updates.push(update),
+            Ok(None) => report.up_to_date.push(skill.name.clone()),
+            Err(e) => report.errors.push((skill.name.clone(), e)),
+        }
+    }
+
+    report
+}
+
+fn check_skill(skill: &InstalledSkill, archive: &[u8]) -> Result<Option<SkillUpdate>> {
+    let prefix = extract_archive_prefix(archive)?;
+    let skill_dir = format!("{prefix}{}", skill.origin.path.trim_matches('/'));
+    let upstream = extract_dir(archive, &skill_dir)?;
+    if upstream.is_empty() {
+        return Err(Error::NotFound {
+            what: skill.origin.path.clone(),
+        });
+    }
+
+    let local_hash = content_hash(&skill.dir)?;
+    let installed_hash = skill
+        .origin
+        .hash
+        .clone()
+        .unwrap_or_else(|| local_hash.clone());
+    let changelog = find_changelog(&upstream)
+        .or_else(|| {
+            extract_dir(archive, &prefix).ok().and_then(|root| {
+                root.into_iter()
+                    .find(|(path, _)| is_changelog(path))
+                    .map(|(_, bytes)| bytes)
+            })
+        })
+        .and_then(|bytes| decode(&bytes).ok())
+        .and_then(|decoded| changelog_snippet(&decoded.text));
+    let upstream_hash = hash_files(upstream);
+
+    if upstream_hash == installed_hash {
+        return Ok(None);
+    }
+    Ok(Some(SkillUpdate {
+        name: skill.name.clone(),
+        locally_modified: local_hash != installed_hash,
+        installed_hash,
+        upstream_hash,
+        changelog,
+    }))
+}
+
+fn is_changelog(path: &str) -> bool {
+    path.eq_ignore_ascii_case("CHANGELOG.md")
+}
+
+fn find_changelog(files: &[(String, Vec<u8>)]) -> Option<Vec<u8>> {
+    files
+        .iter()
+        .find(|(path, _)| is_changelog(path))
+        .map(|(_, bytes)| bytes.clone())
+}
+
+/// The first `## ` section of a changelog, or its opening lines if it has
+/// no such headings.
+fn changelog_snippet(text: &str) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.iter().position(|l| l.starts_with("## ")).unwrap_or(0);
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| l.starts_with("## "))
+        .map_or(lines.len(), |i| start + 1 + i);
+    let snippet = lines[start..end.min(start + CHANGELOG_LINES)]
+        .join("\n")
+        .trim()
+        .to_string();
+    (!snippet.is_empty()).then_some(snippet)
+}
+
+/// Hashes the files of a skill directory, or a single file.
+///
+/// The hash covers every file's path relative to `path` and its bytes,
+/// skipping files ignored by the built-in patterns and the ignore files at
+/// the root of `path` (see [`harness_locate::ignore`]). It is the same for a
+/// directory on disk and for the skill in its upstream archive, and has the
+/// form `sha256:<hex>`. A single file is hashed as a directory holding only
+/// that file.
+///
+/// # Errors
+///
+/// Returns an error if `path` or a file in it cannot be read.
+pub fn content_hash(path: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    if path.is_file() {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        files.push((name, std::fs::read(path)?));
+    } else {
+        read_tree(path, path, &mut files)?;
+    }
+    Ok(hash_files(files))
+}
+
+fn read_tree(root: &Path, dir: &Path, files: &mut Vec<(String, Vec<u8>)>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            read_tree(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            let relative = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((relative, std::fs::read(&path)?));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn hash_files(mut files: Vec<(String, Vec<u8>)>) -> String {
+    let mut rules = IgnoreRules::builtin();
+    for name in IGNORE_FILES {
+        if let Some((_, bytes)) = files.iter().find(|(path, _)| path == name)
+            && let Ok(decoded) = decode(bytes)
+        {
+            rules.extend(IgnoreRules::parse(&decoded.text));
+        }
+    }
+    files.retain(|(path, _)| !is_ignored(&rules, path));
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut context = ring::digest::Context::new(&ring::digest::SHA256);
+    for (path, bytes) in &files {
+        context.update(path.as_bytes());
+        context.update(&[0]);
+        context.update(&(bytes.len() as u64).to_le_bytes());
+        context.update(bytes);
+    }
+    let digest = context.finish();
+    let hex: String = digest.as_ref().iter().map(|b| format!("{b:02x}")).collect();
+    format!("sha256:{hex}")
+}
+
+/// Returns `true` if the file at `path` or any directory above it is
+/// ignored.
+fn is_ignored(rules: &IgnoreRules, path: &str) -> bool {
+    let parts: Vec<&str> = path.split('/').collect();
+    (1..parts.len()).any(|depth| rules.is_ignored(Path::new(&parts[..depth].join("/")), true))
+        || rules.is_ignored(Path::new(path), false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Cursor, Write};
+
+    const REPO: &str = "https://github.com/acme/skills";
+
+    fn create_test_zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut buf = Cursor::new(Vec::new());
+        {
+            let mut zip = zip::ZipWriter::new(&mut buf);
+            let options = zip::write::SimpleFileOptions::default();
+            for (name, content) in files {
+                zip.start_file(*name, options).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap();
+        }
+        buf.into_inner()
+    }
+
+    fn skill_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "skills-locate-update-{}-{name}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        for (path, content) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn installed(name: &str, dir: &Path, hash: Option<String>) -> InstalledSkill {
+        InstalledSkill {
+            name: name.to_string(),
+            dir: dir.to_path_buf(),
+            origin: SkillOrigin {
+                repo: REPO.to_string(),
+                path: format!("skills/{name}"),
+                hash,
+            },
+        }
+    }
+
+    #[test]
+    fn content_hash_matches_archive_and_skips_ignored_files() {
+        let dir = skill_dir(
+            "hash",
+            &[
+                ("SKILL.md", "---\nname: hash\n---\n"),
+                ("scripts/run.sh", "echo hi"),
+                (".skillignore", "*.log\n"),
+                ("debug.log", "noise"),
+                ("node_modules/x/index.js", ""),
+            ],
+        );
+        let archive = create_test_zip(&[
+            ("skills-main/skills/hash/SKILL.md", "---\nname: hash\n---\n"),
+            ("skills-main/skills/hash/scripts/run.sh", "echo hi"),
+        ]);
+
+        let upstream = extract_dir(&archive, "skills-main/skills/hash").unwrap();
+        assert_eq!(content_hash(&dir).unwrap(), hash_files(upstream));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn check_updates_reports_changed_skills_with_changelog() {
+        let deploy = skill_dir("deploy", &[("SKILL.md", "v1")]);
+        let review = skill_dir("review", &[("SKILL.md", "same")]);
+        let installed_hash = content_hash(&deploy).unwrap();
+        std::fs::write(deploy.join("SKILL.md"), "v1 with local edits").unwrap();
+        let archive = create_test_zip(&[
+            ("skills-main/skills/deploy/SKILL.md", "v2"),
+            ("skills-main/skills/review/SKILL.md", "same"),
+            (
+                "skills-main/CHANGELOG.md",
+                "# Changelog\n\n## 2.0\n\n- Deploy to staging first\n\n## 1.0\n\n- Initial\n",
+            ),
+        ]);
+        let skills = [
+            installed("deploy", &deploy, Some(installed_hash.clone())),
+            installed("review", &review, None),
+            installed("missing", &review, None),
+        ];
+
+        let report = check_updates_with(&skills, |github_ref| {
+            assert_eq!(github_ref.repo, "skills");
+            Ok(archive.clone())
+        });
+
+        assert_eq!(report.up_to_date, ["review"]);
+        assert_eq!(report.updates.len(), 1);
+        let update = &report.updates[0];
+        assert_eq!(update.name, "deploy");
+        assert_eq!(update.installed_hash, installed_hash);
+        assert!(update.locally_modified);
+        assert_eq!(
+            update.changelog.as_deref(),
+            Some("## 2.0\n\n- Deploy to staging first")
+        );
+        assert!(
+            matches!(&report.errors[..], [(name, Error::NotFound { .. })] if name == "missing")
+        );
+
+        std::fs::remove_dir_all(&deploy).unwrap();
+        std::fs::remove_dir_all(&review).unwrap();
+    }
+}